@@ -41,6 +41,17 @@ fn main() -> std::io::Result<()> {
     }
     println!("cargo:rerun-if-env-changed=NIX_JJ_GIT_HASH");
 
+    // The help topics are read from the docs. cli/docs is a symlink to them in
+    // the repo, and a copy of them in the published crate. Fall back to the
+    // parent directory where the symlink isn't checked out as one.
+    let docs_symlink_path = Path::new("docs");
+    println!("cargo:rerun-if-changed={}", docs_symlink_path.display());
+    if docs_symlink_path.join("config.md").exists() {
+        println!("cargo:rustc-env=JJ_DOCS_DIR=docs/");
+    } else {
+        println!("cargo:rustc-env=JJ_DOCS_DIR=../docs/");
+    }
+
     if let Some(git_hash) = get_git_hash() {
         println!("cargo:rustc-env=JJ_VERSION={}-{}", version, git_hash);
     } else {
@@ -41,7 +41,7 @@ use jj_lib::git_backend::GitBackend;
 use jj_lib::gitignore::{GitIgnoreError, GitIgnoreFile};
 use jj_lib::hex_util::to_reverse_hex;
 use jj_lib::id_prefix::IdPrefixContext;
-use jj_lib::matchers::Matcher;
+use jj_lib::matchers::{EverythingMatcher, Matcher};
 use jj_lib::merge::MergedTreeValue;
 use jj_lib::merged_tree::MergedTree;
 use jj_lib::object_id::ObjectId;
@@ -65,7 +65,8 @@ use jj_lib::str_util::StringPattern;
 use jj_lib::transaction::Transaction;
 use jj_lib::view::View;
 use jj_lib::working_copy::{
-    CheckoutStats, LockedWorkingCopy, SnapshotOptions, WorkingCopy, WorkingCopyFactory,
+    CheckoutOptions, CheckoutStats, LockedWorkingCopy, SnapshotOptions, WorkingCopy,
+    WorkingCopyFactory,
 };
 use jj_lib::workspace::{
     default_working_copy_factories, LockedWorkspace, WorkingCopyFactories, Workspace,
@@ -80,7 +81,7 @@ use tracing_subscriber::prelude::*;
 use crate::command_error::{
     cli_error, config_error_with_message, handle_command_result, internal_error,
     internal_error_with_message, user_error, user_error_with_hint, user_error_with_message,
-    CommandError,
+    CommandError, ErrorCode,
 };
 use crate::commit_templater::{CommitTemplateLanguage, CommitTemplateLanguageExtension};
 use crate::config::{
@@ -302,6 +303,10 @@ impl CommandHelper {
         self.for_loaded_repo(ui, workspace, repo)
     }
 
+    pub fn working_copy_factories(&self) -> &WorkingCopyFactories {
+        &self.working_copy_factories
+    }
+
     pub fn get_working_copy_factory(&self) -> Result<&dyn WorkingCopyFactory, CommandError> {
         let loader = self.workspace_loader()?;
 
@@ -546,6 +551,7 @@ impl WorkspaceCommandHelper {
                 "Don't use --at-op."
             };
             Err(user_error_with_hint(
+                ErrorCode::InvalidArgument,
                 "This command must be able to update the working copy.",
                 hint,
             ))
@@ -556,6 +562,17 @@ impl WorkspaceCommandHelper {
     /// copy is collocated with Git.
     #[instrument(skip_all)]
     pub fn maybe_snapshot(&mut self, ui: &mut Ui) -> Result<(), CommandError> {
+        self.maybe_snapshot_matching(ui, &EverythingMatcher)
+    }
+
+    /// Like `maybe_snapshot()`, but only snapshots the paths matching
+    /// `matcher`. The previously recorded state is kept for the other paths.
+    #[instrument(skip_all)]
+    pub fn maybe_snapshot_matching(
+        &mut self,
+        ui: &mut Ui,
+        matcher: &dyn Matcher,
+    ) -> Result<(), CommandError> {
         if self.may_update_working_copy {
             if self.working_copy_shared_with_git {
                 self.import_git_head(ui)?;
@@ -564,7 +581,7 @@ impl WorkspaceCommandHelper {
             // pointing to the new working-copy commit might not be exported.
             // In that situation, the ref would be conflicted anyway, so export
             // failure is okay.
-            self.snapshot_working_copy(ui)?;
+            self.snapshot_working_copy(ui, matcher)?;
             // import_git_refs() can rebase the working-copy commit.
             if self.working_copy_shared_with_git {
                 self.import_git_refs(ui)?;
@@ -682,7 +699,10 @@ impl WorkspaceCommandHelper {
         let wc_commit = if let Some(wc_commit_id) = self.get_wc_commit_id() {
             self.repo().store().get_commit(wc_commit_id)?
         } else {
-            return Err(user_error("Nothing checked out in this workspace"));
+            return Err(user_error(
+                ErrorCode::NoWorkingCopy,
+                "Nothing checked out in this workspace",
+            ));
         };
 
         let locked_ws = self.workspace.start_working_copy_mutation()?;
@@ -695,7 +715,10 @@ impl WorkspaceCommandHelper {
     ) -> Result<(LockedWorkspace, Commit), CommandError> {
         let (mut locked_ws, wc_commit) = self.unchecked_start_working_copy_mutation()?;
         if wc_commit.tree_id() != locked_ws.locked_wc().old_tree_id() {
-            return Err(user_error("Concurrent working copy operation. Try again."));
+            return Err(user_error(
+                ErrorCode::ConcurrentOperation,
+                "Concurrent working copy operation. Try again.",
+            ));
         }
         Ok((locked_ws, wc_commit))
     }
@@ -800,6 +823,7 @@ impl WorkspaceCommandHelper {
                 git_ignores = git_ignores.chain_with_file("", excludes_file_path)?;
             }
         }
+        git_ignores = git_ignores.chain_with_file("", self.workspace.repo_path().join("ignore"))?;
         Ok(git_ignores)
     }
 
@@ -926,14 +950,15 @@ impl WorkspaceCommandHelper {
                 )?;
                 let commit_hash = short_commit_hash(commit.id());
                 if !all_commits.insert(commit) {
-                    return Err(user_error(format!(
-                        r#"More than one revset resolved to revision {commit_hash}"#,
-                    )));
+                    return Err(user_error(
+                        ErrorCode::InvalidArgument,
+                        format!(r#"More than one revset resolved to revision {commit_hash}"#,),
+                    ));
                 }
             }
         }
         if all_commits.is_empty() {
-            Err(user_error("Empty revision set"))
+            Err(user_error(ErrorCode::EmptyRevset, "Empty revision set"))
         } else {
             Ok(all_commits)
         }
@@ -1104,10 +1129,13 @@ impl WorkspaceCommandHelper {
         if self.global_args.ignore_immutable {
             let root_id = self.repo().store().root_commit_id();
             return if commits.into_iter().contains(root_id) {
-                Err(user_error(format!(
-                    "The root commit {} is immutable",
-                    short_commit_hash(root_id),
-                )))
+                Err(user_error(
+                    ErrorCode::ImmutableCommit,
+                    format!(
+                        "The root commit {} is immutable",
+                        short_commit_hash(root_id),
+                    ),
+                ))
             } else {
                 Ok(())
             };
@@ -1137,12 +1165,16 @@ impl WorkspaceCommandHelper {
 
         if let Some(commit_id) = commit_id_iter.next() {
             let error = if &commit_id == self.repo().store().root_commit_id() {
-                user_error(format!(
-                    "The root commit {} is immutable",
-                    short_commit_hash(&commit_id),
-                ))
+                user_error(
+                    ErrorCode::ImmutableCommit,
+                    format!(
+                        "The root commit {} is immutable",
+                        short_commit_hash(&commit_id),
+                    ),
+                )
             } else {
                 user_error_with_hint(
+                    ErrorCode::ImmutableCommit,
                     format!("Commit {} is immutable", short_commit_hash(&commit_id)),
                     "Pass `--ignore-immutable` or configure the set of immutable commits via \
                      `revset-aliases.immutable_heads()`.",
@@ -1162,7 +1194,11 @@ impl WorkspaceCommandHelper {
     }
 
     #[instrument(skip_all)]
-    fn snapshot_working_copy(&mut self, ui: &mut Ui) -> Result<(), CommandError> {
+    fn snapshot_working_copy(
+        &mut self,
+        ui: &mut Ui,
+        matcher: &dyn Matcher,
+    ) -> Result<(), CommandError> {
         let workspace_id = self.workspace_id().to_owned();
         let get_wc_commit = |repo: &ReadonlyRepo| -> Result<Option<_>, _> {
             repo.view()
@@ -1196,6 +1232,7 @@ impl WorkspaceCommandHelper {
                 }
                 Ok(WorkingCopyFreshness::WorkingCopyStale) => {
                     return Err(user_error_with_hint(
+                        ErrorCode::StaleWorkingCopy,
                         format!(
                             "The working copy is stale (not updated since operation {}).",
                             short_operation_hash(&old_op_id)
@@ -1215,6 +1252,7 @@ See https://github.com/martinvonz/jj/blob/main/docs/working-copy.md#stale-workin
                 }
                 Err(OpStoreError::ObjectNotFound { .. }) => {
                     return Err(user_error_with_hint(
+                        ErrorCode::StaleWorkingCopy,
                         "Could not read working copy's operation.",
                         "Run `jj workspace update-stale` to recover.
 See https://github.com/martinvonz/jj/blob/main/docs/working-copy.md#stale-working-copy \
@@ -1230,6 +1268,7 @@ See https://github.com/martinvonz/jj/blob/main/docs/working-copy.md#stale-workin
             fsmonitor_settings: self.settings.fsmonitor_settings()?,
             progress: progress.as_ref().map(|x| x as _),
             max_new_file_size: self.settings.max_new_file_size()?,
+            matcher,
         })?;
         drop(progress);
         if new_tree_id != *wc_commit.tree_id() {
@@ -1270,11 +1309,13 @@ See https://github.com/martinvonz/jj/blob/main/docs/working-copy.md#stale-workin
         new_commit: &Commit,
     ) -> Result<(), CommandError> {
         assert!(self.may_update_working_copy);
+        let options = self.checkout_options()?;
         let stats = update_working_copy(
             &self.user_repo.repo,
             &mut self.workspace,
             maybe_old_commit,
             new_commit,
+            &options,
         )?;
         if Some(new_commit) != maybe_old_commit {
             if let Some(mut formatter) = ui.status_formatter() {
@@ -1291,8 +1332,12 @@ See https://github.com/martinvonz/jj/blob/main/docs/working-copy.md#stale-workin
                 }
             }
         }
-        if let Some(stats) = stats {
+        if let Some(mut stats) = stats {
+            let backup_tree_id = stats.backup_tree_id.take();
             print_checkout_stats(ui, stats, new_commit)?;
+            if let (Some(old_commit), Some(tree_id)) = (maybe_old_commit, backup_tree_id) {
+                self.record_working_copy_backup(ui, old_commit, tree_id)?;
+            }
         }
         if Some(new_commit) != maybe_old_commit {
             if let Some(mut formatter) = ui.status_formatter() {
@@ -1306,6 +1351,81 @@ See https://github.com/martinvonz/jj/blob/main/docs/working-copy.md#stale-workin
         Ok(())
     }
 
+    /// Options for updating the working copy, as configured by the user.
+    pub fn checkout_options(&self) -> Result<CheckoutOptions, CommandError> {
+        let backup_unsnapshotted = self
+            .settings
+            .config()
+            .get_bool("working-copy.backup-on-discard")
+            .optional()?
+            .unwrap_or(true);
+        Ok(CheckoutOptions {
+            backup_unsnapshotted,
+        })
+    }
+
+    /// Saves unsnapshotted changes that were discarded by a working-copy
+    /// update as a hidden commit on top of `old_commit`, recorded in a new
+    /// operation.
+    ///
+    /// The operation only records the working-copy state, so it's marked as a
+    /// snapshot to keep `jj undo` and `jj op log` from picking it over the
+    /// operation that discarded the changes.
+    pub fn record_working_copy_backup(
+        &mut self,
+        ui: &Ui,
+        old_commit: &Commit,
+        backup_tree_id: MergedTreeId,
+    ) -> Result<(), CommandError> {
+        let old_tree = old_commit.tree()?;
+        let backup_tree = self.repo().store().get_root_tree(&backup_tree_id)?;
+        let backed_up_paths: Vec<_> = old_tree
+            .diff(&backup_tree, &EverythingMatcher)
+            .map(|(path, _)| path)
+            .collect();
+        let mut tx = self.repo().start_transaction(&self.settings);
+        tx.set_is_snapshot(true);
+        let backup_commit = tx
+            .mut_repo()
+            .new_commit(
+                &self.settings,
+                vec![old_commit.id().clone()],
+                backup_tree_id,
+            )
+            .set_description("backup of discarded working-copy changes")
+            .write()?;
+        tx.mut_repo().remove_head(backup_commit.id());
+        tx.set_tag("working-copy-backup".to_owned(), backup_commit.id().hex());
+        let new_repo = tx.commit("back up discarded working-copy changes");
+        let locked_ws = self.workspace.start_working_copy_mutation()?;
+        locked_ws.finish(new_repo.op_id().clone())?;
+        self.user_repo = ReadonlyUserRepo::new(new_repo);
+
+        let backup_hash = short_commit_hash(backup_commit.id());
+        let num_files = backed_up_paths.len();
+        let mut formatter = ui.warning_default();
+        writeln!(
+            formatter,
+            "Unsnapshotted changes to {num_files} file{s} were overwritten. They were saved in \
+             hidden commit {backup_hash}:",
+            s = if num_files == 1 { "" } else { "s" }
+        )?;
+        for path in &backed_up_paths {
+            writeln!(formatter, "  {}", self.format_file_path(path))?;
+        }
+        drop(formatter);
+        // Restoring the whole backup tree would also revert the working-copy
+        // commit's other files if it has moved since, so only the backed-up
+        // paths should be restored.
+        writeln!(
+            ui.hint_default(),
+            "Run `jj restore --from {backup_hash} <paths>` with the paths above to recover them, \
+             or `jj new {backup_hash}` to continue from the backup. Use `jj op show` to see the \
+             operation that recorded the backup."
+        )?;
+        Ok(())
+    }
+
     pub fn start_transaction(&mut self) -> WorkspaceCommandTransaction {
         let tx = start_repo_transaction(self.repo(), &self.settings, &self.string_args);
         WorkspaceCommandTransaction { helper: self, tx }
@@ -1662,6 +1782,11 @@ impl WorkspaceCommandTransaction<'_> {
         template.format(commit, formatter)
     }
 
+    /// Adds a tag to the operation metadata.
+    pub fn set_tag(&mut self, key: String, value: String) {
+        self.tx.set_tag(key, value);
+    }
+
     pub fn finish(self, ui: &mut Ui, description: impl Into<String>) -> Result<(), CommandError> {
         self.helper.finish_transaction(ui, self.tx, description)
     }
@@ -1707,19 +1832,23 @@ fn map_workspace_load_error(err: WorkspaceLoadError, workspace_path: Option<&str
             let git_dir = wc_path.join(".git");
             if git_dir.is_dir() {
                 user_error_with_hint(
+                    ErrorCode::NoRepo,
                     message,
                     "It looks like this is a git repo. You can create a jj repo backed by it by \
                      running this:
 jj git init --colocate",
                 )
             } else {
-                user_error(message)
+                user_error(ErrorCode::NoRepo, message)
             }
         }
-        WorkspaceLoadError::RepoDoesNotExist(repo_dir) => user_error(format!(
-            "The repository directory at {} is missing. Was it moved?",
-            repo_dir.display(),
-        )),
+        WorkspaceLoadError::RepoDoesNotExist(repo_dir) => user_error(
+            ErrorCode::NoRepo,
+            format!(
+                "The repository directory at {} is missing. Was it moved?",
+                repo_dir.display(),
+            ),
+        ),
         WorkspaceLoadError::StoreLoadError(err @ StoreLoadError::UnsupportedType { .. }) => {
             internal_error_with_message(
                 "This version of the jj binary doesn't support this type of repo",
@@ -1731,10 +1860,12 @@ jj git init --colocate",
         ) => internal_error_with_message("The repository appears broken or inaccessible", err),
         WorkspaceLoadError::StoreLoadError(StoreLoadError::Signing(
             err @ SignInitError::UnknownBackend(_),
-        )) => user_error(err),
+        )) => user_error(ErrorCode::Config, err),
         WorkspaceLoadError::StoreLoadError(err) => internal_error(err),
         WorkspaceLoadError::WorkingCopyState(err) => internal_error(err),
-        WorkspaceLoadError::NonUnicodePath | WorkspaceLoadError::Path(_) => user_error(err),
+        WorkspaceLoadError::NonUnicodePath | WorkspaceLoadError::Path(_) => {
+            user_error(ErrorCode::InvalidPath, err)
+        }
     }
 }
 
@@ -1791,19 +1922,32 @@ pub fn check_stale_working_copy(
     locked_wc: &dyn LockedWorkingCopy,
     wc_commit: &Commit,
     repo: &ReadonlyRepo,
+) -> Result<WorkingCopyFreshness, OpStoreError> {
+    check_working_copy_freshness(
+        locked_wc.old_tree_id(),
+        locked_wc.old_operation_id(),
+        wc_commit,
+        repo,
+    )
+}
+
+/// Checks the freshness of a working copy which was last updated to
+/// `wc_tree_id` at `wc_operation_id`, without locking it.
+pub fn check_working_copy_freshness(
+    wc_tree_id: &MergedTreeId,
+    wc_operation_id: &OperationId,
+    wc_commit: &Commit,
+    repo: &ReadonlyRepo,
 ) -> Result<WorkingCopyFreshness, OpStoreError> {
     // Check if the working copy's tree matches the repo's view
-    let wc_tree_id = locked_wc.old_tree_id();
     if wc_commit.tree_id() == wc_tree_id {
         // The working copy isn't stale, and no need to reload the repo.
         Ok(WorkingCopyFreshness::Fresh)
     } else {
-        let wc_operation_data = repo
-            .op_store()
-            .read_operation(locked_wc.old_operation_id())?;
+        let wc_operation_data = repo.op_store().read_operation(wc_operation_id)?;
         let wc_operation = Operation::new(
             repo.op_store().clone(),
-            locked_wc.old_operation_id().clone(),
+            wc_operation_id.clone(),
             wc_operation_data,
         );
         let repo_operation = repo.operation();
@@ -2018,13 +2162,19 @@ pub fn update_working_copy(
     workspace: &mut Workspace,
     old_commit: Option<&Commit>,
     new_commit: &Commit,
+    options: &CheckoutOptions,
 ) -> Result<Option<CheckoutStats>, CommandError> {
     let old_tree_id = old_commit.map(|commit| commit.tree_id().clone());
     let stats = if Some(new_commit.tree_id()) != old_tree_id.as_ref() {
         // TODO: CheckoutError::ConcurrentCheckout should probably just result in a
         // warning for most commands (but be an error for the checkout command)
         let stats = workspace
-            .check_out(repo.op_id().clone(), old_tree_id.as_ref(), new_commit)
+            .check_out(
+                repo.op_id().clone(),
+                old_tree_id.as_ref(),
+                new_commit,
+                options,
+            )
             .map_err(|err| {
                 internal_error_with_message(
                     format!("Failed to check out commit {}", new_commit.id().hex()),
@@ -2124,14 +2274,14 @@ pub fn get_new_config_file_path(
 ) -> Result<PathBuf, CommandError> {
     let edit_path = match config_source {
         // TODO(#531): Special-case for editors that can't handle viewing directories?
-        ConfigSource::User => {
-            new_config_path()?.ok_or_else(|| user_error("No repo config path found to edit"))?
-        }
+        ConfigSource::User => new_config_path()?
+            .ok_or_else(|| user_error(ErrorCode::Config, "No repo config path found to edit"))?,
         ConfigSource::Repo => command.workspace_loader()?.repo_path().join("config.toml"),
         _ => {
-            return Err(user_error(format!(
-                "Can't get path for config source {config_source:?}"
-            )));
+            return Err(user_error(
+                ErrorCode::Unsupported,
+                format!("Can't get path for config source {config_source:?}"),
+            ));
         }
     };
     Ok(edit_path)
@@ -2147,6 +2297,7 @@ pub fn run_ui_editor(settings: &UserSettings, edit_path: &PathBuf) -> Result<(),
     tracing::info!(?cmd, "running editor");
     let exit_status = cmd.status().map_err(|err| {
         user_error_with_message(
+            ErrorCode::ToolFailed,
             format!(
                 // The executable couldn't be found or run; command-line arguments are not relevant
                 "Failed to run editor '{name}'",
@@ -2156,9 +2307,10 @@ pub fn run_ui_editor(settings: &UserSettings, edit_path: &PathBuf) -> Result<(),
         )
     })?;
     if !exit_status.success() {
-        return Err(user_error(format!(
-            "Editor '{editor}' exited with an error"
-        )));
+        return Err(user_error(
+            ErrorCode::ToolFailed,
+            format!("Editor '{editor}' exited with an error"),
+        ));
     }
 
     Ok(())
@@ -2182,6 +2334,7 @@ pub fn edit_temp_file(
     })()
     .map_err(|e| {
         user_error_with_message(
+            ErrorCode::Io,
             format!(
                 r#"Failed to create {} file in "{}""#,
                 error_name,
@@ -2195,6 +2348,7 @@ pub fn edit_temp_file(
 
     let edited = fs::read_to_string(&path).map_err(|e| {
         user_error_with_message(
+            ErrorCode::Io,
             format!(r#"Failed to read {} file "{}""#, error_name, path.display()),
             e,
         )
@@ -2450,6 +2604,76 @@ impl ValueParserFactory for RevisionArg {
     }
 }
 
+/// Argument to read the `-T` template from a file instead.
+///
+/// The command must also define the `template` argument.
+#[derive(clap::Args, Clone, Debug)]
+pub struct TemplateFileArgs {
+    /// Render using the template read from the given file
+    ///
+    /// The path is relative to the current directory.
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = clap::ValueHint::FilePath,
+        conflicts_with = "template"
+    )]
+    template_file: Option<String>,
+}
+
+impl TemplateFileArgs {
+    /// Returns the template text given by `-T` or read from `--template-file`,
+    /// or the value of `config_key` if neither is specified.
+    pub fn template_text(
+        &self,
+        command: &CommandHelper,
+        template: Option<&str>,
+        config_key: &str,
+    ) -> Result<TemplateText, CommandError> {
+        let text = if let Some(text) = template {
+            text.to_owned()
+        } else if let Some(path) = &self.template_file {
+            fs::read_to_string(command.cwd().join(path)).map_err(|err| {
+                user_error_with_message(
+                    ErrorCode::Io,
+                    format!("Failed to read template file {path}"),
+                    err,
+                )
+            })?
+        } else {
+            command.settings().config().get_string(config_key)?
+        };
+        Ok(TemplateText {
+            text,
+            path: self.template_file.clone(),
+        })
+    }
+}
+
+/// Template text resolved by [`TemplateFileArgs`].
+#[derive(Clone, Debug)]
+pub struct TemplateText {
+    text: String,
+    /// The file the text was read from.
+    path: Option<String>,
+}
+
+impl TemplateText {
+    /// Parses the template text by `parse`, adding a hint naming the template
+    /// file to parse errors.
+    pub fn parse<T>(
+        &self,
+        parse: impl FnOnce(&str) -> Result<T, CommandError>,
+    ) -> Result<T, CommandError> {
+        parse(&self.text).map_err(|mut err| {
+            if let (ErrorCode::TemplateParse, Some(path)) = (err.code, &self.path) {
+                err.add_hint(format!("The template was read from {path}"));
+            }
+            err
+        })
+    }
+}
+
 fn get_string_or_array(
     config: &config::Config,
     key: &str,
@@ -2514,6 +2738,7 @@ fn resolve_aliases(
         for (alias, definition) in alias_map {
             if aliases_map.insert(alias.clone(), definition).is_some() {
                 return Err(user_error_with_hint(
+                    ErrorCode::Config,
                     format!(r#"Alias "{alias}" is defined in both [aliases] and [alias]"#),
                     "[aliases] is the preferred section for aliases. Please remove the alias from \
                      [alias].",
@@ -2551,9 +2776,10 @@ fn resolve_aliases(
                     .map(|arg| arg.to_str().unwrap().to_string())
                     .collect_vec();
                 if resolved_aliases.contains(&alias_name) {
-                    return Err(user_error(format!(
-                        r#"Recursive alias definition involving "{alias_name}""#
-                    )));
+                    return Err(user_error(
+                        ErrorCode::Config,
+                        format!(r#"Recursive alias definition involving "{alias_name}""#),
+                    ));
                 }
                 if let Some(value) = aliases_map.remove(&alias_name) {
                     if let Ok(alias_definition) = value.try_deserialize::<Vec<String>>() {
@@ -2564,9 +2790,10 @@ fn resolve_aliases(
                         resolved_aliases.insert(alias_name.clone());
                         continue;
                     } else {
-                        return Err(user_error(format!(
-                            r#"Alias definition for "{alias_name}" must be a string list"#
-                        )));
+                        return Err(user_error(
+                            ErrorCode::Config,
+                            format!(r#"Alias definition for "{alias_name}" must be a string list"#),
+                        ));
                     }
                 } else {
                     // Not a real command and not an alias, so return what we've resolved so far
@@ -2598,6 +2825,9 @@ fn handle_early_args(
 
     if let Some(choice) = args.color {
         args.config_toml.push(format!(r#"ui.color="{choice}""#));
+        // The command-line flag should also override the diff-specific setting.
+        args.config_toml
+            .push(format!(r#"ui.diff.color="{choice}""#));
     }
     if args.quiet.unwrap_or_default() {
         args.config_toml.push(r#"ui.quiet=true"#.to_string());
@@ -2817,6 +3047,7 @@ impl CliRunner {
             .and_then(|cwd| cwd.canonicalize())
             .map_err(|_| {
                 user_error_with_hint(
+                    ErrorCode::Io,
                     "Could not determine current directory",
                     "Did you update to a commit where the directory doesn't exist?",
                 )
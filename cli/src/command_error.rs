@@ -22,9 +22,11 @@ use jj_lib::backend::BackendError;
 use jj_lib::fileset::{FilePatternParseError, FilesetParseError, FilesetParseErrorKind};
 use jj_lib::git::{GitConfigParseError, GitExportError, GitImportError, GitRemoteManagementError};
 use jj_lib::gitignore::GitIgnoreError;
+use jj_lib::local_working_copy::TreeStateError;
 use jj_lib::op_heads_store::OpHeadResolutionError;
 use jj_lib::op_store::OpStoreError;
-use jj_lib::op_walk::OpsetEvaluationError;
+use jj_lib::op_walk::{OpsetEvaluationError, OpsetResolutionError};
+use jj_lib::opset::{OpsetParseError, OpsetParseErrorKind};
 use jj_lib::repo::{CheckOutCommitError, EditCommitError, RepoLoaderError, RewriteRootCommit};
 use jj_lib::repo_path::{RepoPathBuf, UiPathParseError};
 use jj_lib::revset::{
@@ -34,6 +36,7 @@ use jj_lib::signing::SignInitError;
 use jj_lib::str_util::StringPatternParseError;
 use jj_lib::working_copy::{ResetError, SnapshotError, WorkingCopyStateError};
 use jj_lib::workspace::WorkspaceInitError;
+use jj_lib::workspace_store::WorkspaceStoreError;
 use thiserror::Error;
 
 use crate::diff_util::DiffRenderError;
@@ -41,7 +44,7 @@ use crate::formatter::{FormatRecorder, Formatter};
 use crate::merge_tools::{ConflictResolveError, DiffEditError, MergeToolConfigError};
 use crate::revset_util::UserRevsetEvaluationError;
 use crate::template_parser::{TemplateParseError, TemplateParseErrorKind};
-use crate::ui::Ui;
+use crate::ui::{ErrorFormat, Ui};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum CommandErrorKind {
@@ -53,9 +56,165 @@ pub enum CommandErrorKind {
     Internal,
 }
 
+/// Stable identifier of an error, which scripts can match on instead of the
+/// message.
+///
+/// The code is printed with `ui.error-format = "json"`. Once released, codes
+/// must not be renamed. User errors must declare their code when they're
+/// built. Other errors get the generic code of their [`CommandErrorKind`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorCode {
+    /// Config error without more specific code
+    Config,
+    /// Invalid command line
+    Cli,
+    /// Output pipe was closed (never printed)
+    BrokenPipe,
+    /// Internal error without more specific code
+    Internal,
+    /// Repo, workspace, or destination path already exists
+    AlreadyExists,
+    /// Commit, change, or operation id prefix matches more than one id
+    AmbiguousPrefix,
+    /// Unexpected error from the commit or operation backend
+    Backend,
+    /// Branch would be moved backwards or sideways
+    BackwardsBranchMove,
+    /// Local branch conflicts with its remote-tracking branch
+    BranchConflict,
+    /// Command would make a commit its own ancestor
+    CommitLoop,
+    /// Working copy was updated by another process concurrently
+    ConcurrentOperation,
+    /// Revset didn't resolve to any revisions
+    EmptyRevset,
+    /// File is too large to be snapshotted or diffed
+    FileTooLarge,
+    /// Fileset expression failed to parse
+    FilesetParse,
+    /// Git operation failed
+    Git,
+    /// Command would rewrite an immutable commit
+    ImmutableCommit,
+    /// Argument value is invalid, or arguments can't be used together
+    InvalidArgument,
+    /// Operation can't be abandoned or undone as requested
+    InvalidOperation,
+    /// Path is invalid, e.g. not valid UTF-8 or outside the workspace
+    InvalidPath,
+    /// I/O error other than a broken pipe
+    Io,
+    /// Operation expression resolved to more than one operation
+    MultipleOperations,
+    /// Revset resolved to more than one revision
+    MultipleRevisions,
+    /// No (or no suitable) repository found
+    NoRepo,
+    /// Branch doesn't exist
+    NoSuchBranch,
+    /// Operation doesn't exist
+    NoSuchOperation,
+    /// Path doesn't exist in the revision
+    NoSuchPath,
+    /// Revision doesn't exist
+    NoSuchRevision,
+    /// Workspace doesn't exist
+    NoSuchWorkspace,
+    /// Workspace has no working-copy commit, or the command needs one
+    NoWorkingCopy,
+    /// Path isn't conflicted
+    NotAConflict,
+    /// No changes were selected interactively
+    NothingSelected,
+    /// There is no undo operation to redo
+    NothingToRedo,
+    /// Operation expression failed to parse
+    OpsetParse,
+    /// Fetching from some of the remotes failed
+    PartialFetch,
+    /// Push was refused, locally or by the remote
+    PushRejected,
+    /// Operations after the undo changed the refs the redo would restore
+    RedoConflict,
+    /// Revset failed to evaluate
+    RevsetEvaluation,
+    /// Revset expression failed to parse
+    RevsetParse,
+    /// Symbol in a revset failed to resolve
+    RevsetResolution,
+    /// Working copy is stale and needs `jj workspace update-stale`
+    StaleWorkingCopy,
+    /// Template failed to parse
+    TemplateParse,
+    /// External tool such as an editor or a diff/merge tool failed
+    ToolFailed,
+    /// Conflicts remain that the command requires to be resolved
+    UnresolvedConflicts,
+    /// Operation isn't supported by the repo, the backend, or this build
+    Unsupported,
+    /// Remote branch must be tracked first
+    UntrackedRemoteBranch,
+    /// `jj diff --check` found whitespace problems
+    WhitespaceProblems,
+}
+
+impl ErrorCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::Config => "config-error",
+            ErrorCode::Cli => "cli-error",
+            ErrorCode::BrokenPipe => "broken-pipe",
+            ErrorCode::Internal => "internal-error",
+            ErrorCode::AlreadyExists => "already-exists",
+            ErrorCode::AmbiguousPrefix => "ambiguous-prefix",
+            ErrorCode::Backend => "backend",
+            ErrorCode::BackwardsBranchMove => "backwards-branch-move",
+            ErrorCode::BranchConflict => "branch-conflict",
+            ErrorCode::CommitLoop => "commit-loop",
+            ErrorCode::ConcurrentOperation => "concurrent-operation",
+            ErrorCode::EmptyRevset => "empty-revset",
+            ErrorCode::FileTooLarge => "file-too-large",
+            ErrorCode::FilesetParse => "fileset-parse",
+            ErrorCode::Git => "git",
+            ErrorCode::ImmutableCommit => "immutable-commit",
+            ErrorCode::InvalidArgument => "invalid-argument",
+            ErrorCode::InvalidOperation => "invalid-operation",
+            ErrorCode::InvalidPath => "invalid-path",
+            ErrorCode::Io => "io",
+            ErrorCode::MultipleOperations => "multiple-operations",
+            ErrorCode::MultipleRevisions => "multiple-revisions",
+            ErrorCode::NoRepo => "no-repo",
+            ErrorCode::NoSuchBranch => "no-such-branch",
+            ErrorCode::NoSuchOperation => "no-such-operation",
+            ErrorCode::NoSuchPath => "no-such-path",
+            ErrorCode::NoSuchRevision => "no-such-revision",
+            ErrorCode::NoSuchWorkspace => "no-such-workspace",
+            ErrorCode::NoWorkingCopy => "no-working-copy",
+            ErrorCode::NotAConflict => "not-a-conflict",
+            ErrorCode::NothingSelected => "nothing-selected",
+            ErrorCode::NothingToRedo => "nothing-to-redo",
+            ErrorCode::OpsetParse => "opset-parse",
+            ErrorCode::PartialFetch => "partial-fetch",
+            ErrorCode::PushRejected => "push-rejected",
+            ErrorCode::RedoConflict => "redo-conflict",
+            ErrorCode::RevsetEvaluation => "revset-evaluation",
+            ErrorCode::RevsetParse => "revset-parse",
+            ErrorCode::RevsetResolution => "revset-resolution",
+            ErrorCode::StaleWorkingCopy => "stale-working-copy",
+            ErrorCode::TemplateParse => "template-parse",
+            ErrorCode::ToolFailed => "tool-failed",
+            ErrorCode::UnresolvedConflicts => "unresolved-conflicts",
+            ErrorCode::Unsupported => "unsupported",
+            ErrorCode::UntrackedRemoteBranch => "untracked-remote-branch",
+            ErrorCode::WhitespaceProblems => "whitespace-problems",
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct CommandError {
     pub kind: CommandErrorKind,
+    pub code: ErrorCode,
     pub error: Arc<dyn error::Error + Send + Sync>,
     pub hints: Vec<ErrorHint>,
 }
@@ -63,10 +222,12 @@ pub struct CommandError {
 impl CommandError {
     pub fn new(
         kind: CommandErrorKind,
+        code: ErrorCode,
         err: impl Into<Box<dyn error::Error + Send + Sync>>,
     ) -> Self {
         CommandError {
             kind,
+            code,
             error: Arc::from(err.into()),
             hints: vec![],
         }
@@ -74,10 +235,17 @@ impl CommandError {
 
     pub fn with_message(
         kind: CommandErrorKind,
+        code: ErrorCode,
         message: impl Into<String>,
         source: impl Into<Box<dyn error::Error + Send + Sync>>,
     ) -> Self {
-        Self::new(kind, ErrorWithMessage::new(message, source))
+        Self::new(kind, code, ErrorWithMessage::new(message, source))
+    }
+
+    /// Returns error with the more specific `code`.
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        self.code = code;
+        self
     }
 
     /// Returns error with the given plain-text `hint` attached.
@@ -139,48 +307,58 @@ impl ErrorWithMessage {
     }
 }
 
-pub fn user_error(err: impl Into<Box<dyn error::Error + Send + Sync>>) -> CommandError {
-    CommandError::new(CommandErrorKind::User, err)
+pub fn user_error(
+    code: ErrorCode,
+    err: impl Into<Box<dyn error::Error + Send + Sync>>,
+) -> CommandError {
+    CommandError::new(CommandErrorKind::User, code, err)
 }
 
 pub fn user_error_with_hint(
+    code: ErrorCode,
     err: impl Into<Box<dyn error::Error + Send + Sync>>,
     hint: impl Into<String>,
 ) -> CommandError {
-    user_error(err).hinted(hint)
+    user_error(code, err).hinted(hint)
 }
 
 pub fn user_error_with_message(
+    code: ErrorCode,
     message: impl Into<String>,
     source: impl Into<Box<dyn error::Error + Send + Sync>>,
 ) -> CommandError {
-    CommandError::with_message(CommandErrorKind::User, message, source)
+    CommandError::with_message(CommandErrorKind::User, code, message, source)
 }
 
 pub fn config_error(err: impl Into<Box<dyn error::Error + Send + Sync>>) -> CommandError {
-    CommandError::new(CommandErrorKind::Config, err)
+    CommandError::new(CommandErrorKind::Config, ErrorCode::Config, err)
 }
 
 pub fn config_error_with_message(
     message: impl Into<String>,
     source: impl Into<Box<dyn error::Error + Send + Sync>>,
 ) -> CommandError {
-    CommandError::with_message(CommandErrorKind::Config, message, source)
+    CommandError::with_message(CommandErrorKind::Config, ErrorCode::Config, message, source)
 }
 
 pub fn cli_error(err: impl Into<Box<dyn error::Error + Send + Sync>>) -> CommandError {
-    CommandError::new(CommandErrorKind::Cli, err)
+    CommandError::new(CommandErrorKind::Cli, ErrorCode::Cli, err)
 }
 
 pub fn internal_error(err: impl Into<Box<dyn error::Error + Send + Sync>>) -> CommandError {
-    CommandError::new(CommandErrorKind::Internal, err)
+    CommandError::new(CommandErrorKind::Internal, ErrorCode::Internal, err)
 }
 
 pub fn internal_error_with_message(
     message: impl Into<String>,
     source: impl Into<Box<dyn error::Error + Send + Sync>>,
 ) -> CommandError {
-    CommandError::with_message(CommandErrorKind::Internal, message, source)
+    CommandError::with_message(
+        CommandErrorKind::Internal,
+        ErrorCode::Internal,
+        message,
+        source,
+    )
 }
 
 fn format_similarity_hint<S: AsRef<str>>(candidates: &[S]) -> Option<String> {
@@ -198,17 +376,18 @@ fn format_similarity_hint<S: AsRef<str>>(candidates: &[S]) -> Option<String> {
 
 impl From<io::Error> for CommandError {
     fn from(err: io::Error) -> Self {
-        let kind = match err.kind() {
-            io::ErrorKind::BrokenPipe => CommandErrorKind::BrokenPipe,
-            _ => CommandErrorKind::User,
-        };
-        CommandError::new(kind, err)
+        match err.kind() {
+            io::ErrorKind::BrokenPipe => {
+                CommandError::new(CommandErrorKind::BrokenPipe, ErrorCode::BrokenPipe, err)
+            }
+            _ => user_error(ErrorCode::Io, err),
+        }
     }
 }
 
 impl From<jj_lib::file_util::PathError> for CommandError {
     fn from(err: jj_lib::file_util::PathError) -> Self {
-        user_error(err)
+        user_error(ErrorCode::Io, err)
     }
 }
 
@@ -245,8 +424,9 @@ impl From<CheckOutCommitError> for CommandError {
 impl From<BackendError> for CommandError {
     fn from(err: BackendError) -> Self {
         match &err {
-            BackendError::Unsupported(_) => user_error(err),
-            _ => internal_error_with_message("Unexpected error from backend", err),
+            BackendError::Unsupported(_) => user_error(ErrorCode::Unsupported, err),
+            _ => internal_error_with_message("Unexpected error from backend", err)
+                .with_code(ErrorCode::Backend),
         }
     }
 }
@@ -255,11 +435,12 @@ impl From<WorkspaceInitError> for CommandError {
     fn from(err: WorkspaceInitError) -> Self {
         match err {
             WorkspaceInitError::DestinationExists(_) => {
-                user_error("The target repo already exists")
-            }
-            WorkspaceInitError::NonUnicodePath => {
-                user_error("The target repo path contains non-unicode characters")
+                user_error(ErrorCode::AlreadyExists, "The target repo already exists")
             }
+            WorkspaceInitError::NonUnicodePath => user_error(
+                ErrorCode::InvalidPath,
+                "The target repo path contains non-unicode characters",
+            ),
             WorkspaceInitError::CheckOutCommit(err) => {
                 internal_error_with_message("Failed to check out the initial commit", err)
             }
@@ -267,17 +448,25 @@ impl From<WorkspaceInitError> for CommandError {
                 internal_error_with_message("Failed to access the repository", err)
             }
             WorkspaceInitError::Backend(err) => {
-                user_error_with_message("Failed to access the repository", err)
+                user_error_with_message(ErrorCode::Backend, "Failed to access the repository", err)
             }
             WorkspaceInitError::WorkingCopyState(err) => {
                 internal_error_with_message("Failed to access the repository", err)
             }
-            WorkspaceInitError::SignInit(err @ SignInitError::UnknownBackend(_)) => user_error(err),
+            WorkspaceInitError::SignInit(err @ SignInitError::UnknownBackend(_)) => {
+                user_error(ErrorCode::Config, err)
+            }
             WorkspaceInitError::SignInit(err) => internal_error(err),
         }
     }
 }
 
+impl From<WorkspaceStoreError> for CommandError {
+    fn from(err: WorkspaceStoreError) -> Self {
+        internal_error_with_message("Failed to access the workspace store", err)
+    }
+}
+
 impl From<OpHeadResolutionError> for CommandError {
     fn from(err: OpHeadResolutionError) -> Self {
         match err {
@@ -291,9 +480,19 @@ impl From<OpHeadResolutionError> for CommandError {
 impl From<OpsetEvaluationError> for CommandError {
     fn from(err: OpsetEvaluationError) -> Self {
         match err {
-            OpsetEvaluationError::OpsetResolution(err) => user_error(err),
+            OpsetEvaluationError::OpsetResolution(err) => {
+                let code = match &err {
+                    OpsetResolutionError::MultipleOperations(_) => ErrorCode::MultipleOperations,
+                    OpsetResolutionError::EmptyOperations(_)
+                    | OpsetResolutionError::NoSuchOperation(_) => ErrorCode::NoSuchOperation,
+                    OpsetResolutionError::InvalidIdPrefix(_) => ErrorCode::InvalidArgument,
+                    OpsetResolutionError::AmbiguousIdPrefix(_) => ErrorCode::AmbiguousPrefix,
+                };
+                user_error(code, err)
+            }
             OpsetEvaluationError::OpHeadResolution(err) => err.into(),
             OpsetEvaluationError::OpStore(err) => err.into(),
+            OpsetEvaluationError::RepoLoader(err) => err.into(),
         }
     }
 }
@@ -320,12 +519,15 @@ impl From<SnapshotError> for CommandError {
                     format!("it is {}; the maximum size allowed is ~{}.", size, max_size,)
                 };
 
-                user_error(format!(
-                    "Failed to snapshot the working copy\nThe file '{}' is too large to be \
+                user_error(
+                    ErrorCode::FileTooLarge,
+                    format!(
+                        "Failed to snapshot the working copy\nThe file '{}' is too large to be \
                      snapshotted: {}",
-                    path.display(),
-                    err_str,
-                ))
+                        path.display(),
+                        err_str,
+                    ),
+                )
                 .hinted(format!(
                     "This is to prevent large files from being added on accident. You can fix \
                      this error by:
@@ -337,7 +539,13 @@ impl From<SnapshotError> for CommandError {
                     size.0, size.0
                 ))
             }
-            err => internal_error_with_message("Failed to snapshot the working copy", err),
+            err => {
+                let hint = find_tree_state_error_hint(&err);
+                let mut cmd_err =
+                    internal_error_with_message("Failed to snapshot the working copy", err);
+                cmd_err.extend_hints(hint);
+                cmd_err
+            }
         }
     }
 }
@@ -362,16 +570,18 @@ impl From<ResetError> for CommandError {
 
 impl From<DiffEditError> for CommandError {
     fn from(err: DiffEditError) -> Self {
-        user_error_with_message("Failed to edit diff", err)
+        user_error_with_message(ErrorCode::ToolFailed, "Failed to edit diff", err)
     }
 }
 
 impl From<DiffRenderError> for CommandError {
     fn from(err: DiffRenderError) -> Self {
         match err {
-            DiffRenderError::DiffGenerate(_) => user_error(err),
+            DiffRenderError::DiffGenerate(_) => user_error(ErrorCode::ToolFailed, err),
             DiffRenderError::Backend(err) => err.into(),
-            DiffRenderError::AccessDenied { .. } => user_error(err),
+            DiffRenderError::AccessDenied { .. } => user_error(ErrorCode::Io, err),
+            DiffRenderError::BinaryTooLarge { .. } => user_error(ErrorCode::FileTooLarge, err),
+            DiffRenderError::ToolUnsupported => user_error(ErrorCode::Unsupported, err),
             DiffRenderError::Io(err) => err.into(),
         }
     }
@@ -379,7 +589,17 @@ impl From<DiffRenderError> for CommandError {
 
 impl From<ConflictResolveError> for CommandError {
     fn from(err: ConflictResolveError) -> Self {
-        user_error_with_message("Failed to resolve conflicts", err)
+        let code = match &err {
+            ConflictResolveError::InternalTool(_)
+            | ConflictResolveError::ExternalTool(_)
+            | ConflictResolveError::EmptyOrUnchanged => ErrorCode::ToolFailed,
+            ConflictResolveError::PathNotFound(_) => ErrorCode::NoSuchPath,
+            ConflictResolveError::NotAConflict(_) => ErrorCode::NotAConflict,
+            ConflictResolveError::NotNormalFiles(..)
+            | ConflictResolveError::ConflictTooComplicated { .. } => ErrorCode::Unsupported,
+            ConflictResolveError::Backend(_) => ErrorCode::Backend,
+        };
+        user_error_with_message(code, "Failed to resolve conflicts", err)
     }
 }
 
@@ -389,6 +609,7 @@ impl From<MergeToolConfigError> for CommandError {
             MergeToolConfigError::MergeArgsNotConfigured { tool_name } => {
                 let tool_name = tool_name.clone();
                 user_error_with_hint(
+                    ErrorCode::Config,
                     err,
                     format!(
                         "To use `{tool_name}` as a merge tool, the config \
@@ -397,14 +618,16 @@ impl From<MergeToolConfigError> for CommandError {
                     ),
                 )
             }
-            _ => user_error_with_message("Failed to load tool configuration", err),
+            _ => {
+                user_error_with_message(ErrorCode::Config, "Failed to load tool configuration", err)
+            }
         }
     }
 }
 
 impl From<git2::Error> for CommandError {
     fn from(err: git2::Error) -> Self {
-        user_error_with_message("Git operation failed", err)
+        user_error_with_message(ErrorCode::Git, "Git operation failed", err)
     }
 }
 
@@ -429,8 +652,11 @@ repository contents."
             GitImportError::InternalGitError(_) => None,
             GitImportError::UnexpectedBackend => None,
         };
-        let mut cmd_err =
-            user_error_with_message("Failed to import refs from underlying Git repo", err);
+        let mut cmd_err = user_error_with_message(
+            ErrorCode::Git,
+            "Failed to import refs from underlying Git repo",
+            err,
+        );
         cmd_err.extend_hints(hint);
         cmd_err
     }
@@ -444,21 +670,37 @@ impl From<GitExportError> for CommandError {
 
 impl From<GitRemoteManagementError> for CommandError {
     fn from(err: GitRemoteManagementError) -> Self {
-        user_error(err)
+        user_error(ErrorCode::Git, err)
     }
 }
 
 impl From<RevsetEvaluationError> for CommandError {
     fn from(err: RevsetEvaluationError) -> Self {
-        user_error(err)
+        user_error(ErrorCode::RevsetEvaluation, err)
     }
 }
 
 impl From<FilesetParseError> for CommandError {
     fn from(err: FilesetParseError) -> Self {
         let hint = fileset_parse_error_hint(&err);
-        let mut cmd_err =
-            user_error_with_message(format!("Failed to parse fileset: {}", err.kind()), err);
+        let mut cmd_err = user_error_with_message(
+            ErrorCode::FilesetParse,
+            format!("Failed to parse fileset: {}", err.kind()),
+            err,
+        );
+        cmd_err.extend_hints(hint);
+        cmd_err
+    }
+}
+
+impl From<OpsetParseError> for CommandError {
+    fn from(err: OpsetParseError) -> Self {
+        let hint = opset_parse_error_hint(&err);
+        let mut cmd_err = user_error_with_message(
+            ErrorCode::OpsetParse,
+            format!("Failed to parse opset: {}", err.kind()),
+            err,
+        );
         cmd_err.extend_hints(hint);
         cmd_err
     }
@@ -467,8 +709,11 @@ impl From<FilesetParseError> for CommandError {
 impl From<RevsetParseError> for CommandError {
     fn from(err: RevsetParseError) -> Self {
         let hint = revset_parse_error_hint(&err);
-        let mut cmd_err =
-            user_error_with_message(format!("Failed to parse revset: {}", err.kind()), err);
+        let mut cmd_err = user_error_with_message(
+            ErrorCode::RevsetParse,
+            format!("Failed to parse revset: {}", err.kind()),
+            err,
+        );
         cmd_err.extend_hints(hint);
         cmd_err
     }
@@ -477,7 +722,16 @@ impl From<RevsetParseError> for CommandError {
 impl From<RevsetResolutionError> for CommandError {
     fn from(err: RevsetResolutionError) -> Self {
         let hint = revset_resolution_error_hint(&err);
-        let mut cmd_err = user_error(err);
+        let code = match &err {
+            RevsetResolutionError::NoSuchRevision { .. } => ErrorCode::NoSuchRevision,
+            RevsetResolutionError::WorkspaceMissingWorkingCopy { .. } => ErrorCode::NoWorkingCopy,
+            RevsetResolutionError::EmptyString => ErrorCode::RevsetResolution,
+            RevsetResolutionError::AmbiguousCommitIdPrefix(_)
+            | RevsetResolutionError::AmbiguousChangeIdPrefix(_) => ErrorCode::AmbiguousPrefix,
+            RevsetResolutionError::StoreError(_) => ErrorCode::Backend,
+            RevsetResolutionError::Other(_) => ErrorCode::RevsetResolution,
+        };
+        let mut cmd_err = user_error(code, err);
         cmd_err.extend_hints(hint);
         cmd_err
     }
@@ -495,8 +749,11 @@ impl From<UserRevsetEvaluationError> for CommandError {
 impl From<TemplateParseError> for CommandError {
     fn from(err: TemplateParseError) -> Self {
         let hint = template_parse_error_hint(&err);
-        let mut cmd_err =
-            user_error_with_message(format!("Failed to parse template: {}", err.kind()), err);
+        let mut cmd_err = user_error_with_message(
+            ErrorCode::TemplateParse,
+            format!("Failed to parse template: {}", err.kind()),
+            err,
+        );
         cmd_err.extend_hints(hint);
         cmd_err
     }
@@ -504,7 +761,7 @@ impl From<TemplateParseError> for CommandError {
 
 impl From<UiPathParseError> for CommandError {
     fn from(err: UiPathParseError) -> Self {
-        user_error(err)
+        user_error(ErrorCode::InvalidPath, err)
     }
 }
 
@@ -525,14 +782,34 @@ impl From<GitConfigParseError> for CommandError {
 
 impl From<WorkingCopyStateError> for CommandError {
     fn from(err: WorkingCopyStateError) -> Self {
-        internal_error_with_message("Failed to access working copy state", err)
+        let hint = find_tree_state_error_hint(&err);
+        let mut cmd_err = internal_error_with_message("Failed to access working copy state", err);
+        cmd_err.extend_hints(hint);
+        cmd_err
     }
 }
 
 impl From<GitIgnoreError> for CommandError {
     fn from(err: GitIgnoreError) -> Self {
-        user_error_with_message("Failed to process .gitignore.", err)
+        user_error_with_message(ErrorCode::Io, "Failed to process .gitignore.", err)
+    }
+}
+
+fn find_tree_state_error_hint(err: &dyn error::Error) -> Option<String> {
+    let mut source = err.source();
+    while let Some(err) = source {
+        if let Some(TreeStateError::ReadTreeState { .. } | TreeStateError::DecodeTreeState { .. }) =
+            err.downcast_ref()
+        {
+            return Some(
+                "The working copy state may be corrupted. Run `jj workspace update-stale \
+                 --force-rebuild` to rebuild it."
+                    .to_owned(),
+            );
+        }
+        source = err.source();
     }
+    None
 }
 
 fn find_source_parse_error_hint(err: &dyn error::Error) -> Option<String> {
@@ -583,6 +860,19 @@ fn fileset_parse_error_hint(err: &FilesetParseError) -> Option<String> {
     }
 }
 
+fn opset_parse_error_hint(err: &OpsetParseError) -> Option<String> {
+    match err.kind() {
+        OpsetParseErrorKind::NoSuchFunction {
+            name: _,
+            candidates,
+        } => format_similarity_hint(candidates),
+        OpsetParseErrorKind::InvalidArguments { .. } | OpsetParseErrorKind::Expression(_) => {
+            find_source_parse_error_hint(&err)
+        }
+        _ => None,
+    }
+}
+
 fn revset_parse_error_hint(err: &RevsetParseError) -> Option<String> {
     // Only for the bottom error, which is usually the root cause
     let bottom_err = iter::successors(Some(err), |e| e.origin()).last().unwrap();
@@ -667,10 +957,13 @@ fn try_handle_command_result(
     };
     let err = &cmd_err.error;
     let hints = &cmd_err.hints;
+    if ui.error_format() == ErrorFormat::Json && is_printable_as_json(cmd_err) {
+        print_error_json(ui, cmd_err)?;
+        return Ok(error_exit_code(cmd_err.kind));
+    }
     match cmd_err.kind {
         CommandErrorKind::User => {
             print_error(ui, "Error: ", err, hints)?;
-            Ok(ExitCode::from(1))
         }
         CommandErrorKind::Config => {
             print_error(ui, "Config error: ", err, hints)?;
@@ -678,25 +971,84 @@ fn try_handle_command_result(
                 ui.stderr_formatter().labeled("hint"),
                 "For help, see https://github.com/martinvonz/jj/blob/main/docs/config.md."
             )?;
-            Ok(ExitCode::from(1))
         }
         CommandErrorKind::Cli => {
             if let Some(err) = err.downcast_ref::<clap::Error>() {
-                handle_clap_error(ui, err, hints)
+                return handle_clap_error(ui, err, hints);
             } else {
                 print_error(ui, "Error: ", err, hints)?;
-                Ok(ExitCode::from(2))
             }
         }
         CommandErrorKind::BrokenPipe => {
             // A broken pipe is not an error, but a signal to exit gracefully.
-            Ok(ExitCode::from(BROKEN_PIPE_EXIT_CODE))
         }
         CommandErrorKind::Internal => {
             print_error(ui, "Internal error: ", err, hints)?;
-            Ok(ExitCode::from(255))
         }
     }
+    Ok(error_exit_code(cmd_err.kind))
+}
+
+fn error_exit_code(kind: CommandErrorKind) -> ExitCode {
+    match kind {
+        CommandErrorKind::User | CommandErrorKind::Config => ExitCode::from(1),
+        CommandErrorKind::Cli => ExitCode::from(2),
+        CommandErrorKind::BrokenPipe => ExitCode::from(BROKEN_PIPE_EXIT_CODE),
+        CommandErrorKind::Internal => ExitCode::from(255),
+    }
+}
+
+/// Whether the error should be printed as JSON. The help and version requested
+/// by the user are printed as usual, and nothing is printed on broken pipe.
+fn is_printable_as_json(cmd_err: &CommandError) -> bool {
+    match cmd_err.kind {
+        CommandErrorKind::BrokenPipe => false,
+        CommandErrorKind::Cli => !cmd_err
+            .error
+            .downcast_ref::<clap::Error>()
+            .is_some_and(|err| {
+                matches!(
+                    err.kind(),
+                    clap::error::ErrorKind::DisplayHelp | clap::error::ErrorKind::DisplayVersion
+                )
+            }),
+        CommandErrorKind::User | CommandErrorKind::Config | CommandErrorKind::Internal => true,
+    }
+}
+
+/// Prints the error as one line of JSON with `code`, `message`, `causes`, and
+/// `hints` fields.
+fn print_error_json(ui: &Ui, cmd_err: &CommandError) -> io::Result<()> {
+    let message = if let Some(err) = cmd_err.error.downcast_ref::<clap::Error>() {
+        let rendered = err.render().to_string();
+        rendered
+            .strip_prefix("error: ")
+            .unwrap_or(&rendered)
+            .trim_end()
+            .to_owned()
+    } else {
+        cmd_err.error.to_string()
+    };
+    let causes = iter::successors(cmd_err.error.source(), |err| err.source())
+        .map(|err| err.to_string())
+        .collect_vec();
+    let hints = cmd_err
+        .hints
+        .iter()
+        .map(|hint| match hint {
+            ErrorHint::PlainText(message) => message.clone(),
+            ErrorHint::Formatted(recorded) => String::from_utf8_lossy(recorded.data())
+                .trim_end()
+                .to_owned(),
+        })
+        .collect_vec();
+    let value = serde_json::json!({
+        "code": cmd_err.code.as_str(),
+        "message": message,
+        "causes": causes,
+        "hints": hints,
+    });
+    writeln!(ui.stderr(), "{value}")
 }
 
 fn print_error(
@@ -39,6 +39,20 @@ pub(crate) struct AbandonArgs {
     /// Do not print every abandoned commit on a separate line
     #[arg(long, short)]
     summary: bool,
+    /// Do not modify the content of the children of the abandoned commits
+    ///
+    /// The children are reparented onto the parents of the abandoned commits,
+    /// but keep their current trees. This effectively moves the changes of the
+    /// abandoned commits into their children.
+    #[arg(long)]
+    restore_descendants: bool,
+    /// Leave branches pointing to the abandoned commits
+    ///
+    /// By default, branches pointing to an abandoned commit are moved to its
+    /// parent(s). With this flag, they keep pointing to the abandoned (now
+    /// hidden) commit, so the change can be found again later.
+    #[arg(long)]
+    retain_branches: bool,
     /// Ignored (but lets you pass `-r` for consistency with other commands)
     #[arg(short = 'r', hide = true, action = clap::ArgAction::Count)]
     unused_revision: u8,
@@ -62,10 +76,27 @@ pub(crate) fn cmd_abandon(
     workspace_command.check_rewritable(to_abandon.iter().ids())?;
 
     let mut tx = workspace_command.start_transaction();
+    let retained_branches = if args.retain_branches {
+        let view = tx.base_repo().view();
+        to_abandon
+            .iter()
+            .flat_map(|commit| view.local_branches_for_commit(commit.id()))
+            .map(|(name, target)| (name.to_owned(), target.clone()))
+            .collect_vec()
+    } else {
+        vec![]
+    };
     for commit in &to_abandon {
         tx.mut_repo().record_abandoned_commit(commit.id().clone());
     }
-    let num_rebased = tx.mut_repo().rebase_descendants(command.settings())?;
+    let num_rebased = if args.restore_descendants {
+        tx.mut_repo().reparent_descendants(command.settings())?
+    } else {
+        tx.mut_repo().rebase_descendants(command.settings())?
+    };
+    for (name, target) in retained_branches {
+        tx.mut_repo().set_local_branch_target(&name, target);
+    }
 
     if let Some(mut formatter) = ui.status_formatter() {
         if to_abandon.len() == 1 {
@@ -85,10 +116,18 @@ pub(crate) fn cmd_abandon(
             writeln!(formatter, "Abandoned {} commits.", &to_abandon.len())?;
         }
         if num_rebased > 0 {
-            writeln!(
-                formatter,
-                "Rebased {num_rebased} descendant commits onto parents of abandoned commits"
-            )?;
+            if args.restore_descendants {
+                writeln!(
+                    formatter,
+                    "Rebased {num_rebased} descendant commits (while preserving their content) \
+                     onto parents of abandoned commits"
+                )?;
+            } else {
+                writeln!(
+                    formatter,
+                    "Rebased {num_rebased} descendant commits onto parents of abandoned commits"
+                )?;
+            }
         }
     }
     let transaction_description = if to_abandon.len() == 1 {
@@ -13,12 +13,15 @@
 // limitations under the License.
 
 use clap::builder::NonEmptyStringValueParser;
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::op_store::RefTarget;
+use jj_lib::view::View;
 
-use super::has_tracked_remote_branches;
-use crate::cli_util::{CommandHelper, RevisionArg};
-use crate::command_error::{user_error_with_hint, CommandError};
+use super::{check_remote_branch_to_track, has_tracked_remote_branches};
+use crate::cli_util::{short_commit_hash, CommandHelper, RevisionArg};
+use crate::command_error::{user_error_with_hint, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 /// Create a new branch
@@ -28,6 +31,28 @@ pub struct BranchCreateArgs {
     #[arg(long, short)]
     revision: Option<RevisionArg>,
 
+    /// Create each branch at the commit the remote branch of the same name on
+    /// this remote points to
+    ///
+    /// Unlike `--revision`, this works even if the local view of the
+    /// repository has diverged from the remote.
+    #[arg(long, value_name = "REMOTE", conflicts_with = "revision")]
+    at_remote_head: Option<String>,
+
+    /// Start tracking the remote branch of the same name on this remote
+    #[arg(long, value_name = "REMOTE")]
+    track: Option<String>,
+
+    /// Allow tracking a remote branch which points to a different commit
+    #[arg(long, requires = "track")]
+    allow_divergent: bool,
+
+    /// Attach a description to the branches
+    ///
+    /// The description is stored locally and never pushed to remotes.
+    #[arg(long, value_name = "TEXT")]
+    description: Option<String>,
+
     /// The branches to create
     #[arg(required = true, value_parser = NonEmptyStringValueParser::new())]
     names: Vec<String>,
@@ -39,19 +64,29 @@ pub fn cmd_branch_create(
     args: &BranchCreateArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
-    let target_commit =
-        workspace_command.resolve_single_rev(args.revision.as_ref().unwrap_or(&RevisionArg::AT))?;
     let view = workspace_command.repo().view();
     let branch_names = &args.names;
-    for name in branch_names {
+    let target_ids: Vec<CommitId> = if let Some(remote) = &args.at_remote_head {
+        branch_names
+            .iter()
+            .map(|name| resolve_remote_branch_target(view, name, remote))
+            .try_collect()?
+    } else {
+        let target_commit = workspace_command
+            .resolve_single_rev(args.revision.as_ref().unwrap_or(&RevisionArg::AT))?;
+        vec![target_commit.id().clone(); branch_names.len()]
+    };
+    for (name, target_id) in branch_names.iter().zip(&target_ids) {
         if view.get_local_branch(name).is_present() {
             return Err(user_error_with_hint(
+                ErrorCode::AlreadyExists,
                 format!("Branch already exists: {name}"),
                 "Use `jj branch set` to update it.",
             ));
         }
         if has_tracked_remote_branches(view, name) {
             return Err(user_error_with_hint(
+                ErrorCode::BranchConflict,
                 format!("Tracked remote branches exist for deleted branch: {name}"),
                 format!(
                     "Use `jj branch set` to recreate the local branch. Run `jj branch untrack \
@@ -59,6 +94,9 @@ pub fn cmd_branch_create(
                 ),
             ));
         }
+        if let Some(remote) = &args.track {
+            check_remote_branch_to_track(view, name, remote, target_id, args.allow_divergent)?;
+        }
     }
 
     if branch_names.len() > 1 {
@@ -70,17 +108,62 @@ pub fn cmd_branch_create(
     }
 
     let mut tx = workspace_command.start_transaction();
-    for branch_name in branch_names {
+    for (branch_name, target_id) in branch_names.iter().zip(&target_ids) {
+        if let Some(remote) = &args.track {
+            tx.mut_repo().track_remote_branch(branch_name, remote);
+        }
         tx.mut_repo()
-            .set_local_branch_target(branch_name, RefTarget::normal(target_commit.id().clone()));
+            .set_local_branch_target(branch_name, RefTarget::normal(target_id.clone()));
+        if let Some(description) = &args.description {
+            tx.mut_repo()
+                .set_branch_description(branch_name, description.trim_end().to_owned());
+        }
     }
-    tx.finish(
-        ui,
+    let description = if let Some(remote) = &args.at_remote_head {
+        format!(
+            "create branch {names} pointing to the remote branches on {remote}",
+            names = branch_names.join(", "),
+        )
+    } else {
         format!(
             "create branch {names} pointing to commit {id}",
             names = branch_names.join(", "),
-            id = target_commit.id().hex()
-        ),
-    )?;
+            id = target_ids[0].hex()
+        )
+    };
+    tx.finish(ui, description)?;
     Ok(())
 }
+
+/// Looks up the commit the remote branch `name@remote` points to.
+fn resolve_remote_branch_target(
+    view: &View,
+    name: &str,
+    remote: &str,
+) -> Result<CommitId, CommandError> {
+    let remote_ref = view.get_remote_branch(name, remote);
+    if remote_ref.is_absent() {
+        return Err(user_error_with_hint(
+            ErrorCode::NoSuchBranch,
+            format!("No such remote branch: {name}@{remote}"),
+            "Use `jj branch list --all-remotes` to list the remote branches.",
+        ));
+    }
+    if let Some(id) = remote_ref.target.as_normal() {
+        Ok(id.clone())
+    } else {
+        Err(user_error_with_hint(
+            ErrorCode::BranchConflict,
+            format!("Remote branch {name}@{remote} is conflicted"),
+            format!(
+                "It points to multiple commits: {ids}. Use `jj branch create -r <REVISION>` to \
+                 create the branch at one of them.",
+                ids = remote_ref
+                    .target
+                    .added_ids()
+                    .map(short_commit_hash)
+                    .join(", "),
+            ),
+        ))
+    }
+}
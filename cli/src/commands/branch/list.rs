@@ -18,7 +18,7 @@ use jj_lib::git;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::str_util::StringPattern;
 
-use crate::cli_util::{CommandHelper, RevisionArg};
+use crate::cli_util::{CommandHelper, RevisionArg, TemplateFileArgs};
 use crate::command_error::CommandError;
 use crate::commit_templater::{CommitTemplateLanguage, RefName};
 use crate::ui::Ui;
@@ -71,6 +71,8 @@ pub struct BranchListArgs {
     /// For the syntax, see https://github.com/martinvonz/jj/blob/main/docs/templates.md
     #[arg(long, short = 'T')]
     template: Option<String>,
+    #[command(flatten)]
+    template_file: TemplateFileArgs,
 }
 
 pub fn cmd_branch_list(
@@ -113,13 +115,15 @@ pub fn cmd_branch_list(
 
     let template = {
         let language = workspace_command.commit_template_language()?;
-        let text = match &args.template {
-            Some(value) => value.to_owned(),
-            None => command.settings().config().get("templates.branch_list")?,
-        };
-        workspace_command
-            .parse_template(&language, &text, CommitTemplateLanguage::wrap_ref_name)?
-            .labeled("branch_list")
+        let text = args.template_file.template_text(
+            command,
+            args.template.as_deref(),
+            "templates.branch_list",
+        )?;
+        text.parse(|text| {
+            workspace_command.parse_template(&language, text, CommitTemplateLanguage::wrap_ref_name)
+        })?
+        .labeled("branch_list")
     };
 
     ui.request_pager();
@@ -149,10 +153,11 @@ pub fn cmd_branch_list(
         }
 
         if !args.tracked && local_target.is_present() || !tracking_remote_refs.is_empty() {
-            let ref_name = RefName::local(
+            let ref_name = RefName::local_with_description(
                 name,
                 local_target.clone(),
                 remote_refs.iter().map(|&(_, remote_ref)| remote_ref),
+                view.get_branch_description(name).unwrap_or_default(),
             );
             template.format(&ref_name, formatter.as_mut())?;
         }
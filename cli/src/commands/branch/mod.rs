@@ -24,8 +24,10 @@ mod untrack;
 
 use itertools::Itertools as _;
 use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
 use jj_lib::op_store::{RefTarget, RemoteRef};
 use jj_lib::repo::Repo;
+use jj_lib::settings::{ConfigResultExt as _, UserSettings};
 use jj_lib::str_util::StringPattern;
 use jj_lib::view::View;
 
@@ -38,8 +40,10 @@ use self::rename::{cmd_branch_rename, BranchRenameArgs};
 use self::set::{cmd_branch_set, BranchSetArgs};
 use self::track::{cmd_branch_track, BranchTrackArgs};
 use self::untrack::{cmd_branch_untrack, BranchUntrackArgs};
-use crate::cli_util::{CommandHelper, RemoteBranchName, RemoteBranchNamePattern};
-use crate::command_error::{user_error, CommandError};
+use crate::cli_util::{
+    CommandHelper, RemoteBranchName, RemoteBranchNamePattern, WorkspaceCommandHelper,
+};
+use crate::command_error::{user_error, user_error_with_hint, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 /// Manage branches
@@ -113,11 +117,17 @@ fn find_branches_with<'a, 'b, V, I: Iterator<Item = (&'a str, V)>>(
             matching_branches.dedup_by_key(|(name, _)| *name);
             Ok(matching_branches)
         }
-        [pattern] if pattern.is_exact() => Err(user_error(format!("No such branch: {pattern}"))),
-        patterns => Err(user_error(format!(
-            "No matching branches for patterns: {}",
-            patterns.iter().join(", ")
-        ))),
+        [pattern] if pattern.is_exact() => Err(user_error(
+            ErrorCode::NoSuchBranch,
+            format!("No such branch: {pattern}"),
+        )),
+        patterns => Err(user_error(
+            ErrorCode::NoSuchBranch,
+            format!(
+                "No matching branches for patterns: {}",
+                patterns.iter().join(", ")
+            ),
+        )),
     }
 }
 
@@ -149,13 +159,17 @@ fn find_remote_branches<'a>(
             matching_branches.dedup_by(|(name1, _), (name2, _)| name1 == name2);
             Ok(matching_branches)
         }
-        [pattern] if pattern.is_exact() => {
-            Err(user_error(format!("No such remote branch: {pattern}")))
-        }
-        patterns => Err(user_error(format!(
-            "No matching remote branches for patterns: {}",
-            patterns.iter().join(", ")
-        ))),
+        [pattern] if pattern.is_exact() => Err(user_error(
+            ErrorCode::NoSuchBranch,
+            format!("No such remote branch: {pattern}"),
+        )),
+        patterns => Err(user_error(
+            ErrorCode::NoSuchBranch,
+            format!(
+                "No matching remote branches for patterns: {}",
+                patterns.iter().join(", ")
+            ),
+        )),
     }
 }
 
@@ -166,6 +180,36 @@ fn has_tracked_remote_branches(view: &View, branch: &str) -> bool {
         .any(|(_, remote_ref)| remote_ref.is_tracking())
 }
 
+/// Checks that the remote branch `name@remote` can be tracked by a local
+/// branch pointing to `target_id`.
+fn check_remote_branch_to_track(
+    view: &View,
+    name: &str,
+    remote: &str,
+    target_id: &CommitId,
+    allow_divergent: bool,
+) -> Result<(), CommandError> {
+    let remote_ref = view.get_remote_branch(name, remote);
+    if remote_ref.is_absent() {
+        return Err(user_error_with_hint(
+            ErrorCode::NoSuchBranch,
+            format!("No such remote branch: {name}@{remote}"),
+            format!(
+                "Use `jj git push --remote {remote} --branch {name}` to create the branch on the \
+                 remote."
+            ),
+        ));
+    }
+    if !allow_divergent && remote_ref.target != RefTarget::normal(target_id.clone()) {
+        return Err(user_error_with_hint(
+            ErrorCode::BranchConflict,
+            format!("Branch target differs from the remote branch: {name}@{remote}"),
+            "Use --allow-divergent to track it anyway.",
+        ));
+    }
+    Ok(())
+}
+
 fn is_fast_forward(repo: &dyn Repo, old_target: &RefTarget, new_target_id: &CommitId) -> bool {
     if old_target.is_present() {
         // Strictly speaking, "all" old targets should be ancestors, but we allow
@@ -177,3 +221,52 @@ fn is_fast_forward(repo: &dyn Repo, old_target: &RefTarget, new_target_id: &Comm
         true
     }
 }
+
+/// Checks that the branch `name` can be moved from `old_target` to
+/// `new_target`. A backward or sideways move is refused unless
+/// `allow_backwards` is set or, with `branch.require-confirm-backwards`
+/// enabled, the user confirms it at the prompt.
+fn check_backwards_move(
+    ui: &Ui,
+    settings: &UserSettings,
+    workspace_command: &WorkspaceCommandHelper,
+    name: &str,
+    old_target: &RefTarget,
+    new_target: &Commit,
+    allow_backwards: bool,
+) -> Result<(), CommandError> {
+    let repo = workspace_command.repo().as_ref();
+    if allow_backwards || is_fast_forward(repo, old_target, new_target.id()) {
+        return Ok(());
+    }
+    let require_confirm = settings
+        .config()
+        .get_bool("branch.require-confirm-backwards")
+        .optional()?
+        .unwrap_or(false);
+    if require_confirm && Ui::can_prompt() {
+        let mut formatter = ui.stdout_formatter();
+        writeln!(
+            formatter,
+            "Branch {name} would be moved backwards or sideways."
+        )?;
+        for id in old_target.added_ids() {
+            write!(formatter, "  From: ")?;
+            workspace_command
+                .write_commit_summary(formatter.as_mut(), &repo.store().get_commit(id)?)?;
+            writeln!(formatter)?;
+        }
+        write!(formatter, "  To:   ")?;
+        workspace_command.write_commit_summary(formatter.as_mut(), new_target)?;
+        writeln!(formatter)?;
+        drop(formatter);
+        if ui.prompt_yes_no("Move it anyway?", Some(false))? {
+            return Ok(());
+        }
+    }
+    Err(user_error_with_hint(
+        ErrorCode::BackwardsBranchMove,
+        format!("Refusing to move branch backwards or sideways: {name}"),
+        "Use --allow-backwards to allow it.",
+    ))
+}
@@ -18,9 +18,9 @@ use jj_lib::object_id::ObjectId as _;
 use jj_lib::op_store::RefTarget;
 use jj_lib::str_util::StringPattern;
 
-use super::{find_branches_with, is_fast_forward};
+use super::{check_backwards_move, find_branches_with};
 use crate::cli_util::{CommandHelper, RevisionArg};
-use crate::command_error::{user_error_with_hint, CommandError};
+use crate::command_error::CommandError;
 use crate::ui::Ui;
 
 /// Move existing branches to target revision
@@ -105,16 +105,16 @@ pub fn cmd_branch_move(
         }
     }
 
-    if !args.allow_backwards {
-        if let Some((name, _)) = matched_branches
-            .iter()
-            .find(|(_, old_target)| !is_fast_forward(repo.as_ref(), old_target, target_commit.id()))
-        {
-            return Err(user_error_with_hint(
-                format!("Refusing to move branch backwards or sideways: {name}"),
-                "Use --allow-backwards to allow it.",
-            ));
-        }
+    for (name, old_target) in &matched_branches {
+        check_backwards_move(
+            ui,
+            command.settings(),
+            &workspace_command,
+            name,
+            old_target,
+            &target_commit,
+            args.allow_backwards,
+        )?;
     }
 
     let mut tx = workspace_command.start_transaction();
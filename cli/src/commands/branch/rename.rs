@@ -16,7 +16,7 @@ use jj_lib::op_store::RefTarget;
 
 use super::has_tracked_remote_branches;
 use crate::cli_util::CommandHelper;
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 /// Rename `old` branch name to `new` branch name
@@ -41,17 +41,30 @@ pub fn cmd_branch_rename(
     let old_branch = &args.old;
     let ref_target = view.get_local_branch(old_branch).clone();
     if ref_target.is_absent() {
-        return Err(user_error(format!("No such branch: {old_branch}")));
+        return Err(user_error(
+            ErrorCode::NoSuchBranch,
+            format!("No such branch: {old_branch}"),
+        ));
     }
 
     let new_branch = &args.new;
     if view.get_local_branch(new_branch).is_present() {
-        return Err(user_error(format!("Branch already exists: {new_branch}")));
+        return Err(user_error(
+            ErrorCode::AlreadyExists,
+            format!("Branch already exists: {new_branch}"),
+        ));
     }
 
+    let description = view
+        .get_branch_description(old_branch)
+        .unwrap_or_default()
+        .to_owned();
+
     let mut tx = workspace_command.start_transaction();
     tx.mut_repo()
         .set_local_branch_target(new_branch, ref_target);
+    tx.mut_repo()
+        .set_branch_description(new_branch, description);
     tx.mut_repo()
         .set_local_branch_target(old_branch, RefTarget::absent());
     tx.finish(ui, format!("rename branch {old_branch} to {new_branch}"))?;
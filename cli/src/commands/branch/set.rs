@@ -16,9 +16,9 @@ use clap::builder::NonEmptyStringValueParser;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::op_store::RefTarget;
 
-use super::{has_tracked_remote_branches, is_fast_forward};
+use super::{check_backwards_move, check_remote_branch_to_track, has_tracked_remote_branches};
 use crate::cli_util::{CommandHelper, RevisionArg};
-use crate::command_error::{user_error_with_hint, CommandError};
+use crate::command_error::CommandError;
 use crate::ui::Ui;
 
 /// Create or update a branch to point to a certain commit
@@ -32,6 +32,21 @@ pub struct BranchSetArgs {
     #[arg(long, short = 'B')]
     allow_backwards: bool,
 
+    /// Start tracking the remote branch of the same name on this remote
+    #[arg(long, value_name = "REMOTE")]
+    track: Option<String>,
+
+    /// Allow tracking a remote branch which points to a different commit
+    #[arg(long, requires = "track")]
+    allow_divergent: bool,
+
+    /// Update the description attached to the branches
+    ///
+    /// Pass an empty string to remove the description. The description is
+    /// stored locally and never pushed to remotes.
+    #[arg(long, value_name = "TEXT")]
+    description: Option<String>,
+
     /// The branches to update
     #[arg(required = true, value_parser = NonEmptyStringValueParser::new())]
     names: Vec<String>,
@@ -48,6 +63,7 @@ pub fn cmd_branch_set(
     let repo = workspace_command.repo().as_ref();
     let branch_names = &args.names;
     let mut new_branch_names: Vec<&str> = Vec::new();
+    let mut branch_names_to_track: Vec<&str> = Vec::new();
     for name in branch_names {
         let old_target = repo.view().get_local_branch(name);
         // If a branch is absent locally but is still tracking remote branches,
@@ -55,11 +71,31 @@ pub fn cmd_branch_set(
         if old_target.is_absent() && !has_tracked_remote_branches(repo.view(), name) {
             new_branch_names.push(name);
         }
-        if !args.allow_backwards && !is_fast_forward(repo, old_target, target_commit.id()) {
-            return Err(user_error_with_hint(
-                format!("Refusing to move branch backwards or sideways: {name}"),
-                "Use --allow-backwards to allow it.",
-            ));
+        check_backwards_move(
+            ui,
+            command.settings(),
+            &workspace_command,
+            name,
+            old_target,
+            &target_commit,
+            args.allow_backwards,
+        )?;
+        if let Some(remote) = &args.track {
+            if repo.view().get_remote_branch(name, remote).is_tracking() {
+                writeln!(
+                    ui.warning_default(),
+                    "Remote branch already tracked: {name}@{remote}"
+                )?;
+            } else {
+                check_remote_branch_to_track(
+                    repo.view(),
+                    name,
+                    remote,
+                    target_commit.id(),
+                    args.allow_divergent,
+                )?;
+                branch_names_to_track.push(name);
+            }
         }
     }
 
@@ -72,9 +108,18 @@ pub fn cmd_branch_set(
     }
 
     let mut tx = workspace_command.start_transaction();
+    if let Some(remote) = &args.track {
+        for branch_name in &branch_names_to_track {
+            tx.mut_repo().track_remote_branch(branch_name, remote);
+        }
+    }
     for branch_name in branch_names {
         tx.mut_repo()
             .set_local_branch_target(branch_name, RefTarget::normal(target_commit.id().clone()));
+        if let Some(description) = &args.description {
+            tx.mut_repo()
+                .set_branch_description(branch_name, description.trim_end().to_owned());
+        }
     }
     tx.finish(
         ui,
@@ -12,7 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::slice;
 
 use itertools::Itertools as _;
 
@@ -35,6 +36,9 @@ pub struct BranchTrackArgs {
     /// select branches by wildcard pattern. For details, see
     /// https://github.com/martinvonz/jj/blob/main/docs/revsets.md#string-patterns.
     ///
+    /// Already tracked branches matched by a `glob:` pattern are skipped
+    /// silently, and a pattern matching no branches only produces a warning.
+    ///
     /// Examples: branch@remote, glob:main@*, glob:jjfan-*@upstream
     #[arg(required = true, value_name = "BRANCH@REMOTE")]
     names: Vec<RemoteBranchNamePattern>,
@@ -47,13 +51,43 @@ pub fn cmd_branch_track(
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
     let view = workspace_command.repo().view();
+    // Exact names must exist, but glob patterns may legitimately match nothing,
+    // e.g. when the branches haven't been pushed yet.
+    let (exact_patterns, glob_patterns): (Vec<_>, Vec<_>) = args
+        .names
+        .iter()
+        .cloned()
+        .partition(|pattern| pattern.is_exact());
+    let mut matching_branches = if exact_patterns.is_empty() {
+        vec![]
+    } else {
+        find_remote_branches(view, &exact_patterns)?
+    };
+    let exact_names: HashSet<_> = matching_branches
+        .iter()
+        .map(|(name, _)| name.clone())
+        .collect();
+    for pattern in &glob_patterns {
+        match find_remote_branches(view, slice::from_ref(pattern)) {
+            Ok(matches) => matching_branches.extend(matches),
+            Err(_) => writeln!(
+                ui.warning_default(),
+                "No matching remote branches for pattern: {pattern}"
+            )?,
+        }
+    }
+    matching_branches.sort_unstable_by(|(name1, _), (name2, _)| name1.cmp(name2));
+    matching_branches.dedup_by(|(name1, _), (name2, _)| name1 == name2);
     let mut names = Vec::new();
-    for (name, remote_ref) in find_remote_branches(view, &args.names)? {
+    for (name, remote_ref) in matching_branches {
         if remote_ref.is_tracking() {
-            writeln!(
-                ui.warning_default(),
-                "Remote branch already tracked: {name}"
-            )?;
+            // Patterns are expected to match already tracked branches
+            if exact_names.contains(&name) {
+                writeln!(
+                    ui.warning_default(),
+                    "Remote branch already tracked: {name}"
+                )?;
+            }
         } else {
             names.push(name);
         }
@@ -12,14 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use jj_lib::backend::Signature;
 use jj_lib::object_id::ObjectId;
 use jj_lib::repo::Repo;
+use jj_lib::settings::ConfigResultExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, user_error_with_hint, CommandError, ErrorCode};
 use crate::description_util::{
-    description_template_for_commit, edit_description, join_message_paragraphs,
+    description_template_for_commit, edit_description, join_message_paragraphs, parse_author,
 };
 use crate::ui::Ui;
 
@@ -28,6 +30,9 @@ use crate::ui::Ui;
 #[command(visible_aliases=&["ci"])]
 pub(crate) struct CommitArgs {
     /// Interactively choose which changes to include in the first commit
+    ///
+    /// If no changes are selected, the commit will be empty, unless
+    /// `ui.allow-empty-interactive-commit` is set to false.
     #[arg(short, long)]
     interactive: bool,
     /// Specify diff editor to be used (implies --interactive)
@@ -36,6 +41,18 @@ pub(crate) struct CommitArgs {
     /// The change description to use (don't open editor)
     #[arg(long = "message", short, value_name = "MESSAGE")]
     message_paragraphs: Vec<String>,
+    /// Reset the author of the first commit to the configured user
+    ///
+    /// This resets the author name, email, and timestamp.
+    #[arg(long)]
+    reset_author: bool,
+    /// Set the author of the first commit to the given name and email
+    ///
+    /// The value must be in the form "Name <email>". The committer is still
+    /// set to the configured user. The author timestamp is kept, unless
+    /// combined with `--reset-author`, which resets it.
+    #[arg(long, value_name = "AUTHOR", value_parser = parse_author)]
+    author: Option<(String, String)>,
     /// Put these paths in the first commit
     #[arg(value_hint = clap::ValueHint::AnyPath)]
     paths: Vec<String>,
@@ -49,9 +66,12 @@ pub(crate) fn cmd_commit(
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
 
-    let commit_id = workspace_command
-        .get_wc_commit_id()
-        .ok_or_else(|| user_error("This command requires a working copy"))?;
+    let commit_id = workspace_command.get_wc_commit_id().ok_or_else(|| {
+        user_error(
+            ErrorCode::NoWorkingCopy,
+            "This command requires a working copy",
+        )
+    })?;
     let commit = workspace_command.repo().store().get_commit(commit_id)?;
     let matcher = workspace_command
         .parse_file_patterns(&args.paths)?
@@ -78,7 +98,25 @@ new working-copy commit.
         Some(&instructions),
     )?;
     let middle_tree = tx.repo().store().get_root_tree(&tree_id)?;
-    if !args.paths.is_empty() && middle_tree.id() == base_tree.id() {
+    if diff_selector.is_interactive() && middle_tree.id() == base_tree.id() {
+        let allow_empty = command
+            .settings()
+            .config()
+            .get_bool("ui.allow-empty-interactive-commit")
+            .optional()?
+            .unwrap_or(true);
+        if !allow_empty {
+            return Err(user_error_with_hint(
+                ErrorCode::NothingSelected,
+                "No changes were selected, so nothing was committed",
+                "Set `ui.allow-empty-interactive-commit = true` to create an empty commit instead.",
+            ));
+        }
+        writeln!(
+            ui.warning_default(),
+            "No changes were selected, so the commit will be empty."
+        )?;
+    } else if !args.paths.is_empty() && middle_tree.id() == base_tree.id() {
         writeln!(
             ui.warning_default(),
             "The given paths do not match any file: {}",
@@ -102,12 +140,24 @@ new working-copy commit.
         edit_description(tx.base_repo(), &template, command.settings())?
     };
 
-    let new_commit = tx
+    let mut commit_builder = tx
         .mut_repo()
         .rewrite_commit(command.settings(), &commit)
         .set_tree_id(tree_id)
-        .set_description(description)
-        .write()?;
+        .set_description(description);
+    if args.reset_author {
+        let new_author = commit_builder.committer().clone();
+        commit_builder = commit_builder.set_author(new_author);
+    }
+    if let Some((name, email)) = &args.author {
+        let new_author = Signature {
+            name: name.clone(),
+            email: email.clone(),
+            timestamp: commit_builder.author().timestamp.clone(),
+        };
+        commit_builder = commit_builder.set_author(new_author);
+    }
+    let new_commit = commit_builder.write()?;
     let workspace_ids = tx
         .mut_repo()
         .view()
@@ -12,14 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt;
 use std::io::Write;
 
+use clap::ValueEnum as _;
 use tracing::instrument;
 
-use crate::cli_util::{get_new_config_file_path, run_ui_editor, CommandHelper};
-use crate::command_error::{config_error, user_error, CommandError};
+use crate::cli_util::{get_new_config_file_path, run_ui_editor, CommandHelper, TemplateFileArgs};
+use crate::command_error::{config_error, user_error, CommandError, ErrorCode};
 use crate::config::{
-    to_toml_value, write_config_value_to_file, AnnotatedValue, ConfigNamePathBuf, ConfigSource,
+    append_config_value_to_file, remove_config_value_from_file, to_toml_value,
+    write_config_value_to_file, AnnotatedValue, ConfigNamePathBuf, ConfigSource,
 };
 use crate::generic_templater::GenericTemplateLanguage;
 use crate::template_builder::TemplateLanguage as _;
@@ -73,6 +76,8 @@ pub(crate) enum ConfigCommand {
     Path(ConfigPathArgs),
     #[command(visible_alias("s"))]
     Set(ConfigSetArgs),
+    #[command(visible_alias("u"))]
+    Unset(ConfigUnsetArgs),
 }
 
 /// List variables set in config file, along with their values.
@@ -89,7 +94,12 @@ pub(crate) struct ConfigListArgs {
     pub include_overridden: bool,
     #[command(flatten)]
     pub level: ConfigLevelArgs,
-    // TODO(#1047): Support --show-origin using LayeredConfigs.
+    /// Prefix each variable with where it's defined
+    ///
+    /// The origin is one of `default`, `env`, `user`, `repo`, or `cli` (for
+    /// `--config-toml`).
+    #[arg(long)]
+    pub show_origin: bool,
     /// Render each variable using the given template
     ///
     /// The following keywords are defined:
@@ -97,10 +107,14 @@ pub(crate) struct ConfigListArgs {
     /// * `name: String`: Config name.
     /// * `value: String`: Serialized value in TOML syntax.
     /// * `overridden: Boolean`: True if the value is shadowed by other.
+    /// * `source: String`: Where the value is defined: `default`, `env`,
+    ///   `user`, `repo`, or `cli`.
     ///
     /// For the syntax, see https://github.com/martinvonz/jj/blob/main/docs/templates.md
     #[arg(long, short = 'T', verbatim_doc_comment)]
     template: Option<String>,
+    #[command(flatten)]
+    template_file: TemplateFileArgs,
 }
 
 /// Get the value of a given config option.
@@ -124,10 +138,107 @@ pub(crate) struct ConfigGetArgs {
 pub(crate) struct ConfigSetArgs {
     #[arg(required = true)]
     name: ConfigNamePathBuf,
-    #[arg(required = true)]
+    #[arg(required = true, allow_negative_numbers = true)]
     value: String,
     #[command(flatten)]
     level: ConfigLevelArgs,
+    /// How to interpret the value
+    ///
+    /// By default, the value is parsed as TOML, falling back to a string if it
+    /// isn't valid TOML.
+    #[arg(long = "type", value_enum, default_value_t)]
+    value_type: ConfigValueType,
+    /// Append the value to the array instead of replacing it
+    ///
+    /// The array is created if the option doesn't exist yet.
+    #[arg(long)]
+    append: bool,
+}
+
+/// Type of the value passed to `jj config set`.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum ConfigValueType {
+    /// Infer the type from the TOML syntax
+    #[default]
+    Auto,
+    Bool,
+    Int,
+    String,
+    /// Parse the value as JSON (e.g. to pass an array or a table)
+    Json,
+}
+
+impl ConfigValueType {
+    fn parse_value(self, value_str: &str) -> Result<toml_edit::Value, CommandError> {
+        let invalid_value_error = |err: &dyn fmt::Display| {
+            user_error(
+                ErrorCode::InvalidArgument,
+                format!("Invalid {self} value: {err}"),
+            )
+        };
+        match self {
+            // Interpret value as string if it can't be parsed as a TOML value.
+            ConfigValueType::Auto => Ok(value_str.parse().unwrap_or_else(|_| value_str.into())),
+            ConfigValueType::Bool => value_str
+                .parse::<bool>()
+                .map(Into::into)
+                .map_err(|err| invalid_value_error(&err)),
+            ConfigValueType::Int => value_str
+                .parse::<i64>()
+                .map(Into::into)
+                .map_err(|err| invalid_value_error(&err)),
+            ConfigValueType::String => Ok(value_str.into()),
+            ConfigValueType::Json => {
+                let value =
+                    serde_json::from_str(value_str).map_err(|err| invalid_value_error(&err))?;
+                json_to_toml_value(&value).map_err(|err| invalid_value_error(&err))
+            }
+        }
+    }
+}
+
+impl fmt::Display for ConfigValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.to_possible_value().expect("no skipped variants");
+        f.write_str(value.get_name())
+    }
+}
+
+fn json_to_toml_value(value: &serde_json::Value) -> Result<toml_edit::Value, String> {
+    match value {
+        serde_json::Value::Null => Err("null can't be represented in TOML".to_owned()),
+        serde_json::Value::Bool(v) => Ok((*v).into()),
+        serde_json::Value::Number(v) => {
+            if let Some(v) = v.as_i64() {
+                Ok(v.into())
+            } else if v.is_u64() {
+                // TOML integers are 64-bit signed
+                Err(format!("number {v} is out of range"))
+            } else if let Some(v) = v.as_f64() {
+                Ok(v.into())
+            } else {
+                Err(format!("number {v} is out of range"))
+            }
+        }
+        serde_json::Value::String(v) => Ok(v.into()),
+        serde_json::Value::Array(array) => array.iter().map(json_to_toml_value).collect(),
+        serde_json::Value::Object(table) => table
+            .iter()
+            .map(|(k, v)| Ok((k, json_to_toml_value(v)?)))
+            .collect(),
+    }
+}
+
+/// Update config file to unset the given option.
+///
+/// If the option is a table, you will be asked whether to remove the entire
+/// table. Unsetting an option that isn't set in the config file does nothing.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct ConfigUnsetArgs {
+    #[arg(required = true)]
+    name: ConfigNamePathBuf,
+    #[command(flatten)]
+    level: ConfigLevelArgs,
 }
 
 /// Start an editor on a jj config file.
@@ -163,6 +274,7 @@ pub(crate) fn cmd_config(
         ConfigCommand::List(args) => cmd_config_list(ui, command, args),
         ConfigCommand::Path(args) => cmd_config_path(ui, command, args),
         ConfigCommand::Set(args) => cmd_config_set(ui, command, args),
+        ConfigCommand::Unset(args) => cmd_config_unset(ui, command, args),
     }
 }
 
@@ -186,6 +298,10 @@ fn config_template_language() -> GenericTemplateLanguage<'static, AnnotatedValue
         let out_property = self_property.map(|annotated| annotated.is_overridden);
         Ok(L::wrap_boolean(out_property))
     });
+    language.add_keyword("source", |self_property| {
+        let out_property = self_property.map(|annotated| annotated.source.to_string());
+        Ok(L::wrap_string(out_property))
+    });
     language
 }
 
@@ -197,16 +313,15 @@ pub(crate) fn cmd_config_list(
 ) -> Result<(), CommandError> {
     let template = {
         let language = config_template_language();
-        let text = match &args.template {
-            Some(value) => value.to_owned(),
-            None => command
-                .settings()
-                .config()
-                .get_string("templates.config_list")?,
-        };
-        command
-            .parse_template(ui, &language, &text, GenericTemplateLanguage::wrap_self)?
-            .labeled("config_list")
+        let text = args.template_file.template_text(
+            command,
+            args.template.as_deref(),
+            "templates.config_list",
+        )?;
+        text.parse(|text| {
+            command.parse_template(ui, &language, text, GenericTemplateLanguage::wrap_self)
+        })?
+        .labeled("config_list")
     };
 
     ui.request_pager();
@@ -230,6 +345,13 @@ pub(crate) fn cmd_config_list(
             continue;
         }
 
+        if args.show_origin {
+            formatter.with_label("config_list", |formatter| {
+                formatter.with_label("origin", |formatter| {
+                    write!(formatter, "{}: ", annotated.source)
+                })
+            })?;
+        }
         template.format(&annotated, formatter.as_mut())?;
         wrote_values = true;
     }
@@ -291,12 +413,52 @@ pub(crate) fn cmd_config_set(
 ) -> Result<(), CommandError> {
     let config_path = get_new_config_file_path(&args.level.expect_source_kind(), command)?;
     if config_path.is_dir() {
-        return Err(user_error(format!(
-            "Can't set config in path {path} (dirs not supported)",
-            path = config_path.display()
-        )));
+        return Err(user_error(
+            ErrorCode::Unsupported,
+            format!(
+                "Can't set config in path {path} (dirs not supported)",
+                path = config_path.display()
+            ),
+        ));
+    }
+    let value = args.value_type.parse_value(&args.value)?;
+    if args.append {
+        append_config_value_to_file(&args.name, value, &config_path)
+    } else {
+        write_config_value_to_file(&args.name, value, &config_path)
     }
-    write_config_value_to_file(&args.name, &args.value, &config_path)
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_config_unset(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ConfigUnsetArgs,
+) -> Result<(), CommandError> {
+    let config_path = get_new_config_file_path(&args.level.expect_source_kind(), command)?;
+    if config_path.is_dir() {
+        return Err(user_error(
+            ErrorCode::Unsupported,
+            format!(
+                "Can't unset config in path {path} (dirs not supported)",
+                path = config_path.display()
+            ),
+        ));
+    }
+    let removed = remove_config_value_from_file(&args.name, &config_path, || {
+        Ok(ui.prompt_yes_no(
+            &format!("{} is a table. Remove the entire table?", args.name),
+            Some(false),
+        )?)
+    })?;
+    if !removed {
+        writeln!(
+            ui.warning_default(),
+            "Key {} doesn't exist; nothing to unset",
+            args.name
+        )?;
+    }
+    Ok(())
 }
 
 #[instrument(skip_all)]
@@ -319,9 +481,10 @@ pub(crate) fn cmd_config_path(
     writeln!(
         ui.stdout(),
         "{}",
-        config_path
-            .to_str()
-            .ok_or_else(|| user_error("The config path is not valid UTF-8"))?
+        config_path.to_str().ok_or_else(|| user_error(
+            ErrorCode::InvalidPath,
+            "The config path is not valid UTF-8"
+        ))?
     )?;
     Ok(())
 }
@@ -19,7 +19,7 @@ use jj_lib::default_index::{AsCompositeIndex as _, DefaultReadonlyIndex};
 use jj_lib::op_walk;
 
 use crate::cli_util::CommandHelper;
-use crate::command_error::{internal_error, user_error, CommandError};
+use crate::command_error::{internal_error, user_error, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 /// Show commit index stats
@@ -58,10 +58,13 @@ pub fn cmd_debug_index(
             writeln!(ui.stdout(), "    Name: {}", level.name.as_ref().unwrap())?;
         }
     } else {
-        return Err(user_error(format!(
-            "Cannot get stats for indexes of type '{}'",
-            index_store.name()
-        )));
+        return Err(user_error(
+            ErrorCode::Unsupported,
+            format!(
+                "Cannot get stats for indexes of type '{}'",
+                index_store.name()
+            ),
+        ));
     }
     Ok(())
 }
@@ -42,7 +42,7 @@ use self::tree::{cmd_debug_tree, DebugTreeArgs};
 use self::watchman::{cmd_debug_watchman, DebugWatchmanCommand};
 use self::working_copy::{cmd_debug_working_copy, DebugWorkingCopyArgs};
 use crate::cli_util::CommandHelper;
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 /// Low-level commands not intended for users
@@ -85,6 +85,10 @@ pub fn cmd_debug(
 }
 
 fn check_local_disk_wc(x: &dyn Any) -> Result<&LocalWorkingCopy, CommandError> {
-    x.downcast_ref()
-        .ok_or_else(|| user_error("This command requires a standard local-disk working copy"))
+    x.downcast_ref().ok_or_else(|| {
+        user_error(
+            ErrorCode::Unsupported,
+            "This command requires a standard local-disk working copy",
+        )
+    })
 }
@@ -19,7 +19,7 @@ use jj_lib::default_index::{AsCompositeIndex as _, DefaultIndexStore};
 use jj_lib::op_walk;
 
 use crate::cli_util::CommandHelper;
-use crate::command_error::{internal_error, user_error, CommandError};
+use crate::command_error::{internal_error, user_error, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 /// Rebuild commit index
@@ -48,10 +48,10 @@ pub fn cmd_debug_reindex(
             default_index.as_composite().stats().num_commits
         )?;
     } else {
-        return Err(user_error(format!(
-            "Cannot reindex indexes of type '{}'",
-            index_store.name()
-        )));
+        return Err(user_error(
+            ErrorCode::Unsupported,
+            format!("Cannot reindex indexes of type '{}'", index_store.name()),
+        ));
     }
     Ok(())
 }
@@ -20,14 +20,35 @@ use crate::ui::Ui;
 
 /// Trigger a snapshot in the op log
 #[derive(clap::Args, Clone, Debug)]
-pub struct DebugSnapshotArgs {}
+pub struct DebugSnapshotArgs {
+    /// Only snapshot the paths matching these filesets
+    ///
+    /// The previously recorded state is kept for the other paths, so changes
+    /// to them (including deletions) aren't recorded until the next full
+    /// snapshot.
+    #[arg(long, value_name = "FILESETS", num_args = 1..)]
+    paths: Vec<String>,
+}
 
 pub fn cmd_debug_snapshot(
     ui: &mut Ui,
     command: &CommandHelper,
-    _args: &DebugSnapshotArgs,
+    args: &DebugSnapshotArgs,
 ) -> Result<(), CommandError> {
-    // workspace helper will snapshot as needed
-    command.workspace_helper(ui)?;
+    if args.paths.is_empty() {
+        // workspace helper will snapshot as needed
+        command.workspace_helper(ui)?;
+        return Ok(());
+    }
+    let mut workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    let matcher = workspace_command
+        .parse_file_patterns(&args.paths)?
+        .to_matcher();
+    workspace_command.maybe_snapshot_matching(ui, matcher.as_ref())?;
+    writeln!(
+        ui.warning_default(),
+        "Only the specified paths were snapshotted. Changes to other paths will be recorded by \
+         the next snapshot."
+    )?;
     Ok(())
 }
@@ -21,7 +21,7 @@ use jj_lib::repo::Repo;
 use jj_lib::repo_path::RepoPathBuf;
 
 use crate::cli_util::{CommandHelper, RevisionArg};
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 /// List the recursive entries of a tree.
@@ -44,8 +44,8 @@ pub fn cmd_debug_tree(
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper(ui)?;
     let tree = if let Some(tree_id_hex) = &args.id {
-        let tree_id =
-            TreeId::try_from_hex(tree_id_hex).map_err(|_| user_error("Invalid tree id"))?;
+        let tree_id = TreeId::try_from_hex(tree_id_hex)
+            .map_err(|_| user_error(ErrorCode::InvalidArgument, "Invalid tree id"))?;
         let dir = if let Some(dir_str) = &args.dir {
             workspace_command.parse_file_path(dir_str)?
         } else {
@@ -25,7 +25,7 @@ use jj_lib::fsmonitor::{FsmonitorSettings, WatchmanConfig};
 use jj_lib::local_working_copy::LocalWorkingCopy;
 
 use crate::cli_util::CommandHelper;
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 #[derive(Subcommand, Clone, Debug)]
@@ -76,9 +76,12 @@ pub fn cmd_debug_watchman(
                     WatchmanConfig::default()
                 }
                 other_fsmonitor => {
-                    return Err(user_error(format!(
-                        r"This command does not support the currently enabled filesystem monitor: {other_fsmonitor:?}."
-                    )))
+                    return Err(user_error(
+                        ErrorCode::Unsupported,
+                        format!(
+                            r"This command does not support the currently enabled filesystem monitor: {other_fsmonitor:?}."
+                        ),
+                    ))
                 }
             };
             let wc = check_local_disk_wc(workspace_command.working_copy().as_any())?;
@@ -113,6 +116,7 @@ pub fn cmd_debug_watchman(
                 locked_ws.locked_wc().as_any_mut().downcast_mut()
             else {
                 return Err(user_error(
+                    ErrorCode::Unsupported,
                     "This command requires a standard local-disk working copy",
                 ));
             };
@@ -131,12 +135,17 @@ pub fn cmd_debug_watchman(
     _subcommand: &DebugWatchmanCommand,
 ) -> Result<(), CommandError> {
     Err(user_error(
+        ErrorCode::Unsupported,
         "Cannot query Watchman because jj was not compiled with the `watchman` feature",
     ))
 }
 
 #[cfg(feature = "watchman")]
 fn check_local_disk_wc(x: &dyn Any) -> Result<&LocalWorkingCopy, CommandError> {
-    x.downcast_ref()
-        .ok_or_else(|| user_error("This command requires a standard local-disk working copy"))
+    x.downcast_ref().ok_or_else(|| {
+        user_error(
+            ErrorCode::Unsupported,
+            "This command requires a standard local-disk working copy",
+        )
+    })
 }
@@ -12,15 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::io::{self, Read, Write};
 
+use itertools::Itertools;
+use jj_lib::backend::Signature;
+use jj_lib::commit::CommitIteratorExt;
 use jj_lib::object_id::ObjectId;
 use tracing::instrument;
 
 use crate::cli_util::{CommandHelper, RevisionArg};
-use crate::command_error::CommandError;
+use crate::command_error::{user_error_with_hint, CommandError, ErrorCode};
 use crate::description_util::{
-    description_template_for_describe, edit_description, join_message_paragraphs,
+    description_template_for_describe, edit_description, join_message_paragraphs, parse_author,
 };
 use crate::ui::Ui;
 
@@ -28,26 +32,45 @@ use crate::ui::Ui;
 ///
 /// Starts an editor to let you edit the description of a change. The editor
 /// will be $EDITOR, or `pico` if that's not defined (`Notepad` on Windows).
+///
+/// If multiple revisions are given, the new description or other metadata
+/// must be specified without an editor, e.g. with `--message` or `--no-edit`.
 #[derive(clap::Args, Clone, Debug)]
 #[command(visible_aliases = &["desc"])]
 pub(crate) struct DescribeArgs {
-    /// The revision whose description to edit
+    /// The revision(s) whose description to edit
     #[arg(default_value = "@")]
-    revision: RevisionArg,
+    revisions: Vec<RevisionArg>,
     /// Ignored (but lets you pass `-r` for consistency with other commands)
-    #[arg(short = 'r', hide = true)]
-    unused_revision: bool,
+    #[arg(short = 'r', hide = true, action = clap::ArgAction::Count)]
+    unused_revision: u8,
     /// The change description to use (don't open editor)
+    ///
+    /// Pass an empty string (`-m ""`) to clear the description.
     #[arg(long = "message", short, value_name = "MESSAGE")]
     message_paragraphs: Vec<String>,
     /// Read the change description from stdin
     #[arg(long)]
     stdin: bool,
-    /// Don't open an editor
+    /// Don't open an editor, and keep the existing description
     ///
     /// This is mainly useful in combination with e.g. `--reset-author`.
-    #[arg(long)]
+    #[arg(long, conflicts_with_all = ["message_paragraphs", "stdin", "reuse_message"])]
     no_edit: bool,
+    /// Use the description of the given revision (don't open editor)
+    #[arg(
+        long,
+        value_name = "REVISION",
+        conflicts_with_all = ["message_paragraphs", "stdin", "reedit_message"]
+    )]
+    reuse_message: Option<RevisionArg>,
+    /// Open an editor with the description of the given revision
+    #[arg(
+        long,
+        value_name = "REVISION",
+        conflicts_with_all = ["message_paragraphs", "stdin", "no_edit"]
+    )]
+    reedit_message: Option<RevisionArg>,
     /// Reset the author to the configured user
     ///
     /// This resets the author name, email, and timestamp.
@@ -58,6 +81,20 @@ pub(crate) struct DescribeArgs {
     /// $ JJ_USER='Foo Bar' JJ_EMAIL=foo@bar.com jj describe --reset-author
     #[arg(long)]
     reset_author: bool,
+    /// Set the author to the given name and email
+    ///
+    /// The value must be in the form "Name <email>". The committer is still
+    /// set to the configured user. The author timestamp is kept, unless
+    /// combined with `--reset-author`, which resets it.
+    ///
+    /// $ jj describe --author 'Foo Bar <foo@bar.com>'
+    ///
+    /// To change the author of several commits at once, pass a revset and
+    /// `--no-edit`:
+    ///
+    /// $ jj describe -r 'mutable()' --no-edit --author 'Foo Bar <foo@bar.com>'
+    #[arg(long, value_name = "AUTHOR", value_parser = parse_author)]
+    author: Option<(String, String)>,
 }
 
 #[instrument(skip_all)]
@@ -67,35 +104,112 @@ pub(crate) fn cmd_describe(
     args: &DescribeArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
-    let commit = workspace_command.resolve_single_rev(&args.revision)?;
-    workspace_command.check_rewritable([commit.id()])?;
-    let description = if args.stdin {
+    let commits: Vec<_> = workspace_command
+        .parse_union_revsets(&args.revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    if commits.is_empty() {
+        writeln!(ui.status(), "No revisions to describe.")?;
+        return Ok(());
+    }
+    workspace_command.check_rewritable(commits.iter().ids())?;
+    // `None` means that each commit keeps its own description.
+    let shared_description = if args.stdin {
         let mut buffer = String::new();
         io::stdin().read_to_string(&mut buffer).unwrap();
-        buffer
+        Some(buffer)
     } else if !args.message_paragraphs.is_empty() {
-        join_message_paragraphs(&args.message_paragraphs)
+        Some(join_message_paragraphs(&args.message_paragraphs))
+    } else if let Some(source) = &args.reuse_message {
+        let source_commit = workspace_command.resolve_single_rev(source)?;
+        Some(source_commit.description().to_owned())
     } else if args.no_edit {
-        commit.description().to_owned()
+        None
     } else {
-        let template =
-            description_template_for_describe(ui, command.settings(), &workspace_command, &commit)?;
-        edit_description(workspace_command.repo(), &template, command.settings())?
+        let [commit] = commits.as_slice() else {
+            return Err(user_error_with_hint(
+                ErrorCode::InvalidArgument,
+                "Cannot edit the descriptions of multiple revisions in an editor",
+                "Use --message, --stdin, or --reuse-message to set the description, or --no-edit \
+                 to keep it",
+            ));
+        };
+        let source_commit = match &args.reedit_message {
+            Some(source) => workspace_command.resolve_single_rev(source)?,
+            None => commit.clone(),
+        };
+        let template = description_template_for_describe(
+            ui,
+            command.settings(),
+            &workspace_command,
+            commit,
+            source_commit.description(),
+        )?;
+        Some(edit_description(
+            workspace_command.repo(),
+            &template,
+            command.settings(),
+        )?)
     };
-    if description == *commit.description() && !args.reset_author {
+    let new_descriptions: HashMap<_, _> = commits
+        .iter()
+        .filter_map(|commit| {
+            let description = shared_description
+                .clone()
+                .unwrap_or_else(|| commit.description().to_owned());
+            let changed =
+                description != *commit.description() || args.reset_author || args.author.is_some();
+            changed.then(|| (commit.id().clone(), description))
+        })
+        .collect();
+    if new_descriptions.is_empty() {
         writeln!(ui.status(), "Nothing changed.")?;
-    } else {
-        let mut tx = workspace_command.start_transaction();
-        let mut commit_builder = tx
-            .mut_repo()
-            .rewrite_commit(command.settings(), &commit)
-            .set_description(description);
-        if args.reset_author {
-            let new_author = commit_builder.committer().clone();
-            commit_builder = commit_builder.set_author(new_author);
-        }
-        commit_builder.write()?;
-        tx.finish(ui, format!("describe commit {}", commit.id().hex()))?;
+        return Ok(());
+    }
+
+    let mut tx = workspace_command.start_transaction();
+    let mut num_rebased = 0;
+    let roots = commits
+        .iter()
+        .ids()
+        .filter(|id| new_descriptions.contains_key(id))
+        .cloned()
+        .collect();
+    tx.mut_repo()
+        .transform_descendants(command.settings(), roots, |rewriter| {
+            let Some(description) = new_descriptions.get(rewriter.old_commit().id()) else {
+                if rewriter.parents_changed() {
+                    rewriter.rebase(command.settings())?.write()?;
+                    num_rebased += 1;
+                }
+                return Ok(());
+            };
+            let mut commit_builder = rewriter
+                .reparent(command.settings())?
+                .set_description(description);
+            if args.reset_author {
+                let new_author = commit_builder.committer().clone();
+                commit_builder = commit_builder.set_author(new_author);
+            }
+            if let Some((name, email)) = &args.author {
+                let new_author = Signature {
+                    name: name.clone(),
+                    email: email.clone(),
+                    timestamp: commit_builder.author().timestamp.clone(),
+                };
+                commit_builder = commit_builder.set_author(new_author);
+            }
+            commit_builder.write()?;
+            Ok(())
+        })?;
+    if num_rebased > 0 {
+        writeln!(ui.status(), "Rebased {num_rebased} descendant commits")?;
     }
+    let tx_description = if let [commit] = commits.as_slice() {
+        format!("describe commit {}", commit.id().hex())
+    } else {
+        format!("describe {} commits", new_descriptions.len())
+    };
+    tx.finish(ui, tx_description)?;
     Ok(())
 }
@@ -12,11 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use itertools::Itertools as _;
+use jj_lib::backend::TreeValue;
+use jj_lib::fileset::FilesetExpression;
+use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPathUiConverter;
+use jj_lib::rewrite::merge_commit_trees;
 use tracing::instrument;
 
 use crate::cli_util::{print_unmatched_explicit_paths, CommandHelper, RevisionArg};
-use crate::command_error::CommandError;
-use crate::diff_util::DiffFormatArgs;
+use crate::command_error::{user_error, CommandError, ErrorCode};
+use crate::diff_util::{
+    diff_formats_for, diff_render_error_with_tool_hint, show_whitespace_check,
+    whitespace_checks_from_settings, DiffFormatArgs, DiffRenderer,
+};
 use crate::ui::Ui;
 
 /// Compare file contents between two revisions
@@ -30,6 +39,29 @@ use crate::ui::Ui;
 /// given revisions. If either is left out, it defaults to the working-copy
 /// commit. For example, `jj diff --from main` shows the changes from "main"
 /// (perhaps a branch name) to the working-copy commit.
+///
+/// The `--from` and `--to` revisions don't need to be related. For example, to
+/// see how the two parents of a merge commit differ from each other, pass one
+/// parent to `--from` and the other to `--to`. Conflicted files in either
+/// revision are compared as their contents with conflict markers.
+///
+/// With the `--merge-base` option, shows the changes from the merge base of the
+/// given revision and the `--to` revision (the working-copy commit by default).
+/// For example, `jj diff --merge-base main` shows all changes made on the
+/// current line of development since it forked from "main". If there are
+/// several merge bases, they will be merged.
+///
+/// With the `--path-pair` option, compares the contents of two files in the
+/// given revision instead.
+///
+/// With the `--relative` option, only changes in the given directory (the
+/// current directory by default) are shown, and paths are displayed relative
+/// to it. This also applies to the file headers in the Git format.
+///
+/// With the `--check` option, no diff is shown. Instead, added lines are
+/// checked for whitespace problems, such as trailing whitespace, and the
+/// command fails if any are found. The problems to check for are configured
+/// by the `diff.whitespace` setting.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct DiffArgs {
     /// Show changes in this revision, compared to its parent(s)
@@ -45,9 +77,46 @@ pub(crate) struct DiffArgs {
     /// Show changes to this revision
     #[arg(long, conflicts_with = "revision")]
     to: Option<RevisionArg>,
+    /// Show changes from the merge base of this revision and the `--to`
+    /// revision
+    #[arg(long, value_name = "REVISION", conflicts_with_all = ["revision", "from"])]
+    merge_base: Option<RevisionArg>,
     /// Restrict the diff to these paths
     #[arg(value_hint = clap::ValueHint::AnyPath)]
     paths: Vec<String>,
+    /// Compare the contents of two files in the revision
+    ///
+    /// The diff is shown under the name of the second file, or the first one if
+    /// the second file doesn't exist. If one of the files doesn't exist, the
+    /// other file's contents are shown as added or removed. External diff
+    /// tools aren't supported.
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["FROM", "TO"],
+        value_hint = clap::ValueHint::AnyPath,
+        conflicts_with_all = ["from", "to", "merge_base", "paths"]
+    )]
+    path_pair: Option<Vec<String>>,
+    /// Only show changes in this directory, with paths relative to it
+    ///
+    /// Defaults to the current directory if no directory is given.
+    #[arg(
+        long,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = ".",
+        value_name = "DIR",
+        value_hint = clap::ValueHint::DirPath,
+        conflicts_with = "path_pair"
+    )]
+    relative: Option<String>,
+    /// Check added lines for whitespace problems instead of showing the diff
+    ///
+    /// Each problem is reported as `path:line`, and the command exits with an
+    /// error if any were found. Binary files aren't checked.
+    #[arg(long, conflicts_with_all = ["path_pair", "short-format", "long-format"])]
+    check: bool,
     #[command(flatten)]
     format: DiffFormatArgs,
 }
@@ -59,9 +128,73 @@ pub(crate) fn cmd_diff(
     args: &DiffArgs,
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper(ui)?;
+    if let Some(path_pair) = &args.path_pair {
+        let commit = workspace_command
+            .resolve_single_rev(args.revision.as_ref().unwrap_or(&RevisionArg::AT))?;
+        let tree = commit.tree()?;
+        let [from_path, to_path] =
+            [&path_pair[0], &path_pair[1]].map(|path| workspace_command.parse_file_path(path));
+        let (from_path, to_path) = (from_path?, to_path?);
+        let from_value = tree.path_value(&from_path)?;
+        let to_value = tree.path_value(&to_path)?;
+        if from_value.is_absent() && to_value.is_absent() {
+            return Err(user_error(
+                ErrorCode::NoSuchPath,
+                format!(
+                    "Neither {} nor {} exists in this revision",
+                    workspace_command.format_file_path(&from_path),
+                    workspace_command.format_file_path(&to_path)
+                ),
+            ));
+        }
+        for (path, value) in [(&from_path, &from_value), (&to_path, &to_value)] {
+            if value
+                .iter()
+                .flatten()
+                .any(|value| matches!(value, TreeValue::Tree(_)))
+            {
+                return Err(user_error(
+                    ErrorCode::InvalidPath,
+                    format!(
+                        "{} is a directory",
+                        workspace_command.format_file_path(path)
+                    ),
+                ));
+            }
+        }
+        // Show both contents at the same path so they're diffed against each other
+        let diff_path = if to_value.is_absent() {
+            &from_path
+        } else {
+            &to_path
+        };
+        let diff_renderer = workspace_command.diff_renderer_for(&args.format)?;
+        ui.request_pager();
+        diff_renderer.show_value_diff(
+            ui,
+            ui.diff_stdout_formatter().as_mut(),
+            diff_path,
+            &from_value,
+            &to_value,
+        )?;
+        return Ok(());
+    }
     let from_tree;
     let to_tree;
-    if args.from.is_some() || args.to.is_some() {
+    if let Some(merge_base) = &args.merge_base {
+        let to =
+            workspace_command.resolve_single_rev(args.to.as_ref().unwrap_or(&RevisionArg::AT))?;
+        let other = workspace_command.resolve_single_rev(merge_base)?;
+        let repo = workspace_command.repo();
+        let base_commits: Vec<_> = repo
+            .index()
+            .common_ancestors(&[to.id().clone()], &[other.id().clone()])
+            .iter()
+            .map(|id| repo.store().get_commit(id))
+            .try_collect()?;
+        from_tree = merge_commit_trees(repo.as_ref(), &base_commits)?;
+        to_tree = to.tree()?;
+    } else if args.from.is_some() || args.to.is_some() {
         let from =
             workspace_command.resolve_single_rev(args.from.as_ref().unwrap_or(&RevisionArg::AT))?;
         from_tree = from.tree()?;
@@ -75,16 +208,74 @@ pub(crate) fn cmd_diff(
         to_tree = commit.tree()?
     }
     let fileset_expression = workspace_command.parse_file_patterns(&args.paths)?;
-    let matcher = fileset_expression.to_matcher();
-    let diff_renderer = workspace_command.diff_renderer_for(&args.format)?;
+    let relative_root = args
+        .relative
+        .as_ref()
+        .map(|dir| workspace_command.parse_file_path(dir))
+        .transpose()?;
+    let matcher = match &relative_root {
+        Some(root) => fileset_expression
+            .clone()
+            .intersection(FilesetExpression::prefix_path(root.clone()))
+            .to_matcher(),
+        None => fileset_expression.to_matcher(),
+    };
+    let relative_path_converter = relative_root.as_ref().map(|root| {
+        let workspace_root = workspace_command.workspace_root();
+        RepoPathUiConverter::Fs {
+            cwd: root.to_fs_path(workspace_root),
+            base: workspace_root.clone(),
+        }
+    });
+    if args.check {
+        let checks = whitespace_checks_from_settings(command.settings())?;
+        let num_problems = show_whitespace_check(
+            ui.stdout_formatter().as_mut(),
+            workspace_command.repo().store(),
+            from_tree.diff_stream(&to_tree, matcher.as_ref()),
+            relative_path_converter
+                .as_ref()
+                .unwrap_or_else(|| workspace_command.path_converter()),
+            &checks,
+        )?;
+        print_unmatched_explicit_paths(
+            ui,
+            &workspace_command,
+            &fileset_expression,
+            [&from_tree, &to_tree],
+        )?;
+        if num_problems > 0 {
+            return Err(user_error(
+                ErrorCode::WhitespaceProblems,
+                format!(
+                    "Found whitespace problems in {num_problems} {}",
+                    if num_problems == 1 { "line" } else { "lines" }
+                ),
+            ));
+        }
+        return Ok(());
+    }
+    let diff_renderer =
+        if let (Some(root), Some(path_converter)) = (&relative_root, &relative_path_converter) {
+            DiffRenderer::new(
+                workspace_command.repo().as_ref(),
+                path_converter,
+                diff_formats_for(command.settings(), &args.format)?,
+            )
+            .with_relative_root(root.clone())
+        } else {
+            workspace_command.diff_renderer_for(&args.format)?
+        };
     ui.request_pager();
-    diff_renderer.show_diff(
-        ui,
-        ui.stdout_formatter().as_mut(),
-        &from_tree,
-        &to_tree,
-        matcher.as_ref(),
-    )?;
+    diff_renderer
+        .show_diff(
+            ui,
+            ui.diff_stdout_formatter().as_mut(),
+            &from_tree,
+            &to_tree,
+            matcher.as_ref(),
+        )
+        .map_err(|err| diff_render_error_with_tool_hint(command.settings(), err))?;
     print_unmatched_explicit_paths(
         ui,
         &workspace_command,
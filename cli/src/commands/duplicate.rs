@@ -21,10 +21,14 @@ use jj_lib::repo::Repo;
 use tracing::instrument;
 
 use crate::cli_util::{short_commit_hash, CommandHelper, RevisionArg};
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 /// Create a new change with the same content as an existing one
+///
+/// By default, the duplicates get new change ids. With `--reuse-change-ids`,
+/// they keep the change ids of the originals instead, which makes those
+/// changes divergent.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct DuplicateArgs {
     /// The revision(s) to duplicate
@@ -33,6 +37,12 @@ pub(crate) struct DuplicateArgs {
     /// Ignored (but lets you pass `-r` for consistency with other commands)
     #[arg(short = 'r', hide = true, action = clap::ArgAction::Count)]
     unused_revision: u8,
+    /// Give the duplicates the change ids of the originals
+    #[arg(long, conflicts_with = "new_change_ids")]
+    reuse_change_ids: bool,
+    /// Give the duplicates new change ids (default)
+    #[arg(long)]
+    new_change_ids: bool,
 }
 
 #[instrument(skip_all)]
@@ -51,7 +61,10 @@ pub(crate) fn cmd_duplicate(
         return Ok(());
     }
     if to_duplicate.last() == Some(workspace_command.repo().store().root_commit_id()) {
-        return Err(user_error("Cannot duplicate the root commit"));
+        return Err(user_error(
+            ErrorCode::ImmutableCommit,
+            "Cannot duplicate the root commit",
+        ));
     }
     let mut duplicated_old_to_new: IndexMap<&CommitId, Commit> = IndexMap::new();
 
@@ -69,11 +82,27 @@ pub(crate) fn cmd_duplicate(
             .iter()
             .map(|id| duplicated_old_to_new.get(id).map_or(id, |c| c.id()).clone())
             .collect();
-        let new_commit = mut_repo
-            .rewrite_commit(command.settings(), &original_commit)
-            .generate_new_change_id()
-            .set_parents(new_parents)
-            .write()?;
+        let new_commit = if args.reuse_change_ids {
+            // Not written as a rewrite of the original commit, which would
+            // abandon it.
+            mut_repo
+                .new_commit(
+                    command.settings(),
+                    new_parents,
+                    original_commit.tree_id().clone(),
+                )
+                .set_change_id(original_commit.change_id().clone())
+                .set_predecessors(vec![original_commit.id().clone()])
+                .set_description(original_commit.description())
+                .set_author(original_commit.author().clone())
+                .write()?
+        } else {
+            mut_repo
+                .rewrite_commit(command.settings(), &original_commit)
+                .generate_new_change_id()
+                .set_parents(new_parents)
+                .write()?
+        };
         duplicated_old_to_new.insert(original_commit_id, new_commit);
     }
 
@@ -83,6 +112,14 @@ pub(crate) fn cmd_duplicate(
             tx.write_commit_summary(formatter.as_mut(), new_commit)?;
             writeln!(formatter)?;
         }
+        if args.reuse_change_ids {
+            writeln!(
+                formatter,
+                "The duplicates share change ids with the originals, so {} changes are now \
+                 divergent.",
+                duplicated_old_to_new.len()
+            )?;
+        }
     }
     tx.finish(ui, format!("duplicate {} commit(s)", to_duplicate.len()))?;
     Ok(())
@@ -18,7 +18,7 @@ use jj_lib::object_id::ObjectId;
 use tracing::instrument;
 
 use crate::cli_util::{print_unmatched_explicit_paths, CommandHelper, RevisionArg};
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
@@ -91,10 +91,13 @@ pub(crate) fn cmd_file_chmod(
     for (repo_path, result) in tree.entries_matching(matcher.as_ref()) {
         let tree_value = result?;
         let user_error_with_path = |msg: &str| {
-            user_error(format!(
-                "{msg} at '{}'.",
-                tx.base_workspace_helper().format_file_path(&repo_path)
-            ))
+            user_error(
+                ErrorCode::Unsupported,
+                format!(
+                    "{msg} at '{}'.",
+                    tx.base_workspace_helper().format_file_path(&repo_path)
+                ),
+            )
         };
         let all_files = tree_value
             .adds()
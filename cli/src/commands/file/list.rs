@@ -12,25 +12,112 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io::Write;
+use std::io;
 
+use jj_lib::backend::TreeValue;
+use jj_lib::local_working_copy::LocalWorkingCopy;
+use jj_lib::merge::MergedTreeValue;
+use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::store::Store;
 use tracing::instrument;
 
-use crate::cli_util::{CommandHelper, RevisionArg};
-use crate::command_error::CommandError;
+use crate::cli_util::{CommandHelper, RevisionArg, TemplateFileArgs, WorkspaceCommandHelper};
+use crate::command_error::{user_error, CommandError, ErrorCode};
+use crate::generic_templater::GenericTemplateLanguage;
+use crate::template_builder::TemplateLanguage as _;
+use crate::templater::TemplatePropertyExt as _;
 use crate::ui::Ui;
 
 /// List files in a revision
+///
+/// With `--ignored` or `--untracked`, lists files in the working copy on disk
+/// which aren't tracked in the working-copy commit instead.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct FileListArgs {
     /// The revision to list files in
     #[arg(long, short, default_value = "@")]
     revision: RevisionArg,
+    /// List untracked files in the working copy that are ignored by
+    /// `.gitignore` or `.jjignore` rules
+    #[arg(long, conflicts_with_all = ["revision", "template", "template_file"])]
+    ignored: bool,
+    /// List untracked files in the working copy that aren't ignored
+    ///
+    /// Such files are usually picked up by the next snapshot, but may exist
+    /// if the snapshot was skipped with `--ignore-working-copy`.
+    #[arg(long, conflicts_with_all = ["revision", "template", "template_file"])]
+    untracked: bool,
+    /// Render each file entry using the given template
+    ///
+    /// The following keywords are available: `path`, `size`, `executable`,
+    /// `symlink`, and `conflicted`.
+    ///
+    /// For the syntax, see https://github.com/martinvonz/jj/blob/main/docs/templates.md
+    #[arg(long, short = 'T')]
+    template: Option<String>,
+    #[command(flatten)]
+    template_file: TemplateFileArgs,
     /// Only list files matching these prefixes (instead of all files)
     #[arg(value_hint = clap::ValueHint::AnyPath)]
     paths: Vec<String>,
 }
 
+/// File entry to be rendered by the `file list` template.
+#[derive(Clone, Debug)]
+struct FileListEntry {
+    path: RepoPathBuf,
+    ui_path: String,
+    value: MergedTreeValue,
+}
+
+fn file_list_template_language(store: &Store) -> GenericTemplateLanguage<'_, FileListEntry> {
+    type L<'a> = GenericTemplateLanguage<'a, FileListEntry>;
+    let mut language = L::new();
+    language.add_keyword("path", |self_property| {
+        let out_property = self_property.map(|entry| entry.ui_path);
+        Ok(L::wrap_string(out_property))
+    });
+    language.add_keyword("size", |self_property| {
+        let out_property = self_property.and_then(|entry| {
+            let size = match entry.value.as_resolved() {
+                Some(Some(TreeValue::File { id, .. })) => {
+                    let mut reader = store.read_file(&entry.path, id)?;
+                    io::copy(&mut reader, &mut io::sink())?
+                }
+                Some(Some(TreeValue::Symlink(id))) => {
+                    store.read_symlink(&entry.path, id)?.len() as u64
+                }
+                _ => 0,
+            };
+            Ok(i64::try_from(size)?)
+        });
+        Ok(L::wrap_integer(out_property))
+    });
+    language.add_keyword("executable", |self_property| {
+        let out_property = self_property.map(|entry| {
+            matches!(
+                entry.value.as_resolved(),
+                Some(Some(TreeValue::File {
+                    executable: true,
+                    ..
+                }))
+            )
+        });
+        Ok(L::wrap_boolean(out_property))
+    });
+    language.add_keyword("symlink", |self_property| {
+        let out_property = self_property
+            .map(|entry| matches!(entry.value.as_resolved(), Some(Some(TreeValue::Symlink(_)))));
+        Ok(L::wrap_boolean(out_property))
+    });
+    language.add_keyword("conflicted", |self_property| {
+        let out_property = self_property.map(|entry| !entry.value.is_resolved());
+        Ok(L::wrap_boolean(out_property))
+    });
+    language
+}
+
 #[instrument(skip_all)]
 pub(crate) fn deprecated_cmd_files(
     ui: &mut Ui,
@@ -55,18 +142,73 @@ pub(crate) fn cmd_file_list(
     args: &FileListArgs,
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper(ui)?;
+    if args.ignored || args.untracked {
+        return list_untracked_files(ui, &workspace_command, args);
+    }
     let commit = workspace_command.resolve_single_rev(&args.revision)?;
     let tree = commit.tree()?;
     let matcher = workspace_command
         .parse_file_patterns(&args.paths)?
         .to_matcher();
-    ui.request_pager();
-    for (name, _value) in tree.entries_matching(matcher.as_ref()) {
-        writeln!(
-            ui.stdout(),
-            "{}",
-            &workspace_command.format_file_path(&name)
+    let store = workspace_command.repo().store();
+    let template = {
+        let language = file_list_template_language(store);
+        let text = args.template_file.template_text(
+            command,
+            args.template.as_deref(),
+            "templates.file_list",
         )?;
+        text.parse(|text| {
+            command.parse_template(ui, &language, text, GenericTemplateLanguage::wrap_self)
+        })?
+        .labeled("file_list")
+    };
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for (path, value) in tree.entries_matching(matcher.as_ref()) {
+        let entry = FileListEntry {
+            ui_path: workspace_command.format_file_path(&path),
+            path,
+            value: value?,
+        };
+        template.format(&entry, formatter.as_mut())?;
+    }
+    Ok(())
+}
+
+fn list_untracked_files(
+    ui: &mut Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    args: &FileListArgs,
+) -> Result<(), CommandError> {
+    let working_copy: &LocalWorkingCopy = workspace_command
+        .working_copy()
+        .as_any()
+        .downcast_ref()
+        .ok_or_else(|| {
+            user_error(
+                ErrorCode::Unsupported,
+                "This command requires a standard local-disk working copy",
+            )
+        })?;
+    let matcher = workspace_command
+        .parse_file_patterns(&args.paths)?
+        .to_matcher();
+    let untracked =
+        working_copy.untracked_files(workspace_command.base_ignores()?, matcher.as_ref())?;
+    let mut paths = vec![];
+    if args.ignored {
+        paths.extend(untracked.ignored);
+    }
+    if args.untracked {
+        paths.extend(untracked.not_ignored);
+    }
+    paths.sort();
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for path in &paths {
+        writeln!(formatter, "{}", workspace_command.format_file_path(path))?;
     }
     Ok(())
 }
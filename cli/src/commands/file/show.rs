@@ -26,7 +26,7 @@ use tracing::instrument;
 use crate::cli_util::{
     print_unmatched_explicit_paths, CommandHelper, RevisionArg, WorkspaceCommandHelper,
 };
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 /// Print contents of files in a revision
@@ -78,7 +78,10 @@ pub(crate) fn cmd_file_show(
         let value = tree.path_value(path)?;
         if value.is_absent() {
             let ui_path = workspace_command.format_file_path(path);
-            return Err(user_error(format!("No such path: {ui_path}")));
+            return Err(user_error(
+                ErrorCode::NoSuchPath,
+                format!("No such path: {ui_path}"),
+            ));
         }
         if !value.is_tree() {
             ui.request_pager();
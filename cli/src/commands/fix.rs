@@ -33,6 +33,7 @@ use tracing::instrument;
 use crate::cli_util::{CommandHelper, RevisionArg};
 use crate::command_error::{config_error_with_message, CommandError};
 use crate::config::CommandNameAndArgs;
+use crate::progress::RewriteProgressReporter;
 use crate::ui::Ui;
 
 /// Update files with formatting fixes or other changes
@@ -183,9 +184,11 @@ pub(crate) fn cmd_fix(
     // other parts of the commit like the description.
     let mut num_checked_commits = 0;
     let mut num_fixed_commits = 0;
-    tx.mut_repo().transform_descendants(
+    let mut progress = RewriteProgressReporter::new(ui, "Fixing");
+    tx.mut_repo().transform_descendants_with_progress(
         command.settings(),
         root_commits.iter().cloned().collect_vec(),
+        &mut |p| progress.update(p),
         |mut rewriter| {
             // TODO: Build the trees in parallel before `transform_descendants()` and only
             // keep the tree IDs in memory, so we can pass them to the rewriter.
@@ -225,6 +228,7 @@ pub(crate) fn cmd_fix(
             Ok(())
         },
     )?;
+    drop(progress);
     writeln!(
         ui.status(),
         "Fixed {num_fixed_commits} commits of {num_checked_commits} checked."
@@ -22,14 +22,17 @@ use jj_lib::str_util::StringPattern;
 use jj_lib::workspace::Workspace;
 
 use crate::cli_util::{CommandHelper, WorkspaceCommandHelper};
-use crate::command_error::{user_error, user_error_with_message, CommandError};
+use crate::command_error::{user_error, user_error_with_message, CommandError, ErrorCode};
 use crate::commands::git::{map_git_error, maybe_add_gitignore};
 use crate::git_util::{get_git_repo, print_git_import_stats, with_remote_git_callbacks};
 use crate::ui::Ui;
 
 /// Create a new repo backed by a clone of a Git repo
 ///
-/// The Git repo will be a bare git repo stored inside the `.jj/` directory.
+/// The Git repo will be a bare git repo stored inside the `.jj/` directory,
+/// unless `--colocate` is passed. In that case, the Git repo is created at
+/// `.git` next to the `.jj/` directory, as with `jj git init --colocate`, and
+/// the remote's default branch is exported to it once it's checked out.
 #[derive(clap::Args, Clone, Debug)]
 pub struct GitCloneArgs {
     /// URL or path of the Git repo to clone
@@ -87,13 +90,19 @@ pub fn cmd_git_clone(
         .destination
         .as_deref()
         .or_else(|| clone_destination_for_source(&source))
-        .ok_or_else(|| user_error("No destination specified and wasn't able to guess it"))?;
+        .ok_or_else(|| {
+            user_error(
+                ErrorCode::InvalidArgument,
+                "No destination specified and wasn't able to guess it",
+            )
+        })?;
     let wc_path = command.cwd().join(wc_path_str);
     let wc_path_existed = match fs::create_dir(&wc_path) {
         Ok(()) => false,
         Err(err) if err.kind() == io::ErrorKind::AlreadyExists => true,
         Err(err) => {
             return Err(user_error_with_message(
+                ErrorCode::Io,
                 format!("Failed to create {wc_path_str}"),
                 err,
             ));
@@ -101,15 +110,20 @@ pub fn cmd_git_clone(
     };
     if wc_path_existed && !is_empty_dir(&wc_path) {
         return Err(user_error(
+            ErrorCode::AlreadyExists,
             "Destination path exists and is not an empty directory",
         ));
     }
 
     // Canonicalize because fs::remove_dir_all() doesn't seem to like e.g.
     // `/some/path/.`
-    let canonical_wc_path: PathBuf = wc_path
-        .canonicalize()
-        .map_err(|err| user_error_with_message(format!("Failed to create {wc_path_str}"), err))?;
+    let canonical_wc_path: PathBuf = wc_path.canonicalize().map_err(|err| {
+        user_error_with_message(
+            ErrorCode::Io,
+            format!("Failed to create {wc_path_str}"),
+            err,
+        )
+    })?;
     let clone_result = do_git_clone(
         ui,
         command,
@@ -12,6 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::error::Error as _;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
 use itertools::Itertools;
 use jj_lib::git::{self, GitFetchError};
 use jj_lib::repo::Repo;
@@ -19,15 +23,21 @@ use jj_lib::settings::{ConfigResultExt as _, UserSettings};
 use jj_lib::str_util::StringPattern;
 
 use crate::cli_util::CommandHelper;
-use crate::command_error::{user_error, user_error_with_hint, CommandError};
+use crate::command_error::{user_error, user_error_with_hint, CommandError, ErrorCode};
 use crate::commands::git::{get_single_remote, map_git_error};
-use crate::git_util::{get_git_repo, print_git_import_stats, with_remote_git_callbacks};
+use crate::git_util::{
+    get_git_repo, print_git_import_stats, with_remote_git_callbacks, ConcurrentRemoteCallbacks,
+};
 use crate::ui::Ui;
 
 /// Fetch from a Git remote
 ///
 /// If a working-copy commit gets abandoned, it will be given a new, empty
 /// commit. This is true in general; it is not specific to this command.
+///
+/// When fetching from multiple remotes, the remotes are fetched from
+/// concurrently. A remote that can't be fetched from is reported, but doesn't
+/// prevent fetching from the others.
 #[derive(clap::Args, Clone, Debug)]
 pub struct GitFetchArgs {
     /// Fetch only some of the branches
@@ -43,6 +53,25 @@ pub struct GitFetchArgs {
     /// Fetch from all remotes
     #[arg(long, conflicts_with = "remotes")]
     all_remotes: bool,
+    /// Reset the matching remote-tracking branches, and import them again as
+    /// if they were seen for the first time
+    ///
+    /// This can be used to recover from a bad import, or after changing the
+    /// refspecs of the remote or the `git.auto-local-branch` setting.
+    #[arg(long)]
+    refetch: bool,
+    /// Maximum number of remotes to fetch from at the same time
+    ///
+    /// Defaults to the `git.fetch-concurrency` setting, or 4 if it is not set.
+    #[arg(long, short = 'j', value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: Option<u16>,
+    /// Whether to import tags fetched from the remotes
+    ///
+    /// If disabled, tags that were already imported are kept as is, and tags
+    /// deleted on the remote aren't deleted. Defaults to the `git.import-tags`
+    /// setting, or true if it is not set.
+    #[arg(long, value_name = "BOOL")]
+    import_tags: Option<bool>,
 }
 
 #[tracing::instrument(skip(ui, command))]
@@ -60,46 +89,202 @@ pub fn cmd_git_fetch(
     } else {
         args.remotes.clone()
     };
+    let concurrency = match args.jobs {
+        Some(jobs) => usize::from(jobs),
+        None => command
+            .settings()
+            .config()
+            .get_int("git.fetch-concurrency")
+            .optional()?
+            .map_or(DEFAULT_FETCH_CONCURRENCY, |n| n.max(1) as usize),
+    };
+    let partial_failure_is_error = command
+        .settings()
+        .config()
+        .get_bool("git.fetch-partial-failure-is-error")
+        .optional()?
+        .unwrap_or(false);
+    let import_tags = match args.import_tags {
+        Some(import_tags) => import_tags,
+        None => command
+            .settings()
+            .config()
+            .get_bool("git.import-tags")
+            .optional()?
+            .unwrap_or(true),
+    };
+
+    // The network transfers don't touch the jj repo, so they can run
+    // concurrently. The fetched refs are then imported in the order the
+    // remotes were given, so the result doesn't depend on which fetch finished
+    // first.
+    let fetch_results = if let [remote] = remotes.as_slice() {
+        let result = with_remote_git_callbacks(ui, None, |cb| {
+            git::fetch_refs(&git_repo, remote, &args.branch, import_tags, cb)
+        });
+        vec![result]
+    } else {
+        fetch_refs_concurrently(
+            ui,
+            &git_repo,
+            &remotes,
+            &args.branch,
+            import_tags,
+            concurrency,
+        )
+    };
+
     let mut tx = workspace_command.start_transaction();
-    for remote in &remotes {
-        let stats = with_remote_git_callbacks(ui, None, |cb| {
-            git::fetch(
-                tx.mut_repo(),
-                &git_repo,
-                remote,
-                &args.branch,
-                cb,
-                &command.settings().git_settings(),
-            )
-        })
-        .map_err(|err| match err {
-            GitFetchError::InvalidBranchPattern => {
-                if args
-                    .branch
-                    .iter()
-                    .any(|pattern| pattern.as_exact().map_or(false, |s| s.contains('*')))
-                {
-                    user_error_with_hint(
-                        err,
-                        "Prefix the pattern with `glob:` to expand `*` as a glob",
-                    )
-                } else {
-                    user_error(err)
-                }
+    let mut failures = vec![];
+    for (remote, result) in remotes.iter().zip(fetch_results) {
+        match result {
+            Ok(_default_branch) => {}
+            // The branch patterns are the same for all remotes.
+            Err(err @ GitFetchError::InvalidBranchPattern) => {
+                return Err(map_fetch_error(err, &args.branch));
+            }
+            Err(err) if remotes.len() == 1 => return Err(map_fetch_error(err, &args.branch)),
+            Err(err) => {
+                failures.push((remote, err));
+                continue;
             }
-            GitFetchError::GitImportError(err) => err.into(),
-            GitFetchError::InternalGitError(err) => map_git_error(err),
-            _ => user_error(err),
-        })?;
-        print_git_import_stats(ui, tx.repo(), &stats.import_stats, true)?;
+        }
+        let import_stats = git::import_fetched_refs(
+            tx.mut_repo(),
+            remote,
+            &args.branch,
+            &command.settings().git_settings(),
+            args.refetch,
+            import_tags,
+        )?;
+        print_git_import_stats(ui, tx.repo(), &import_stats, true)?;
+        if args.refetch {
+            writeln!(
+                ui.status(),
+                "Re-imported {} unchanged refs from {remote}",
+                import_stats.reimported_refs.len()
+            )?;
+        }
+    }
+    if !failures.is_empty() && failures.len() == remotes.len() {
+        print_fetch_failures(ui, &failures)?;
+        return Err(user_error(
+            ErrorCode::Git,
+            "Failed to fetch from all remotes",
+        ));
     }
     tx.finish(
         ui,
         format!("fetch from git remote(s) {}", remotes.iter().join(",")),
     )?;
+    if !failures.is_empty() {
+        print_fetch_failures(ui, &failures)?;
+        let fetched_remotes = remotes
+            .iter()
+            .filter(|remote| failures.iter().all(|(failed, _)| failed != remote))
+            .join(", ");
+        writeln!(ui.status(), "Fetched from {fetched_remotes}")?;
+        if partial_failure_is_error {
+            return Err(user_error(
+                ErrorCode::PartialFetch,
+                format!(
+                    "Failed to fetch from {} of {} remotes",
+                    failures.len(),
+                    remotes.len()
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Fetches from the `remotes` using up to `concurrency` threads, and returns
+/// the results in the same order as the `remotes`.
+fn fetch_refs_concurrently(
+    ui: &Ui,
+    git_repo: &git2::Repository,
+    remotes: &[String],
+    branch_names: &[StringPattern],
+    fetch_tags: bool,
+    concurrency: usize,
+) -> Vec<Result<Option<String>, GitFetchError>> {
+    let callbacks = ConcurrentRemoteCallbacks::new(ui, remotes.len());
+    let git_repo_path = git_repo.path();
+    let next_index = AtomicUsize::new(0);
+    let fetch_next = || {
+        let mut results = vec![];
+        loop {
+            let index = next_index.fetch_add(1, Ordering::Relaxed);
+            let Some(remote) = remotes.get(index) else {
+                return results;
+            };
+            // `git2::Repository` can't be shared between threads, so each
+            // fetch opens its own.
+            let result = git2::Repository::open(git_repo_path)
+                .map_err(GitFetchError::InternalGitError)
+                .and_then(|git_repo| {
+                    callbacks.with_callbacks(index, |cb| {
+                        git::fetch_refs(&git_repo, remote, branch_names, fetch_tags, cb)
+                    })
+                });
+            results.push((index, result));
+        }
+    };
+    let results = thread::scope(|scope| {
+        let workers = (0..concurrency.min(remotes.len()))
+            .map(|_| scope.spawn(fetch_next))
+            .collect_vec();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect_vec()
+    });
+    callbacks.finish();
+    results
+        .into_iter()
+        .sorted_by_key(|(index, _)| *index)
+        .map(|(_, result)| result)
+        .collect()
+}
+
+fn map_fetch_error(err: GitFetchError, branch_names: &[StringPattern]) -> CommandError {
+    match err {
+        GitFetchError::InvalidBranchPattern => {
+            if branch_names
+                .iter()
+                .any(|pattern| pattern.as_exact().is_some_and(|s| s.contains('*')))
+            {
+                user_error_with_hint(
+                    ErrorCode::InvalidArgument,
+                    err,
+                    "Prefix the pattern with `glob:` to expand `*` as a glob",
+                )
+            } else {
+                user_error(ErrorCode::InvalidArgument, err)
+            }
+        }
+        GitFetchError::GitImportError(err) => err.into(),
+        GitFetchError::InternalGitError(err) => map_git_error(err),
+        _ => user_error(ErrorCode::Git, err),
+    }
+}
+
+fn print_fetch_failures(
+    ui: &Ui,
+    failures: &[(&String, GitFetchError)],
+) -> Result<(), CommandError> {
+    let mut formatter = ui.warning_default();
+    for (remote, err) in failures {
+        write!(formatter, "Failed to fetch from {remote}: {err}")?;
+        if let Some(source) = err.source() {
+            write!(formatter, ": {source}")?;
+        }
+        writeln!(formatter)?;
+    }
     Ok(())
 }
 
+const DEFAULT_FETCH_CONCURRENCY: usize = 4;
 const DEFAULT_REMOTE: &str = "origin";
 
 fn get_default_fetch_remotes(
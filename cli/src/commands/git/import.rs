@@ -12,7 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use jj_lib::git;
+use std::cell::Cell;
+
+use itertools::Itertools as _;
+use jj_lib::git::{self, RefName};
+use jj_lib::str_util::StringPattern;
 
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
@@ -24,19 +28,62 @@ use crate::ui::Ui;
 /// If a working-copy commit gets abandoned, it will be given a new, empty
 /// commit. This is true in general; it is not specific to this command.
 #[derive(clap::Args, Clone, Debug)]
-pub struct GitImportArgs {}
+pub struct GitImportArgs {
+    /// Import only the branches and tags matching the pattern (can be
+    /// repeated)
+    ///
+    /// Remote branches are matched by the branch name. Other refs, including
+    /// `HEAD`, are left unchanged.
+    ///
+    /// By default, the specified name matches exactly. Use `glob:` prefix to
+    /// select refs by wildcard pattern. For details, see
+    /// https://martinvonz.github.io/jj/latest/revsets#string-patterns.
+    #[arg(long, value_name = "PATTERN", value_parser = StringPattern::parse)]
+    only: Vec<StringPattern>,
+}
 
 pub fn cmd_git_import(
     ui: &mut Ui,
     command: &CommandHelper,
-    _args: &GitImportArgs,
+    args: &GitImportArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
     let mut tx = workspace_command.start_transaction();
-    // In non-colocated repo, HEAD@git will never be moved internally by jj.
-    // That's why cmd_git_export() doesn't export the HEAD ref.
-    git::import_head(tx.mut_repo())?;
-    let stats = git::import_refs(tx.mut_repo(), &command.settings().git_settings())?;
+    let stats = if args.only.is_empty() {
+        // In non-colocated repo, HEAD@git will never be moved internally by jj.
+        // That's why cmd_git_export() doesn't export the HEAD ref.
+        git::import_head(tx.mut_repo())?;
+        git::import_refs(tx.mut_repo(), &command.settings().git_settings())?
+    } else {
+        let matched_any = Cell::new(false);
+        let stats = git::import_some_refs(
+            tx.mut_repo(),
+            &command.settings().git_settings(),
+            |ref_name| {
+                let name = match ref_name {
+                    RefName::LocalBranch(branch) => branch,
+                    RefName::RemoteBranch { branch, .. } => branch,
+                    RefName::Tag(tag) => tag,
+                };
+                let matches = args.only.iter().any(|pattern| pattern.matches(name));
+                if matches {
+                    matched_any.set(true);
+                }
+                matches
+            },
+        )?;
+        if !matched_any.get() {
+            writeln!(
+                ui.warning_default(),
+                "No branches or tags found matching {}",
+                args.only
+                    .iter()
+                    .map(|pattern| format!("`{pattern}`"))
+                    .join(", ")
+            )?;
+        }
+        stats
+    };
     print_git_import_stats(ui, tx.repo(), &stats, true)?;
     tx.finish(ui, "import git refs")?;
     Ok(())
@@ -21,7 +21,9 @@ use jj_lib::workspace::Workspace;
 use jj_lib::{file_util, git};
 
 use crate::cli_util::{print_trackable_remote_branches, start_repo_transaction, CommandHelper};
-use crate::command_error::{user_error_with_hint, user_error_with_message, CommandError};
+use crate::command_error::{
+    user_error_with_hint, user_error_with_message, CommandError, ErrorCode,
+};
 use crate::commands::git::maybe_add_gitignore;
 use crate::git_util::{
     is_colocated_git_workspace, print_failed_git_export, print_git_import_stats,
@@ -73,7 +75,7 @@ pub fn cmd_git_init(
     let wc_path = cwd.join(&args.destination);
     let wc_path = file_util::create_or_reuse_dir(&wc_path)
         .and_then(|_| wc_path.canonicalize())
-        .map_err(|e| user_error_with_message("Failed to create workspace", e))?;
+        .map_err(|e| user_error_with_message(ErrorCode::Io, "Failed to create workspace", e))?;
 
     do_init(
         ui,
@@ -127,6 +129,7 @@ pub fn do_init(
     } else {
         if colocated_git_repo_path.exists() {
             return Err(user_error_with_hint(
+                ErrorCode::AlreadyExists,
                 "Did not create a jj repo because there is an existing Git repo in this directory.",
                 "To create a repo backed by the existing Git repo, run `jj git init --colocate` \
                  instead.",
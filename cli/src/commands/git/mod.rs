@@ -33,7 +33,7 @@ use self::remote::{cmd_git_remote, RemoteCommand};
 use self::submodule::{cmd_git_submodule, GitSubmoduleCommand};
 use crate::cli_util::{CommandHelper, WorkspaceCommandHelper};
 use crate::command_error::{
-    user_error, user_error_with_hint, user_error_with_message, CommandError,
+    user_error, user_error_with_hint, user_error_with_message, CommandError, ErrorCode,
 };
 use crate::ui::Ui;
 
@@ -84,9 +84,9 @@ fn map_git_error(err: git2::Error) -> CommandError {
                  /dev/null` to the host work?"
             };
 
-        user_error_with_hint(err, hint)
+        user_error_with_hint(ErrorCode::Git, err, hint)
     } else {
-        user_error(err.to_string())
+        user_error(ErrorCode::Git, err.to_string())
     }
 }
 
@@ -99,7 +99,9 @@ pub fn maybe_add_gitignore(workspace_command: &WorkspaceCommandHelper) -> Result
                 .join(".gitignore"),
             "/*\n",
         )
-        .map_err(|e| user_error_with_message("Failed to write .jj/.gitignore file", e))
+        .map_err(|e| {
+            user_error_with_message(ErrorCode::Io, "Failed to write .jj/.gitignore file", e)
+        })
     } else {
         Ok(())
     }
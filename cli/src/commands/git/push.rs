@@ -18,7 +18,8 @@ use std::{fmt, io};
 
 use clap::ArgGroup;
 use itertools::Itertools;
-use jj_lib::git::{self, GitBranchPushTargets, GitPushError};
+use jj_lib::backend::CommitId;
+use jj_lib::git::{self, GitBranchPushTargets, GitPushError, GitTagPushTargets};
 use jj_lib::object_id::ObjectId;
 use jj_lib::op_store::RefTarget;
 use jj_lib::refs::{
@@ -34,7 +35,7 @@ use crate::cli_util::{
     short_change_hash, short_commit_hash, CommandHelper, RevisionArg, WorkspaceCommandHelper,
     WorkspaceCommandTransaction,
 };
-use crate::command_error::{user_error, user_error_with_hint, CommandError};
+use crate::command_error::{user_error, user_error_with_hint, CommandError, ErrorCode};
 use crate::commands::git::{get_single_remote, map_git_error};
 use crate::git_util::{get_git_repo, with_remote_git_callbacks, GitSidebandProgressMessageWriter};
 use crate::revset_util;
@@ -52,6 +53,17 @@ use crate::ui::Ui;
 /// `jj git fetch --remote <remote name>` and/or resolve some [branch
 /// conflicts].
 ///
+/// Use `--tags` to push tags. If no branches or revisions are specified, only
+/// the tags are pushed. Tags are pushed as they are in the underlying Git
+/// repo, so annotated tags keep their messages. Like in Git,
+/// moving a tag that already exists on the remote requires `--force-tags`.
+///
+/// Deleting a remote branch requires `--deleted` or naming the branch with
+/// `--branch`; `--all` and `--tracked` leave remote branches in place. If a
+/// deletion would make commits unreachable on the remote, it must also be
+/// confirmed interactively or allowed by `--force-delete`. These protections
+/// can be disabled by setting `git.push-protect-deletions = false`.
+///
 /// [safety checks]:
 ///     https://martinvonz.github.io/jj/latest/branches/#pushing-branches-safety-checks
 ///
@@ -72,10 +84,16 @@ pub struct GitPushArgs {
     /// https://martinvonz.github.io/jj/latest/revsets#string-patterns.
     #[arg(long, short, value_parser = StringPattern::parse)]
     branch: Vec<StringPattern>,
-    /// Push all branches (including deleted branches)
+    /// Push all branches
+    ///
+    /// Deleted branches are only included if `git.push-protect-deletions` is
+    /// disabled.
     #[arg(long)]
     all: bool,
-    /// Push all tracked branches (including deleted branches)
+    /// Push all tracked branches
+    ///
+    /// Deleted branches are only included if `git.push-protect-deletions` is
+    /// disabled.
     ///
     /// This usually means that the branch was already pushed to or fetched from
     /// the relevant remote. For details, see
@@ -89,9 +107,19 @@ pub struct GitPushArgs {
     /// correspond to missing local branches.
     #[arg(long)]
     deleted: bool,
+    /// Delete remote branches even if that makes commits unreachable on the
+    /// remote
+    #[arg(long)]
+    force_delete: bool,
     /// Allow pushing commits with empty descriptions
+    ///
+    /// The commits are still listed in a warning. Commits with conflicts or
+    /// without an author are rejected regardless.
     #[arg(long)]
     allow_empty_description: bool,
+    /// Allow pushing commits that match the `git.private-commits` revset
+    #[arg(long)]
+    allow_private: bool,
     /// Push branches pointing to these commits (can be repeated)
     #[arg(long, short)]
     revisions: Vec<RevisionArg>,
@@ -99,9 +127,36 @@ pub struct GitPushArgs {
     /// repeated)
     #[arg(long, short)]
     change: Vec<RevisionArg>,
+    /// Push tags matching the pattern, or all tags if no pattern is given
+    ///
+    /// By default, the specified name matches exactly. Use `glob:` prefix to
+    /// select tags by wildcard pattern.
+    #[arg(
+        long,
+        value_name = "PATTERN",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "glob:*",
+        value_parser = StringPattern::parse
+    )]
+    tags: Option<StringPattern>,
+    /// Allow moving tags that already exist on the remote
+    #[arg(long, requires = "tags")]
+    force_tags: bool,
     /// Only display what will change on the remote
     #[arg(long)]
     dry_run: bool,
+    /// Push branches under this ref namespace instead of `refs/heads/`
+    ///
+    /// For example, `--remote-ref-prefix=refs/for/` pushes branch `main` to
+    /// `refs/for/main`, as used for code review on Gerrit. Refs outside
+    /// `refs/heads/` aren't tracked, so remote-tracking branches are left
+    /// unchanged, branches can't be deleted, and refs that already exist on
+    /// the remote are only updated by fast-forwards.
+    ///
+    /// Defaults to the `git.push-remote-ref-prefix` setting.
+    #[arg(long, value_name = "PREFIX")]
+    remote_ref_prefix: Option<String>,
 }
 
 fn make_branch_term(branch_names: &[impl fmt::Display]) -> String {
@@ -112,6 +167,10 @@ fn make_branch_term(branch_names: &[impl fmt::Display]) -> String {
 }
 
 const DEFAULT_REMOTE: &str = "origin";
+const DEFAULT_REMOTE_REF_PREFIX: &str = "refs/heads/";
+
+/// Operation tag in which the remote pushed to is stored.
+pub(crate) const GIT_PUSH_TAG: &str = "git-push";
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum BranchMoveDirection {
@@ -134,13 +193,44 @@ pub fn cmd_git_push(
         get_default_push_remote(ui, command.settings(), &git_repo)?
     };
 
+    let remote_ref_prefix = match &args.remote_ref_prefix {
+        Some(prefix) => prefix.clone(),
+        None => command
+            .settings()
+            .config()
+            .get_string("git.push-remote-ref-prefix")
+            .optional()?
+            .unwrap_or_else(|| DEFAULT_REMOTE_REF_PREFIX.to_owned()),
+    };
+    validate_remote_ref_prefix(&remote_ref_prefix)?;
+    // Branches pushed outside of refs/heads/ don't have remote-tracking branches
+    let custom_ref_prefix =
+        (remote_ref_prefix != DEFAULT_REMOTE_REF_PREFIX).then_some(remote_ref_prefix);
+
+    let protect_deletions = command
+        .settings()
+        .config()
+        .get_bool("git.push-protect-deletions")
+        .optional()?
+        .unwrap_or(true);
+
     let repo = workspace_command.repo().clone();
     let mut tx = workspace_command.start_transaction();
     let tx_description;
     let mut branch_updates = vec![];
+    // Deletions which weren't explicitly requested by --deleted or --branch
+    let mut skipped_deletions = vec![];
     if args.all {
         for (branch_name, targets) in repo.view().local_remote_branches(&remote) {
-            match classify_branch_update(branch_name, &remote, targets) {
+            match classify_branch_update(
+                branch_name,
+                &remote,
+                custom_ref_prefix.as_deref(),
+                targets,
+            ) {
+                Ok(Some(update)) if protect_deletions && update.new_target.is_none() => {
+                    skipped_deletions.push(branch_name.to_owned());
+                }
                 Ok(Some(update)) => branch_updates.push((branch_name.to_owned(), update)),
                 Ok(None) => {}
                 Err(reason) => reason.print(ui)?,
@@ -152,7 +242,15 @@ pub fn cmd_git_push(
             if !targets.remote_ref.is_tracking() {
                 continue;
             }
-            match classify_branch_update(branch_name, &remote, targets) {
+            match classify_branch_update(
+                branch_name,
+                &remote,
+                custom_ref_prefix.as_deref(),
+                targets,
+            ) {
+                Ok(Some(update)) if protect_deletions && update.new_target.is_none() => {
+                    skipped_deletions.push(branch_name.to_owned());
+                }
                 Ok(Some(update)) => branch_updates.push((branch_name.to_owned(), update)),
                 Ok(None) => {}
                 Err(reason) => reason.print(ui)?,
@@ -164,7 +262,12 @@ pub fn cmd_git_push(
             if targets.local_target.is_present() {
                 continue;
             }
-            match classify_branch_update(branch_name, &remote, targets) {
+            match classify_branch_update(
+                branch_name,
+                &remote,
+                custom_ref_prefix.as_deref(),
+                targets,
+            ) {
                 Ok(Some(update)) => branch_updates.push((branch_name.to_owned(), update)),
                 Ok(None) => {}
                 Err(reason) => reason.print(ui)?,
@@ -193,7 +296,12 @@ pub fn cmd_git_push(
             if !seen_branches.insert(branch_name) {
                 continue;
             }
-            match classify_branch_update(branch_name, &remote, targets) {
+            match classify_branch_update(
+                branch_name,
+                &remote,
+                custom_ref_prefix.as_deref(),
+                targets,
+            ) {
                 Ok(Some(update)) => branch_updates.push((branch_name.to_owned(), update)),
                 Ok(None) => writeln!(
                     ui.status(),
@@ -203,8 +311,12 @@ pub fn cmd_git_push(
             }
         }
 
-        let use_default_revset =
-            args.branch.is_empty() && args.change.is_empty() && args.revisions.is_empty();
+        // Like `git push --tags`, pushing tags alone doesn't push the branches
+        // in the default revset.
+        let use_default_revset = args.branch.is_empty()
+            && args.change.is_empty()
+            && args.revisions.is_empty()
+            && args.tags.is_none();
         let branches_targeted = find_branches_targeted_by_revisions(
             ui,
             tx.base_workspace_helper(),
@@ -216,7 +328,12 @@ pub fn cmd_git_push(
             if !seen_branches.insert(branch_name) {
                 continue;
             }
-            match classify_branch_update(branch_name, &remote, targets) {
+            match classify_branch_update(
+                branch_name,
+                &remote,
+                custom_ref_prefix.as_deref(),
+                targets,
+            ) {
                 Ok(Some(update)) => branch_updates.push((branch_name.to_owned(), update)),
                 Ok(None) => {}
                 Err(reason) => reason.print(ui)?,
@@ -234,10 +351,58 @@ pub fn cmd_git_push(
             &remote
         );
     }
-    if branch_updates.is_empty() {
+    if !skipped_deletions.is_empty() {
+        writeln!(
+            ui.warning_default(),
+            "Not deleting {} on the remote",
+            make_branch_term(&skipped_deletions)
+        )?;
+        writeln!(
+            ui.hint_default(),
+            "Use `--deleted` or `--branch` to delete remote branches."
+        )?;
+    }
+    let tags_to_push = match &args.tags {
+        Some(pattern) => find_tags_to_push(
+            ui,
+            tx.base_workspace_helper(),
+            &git_repo,
+            pattern,
+            args.allow_empty_description,
+        )?,
+        None => vec![],
+    };
+    if branch_updates.is_empty() && tags_to_push.is_empty() {
         writeln!(ui.status(), "Nothing changed.")?;
         return Ok(());
     }
+    if let Some(prefix) = &custom_ref_prefix {
+        let deleted_branches = branch_updates
+            .iter()
+            .filter(|(_, update)| update.new_target.is_none())
+            .map(|(branch_name, _)| branch_name.as_str())
+            .collect_vec();
+        if !deleted_branches.is_empty() {
+            return Err(user_error_with_hint(
+                ErrorCode::PushRejected,
+                format!(
+                    "Cannot delete {} under {prefix} on the remote",
+                    make_branch_term(&deleted_branches)
+                ),
+                format!("Only branches under {DEFAULT_REMOTE_REF_PREFIX} can be deleted."),
+            ));
+        }
+    }
+    ensure_no_hidden_branch_targets(repo.as_ref(), &branch_updates)?;
+    if protect_deletions && !args.force_delete {
+        confirm_unreachable_after_deletion(
+            ui,
+            tx.base_workspace_helper(),
+            &remote,
+            &branch_updates,
+            args.dry_run,
+        )?;
+    }
 
     let mut branch_push_direction = HashMap::new();
     for (branch_name, update) in &branch_updates {
@@ -266,6 +431,7 @@ pub fn cmd_git_push(
     let new_heads = branch_updates
         .iter()
         .filter_map(|(_, update)| update.new_target.clone())
+        .chain(tags_to_push.iter().map(|(_, commit_id)| commit_id.clone()))
         .collect_vec();
     let old_heads = repo
         .view()
@@ -273,12 +439,21 @@ pub fn cmd_git_push(
         .flat_map(|(_, old_head)| old_head.target.added_ids())
         .cloned()
         .collect_vec();
+    if !args.allow_private {
+        ensure_no_private_commits(
+            tx.base_workspace_helper(),
+            command.settings(),
+            &old_heads,
+            &new_heads,
+        )?;
+    }
     // (old_heads | immutable_heads() | root())..new_heads
     let commits_to_push = RevsetExpression::commits(old_heads)
         .union(&revset_util::parse_immutable_heads_expression(
             &tx.base_workspace_helper().revset_parse_context(),
         )?)
         .range(&RevsetExpression::commits(new_heads));
+    let mut commits_without_description = vec![];
     for commit in tx
         .base_workspace_helper()
         .attach_revset_evaluator(commits_to_push)?
@@ -286,8 +461,12 @@ pub fn cmd_git_push(
     {
         let commit = commit?;
         let mut reasons = vec![];
-        if commit.description().is_empty() && !args.allow_empty_description {
-            reasons.push("it has no description");
+        if commit.description().is_empty() {
+            if args.allow_empty_description {
+                commits_without_description.push(commit.id().clone());
+            } else {
+                reasons.push("it has no description");
+            }
         }
         if commit.author().name.is_empty()
             || commit.author().name == UserSettings::USER_NAME_PLACEHOLDER
@@ -304,15 +483,34 @@ pub fn cmd_git_push(
             reasons.push("it has conflicts");
         }
         if !reasons.is_empty() {
-            return Err(user_error(format!(
-                "Won't push commit {} since {}",
-                short_commit_hash(commit.id()),
-                reasons.join(" and ")
-            )));
+            return Err(user_error(
+                ErrorCode::PushRejected,
+                format!(
+                    "Won't push commit {} since {}",
+                    short_commit_hash(commit.id()),
+                    reasons.join(" and ")
+                ),
+            ));
         }
     }
+    for commit_id in &commits_without_description {
+        writeln!(
+            ui.warning_default(),
+            "Pushing commit {} with no description",
+            short_commit_hash(commit_id)
+        )?;
+    }
 
-    writeln!(ui.status(), "Branch changes to push to {}:", &remote)?;
+    if !branch_updates.is_empty() {
+        if let Some(prefix) = &custom_ref_prefix {
+            writeln!(
+                ui.status(),
+                "Branch changes to push to {remote} under {prefix}:"
+            )?;
+        } else {
+            writeln!(ui.status(), "Branch changes to push to {}:", &remote)?;
+        }
+    }
     for (branch_name, update) in &branch_updates {
         match (&update.old_target, &update.new_target) {
             (Some(old_target), Some(new_target)) => {
@@ -344,11 +542,19 @@ pub fn cmd_git_push(
                 )?;
             }
             (None, Some(new_target)) => {
-                writeln!(
-                    ui.status(),
-                    "  Add branch {branch_name} to {}",
-                    short_commit_hash(new_target)
-                )?;
+                if let Some(prefix) = &custom_ref_prefix {
+                    writeln!(
+                        ui.status(),
+                        "  Push branch {branch_name} to {prefix}{branch_name} at {}",
+                        short_commit_hash(new_target)
+                    )?;
+                } else {
+                    writeln!(
+                        ui.status(),
+                        "  Add branch {branch_name} to {}",
+                        short_commit_hash(new_target)
+                    )?;
+                }
             }
             (None, None) => {
                 panic!("Not pushing any change to branch {branch_name}");
@@ -356,22 +562,69 @@ pub fn cmd_git_push(
         }
     }
 
+    if !tags_to_push.is_empty() {
+        writeln!(ui.status(), "Tags to push to {remote}:")?;
+        for (tag_name, commit_id) in &tags_to_push {
+            writeln!(
+                ui.status(),
+                "  Push tag {tag_name} at {}",
+                short_commit_hash(commit_id)
+            )?;
+        }
+    }
+
     if args.dry_run {
         writeln!(ui.status(), "Dry-run requested, not pushing.")?;
         return Ok(());
     }
 
     let targets = GitBranchPushTargets { branch_updates };
+    let tags = GitTagPushTargets {
+        tag_names: tags_to_push.into_iter().map(|(name, _)| name).collect(),
+        force: args.force_tags,
+    };
     let mut writer = GitSidebandProgressMessageWriter::new(ui);
     let mut sideband_progress_callback = |progress_message: &[u8]| {
         _ = writer.write(ui, progress_message);
     };
     with_remote_git_callbacks(ui, Some(&mut sideband_progress_callback), |cb| {
-        git::push_branches(tx.mut_repo(), &git_repo, &remote, &targets, cb)
+        if let Some(prefix) = &custom_ref_prefix {
+            git::push_branches_to_ref_prefix(
+                tx.repo(),
+                &git_repo,
+                &remote,
+                prefix,
+                &targets,
+                &tags,
+                cb,
+            )
+        } else {
+            git::push_branches(tx.mut_repo(), &git_repo, &remote, &targets, &tags, cb)
+        }
     })
     .map_err(|err| match err {
         GitPushError::InternalGitError(err) => map_git_error(err),
+        GitPushError::RefAlreadyExists(refs) => user_error_with_hint(
+            ErrorCode::PushRejected,
+            format!(
+                "Refusing to move tags that already exist on the remote: {}",
+                refs.join(", ")
+            ),
+            "Use --force-tags to move them.",
+        ),
+        GitPushError::RefInUnexpectedLocation(refs) if custom_ref_prefix.is_some() => {
+            user_error_with_hint(
+                ErrorCode::PushRejected,
+                format!(
+                    "Refusing to push a branch that isn't a fast-forward of the remote ref. \
+                     Affected refs: {}",
+                    refs.join(", ")
+                ),
+                "Make the branch a descendant of the remote ref, and push again.",
+            )
+        }
         GitPushError::RefInUnexpectedLocation(refs) => user_error_with_hint(
+            ErrorCode::PushRejected,
             format!(
                 "Refusing to push a branch that unexpectedly moved on the remote. Affected refs: \
                  {}",
@@ -380,13 +633,197 @@ pub fn cmd_git_push(
             "Try fetching from the remote, then make the branch point to where you want it to be, \
              and push again.",
         ),
-        _ => user_error(err),
+        _ => user_error(ErrorCode::Git, err),
     })?;
     writer.flush(ui)?;
+    if targets.branch_updates.is_empty() {
+        return Ok(());
+    }
+    if custom_ref_prefix.is_some() && !tx.mut_repo().has_changes() {
+        // Only branches created by --change would be recorded
+        return Ok(());
+    }
+    tx.set_tag(GIT_PUSH_TAG.to_owned(), remote);
     tx.finish(ui, tx_description)?;
     Ok(())
 }
 
+fn validate_remote_ref_prefix(prefix: &str) -> Result<(), CommandError> {
+    if prefix.starts_with("refs/")
+        && prefix.ends_with('/')
+        && git2::Reference::is_valid_name(&format!("{prefix}branch"))
+    {
+        Ok(())
+    } else {
+        Err(user_error_with_hint(
+            ErrorCode::InvalidArgument,
+            format!("Invalid remote ref prefix: {prefix}"),
+            "The prefix must be a ref namespace starting with `refs/` and ending with `/`, such \
+             as `refs/for/`.",
+        ))
+    }
+}
+
+/// Rejects pushing branches that point to hidden commits, e.g. ones retained by
+/// `jj abandon --retain-branches`.
+fn ensure_no_hidden_branch_targets(
+    repo: &dyn Repo,
+    branch_updates: &[(String, BranchPushUpdate)],
+) -> Result<(), CommandError> {
+    let mut hidden_branches = vec![];
+    for (branch_name, update) in branch_updates {
+        let Some(new_target) = &update.new_target else {
+            continue;
+        };
+        let commit = repo.store().get_commit(new_target)?;
+        let is_visible = repo
+            .resolve_change_id(commit.change_id())
+            .is_some_and(|ids| ids.contains(new_target));
+        if !is_visible {
+            hidden_branches.push(branch_name.as_str());
+        }
+    }
+    if hidden_branches.is_empty() {
+        return Ok(());
+    }
+    Err(user_error_with_hint(
+        ErrorCode::PushRejected,
+        format!(
+            "Won't push {} pointing to a hidden commit",
+            make_branch_term(&hidden_branches)
+        ),
+        "Use `jj branch set` to move the branch to a visible commit.",
+    ))
+}
+
+/// Refuses to push commits matching the `git.private-commits` revset, unless
+/// they're already reachable from the remote branches.
+fn ensure_no_private_commits(
+    workspace_command: &WorkspaceCommandHelper,
+    settings: &UserSettings,
+    old_heads: &[CommitId],
+    new_heads: &[CommitId],
+) -> Result<(), CommandError> {
+    let Some(private_revset) = settings
+        .config()
+        .get_string("git.private-commits")
+        .optional()?
+    else {
+        return Ok(());
+    };
+    let mut private_commits =
+        workspace_command.parse_revset(&RevisionArg::from(private_revset.clone()))?;
+    private_commits.intersect_with(
+        &RevsetExpression::commits(old_heads.to_vec())
+            .range(&RevsetExpression::commits(new_heads.to_vec())),
+    );
+    let private_commits: Vec<_> = private_commits.evaluate_to_commits()?.try_collect()?;
+    if private_commits.is_empty() {
+        return Ok(());
+    }
+    let mut message = format!(
+        "Won't push {} private {}:",
+        private_commits.len(),
+        if private_commits.len() == 1 {
+            "commit"
+        } else {
+            "commits"
+        }
+    );
+    for commit in &private_commits {
+        message.push_str("\n  ");
+        message.push_str(&workspace_command.format_commit_summary(commit));
+    }
+    Err(user_error_with_hint(
+        ErrorCode::PushRejected,
+        message,
+        format!(
+            "Configured git.private-commits: '{private_revset}'. Use --allow-private to push them \
+             anyway."
+        ),
+    ))
+}
+
+/// Checks that deleting branches on the remote doesn't make commits unreachable
+/// there, or asks the user to confirm if it does.
+///
+/// The commits are determined from the last-known targets of the remote
+/// branches.
+fn confirm_unreachable_after_deletion(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    remote_name: &str,
+    branch_updates: &[(String, BranchPushUpdate)],
+    dry_run: bool,
+) -> Result<(), CommandError> {
+    let deleted_branches: HashMap<&str, &CommitId> = branch_updates
+        .iter()
+        .filter_map(|(branch_name, update)| match update {
+            BranchPushUpdate {
+                old_target: Some(old_target),
+                new_target: None,
+            } => Some((branch_name.as_str(), old_target)),
+            _ => None,
+        })
+        .collect();
+    if deleted_branches.is_empty() {
+        return Ok(());
+    }
+    // Heads that will remain on the remote after the push
+    let remaining_heads = workspace_command
+        .repo()
+        .view()
+        .remote_branches(remote_name)
+        .filter(|(branch_name, _)| {
+            !branch_updates
+                .iter()
+                .any(|(updated_name, _)| updated_name == branch_name)
+        })
+        .flat_map(|(_, remote_ref)| remote_ref.target.added_ids())
+        .chain(
+            branch_updates
+                .iter()
+                .filter_map(|(_, update)| update.new_target.as_ref()),
+        )
+        .cloned()
+        .collect_vec();
+    let unreachable_expression = RevsetExpression::commits(remaining_heads).range(
+        &RevsetExpression::commits(deleted_branches.values().copied().cloned().collect()),
+    );
+    let unreachable_commits: Vec<_> = workspace_command
+        .attach_revset_evaluator(unreachable_expression)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    if unreachable_commits.is_empty() {
+        return Ok(());
+    }
+
+    let branch_names = deleted_branches.keys().sorted().collect_vec();
+    writeln!(
+        ui.warning_default(),
+        "Deleting {} would make these commits unreachable on {remote_name}:",
+        make_branch_term(&branch_names)
+    )?;
+    if let Some(mut formatter) = ui.status_formatter() {
+        for commit in &unreachable_commits {
+            write!(formatter, "  ")?;
+            workspace_command.write_commit_summary(formatter.as_mut(), commit)?;
+            writeln!(formatter)?;
+        }
+    }
+    if dry_run {
+        return Ok(());
+    }
+    if Ui::can_prompt() && ui.prompt_yes_no("Delete anyway?", Some(false))? {
+        return Ok(());
+    }
+    Err(user_error_with_hint(
+        ErrorCode::PushRejected,
+        "Refusing to delete remote branches that would leave commits unreachable",
+        "Use `--force-delete` to delete them anyway.",
+    ))
+}
+
 fn get_default_push_remote(
     ui: &Ui,
     settings: &UserSettings,
@@ -410,6 +847,7 @@ fn get_default_push_remote(
 
 #[derive(Clone, Debug)]
 struct RejectedBranchUpdateReason {
+    code: ErrorCode,
     message: String,
     hint: Option<String>,
 }
@@ -426,8 +864,12 @@ impl RejectedBranchUpdateReason {
 
 impl From<RejectedBranchUpdateReason> for CommandError {
     fn from(reason: RejectedBranchUpdateReason) -> Self {
-        let RejectedBranchUpdateReason { message, hint } = reason;
-        let mut cmd_err = user_error(message);
+        let RejectedBranchUpdateReason {
+            code,
+            message,
+            hint,
+        } = reason;
+        let mut cmd_err = user_error(code, message);
         cmd_err.extend_hints(hint);
         cmd_err
     }
@@ -436,22 +878,36 @@ impl From<RejectedBranchUpdateReason> for CommandError {
 fn classify_branch_update(
     branch_name: &str,
     remote_name: &str,
+    custom_ref_prefix: Option<&str>,
     targets: LocalAndRemoteRef,
 ) -> Result<Option<BranchPushUpdate>, RejectedBranchUpdateReason> {
-    let push_action = classify_branch_push_action(targets);
+    // The remote-tracking branch lives under refs/heads/, so it says nothing
+    // about the destination ref under a custom prefix.
+    let push_action = match targets.local_target.as_normal() {
+        Some(new_target) if custom_ref_prefix.is_some() => {
+            BranchPushAction::Update(BranchPushUpdate {
+                old_target: None,
+                new_target: Some(new_target.clone()),
+            })
+        }
+        _ => classify_branch_push_action(targets),
+    };
     match push_action {
         BranchPushAction::AlreadyMatches => Ok(None),
         BranchPushAction::LocalConflicted => Err(RejectedBranchUpdateReason {
+            code: ErrorCode::BranchConflict,
             message: format!("Branch {branch_name} is conflicted"),
             hint: Some(
                 "Run `jj branch list` to inspect, and use `jj branch set` to fix it up.".to_owned(),
             ),
         }),
         BranchPushAction::RemoteConflicted => Err(RejectedBranchUpdateReason {
+            code: ErrorCode::BranchConflict,
             message: format!("Branch {branch_name}@{remote_name} is conflicted"),
             hint: Some("Run `jj git fetch` to update the conflicted remote branch.".to_owned()),
         }),
         BranchPushAction::RemoteUntracked => Err(RejectedBranchUpdateReason {
+            code: ErrorCode::UntrackedRemoteBranch,
             message: format!("Non-tracking remote branch {branch_name}@{remote_name} exists"),
             hint: Some(format!(
                 "Run `jj branch track {branch_name}@{remote_name}` to import the remote branch."
@@ -530,12 +986,85 @@ fn find_branches_to_push<'a>(
     }
     match &unmatched_patterns[..] {
         [] => Ok(matching_branches),
-        [pattern] if pattern.is_exact() => Err(user_error(format!("No such branch: {pattern}"))),
-        patterns => Err(user_error(format!(
-            "No matching branches for patterns: {}",
-            patterns.iter().join(", ")
-        ))),
+        [pattern] if pattern.is_exact() => Err(user_error(
+            ErrorCode::NoSuchBranch,
+            format!("No such branch: {pattern}"),
+        )),
+        patterns => Err(user_error(
+            ErrorCode::NoSuchBranch,
+            format!(
+                "No matching branches for patterns: {}",
+                patterns.iter().join(", ")
+            ),
+        )),
+    }
+}
+
+/// Finds the tags matching `pattern` and checks that they can be pushed as
+/// they are in the underlying Git repo.
+fn find_tags_to_push(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    git_repo: &git2::Repository,
+    pattern: &StringPattern,
+    allow_empty_description: bool,
+) -> Result<Vec<(String, CommitId)>, CommandError> {
+    let repo = workspace_command.repo();
+    let mut tags_to_push = vec![];
+    for (tag_name, target) in pattern.filter_btree_map(repo.view().tags()) {
+        if target.has_conflict() {
+            return Err(user_error_with_hint(
+                ErrorCode::PushRejected,
+                format!("Won't push tag {tag_name} since it is conflicted"),
+                "Run `jj tag list` to inspect it, and update the tag in the Git repo.",
+            ));
+        }
+        let Some(commit_id) = target.as_normal() else {
+            continue;
+        };
+        let commit = repo.store().get_commit(commit_id)?;
+        let mut reasons = vec![];
+        if commit.description().is_empty() && !allow_empty_description {
+            reasons.push("has no description");
+        }
+        if commit.has_conflict()? {
+            reasons.push("has conflicts");
+        }
+        if !reasons.is_empty() {
+            return Err(user_error(
+                ErrorCode::PushRejected,
+                format!(
+                    "Won't push tag {tag_name} since it points to commit {}, which {}",
+                    short_commit_hash(commit_id),
+                    reasons.join(" and ")
+                ),
+            ));
+        }
+        if commit.description().is_empty() {
+            writeln!(
+                ui.warning_default(),
+                "Pushing tag {tag_name} pointing to commit {} with no description",
+                short_commit_hash(commit_id)
+            )?;
+        }
+        let git_commit_id = git_repo
+            .find_reference(&format!("refs/tags/{tag_name}"))
+            .and_then(|git_ref| git_ref.peel_to_commit())
+            .ok()
+            .map(|git_commit| CommitId::from_bytes(git_commit.id().as_bytes()));
+        if git_commit_id.as_ref() != Some(commit_id) {
+            return Err(user_error_with_hint(
+                ErrorCode::PushRejected,
+                format!("Tag {tag_name} differs in the underlying Git repo"),
+                "Run `jj git import` to update it.",
+            ));
+        }
+        tags_to_push.push((tag_name.clone(), commit_id.clone()));
+    }
+    if tags_to_push.is_empty() {
+        writeln!(ui.warning_default(), "No tags matching {pattern}")?;
     }
+    Ok(tags_to_push)
 }
 
 fn find_branches_targeted_by_revisions<'a>(
@@ -548,7 +1077,10 @@ fn find_branches_targeted_by_revisions<'a>(
     let mut revision_commit_ids = HashSet::new();
     if use_default_revset {
         let Some(wc_commit_id) = workspace_command.get_wc_commit_id().cloned() else {
-            return Err(user_error("Nothing checked out in this workspace"));
+            return Err(user_error(
+                ErrorCode::NoWorkingCopy,
+                "Nothing checked out in this workspace",
+            ));
         };
         let current_branches_expression = RevsetExpression::remote_branches(
             StringPattern::everything(),
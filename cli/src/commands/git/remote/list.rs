@@ -35,12 +35,16 @@ pub fn cmd_git_remote_list(
     let git_repo = get_git_repo(repo.store())?;
     for remote_name in git_repo.remotes()?.iter().flatten() {
         let remote = git_repo.find_remote(remote_name)?;
-        writeln!(
+        write!(
             ui.stdout(),
             "{} {}",
             remote_name,
             remote.url().unwrap_or("<no URL>")
         )?;
+        if let Some(push_url) = remote.pushurl() {
+            write!(ui.stdout(), " (push: {push_url})")?;
+        }
+        writeln!(ui.stdout())?;
     }
     Ok(())
 }
@@ -21,12 +21,17 @@ use crate::git_util::get_git_repo;
 use crate::ui::Ui;
 
 /// Set the URL of a Git remote
+///
+/// The remote's branches and their tracking state are kept.
 #[derive(clap::Args, Clone, Debug)]
 pub struct GitRemoteSetUrlArgs {
     /// The remote's name
     remote: String,
     /// The desired url for `remote`
     url: String,
+    /// Set the URL used for pushing instead of the one used for fetching
+    #[arg(long)]
+    push: bool,
 }
 
 pub fn cmd_git_remote_set_url(
@@ -37,6 +42,10 @@ pub fn cmd_git_remote_set_url(
     let workspace_command = command.workspace_helper(ui)?;
     let repo = workspace_command.repo();
     let git_repo = get_git_repo(repo.store())?;
-    git::set_remote_url(&git_repo, &args.remote, &args.url)?;
+    if args.push {
+        git::set_remote_push_url(&git_repo, &args.remote, &args.url)?;
+    } else {
+        git::set_remote_url(&git_repo, &args.remote, &args.url)?;
+    }
     Ok(())
 }
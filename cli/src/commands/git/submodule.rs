@@ -21,7 +21,7 @@ use jj_lib::repo::Repo;
 use jj_lib::repo_path::RepoPath;
 
 use crate::cli_util::{CommandHelper, RevisionArg};
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 /// FOR INTERNAL USE ONLY Interact with git submodules
@@ -71,7 +71,7 @@ fn cmd_submodule_print(
         }
         Ok(Some(TreeValue::File { id, .. })) => repo.store().read_file(gitmodules_path, &id)?,
         _ => {
-            return Err(user_error(".gitmodules is not a file."));
+            return Err(user_error(ErrorCode::Io, ".gitmodules is not a file."));
         }
     };
 
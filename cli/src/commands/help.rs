@@ -0,0 +1,276 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::{self, Write as _};
+
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::{user_error_with_hint, CommandError, ErrorCode};
+use crate::formatter::Formatter;
+use crate::text_util;
+use crate::ui::Ui;
+
+/// Print this message, the help of the given subcommand, or a help topic
+///
+/// Run `jj help <TOPIC>` to read one of the help topics listed at the end of
+/// `jj help`, or `jj help --keyword <KEYWORD>` to find where a revset function,
+/// template keyword, or option is documented.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct HelpArgs {
+    /// The subcommand or help topic to print help for
+    #[arg(value_name = "COMMAND_OR_TOPIC")]
+    command: Vec<String>,
+    /// Search the help topics and subcommands for a function or keyword
+    #[arg(long, short, allow_hyphen_values = true, conflicts_with = "command")]
+    keyword: Option<String>,
+}
+
+/// A page of documentation that can be read with `jj help <name>`.
+struct HelpTopic {
+    name: &'static str,
+    description: &'static str,
+    /// The page in Markdown
+    content: &'static str,
+}
+
+const HELP_TOPICS: &[HelpTopic] = &[
+    HelpTopic {
+        name: "configuration",
+        description: "Settings and config files",
+        content: include_str!(concat!("../../", env!("JJ_DOCS_DIR"), "config.md")),
+    },
+    HelpTopic {
+        name: "conflicts",
+        description: "How conflicts are recorded and resolved",
+        content: include_str!(concat!("../../", env!("JJ_DOCS_DIR"), "conflicts.md")),
+    },
+    HelpTopic {
+        name: "filesets",
+        description: "The language for selecting files",
+        content: include_str!(concat!("../../", env!("JJ_DOCS_DIR"), "filesets.md")),
+    },
+    HelpTopic {
+        name: "glossary",
+        description: "Definitions of the terms used in jj",
+        content: include_str!(concat!("../../", env!("JJ_DOCS_DIR"), "glossary.md")),
+    },
+    HelpTopic {
+        name: "revsets",
+        description: "The language for selecting revisions",
+        content: include_str!(concat!("../../", env!("JJ_DOCS_DIR"), "revsets.md")),
+    },
+    HelpTopic {
+        name: "templates",
+        description: "The language for customizing command output",
+        content: include_str!(concat!("../../", env!("JJ_DOCS_DIR"), "templates.md")),
+    },
+];
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_help(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &HelpArgs,
+) -> Result<(), CommandError> {
+    if let Some(keyword) = &args.keyword {
+        return search_keyword(ui, command.app(), keyword);
+    }
+    if let [name] = args.command.as_slice() {
+        if let Some(topic) = HELP_TOPICS.iter().find(|topic| topic.name == name) {
+            ui.request_pager();
+            let width = usize::from(ui.term_width().unwrap_or(80));
+            return Ok(render_markdown(
+                ui.stdout_formatter().as_mut(),
+                topic.content,
+                width,
+            )?);
+        }
+    }
+    if args.command.is_empty() {
+        let help = command.app().clone().render_long_help();
+        let help = if ui.color() {
+            help.ansi().to_string()
+        } else {
+            help.to_string()
+        };
+        ui.request_pager();
+        // The help is already styled by clap, so bypass the formatter
+        write!(ui.stdout(), "{help}")?;
+        let mut formatter = ui.stdout_formatter();
+        writeln!(formatter)?;
+        writeln!(formatter.labeled("help heading"), "Help Topics:")?;
+        let name_width = HELP_TOPICS.iter().map(|topic| topic.name.len()).max();
+        for topic in HELP_TOPICS {
+            writeln!(
+                formatter,
+                "  {:width$}  {}",
+                topic.name,
+                topic.description,
+                width = name_width.unwrap_or(0)
+            )?;
+        }
+        return Ok(());
+    }
+    // Let clap print the help of the subcommand as if `--help` were given
+    let err = command
+        .app()
+        .clone()
+        .try_get_matches_from(
+            ["jj"]
+                .into_iter()
+                .chain(args.command.iter().map(String::as_str))
+                .chain(["--help"]),
+        )
+        .unwrap_err();
+    Err(err.into())
+}
+
+/// Lists where the `keyword` is documented, both in the help topics and in the
+/// help of the subcommands.
+///
+/// Only the identifiers quoted in backticks are matched, so searching for a
+/// function name doesn't also find every sentence using the same word.
+fn search_keyword(ui: &mut Ui, app: &clap::Command, keyword: &str) -> Result<(), CommandError> {
+    let keyword = normalize_identifier(keyword);
+    let mut matches = vec![];
+    for topic in HELP_TOPICS {
+        for line in matching_lines(topic.content, &keyword) {
+            matches.push((format!("jj help {}", topic.name), line));
+        }
+    }
+    let mut app = app.clone();
+    app.build();
+    search_command_help(&app, &keyword, &mut matches);
+    if matches.is_empty() {
+        return Err(user_error_with_hint(
+            ErrorCode::InvalidArgument,
+            format!("No help found for keyword {keyword}"),
+            "Run `jj help` to list the subcommands and help topics.",
+        ));
+    }
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for (source, line) in matches {
+        write!(formatter.labeled("help heading"), "{source}")?;
+        writeln!(formatter, ": {line}")?;
+    }
+    Ok(())
+}
+
+fn search_command_help(app: &clap::Command, keyword: &str, matches: &mut Vec<(String, String)>) {
+    let texts = app.get_long_about().or(app.get_about()).into_iter().chain(
+        app.get_arguments()
+            .filter_map(|arg| arg.get_long_help().or(arg.get_help())),
+    );
+    let name = app.get_bin_name().unwrap_or_else(|| app.get_name());
+    for text in texts {
+        for line in matching_lines(&text.to_string(), keyword) {
+            matches.push((name.to_owned(), line));
+        }
+    }
+    for subcommand in app.get_subcommands() {
+        if !subcommand.is_hide_set() {
+            search_command_help(subcommand, keyword, matches);
+        }
+    }
+}
+
+/// Returns the trimmed lines that quote the `keyword` in backticks.
+fn matching_lines(text: &str, keyword: &str) -> Vec<String> {
+    text.lines()
+        .filter(|line| {
+            line.split('`')
+                .skip(1)
+                .step_by(2)
+                .any(|quoted| normalize_identifier(leading_identifier(quoted)) == keyword)
+        })
+        .map(|line| {
+            let line = line.trim();
+            line.strip_prefix("* ").unwrap_or(line).to_owned()
+        })
+        .collect()
+}
+
+/// Returns the identifier at the start of quoted code, e.g. `latest` for
+/// `latest(x[, count])`.
+fn leading_identifier(code: &str) -> &str {
+    let end = code
+        .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.')))
+        .unwrap_or(code.len());
+    &code[..end]
+}
+
+fn normalize_identifier(identifier: &str) -> String {
+    identifier
+        .trim_start_matches('-')
+        .trim_end_matches("()")
+        .to_lowercase()
+}
+
+/// Renders a Markdown page for the terminal.
+///
+/// Paragraphs and list items are rewrapped to the `width`. Other blocks, like
+/// code and tables, are printed as is.
+fn render_markdown(formatter: &mut dyn Formatter, content: &str, width: usize) -> io::Result<()> {
+    let mut lines = content.lines().peekable();
+    while let Some(line) = lines.next() {
+        if line.trim().is_empty() {
+            writeln!(formatter)?;
+        } else if line.starts_with("```") {
+            for line in lines.by_ref().take_while(|line| !line.starts_with("```")) {
+                writeln!(formatter, "    {line}")?;
+            }
+        } else if let Some(heading) = line.strip_prefix('#') {
+            let heading = heading.trim_start_matches('#').trim();
+            writeln!(formatter.labeled("help heading"), "{heading}")?;
+        } else if line.starts_with('|') || line.starts_with('<') {
+            writeln!(formatter, "{line}")?;
+        } else {
+            let indent = line.len() - line.trim_start().len();
+            let is_list_item = ["* ", "- "]
+                .iter()
+                .any(|marker| line.trim_start().starts_with(marker));
+            // Continuation lines of list items are indented more than the item
+            let mut words = vec![line.trim()];
+            while let Some(next) = lines.peek() {
+                let next_indent = next.len() - next.trim_start().len();
+                let next_trimmed = next.trim_start();
+                if next.trim().is_empty()
+                    || next.starts_with('#')
+                    || next_trimmed.starts_with("```")
+                    || next_trimmed.starts_with("* ")
+                    || next_trimmed.starts_with("- ")
+                    || (is_list_item && next_indent <= indent)
+                {
+                    break;
+                }
+                words.push(next.trim());
+                lines.next();
+            }
+            let first_indent = " ".repeat(indent);
+            let rest_indent = " ".repeat(if is_list_item { indent + 2 } else { indent });
+            let text = words.join(" ");
+            let wrapped = text_util::wrap_bytes(text.as_bytes(), width.saturating_sub(indent + 2));
+            for (i, line) in wrapped.iter().enumerate() {
+                let indent = if i == 0 { &first_indent } else { &rest_indent };
+                write!(formatter, "{indent}")?;
+                formatter.write_all(line)?;
+                writeln!(formatter)?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -21,7 +21,9 @@ use tracing::instrument;
 
 use super::git;
 use crate::cli_util::CommandHelper;
-use crate::command_error::{user_error_with_hint, user_error_with_message, CommandError};
+use crate::command_error::{
+    user_error_with_hint, user_error_with_message, CommandError, ErrorCode,
+};
 use crate::ui::Ui;
 
 /// Create a new repo in the given directory
@@ -54,7 +56,7 @@ pub(crate) fn cmd_init(
     let wc_path = cwd.join(&args.destination);
     let wc_path = file_util::create_or_reuse_dir(&wc_path)
         .and_then(|_| wc_path.canonicalize())
-        .map_err(|e| user_error_with_message("Failed to create workspace", e))?;
+        .map_err(|e| user_error_with_message(ErrorCode::Io, "Failed to create workspace", e))?;
 
     // Preserve existing behaviour where `jj init` is not able to create
     // a colocated repo.
@@ -69,6 +71,7 @@ Use `jj git init` instead"
     } else {
         if !command.settings().allow_native_backend() {
             return Err(user_error_with_hint(
+                ErrorCode::Unsupported,
                 "The native backend is disallowed by default.",
                 "Did you mean to call `jj git init`?
 Set `ui.allow-init-native` to allow initializing a repo with the native backend.",
@@ -12,17 +12,37 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use jj_lib::backend::CommitId;
-use jj_lib::graph::{GraphEdgeType, ReverseGraphIterator, TopoGroupedGraphIterator};
+use std::collections::HashMap;
+use std::io::{self, Read as _};
+use std::sync::Arc;
+
+use itertools::Itertools as _;
+use jj_lib::backend::{BackendResult, CommitId};
+use jj_lib::commit::Commit;
+use jj_lib::conflicts::{materialize_tree_value, MaterializedTreeValue};
+use jj_lib::graph::{GraphEdge, GraphEdgeType, ReverseGraphIterator, TopoGroupedGraphIterator};
+use jj_lib::matchers::Matcher;
+use jj_lib::op_walk;
 use jj_lib::repo::Repo;
 use jj_lib::revset::{RevsetExpression, RevsetFilterPredicate, RevsetIteratorExt};
+use jj_lib::settings::ConfigResultExt as _;
+use jj_lib::store::Store;
+use pollster::FutureExt as _;
 use tracing::instrument;
 
-use crate::cli_util::{format_template, CommandHelper, LogContentFormat, RevisionArg};
-use crate::command_error::CommandError;
-use crate::commit_templater::CommitTemplateLanguage;
-use crate::diff_util::DiffFormatArgs;
+use crate::cli_util::{
+    format_template, print_conflicted_paths, short_operation_hash, CommandHelper, LogContentFormat,
+    RevisionArg, TemplateFileArgs, WorkspaceCommandHelper,
+};
+use crate::command_error::{config_error_with_message, user_error, CommandError, ErrorCode};
+use crate::commit_templater::{AheadBehindCounter, CommitTemplateLanguage};
+use crate::diff_util::{is_binary_content, DiffFormatArgs};
+use crate::formatter::{FormatRecorder, Formatter};
 use crate::graphlog::{get_graphlog, Edge};
+use crate::templater::{
+    ConcatTemplate, PropertyPlaceholder, TemplatePropertyExt as _, TemplateRenderer,
+};
+use crate::text_util::write_indented;
 use crate::ui::Ui;
 
 /// Show revision history
@@ -32,7 +52,9 @@ use crate::ui::Ui;
 /// along with some additional revisions for context.
 ///
 /// Spans of revisions that are not included in the graph per `--revisions` are
-/// rendered as a synthetic node labeled "(elided revisions)".
+/// rendered as a synthetic node labeled "(elided revisions)", unless
+/// `--no-elide` is given. Use `--elided` or the `ui.log-elided-style` setting
+/// to render them differently.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct LogArgs {
     /// Which revisions to show. If no paths nor revisions are specified, this
@@ -40,6 +62,25 @@ pub(crate) struct LogArgs {
     /// ancestors(immutable_heads().., 2) | trunk()` if it is not set.
     #[arg(long, short)]
     revisions: Vec<RevisionArg>,
+    /// Show all revisions
+    ///
+    /// This uses the `revsets.log-graph-all` setting, or `all()` if it is not
+    /// set. If paths are specified, only the revisions modifying them are
+    /// shown.
+    #[arg(long, conflicts_with = "revisions")]
+    all: bool,
+    /// Show the revisions in between the selected revisions instead of eliding
+    /// them
+    ///
+    /// If that would show more than `ui.log-no-elide-limit` revisions, the
+    /// graph is elided as usual.
+    #[arg(long)]
+    no_elide: bool,
+    /// How to render the spans of revisions elided from the graph
+    ///
+    /// Defaults to the `ui.log-elided-style` setting.
+    #[arg(long, value_name = "STYLE", conflicts_with = "no_graph")]
+    elided: Option<ElidedStyle>,
     /// Show revisions modifying the given paths
     #[arg(value_hint = clap::ValueHint::AnyPath)]
     paths: Vec<String>,
@@ -62,18 +103,62 @@ pub(crate) struct LogArgs {
     /// Don't show the graph, show a flat list of revisions
     #[arg(long)]
     no_graph: bool,
+    /// Collapse runs of revisions with the same description
+    ///
+    /// A run of revisions in a single line of history whose descriptions start
+    /// with the same line (such as a series of "fixup" commits) is rendered as
+    /// its first revision followed by a node counting the others. Revisions
+    /// with other parents or children in the graph are never collapsed. Omit
+    /// this flag to show all the revisions.
+    #[arg(long, conflicts_with = "no_graph")]
+    group_by_description: bool,
     /// Render each revision using the given template
     ///
     /// For the syntax, see https://github.com/martinvonz/jj/blob/main/docs/templates.md
     #[arg(long, short = 'T')]
     template: Option<String>,
+    #[command(flatten)]
+    template_file: TemplateFileArgs,
+    /// Show how many commits each revision is ahead of and behind the given
+    /// revision
+    ///
+    /// The counts are rendered as `+<ahead> -<behind>` in front of each
+    /// revision. This is a shorthand for the `ahead_behind(revset)` template
+    /// method.
+    #[arg(long, value_name = "REVSET")]
+    ahead_behind: Option<String>,
     /// Show patch
     #[arg(long, short = 'p')]
     patch: bool,
+    /// Show the contents of the files matching the given fileset at each
+    /// revision
+    ///
+    /// The full contents are shown, not the changes made by the revision.
+    /// Binary files are skipped.
+    #[arg(long, value_name = "FILESET", value_hint = clap::ValueHint::AnyPath)]
+    with_content: Vec<String>,
+    /// Show the conflicted files of each conflicted revision
+    ///
+    /// The files are listed under the revision along with the number of sides
+    /// of each conflict, as in `jj status`.
+    #[arg(long)]
+    conflict_detail: bool,
     #[command(flatten)]
     diff_format: DiffFormatArgs,
 }
 
+/// How spans of revisions elided from the graph are rendered.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all(deserialize = "kebab-case"))]
+pub(crate) enum ElidedStyle {
+    /// Render a synthetic node labeled "(elided revisions)"
+    Node,
+    /// Render a `~` marker line
+    Marker,
+    /// Only connect the revisions around the span
+    None,
+}
+
 #[instrument(skip_all)]
 pub(crate) fn cmd_log(
     ui: &mut Ui,
@@ -85,7 +170,14 @@ pub(crate) fn cmd_log(
     let fileset_expression = workspace_command.parse_file_patterns(&args.paths)?;
     let revset_expression = {
         // only use default revset if neither revset nor path are specified
-        let mut expression = if args.revisions.is_empty() && args.paths.is_empty() {
+        let mut expression = if args.all {
+            workspace_command.parse_revset(&RevisionArg::from(
+                command
+                    .settings()
+                    .config()
+                    .get_string("revsets.log-graph-all")?,
+            ))?
+        } else if args.revisions.is_empty() && args.paths.is_empty() {
             workspace_command
                 .parse_revset(&RevisionArg::from(command.settings().default_revset()))?
         } else if !args.revisions.is_empty() {
@@ -105,31 +197,65 @@ pub(crate) fn cmd_log(
 
     let repo = workspace_command.repo();
     let matcher = fileset_expression.to_matcher();
-    let revset = revset_expression.evaluate()?;
+    let mut revset = revset_expression.evaluate()?;
+    if args.no_elide {
+        let max_count = command
+            .settings()
+            .config()
+            .get::<usize>("ui.log-no-elide-limit")?;
+        let mut connected_expression = revset_expression.clone();
+        connected_expression.connect();
+        let connected_revset = connected_expression.evaluate()?;
+        if connected_revset.iter().nth(max_count).is_some() {
+            writeln!(
+                ui.warning_default(),
+                "Not expanding elided revisions because the graph would show more than \
+                 {max_count} revisions."
+            )?;
+            writeln!(
+                ui.hint_default(),
+                "Set `ui.log-no-elide-limit` to a larger value to show them."
+            )?;
+        } else {
+            revset = connected_revset;
+        }
+    }
 
     let store = repo.store();
     let diff_renderer = workspace_command.diff_renderer_for_log(&args.diff_format, args.patch)?;
+    let content_matcher = if args.with_content.is_empty() {
+        None
+    } else {
+        Some(
+            workspace_command
+                .parse_file_patterns(&args.with_content)?
+                .to_matcher(),
+        )
+    };
 
-    let use_elided_nodes = command
-        .settings()
-        .config()
-        .get_bool("ui.log-synthetic-elided-nodes")?;
+    let elided_style = match args.elided {
+        Some(style) => style,
+        None => elided_style_setting(command.settings().config())?,
+    };
     let with_content_format = LogContentFormat::new(ui, command.settings())?;
 
+    let limit = args.limit.or(args.deprecated_limit).unwrap_or(usize::MAX);
     let template;
     let node_template;
+    let ahead_behind_template;
     {
         let language = workspace_command.commit_template_language()?;
-        let template_string = match &args.template {
-            Some(value) => value.to_string(),
-            None => command.settings().config().get_string("templates.log")?,
-        };
-        template = workspace_command
-            .parse_template(
-                &language,
-                &template_string,
-                CommitTemplateLanguage::wrap_commit,
-            )?
+        let template_string =
+            args.template_file
+                .template_text(command, args.template.as_deref(), "templates.log")?;
+        template = template_string
+            .parse(|text| {
+                workspace_command.parse_template(
+                    &language,
+                    text,
+                    CommitTemplateLanguage::wrap_commit,
+                )
+            })?
             .labeled("log");
         node_template = workspace_command
             .parse_template(
@@ -138,7 +264,46 @@ pub(crate) fn cmd_log(
                 CommitTemplateLanguage::wrap_commit_opt,
             )?
             .labeled("node");
+        ahead_behind_template = args
+            .ahead_behind
+            .as_ref()
+            .map(|revision| -> Result<_, CommandError> {
+                let reference_ids = workspace_command
+                    .parse_revset(&RevisionArg::from(revision.clone()))?
+                    .evaluate_to_commit_ids()?
+                    .collect_vec();
+                let counter = AheadBehindCounter::new(&language, reference_ids).map_err(|_| {
+                    user_error(
+                        ErrorCode::EmptyRevset,
+                        format!(r#"Revset "{revision}" didn't resolve to any revisions"#),
+                    )
+                })?;
+                let placeholder = PropertyPlaceholder::new();
+                let property = placeholder
+                    .clone()
+                    .and_then(move |commit: Commit| Ok(counter.count(commit.id())?));
+                let template = ConcatTemplate(vec![property.into_template(), Box::new(" ")]);
+                Ok(TemplateRenderer::new(Box::new(template), placeholder).labeled("log"))
+            })
+            .transpose()?;
+        // Count all displayed commits in one go, so their shared ancestry is
+        // walked only once.
+        let ahead_behind_cache = language.keyword_cache().ahead_behind();
+        if ahead_behind_cache.is_used() {
+            let displayed_ids = if args.reversed {
+                revset.iter().reversed().take(limit).collect_vec()
+            } else {
+                revset.iter().take(limit).collect_vec()
+            };
+            ahead_behind_cache.set_batch(displayed_ids);
+        }
     }
+    let write_commit = |formatter: &mut dyn Formatter, commit: &Commit| -> io::Result<()> {
+        if let Some(template) = &ahead_behind_template {
+            template.format(commit, formatter)?;
+        }
+        template.format(commit, formatter)
+    };
 
     {
         ui.request_pager();
@@ -151,8 +316,6 @@ pub(crate) fn cmd_log(
                 "The -l shorthand is deprecated, use -n instead."
             )?;
         }
-        let limit = args.limit.or(args.deprecated_limit).unwrap_or(usize::MAX);
-
         if !args.no_graph {
             let mut graph = get_graphlog(command.settings(), formatter.raw());
             let forward_iter = TopoGroupedGraphIterator::new(revset.iter_graph());
@@ -161,7 +324,12 @@ pub(crate) fn cmd_log(
             } else {
                 Box::new(forward_iter)
             };
-            for (commit_id, edges) in iter.take(limit) {
+            let iter: Box<dyn Iterator<Item = _>> = if args.group_by_description {
+                Box::new(group_by_description(store, iter.collect())?.into_iter())
+            } else {
+                Box::new(iter.map(|(commit_id, edges)| (commit_id, edges, None)))
+            };
+            for (commit_id, edges, collapsed) in iter.take(limit) {
                 // The graph is keyed by (CommitId, is_synthetic)
                 let mut graphlog_edges = vec![];
                 // TODO: Should we update revset.iter_graph() to yield this flag instead of all
@@ -178,11 +346,11 @@ pub(crate) fn cmd_log(
                             graphlog_edges.push(Edge::Direct((edge.target, false)));
                         }
                         GraphEdgeType::Indirect => {
-                            if use_elided_nodes {
+                            if elided_style == ElidedStyle::None {
+                                graphlog_edges.push(Edge::Indirect((edge.target, false)));
+                            } else {
                                 elided_targets.push(edge.target.clone());
                                 graphlog_edges.push(Edge::Direct((edge.target, true)));
-                            } else {
-                                graphlog_edges.push(Edge::Indirect((edge.target, false)));
                             }
                         }
                     }
@@ -190,12 +358,22 @@ pub(crate) fn cmd_log(
                 if has_missing {
                     graphlog_edges.push(Edge::Missing);
                 }
+                // A collapsed run is connected through a synthetic node, which
+                // takes over the edges of the last revision in the run.
+                let (graphlog_edges, collapsed_node) = match collapsed {
+                    Some((first_id, count)) => {
+                        let collapsed_key = (first_id, true);
+                        let edges = vec![Edge::Direct(collapsed_key.clone())];
+                        (edges, Some((collapsed_key, count, graphlog_edges)))
+                    }
+                    None => (graphlog_edges, None),
+                };
                 let mut buffer = vec![];
                 let key = (commit_id, false);
                 let commit = store.get_commit(&key.0)?;
                 with_content_format.write_graph_text(
                     ui.new_formatter(&mut buffer).as_mut(),
-                    |formatter| template.format(&commit, formatter),
+                    |formatter| write_commit(formatter, &commit),
                     || graph.width(&key, &graphlog_edges),
                 )?;
                 if !buffer.ends_with(b"\n") {
@@ -205,6 +383,19 @@ pub(crate) fn cmd_log(
                     let mut formatter = ui.new_formatter(&mut buffer);
                     renderer.show_patch(ui, formatter.as_mut(), &commit, matcher.as_ref())?;
                 }
+                if let Some(content_matcher) = &content_matcher {
+                    let mut formatter = ui.new_formatter(&mut buffer);
+                    show_file_contents(
+                        formatter.as_mut(),
+                        &workspace_command,
+                        &commit,
+                        content_matcher.as_ref(),
+                    )?;
+                }
+                if args.conflict_detail {
+                    let mut formatter = ui.new_formatter(&mut buffer);
+                    show_conflict_detail(formatter.as_mut(), &workspace_command, &commit)?;
+                }
 
                 let node_symbol = format_template(ui, &Some(commit), &node_template);
                 graph.add_node(
@@ -213,17 +404,43 @@ pub(crate) fn cmd_log(
                     &node_symbol,
                     &String::from_utf8_lossy(&buffer),
                 )?;
+                if let Some((collapsed_key, count, edges)) = collapsed_node {
+                    let mut buffer = vec![];
+                    with_content_format.write_graph_text(
+                        ui.new_formatter(&mut buffer).as_mut(),
+                        |formatter| {
+                            writeln!(
+                                formatter.labeled("elided"),
+                                "({count} more {noun} with the same description)",
+                                noun = if count == 1 { "revision" } else { "revisions" }
+                            )
+                        },
+                        || graph.width(&collapsed_key, &edges),
+                    )?;
+                    graph.add_node(
+                        &collapsed_key,
+                        &edges,
+                        &format_template(ui, &None, &node_template),
+                        &String::from_utf8_lossy(&buffer),
+                    )?;
+                }
                 for elided_target in elided_targets {
                     let elided_key = (elided_target, true);
                     let real_key = (elided_key.0.clone(), false);
                     let edges = [Edge::Direct(real_key)];
                     let mut buffer = vec![];
-                    with_content_format.write_graph_text(
-                        ui.new_formatter(&mut buffer).as_mut(),
-                        |formatter| writeln!(formatter.labeled("elided"), "(elided revisions)"),
-                        || graph.width(&elided_key, &edges),
-                    )?;
-                    let node_symbol = format_template(ui, &None, &node_template);
+                    let node_symbol = if elided_style == ElidedStyle::Marker {
+                        let mut node_symbol = vec![];
+                        write!(ui.new_formatter(&mut node_symbol).labeled("elided"), "~")?;
+                        String::from_utf8(node_symbol).unwrap()
+                    } else {
+                        with_content_format.write_graph_text(
+                            ui.new_formatter(&mut buffer).as_mut(),
+                            |formatter| writeln!(formatter.labeled("elided"), "(elided revisions)"),
+                            || graph.width(&elided_key, &edges),
+                        )?;
+                        format_template(ui, &None, &node_template)
+                    };
                     graph.add_node(
                         &elided_key,
                         &edges,
@@ -241,10 +458,21 @@ pub(crate) fn cmd_log(
             for commit_or_error in iter.commits(store).take(limit) {
                 let commit = commit_or_error?;
                 with_content_format
-                    .write(formatter, |formatter| template.format(&commit, formatter))?;
+                    .write(formatter, |formatter| write_commit(formatter, &commit))?;
                 if let Some(renderer) = &diff_renderer {
                     renderer.show_patch(ui, formatter, &commit, matcher.as_ref())?;
                 }
+                if let Some(content_matcher) = &content_matcher {
+                    show_file_contents(
+                        formatter,
+                        &workspace_command,
+                        &commit,
+                        content_matcher.as_ref(),
+                    )?;
+                }
+                if args.conflict_detail {
+                    show_conflict_detail(formatter, &workspace_command, &commit)?;
+                }
             }
         }
     }
@@ -273,5 +501,185 @@ pub(crate) fn cmd_log(
         }
     }
 
+    // Tell how far behind the real operation heads the --at-op operation is.
+    if command.global_args().at_operation != "@" {
+        let repo_loader = repo.loader();
+        let head_ops = op_walk::get_current_head_ops(
+            repo_loader.op_store(),
+            repo_loader.op_heads_store().as_ref(),
+        )?;
+        let future_count = op_walk::find_descendant_ids(&head_ops, repo.operation())?.len();
+        if future_count > 0 {
+            writeln!(
+                ui.hint_default(),
+                "Showing the repo at operation {op_id}. There {verb} {future_count} newer {noun}.",
+                op_id = short_operation_hash(repo.op_id()),
+                verb = if future_count == 1 { "is" } else { "are" },
+                noun = if future_count == 1 {
+                    "operation"
+                } else {
+                    "operations"
+                },
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the contents of the files in `commit` that match `matcher`, each
+/// indented under its path.
+fn show_file_contents(
+    formatter: &mut dyn Formatter,
+    workspace_command: &WorkspaceCommandHelper,
+    commit: &Commit,
+    matcher: &dyn Matcher,
+) -> Result<(), CommandError> {
+    let store = workspace_command.repo().store();
+    for (path, value) in commit.tree()?.entries_matching(matcher) {
+        let materialized = materialize_tree_value(store, &path, value?).block_on()?;
+        let ui_path = workspace_command.format_file_path(&path);
+        let contents = match materialized {
+            MaterializedTreeValue::Absent | MaterializedTreeValue::Tree(_) => continue,
+            MaterializedTreeValue::AccessDenied(err) => {
+                writeln!(formatter, "{ui_path}: (access denied: {err})")?;
+                continue;
+            }
+            MaterializedTreeValue::File { mut reader, .. } => {
+                let mut contents = vec![];
+                reader.read_to_end(&mut contents)?;
+                contents
+            }
+            MaterializedTreeValue::Conflict { contents, .. } => contents,
+            MaterializedTreeValue::Symlink { .. } | MaterializedTreeValue::GitSubmodule(_) => {
+                writeln!(formatter, "{ui_path}: (not a file)")?;
+                continue;
+            }
+        };
+        if is_binary_content(&contents) {
+            writeln!(formatter, "{ui_path}: (binary file, not shown)")?;
+            continue;
+        }
+        writeln!(formatter, "{ui_path}:")?;
+        for line in contents.split_inclusive(|b| *b == b'\n') {
+            write!(formatter, "    ")?;
+            formatter.write_all(line)?;
+        }
+        if !contents.is_empty() && !contents.ends_with(b"\n") {
+            writeln!(formatter)?;
+        }
+    }
+    Ok(())
+}
+
+/// Maximum number of conflicted files listed by `--conflict-detail` per
+/// revision.
+const MAX_CONFLICT_DETAIL_PATHS: usize = 10;
+
+/// Writes the conflicted files of `commit` indented under it, if it has any.
+fn show_conflict_detail(
+    formatter: &mut dyn Formatter,
+    workspace_command: &WorkspaceCommandHelper,
+    commit: &Commit,
+) -> Result<(), CommandError> {
+    if !commit.has_conflict()? {
+        return Ok(());
+    }
+    let conflicts = commit.tree()?.conflicts().collect_vec();
+    let num_shown = conflicts.len().min(MAX_CONFLICT_DETAIL_PATHS);
+    let mut recorder = FormatRecorder::new();
+    print_conflicted_paths(&conflicts[..num_shown], &mut recorder, workspace_command)?;
+    write_indented(formatter, &recorder, |formatter| write!(formatter, "  "))?;
+    let num_hidden = conflicts.len() - num_shown;
+    if num_hidden > 0 {
+        write!(formatter, "  ")?;
+        writeln!(
+            formatter.labeled("conflict_description"),
+            "...and {num_hidden} more conflicted {noun}",
+            noun = if num_hidden == 1 { "file" } else { "files" }
+        )?;
+    }
     Ok(())
 }
+
+type GraphNode = (CommitId, Vec<GraphEdge<CommitId>>);
+/// Graph node with the id of the first collapsed node and the number of them.
+type GroupedGraphNode = (
+    CommitId,
+    Vec<GraphEdge<CommitId>>,
+    Option<(CommitId, usize)>,
+);
+
+/// Collapses runs of nodes whose descriptions have the same first line. Each
+/// run is emitted as its first node with the edges of the last node, along
+/// with the id of the second node and the number of the collapsed nodes.
+///
+/// Only nodes forming a single line of history are collapsed, so that no edges
+/// from the other nodes point into a run.
+fn group_by_description(
+    store: &Arc<Store>,
+    nodes: Vec<GraphNode>,
+) -> BackendResult<Vec<GroupedGraphNode>> {
+    let mut num_incoming_edges: HashMap<&CommitId, usize> = HashMap::new();
+    for (_, edges) in &nodes {
+        for edge in edges {
+            if edge.edge_type != GraphEdgeType::Missing {
+                *num_incoming_edges.entry(&edge.target).or_default() += 1;
+            }
+        }
+    }
+    let subjects: Vec<Option<String>> = nodes
+        .iter()
+        .map(|(id, _)| store.get_commit(id))
+        .map_ok(|commit| commit.description().lines().next().map(str::to_owned))
+        .try_collect()?;
+    let is_chained = |i: usize| {
+        let (_, edges) = &nodes[i];
+        let (next_id, _) = &nodes[i + 1];
+        matches!(
+            edges.as_slice(),
+            [edge] if edge.edge_type == GraphEdgeType::Direct && edge.target == *next_id
+        ) && num_incoming_edges.get(next_id) == Some(&1)
+    };
+
+    let mut grouped = vec![];
+    let mut start = 0;
+    while start < nodes.len() {
+        let mut end = start;
+        if subjects[start].is_some() {
+            while end + 1 < nodes.len() && subjects[end + 1] == subjects[start] && is_chained(end) {
+                end += 1;
+            }
+        }
+        let (id, edges) = &nodes[start];
+        if end == start {
+            grouped.push((id.clone(), edges.clone(), None));
+        } else {
+            let (first_collapsed_id, _) = &nodes[start + 1];
+            let (_, last_edges) = &nodes[end];
+            grouped.push((
+                id.clone(),
+                last_edges.clone(),
+                Some((first_collapsed_id.clone(), end - start)),
+            ));
+        }
+        start = end + 1;
+    }
+    Ok(grouped)
+}
+
+fn elided_style_setting(config: &config::Config) -> Result<ElidedStyle, CommandError> {
+    if let Some(style) = config
+        .get::<ElidedStyle>("ui.log-elided-style")
+        .optional()
+        .map_err(|err| config_error_with_message("Invalid `ui.log-elided-style`", err))?
+    {
+        return Ok(style);
+    }
+    // Older setting, used if the style isn't configured
+    if config.get_bool("ui.log-synthetic-elided-nodes")? {
+        Ok(ElidedStyle::Node)
+    } else {
+        Ok(ElidedStyle::None)
+    }
+}
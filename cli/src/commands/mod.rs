@@ -29,6 +29,7 @@ mod edit;
 mod file;
 mod fix;
 mod git;
+mod help;
 mod init;
 mod interdiff;
 mod log;
@@ -36,6 +37,7 @@ mod merge;
 mod r#move;
 mod new;
 mod next;
+mod note;
 mod obslog;
 mod operation;
 mod parallelize;
@@ -51,6 +53,7 @@ mod split;
 mod squash;
 mod status;
 mod tag;
+mod unsign;
 mod unsquash;
 mod untrack;
 mod util;
@@ -63,7 +66,7 @@ use clap::{CommandFactory, FromArgMatches, Subcommand};
 use tracing::instrument;
 
 use crate::cli_util::{Args, CommandHelper};
-use crate::command_error::{user_error_with_hint, CommandError};
+use crate::command_error::{user_error_with_hint, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 #[derive(clap::Parser, Clone, Debug)]
@@ -99,6 +102,7 @@ enum Command {
     Fix(fix::FixArgs),
     #[command(subcommand)]
     Git(git::GitCommand),
+    Help(help::HelpArgs),
     Init(init::InitArgs),
     Interdiff(interdiff::InterdiffArgs),
     Log(log::LogArgs),
@@ -117,6 +121,8 @@ enum Command {
     Move(r#move::MoveArgs),
     New(new::NewArgs),
     Next(next::NextArgs),
+    #[command(subcommand)]
+    Note(note::NoteCommand),
     Obslog(obslog::ObslogArgs),
     #[command(subcommand)]
     #[command(visible_alias = "op")]
@@ -124,6 +130,8 @@ enum Command {
     Parallelize(parallelize::ParallelizeArgs),
     Prev(prev::PrevArgs),
     Rebase(rebase::RebaseArgs),
+    /// Redo the most recently undone operation (shortcut for `jj op redo`)
+    Redo(operation::redo::OperationRedoArgs),
     Resolve(resolve::ResolveArgs),
     Restore(restore::RestoreArgs),
     #[command(
@@ -147,6 +155,7 @@ enum Command {
     Util(util::UtilCommand),
     /// Undo an operation (shortcut for `jj op undo`)
     Undo(operation::undo::OperationUndoArgs),
+    Unsign(unsign::UnsignArgs),
     Unsquash(unsquash::UnsquashArgs),
     Untrack(untrack::UntrackArgs),
     Version(version::VersionArgs),
@@ -162,7 +171,7 @@ struct DummyCommandArgs {
 }
 
 pub fn default_app() -> clap::Command {
-    Command::augment_subcommands(Args::command())
+    Command::augment_subcommands(Args::command()).disable_help_subcommand(true)
 }
 
 #[instrument(skip_all)]
@@ -189,6 +198,7 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         Command::Files(args) => file::list::deprecated_cmd_files(ui, command_helper, args),
         Command::Fix(args) => fix::cmd_fix(ui, command_helper, args),
         Command::Git(args) => git::cmd_git(ui, command_helper, args),
+        Command::Help(args) => help::cmd_help(ui, command_helper, args),
         Command::Init(args) => init::cmd_init(ui, command_helper, args),
         Command::Interdiff(args) => interdiff::cmd_interdiff(ui, command_helper, args),
         Command::Log(args) => log::cmd_log(ui, command_helper, args),
@@ -196,11 +206,13 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         Command::Move(args) => r#move::cmd_move(ui, command_helper, args),
         Command::New(args) => new::cmd_new(ui, command_helper, args),
         Command::Next(args) => next::cmd_next(ui, command_helper, args),
+        Command::Note(args) => note::cmd_note(ui, command_helper, args),
         Command::Obslog(args) => obslog::cmd_obslog(ui, command_helper, args),
         Command::Operation(args) => operation::cmd_operation(ui, command_helper, args),
         Command::Parallelize(args) => parallelize::cmd_parallelize(ui, command_helper, args),
         Command::Prev(args) => prev::cmd_prev(ui, command_helper, args),
         Command::Rebase(args) => rebase::cmd_rebase(ui, command_helper, args),
+        Command::Redo(args) => operation::redo::cmd_op_redo(ui, command_helper, args),
         Command::Resolve(args) => resolve::cmd_resolve(ui, command_helper, args),
         Command::Restore(args) => restore::cmd_restore(ui, command_helper, args),
         Command::Revert(_args) => revert(),
@@ -213,6 +225,7 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         Command::Status(args) => status::cmd_status(ui, command_helper, args),
         Command::Tag(args) => tag::cmd_tag(ui, command_helper, args),
         Command::Undo(args) => operation::undo::cmd_op_undo(ui, command_helper, args),
+        Command::Unsign(args) => unsign::cmd_unsign(ui, command_helper, args),
         Command::Unsquash(args) => unsquash::cmd_unsquash(ui, command_helper, args),
         Command::Untrack(args) => untrack::cmd_untrack(ui, command_helper, args),
         Command::Util(args) => util::cmd_util(ui, command_helper, args),
@@ -223,6 +236,7 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
 
 fn revert() -> Result<(), CommandError> {
     Err(user_error_with_hint(
+        ErrorCode::InvalidArgument,
         "No such subcommand: revert",
         "Consider `jj backout` or `jj restore`",
     ))
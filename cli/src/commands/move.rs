@@ -18,7 +18,7 @@ use tracing::instrument;
 
 use super::squash::{move_diff, SquashedDescription};
 use crate::cli_util::{CommandHelper, RevisionArg};
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 /// Move changes from one revision into another (DEPRECATED, use `jj squash`)
@@ -76,7 +76,10 @@ pub(crate) fn cmd_move(
     let destination =
         workspace_command.resolve_single_rev(args.to.as_ref().unwrap_or(&RevisionArg::AT))?;
     if source.id() == destination.id() {
-        return Err(user_error("Source and destination cannot be the same."));
+        return Err(user_error(
+            ErrorCode::InvalidArgument,
+            "Source and destination cannot be the same.",
+        ));
     }
     let matcher = workspace_command
         .parse_file_patterns(&args.paths)?
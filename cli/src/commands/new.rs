@@ -16,16 +16,17 @@ use std::collections::HashSet;
 use std::io::Write;
 use std::rc::Rc;
 
+use indexmap::IndexSet;
 use itertools::Itertools;
 use jj_lib::backend::CommitId;
-use jj_lib::commit::CommitIteratorExt;
+use jj_lib::commit::{Commit, CommitIteratorExt};
 use jj_lib::repo::{ReadonlyRepo, Repo};
 use jj_lib::revset::{RevsetExpression, RevsetIteratorExt};
 use jj_lib::rewrite::{merge_commit_trees, rebase_commit};
 use tracing::instrument;
 
-use crate::cli_util::{short_commit_hash, CommandHelper, RevisionArg};
-use crate::command_error::{user_error, CommandError};
+use crate::cli_util::{short_commit_hash, CommandHelper, RevisionArg, WorkspaceCommandHelper};
+use crate::command_error::{user_error, CommandError, ErrorCode};
 use crate::description_util::join_message_paragraphs;
 use crate::ui::Ui;
 
@@ -55,6 +56,10 @@ pub(crate) struct NewArgs {
     #[arg(long, short = 'L', hide = true)]
     allow_large_revsets: bool,
     /// Do not edit the newly created change
+    ///
+    /// The working copy stays where it is, so nothing needs to be checked out.
+    /// Descendants are still rebased onto the new change when used with
+    /// `--insert-before` or `--insert-after`.
     #[arg(long, conflicts_with = "_edit")]
     no_edit: bool,
     /// No-op flag to pair with --no-edit
@@ -86,6 +91,7 @@ pub(crate) fn cmd_new(
 ) -> Result<(), CommandError> {
     if args.allow_large_revsets {
         return Err(user_error(
+            ErrorCode::InvalidArgument,
             "--allow-large-revsets has been deprecated.
 Please use `jj new 'all:x|y'` instead of `jj new --allow-large-revsets x y`.",
         ));
@@ -94,20 +100,19 @@ Please use `jj new 'all:x|y'` instead of `jj new --allow-large-revsets x y`.",
 
     let parent_commits;
     let parent_commit_ids: Vec<CommitId>;
+    // Parents of the children which will be replaced by the new commit
+    let replaced_parent_ids: HashSet<CommitId>;
     let children_commits;
     let mut advance_branches_target = None;
     let mut advanceable_branches = vec![];
 
     if !args.insert_before.is_empty() && !args.insert_after.is_empty() {
-        parent_commits = workspace_command
-            .resolve_some_revsets_default_single(&args.insert_after)?
-            .into_iter()
-            .collect_vec();
+        let target_commits = resolve_insertion_points(&workspace_command, &args.insert_after)?;
+        replaced_parent_ids = target_commits.iter().ids().cloned().collect();
+        parent_commits =
+            select_commits(&workspace_command, target_commits, RevsetExpression::heads)?;
         parent_commit_ids = parent_commits.iter().ids().cloned().collect();
-        children_commits = workspace_command
-            .resolve_some_revsets_default_single(&args.insert_before)?
-            .into_iter()
-            .collect_vec();
+        children_commits = resolve_insertion_points(&workspace_command, &args.insert_before)?;
         let children_commit_ids = children_commits.iter().ids().cloned().collect();
         let children_expression = RevsetExpression::commits(children_commit_ids);
         let parents_expression = RevsetExpression::commits(parent_commit_ids.clone());
@@ -121,10 +126,7 @@ Please use `jj new 'all:x|y'` instead of `jj new --allow-large-revsets x y`.",
         // command line, add it between the changes' parents and the changes.
         // The parents of the new commit will be the parents of the target commits
         // which are not descendants of other target commits.
-        children_commits = workspace_command
-            .resolve_some_revsets_default_single(&args.insert_before)?
-            .into_iter()
-            .collect_vec();
+        children_commits = resolve_insertion_points(&workspace_command, &args.insert_before)?;
         let children_commit_ids = children_commits.iter().ids().cloned().collect();
         workspace_command.check_rewritable(&children_commit_ids)?;
         let children_expression = RevsetExpression::commits(children_commit_ids);
@@ -145,19 +147,21 @@ Please use `jj new 'all:x|y'` instead of `jj new --allow-large-revsets x y`.",
             .iter()
             .map(|commit_id| workspace_command.repo().store().get_commit(commit_id))
             .try_collect()?;
+        replaced_parent_ids = parent_commit_ids.iter().cloned().collect();
     } else if !args.insert_after.is_empty() {
-        parent_commits = workspace_command
-            .resolve_some_revsets_default_single(&args.insert_after)?
-            .into_iter()
-            .collect_vec();
+        let target_commits = resolve_insertion_points(&workspace_command, &args.insert_after)?;
+        replaced_parent_ids = target_commits.iter().ids().cloned().collect();
+        let targets_expression =
+            RevsetExpression::commits(replaced_parent_ids.iter().cloned().collect());
+        parent_commits =
+            select_commits(&workspace_command, target_commits, RevsetExpression::heads)?;
         parent_commit_ids = parent_commits.iter().ids().cloned().collect();
-        let parents_expression = RevsetExpression::commits(parent_commit_ids.clone());
         // Each child of the targets will be rebased: its set of parents will be updated
         // so that the targets are replaced by the new commit.
         // Exclude children that are ancestors of the new commit
-        let children_expression = parents_expression
+        let children_expression = targets_expression
             .children()
-            .minus(&parents_expression.ancestors());
+            .minus(&targets_expression.ancestors());
         children_commits = children_expression
             .evaluate_programmatic(workspace_command.repo().as_ref())?
             .iter()
@@ -169,6 +173,7 @@ Please use `jj new 'all:x|y'` instead of `jj new --allow-large-revsets x y`.",
             .into_iter()
             .collect_vec();
         parent_commit_ids = parent_commits.iter().ids().cloned().collect();
+        replaced_parent_ids = HashSet::new();
         children_commits = vec![];
 
         let should_advance_branches = parent_commits.len() == 1;
@@ -180,8 +185,6 @@ Please use `jj new 'all:x|y'` instead of `jj new --allow-large-revsets x y`.",
     };
     workspace_command.check_rewritable(children_commits.iter().ids())?;
 
-    let parent_commit_ids_set: HashSet<CommitId> = parent_commit_ids.iter().cloned().collect();
-
     let mut tx = workspace_command.start_transaction();
     let merged_tree = merge_commit_trees(tx.repo(), &parent_commits)?;
     let new_commit = tx
@@ -195,7 +198,7 @@ Please use `jj new 'all:x|y'` instead of `jj new --allow-large-revsets x y`.",
         let new_parent_ids = child_commit
             .parent_ids()
             .iter()
-            .filter(|id| !parent_commit_ids_set.contains(id))
+            .filter(|id| !replaced_parent_ids.contains(id))
             .cloned()
             .chain(std::iter::once(new_commit.id().clone()))
             .collect_vec();
@@ -232,6 +235,45 @@ Please use `jj new 'all:x|y'` instead of `jj new --allow-large-revsets x y`.",
     Ok(())
 }
 
+/// Resolves the given revsets, each of which may evaluate to any number of
+/// commits. The order of the input revsets is preserved.
+fn resolve_insertion_points(
+    workspace_command: &WorkspaceCommandHelper,
+    revision_args: &[RevisionArg],
+) -> Result<Vec<Commit>, CommandError> {
+    let mut commits = IndexSet::new();
+    for revision_arg in revision_args {
+        for commit in workspace_command
+            .parse_revset(revision_arg)?
+            .evaluate_to_commits()?
+        {
+            commits.insert(commit?);
+        }
+    }
+    if commits.is_empty() {
+        return Err(user_error(ErrorCode::EmptyRevset, "Empty revision set"));
+    }
+    Ok(commits.into_iter().collect())
+}
+
+/// Keeps the commits selected by `select`, e.g. the heads, so that commits
+/// related to each other don't create redundant edges.
+fn select_commits(
+    workspace_command: &WorkspaceCommandHelper,
+    commits: Vec<Commit>,
+    select: impl FnOnce(&Rc<RevsetExpression>) -> Rc<RevsetExpression>,
+) -> Result<Vec<Commit>, CommandError> {
+    let expression = RevsetExpression::commits(commits.iter().ids().cloned().collect());
+    let selected: HashSet<CommitId> = select(&expression)
+        .evaluate_programmatic(workspace_command.repo().as_ref())?
+        .iter()
+        .collect();
+    Ok(commits
+        .into_iter()
+        .filter(|commit| selected.contains(commit.id()))
+        .collect())
+}
+
 /// Ensure that there is no possible cycle between the potential children and
 /// parents of the new commit.
 fn ensure_no_commit_loop(
@@ -245,11 +287,14 @@ fn ensure_no_commit_loop(
         .iter()
         .next()
     {
-        return Err(user_error(format!(
+        return Err(user_error(
+            ErrorCode::CommitLoop,
+            format!(
             "Refusing to create a loop: commit {} would be both an ancestor and a descendant of \
              the new commit",
             short_commit_hash(&commit_id),
-        )));
+        ),
+        ));
     }
     Ok(())
 }
@@ -20,7 +20,7 @@ use jj_lib::repo::Repo;
 use jj_lib::revset::{RevsetExpression, RevsetFilterPredicate, RevsetIteratorExt};
 
 use crate::cli_util::{short_commit_hash, CommandHelper, WorkspaceCommandHelper};
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 /// Move the working-copy commit to the child revision
@@ -96,7 +96,10 @@ pub fn choose_commit<'a>(
         None,
     )?;
     if choice == "q" {
-        return Err(user_error("ambiguous target commit"));
+        return Err(user_error(
+            ErrorCode::MultipleRevisions,
+            "ambiguous target commit",
+        ));
     }
 
     Ok(&commits[choice.parse::<usize>().unwrap() - 1])
@@ -108,9 +111,12 @@ pub(crate) fn cmd_next(
     args: &NextArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
-    let current_wc_id = workspace_command
-        .get_wc_commit_id()
-        .ok_or_else(|| user_error("This command requires a working copy"))?;
+    let current_wc_id = workspace_command.get_wc_commit_id().ok_or_else(|| {
+        user_error(
+            ErrorCode::NoWorkingCopy,
+            "This command requires a working copy",
+        )
+    })?;
     let edit = args.edit
         || !workspace_command
             .repo()
@@ -147,11 +153,14 @@ pub(crate) fn cmd_next(
         [target] => target,
         [] => {
             // We found no descendant.
-            return Err(user_error(format!(
-                "No descendant found {} commit{} forward",
-                args.offset,
-                if args.offset > 1 { "s" } else { "" }
-            )));
+            return Err(user_error(
+                ErrorCode::EmptyRevset,
+                format!(
+                    "No descendant found {} commit{} forward",
+                    args.offset,
+                    if args.offset > 1 { "s" } else { "" }
+                ),
+            ));
         }
         commits => choose_commit(ui, &workspace_command, "next", commits)?,
     };
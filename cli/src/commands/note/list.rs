@@ -0,0 +1,69 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jj_lib::repo::Repo as _;
+use tracing::instrument;
+
+use crate::cli_util::{short_change_hash, CommandHelper};
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// List notes attached to changes
+///
+/// Each note is printed below the commit of its change.
+#[derive(clap::Args, Clone, Debug)]
+pub struct NoteListArgs {
+    /// Also list notes of changes without visible commits (e.g. abandoned
+    /// changes)
+    #[arg(long, short)]
+    all: bool,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_note_list(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &NoteListArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo();
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    let formatter = formatter.as_mut();
+    for (change_id, note) in repo.view().notes() {
+        let commit_ids = repo.resolve_change_id(change_id).unwrap_or_default();
+        if commit_ids.is_empty() {
+            if !args.all {
+                continue;
+            }
+            write!(
+                formatter.labeled("change_id"),
+                "{}",
+                short_change_hash(change_id)
+            )?;
+            writeln!(formatter, " (no visible commit)")?;
+        } else {
+            for commit_id in &commit_ids {
+                let commit = repo.store().get_commit(commit_id)?;
+                workspace_command.write_commit_summary(formatter, &commit)?;
+                writeln!(formatter)?;
+            }
+        }
+        for line in note.lines() {
+            writeln!(formatter, "  {line}")?;
+        }
+    }
+    Ok(())
+}
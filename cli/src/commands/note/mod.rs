@@ -0,0 +1,47 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod list;
+pub mod set;
+pub mod show;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Manage notes attached to changes
+///
+/// A note is free-form text attached to a change, which is kept separately from
+/// the commit description. Since notes are keyed by change id, they follow the
+/// change when its commit is rewritten. Notes are recorded in the operation
+/// log, so changes to them can be undone.
+#[derive(clap::Subcommand, Clone, Debug)]
+pub enum NoteCommand {
+    #[command(visible_alias("l"))]
+    List(list::NoteListArgs),
+    Set(set::NoteSetArgs),
+    Show(show::NoteShowArgs),
+}
+
+pub fn cmd_note(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &NoteCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        NoteCommand::List(args) => list::cmd_note_list(ui, command, args),
+        NoteCommand::Set(args) => set::cmd_note_set(ui, command, args),
+        NoteCommand::Show(args) => show::cmd_note_show(ui, command, args),
+    }
+}
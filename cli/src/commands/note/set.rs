@@ -0,0 +1,67 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use jj_lib::object_id::ObjectId as _;
+use tracing::instrument;
+
+use crate::cli_util::{CommandHelper, RevisionArg};
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Attach a note to a change, replacing any existing note
+#[derive(clap::Args, Clone, Debug)]
+pub struct NoteSetArgs {
+    /// The revision whose change to attach the note to
+    #[arg(long, short, default_value = "@")]
+    revision: RevisionArg,
+    /// The text of the note
+    ///
+    /// An empty message removes the note.
+    #[arg(long = "message", short, value_name = "MESSAGE")]
+    message: String,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_note_set(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &NoteSetArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let commit = workspace_command.resolve_single_rev(&args.revision)?;
+    let text = args.message.trim().to_owned();
+    let change_id = commit.change_id().clone();
+    let old_text = workspace_command.repo().view().get_note(&change_id);
+    if old_text.unwrap_or_default() == text {
+        writeln!(ui.status(), "Nothing changed.")?;
+        return Ok(());
+    }
+
+    let mut tx = workspace_command.start_transaction();
+    let removed = text.is_empty();
+    tx.mut_repo().set_note(change_id.clone(), text);
+    if let Some(mut formatter) = ui.status_formatter() {
+        if removed {
+            write!(formatter, "Removed note from ")?;
+        } else {
+            write!(formatter, "Set note on ")?;
+        }
+        tx.write_commit_summary(formatter.as_mut(), &commit)?;
+        writeln!(formatter)?;
+    }
+    tx.finish(ui, format!("set note on change {}", change_id.hex()))?;
+    Ok(())
+}
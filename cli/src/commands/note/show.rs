@@ -0,0 +1,46 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use tracing::instrument;
+
+use crate::cli_util::{CommandHelper, RevisionArg};
+use crate::command_error::CommandError;
+use crate::text_util;
+use crate::ui::Ui;
+
+/// Show the note attached to a change
+///
+/// Nothing is printed if the change has no note.
+#[derive(clap::Args, Clone, Debug)]
+pub struct NoteShowArgs {
+    /// The revision whose note to show
+    #[arg(long, short, default_value = "@")]
+    revision: RevisionArg,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_note_show(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &NoteShowArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let commit = workspace_command.resolve_single_rev(&args.revision)?;
+    if let Some(note) = workspace_command.repo().view().get_note(commit.change_id()) {
+        write!(ui.stdout(), "{}", text_util::complete_newline(note))?;
+    }
+    Ok(())
+}
@@ -20,7 +20,9 @@ use jj_lib::repo::Repo;
 use jj_lib::rewrite::rebase_to_dest_parent;
 use tracing::instrument;
 
-use crate::cli_util::{format_template, CommandHelper, LogContentFormat, RevisionArg};
+use crate::cli_util::{
+    format_template, CommandHelper, LogContentFormat, RevisionArg, TemplateFileArgs,
+};
 use crate::command_error::CommandError;
 use crate::commit_templater::CommitTemplateLanguage;
 use crate::diff_util::{DiffFormatArgs, DiffRenderer};
@@ -57,6 +59,8 @@ pub(crate) struct ObslogArgs {
     /// For the syntax, see https://github.com/martinvonz/jj/blob/main/docs/templates.md
     #[arg(long, short = 'T')]
     template: Option<String>,
+    #[command(flatten)]
+    template_file: TemplateFileArgs,
     /// Show patch compared to the previous version of this change
     ///
     /// If the previous version has different parents, it will be temporarily
@@ -86,16 +90,17 @@ pub(crate) fn cmd_obslog(
     let node_template;
     {
         let language = workspace_command.commit_template_language()?;
-        let template_string = match &args.template {
-            Some(value) => value.to_string(),
-            None => command.settings().config().get_string("templates.log")?,
-        };
-        template = workspace_command
-            .parse_template(
-                &language,
-                &template_string,
-                CommitTemplateLanguage::wrap_commit,
-            )?
+        let template_string =
+            args.template_file
+                .template_text(command, args.template.as_deref(), "templates.log")?;
+        template = template_string
+            .parse(|text| {
+                workspace_command.parse_template(
+                    &language,
+                    text,
+                    CommitTemplateLanguage::wrap_commit,
+                )
+            })?
             .labeled("log");
         node_template = workspace_command
             .parse_template(
@@ -21,7 +21,7 @@ use jj_lib::op_walk;
 use jj_lib::operation::Operation;
 
 use crate::cli_util::{short_operation_hash, CommandHelper};
-use crate::command_error::{user_error, user_error_with_hint, CommandError};
+use crate::command_error::{user_error, user_error_with_hint, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 /// Abandon operation history
@@ -55,7 +55,10 @@ pub fn cmd_op_abandon(
     // with the current head.
     let head_op_str = &command.global_args().at_operation;
     if head_op_str != "@" {
-        return Err(user_error("--at-op is not respected"));
+        return Err(user_error(
+            ErrorCode::Unsupported,
+            "--at-op is not respected",
+        ));
     }
     let current_head_op = op_walk::resolve_op_for_load(repo_loader, head_op_str)?;
     let resolve_op = |op_str| op_walk::resolve_op_at(op_store, &current_head_op, op_str);
@@ -78,15 +81,26 @@ pub fn cmd_op_abandon(
             let op = resolve_op(&args.operation)?;
             let parent_ops: Vec<_> = op.parents().try_collect()?;
             let parent_op = match parent_ops.len() {
-                0 => return Err(user_error("Cannot abandon the root operation")),
+                0 => {
+                    return Err(user_error(
+                        ErrorCode::InvalidOperation,
+                        "Cannot abandon the root operation",
+                    ))
+                }
                 1 => parent_ops.into_iter().next().unwrap(),
-                _ => return Err(user_error("Cannot abandon a merge operation")),
+                _ => {
+                    return Err(user_error(
+                        ErrorCode::Unsupported,
+                        "Cannot abandon a merge operation",
+                    ))
+                }
             };
             (parent_op, op)
         };
 
     if abandon_head_op == current_head_op {
         return Err(user_error_with_hint(
+            ErrorCode::InvalidOperation,
             "Cannot abandon the current operation",
             "Run `jj undo` to revert the current operation, then use `jj op abandon`",
         ));
@@ -0,0 +1,133 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io;
+
+use itertools::Itertools as _;
+use jj_lib::operation::Operation;
+
+use super::show::{merged_parents_view, write_changes_summary};
+use crate::cli_util::{short_operation_hash, CommandHelper};
+use crate::command_error::CommandError;
+use crate::diff_util::{self, DiffFormatArgs, DiffRenderer};
+use crate::formatter::Formatter;
+use crate::ui::Ui;
+
+/// Compare the repo state at two operations
+///
+/// Prints a summary of the changes to the commits, branches, and working
+/// copies between the `--from` and `--to` operations. Unlike `jj op show`, the
+/// operations don't need to be adjacent, so the combined changes of several
+/// operations can be shown at once.
+///
+/// With `--patch` or any of the diff format options, the content changes of
+/// the commits are shown too.
+#[derive(clap::Args, Clone, Debug)]
+pub struct OperationDiffArgs {
+    /// Show the changes from this operation [default: the parents of `--to`]
+    #[arg(long)]
+    from: Option<String>,
+    /// Show the changes to this operation
+    #[arg(long, default_value = "@")]
+    to: String,
+    /// Show the content changes of the commits that were changed
+    ///
+    /// A rewritten commit is compared to its version at the `--from`
+    /// operation. If that version has different parents, it will be
+    /// temporarily rebased to the parents of the new version, so the diff is
+    /// not contaminated by unrelated changes. A newly created commit shows its
+    /// changes as added, and an abandoned commit shows its changes as removed.
+    #[arg(long, short = 'p')]
+    patch: bool,
+    #[command(flatten)]
+    diff_format: DiffFormatArgs,
+}
+
+pub fn cmd_op_diff(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &OperationDiffArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo();
+    let repo_loader = repo.loader();
+    let to_op = workspace_command.resolve_single_op(&args.to)?;
+    let to_repo = repo_loader.load_at(&to_op)?;
+    let (from_ops, from_view) = if let Some(from) = &args.from {
+        let from_op = workspace_command.resolve_single_op(from)?;
+        let from_view = repo_loader.load_at(&from_op)?.view().clone();
+        (vec![from_op], from_view)
+    } else {
+        let parent_ops: Vec<Operation> = to_op.parents().try_collect()?;
+        match merged_parents_view(command, repo, &parent_ops)? {
+            Some(view) => (parent_ops, view),
+            // The root operation is only compared to itself.
+            None => (vec![to_op.clone()], to_repo.view().clone()),
+        }
+    };
+
+    // The commits on both sides need to be indexed, even if the operations
+    // have diverged, so the indexes are merged. The view is kept as it was at
+    // the `--to` operation.
+    let mut tx = to_repo.start_transaction(command.settings());
+    for from_op in &from_ops {
+        tx.merge_operation(from_op.clone())?;
+    }
+    tx.mut_repo().set_view(to_repo.view().store_view().clone());
+    let merged_repo = tx.repo();
+
+    let diff_formats =
+        diff_util::diff_formats_for_log(command.settings(), &args.diff_format, args.patch)?;
+    let diff_renderer = (!diff_formats.is_empty()).then(|| {
+        DiffRenderer::new(
+            merged_repo,
+            workspace_command.path_converter(),
+            diff_formats,
+        )
+    });
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    let formatter = formatter.as_mut();
+    for from_op in &from_ops {
+        write!(formatter, "From operation: ")?;
+        write_operation_line(formatter, from_op)?;
+    }
+    write!(formatter, "  To operation: ")?;
+    write_operation_line(formatter, &to_op)?;
+    writeln!(formatter)?;
+
+    let has_changes = write_changes_summary(
+        ui,
+        formatter,
+        &workspace_command,
+        merged_repo,
+        &from_view,
+        diff_renderer.as_ref(),
+    )?;
+    if !has_changes {
+        writeln!(formatter, "No changes.")?;
+    }
+    Ok(())
+}
+
+fn write_operation_line(formatter: &mut dyn Formatter, op: &Operation) -> io::Result<()> {
+    write!(
+        formatter.labeled("operation_id"),
+        "{}",
+        short_operation_hash(op.id())
+    )?;
+    let description = &op.metadata().description;
+    writeln!(formatter, " {}", description.lines().next().unwrap_or(""))
+}
@@ -12,12 +12,29 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use itertools::Itertools as _;
+use jj_lib::backend::Timestamp;
+use jj_lib::graph::{GraphEdge, GraphEdgeType, ReverseGraphIterator};
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_store::OperationId;
 use jj_lib::op_walk;
+use jj_lib::operation::Operation;
+use jj_lib::opset::{self, OpsetExpression, OpsetMatchContext, OpsetParseContext};
+use jj_lib::repo::RepoLoader;
+use jj_lib::revset::RevsetExpression;
 
-use crate::cli_util::{format_template, CommandHelper, LogContentFormat};
-use crate::command_error::CommandError;
+use crate::cli_util::{
+    format_template, short_operation_hash, CommandHelper, LogContentFormat, TemplateFileArgs,
+};
+use crate::command_error::{internal_error, CommandError};
+use crate::formatter::Formatter;
 use crate::graphlog::{get_graphlog, Edge};
 use crate::operation_templater::OperationTemplateLanguage;
+use crate::time_util::format_rfc3339_timestamp;
 use crate::ui::Ui;
 
 /// Show the operation log
@@ -34,14 +51,233 @@ pub struct OperationLogArgs {
         value_name = "LIMIT"
     )]
     deprecated_limit: Option<usize>,
+    /// Show only the operations matching the opset expression
+    ///
+    /// For example, `-r 'command("git push") & after("2 days ago")'` shows the
+    /// recent pushes. For the syntax, see
+    /// https://github.com/martinvonz/jj/blob/main/docs/operation-log.md. In
+    /// the graph, each operation is connected to its closest shown ancestors.
+    #[arg(long = "operations", short = 'r', value_name = "OPSET")]
+    operations: Option<String>,
+    /// Show all operations, including the ones that only snapshotted the
+    /// working copy
+    ///
+    /// By default, such operations are hidden unless they're the current
+    /// operation.
+    #[arg(long, conflicts_with = "operations")]
+    all: bool,
     /// Don't show the graph, show a flat list of operations
     #[arg(long)]
     no_graph: bool,
+    /// Show operations in the opposite order (older operations first)
+    ///
+    /// The limit is applied after reordering, so `--reversed -n 3` shows the
+    /// three oldest operations.
+    #[arg(long)]
+    reversed: bool,
     /// Render each operation using the given template
     ///
     /// For the syntax, see https://github.com/martinvonz/jj/blob/main/docs/templates.md
     #[arg(long, short = 'T')]
     template: Option<String>,
+    #[command(flatten)]
+    template_file: TemplateFileArgs,
+    /// Also show operations made after the one selected by `--at-op`
+    ///
+    /// These operations are rendered dimmed, and the `@` marker stays on the
+    /// selected operation.
+    #[arg(long)]
+    show_future: bool,
+    /// Show only the operations made since the current head operations
+    /// diverged
+    ///
+    /// The operations are shown down to the closest common ancestor of the
+    /// concurrent head operations, whose ID is printed before the log. If
+    /// there is a single head operation, nothing is shown.
+    #[arg(long, conflicts_with = "show_future")]
+    since_divergence: bool,
+    /// Show how many commits each operation added and removed
+    ///
+    /// The total is the number of visible commits after the operation. This
+    /// loads the repo at each operation, so it's slower than the plain log.
+    #[arg(long)]
+    stat: bool,
+    /// Print operations in a machine-readable format instead of the template
+    ///
+    /// With `tsv`, each operation is printed on one line with these
+    /// tab-separated columns: the full operation id, the full parent operation
+    /// ids (separated by spaces), the start and end times (RFC 3339), the user
+    /// (`username@hostname`), and the command line (or the operation
+    /// description if no command line was recorded). The columns are kept
+    /// stable across releases.
+    ///
+    /// Backslashes, tabs, carriage returns, and newlines in the user and
+    /// command are escaped as `\\`, `\t`, `\r`, and `\n`.
+    #[arg(
+        long,
+        value_enum,
+        conflicts_with_all = ["template", "template_file", "stat"]
+    )]
+    format: Option<OperationLogFormat>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum OperationLogFormat {
+    /// Tab-separated values, one operation per line
+    Tsv,
+}
+
+/// Escapes the characters that would break a tab-separated line.
+fn escape_tsv_field(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn write_op_tsv(formatter: &mut dyn Formatter, op: &Operation) -> Result<(), CommandError> {
+    let metadata = op.metadata();
+    let parent_ids = op.parent_ids().iter().map(|id| id.hex()).join(" ");
+    let command = metadata.tags.get("args").unwrap_or(&metadata.description);
+    writeln!(
+        formatter,
+        "{}\t{}\t{}\t{}\t{}\t{}",
+        op.id().hex(),
+        parent_ids,
+        format_rfc3339_timestamp(&metadata.start_time).map_err(internal_error)?,
+        format_rfc3339_timestamp(&metadata.end_time).map_err(internal_error)?,
+        escape_tsv_field(&format!("{}@{}", metadata.username, metadata.hostname)),
+        escape_tsv_field(command),
+    )?;
+    Ok(())
+}
+
+/// Selects the operations to show, and connects each of them to its closest
+/// shown ancestors. Operations are tested as the log is walked, so the log can
+/// be printed without walking the whole history.
+struct OperationFilter<'a> {
+    opset_context: OpsetMatchContext<'a>,
+    /// If set, only the operations matching the opset are shown.
+    opset: Option<OpsetExpression>,
+    /// Shows trivial operations, unless an opset is given.
+    show_trivial: bool,
+    /// Operations that are shown even if they're trivial.
+    pinned_op_ids: HashSet<OperationId>,
+    /// Operations in the log. If unset, all ancestors are.
+    log_op_ids: Option<HashSet<OperationId>>,
+    is_shown_cache: RefCell<HashMap<OperationId, bool>>,
+}
+
+impl OperationFilter<'_> {
+    fn shows_all(&self) -> bool {
+        self.opset.is_none() && self.show_trivial
+    }
+
+    fn is_in_log(&self, id: &OperationId) -> bool {
+        match &self.log_op_ids {
+            Some(ids) => ids.contains(id),
+            None => true,
+        }
+    }
+
+    fn is_shown(&self, op: &Operation) -> Result<bool, CommandError> {
+        if let Some(&is_shown) = self.is_shown_cache.borrow().get(op.id()) {
+            return Ok(is_shown);
+        }
+        let is_shown = if let Some(opset) = &self.opset {
+            opset.matches(&self.opset_context, op)?
+        } else {
+            self.show_trivial || self.pinned_op_ids.contains(op.id()) || !op.is_trivial()
+        };
+        self.is_shown_cache
+            .borrow_mut()
+            .insert(op.id().clone(), is_shown);
+        Ok(is_shown)
+    }
+
+    fn parent_edges(&self, op: &Operation) -> Result<Vec<GraphEdge<OperationId>>, CommandError> {
+        if self.shows_all() {
+            return Ok(op
+                .parent_ids()
+                .iter()
+                .filter(|id| self.is_in_log(id))
+                .cloned()
+                .map(GraphEdge::direct)
+                .collect());
+        }
+        // Walk through the hidden ancestors until shown ones are reached.
+        let mut edges = vec![];
+        let mut visited = HashSet::new();
+        let mut to_visit = vec![(op.clone(), false)];
+        while let Some((op, is_indirect)) = to_visit.pop() {
+            let mut hidden_parents = vec![];
+            for parent in op.parents() {
+                let parent = parent?;
+                if !self.is_in_log(parent.id()) || !visited.insert(parent.id().clone()) {
+                    continue;
+                }
+                if !self.is_shown(&parent)? {
+                    hidden_parents.push((parent, true));
+                } else if is_indirect {
+                    edges.push(GraphEdge::indirect(parent.id().clone()));
+                } else {
+                    edges.push(GraphEdge::direct(parent.id().clone()));
+                }
+            }
+            to_visit.extend(hidden_parents.into_iter().rev());
+        }
+        Ok(edges)
+    }
+}
+
+/// Commits added and removed by an operation compared to its parents.
+struct OperationStat {
+    added: usize,
+    removed: usize,
+    total: usize,
+}
+
+fn compute_operation_stat(
+    repo_loader: &RepoLoader,
+    op: &Operation,
+) -> Result<OperationStat, CommandError> {
+    if op.id() == repo_loader.op_store().root_operation_id() {
+        // The index of the root operation is empty, and there are no changes.
+        return Ok(OperationStat {
+            added: 0,
+            removed: 0,
+            total: 0,
+        });
+    }
+    let repo = repo_loader.load_at(op)?;
+    // The parent views are combined so that commits shared by the parents of a
+    // merge operation are counted once. Hidden commits are still indexed, so
+    // they can be evaluated against the repo at this operation.
+    let mut old_head_ids = vec![];
+    for parent_op in op.parents() {
+        old_head_ids.extend(parent_op?.view()?.heads().iter().cloned());
+    }
+    // The root commit is never counted.
+    let old_heads = RevsetExpression::commits(old_head_ids).union(&RevsetExpression::root());
+    let new_heads = RevsetExpression::commits(repo.view().heads().iter().cloned().collect());
+    let count = |expression: Rc<RevsetExpression>| -> Result<usize, CommandError> {
+        Ok(expression
+            .evaluate_programmatic(repo.as_ref())?
+            .iter()
+            .count())
+    };
+    Ok(OperationStat {
+        added: count(old_heads.range(&new_heads))?,
+        removed: count(new_heads.range(&old_heads))?,
+        total: count(RevsetExpression::root().range(&new_heads))?,
+    })
 }
 
 pub fn cmd_op_log(
@@ -75,6 +311,53 @@ pub fn cmd_op_log(
         [op] => Some(op.id()),
         _ => None,
     };
+    // If an older operation was selected by --at-op, the operations made after
+    // it are found by walking from the real head operations.
+    let at_op = match &*head_ops {
+        [op] if head_op_str != "@" => Some(op),
+        _ => None,
+    };
+    let real_head_ops = if at_op.is_some() {
+        op_walk::get_current_head_ops(
+            repo_loader.op_store(),
+            repo_loader.op_heads_store().as_ref(),
+        )?
+    } else {
+        vec![]
+    };
+    let divergence_base = if args.since_divergence {
+        if head_ops.len() < 2 {
+            writeln!(
+                ui.status(),
+                "No divergence: there is a single head operation."
+            )?;
+            return Ok(());
+        }
+        let base_op = op_walk::find_closest_common_ancestor(&head_ops)?
+            .ok_or_else(|| internal_error("Head operations have no common ancestor"))?;
+        writeln!(
+            ui.status(),
+            "{} concurrent head operations diverged after operation {}",
+            head_ops.len(),
+            short_operation_hash(base_op.id())
+        )?;
+        Some(base_op)
+    } else {
+        None
+    };
+    let opset = args
+        .operations
+        .as_ref()
+        .map(|text| {
+            let context = OpsetParseContext {
+                now: command
+                    .settings()
+                    .operation_timestamp()
+                    .unwrap_or_else(Timestamp::now),
+            };
+            opset::parse(text, &context)
+        })
+        .transpose()?;
     let with_content_format = LogContentFormat::new(ui, command.settings())?;
 
     let template;
@@ -85,17 +368,20 @@ pub fn cmd_op_log(
             current_op_id,
             command.operation_template_extensions(),
         );
-        let text = match &args.template {
-            Some(value) => value.to_owned(),
-            None => command.settings().config().get_string("templates.op_log")?,
-        };
-        template = command
-            .parse_template(
-                ui,
-                &language,
-                &text,
-                OperationTemplateLanguage::wrap_operation,
-            )?
+        let text = args.template_file.template_text(
+            command,
+            args.template.as_deref(),
+            "templates.op_log",
+        )?;
+        template = text
+            .parse(|text| {
+                command.parse_template(
+                    ui,
+                    &language,
+                    text,
+                    OperationTemplateLanguage::wrap_operation,
+                )
+            })?
             .labeled("op_log");
         op_node_template = command
             .parse_template(
@@ -117,19 +403,126 @@ pub fn cmd_op_log(
         )?;
     }
     let limit = args.limit.or(args.deprecated_limit).unwrap_or(usize::MAX);
-    let iter = op_walk::walk_ancestors(&head_ops).take(limit);
-    if !args.no_graph {
+    let iter: Box<dyn Iterator<Item = _>> = match at_op {
+        Some(at_op) if args.show_future => {
+            let future_op_ids = op_walk::find_descendant_ids(&real_head_ops, at_op)?;
+            let all_heads = real_head_ops.iter().chain([at_op]).cloned().collect_vec();
+            Box::new(op_walk::walk_ancestors(&all_heads).map_ok(move |op| {
+                let is_future = future_op_ids.contains(op.id());
+                (op, is_future)
+            }))
+        }
+        _ => Box::new(op_walk::walk_ancestors(&head_ops).map_ok(|op| (op, false))),
+    };
+    // Operations since the divergence, which is everything but the ancestors
+    // of the base operation.
+    let (iter, divergence_op_ids): (Box<dyn Iterator<Item = _>>, _) =
+        if let Some(base_op) = &divergence_base {
+            let ops: Vec<Operation> = op_walk::walk_ancestors_relative_to(&head_ops, base_op)
+                .filter_ok(|(op, is_ancestor)| !is_ancestor || op == base_op)
+                .map_ok(|(op, _)| op)
+                .try_collect()?;
+            let op_ids: HashSet<_> = ops.iter().map(|op| op.id().clone()).collect();
+            (
+                Box::new(ops.into_iter().map(|op| Ok((op, false)))),
+                Some(op_ids),
+            )
+        } else {
+            (iter, None)
+        };
+    let mut pinned_op_ids: HashSet<_> = head_ops.iter().map(|op| op.id().clone()).collect();
+    pinned_op_ids.extend(divergence_base.as_ref().map(|op| op.id().clone()));
+    let filter = OperationFilter {
+        opset_context: OpsetMatchContext::new(repo_loader),
+        opset,
+        show_trivial: args.all,
+        pinned_op_ids,
+        log_op_ids: divergence_op_ids,
+        is_shown_cache: RefCell::new(HashMap::new()),
+    };
+    let iter = iter.filter_map(|item| {
+        let (op, is_future) = match item {
+            Ok(item) => item,
+            Err(err) => return Some(Err(CommandError::from(err))),
+        };
+        match filter.is_shown(&op) {
+            Ok(true) => Some(Ok((op, is_future))),
+            Ok(false) => None,
+            Err(err) => Some(Err(err)),
+        }
+    });
+    let iter: Box<dyn Iterator<Item = _>> = if args.reversed {
+        let ops: Vec<(Operation, bool)> = iter.try_collect()?;
+        let graph: Vec<_> = ops
+            .iter()
+            .map(|(op, _)| Ok::<_, CommandError>((op.id().clone(), filter.parent_edges(op)?)))
+            .try_collect()?;
+        let reversed_graph = ReverseGraphIterator::new(graph);
+        let mut ops_by_id: HashMap<_, _> = ops
+            .into_iter()
+            .map(|(op, is_future)| (op.id().clone(), (op, is_future)))
+            .collect();
+        Box::new(reversed_graph.map(move |(id, edges)| {
+            let (op, is_future) = ops_by_id.remove(&id).unwrap();
+            Ok::<_, CommandError>((op, is_future, edges))
+        }))
+    } else {
+        Box::new(iter.map(|item| {
+            let (op, is_future) = item?;
+            let edges = filter.parent_edges(&op)?;
+            Ok((op, is_future, edges))
+        }))
+    };
+    let write_op = |formatter: &mut dyn Formatter,
+                    op: &Operation,
+                    is_future: bool,
+                    stat: Option<&OperationStat>| {
+        let write_content = |formatter: &mut dyn Formatter| {
+            template.format(op, formatter)?;
+            if let Some(stat) = stat {
+                formatter.with_label("stat", |formatter| {
+                    writeln!(
+                        formatter,
+                        "commits: +{} -{}, {} total",
+                        stat.added, stat.removed, stat.total
+                    )
+                })?;
+            }
+            Ok(())
+        };
+        if is_future {
+            formatter.with_label("future", write_content)
+        } else {
+            write_content(formatter)
+        }
+    };
+    let get_stat = |op: &Operation| -> Result<Option<OperationStat>, CommandError> {
+        args.stat
+            .then(|| compute_operation_stat(repo_loader, op))
+            .transpose()
+    };
+    if args.format == Some(OperationLogFormat::Tsv) {
+        for op_and_edges in iter.take(limit) {
+            let (op, _, _) = op_and_edges?;
+            write_op_tsv(formatter, &op)?;
+        }
+    } else if !args.no_graph {
         let mut graph = get_graphlog(command.settings(), formatter.raw());
-        for op in iter {
-            let op = op?;
+        for op_and_edges in iter.take(limit) {
+            let (op, is_future, op_edges) = op_and_edges?;
+            let stat = get_stat(&op)?;
             let mut edges = vec![];
-            for id in op.parent_ids() {
-                edges.push(Edge::Direct(id.clone()));
+            for edge in op_edges {
+                edges.push(match edge.edge_type {
+                    GraphEdgeType::Direct => Edge::Direct(edge.target),
+                    GraphEdgeType::Indirect => Edge::Indirect(edge.target),
+                    GraphEdgeType::Missing => Edge::Missing,
+                });
             }
             let mut buffer = vec![];
             with_content_format.write_graph_text(
                 ui.new_formatter(&mut buffer).as_mut(),
-                |formatter| template.format(&op, formatter),
+                |formatter| write_op(formatter, &op, is_future, stat.as_ref()),
                 || graph.width(op.id(), &edges),
             )?;
             if !buffer.ends_with(b"\n") {
@@ -144,9 +537,28 @@ pub fn cmd_op_log(
             )?;
         }
     } else {
-        for op in iter {
-            let op = op?;
-            with_content_format.write(formatter, |formatter| template.format(&op, formatter))?;
+        for op_and_edges in iter.take(limit) {
+            let (op, is_future, _) = op_and_edges?;
+            let stat = get_stat(&op)?;
+            with_content_format.write(formatter, |formatter| {
+                write_op(formatter, &op, is_future, stat.as_ref())
+            })?;
+        }
+    }
+
+    if let Some(at_op) = at_op.filter(|_| !args.show_future) {
+        let future_count = op_walk::find_descendant_ids(&real_head_ops, at_op)?.len();
+        if future_count > 0 {
+            writeln!(
+                ui.hint_default(),
+                "{future_count} newer {noun} not shown. Use `--show-future` to show {pronoun}.",
+                noun = if future_count == 1 {
+                    "operation is"
+                } else {
+                    "operations are"
+                },
+                pronoun = if future_count == 1 { "it" } else { "them" },
+            )?;
         }
     }
 
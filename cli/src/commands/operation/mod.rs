@@ -13,18 +13,32 @@
 // limitations under the License.
 
 mod abandon;
+mod diff;
 mod log;
+pub mod redo;
 mod restore;
+mod show;
 pub mod undo;
 
+use std::io;
+
 use abandon::{cmd_op_abandon, OperationAbandonArgs};
 use clap::Subcommand;
+use diff::{cmd_op_diff, OperationDiffArgs};
+use itertools::Itertools as _;
+use jj_lib::op_store::{OperationId, WorkspaceId};
+use jj_lib::operation::Operation;
+use jj_lib::repo::Repo as _;
 use log::{cmd_op_log, OperationLogArgs};
+use redo::{cmd_op_redo, OperationRedoArgs};
 use restore::{cmd_op_restore, OperationRestoreArgs};
+use show::{cmd_op_show, OperationShowArgs};
 use undo::{cmd_op_undo, OperationUndoArgs};
 
-use crate::cli_util::CommandHelper;
-use crate::command_error::CommandError;
+use crate::cli_util::{CommandHelper, WorkspaceCommandTransaction};
+use crate::command_error::{user_error, CommandError, ErrorCode};
+use crate::commands::git::push::GIT_PUSH_TAG;
+use crate::operation_templater::REASON_TAG;
 use crate::ui::Ui;
 
 /// Commands for working with the operation log
@@ -34,8 +48,11 @@ use crate::ui::Ui;
 #[derive(Subcommand, Clone, Debug)]
 pub enum OperationCommand {
     Abandon(OperationAbandonArgs),
+    Diff(OperationDiffArgs),
     Log(OperationLogArgs),
+    Redo(OperationRedoArgs),
     Restore(OperationRestoreArgs),
+    Show(OperationShowArgs),
     Undo(OperationUndoArgs),
 }
 
@@ -46,47 +63,165 @@ pub fn cmd_operation(
 ) -> Result<(), CommandError> {
     match subcommand {
         OperationCommand::Abandon(args) => cmd_op_abandon(ui, command, args),
+        OperationCommand::Diff(args) => cmd_op_diff(ui, command, args),
         OperationCommand::Log(args) => cmd_op_log(ui, command, args),
+        OperationCommand::Redo(args) => cmd_op_redo(ui, command, args),
         OperationCommand::Restore(args) => cmd_op_restore(ui, command, args),
+        OperationCommand::Show(args) => cmd_op_show(ui, command, args),
         OperationCommand::Undo(args) => cmd_op_undo(ui, command, args),
     }
 }
 
+/// Operation tag in which `jj undo` stores the id of the undone operation. If
+/// a range of operations was undone, the last one is stored.
+const UNDONE_OPERATION_TAG: &str = "undone-operation";
+
+/// Operation tag in which `jj redo` stores the id of the redone undo operation.
+const REDONE_OPERATION_TAG: &str = "redone-operation";
+
+/// Returns the id of the operation that `op` undid if `op` was created by `jj
+/// undo`. If a range of operations was undone, returns the last one.
+fn undone_operation_id(op: &Operation) -> Option<OperationId> {
+    let hex = op.metadata().tags.get(UNDONE_OPERATION_TAG)?;
+    OperationId::try_from_hex(hex).ok()
+}
+
+/// Returns true if `op` was created by `jj redo`.
+fn is_redo_operation(op: &Operation) -> bool {
+    op.metadata().tags.contains_key(REDONE_OPERATION_TAG)
+}
+
+/// Returns true if `op` was created by `jj git push`.
+fn is_git_push_operation(op: &Operation) -> bool {
+    op.metadata().tags.contains_key(GIT_PUSH_TAG)
+}
+
+const MAX_REASON_LEN: usize = 200;
+
+/// Records the `reason` for the operation in its metadata. Empty reasons are
+/// omitted.
+fn set_reason_tag(
+    tx: &mut WorkspaceCommandTransaction,
+    reason: Option<&str>,
+) -> Result<(), CommandError> {
+    let Some(reason) = reason.map(str::trim).filter(|reason| !reason.is_empty()) else {
+        return Ok(());
+    };
+    if reason.chars().count() > MAX_REASON_LEN {
+        return Err(user_error(
+            ErrorCode::InvalidArgument,
+            format!("The reason must not be longer than {MAX_REASON_LEN} characters"),
+        ));
+    }
+    if reason.contains('\n') {
+        return Err(user_error(
+            ErrorCode::InvalidArgument,
+            "The reason must be a single line",
+        ));
+    }
+    tx.set_tag(REASON_TAG.to_owned(), reason.to_owned());
+    Ok(())
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
 enum UndoWhatToRestore {
     /// The jj repo state and local branches
     Repo,
+    /// The commits, i.e. the visible heads, working-copy commits, tags, and
+    /// notes (the part of `repo` other than the local branches)
+    Commits,
+    /// The local branches (the part of `repo` other than the commits)
+    LocalBranches,
     /// The remote-tracking branches. Do not restore these if you'd like to push
     /// after the undo
     RemoteTracking,
 }
 
+impl UndoWhatToRestore {
+    /// Whether `what` includes this portion, either directly or through a
+    /// portion containing it.
+    fn is_included_in(self, what: &[UndoWhatToRestore]) -> bool {
+        what.contains(&self)
+            || (matches!(self, Self::Commits | Self::LocalBranches) && what.contains(&Self::Repo))
+    }
+}
+
 const DEFAULT_UNDO_WHAT: [UndoWhatToRestore; 2] =
     [UndoWhatToRestore::Repo, UndoWhatToRestore::RemoteTracking];
 
+/// Returns the workspaces other than the current one whose working-copy
+/// commits are changed by the transaction.
+///
+/// Only the current workspace's working copy is updated when the transaction
+/// is finished, so the others become stale.
+fn other_changed_workspaces(tx: &WorkspaceCommandTransaction) -> Vec<WorkspaceId> {
+    let current_workspace_id = tx.base_workspace_helper().workspace_id();
+    let old_wc_commit_ids = tx.base_repo().view().wc_commit_ids();
+    let new_wc_commit_ids = tx.repo().view().wc_commit_ids();
+    new_wc_commit_ids
+        .iter()
+        .filter(|(workspace_id, commit_id)| {
+            *workspace_id != current_workspace_id
+                && old_wc_commit_ids.get(*workspace_id) != Some(*commit_id)
+        })
+        .map(|(workspace_id, _)| workspace_id.clone())
+        .collect()
+}
+
+fn write_stale_workspaces_hint(ui: &Ui, workspace_ids: &[WorkspaceId]) -> io::Result<()> {
+    if workspace_ids.is_empty() {
+        return Ok(());
+    }
+    writeln!(
+        ui.hint_default(),
+        "The working copies of these workspaces were not updated and are now stale: {}",
+        workspace_ids.iter().map(|id| id.as_str()).join(", ")
+    )?;
+    writeln!(
+        ui.hint_no_heading(),
+        "Run `jj workspace update-stale` in them to update them."
+    )?;
+    Ok(())
+}
+
 /// Restore only the portions of the view specified by the `what` argument
 fn view_with_desired_portions_restored(
     view_being_restored: &jj_lib::op_store::View,
     current_view: &jj_lib::op_store::View,
     what: &[UndoWhatToRestore],
 ) -> jj_lib::op_store::View {
-    let repo_source = if what.contains(&UndoWhatToRestore::Repo) {
-        view_being_restored
-    } else {
-        current_view
-    };
-    let remote_source = if what.contains(&UndoWhatToRestore::RemoteTracking) {
-        view_being_restored
-    } else {
-        current_view
+    let source_for = |portion: UndoWhatToRestore| {
+        if portion.is_included_in(what) {
+            view_being_restored
+        } else {
+            current_view
+        }
     };
+    let restore_commits = UndoWhatToRestore::Commits.is_included_in(what);
+    let restore_branches = UndoWhatToRestore::LocalBranches.is_included_in(what);
+    let commits_source = source_for(UndoWhatToRestore::Commits);
+    let branches_source = source_for(UndoWhatToRestore::LocalBranches);
+    let remote_source = source_for(UndoWhatToRestore::RemoteTracking);
+    let mut head_ids = commits_source.head_ids.clone();
+    if restore_commits != restore_branches {
+        // Keep the commits the local branches point to visible.
+        head_ids.extend(
+            branches_source
+                .local_branches
+                .values()
+                .flat_map(|target| target.added_ids())
+                .cloned(),
+        );
+    }
     jj_lib::op_store::View {
-        head_ids: repo_source.head_ids.clone(),
-        local_branches: repo_source.local_branches.clone(),
-        tags: repo_source.tags.clone(),
+        head_ids,
+        local_branches: branches_source.local_branches.clone(),
+        tags: commits_source.tags.clone(),
         remote_views: remote_source.remote_views.clone(),
         git_refs: current_view.git_refs.clone(),
         git_head: current_view.git_head.clone(),
-        wc_commit_ids: repo_source.wc_commit_ids.clone(),
+        wc_commit_ids: commits_source.wc_commit_ids.clone(),
+        notes: commits_source.notes.clone(),
+        branch_descriptions: branches_source.branch_descriptions.clone(),
     }
 }
@@ -0,0 +1,172 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+
+use itertools::Itertools as _;
+use jj_lib::object_id::ObjectId;
+use jj_lib::op_store;
+use jj_lib::operation::Operation;
+use jj_lib::repo::Repo;
+
+use super::{
+    is_redo_operation, other_changed_workspaces, undone_operation_id,
+    view_with_desired_portions_restored, write_stale_workspaces_hint, DEFAULT_UNDO_WHAT,
+    REDONE_OPERATION_TAG,
+};
+use crate::cli_util::CommandHelper;
+use crate::command_error::{user_error, user_error_with_hint, CommandError, ErrorCode};
+use crate::ui::Ui;
+
+/// Redo the most recently undone operation
+///
+/// This reverts the latest `jj undo` by creating a new operation. Working-copy
+/// snapshots taken after the undo are skipped over.
+#[derive(clap::Args, Clone, Debug)]
+pub struct OperationRedoArgs {
+    /// Redo even if operations after the undo changed the same refs
+    #[arg(long)]
+    force: bool,
+}
+
+pub fn cmd_op_redo(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &OperationRedoArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let head_op = workspace_command.repo().operation().clone();
+
+    let mut op = head_op.clone();
+    let undo_op = loop {
+        if undone_operation_id(&op).is_some() {
+            break op;
+        }
+        if is_redo_operation(&op) {
+            return Err(user_error_with_hint(
+                ErrorCode::NothingToRedo,
+                "Nothing to redo",
+                "The last undo operation has already been redone",
+            ));
+        }
+        if !op.is_trivial() {
+            return Err(user_error_with_hint(
+                ErrorCode::NothingToRedo,
+                "Nothing to redo",
+                "The latest operation is not an undo operation",
+            ));
+        }
+        op = single_parent(&op)?;
+    };
+    let before_undo_op = single_parent(&undo_op)?;
+
+    if undo_op.id() != head_op.id() && !args.force {
+        let undone_refs = changed_refs(
+            before_undo_op.view()?.store_view(),
+            undo_op.view()?.store_view(),
+        );
+        let later_refs = changed_refs(undo_op.view()?.store_view(), head_op.view()?.store_view());
+        let overlapping_refs = undone_refs.intersection(&later_refs).collect_vec();
+        if !overlapping_refs.is_empty() {
+            return Err(user_error_with_hint(
+                ErrorCode::RedoConflict,
+                format!(
+                    "Operations after the undo changed the same refs: {}",
+                    overlapping_refs.iter().join(", ")
+                ),
+                "Use --force to redo anyway",
+            ));
+        }
+    }
+
+    let mut tx = workspace_command.start_transaction();
+    let repo_loader = tx.base_repo().loader();
+    let undo_repo = repo_loader.load_at(&undo_op)?;
+    let before_undo_repo = repo_loader.load_at(&before_undo_op)?;
+    tx.mut_repo().merge(&undo_repo, &before_undo_repo);
+    let new_view = view_with_desired_portions_restored(
+        tx.repo().view().store_view(),
+        tx.base_repo().view().store_view(),
+        &DEFAULT_UNDO_WHAT,
+    );
+    tx.mut_repo().set_view(new_view);
+    let stale_workspace_ids = other_changed_workspaces(&tx);
+    tx.set_tag(REDONE_OPERATION_TAG.to_owned(), undo_op.id().hex());
+    tx.finish(ui, format!("redo operation {}", undo_op.id().hex()))?;
+    write_stale_workspaces_hint(ui, &stale_workspace_ids)?;
+
+    Ok(())
+}
+
+fn single_parent(op: &Operation) -> Result<Operation, CommandError> {
+    let mut parent_ops = op.parents();
+    let Some(parent_op) = parent_ops.next().transpose()? else {
+        return Err(user_error(ErrorCode::NothingToRedo, "Nothing to redo"));
+    };
+    if parent_ops.next().is_some() {
+        return Err(user_error(
+            ErrorCode::Unsupported,
+            "Cannot redo across a merge operation",
+        ));
+    }
+    Ok(parent_op)
+}
+
+/// Returns the names of the refs which differ between the two views.
+fn changed_refs(old_view: &op_store::View, new_view: &op_store::View) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    let branch_names = old_view
+        .local_branches
+        .keys()
+        .chain(new_view.local_branches.keys());
+    for name in branch_names {
+        if old_view.local_branches.get(name) != new_view.local_branches.get(name) {
+            names.insert(name.clone());
+        }
+    }
+    for name in old_view.tags.keys().chain(new_view.tags.keys()) {
+        if old_view.tags.get(name) != new_view.tags.get(name) {
+            names.insert(format!("tag {name}"));
+        }
+    }
+    let remote_names = old_view
+        .remote_views
+        .keys()
+        .chain(new_view.remote_views.keys());
+    for remote_name in remote_names {
+        let old_branches = old_view.remote_views.get(remote_name).map(|v| &v.branches);
+        let new_branches = new_view.remote_views.get(remote_name).map(|v| &v.branches);
+        for name in old_branches
+            .into_iter()
+            .chain(new_branches)
+            .flat_map(|b| b.keys())
+        {
+            let old_ref = old_branches.and_then(|b| b.get(name));
+            let new_ref = new_branches.and_then(|b| b.get(name));
+            if old_ref != new_ref {
+                names.insert(format!("{name}@{remote_name}"));
+            }
+        }
+    }
+    let workspace_ids = old_view
+        .wc_commit_ids
+        .keys()
+        .chain(new_view.wc_commit_ids.keys());
+    for workspace_id in workspace_ids {
+        if old_view.wc_commit_ids.get(workspace_id) != new_view.wc_commit_ids.get(workspace_id) {
+            names.insert(format!("working copy of {}", workspace_id.as_str()));
+        }
+    }
+    names
+}
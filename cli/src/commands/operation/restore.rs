@@ -12,10 +12,32 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write as _;
+use std::sync::Arc;
+
+use itertools::Itertools as _;
+use jj_lib::backend::{ChangeId, CommitId, TreeValue};
+use jj_lib::hex_util::to_reverse_hex;
+use jj_lib::local_backend::LocalBackend;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::merge::Merge;
+use jj_lib::merged_tree::{MergedTree, MergedTreeBuilder};
 use jj_lib::object_id::ObjectId;
+use jj_lib::op_store::{RefTarget, RemoteRefState, View, WorkspaceId};
+use jj_lib::operation::Operation;
+use jj_lib::repo::{ReadonlyRepo, Repo as _};
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::signing::Signer;
+use jj_lib::store::Store;
 
-use super::{view_with_desired_portions_restored, UndoWhatToRestore, DEFAULT_UNDO_WHAT};
-use crate::cli_util::CommandHelper;
+use super::{
+    other_changed_workspaces, set_reason_tag, view_with_desired_portions_restored,
+    write_stale_workspaces_hint, UndoWhatToRestore, DEFAULT_UNDO_WHAT,
+};
+use crate::cli_util::{
+    short_change_hash, short_operation_hash, CommandHelper, RevisionArg, WorkspaceCommandHelper,
+};
 use crate::command_error::CommandError;
 use crate::ui::Ui;
 
@@ -23,6 +45,9 @@ use crate::ui::Ui;
 ///
 /// This restores the repo to the state at the specified operation, effectively
 /// undoing all later operations. It does so by creating a new operation.
+///
+/// With `--revisions`, only the given commits are restored to the version they
+/// had at the specified operation, and the rest of the repo is left as is.
 #[derive(clap::Args, Clone, Debug)]
 pub struct OperationRestoreArgs {
     /// The operation to restore to
@@ -37,6 +62,36 @@ pub struct OperationRestoreArgs {
     /// This option is EXPERIMENTAL.
     #[arg(long, value_enum, default_values_t = DEFAULT_UNDO_WHAT)]
     what: Vec<UndoWhatToRestore>,
+
+    /// Restore only the commits in these revisions
+    ///
+    /// Each commit is replaced by the visible commit with the same change ID at
+    /// the specified operation, including its content, description, and
+    /// parents. Descendants are rebased onto the restored commits, and
+    /// branches pointing to the replaced commits are moved along. Changes
+    /// that didn't exist (or were divergent) at the operation are skipped.
+    #[arg(long, short, conflicts_with = "what")]
+    revisions: Vec<RevisionArg>,
+
+    /// Interactively choose which parts of the repo state to restore
+    ///
+    /// Opens the diff editor with a file for each part of the repo state that
+    /// differs from the operation, such as a branch, a tag, or the
+    /// working-copy commit of a workspace. Only the parts whose files are
+    /// fully restored on the right side are restored.
+    #[arg(long, short, conflicts_with = "revisions")]
+    interactive: bool,
+
+    /// Specify diff editor to be used (implies --interactive)
+    #[arg(long, value_name = "NAME", conflicts_with = "revisions")]
+    tool: Option<String>,
+
+    /// Reason for the operation, which is recorded in the operation log
+    ///
+    /// The reason is shown by `jj op log` and `jj op show`, and is available
+    /// as `reason` in operation templates.
+    #[arg(long, value_name = "TEXT")]
+    reason: Option<String>,
 }
 
 pub fn cmd_op_restore(
@@ -46,14 +101,456 @@ pub fn cmd_op_restore(
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
     let target_op = workspace_command.resolve_single_op(&args.operation)?;
+    if !args.revisions.is_empty() {
+        return restore_revisions(ui, command, &mut workspace_command, &target_op, args);
+    }
+    if args.interactive || args.tool.is_some() {
+        return restore_interactively(ui, &mut workspace_command, &target_op, args);
+    }
     let mut tx = workspace_command.start_transaction();
+    set_reason_tag(&mut tx, args.reason.as_deref())?;
     let new_view = view_with_desired_portions_restored(
         target_op.view()?.store_view(),
         tx.base_repo().view().store_view(),
         &args.what,
     );
     tx.mut_repo().set_view(new_view);
+    let stale_workspace_ids = other_changed_workspaces(&tx);
     tx.finish(ui, format!("restore to operation {}", target_op.id().hex()))?;
+    write_stale_workspaces_hint(ui, &stale_workspace_ids)?;
+
+    Ok(())
+}
+
+fn restore_revisions(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    workspace_command: &mut WorkspaceCommandHelper,
+    target_op: &Operation,
+    args: &OperationRestoreArgs,
+) -> Result<(), CommandError> {
+    let commits: Vec<_> = workspace_command
+        .parse_union_revsets(&args.revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    workspace_command.check_rewritable(commits.iter().map(|commit| commit.id()))?;
+    let target_repo = workspace_command.repo().loader().load_at(target_op)?;
 
+    let mut tx = workspace_command.start_transaction();
+    set_reason_tag(&mut tx, args.reason.as_deref())?;
+    let mut num_restored = 0;
+    for commit in &commits {
+        let change_id = commit.change_id();
+        let old_commit = match target_repo.resolve_change_id(change_id).as_deref() {
+            Some([old_id]) => target_repo.store().get_commit(old_id)?,
+            Some(_) => {
+                writeln!(
+                    ui.warning_default(),
+                    "Skipping change {} since it was divergent at operation {}",
+                    short_change_hash(change_id),
+                    short_operation_hash(target_op.id())
+                )?;
+                continue;
+            }
+            None => {
+                writeln!(
+                    ui.warning_default(),
+                    "Skipping change {} since it didn't exist at operation {}",
+                    short_change_hash(change_id),
+                    short_operation_hash(target_op.id())
+                )?;
+                continue;
+            }
+        };
+        if old_commit.id() == commit.id() {
+            continue;
+        }
+        tx.mut_repo().add_head(&old_commit)?;
+        tx.mut_repo()
+            .set_rewritten_commit(commit.id().clone(), old_commit.id().clone());
+        num_restored += 1;
+    }
+    if num_restored == 0 {
+        writeln!(ui.status(), "Nothing changed.")?;
+        return Ok(());
+    }
+    let num_rebased = tx.mut_repo().rebase_descendants(command.settings())?;
+    writeln!(
+        ui.status(),
+        "Restored {num_restored} commits from operation {}",
+        short_operation_hash(target_op.id())
+    )?;
+    if num_rebased > 0 {
+        writeln!(ui.status(), "Rebased {num_rebased} descendant commits")?;
+    }
+    tx.finish(
+        ui,
+        format!(
+            "restore {num_restored} commits to operation {}",
+            target_op.id().hex()
+        ),
+    )?;
     Ok(())
 }
+
+fn restore_interactively(
+    ui: &mut Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+    target_op: &Operation,
+    args: &OperationRestoreArgs,
+) -> Result<(), CommandError> {
+    let repo = workspace_command.repo().clone();
+    let current_view = repo.view().store_view();
+    let target_view = view_with_desired_portions_restored(
+        target_op.view()?.store_view(),
+        current_view,
+        &args.what,
+    );
+    let items = changed_view_items(current_view, &target_view);
+    if items.is_empty() {
+        writeln!(ui.status(), "Nothing changed.")?;
+        return Ok(());
+    }
+
+    // The files only exist for the selection, so they're written to a
+    // scratch store rather than to the repo.
+    let scratch_dir = tempfile::Builder::new()
+        .prefix("jj-op-restore-")
+        .tempdir()?;
+    let scratch_store = Store::new(
+        Box::new(LocalBackend::init(scratch_dir.path())),
+        Signer::new(None, vec![]),
+        repo.store().use_tree_conflict_format(),
+    );
+    let left_tree = write_view_tree(&repo, &scratch_store, current_view, &items)?;
+    let right_tree = write_view_tree(&repo, &scratch_store, &target_view, &items)?;
+    let diff_selector =
+        workspace_command.diff_selector(ui, args.tool.as_deref(), args.interactive)?;
+    let instructions = format!(
+        "\
+You are restoring parts of the repo to operation: {} {}
+
+The left side of the diff shows the current state of the repo, and the right
+side shows its state at that operation. Each file is a part of the repo state,
+such as a branch, a tag, or the working-copy commit of a workspace.
+
+Adjust the right side until it shows the parts you want to restore. Files
+which are only partially restored keep their current state.
+",
+        short_operation_hash(target_op.id()),
+        target_op.metadata().description
+    );
+    let selected_tree_id = diff_selector.select(
+        &left_tree,
+        &right_tree,
+        &EverythingMatcher,
+        Some(&instructions),
+    )?;
+    let selected_tree = scratch_store.get_root_tree(&selected_tree_id)?;
+
+    let mut new_view = current_view.clone();
+    let mut num_restored = 0;
+    for item in &items {
+        let path = item.path();
+        if selected_tree.path_value(&path)? == right_tree.path_value(&path)? {
+            item.restore(&target_view, &mut new_view);
+            num_restored += 1;
+        }
+    }
+    if num_restored == 0 {
+        writeln!(ui.status(), "Nothing changed.")?;
+        return Ok(());
+    }
+
+    let mut tx = workspace_command.start_transaction();
+    set_reason_tag(&mut tx, args.reason.as_deref())?;
+    tx.mut_repo().set_view(new_view);
+    writeln!(
+        ui.status(),
+        "Restored {num_restored} of {} parts of the repo state from operation {}",
+        items.len(),
+        short_operation_hash(target_op.id())
+    )?;
+    let stale_workspace_ids = other_changed_workspaces(&tx);
+    tx.finish(
+        ui,
+        format!(
+            "restore {num_restored} parts of the repo state to operation {}",
+            target_op.id().hex()
+        ),
+    )?;
+    write_stale_workspaces_hint(ui, &stale_workspace_ids)?;
+    Ok(())
+}
+
+/// A part of the repo state which can be restored on its own.
+#[derive(Clone, Debug)]
+enum ViewItem {
+    Head(CommitId),
+    WorkingCopy(WorkspaceId),
+    LocalBranch(String),
+    RemoteBranch { remote: String, name: String },
+    Tag(String),
+    Note(ChangeId),
+}
+
+impl ViewItem {
+    /// Path of the file representing this item in the diff editor.
+    fn path(&self) -> RepoPathBuf {
+        let path = match self {
+            ViewItem::Head(id) => format!("heads/{}", id.hex()),
+            ViewItem::WorkingCopy(id) => {
+                format!("working-copies/{}", escape_file_name(id.as_str()))
+            }
+            ViewItem::LocalBranch(name) => format!("branches/{}", escape_file_name(name)),
+            ViewItem::RemoteBranch { remote, name } => {
+                format!(
+                    "remote-branches/{}/{}",
+                    escape_file_name(remote),
+                    escape_file_name(name)
+                )
+            }
+            ViewItem::Tag(name) => format!("tags/{}", escape_file_name(name)),
+            ViewItem::Note(id) => format!("notes/{}", to_reverse_hex(&id.hex()).unwrap()),
+        };
+        RepoPathBuf::from_internal_string(path)
+    }
+
+    /// Describes the state of this item in the `view`, or returns `None` if
+    /// the item doesn't exist there.
+    fn text(&self, repo: &ReadonlyRepo, view: &View) -> Result<Option<String>, CommandError> {
+        let text = match self {
+            ViewItem::Head(id) => {
+                if view.head_ids.contains(id) {
+                    Some(commit_line(repo, id)?)
+                } else {
+                    None
+                }
+            }
+            ViewItem::WorkingCopy(id) => match view.wc_commit_ids.get(id) {
+                Some(commit_id) => Some(commit_line(repo, commit_id)?),
+                None => None,
+            },
+            ViewItem::LocalBranch(name) => {
+                let target = view.local_branches.get(name);
+                let mut text = ref_target_text(repo, target)?;
+                if let (Some(text), Some(description)) =
+                    (&mut text, view.branch_descriptions.get(name))
+                {
+                    text.push_str(&format!("Description: {description}\n"));
+                }
+                text
+            }
+            ViewItem::RemoteBranch { remote, name } => {
+                let remote_ref = view
+                    .remote_views
+                    .get(remote)
+                    .and_then(|remote_view| remote_view.branches.get(name));
+                let mut text = ref_target_text(repo, remote_ref.map(|r| &r.target))?;
+                if let (Some(text), Some(remote_ref)) = (&mut text, remote_ref) {
+                    if remote_ref.state == RemoteRefState::Tracking {
+                        text.push_str("Tracked\n");
+                    }
+                }
+                text
+            }
+            ViewItem::Tag(name) => ref_target_text(repo, view.tags.get(name))?,
+            ViewItem::Note(id) => view.notes.get(id).map(|note| format!("{note}\n")),
+        };
+        Ok(text)
+    }
+
+    /// Copies the state of this item from `source` to `view`.
+    fn restore(&self, source: &View, view: &mut View) {
+        match self {
+            ViewItem::Head(id) => {
+                if source.head_ids.contains(id) {
+                    view.head_ids.insert(id.clone());
+                } else {
+                    view.head_ids.remove(id);
+                }
+            }
+            ViewItem::WorkingCopy(id) => match source.wc_commit_ids.get(id) {
+                Some(commit_id) => {
+                    view.wc_commit_ids.insert(id.clone(), commit_id.clone());
+                    // The working-copy commit must be visible.
+                    view.head_ids.insert(commit_id.clone());
+                }
+                None => {
+                    view.wc_commit_ids.remove(id);
+                }
+            },
+            ViewItem::LocalBranch(name) => {
+                copy_entry(name, &source.local_branches, &mut view.local_branches);
+                copy_entry(
+                    name,
+                    &source.branch_descriptions,
+                    &mut view.branch_descriptions,
+                );
+                if let Some(target) = source.local_branches.get(name) {
+                    // Keep the commits the branch points to visible.
+                    view.head_ids.extend(target.added_ids().cloned());
+                }
+            }
+            ViewItem::RemoteBranch { remote, name } => {
+                let source_branches = source
+                    .remote_views
+                    .get(remote)
+                    .map(|remote_view| remote_view.branches.clone())
+                    .unwrap_or_default();
+                let remote_view = view.remote_views.entry(remote.clone()).or_default();
+                copy_entry(name, &source_branches, &mut remote_view.branches);
+            }
+            ViewItem::Tag(name) => copy_entry(name, &source.tags, &mut view.tags),
+            ViewItem::Note(id) => copy_entry(id, &source.notes, &mut view.notes),
+        }
+    }
+}
+
+/// Returns the items which differ between the two views.
+fn changed_view_items(old_view: &View, new_view: &View) -> Vec<ViewItem> {
+    let mut items = vec![];
+    let head_ids: BTreeSet<_> = old_view
+        .head_ids
+        .symmetric_difference(&new_view.head_ids)
+        .collect();
+    items.extend(head_ids.into_iter().cloned().map(ViewItem::Head));
+    let workspace_ids: BTreeSet<_> = old_view
+        .wc_commit_ids
+        .keys()
+        .chain(new_view.wc_commit_ids.keys())
+        .filter(|id| old_view.wc_commit_ids.get(*id) != new_view.wc_commit_ids.get(*id))
+        .collect();
+    items.extend(
+        workspace_ids
+            .into_iter()
+            .cloned()
+            .map(ViewItem::WorkingCopy),
+    );
+    items.extend(
+        changed_keys(&old_view.local_branches, &new_view.local_branches)
+            .chain(changed_keys(
+                &old_view.branch_descriptions,
+                &new_view.branch_descriptions,
+            ))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(ViewItem::LocalBranch),
+    );
+    let remotes: BTreeSet<_> = old_view
+        .remote_views
+        .keys()
+        .chain(new_view.remote_views.keys())
+        .collect();
+    for remote in remotes {
+        let empty = BTreeMap::new();
+        let old_branches = old_view
+            .remote_views
+            .get(remote)
+            .map_or(&empty, |remote_view| &remote_view.branches);
+        let new_branches = new_view
+            .remote_views
+            .get(remote)
+            .map_or(&empty, |remote_view| &remote_view.branches);
+        items.extend(
+            changed_keys(old_branches, new_branches).map(|name| ViewItem::RemoteBranch {
+                remote: remote.clone(),
+                name,
+            }),
+        );
+    }
+    items.extend(changed_keys(&old_view.tags, &new_view.tags).map(ViewItem::Tag));
+    items.extend(changed_keys(&old_view.notes, &new_view.notes).map(ViewItem::Note));
+    items
+}
+
+/// Returns the keys whose values differ between the two maps, in order.
+fn changed_keys<'a, K: Ord + Clone, V: PartialEq>(
+    old_map: &'a BTreeMap<K, V>,
+    new_map: &'a BTreeMap<K, V>,
+) -> impl Iterator<Item = K> + 'a {
+    old_map
+        .keys()
+        .merge(new_map.keys())
+        .dedup()
+        .filter(|key| old_map.get(*key) != new_map.get(*key))
+        .cloned()
+}
+
+fn copy_entry<K: Ord + Clone, V: Clone>(
+    key: &K,
+    source: &BTreeMap<K, V>,
+    dest: &mut BTreeMap<K, V>,
+) {
+    match source.get(key) {
+        Some(value) => dest.insert(key.clone(), value.clone()),
+        None => dest.remove(key),
+    };
+}
+
+/// Escapes `name` so that it can be used as a single file name.
+///
+/// Names may contain slashes, and `.` or `..` would refer to directories, so
+/// `%`, `/`, and a leading `.` are percent-encoded. An empty name is encoded
+/// as `%`, which can't result from any other name.
+fn escape_file_name(name: &str) -> String {
+    let escaped = name.replace('%', "%25").replace('/', "%2F");
+    if escaped.is_empty() {
+        "%".to_owned()
+    } else if let Some(rest) = escaped.strip_prefix('.') {
+        format!("%2E{rest}")
+    } else {
+        escaped
+    }
+}
+
+/// Writes a tree to the `store` with a file for each of the `items` which
+/// exists in the `view`.
+fn write_view_tree(
+    repo: &ReadonlyRepo,
+    store: &Arc<Store>,
+    view: &View,
+    items: &[ViewItem],
+) -> Result<MergedTree, CommandError> {
+    let mut tree_builder = MergedTreeBuilder::new(store.empty_merged_tree_id());
+    for item in items {
+        if let Some(text) = item.text(repo, view)? {
+            let path = item.path();
+            let id = store.write_file(&path, &mut text.as_bytes())?;
+            let value = TreeValue::File {
+                id,
+                executable: false,
+            };
+            tree_builder.set_or_remove(path, Merge::normal(value));
+        }
+    }
+    let tree_id = tree_builder.write_tree(store)?;
+    Ok(store.get_root_tree(&tree_id)?)
+}
+
+fn ref_target_text(
+    repo: &ReadonlyRepo,
+    target: Option<&RefTarget>,
+) -> Result<Option<String>, CommandError> {
+    let Some(target) = target.filter(|target| target.is_present()) else {
+        return Ok(None);
+    };
+    if let Some(id) = target.as_normal() {
+        return Ok(Some(commit_line(repo, id)?));
+    }
+    let mut text = "Conflict:\n".to_owned();
+    for id in target.removed_ids() {
+        text.push_str(&format!("  - {}", commit_line(repo, id)?));
+    }
+    for id in target.added_ids() {
+        text.push_str(&format!("  + {}", commit_line(repo, id)?));
+    }
+    Ok(Some(text))
+}
+
+/// Formats the commit id along with the first line of its description.
+fn commit_line(repo: &ReadonlyRepo, id: &CommitId) -> Result<String, CommandError> {
+    let commit = repo.store().get_commit(id)?;
+    let description = commit.description().lines().next().unwrap_or_default();
+    Ok(format!("{} {description}\n", id.hex()))
+}
@@ -0,0 +1,378 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::{io, slice};
+
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::op_store::RefTarget;
+use jj_lib::op_walk;
+use jj_lib::operation::Operation;
+use jj_lib::repo::{ReadonlyRepo, Repo};
+use jj_lib::revset::RevsetExpression;
+use jj_lib::rewrite::rebase_to_dest_parent;
+use jj_lib::view::View;
+
+use crate::cli_util::{
+    short_commit_hash, short_operation_hash, CommandHelper, TemplateFileArgs,
+    WorkspaceCommandHelper,
+};
+use crate::command_error::CommandError;
+use crate::diff_util::{self, DiffFormatArgs, DiffRenderer};
+use crate::formatter::Formatter;
+use crate::operation_templater::OperationTemplateLanguage;
+use crate::ui::Ui;
+
+/// Show an operation and the changes it made
+///
+/// Prints the metadata of the operation (such as the command, time, and user),
+/// its parent operations, and a summary of the changes to the commits,
+/// branches, and working copies compared to its parents.
+///
+/// With `--patch` or any of the diff format options, the content changes of
+/// the commits are shown too. A rewritten commit is compared to its previous
+/// version, a newly created commit shows its changes as added, and an
+/// abandoned commit shows its changes as removed.
+#[derive(clap::Args, Clone, Debug)]
+pub struct OperationShowArgs {
+    /// The operation to show
+    #[arg(default_value = "@")]
+    operation: String,
+    /// Render the operation metadata using the given template
+    ///
+    /// For the syntax, see https://github.com/martinvonz/jj/blob/main/docs/templates.md
+    #[arg(long, short = 'T')]
+    template: Option<String>,
+    #[command(flatten)]
+    template_file: TemplateFileArgs,
+    /// Show the content changes of the commits the operation changed
+    ///
+    /// If the previous version of a rewritten commit has different parents, it
+    /// will be temporarily rebased to the parents of the new version, so the
+    /// diff is not contaminated by unrelated changes.
+    #[arg(long, short = 'p')]
+    patch: bool,
+    #[command(flatten)]
+    diff_format: DiffFormatArgs,
+}
+
+pub fn cmd_op_show(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &OperationShowArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo();
+    let repo_loader = repo.loader();
+    let op = workspace_command.resolve_single_op(&args.operation)?;
+    let current_op = repo.operation();
+
+    let template = {
+        let language = OperationTemplateLanguage::new(
+            repo.op_store().root_operation_id(),
+            Some(current_op.id()),
+            command.operation_template_extensions(),
+        );
+        let text = args.template_file.template_text(
+            command,
+            args.template.as_deref(),
+            "templates.op_show",
+        )?;
+        text.parse(|text| {
+            command.parse_template(
+                ui,
+                &language,
+                text,
+                OperationTemplateLanguage::wrap_operation,
+            )
+        })?
+        .labeled("op_show")
+    };
+
+    let parent_ops: Vec<Operation> = op.parents().try_collect()?;
+    let new_repo = repo_loader.load_at(&op)?;
+    let diff_formats =
+        diff_util::diff_formats_for_log(command.settings(), &args.diff_format, args.patch)?;
+    // Hidden commits are still indexed, so the new repo can render the diffs
+    // of both sides.
+    let diff_renderer = (!diff_formats.is_empty()).then(|| {
+        DiffRenderer::new(
+            new_repo.as_ref(),
+            workspace_command.path_converter(),
+            diff_formats,
+        )
+    });
+    let old_view = merged_parents_view(command, repo, &parent_ops)?;
+    let is_reachable = op_walk::walk_ancestors(slice::from_ref(current_op))
+        .process_results(|mut ops| ops.any(|ancestor| ancestor.id() == op.id()))?;
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    let formatter = formatter.as_mut();
+    template.format(&op, formatter)?;
+
+    writeln!(formatter, "Parent operations:")?;
+    if parent_ops.is_empty() {
+        writeln!(formatter, "  (none)")?;
+    }
+    for parent_op in &parent_ops {
+        write!(formatter, "  ")?;
+        write!(
+            formatter.labeled("operation_id"),
+            "{}",
+            short_operation_hash(parent_op.id())
+        )?;
+        let description = &parent_op.metadata().description;
+        writeln!(formatter, " {}", description.lines().next().unwrap_or(""))?;
+    }
+    if op.id() == current_op.id() {
+        writeln!(formatter, "This is the current operation.")?;
+    } else if is_reachable {
+        writeln!(
+            formatter,
+            "This operation is an ancestor of the current operation."
+        )?;
+    } else {
+        writeln!(
+            formatter,
+            "This operation is not reachable from the current operation."
+        )?;
+    }
+    writeln!(formatter)?;
+
+    let Some(old_view) = old_view else {
+        writeln!(formatter, "The root operation has no changes.")?;
+        return Ok(());
+    };
+    let has_changes = write_changes_summary(
+        ui,
+        formatter,
+        &workspace_command,
+        new_repo.as_ref(),
+        &old_view,
+        diff_renderer.as_ref(),
+    )?;
+    if !has_changes {
+        writeln!(formatter, "No changes.")?;
+    }
+    Ok(())
+}
+
+/// Returns the view of the merged `parent_ops`, or `None` if there are no
+/// parents.
+///
+/// The changes of a merge operation are relative to the merged parents.
+pub(super) fn merged_parents_view(
+    command: &CommandHelper,
+    repo: &Arc<ReadonlyRepo>,
+    parent_ops: &[Operation],
+) -> Result<Option<View>, CommandError> {
+    let Some((first_parent_op, other_parent_ops)) = parent_ops.split_first() else {
+        return Ok(None);
+    };
+    let parent_repo = repo.loader().load_at(first_parent_op)?;
+    let mut tx = parent_repo.start_transaction(command.settings());
+    for other_op in other_parent_ops {
+        tx.merge_operation(other_op.clone())?;
+    }
+    Ok(Some(tx.repo().view().clone()))
+}
+
+/// Writes the differences between `old_view` and the view of `new_repo`.
+/// Returns false if there were no differences.
+///
+/// The commits of `old_view` must be indexed in `new_repo`. If `diff_renderer`
+/// is given, the content changes of the changed commits are written below each
+/// of them.
+pub(super) fn write_changes_summary(
+    ui: &Ui,
+    formatter: &mut dyn Formatter,
+    workspace_command: &WorkspaceCommandHelper,
+    new_repo: &dyn Repo,
+    old_view: &View,
+    diff_renderer: Option<&DiffRenderer>,
+) -> Result<bool, CommandError> {
+    let new_view = new_repo.view();
+    let mut has_changes = false;
+
+    // Hidden commits are still indexed, so both sides can be evaluated against
+    // the new repo.
+    let old_heads = RevsetExpression::commits(old_view.heads().iter().cloned().collect());
+    let new_heads = RevsetExpression::commits(new_view.heads().iter().cloned().collect());
+    let added_ids: Vec<CommitId> = old_heads
+        .range(&new_heads)
+        .evaluate_programmatic(new_repo)?
+        .iter()
+        .collect();
+    let removed_ids: Vec<CommitId> = new_heads
+        .range(&old_heads)
+        .evaluate_programmatic(new_repo)?
+        .iter()
+        .collect();
+    if !added_ids.is_empty() || !removed_ids.is_empty() {
+        has_changes = true;
+        writeln!(formatter, "Changed commits:")?;
+        let added_commits: Vec<Commit> = added_ids
+            .iter()
+            .map(|id| new_repo.store().get_commit(id))
+            .try_collect()?;
+        let removed_commits: Vec<Commit> = removed_ids
+            .iter()
+            .map(|id| new_repo.store().get_commit(id))
+            .try_collect()?;
+        for commit in &added_commits {
+            write!(formatter, "  + ")?;
+            workspace_command.write_commit_summary(formatter, commit)?;
+            writeln!(formatter)?;
+            if let Some(renderer) = diff_renderer {
+                let predecessor = removed_commits
+                    .iter()
+                    .find(|removed| removed.change_id() == commit.change_id());
+                let from_tree = match predecessor {
+                    Some(predecessor) => rebase_to_dest_parent(new_repo, predecessor, commit)?,
+                    None => commit.parent_tree(new_repo)?,
+                };
+                renderer.show_diff(
+                    ui,
+                    formatter,
+                    &from_tree,
+                    &commit.tree()?,
+                    &EverythingMatcher,
+                )?;
+            }
+        }
+        for commit in &removed_commits {
+            write!(formatter, "  - ")?;
+            workspace_command.write_commit_summary(formatter, commit)?;
+            writeln!(formatter)?;
+            // The change may have been rewritten by this or another operation.
+            let is_abandoned = new_repo.resolve_change_id(commit.change_id()).is_none();
+            if let Some(renderer) = diff_renderer.filter(|_| is_abandoned) {
+                renderer.show_diff(
+                    ui,
+                    formatter,
+                    &commit.tree()?,
+                    &commit.parent_tree(new_repo)?,
+                    &EverythingMatcher,
+                )?;
+            }
+        }
+    }
+
+    let local_branch_changes = diff_named_targets(
+        old_view
+            .local_branches()
+            .map(|(name, target)| (name.to_owned(), target.clone())),
+        new_view
+            .local_branches()
+            .map(|(name, target)| (name.to_owned(), target.clone())),
+    );
+    if !local_branch_changes.is_empty() {
+        has_changes = true;
+        writeln!(formatter, "Changed local branches:")?;
+        write_target_changes(formatter, &local_branch_changes)?;
+    }
+
+    let remote_branch_changes = diff_named_targets(
+        old_view
+            .all_remote_branches()
+            .map(|((name, remote), remote_ref)| {
+                (format!("{name}@{remote}"), remote_ref.target.clone())
+            }),
+        new_view
+            .all_remote_branches()
+            .map(|((name, remote), remote_ref)| {
+                (format!("{name}@{remote}"), remote_ref.target.clone())
+            }),
+    );
+    if !remote_branch_changes.is_empty() {
+        has_changes = true;
+        writeln!(formatter, "Changed remote branches:")?;
+        write_target_changes(formatter, &remote_branch_changes)?;
+    }
+
+    let wc_changes = diff_named_targets(
+        old_view.wc_commit_ids().iter().map(|(id, commit_id)| {
+            (
+                format!("{}@", id.as_str()),
+                RefTarget::normal(commit_id.clone()),
+            )
+        }),
+        new_view.wc_commit_ids().iter().map(|(id, commit_id)| {
+            (
+                format!("{}@", id.as_str()),
+                RefTarget::normal(commit_id.clone()),
+            )
+        }),
+    );
+    if !wc_changes.is_empty() {
+        has_changes = true;
+        // Named like the working-copy commits in `jj log`, e.g. "default@".
+        writeln!(formatter, "Changed working copies:")?;
+        write_target_changes(formatter, &wc_changes)?;
+    }
+
+    Ok(has_changes)
+}
+
+type TargetChanges = BTreeMap<String, (RefTarget, RefTarget)>;
+
+fn diff_named_targets(
+    old_targets: impl IntoIterator<Item = (String, RefTarget)>,
+    new_targets: impl IntoIterator<Item = (String, RefTarget)>,
+) -> TargetChanges {
+    let mut changes: TargetChanges = BTreeMap::new();
+    for (name, target) in old_targets {
+        changes
+            .entry(name)
+            .or_insert_with(|| (RefTarget::absent(), RefTarget::absent()))
+            .0 = target;
+    }
+    for (name, target) in new_targets {
+        changes
+            .entry(name)
+            .or_insert_with(|| (RefTarget::absent(), RefTarget::absent()))
+            .1 = target;
+    }
+    changes.retain(|_, (old_target, new_target)| old_target != new_target);
+    changes
+}
+
+fn write_target_changes(formatter: &mut dyn Formatter, changes: &TargetChanges) -> io::Result<()> {
+    for (name, (old_target, new_target)) in changes {
+        write!(formatter, "  ")?;
+        write!(formatter.labeled("branch"), "{name}")?;
+        write!(formatter, ": ")?;
+        write_target(formatter, old_target)?;
+        write!(formatter, " -> ")?;
+        write_target(formatter, new_target)?;
+        writeln!(formatter)?;
+    }
+    Ok(())
+}
+
+fn write_target(formatter: &mut dyn Formatter, target: &RefTarget) -> io::Result<()> {
+    if target.is_absent() {
+        write!(formatter, "(absent)")
+    } else if let Some(id) = target.as_normal() {
+        write!(formatter.labeled("commit_id"), "{}", short_commit_hash(id))
+    } else {
+        let ids = target.added_ids().map(short_commit_hash).join(", ");
+        write!(formatter.labeled("conflict"), "(conflicted: {ids})")
+    }
+}
@@ -12,31 +12,64 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::slice;
+
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
 use jj_lib::object_id::ObjectId;
+use jj_lib::op_walk;
 use jj_lib::repo::Repo;
+use jj_lib::revset::{RevsetExpression, RevsetIteratorExt as _};
 
-use super::{view_with_desired_portions_restored, UndoWhatToRestore, DEFAULT_UNDO_WHAT};
+use super::{
+    is_git_push_operation, other_changed_workspaces, set_reason_tag, undone_operation_id,
+    view_with_desired_portions_restored, write_stale_workspaces_hint, UndoWhatToRestore,
+    DEFAULT_UNDO_WHAT, UNDONE_OPERATION_TAG,
+};
 use crate::cli_util::CommandHelper;
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 /// Create a new operation that undoes an earlier operation
 ///
 /// This undoes an individual operation by applying the inverse of the
 /// operation.
+///
+/// A range of consecutive operations can be undone at once by passing
+/// `<FROM>..<TO>`, which undoes the operations after `<FROM>` up to and
+/// including `<TO>`.
 #[derive(clap::Args, Clone, Debug)]
 pub struct OperationUndoArgs {
-    /// The operation to undo
+    /// The operation or range of operations to undo
     ///
-    /// Use `jj op log` to find an operation to undo.
-    #[arg(default_value = "@")]
-    operation: String,
+    /// Use `jj op log` to find an operation to undo. Defaults to the latest
+    /// operation that did more than snapshot the working copy.
+    operation: Option<String>,
 
     /// What portions of the local state to restore (can be repeated)
     ///
+    /// Defaults to `repo` and `remote-tracking`. When undoing a `jj git push`
+    /// operation, it defaults to `repo` only, so the remote-tracking branches
+    /// keep matching the branches on the remote.
+    ///
     /// This option is EXPERIMENTAL.
-    #[arg(long, value_enum, default_values_t = DEFAULT_UNDO_WHAT)]
+    #[arg(long, value_enum)]
     what: Vec<UndoWhatToRestore>,
+
+    /// Don't rebase descendants of the commits restored by the undo
+    ///
+    /// Commits created on top of commits that the undone operation rewrote or
+    /// abandoned are left on top of the old commits instead of being rebased
+    /// onto the restored ones. Use `jj rebase` to reattach them later.
+    #[arg(long)]
+    no_rebase_descendants: bool,
+
+    /// Reason for the operation, which is recorded in the operation log
+    ///
+    /// The reason is shown by `jj op log` and `jj op show`, and is available
+    /// as `reason` in operation templates.
+    #[arg(long, value_name = "TEXT")]
+    reason: Option<String>,
 }
 
 pub fn cmd_op_undo(
@@ -45,16 +78,82 @@ pub fn cmd_op_undo(
     args: &OperationUndoArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
-    let bad_op = workspace_command.resolve_single_op(&args.operation)?;
-    let mut parent_ops = bad_op.parents();
-    let Some(parent_op) = parent_ops.next().transpose()? else {
-        return Err(user_error("Cannot undo repo initialization"));
+    let operation_range = args
+        .operation
+        .as_deref()
+        .and_then(|text| text.split_once(".."));
+    let (bad_op, parent_op, is_range) = if let Some((from, to)) = operation_range {
+        let from_op = workspace_command.resolve_single_op(from)?;
+        let to_op = workspace_command.resolve_single_op(to)?;
+        if from_op.id() == to_op.id() {
+            return Err(user_error(
+                ErrorCode::InvalidOperation,
+                "The range of operations to undo is empty",
+            ));
+        }
+        let is_ancestor = op_walk::walk_ancestors(slice::from_ref(&to_op))
+            .process_results(|mut ops| ops.any(|op| op.id() == from_op.id()))?;
+        if !is_ancestor {
+            return Err(user_error(
+                ErrorCode::InvalidOperation,
+                format!("Operation {from} is not an ancestor of operation {to}"),
+            ));
+        }
+        (to_op, from_op, true)
+    } else {
+        let bad_op = if let Some(text) = &args.operation {
+            workspace_command.resolve_single_op(text)?
+        } else {
+            // Undoing a working-copy snapshot alone is rarely what's wanted,
+            // so skip them to find the latest operation that did something.
+            let mut op = workspace_command.resolve_single_op("@")?;
+            while op.is_trivial() && op.parent_ids().len() == 1 {
+                let parent_op = op.parents().next().unwrap()?;
+                op = parent_op;
+            }
+            op
+        };
+        let parent_op = {
+            let mut parent_ops = bad_op.parents();
+            let Some(parent_op) = parent_ops.next().transpose()? else {
+                return Err(user_error(
+                    ErrorCode::InvalidOperation,
+                    "Cannot undo repo initialization",
+                ));
+            };
+            if parent_ops.next().is_some() {
+                return Err(user_error(
+                    ErrorCode::Unsupported,
+                    "Cannot undo a merge operation",
+                ));
+            }
+            parent_op
+        };
+        (bad_op, parent_op, false)
+    };
+
+    // Undoing a push doesn't undo it on the remote, so restoring the
+    // remote-tracking branches would make them disagree with the remote.
+    let keep_pushed_remote_tracking = if !args.what.is_empty() {
+        false
+    } else if is_range {
+        op_walk::walk_ancestors(slice::from_ref(&bad_op))
+            .take_while(|op| op.as_ref().map_or(true, |op| op.id() != parent_op.id()))
+            .process_results(|mut ops| ops.any(|op| is_git_push_operation(&op)))?
+    } else {
+        is_git_push_operation(&bad_op)
+    };
+    let what = if !args.what.is_empty() {
+        args.what.as_slice()
+    } else if keep_pushed_remote_tracking {
+        &[UndoWhatToRestore::Repo]
+    } else {
+        DEFAULT_UNDO_WHAT.as_slice()
     };
-    if parent_ops.next().is_some() {
-        return Err(user_error("Cannot undo a merge operation"));
-    }
 
     let mut tx = workspace_command.start_transaction();
+    set_reason_tag(&mut tx, args.reason.as_deref())?;
+    tx.set_tag(UNDONE_OPERATION_TAG.to_owned(), bad_op.id().hex());
     let repo_loader = tx.base_repo().loader();
     let bad_repo = repo_loader.load_at(&bad_op)?;
     let parent_repo = repo_loader.load_at(&parent_op)?;
@@ -62,10 +161,103 @@ pub fn cmd_op_undo(
     let new_view = view_with_desired_portions_restored(
         tx.repo().view().store_view(),
         tx.base_repo().view().store_view(),
-        &args.what,
+        what,
     );
     tx.mut_repo().set_view(new_view);
-    tx.finish(ui, format!("undo operation {}", bad_op.id().hex()))?;
+    if !UndoWhatToRestore::Commits.is_included_in(what) {
+        // The commits rewritten by the undone operation are kept, so their
+        // descendants must not be rebased.
+        tx.mut_repo().drop_rewrites();
+    }
+    let orphaned_commits: Vec<Commit> = if args.no_rebase_descendants {
+        let old_commits = RevsetExpression::commits(tx.mut_repo().drop_rewrites());
+        old_commits
+            .descendants()
+            .minus(&old_commits)
+            .evaluate_programmatic(tx.repo())?
+            .iter()
+            .commits(tx.repo().store())
+            .try_collect()?
+    } else {
+        vec![]
+    };
+    // Rebase the descendants here (rather than when the transaction is
+    // finished) so we can tell whether the undo introduced conflicts. Either
+    // way, the rebase is part of the same operation.
+    let has_conflicted_descendants = if args.no_rebase_descendants {
+        false
+    } else {
+        let rebased = tx
+            .mut_repo()
+            .rebase_descendants_return_map(command.settings())?;
+        if !rebased.is_empty() {
+            writeln!(
+                ui.status(),
+                "Rebased {} descendant commit{}",
+                rebased.len(),
+                if rebased.len() == 1 { "" } else { "s" }
+            )?;
+        }
+        let store = tx.repo().store();
+        rebased
+            .values()
+            .map(|new_id| store.get_commit(new_id)?.has_conflict())
+            .process_results(|mut conflicts| conflicts.any(|has_conflict| has_conflict))?
+    };
+    if !orphaned_commits.is_empty() {
+        if let Some(mut formatter) = ui.status_formatter() {
+            writeln!(
+                formatter,
+                "Left {} descendant commit{} on top of the undone commits:",
+                orphaned_commits.len(),
+                if orphaned_commits.len() == 1 { "" } else { "s" }
+            )?;
+            for commit in &orphaned_commits {
+                write!(formatter, "  ")?;
+                tx.write_commit_summary(formatter.as_mut(), commit)?;
+                writeln!(formatter)?;
+            }
+        }
+    }
+    let stale_workspace_ids = other_changed_workspaces(&tx);
+    let tx_description = if is_range {
+        format!(
+            "undo operations {}..{}",
+            parent_op.id().hex(),
+            bad_op.id().hex()
+        )
+    } else {
+        format!("undo operation {}", bad_op.id().hex())
+    };
+    tx.finish(ui, tx_description)?;
+    write_stale_workspaces_hint(ui, &stale_workspace_ids)?;
+    if !orphaned_commits.is_empty() {
+        writeln!(
+            ui.hint_default(),
+            "Use `jj rebase` to move the orphaned commits onto the restored commits."
+        )?;
+    }
+    if has_conflicted_descendants {
+        // The conflicted commits are listed by the report of the transaction.
+        writeln!(
+            ui.hint_default(),
+            "Run `jj undo` to revert this undo instead of resolving the conflicts."
+        )?;
+    }
+    if keep_pushed_remote_tracking {
+        writeln!(
+            ui.hint_default(),
+            "The remote-tracking branches updated by the push were kept, since the push wasn't \
+             undone on the remote. Use `--what=repo --what=remote-tracking` to restore them, too."
+        )?;
+    }
+    if undone_operation_id(&bad_op).is_some() {
+        writeln!(
+            ui.hint_default(),
+            "The undone operation was itself an undo. Use `jj redo` to reapply an undone \
+             operation."
+        )?;
+    }
 
     Ok(())
 }
@@ -22,6 +22,7 @@ use tracing::instrument;
 
 use crate::cli_util::{CommandHelper, RevisionArg};
 use crate::command_error::CommandError;
+use crate::progress::RewriteProgressReporter;
 use crate::ui::Ui;
 
 /// Parallelize revisions by making them siblings
@@ -104,9 +105,11 @@ pub(crate) fn cmd_parallelize(
         new_child_parents.insert(commit.id().clone(), new_parents);
     }
 
-    tx.mut_repo().transform_descendants(
+    let mut progress = RewriteProgressReporter::new(ui, "Parallelizing");
+    tx.mut_repo().transform_descendants_with_progress(
         command.settings(),
         target_commits.iter().ids().cloned().collect_vec(),
+        &mut |p| progress.update(p),
         |mut rewriter| {
             // Commits in the target set do not depend on each other but they still depend
             // on other parents
@@ -135,6 +138,7 @@ pub(crate) fn cmd_parallelize(
             Ok(())
         },
     )?;
+    drop(progress);
 
     tx.finish(ui, format!("parallelize {} commits", target_commits.len()))
 }
@@ -17,7 +17,7 @@ use jj_lib::repo::Repo;
 use jj_lib::revset::{RevsetExpression, RevsetFilterPredicate, RevsetIteratorExt};
 
 use crate::cli_util::{short_commit_hash, CommandHelper};
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, CommandError, ErrorCode};
 use crate::commands::next::choose_commit;
 use crate::ui::Ui;
 /// Change the working copy revision relative to the parent revision
@@ -70,9 +70,12 @@ pub(crate) fn cmd_prev(
     args: &PrevArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
-    let current_wc_id = workspace_command
-        .get_wc_commit_id()
-        .ok_or_else(|| user_error("This command requires a working copy"))?;
+    let current_wc_id = workspace_command.get_wc_commit_id().ok_or_else(|| {
+        user_error(
+            ErrorCode::NoWorkingCopy,
+            "This command requires a working copy",
+        )
+    })?;
     let edit = args.edit
         || !workspace_command
             .repo()
@@ -107,11 +110,14 @@ pub(crate) fn cmd_prev(
     let target = match targets.as_slice() {
         [target] => target,
         [] => {
-            return Err(user_error(format!(
-                "No ancestor found {} commit{} back",
-                args.offset,
-                if args.offset > 1 { "s" } else { "" }
-            )))
+            return Err(user_error(
+                ErrorCode::EmptyRevset,
+                format!(
+                    "No ancestor found {} commit{} back",
+                    args.offset,
+                    if args.offset > 1 { "s" } else { "" }
+                ),
+            ))
         }
         commits => choose_commit(ui, &workspace_command, "prev", commits)?,
     };
@@ -21,13 +21,16 @@ use std::sync::Arc;
 use clap::ArgGroup;
 use indexmap::{IndexMap, IndexSet};
 use itertools::Itertools;
-use jj_lib::backend::CommitId;
+use jj_lib::backend::{BackendResult, CommitId};
 use jj_lib::commit::{Commit, CommitIteratorExt};
 use jj_lib::dag_walk;
 use jj_lib::object_id::ObjectId;
 use jj_lib::repo::{MutableRepo, ReadonlyRepo, Repo};
 use jj_lib::revset::{RevsetExpression, RevsetIteratorExt};
-use jj_lib::rewrite::{rebase_commit_with_options, CommitRewriter, EmptyBehaviour, RebaseOptions};
+use jj_lib::rewrite::{
+    rebase_commit_with_options, CommitRewriter, EmptyBehaviour, RebaseOptions, RebasedCommit,
+    RewriteProgress,
+};
 use jj_lib::settings::UserSettings;
 use tracing::instrument;
 
@@ -35,7 +38,8 @@ use crate::cli_util::{
     short_commit_hash, CommandHelper, RevisionArg, WorkspaceCommandHelper,
     WorkspaceCommandTransaction,
 };
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, CommandError, ErrorCode};
+use crate::progress::RewriteProgressReporter;
 use crate::ui::Ui;
 
 /// Move revisions to different parent(s)
@@ -123,6 +127,12 @@ use crate::ui::Ui;
 ///
 /// If a working-copy commit gets abandoned, it will be given a new, empty
 /// commit. This is true in general; it is not specific to this command.
+///
+/// Local branches pointing to rebased commits are moved to the rebased commits
+/// (like `git rebase --update-refs`). Branches pointing to commits that became
+/// empty and were abandoned are moved to the parent of the abandoned commit.
+/// Use `--no-update-refs` to leave the branches on the original commits.
+/// Remote-tracking branches are never changed by this command.
 #[derive(clap::Args, Clone, Debug)]
 #[command(verbatim_doc_comment)]
 #[command(group(ArgGroup::new("to_rebase").args(&["branch", "source", "revisions"])))]
@@ -195,11 +205,44 @@ pub(crate) struct RebaseArgs {
     #[arg(long, conflicts_with = "revisions")]
     skip_empty: bool,
 
+    /// What to do with commits that are empty after the rebase
+    ///
+    /// `drop` abandons commits that became empty because of the rebase, unless
+    /// they have a description. `keep` keeps all empty commits. `abandon`
+    /// abandons all empty commits, including ones that were already empty
+    /// before the rebase. Merge commits are never abandoned.
+    #[arg(long, value_enum, conflicts_with_all = ["revisions", "skip_empty"])]
+    empty: Option<EmptyPolicy>,
+
+    /// Remove merge parents that became ancestors of other parents
+    ///
+    /// If a rebased merge commit would end up with one parent being an
+    /// ancestor of another, the redundant parent is dropped. The contents of
+    /// the commit are unchanged since the merged parents have the same tree
+    /// as the remaining ones.
+    #[arg(long, conflicts_with = "revisions")]
+    simplify_merges: bool,
+
+    /// Leave local branches on the original commits
+    #[arg(long)]
+    no_update_refs: bool,
+
     /// Deprecated. Please prefix the revset with `all:` instead.
     #[arg(long, short = 'L', hide = true)]
     allow_large_revsets: bool,
 }
 
+/// Policy for commits that are empty after a rebase.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+enum EmptyPolicy {
+    /// Abandon commits that became empty, unless they have a description
+    Drop,
+    /// Keep all empty commits
+    Keep,
+    /// Abandon all empty commits
+    Abandon,
+}
+
 #[instrument(skip_all)]
 pub(crate) fn cmd_rebase(
     ui: &mut Ui,
@@ -208,18 +251,22 @@ pub(crate) fn cmd_rebase(
 ) -> Result<(), CommandError> {
     if args.allow_large_revsets {
         return Err(user_error(
+            ErrorCode::InvalidArgument,
             "--allow-large-revsets has been deprecated.
 Please use `jj rebase -d 'all:x|y'` instead of `jj rebase --allow-large-revsets -d x -d y`.",
         ));
     }
 
     let rebase_options = RebaseOptions {
-        empty: match args.skip_empty {
-            true => EmptyBehaviour::AbandonNewlyEmpty,
-            false => EmptyBehaviour::Keep,
+        empty: match (args.empty, args.skip_empty) {
+            (Some(EmptyPolicy::Drop), _) => EmptyBehaviour::AbandonNewlyEmptyUndescribed,
+            (Some(EmptyPolicy::Keep), _) | (None, false) => EmptyBehaviour::Keep,
+            (Some(EmptyPolicy::Abandon), _) => EmptyBehaviour::AbandonAllEmpty,
+            (None, true) => EmptyBehaviour::AbandonNewlyEmpty,
         },
-        simplify_ancestor_merge: false,
+        simplify_ancestor_merge: args.simplify_merges,
     };
+    let update_refs = !args.no_update_refs;
     let mut workspace_command = command.workspace_helper(ui)?;
     if !args.revisions.is_empty() {
         assert_eq!(
@@ -235,7 +282,7 @@ Please use `jj rebase -d 'all:x|y'` instead of `jj rebase --allow-large-revsets
             // be in both categories.
             rebase_options.empty,
             EmptyBehaviour::Keep,
-            "clap should forbid `-r --skip-empty`"
+            "clap should forbid `-r --skip-empty` and `-r --empty`"
         );
         let target_commits: Vec<_> = workspace_command
             .parse_union_revsets(&args.revisions)?
@@ -253,6 +300,7 @@ Please use `jj rebase -d 'all:x|y'` instead of `jj rebase --allow-large-revsets
                 &after_commits,
                 &before_commits,
                 &target_commits,
+                update_refs,
             )?;
         } else if !args.insert_after.is_empty() {
             let after_commits =
@@ -263,6 +311,7 @@ Please use `jj rebase -d 'all:x|y'` instead of `jj rebase --allow-large-revsets
                 &mut workspace_command,
                 &after_commits,
                 &target_commits,
+                update_refs,
             )?;
         } else if !args.insert_before.is_empty() {
             let before_commits =
@@ -273,6 +322,7 @@ Please use `jj rebase -d 'all:x|y'` instead of `jj rebase --allow-large-revsets
                 &mut workspace_command,
                 &before_commits,
                 &target_commits,
+                update_refs,
             )?;
         } else {
             let new_parents = workspace_command
@@ -285,6 +335,7 @@ Please use `jj rebase -d 'all:x|y'` instead of `jj rebase --allow-large-revsets
                 &mut workspace_command,
                 &new_parents,
                 &target_commits,
+                update_refs,
             )?;
         }
     } else if !args.source.is_empty() {
@@ -300,6 +351,7 @@ Please use `jj rebase -d 'all:x|y'` instead of `jj rebase --allow-large-revsets
             new_parents,
             &source_commits,
             rebase_options,
+            update_refs,
         )?;
     } else {
         let new_parents = workspace_command
@@ -318,6 +370,7 @@ Please use `jj rebase -d 'all:x|y'` instead of `jj rebase --allow-large-revsets
             new_parents,
             &branch_commits,
             rebase_options,
+            update_refs,
         )?;
     }
     Ok(())
@@ -330,6 +383,7 @@ fn rebase_branch(
     new_parents: Vec<Commit>,
     branch_commits: &IndexSet<Commit>,
     rebase_options: RebaseOptions,
+    update_refs: bool,
 ) -> Result<(), CommandError> {
     let parent_ids = new_parents
         .iter()
@@ -355,17 +409,55 @@ fn rebase_branch(
         new_parents,
         &root_commits,
         rebase_options,
+        update_refs,
     )
 }
 
+/// Number of commits rewritten and abandoned by a rebase, and the merge
+/// commits whose redundant parents were removed.
+#[derive(Clone, Debug, Default)]
+struct RebaseStats {
+    num_rebased: usize,
+    num_abandoned: usize,
+    simplified_merges: Vec<Commit>,
+}
+
+impl RebaseStats {
+    /// Rebases the commit of the `rewriter`, and records the result.
+    fn rebase_and_record(
+        &mut self,
+        settings: &UserSettings,
+        mut rewriter: CommitRewriter<'_>,
+        rebase_options: &RebaseOptions,
+    ) -> BackendResult<()> {
+        let num_parents = rewriter.new_parents().len();
+        if rebase_options.simplify_ancestor_merge {
+            rewriter.simplify_ancestor_merge();
+        }
+        let simplified = rewriter.new_parents().len() < num_parents;
+        match rebase_commit_with_options(settings, rewriter, rebase_options)? {
+            RebasedCommit::Rewritten(commit) => {
+                self.num_rebased += 1;
+                if simplified {
+                    self.simplified_merges.push(commit);
+                }
+            }
+            RebasedCommit::Abandoned { .. } => self.num_abandoned += 1,
+        }
+        Ok(())
+    }
+}
+
 /// Rebases `old_commits` onto `new_parents`.
 fn rebase_descendants(
+    ui: &Ui,
     tx: &mut WorkspaceCommandTransaction,
     settings: &UserSettings,
     new_parents: Vec<Commit>,
     old_commits: &[impl Borrow<Commit>],
     rebase_options: RebaseOptions,
-) -> Result<usize, CommandError> {
+) -> Result<RebaseStats, CommandError> {
+    let mut stats = RebaseStats::default();
     for old_commit in old_commits.iter() {
         let rewriter = CommitRewriter::new(
             tx.mut_repo(),
@@ -375,12 +467,29 @@ fn rebase_descendants(
                 .map(|parent| parent.id().clone())
                 .collect(),
         );
-        rebase_commit_with_options(settings, rewriter, &rebase_options)?;
+        stats.rebase_and_record(settings, rewriter, &rebase_options)?;
     }
-    let num_rebased = old_commits.len()
-        + tx.mut_repo()
-            .rebase_descendants_with_options(settings, rebase_options)?;
-    Ok(num_rebased)
+    let roots = old_commits
+        .iter()
+        .map(|commit| commit.borrow().id().clone())
+        .collect();
+    let mut progress = RewriteProgressReporter::new(ui, "Rebasing");
+    tx.mut_repo().transform_descendants_with_progress(
+        settings,
+        roots,
+        &mut |p| progress.update(p),
+        |rewriter| {
+            if rewriter.parents_changed() {
+                stats.rebase_and_record(settings, rewriter, &rebase_options)?;
+            }
+            Ok(())
+        },
+    )?;
+    // All descendants have been visited, so this only clears the recorded
+    // rewrites. Otherwise, they would be applied again when the transaction
+    // is finished, even if the branches are reset by `--no-update-refs`.
+    tx.mut_repo().rebase_descendants(settings)?;
+    Ok(stats)
 }
 
 fn rebase_descendants_transaction(
@@ -390,6 +499,7 @@ fn rebase_descendants_transaction(
     new_parents: Vec<Commit>,
     old_commits: &IndexSet<Commit>,
     rebase_options: RebaseOptions,
+    update_refs: bool,
 ) -> Result<(), CommandError> {
     workspace_command.check_rewritable(old_commits.iter().ids())?;
     let (skipped_commits, old_commits) = old_commits
@@ -409,9 +519,38 @@ fn rebase_descendants_transaction(
         check_rebase_destinations(workspace_command.repo(), &new_parents, old_commit)?;
     }
     let mut tx = workspace_command.start_transaction();
-    let num_rebased =
-        rebase_descendants(&mut tx, settings, new_parents, &old_commits, rebase_options)?;
-    writeln!(ui.status(), "Rebased {num_rebased} commits")?;
+    let stats = rebase_descendants(
+        ui,
+        &mut tx,
+        settings,
+        new_parents,
+        &old_commits,
+        rebase_options,
+    )?;
+    writeln!(ui.status(), "Rebased {} commits", stats.num_rebased)?;
+    if stats.num_abandoned > 0 {
+        writeln!(
+            ui.status(),
+            "Abandoned {} empty commit{}",
+            stats.num_abandoned,
+            if stats.num_abandoned == 1 { "" } else { "s" }
+        )?;
+    }
+    if let Some(mut formatter) = ui.status_formatter() {
+        if !stats.simplified_merges.is_empty() {
+            writeln!(
+                formatter,
+                "Removed redundant parents of {} merge commits:",
+                stats.simplified_merges.len()
+            )?;
+        }
+        for commit in &stats.simplified_merges {
+            write!(formatter, "  ")?;
+            tx.write_commit_summary(formatter.as_mut(), commit)?;
+            writeln!(formatter)?;
+        }
+    }
+    update_branches_after_rebase(ui, &mut tx, update_refs)?;
     let tx_message = if old_commits.len() == 1 {
         format!(
             "rebase commit {} and descendants",
@@ -430,6 +569,7 @@ fn rebase_revisions(
     workspace_command: &mut WorkspaceCommandHelper,
     new_parents: &[Commit],
     target_commits: &[Commit],
+    update_refs: bool,
 ) -> Result<(), CommandError> {
     if target_commits.is_empty() {
         return Ok(());
@@ -438,10 +578,13 @@ fn rebase_revisions(
     workspace_command.check_rewritable(target_commits.iter().ids())?;
     for commit in target_commits.iter() {
         if new_parents.contains(commit) {
-            return Err(user_error(format!(
-                "Cannot rebase {} onto itself",
-                short_commit_hash(commit.id()),
-            )));
+            return Err(user_error(
+                ErrorCode::CommitLoop,
+                format!(
+                    "Cannot rebase {} onto itself",
+                    short_commit_hash(commit.id()),
+                ),
+            ));
         }
     }
 
@@ -452,6 +595,7 @@ fn rebase_revisions(
         &new_parents.iter().ids().cloned().collect_vec(),
         &[],
         target_commits,
+        update_refs,
     )
 }
 
@@ -461,6 +605,7 @@ fn rebase_revisions_after(
     workspace_command: &mut WorkspaceCommandHelper,
     after_commits: &IndexSet<Commit>,
     target_commits: &[Commit],
+    update_refs: bool,
 ) -> Result<(), CommandError> {
     workspace_command.check_rewritable(target_commits.iter().ids())?;
 
@@ -489,6 +634,7 @@ fn rebase_revisions_after(
         &new_parent_ids,
         &new_children,
         target_commits,
+        update_refs,
     )
 }
 
@@ -498,6 +644,7 @@ fn rebase_revisions_before(
     workspace_command: &mut WorkspaceCommandHelper,
     before_commits: &IndexSet<Commit>,
     target_commits: &[Commit],
+    update_refs: bool,
 ) -> Result<(), CommandError> {
     workspace_command.check_rewritable(target_commits.iter().ids())?;
     let before_commit_ids = before_commits.iter().ids().cloned().collect_vec();
@@ -528,6 +675,7 @@ fn rebase_revisions_before(
         &new_parent_ids,
         &new_children,
         target_commits,
+        update_refs,
     )
 }
 
@@ -538,6 +686,7 @@ fn rebase_revisions_after_before(
     after_commits: &IndexSet<Commit>,
     before_commits: &IndexSet<Commit>,
     target_commits: &[Commit],
+    update_refs: bool,
 ) -> Result<(), CommandError> {
     workspace_command.check_rewritable(target_commits.iter().ids())?;
     let before_commit_ids = before_commits.iter().ids().cloned().collect_vec();
@@ -563,6 +712,7 @@ fn rebase_revisions_after_before(
         &new_parent_ids,
         &new_children,
         target_commits,
+        update_refs,
     )
 }
 
@@ -574,6 +724,7 @@ fn move_commits_transaction(
     new_parent_ids: &[CommitId],
     new_children: &[Commit],
     target_commits: &[Commit],
+    update_refs: bool,
 ) -> Result<(), CommandError> {
     if target_commits.is_empty() {
         return Ok(());
@@ -600,6 +751,7 @@ fn move_commits_transaction(
         new_parent_ids,
         new_children,
         target_commits,
+        &mut RewriteProgressReporter::new(ui, "Rebasing"),
     )?;
 
     if let Some(mut fmt) = ui.status_formatter() {
@@ -619,10 +771,71 @@ fn move_commits_transaction(
             writeln!(fmt, "Rebased {num_rebased_descendants} descendant commits")?;
         }
     }
+    update_branches_after_rebase(ui, &mut tx, update_refs)?;
 
     tx.finish(ui, tx_description)
 }
 
+/// Moves local branches back to their original commits if `update_refs` is
+/// false. Otherwise, reports branches which were moved to a different change
+/// because their commit was abandoned.
+fn update_branches_after_rebase(
+    ui: &Ui,
+    tx: &mut WorkspaceCommandTransaction,
+    update_refs: bool,
+) -> Result<(), CommandError> {
+    let base_repo = tx.base_repo().clone();
+    let changed_branches = base_repo
+        .view()
+        .local_branches()
+        .filter(|(branch_name, old_target)| {
+            tx.repo().view().get_local_branch(branch_name) != *old_target
+        })
+        .collect_vec();
+    if !update_refs {
+        // The original commits were hidden by the rebase, so they need to be
+        // made visible again.
+        let old_commits: Vec<_> = changed_branches
+            .iter()
+            .flat_map(|(_, old_target)| old_target.added_ids())
+            .map(|id| base_repo.store().get_commit(id))
+            .try_collect()?;
+        tx.mut_repo().add_heads(&old_commits)?;
+        for (branch_name, old_target) in &changed_branches {
+            tx.mut_repo()
+                .set_local_branch_target(branch_name, (*old_target).clone());
+        }
+        if !changed_branches.is_empty() {
+            writeln!(
+                ui.status(),
+                "Left {} branches on the original commits",
+                changed_branches.len()
+            )?;
+        }
+        return Ok(());
+    }
+    let Some(mut formatter) = ui.status_formatter() else {
+        return Ok(());
+    };
+    for (branch_name, old_target) in changed_branches {
+        let new_target = tx.repo().view().get_local_branch(branch_name);
+        let (Some(old_id), Some(new_id)) = (old_target.as_normal(), new_target.as_normal()) else {
+            continue;
+        };
+        let old_commit = base_repo.store().get_commit(old_id)?;
+        let new_commit = tx.repo().store().get_commit(new_id)?;
+        if old_commit.change_id() != new_commit.change_id() {
+            write!(
+                formatter,
+                "Moved branch {branch_name} to the parent of its abandoned commit: "
+            )?;
+            tx.write_commit_summary(formatter.as_mut(), &new_commit)?;
+            writeln!(formatter)?;
+        }
+    }
+    Ok(())
+}
+
 struct MoveCommitsStats {
     /// The number of commits in the target set which were rebased.
     num_rebased_targets: u32,
@@ -646,6 +859,7 @@ fn move_commits(
     new_parent_ids: &[CommitId],
     new_children: &[Commit],
     target_commits: &[Commit],
+    progress: &mut RewriteProgressReporter,
 ) -> Result<MoveCommitsStats, CommandError> {
     if target_commits.is_empty() {
         return Ok(MoveCommitsStats {
@@ -940,6 +1154,8 @@ fn move_commits(
     // TODO(ilyagr): Consider making it possible for descendants of the target set
     // to become emptied, like --skip-empty. This would require writing careful
     // tests.
+    let total = to_visit.len();
+    let mut visited = 0;
     while let Some(old_commit_id) = to_visit.pop() {
         let old_commit = to_visit_commits.get(&old_commit_id).unwrap();
         let parent_ids = to_visit_commits_new_parents
@@ -958,6 +1174,8 @@ fn move_commits(
         } else {
             num_skipped_rebases += 1;
         }
+        visited += 1;
+        progress.update(RewriteProgress { visited, total });
     }
     mut_repo.update_rewritten_references(settings)?;
 
@@ -981,11 +1199,14 @@ fn ensure_no_commit_loop(
         .iter()
         .next()
     {
-        return Err(user_error(format!(
+        return Err(user_error(
+            ErrorCode::CommitLoop,
+            format!(
             "Refusing to create a loop: commit {} would be both an ancestor and a descendant of \
              the rebased commits",
             short_commit_hash(&commit_id),
-        )));
+        ),
+        ));
     }
     Ok(())
 }
@@ -997,11 +1218,14 @@ fn check_rebase_destinations(
 ) -> Result<(), CommandError> {
     for parent in new_parents {
         if repo.index().is_ancestor(commit.id(), parent.id()) {
-            return Err(user_error(format!(
-                "Cannot rebase {} onto descendant {}",
-                short_commit_hash(commit.id()),
-                short_commit_hash(parent.id())
-            )));
+            return Err(user_error(
+                ErrorCode::CommitLoop,
+                format!(
+                    "Cannot rebase {} onto descendant {}",
+                    short_commit_hash(commit.id()),
+                    short_commit_hash(parent.id())
+                ),
+            ));
         }
     }
     Ok(())
@@ -12,14 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io::Write;
+use std::io::{Read as _, Write};
 
 use itertools::Itertools;
+use jj_lib::backend::TreeValue;
+use jj_lib::conflicts::find_conflict_markers;
+use jj_lib::matchers::Matcher;
+use jj_lib::merge::Merge;
+use jj_lib::merged_tree::{MergedTree, MergedTreeBuilder};
 use jj_lib::object_id::ObjectId;
 use tracing::instrument;
 
-use crate::cli_util::{print_conflicted_paths, CommandHelper, RevisionArg};
-use crate::command_error::{cli_error, CommandError};
+use crate::cli_util::{print_conflicted_paths, CommandHelper, RevisionArg, WorkspaceCommandHelper};
+use crate::command_error::{cli_error, user_error, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 /// Resolve a conflicted file with an external merge tool
@@ -27,6 +32,12 @@ use crate::ui::Ui;
 /// Only conflicts that can be resolved with a 3-way merge are supported. See
 /// docs for merge tool configuration instructions.
 ///
+/// With `--ours` or `--theirs`, all the conflicts at the given paths are
+/// instead resolved by picking one side, without running a merge tool.
+///
+/// With `--check`, nothing is resolved. Instead, the files are checked for
+/// leftover conflict markers, and the command fails if any are found.
+///
 /// Note that conflicts can also be resolved without using this command. You may
 /// edit the conflict markers in the conflicted file directly with a text
 /// editor.
@@ -49,6 +60,30 @@ pub(crate) struct ResolveArgs {
     /// Specify 3-way merge tool to be used
     #[arg(long, conflicts_with = "list", value_name = "NAME")]
     tool: Option<String>,
+    /// Resolve all the conflicts by picking the first side ("ours")
+    ///
+    /// When a commit is rebased, the first side of the resulting conflicts is
+    /// the destination it was rebased onto. In a merge commit, it's the first
+    /// parent. Unlike resolving with a merge tool, this applies to all the
+    /// conflicts at the given paths. Conflicts with more than two sides, or
+    /// involving a directory, are skipped with a warning.
+    #[arg(long, conflicts_with_all = ["list", "tool", "theirs"])]
+    ours: bool,
+    /// Resolve all the conflicts by picking the second side ("theirs")
+    ///
+    /// When a commit is rebased, the second side of the resulting conflicts is
+    /// the content of the rebased commit itself. In a merge commit, it's the
+    /// second parent. See `--ours` for which conflicts are skipped.
+    #[arg(long, conflicts_with_all = ["list", "tool"])]
+    theirs: bool,
+    /// Check that no files contain conflict markers, without resolving anything
+    ///
+    /// Reports each file that is still conflicted, and each line where a
+    /// conflict marked up by jj starts in a resolved file (e.g. after the
+    /// markers were committed by mistake). Exits with an error if anything
+    /// was found.
+    #[arg(long, conflicts_with_all = ["list", "tool", "ours", "theirs"])]
+    check: bool,
     /// Restrict to these paths when searching for a conflict to resolve. We
     /// will attempt to resolve the first conflict we can find. You can use
     /// the `--list` argument to find paths to use here.
@@ -69,6 +104,9 @@ pub(crate) fn cmd_resolve(
         .to_matcher();
     let commit = workspace_command.resolve_single_rev(&args.revision)?;
     let tree = commit.tree()?;
+    if args.check {
+        return check_conflict_markers(ui, &workspace_command, &tree, matcher.as_ref());
+    }
     let conflicts = tree
         .conflicts()
         .filter(|path| matcher.matches(&path.0))
@@ -88,16 +126,73 @@ pub(crate) fn cmd_resolve(
         );
     };
 
-    let (repo_path, _) = conflicts.first().unwrap();
     workspace_command.check_rewritable([commit.id()])?;
-    let merge_editor = workspace_command.merge_editor(ui, args.tool.as_deref())?;
-    writeln!(
-        ui.status(),
-        "Resolving conflicts in: {}",
-        workspace_command.format_file_path(repo_path)
-    )?;
+    let new_tree_id = if args.ours || args.theirs {
+        let side = if args.ours { 0 } else { 1 };
+        let mut tree_builder = MergedTreeBuilder::new(commit.tree_id().clone());
+        let mut num_resolved = 0;
+        for (repo_path, conflict) in &conflicts {
+            let conflict = conflict.clone().simplify();
+            if conflict.num_sides() < 2 {
+                return Err(user_error(
+                    ErrorCode::NotAConflict,
+                    format!(
+                        "Cannot pick a side of {} since its conflict simplifies to a single side",
+                        workspace_command.format_file_path(repo_path)
+                    ),
+                ));
+            }
+            let skip_reason = if conflict.num_sides() > 2 {
+                Some(format!("it's a {}-sided conflict", conflict.num_sides()))
+            } else if conflict
+                .iter()
+                .flatten()
+                .any(|value| matches!(value, TreeValue::Tree(_)))
+            {
+                Some("it's a conflict including a directory".to_owned())
+            } else {
+                None
+            };
+            if let Some(reason) = skip_reason {
+                writeln!(
+                    ui.warning_default(),
+                    "Skipping {} since {reason}",
+                    workspace_command.format_file_path(repo_path)
+                )?;
+                continue;
+            }
+            let value = conflict.get_add(side).unwrap().clone();
+            tree_builder.set_or_remove(repo_path.clone(), Merge::resolved(value));
+            num_resolved += 1;
+        }
+        if num_resolved == 0 {
+            return Err(user_error(
+                ErrorCode::UnresolvedConflicts,
+                "None of the conflicts could be resolved",
+            ));
+        }
+        writeln!(
+            ui.status(),
+            "Resolved {num_resolved} {} by picking side #{}",
+            if num_resolved == 1 {
+                "conflict"
+            } else {
+                "conflicts"
+            },
+            side + 1
+        )?;
+        tree_builder.write_tree(tree.store())?
+    } else {
+        let (repo_path, _) = conflicts.first().unwrap();
+        let merge_editor = workspace_command.merge_editor(ui, args.tool.as_deref())?;
+        writeln!(
+            ui.status(),
+            "Resolving conflicts in: {}",
+            workspace_command.format_file_path(repo_path)
+        )?;
+        merge_editor.edit_file(&tree, repo_path)?
+    };
     let mut tx = workspace_command.start_transaction();
-    let new_tree_id = merge_editor.edit_file(&tree, repo_path)?;
     let new_commit = tx
         .mut_repo()
         .rewrite_commit(command.settings(), &commit)
@@ -126,3 +221,50 @@ pub(crate) fn cmd_resolve(
     }
     Ok(())
 }
+
+fn check_conflict_markers(
+    ui: &mut Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    tree: &MergedTree,
+    matcher: &dyn Matcher,
+) -> Result<(), CommandError> {
+    let store = tree.store();
+    let mut num_files = 0;
+    let mut formatter = ui.stdout_formatter();
+    for (path, value) in tree.entries_matching(matcher) {
+        let value = value?;
+        let ui_path = workspace_command.format_file_path(&path);
+        let line_numbers = match value.into_resolved() {
+            Ok(Some(TreeValue::File { id, .. })) => {
+                let mut content = vec![];
+                store.read_file(&path, &id)?.read_to_end(&mut content)?;
+                find_conflict_markers(&content)
+            }
+            Ok(_) => continue,
+            Err(_) => {
+                writeln!(formatter, "{ui_path}: unresolved conflict")?;
+                num_files += 1;
+                continue;
+            }
+        };
+        if line_numbers.is_empty() {
+            continue;
+        }
+        for line_number in line_numbers {
+            writeln!(formatter, "{ui_path}:{}: conflict markers", line_number + 1)?;
+        }
+        num_files += 1;
+    }
+    drop(formatter);
+    if num_files > 0 {
+        return Err(user_error(
+            ErrorCode::UnresolvedConflicts,
+            format!(
+                "Found conflicts in {num_files} {}",
+                if num_files == 1 { "file" } else { "files" }
+            ),
+        ));
+    }
+    writeln!(ui.status(), "No conflict markers found.")?;
+    Ok(())
+}
@@ -19,7 +19,7 @@ use jj_lib::rewrite::restore_tree;
 use tracing::instrument;
 
 use crate::cli_util::{CommandHelper, RevisionArg};
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 /// Restore paths from another revision
@@ -80,6 +80,7 @@ pub(crate) fn cmd_restore(
     let (from_tree, to_commit);
     if args.revision.is_some() {
         return Err(user_error(
+            ErrorCode::InvalidArgument,
             "`jj restore` does not have a `--revision`/`-r` option. If you'd like to modify\nthe \
              *current* revision, use `--from`. If you'd like to modify a *different* \
              revision,\nuse `--to` or `--changes-in`.",
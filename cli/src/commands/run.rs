@@ -17,7 +17,7 @@
 use itertools::Itertools as _;
 
 use crate::cli_util::{CommandHelper, RevisionArg};
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 /// Run a command across a set of revisions.
@@ -59,11 +59,19 @@ pub fn cmd_run(ui: &mut Ui, command: &CommandHelper, args: &RunArgs) -> Result<(
     // 2. the amount of cores available.
     // 3. a single job, if all of the above fails.
     let _jobs = match args.jobs {
-        Some(0) => return Err(user_error("must pass at least one job")),
+        Some(0) => {
+            return Err(user_error(
+                ErrorCode::InvalidArgument,
+                "must pass at least one job",
+            ))
+        }
         Some(jobs) => Some(jobs),
         None => std::thread::available_parallelism().map(|t| t.into()).ok(),
     }
     // Fallback to a single user-visible job.
     .unwrap_or(1usize);
-    Err(user_error("This is a stub, do not use"))
+    Err(user_error(
+        ErrorCode::Unsupported,
+        "This is a stub, do not use",
+    ))
 }
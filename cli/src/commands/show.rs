@@ -12,29 +12,46 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::Write;
+
+use itertools::Itertools as _;
 use jj_lib::matchers::EverythingMatcher;
 use tracing::instrument;
 
-use crate::cli_util::{CommandHelper, RevisionArg};
+use crate::cli_util::{CommandHelper, RevisionArg, TemplateFileArgs};
 use crate::command_error::CommandError;
-use crate::diff_util::DiffFormatArgs;
+use crate::diff_util::{diff_render_error_with_tool_hint, DiffFormatArgs};
 use crate::ui::Ui;
 
 /// Show commit description and changes in a revision
+///
+/// If the revset resolves to multiple revisions, each of them is shown in
+/// topological order, parents first, separated by an empty line.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct ShowArgs {
-    /// Show changes in this revision, compared to its parent(s)
+    /// Show changes in these revisions, compared to their parent(s)
     #[arg(default_value = "@")]
-    revision: RevisionArg,
+    revisions: Vec<RevisionArg>,
     /// Ignored (but lets you pass `-r` for consistency with other commands)
-    #[arg(short = 'r', hide = true)]
-    unused_revision: bool,
+    #[arg(short = 'r', hide = true, action = clap::ArgAction::Count)]
+    unused_revision: u8,
     /// Render a revision using the given template
     ///
     /// For the syntax, see https://github.com/martinvonz/jj/blob/main/docs/templates.md
     #[arg(long, short = 'T')]
     template: Option<String>,
     #[command(flatten)]
+    template_file: TemplateFileArgs,
+    /// Show only the commit description, not the changes
+    ///
+    /// Can be combined with `--summary`, `--stat`, `--types`, or `--name-only`
+    /// to show the metadata followed by only that summary of the changes.
+    #[arg(
+        long,
+        conflicts_with_all = ["git", "color_words", "tool", "binary", "context"]
+    )]
+    no_patch: bool,
+    #[command(flatten)]
     format: DiffFormatArgs,
 }
 
@@ -45,17 +62,37 @@ pub(crate) fn cmd_show(
     args: &ShowArgs,
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper(ui)?;
-    let commit = workspace_command.resolve_single_rev(&args.revision)?;
-    let template_string = match &args.template {
-        Some(value) => value.to_string(),
-        None => command.settings().config().get_string("templates.show")?,
+    let commits: Vec<_> = workspace_command
+        .parse_union_revsets(&args.revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    if commits.is_empty() {
+        writeln!(ui.status(), "No revisions to show.")?;
+        return Ok(());
+    }
+    let template_string =
+        args.template_file
+            .template_text(command, args.template.as_deref(), "templates.show")?;
+    let template = template_string.parse(|text| workspace_command.parse_commit_template(text))?;
+    let diff_renderer = if args.no_patch {
+        workspace_command.diff_renderer_for_log(&args.format, false)?
+    } else {
+        Some(workspace_command.diff_renderer_for(&args.format)?)
     };
-    let template = workspace_command.parse_commit_template(&template_string)?;
-    let diff_renderer = workspace_command.diff_renderer_for(&args.format)?;
     ui.request_pager();
-    let mut formatter = ui.stdout_formatter();
+    let mut formatter = ui.diff_stdout_formatter();
     let formatter = formatter.as_mut();
-    template.format(&commit, formatter)?;
-    diff_renderer.show_patch(ui, formatter, &commit, &EverythingMatcher)?;
+    // Revsets are evaluated children first
+    for (i, commit) in commits.iter().rev().enumerate() {
+        if i > 0 {
+            writeln!(formatter)?;
+        }
+        template.format(commit, formatter)?;
+        if let Some(renderer) = &diff_renderer {
+            renderer
+                .show_patch(ui, formatter, commit, &EverythingMatcher)
+                .map_err(|err| diff_render_error_with_tool_hint(command.settings(), err))?;
+        }
+    }
     Ok(())
 }
@@ -27,7 +27,7 @@ use crate::cli_util::{
     edit_temp_file, print_checkout_stats, CommandHelper, WorkspaceCommandHelper,
 };
 use crate::command_error::{
-    internal_error, internal_error_with_message, user_error_with_message, CommandError,
+    internal_error, internal_error_with_message, user_error_with_message, CommandError, ErrorCode,
 };
 use crate::ui::Ui;
 
@@ -193,7 +193,11 @@ fn edit_sparse(
         .filter(|line| !line.is_empty())
         .map(|line| {
             RepoPathBuf::from_relative_path(line).map_err(|err| {
-                user_error_with_message(format!("Failed to parse sparse pattern: {line}"), err)
+                user_error_with_message(
+                    ErrorCode::InvalidPath,
+                    format!("Failed to parse sparse pattern: {line}"),
+                    err,
+                )
             })
         })
         .try_collect()
@@ -204,14 +208,19 @@ fn update_sparse_patterns_with(
     workspace_command: &mut WorkspaceCommandHelper,
     f: impl FnOnce(&mut Ui, &[RepoPathBuf]) -> Result<Vec<RepoPathBuf>, CommandError>,
 ) -> Result<(), CommandError> {
+    let checkout_options = workspace_command.checkout_options()?;
     let (mut locked_ws, wc_commit) = workspace_command.start_working_copy_mutation()?;
     let new_patterns = f(ui, locked_ws.locked_wc().sparse_patterns()?)?;
-    let stats = locked_ws
+    let mut stats = locked_ws
         .locked_wc()
-        .set_sparse_patterns(new_patterns)
+        .set_sparse_patterns(new_patterns, &checkout_options)
         .map_err(|err| internal_error_with_message("Failed to update working copy paths", err))?;
     let operation_id = locked_ws.locked_wc().old_operation_id().clone();
     locked_ws.finish(operation_id)?;
+    let backup_tree_id = stats.backup_tree_id.take();
     print_checkout_stats(ui, stats, &wc_commit)?;
+    if let Some(tree_id) = backup_tree_id {
+        workspace_command.record_working_copy_backup(ui, &wc_commit, tree_id)?;
+    }
     Ok(())
 }
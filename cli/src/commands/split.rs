@@ -18,7 +18,7 @@ use jj_lib::repo::Repo;
 use tracing::instrument;
 
 use crate::cli_util::{CommandHelper, RevisionArg};
-use crate::command_error::{user_error_with_hint, CommandError};
+use crate::command_error::{user_error_with_hint, CommandError, ErrorCode};
 use crate::description_util::{description_template_for_commit, edit_description};
 use crate::ui::Ui;
 
@@ -71,6 +71,7 @@ pub(crate) fn cmd_split(
     let commit = workspace_command.resolve_single_rev(&args.revision)?;
     if commit.is_empty(workspace_command.repo().as_ref())? {
         return Err(user_error_with_hint(
+            ErrorCode::InvalidArgument,
             format!("Refusing to split empty commit {}.", commit.id().hex()),
             "Use `jj new` if you want to create another empty commit.",
         ));
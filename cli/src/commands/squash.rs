@@ -12,18 +12,30 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+
 use itertools::Itertools as _;
 use jj_lib::commit::{Commit, CommitIteratorExt};
-use jj_lib::matchers::Matcher;
+use jj_lib::matchers::{FilesMatcher, Matcher};
 use jj_lib::merged_tree::MergedTree;
 use jj_lib::object_id::ObjectId;
 use jj_lib::repo::Repo;
+use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::settings::UserSettings;
 use tracing::instrument;
 
-use crate::cli_util::{CommandHelper, DiffSelector, RevisionArg, WorkspaceCommandTransaction};
-use crate::command_error::{user_error, CommandError};
-use crate::description_util::{combine_messages, join_message_paragraphs};
+use crate::cli_util::{
+    edit_temp_file, CommandHelper, DiffSelector, RevisionArg, WorkspaceCommandHelper,
+    WorkspaceCommandTransaction,
+};
+use crate::command_error::{user_error, CommandError, ErrorCode};
+use crate::description_util::{
+    combine_messages, combine_messages_with_template, combined_descriptions_template_language,
+    join_message_paragraphs, CombinedDescriptions,
+};
+use crate::generic_templater::GenericTemplateLanguage;
+use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
 
 /// Move changes from a revision into another revision
@@ -38,12 +50,14 @@ use crate::ui::Ui;
 /// commit to the grandparent.
 ///
 /// If, after moving changes out, the source revision is empty compared to its
-/// parent(s), it will be abandoned. Without `--interactive`, the source
-/// revision will always be empty.
+/// parent(s), it will be abandoned. Without `--interactive` or `--files`, the
+/// source revision will always be empty.
 ///
 /// If the source became empty and both the source and destination had a
 /// non-empty description, you will be asked for the combined description. If
-/// either was empty, then the other one will be used.
+/// either was empty, then the other one will be used. With
+/// `--message-template`, the combined description is instead computed by the
+/// given template.
 ///
 /// If a working-copy commit gets abandoned, it will be given a new, empty
 /// commit. This is true in general; it is not specific to this command.
@@ -65,12 +79,38 @@ pub(crate) struct SquashArgs {
     /// description(s) of the source revision(s)
     #[arg(long, short, conflicts_with = "message_paragraphs")]
     use_destination_message: bool,
+    /// Compute the description of the squashed revision from the source and
+    /// destination descriptions by using the given template (don't open
+    /// editor)
+    ///
+    /// The template can refer to the description of the destination revision
+    /// as `destination`, and to the descriptions of the source revisions that
+    /// become empty as `sources` (a list). For example,
+    /// `--message-template 'destination ++ sources.join("")'` appends the
+    /// source descriptions to the destination description.
+    ///
+    /// If the template produces an empty description, the description of the
+    /// destination revision is kept.
+    #[arg(
+        long,
+        value_name = "TEMPLATE",
+        conflicts_with_all = ["message_paragraphs", "use_destination_message"]
+    )]
+    message_template: Option<String>,
     /// Interactively choose which parts to squash
     #[arg(long, short)]
     interactive: bool,
     /// Specify diff editor to be used (implies --interactive)
     #[arg(long, value_name = "NAME")]
     tool: Option<String>,
+    /// Interactively choose which files to squash, without selecting hunks
+    ///
+    /// Opens an editor with the list of changed files. The changes to the
+    /// files that are left in the list are moved as a whole, including
+    /// conflicted files. If the paths are given, only the matching files are
+    /// listed.
+    #[arg(long, conflicts_with_all = ["interactive", "tool"])]
+    files: bool,
     /// Move only changes to these paths (instead of all paths)
     #[arg(conflicts_with_all = ["interactive", "tool"], value_hint = clap::ValueHint::AnyPath)]
     paths: Vec<String>,
@@ -97,7 +137,10 @@ pub(crate) fn cmd_squash(
         destination =
             workspace_command.resolve_single_rev(args.into.as_ref().unwrap_or(&RevisionArg::AT))?;
         if sources.iter().any(|source| source.id() == destination.id()) {
-            return Err(user_error("Source and destination cannot be the same"));
+            return Err(user_error(
+                ErrorCode::InvalidArgument,
+                "Source and destination cannot be the same",
+            ));
         }
         // Reverse the set so we apply the oldest commits first. It shouldn't affect the
         // result, but it avoids creating transient conflicts and is therefore probably
@@ -108,17 +151,41 @@ pub(crate) fn cmd_squash(
             .resolve_single_rev(args.revision.as_ref().unwrap_or(&RevisionArg::AT))?;
         let mut parents: Vec<_> = source.parents().try_collect()?;
         if parents.len() != 1 {
-            return Err(user_error("Cannot squash merge commits"));
+            return Err(user_error(
+                ErrorCode::Unsupported,
+                "Cannot squash merge commits",
+            ));
         }
         sources = vec![source];
         destination = parents.pop().unwrap();
     }
 
-    let matcher = workspace_command
+    let mut matcher = workspace_command
         .parse_file_patterns(&args.paths)?
         .to_matcher();
+    if args.files {
+        let selected_files = select_files(
+            &workspace_command,
+            command.settings(),
+            &sources,
+            &destination,
+            matcher.as_ref(),
+        )?;
+        matcher = Box::new(FilesMatcher::new(selected_files));
+    }
     let diff_selector =
         workspace_command.diff_selector(ui, args.tool.as_deref(), args.interactive)?;
+    let description = if let Some(template_text) = &args.message_template {
+        let language = combined_descriptions_template_language();
+        let template = workspace_command.parse_template(
+            &language,
+            template_text,
+            GenericTemplateLanguage::wrap_self,
+        )?;
+        SquashedDescription::Template(template)
+    } else {
+        SquashedDescription::from_args(args)
+    };
     let mut tx = workspace_command.start_transaction();
     let tx_description = format!("squash commits into {}", destination.id().hex());
     move_diff(
@@ -129,7 +196,7 @@ pub(crate) fn cmd_squash(
         &destination,
         matcher.as_ref(),
         &diff_selector,
-        SquashedDescription::from_args(args),
+        description,
         args.revision.is_none() && args.from.is_empty() && args.into.is_none(),
         &args.paths,
     )?;
@@ -137,6 +204,71 @@ pub(crate) fn cmd_squash(
     Ok(())
 }
 
+/// Lets the user pick the files to move by editing the list of files changed
+/// in the `sources`.
+fn select_files(
+    workspace_command: &WorkspaceCommandHelper,
+    settings: &UserSettings,
+    sources: &[Commit],
+    destination: &Commit,
+    matcher: &dyn Matcher,
+) -> Result<Vec<RepoPathBuf>, CommandError> {
+    let repo = workspace_command.repo();
+    let mut changed_files = BTreeSet::new();
+    for source in sources {
+        let parent_tree = source.parent_tree(repo.as_ref())?;
+        for (path, diff) in parent_tree.diff(&source.tree()?, matcher) {
+            diff?;
+            changed_files.insert(path);
+        }
+    }
+    if changed_files.is_empty() {
+        return Err(user_error(
+            ErrorCode::NothingSelected,
+            "No changes to select files from",
+        ));
+    }
+
+    let mut content = format!(
+        "\
+JJ: Choose the files to move into: {}
+JJ: Remove the lines of the files whose changes should stay in the source.
+JJ: Lines starting with \"JJ: \" (like this one) will be removed.
+",
+        workspace_command.format_commit_summary(destination)
+    );
+    for path in &changed_files {
+        writeln!(content, "{}", path.as_internal_file_string()).unwrap();
+    }
+    let content = edit_temp_file(
+        "file list",
+        ".jjfiles",
+        repo.repo_path(),
+        &content,
+        settings,
+    )?;
+
+    let selected_files: Vec<RepoPathBuf> = content
+        .lines()
+        .filter(|line| !line.starts_with("JJ: "))
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            changed_files
+                .iter()
+                .find(|path| path.as_internal_file_string() == line)
+                .cloned()
+                .ok_or_else(|| {
+                    user_error(ErrorCode::NoSuchPath, format!("Not a changed file: {line}"))
+                })
+        })
+        .try_collect()?;
+    if selected_files.is_empty() {
+        return Err(user_error(ErrorCode::NothingSelected, "No files selected"));
+    }
+    Ok(selected_files)
+}
+
 // TODO(#2882): Remove public visibility once `jj move` is deleted.
 pub(crate) enum SquashedDescription {
     // Use this exact description.
@@ -146,6 +278,9 @@ pub(crate) enum SquashedDescription {
     UseDestination,
     // Combine the descriptions of the source and destination revisions.
     Combine,
+    // Combine the descriptions of the source and destination revisions by
+    // evaluating the template.
+    Template(TemplateRenderer<'static, CombinedDescriptions>),
 }
 
 // TODO(#2882): Remove public visibility once `jj move` is deleted.
@@ -228,7 +363,10 @@ from the source will be moved into the destination.
     }
     if source_commits.is_empty() {
         if diff_selector.is_interactive() {
-            return Err(user_error("No changes selected"));
+            return Err(user_error(
+                ErrorCode::NothingSelected,
+                "No changes selected",
+            ));
         }
 
         if let [only_path] = path_arg {
@@ -292,6 +430,23 @@ from the source will be moved into the destination.
                 .collect_vec();
             combine_messages(tx.base_repo(), &abandoned_commits, destination, settings)?
         }
+        SquashedDescription::Template(template) => {
+            let abandoned_commits = source_commits
+                .iter()
+                .filter_map(|source| source.abandon.then_some(source.commit))
+                .collect_vec();
+            match combine_messages_with_template(&abandoned_commits, destination, &template)? {
+                Some(description) => description,
+                None => {
+                    writeln!(
+                        ui.status(),
+                        "The message template produced an empty description; keeping the \
+                         description of the destination revision."
+                    )?;
+                    destination.description().to_owned()
+                }
+            }
+        }
     };
     let mut predecessors = vec![destination.id().clone()];
     predecessors.extend(
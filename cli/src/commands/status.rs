@@ -12,16 +12,33 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fs;
+use std::io::{self, Write as _};
+use std::path::Path;
+
+use futures::StreamExt as _;
 use itertools::Itertools;
+use jj_lib::backend::BackendResult;
+use jj_lib::commit::Commit;
+use jj_lib::local_working_copy::LocalWorkingCopy;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::object_id::ObjectId as _;
 use jj_lib::repo::Repo;
+use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::revset::{RevsetExpression, RevsetFilterPredicate};
+use pollster::FutureExt as _;
+use tempfile::NamedTempFile;
 use tracing::instrument;
 
 use crate::cli_util::{print_conflicted_paths, CommandHelper};
 use crate::command_error::CommandError;
-use crate::diff_util::DiffFormat;
-use crate::revset_util;
 use crate::ui::Ui;
+use crate::{diff_util, revset_util};
+
+/// Name of the file in the local working copy's state directory where the
+/// summary of the working-copy changes is cached.
+const STATUS_CACHE_FILE_NAME: &str = "status_cache";
 
 /// Show high-level repo status
 ///
@@ -65,8 +82,39 @@ pub(crate) fn cmd_status(
             writeln!(formatter, "The working copy is clean")?;
         } else {
             writeln!(formatter, "Working copy changes:")?;
-            let diff_renderer = workspace_command.diff_renderer(vec![DiffFormat::Summary]);
-            diff_renderer.show_diff(ui, formatter, &parent_tree, &tree, &matcher)?;
+            // Only local working copies have a state directory to cache in.
+            let cache_path = workspace_command
+                .working_copy()
+                .as_any()
+                .downcast_ref::<LocalWorkingCopy>()
+                .map(|wc| wc.state_path().join(STATUS_CACHE_FILE_NAME));
+            let cache_key = status_cache_key(wc_commit, &parent_tree);
+            let cached_changes = cache_path
+                .as_deref()
+                .and_then(|path| read_status_cache(path, &cache_key));
+            let changes = match cached_changes {
+                Some(changes) => changes,
+                None => {
+                    let changes = summarize_changes(&parent_tree, &tree)?;
+                    if let Some(path) = &cache_path {
+                        // The cache is only advisory, so failing to write it is fine.
+                        write_status_cache(path, &cache_key, &changes).ok();
+                    }
+                    changes
+                }
+            };
+            formatter.with_label("diff", |formatter| -> io::Result<()> {
+                for (status, path) in &changes {
+                    if matcher.matches(path) {
+                        writeln!(
+                            formatter.labeled(diff_util::diff_status_label(*status)),
+                            "{status} {}",
+                            workspace_command.format_file_path(path)
+                        )?;
+                    }
+                }
+                Ok(())
+            })?;
         }
 
         // TODO: Conflicts should also be filtered by the `matcher`. See the related
@@ -156,3 +204,83 @@ pub(crate) fn cmd_status(
 
     Ok(())
 }
+
+/// Identifies the working-copy changes a status cache was computed for.
+///
+/// The working-copy commit id determines its tree and parents. The parent tree
+/// id is included too since merging the parents' trees may change over time.
+fn status_cache_key(wc_commit: &Commit, parent_tree: &MergedTree) -> String {
+    let parent_tree_ids = parent_tree
+        .id()
+        .to_merge()
+        .iter()
+        .map(|id| id.hex())
+        .join(",");
+    format!("{} {parent_tree_ids}", wc_commit.id().hex())
+}
+
+/// Lists the changed paths, each with a status character like `jj diff
+/// --summary`.
+fn summarize_changes(
+    parent_tree: &MergedTree,
+    tree: &MergedTree,
+) -> BackendResult<Vec<(char, RepoPathBuf)>> {
+    let mut tree_diff = parent_tree.diff_stream(tree, &EverythingMatcher);
+    async {
+        let mut changes = vec![];
+        while let Some((path, diff)) = tree_diff.next().await {
+            let (before, after) = diff?;
+            let (status, _) = diff_util::diff_status_char_and_label(&before, &after);
+            changes.push((status, path));
+        }
+        Ok(changes)
+    }
+    .block_on()
+}
+
+/// Reads the cached changes, or returns `None` if the cache is missing,
+/// corrupt, or was written for other changes.
+fn read_status_cache(path: &Path, key: &str) -> Option<Vec<(char, RepoPathBuf)>> {
+    let content = fs::read_to_string(path).ok()?;
+    let mut lines = content.lines();
+    if lines.next()? != key {
+        return None;
+    }
+    lines
+        .map(|line| {
+            let (status, path) = line.split_once(' ')?;
+            let status = match status {
+                "M" => 'M',
+                "A" => 'A',
+                "D" => 'D',
+                _ => return None,
+            };
+            // Reject anything `as_internal_file_string()` can't have produced
+            // rather than panicking in `from_internal_string()`.
+            if path.is_empty()
+                || path.starts_with('/')
+                || path.ends_with('/')
+                || path.contains("//")
+            {
+                return None;
+            }
+            Some((status, RepoPathBuf::from_internal_string(path)))
+        })
+        .collect()
+}
+
+fn write_status_cache(path: &Path, key: &str, changes: &[(char, RepoPathBuf)]) -> io::Result<()> {
+    let mut content = format!("{key}\n");
+    for (status, path) in changes {
+        let path = path.as_internal_file_string();
+        if path.contains('\n') {
+            // Can't be represented in the cache file
+            return Ok(());
+        }
+        content.push_str(&format!("{status} {path}\n"));
+    }
+    let mut temp_file = NamedTempFile::new_in(path.parent().unwrap())?;
+    temp_file.write_all(content.as_bytes())?;
+    temp_file.persist(path).map_err(|err| err.error)?;
+    Ok(())
+}
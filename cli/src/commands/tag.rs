@@ -14,7 +14,7 @@
 
 use jj_lib::str_util::StringPattern;
 
-use crate::cli_util::CommandHelper;
+use crate::cli_util::{CommandHelper, TemplateFileArgs};
 use crate::command_error::CommandError;
 use crate::commit_templater::{CommitTemplateLanguage, RefName};
 use crate::ui::Ui;
@@ -43,6 +43,8 @@ pub struct TagListArgs {
     /// For the syntax, see https://github.com/martinvonz/jj/blob/main/docs/templates.md
     #[arg(long, short = 'T')]
     template: Option<String>,
+    #[command(flatten)]
+    template_file: TemplateFileArgs,
 }
 
 pub fn cmd_tag(
@@ -66,13 +68,15 @@ fn cmd_tag_list(
 
     let template = {
         let language = workspace_command.commit_template_language()?;
-        let text = match &args.template {
-            Some(value) => value.to_owned(),
-            None => command.settings().config().get("templates.tag_list")?,
-        };
-        workspace_command
-            .parse_template(&language, &text, CommitTemplateLanguage::wrap_ref_name)?
-            .labeled("tag_list")
+        let text = args.template_file.template_text(
+            command,
+            args.template.as_deref(),
+            "templates.tag_list",
+        )?;
+        text.parse(|text| {
+            workspace_command.parse_template(&language, text, CommitTemplateLanguage::wrap_ref_name)
+        })?
+        .labeled("tag_list")
     };
 
     ui.request_pager();
@@ -0,0 +1,94 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use itertools::Itertools as _;
+use jj_lib::commit::{Commit, CommitIteratorExt};
+use jj_lib::signing::SignBehavior;
+use tracing::instrument;
+
+use crate::cli_util::{CommandHelper, RevisionArg};
+use crate::command_error::CommandError;
+use crate::progress::RewriteProgressReporter;
+use crate::ui::Ui;
+
+/// Drop the cryptographic signatures of revisions
+///
+/// The selected revisions are rewritten without their signatures. Their change
+/// IDs are preserved, and their descendants are rebased onto the rewritten
+/// revisions. Revisions which aren't signed are left as they are.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct UnsignArgs {
+    /// The revision(s) to unsign
+    #[arg(long, short, required = true)]
+    revisions: Vec<RevisionArg>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_unsign(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &UnsignArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let commits: Vec<Commit> = workspace_command
+        .parse_union_revsets(&args.revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    workspace_command.check_rewritable(commits.iter().ids())?;
+
+    let (to_unsign, not_signed): (Vec<_>, Vec<_>) =
+        commits.into_iter().partition(|commit| commit.is_signed());
+    if let Some(mut formatter) = ui.status_formatter() {
+        for commit in &not_signed {
+            write!(formatter, "Skipping unsigned commit ")?;
+            workspace_command.write_commit_summary(formatter.as_mut(), commit)?;
+            writeln!(formatter)?;
+        }
+    }
+    if to_unsign.is_empty() {
+        writeln!(ui.status(), "Nothing changed.")?;
+        return Ok(());
+    }
+
+    let mut tx = workspace_command.start_transaction();
+    let to_unsign_ids: HashSet<_> = to_unsign.iter().ids().collect();
+    let mut num_rebased = 0;
+    let mut progress = RewriteProgressReporter::new(ui, "Unsigning");
+    tx.mut_repo().transform_descendants_with_progress(
+        command.settings(),
+        to_unsign.iter().ids().cloned().collect_vec(),
+        &mut |p| progress.update(p),
+        |rewriter| {
+            if to_unsign_ids.contains(rewriter.old_commit().id()) {
+                rewriter
+                    .reparent(command.settings())?
+                    .set_sign_behavior(SignBehavior::Drop)
+                    .write()?;
+            } else if rewriter.parents_changed() {
+                rewriter.rebase(command.settings())?.write()?;
+                num_rebased += 1;
+            }
+            Ok(())
+        },
+    )?;
+    drop(progress);
+    writeln!(ui.status(), "Unsigned {} commits", to_unsign.len())?;
+    if num_rebased > 0 {
+        writeln!(ui.status(), "Rebased {num_rebased} descendant commits")?;
+    }
+    tx.finish(ui, format!("unsign {} commits", to_unsign.len()))?;
+    Ok(())
+}
@@ -17,7 +17,7 @@ use jj_lib::object_id::ObjectId;
 use tracing::instrument;
 
 use crate::cli_util::{CommandHelper, RevisionArg};
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, CommandError, ErrorCode};
 use crate::description_util::combine_messages;
 use crate::ui::Ui;
 
@@ -58,7 +58,10 @@ pub(crate) fn cmd_unsquash(
     let commit = workspace_command.resolve_single_rev(&args.revision)?;
     workspace_command.check_rewritable([commit.id()])?;
     if commit.parent_ids().len() > 1 {
-        return Err(user_error("Cannot unsquash merge commits"));
+        return Err(user_error(
+            ErrorCode::Unsupported,
+            "Cannot unsquash merge commits",
+        ));
     }
     let parent = commit.parents().next().unwrap()?;
     workspace_command.check_rewritable([parent.id()])?;
@@ -94,7 +97,10 @@ aborted.
             Some(&instructions),
         )?;
         if new_parent_tree_id == parent_base_tree.id() {
-            return Err(user_error("No changes selected"));
+            return Err(user_error(
+                ErrorCode::NothingSelected,
+                "No changes selected",
+            ));
         }
     } else {
         new_parent_tree_id = parent_base_tree.id().clone();
@@ -15,6 +15,7 @@
 use std::io::Write;
 
 use itertools::Itertools;
+use jj_lib::matchers::EverythingMatcher;
 use jj_lib::merge::Merge;
 use jj_lib::merged_tree::MergedTreeBuilder;
 use jj_lib::repo::Repo;
@@ -22,7 +23,7 @@ use jj_lib::working_copy::SnapshotOptions;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
-use crate::command_error::{user_error_with_hint, CommandError};
+use crate::command_error::{user_error_with_hint, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 /// Stop tracking specified paths in the working copy
@@ -30,8 +31,8 @@ use crate::ui::Ui;
 pub(crate) struct UntrackArgs {
     /// Paths to untrack. They must already be ignored.
     ///
-    /// The paths could be ignored via a .gitignore or .git/info/exclude (in
-    /// colocated repos).
+    /// The paths could be ignored via a .gitignore, .jjignore, .jj/repo/ignore,
+    /// or .git/info/exclude (in colocated repos).
     #[arg(required = true, value_hint = clap::ValueHint::AnyPath)]
     paths: Vec<String>,
 }
@@ -72,6 +73,7 @@ pub(crate) fn cmd_untrack(
         fsmonitor_settings: command.settings().fsmonitor_settings()?,
         progress: None,
         max_new_file_size: command.settings().max_new_file_size()?,
+        matcher: &EverythingMatcher,
     })?;
     if wc_tree_id != *new_commit.tree_id() {
         let wc_tree = store.get_root_tree(&wc_tree_id)?;
@@ -90,6 +92,7 @@ pub(crate) fn cmd_untrack(
                 format!("'{ui_path}' is not ignored.")
             };
             return Err(user_error_with_hint(
+                ErrorCode::InvalidArgument,
                 message,
                 "Files that are not ignored will be added back by the next command.
 Make sure they're ignored, then try again.",
@@ -21,7 +21,7 @@ use jj_lib::repo::Repo;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, CommandError, ErrorCode};
 use crate::ui::Ui;
 
 /// Infrequently used commands such as for generating shell completions
@@ -158,6 +158,7 @@ fn cmd_util_completion(
         }
         _ => {
             return Err(user_error(
+                ErrorCode::Unsupported,
                 "cannot generate completion for multiple shells at once",
             ))
         }
@@ -175,13 +176,19 @@ fn cmd_util_gc(
 ) -> Result<(), CommandError> {
     if command.global_args().at_operation != "@" {
         return Err(user_error(
+            ErrorCode::Unsupported,
             "Cannot garbage collect from a non-head operation",
         ));
     }
     let keep_newer = match args.expire.as_deref() {
         None => SystemTime::now() - Duration::from_secs(14 * 86400),
         Some("now") => SystemTime::now() - Duration::ZERO,
-        _ => return Err(user_error("--expire only accepts 'now'")),
+        _ => {
+            return Err(user_error(
+                ErrorCode::Unsupported,
+                "--expire only accepts 'now'",
+            ))
+        }
     };
     let workspace_command = command.workspace_helper(ui)?;
 
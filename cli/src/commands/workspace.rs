@@ -15,6 +15,7 @@
 use std::fmt::Debug;
 use std::fs;
 use std::io::Write;
+use std::path::Path;
 use std::sync::Arc;
 
 use clap::Subcommand;
@@ -22,19 +23,27 @@ use itertools::Itertools;
 use jj_lib::commit::CommitIteratorExt;
 use jj_lib::file_util;
 use jj_lib::file_util::IoResultExt;
+use jj_lib::local_working_copy::LocalWorkingCopy;
 use jj_lib::object_id::ObjectId;
 use jj_lib::op_store::{OpStoreError, WorkspaceId};
 use jj_lib::operation::Operation;
 use jj_lib::repo::{ReadonlyRepo, Repo};
 use jj_lib::rewrite::merge_commit_trees;
-use jj_lib::workspace::Workspace;
+use jj_lib::working_copy::{LockedWorkingCopy, WorkingCopy};
+use jj_lib::workspace::{Workspace, WorkspaceLoadError, WorkspaceLoader};
+use jj_lib::workspace_store::SimpleWorkspaceStore;
 use tracing::instrument;
 
 use crate::cli_util::{
-    check_stale_working_copy, print_checkout_stats, short_commit_hash, CommandHelper, RevisionArg,
+    check_stale_working_copy, check_working_copy_freshness, print_checkout_stats,
+    short_commit_hash, short_operation_hash, CommandHelper, RevisionArg, TemplateFileArgs,
     WorkingCopyFreshness, WorkspaceCommandHelper,
 };
-use crate::command_error::{internal_error_with_message, user_error, CommandError};
+use crate::command_error::{
+    internal_error_with_message, user_error, user_error_with_hint, user_error_with_message,
+    CommandError, ErrorCode,
+};
+use crate::commit_templater::{CommitTemplateLanguage, WorkspaceRef};
 use crate::ui::Ui;
 
 /// Commands for working with workspaces
@@ -88,18 +97,60 @@ pub(crate) struct WorkspaceAddArgs {
 
 /// Stop tracking a workspace's working-copy commit in the repo
 ///
-/// The workspace will not be touched on disk. It can be deleted from disk
-/// before or after running this command.
+/// The workspace will not be touched on disk unless `--remove-jj-dir` is
+/// given. It can be deleted from disk before or after running this command.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct WorkspaceForgetArgs {
     /// Names of the workspaces to forget. By default, forgets only the current
     /// workspace.
     workspaces: Vec<String>,
+    /// Also abandon the working-copy commits of the workspaces
+    ///
+    /// Only working-copy commits that are empty, have no description, have no
+    /// descendants, and aren't pointed to by a local branch or another
+    /// workspace are abandoned. Other working-copy commits are kept and
+    /// listed. The current workspace can't be forgotten with this option.
+    #[arg(long)]
+    include_working_copy: bool,
+    /// Also remove the `.jj` directory of the workspaces
+    ///
+    /// The workspace is found at the path recorded when it was added. The
+    /// files in the working copy are left in place. The current workspace and
+    /// the workspace holding the repo can't be removed this way.
+    #[arg(long)]
+    remove_jj_dir: bool,
 }
 
 /// List workspaces
+///
+/// The current workspace is marked with `(current)`. Workspaces whose working
+/// copy hasn't been updated to their working-copy commit are marked with
+/// `(stale)`, and workspaces whose directory no longer exists are marked with
+/// `(missing)`. Each workspace is listed with the operation its working copy
+/// was last updated at.
+///
+/// Other workspaces are found through the paths recorded when they were
+/// added, so the state of workspaces added by older versions of jj isn't
+/// known.
 #[derive(clap::Args, Clone, Debug)]
-pub(crate) struct WorkspaceListArgs {}
+pub(crate) struct WorkspaceListArgs {
+    /// Only list stale workspaces
+    ///
+    /// The working copy isn't snapshotted, so this works even if the current
+    /// workspace is stale.
+    #[arg(long)]
+    stale: bool,
+    /// Render each workspace using the given template
+    ///
+    /// All 0-argument methods of the `WorkspaceRef` type are available as
+    /// keywords.
+    ///
+    /// For the syntax, see https://github.com/martinvonz/jj/blob/main/docs/templates.md
+    #[arg(long, short = 'T')]
+    template: Option<String>,
+    #[command(flatten)]
+    template_file: TemplateFileArgs,
+}
 
 /// Show the current workspace root directory
 #[derive(clap::Args, Clone, Debug)]
@@ -110,7 +161,17 @@ pub(crate) struct WorkspaceRootArgs {}
 /// For information about stale working copies, see
 /// https://github.com/martinvonz/jj/blob/main/docs/working-copy.md.
 #[derive(clap::Args, Clone, Debug)]
-pub(crate) struct WorkspaceUpdateStaleArgs {}
+pub(crate) struct WorkspaceUpdateStaleArgs {
+    /// Rebuild the working copy state from scratch
+    ///
+    /// This can be used to recover if the working copy state was corrupted,
+    /// e.g. by a crash during checkout. The state is rebuilt from the
+    /// working-copy commit recorded by the working copy's last operation. Files
+    /// on disk are not touched, and files which differ from that commit will
+    /// be recorded as modifications by the next command.
+    #[arg(long)]
+    force_rebuild: bool,
+}
 
 #[instrument(skip_all)]
 pub(crate) fn cmd_workspace(
@@ -136,7 +197,10 @@ fn cmd_workspace_add(
     let old_workspace_command = command.workspace_helper(ui)?;
     let destination_path = command.cwd().join(&args.destination);
     if destination_path.exists() {
-        return Err(user_error("Workspace already exists"));
+        return Err(user_error(
+            ErrorCode::AlreadyExists,
+            "Workspace already exists",
+        ));
     } else {
         fs::create_dir(&destination_path).context(&destination_path)?;
     }
@@ -153,9 +217,10 @@ fn cmd_workspace_add(
     let workspace_id = WorkspaceId::new(name.clone());
     let repo = old_workspace_command.repo();
     if repo.view().get_wc_commit_id(&workspace_id).is_some() {
-        return Err(user_error(format!(
-            "Workspace named '{name}' already exists"
-        )));
+        return Err(user_error(
+            ErrorCode::AlreadyExists,
+            format!("Workspace named '{name}' already exists"),
+        ));
     }
 
     let working_copy_factory = command.get_working_copy_factory()?;
@@ -175,6 +240,7 @@ fn cmd_workspace_add(
 
     // Copy sparse patterns from workspace where the command was run
     let mut new_workspace_command = WorkspaceCommandHelper::new(ui, command, new_workspace, repo)?;
+    let checkout_options = new_workspace_command.checkout_options()?;
     let (mut locked_ws, _wc_commit) = new_workspace_command.start_working_copy_mutation()?;
     let sparse_patterns = old_workspace_command
         .working_copy()
@@ -182,7 +248,7 @@ fn cmd_workspace_add(
         .to_vec();
     locked_ws
         .locked_wc()
-        .set_sparse_patterns(sparse_patterns)
+        .set_sparse_patterns(sparse_patterns, &checkout_options)
         .map_err(|err| internal_error_with_message("Failed to set sparse patterns", err))?;
     let operation_id = locked_ws.locked_wc().old_operation_id().clone();
     locked_ws.finish(operation_id)?;
@@ -253,14 +319,97 @@ fn cmd_workspace_forget(
             .get_wc_commit_id(ws)
             .is_none()
         {
-            return Err(user_error(format!("No such workspace: {}", ws.as_str())));
+            return Err(user_error(
+                ErrorCode::NoSuchWorkspace,
+                format!("No such workspace: {}", ws.as_str()),
+            ));
         }
     }
 
+    if args.include_working_copy && wss.contains(workspace_command.workspace_id()) {
+        return Err(user_error_with_hint(
+            ErrorCode::InvalidArgument,
+            "Cannot abandon the working-copy commit of the current workspace",
+            "Run the command from another workspace, or forget the workspace without \
+             --include-working-copy.",
+        ));
+    }
+    if args.remove_jj_dir && wss.contains(workspace_command.workspace_id()) {
+        return Err(user_error_with_hint(
+            ErrorCode::InvalidArgument,
+            "Cannot remove the .jj directory of the current workspace",
+            "Run the command from another workspace, or forget the workspace without \
+             --remove-jj-dir.",
+        ));
+    }
+
+    let workspace_store = SimpleWorkspaceStore::load(workspace_command.repo().repo_path());
+    let workspace_roots: Vec<_> = if args.remove_jj_dir {
+        wss.iter()
+            .map(|ws| workspace_store.get_workspace_path(ws))
+            .try_collect()?
+    } else {
+        vec![]
+    };
+
     // bundle every workspace forget into a single transaction, so that e.g.
     // undo correctly restores all of them at once.
     let mut tx = workspace_command.start_transaction();
+    let wc_commit_ids = wss
+        .iter()
+        .map(|ws| tx.repo().view().get_wc_commit_id(ws).unwrap().clone())
+        .collect_vec();
     wss.iter().for_each(|ws| tx.mut_repo().remove_wc_commit(ws));
+    if args.include_working_copy {
+        let mut abandoned_commits = vec![];
+        let mut kept_commits = vec![];
+        // Workspaces may share the working-copy commit
+        for (ws, commit_id) in wss.iter().zip(&wc_commit_ids).unique_by(|(_, id)| *id) {
+            let commit = tx.repo().store().get_commit(commit_id)?;
+            let view = tx.repo().view();
+            let is_abandonable = commit.is_discardable(tx.repo())?
+                && view.heads().contains(commit_id)
+                && view
+                    .local_branches()
+                    .all(|(_, target)| !target.added_ids().contains(commit_id))
+                && view.wc_commit_ids().values().all(|id| id != commit_id);
+            if is_abandonable {
+                abandoned_commits.push((ws, commit));
+            } else {
+                kept_commits.push((ws, commit));
+            }
+        }
+        for (_, commit) in &abandoned_commits {
+            tx.mut_repo().record_abandoned_commit(commit.id().clone());
+        }
+        tx.mut_repo().rebase_descendants(command.settings())?;
+        if let Some(mut formatter) = ui.status_formatter() {
+            for (ws, commit) in &abandoned_commits {
+                write!(
+                    formatter,
+                    "Abandoned working-copy commit of workspace {}: ",
+                    ws.as_str()
+                )?;
+                tx.base_workspace_helper()
+                    .write_commit_summary(formatter.as_mut(), commit)?;
+                writeln!(formatter)?;
+            }
+        }
+        if !kept_commits.is_empty() {
+            writeln!(
+                ui.warning_default(),
+                "Kept the working-copy commits of these workspaces since they have changes or are \
+                 in use:"
+            )?;
+            let mut formatter = ui.stderr_formatter();
+            for (ws, commit) in &kept_commits {
+                write!(formatter, "  {}: ", ws.as_str())?;
+                tx.base_workspace_helper()
+                    .write_commit_summary(formatter.as_mut(), commit)?;
+                writeln!(formatter)?;
+            }
+        }
+    }
     let description = if let [ws] = wss.as_slice() {
         format!("forget workspace {}", ws.as_str())
     } else {
@@ -271,6 +420,48 @@ fn cmd_workspace_forget(
     };
 
     tx.finish(ui, description)?;
+    for (ws, workspace_root) in wss.iter().zip(&workspace_roots) {
+        let Some(workspace_root) = workspace_root else {
+            writeln!(
+                ui.warning_default(),
+                "Not removing the .jj directory of workspace {} since its path isn't recorded",
+                ws.as_str()
+            )?;
+            continue;
+        };
+        let jj_dir = workspace_root.join(".jj");
+        if let Err(reason) = check_removable_jj_dir(workspace_root, workspace_command.repo()) {
+            writeln!(
+                ui.warning_default(),
+                "Not removing {} since {reason}",
+                jj_dir.display()
+            )?;
+            continue;
+        }
+        fs::remove_dir_all(&jj_dir).context(&jj_dir)?;
+        writeln!(
+            ui.status(),
+            "Removed the .jj directory of workspace {}: {}",
+            ws.as_str(),
+            jj_dir.display()
+        )?;
+    }
+    Ok(())
+}
+
+/// Checks that the `.jj` directory at `workspace_root` belongs to a workspace
+/// of `repo` and can be removed without removing the repo itself.
+fn check_removable_jj_dir(workspace_root: &Path, repo: &ReadonlyRepo) -> Result<(), &'static str> {
+    let loader =
+        WorkspaceLoader::init(workspace_root).map_err(|_| "it isn't a workspace anymore")?;
+    // The workspace holding the repo has a .jj/repo directory instead of a
+    // file pointing to it.
+    if workspace_root.join(".jj").join("repo").is_dir() {
+        return Err("it contains the repo");
+    }
+    if repo.repo_path().canonicalize().ok().as_deref() != Some(loader.repo_path()) {
+        return Err("it belongs to another repo");
+    }
     Ok(())
 }
 
@@ -278,21 +469,146 @@ fn cmd_workspace_forget(
 fn cmd_workspace_list(
     ui: &mut Ui,
     command: &CommandHelper,
-    _args: &WorkspaceListArgs,
+    args: &WorkspaceListArgs,
 ) -> Result<(), CommandError> {
-    let workspace_command = command.workspace_helper(ui)?;
+    let workspace_command = if args.stale {
+        command.workspace_helper_no_snapshot(ui)?
+    } else {
+        command.workspace_helper(ui)?
+    };
     let repo = workspace_command.repo();
-    let mut formatter = ui.stdout_formatter();
-    let template = workspace_command.commit_summary_template();
+    let template = {
+        let language = workspace_command.commit_template_language()?;
+        let text = args.template_file.template_text(
+            command,
+            args.template.as_deref(),
+            "templates.workspace_list",
+        )?;
+        text.parse(|text| {
+            workspace_command.parse_template(
+                &language,
+                text,
+                CommitTemplateLanguage::wrap_workspace_ref,
+            )
+        })?
+        .labeled("workspace_list")
+    };
+
+    let workspace_store = SimpleWorkspaceStore::load(repo.repo_path());
+    let mut workspaces = vec![];
     for (workspace_id, wc_commit_id) in repo.view().wc_commit_ids().iter().sorted() {
-        write!(formatter, "{}: ", workspace_id.as_str())?;
+        let current = workspace_id == workspace_command.workspace_id();
+        let other_wc;
+        let (wc, missing) = if current {
+            (Some(workspace_command.working_copy()), false)
+        } else {
+            match workspace_store.get_workspace_path(workspace_id)? {
+                Some(workspace_root) => {
+                    other_wc = load_working_copy_at(command, repo, workspace_id, &workspace_root)?;
+                    (other_wc.as_deref(), other_wc.is_none())
+                }
+                // Workspaces added before their paths were recorded
+                None => (None, false),
+            }
+        };
         let commit = repo.store().get_commit(wc_commit_id)?;
-        template.format(&commit, formatter.as_mut())?;
-        writeln!(formatter)?;
+        let (stale, last_operation) = if let Some(wc) = wc {
+            // Stale in the sense that `jj workspace update-stale` would update
+            // it, which includes recovering from a missing operation.
+            let stale =
+                match check_working_copy_freshness(wc.tree_id()?, wc.operation_id(), &commit, repo)
+                {
+                    Ok(WorkingCopyFreshness::Fresh | WorkingCopyFreshness::Updated(_)) => false,
+                    Ok(
+                        WorkingCopyFreshness::WorkingCopyStale
+                        | WorkingCopyFreshness::SiblingOperation,
+                    ) => true,
+                    Err(OpStoreError::ObjectNotFound { .. }) => true,
+                    Err(err) => return Err(err.into()),
+                };
+            let last_operation = match repo.op_store().read_operation(wc.operation_id()) {
+                Ok(op) => Some((wc.operation_id().clone(), op.metadata.end_time)),
+                Err(OpStoreError::ObjectNotFound { .. }) => None,
+                Err(err) => return Err(err.into()),
+            };
+            (stale, last_operation)
+        } else {
+            (false, None)
+        };
+        if args.stale && !stale {
+            continue;
+        }
+        workspaces.push(WorkspaceRef {
+            name: workspace_id.as_str().to_owned(),
+            target: commit,
+            current,
+            stale,
+            missing,
+            last_operation,
+        });
+    }
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for workspace in &workspaces {
+        template.format(workspace, formatter.as_mut())?;
     }
     Ok(())
 }
 
+/// Loads the working copy of another workspace of the repo. Returns `None` if
+/// the directory no longer exists, or no longer holds that workspace.
+fn load_working_copy_at(
+    command: &CommandHelper,
+    repo: &ReadonlyRepo,
+    workspace_id: &WorkspaceId,
+    workspace_root: &Path,
+) -> Result<Option<Box<dyn WorkingCopy>>, CommandError> {
+    let loader = match WorkspaceLoader::init(workspace_root) {
+        Ok(loader) => loader,
+        Err(WorkspaceLoadError::NoWorkspaceHere(_) | WorkspaceLoadError::RepoDoesNotExist(_)) => {
+            return Ok(None);
+        }
+        Err(err) => {
+            return Err(user_error_with_message(
+                ErrorCode::Io,
+                format!(
+                    "Failed to load workspace {} at {}",
+                    workspace_id.as_str(),
+                    workspace_root.display()
+                ),
+                err,
+            ))
+        }
+    };
+    let is_same_repo = match (
+        loader.repo_path().canonicalize(),
+        repo.repo_path().canonicalize(),
+    ) {
+        (Ok(path), Ok(repo_path)) => path == repo_path,
+        _ => false,
+    };
+    if !is_same_repo {
+        return Ok(None);
+    }
+    let wc = loader
+        .load_working_copy(repo.store(), command.working_copy_factories())
+        .map_err(|err| {
+            user_error_with_message(
+                ErrorCode::Io,
+                format!(
+                    "Failed to load the working copy of workspace {}",
+                    workspace_id.as_str()
+                ),
+                err,
+            )
+        })?;
+    if wc.workspace_id() != workspace_id {
+        return Ok(None);
+    }
+    Ok(Some(wc))
+}
+
 #[instrument(skip_all)]
 fn cmd_workspace_root(
     ui: &mut Ui,
@@ -303,7 +619,12 @@ fn cmd_workspace_root(
         .workspace_loader()?
         .workspace_root()
         .to_str()
-        .ok_or_else(|| user_error("The workspace root is not valid UTF-8"))?;
+        .ok_or_else(|| {
+            user_error(
+                ErrorCode::InvalidPath,
+                "The workspace root is not valid UTF-8",
+            )
+        })?;
     writeln!(ui.stdout(), "{root}")?;
     Ok(())
 }
@@ -375,12 +696,74 @@ fn for_stale_working_copy(
     Ok((command.for_loaded_repo(ui, workspace, repo)?, recovered))
 }
 
+/// Rebuilds the working copy's tree state from the working-copy commit of the
+/// working copy's last operation. The op store isn't modified.
+fn rebuild_working_copy_state(ui: &mut Ui, command: &CommandHelper) -> Result<(), CommandError> {
+    let workspace = command.load_workspace()?;
+    let Some(wc) = workspace
+        .working_copy()
+        .as_any()
+        .downcast_ref::<LocalWorkingCopy>()
+    else {
+        return Err(user_error(
+            ErrorCode::Unsupported,
+            "Rebuilding the working copy state is only supported for local working copies",
+        ));
+    };
+    let op_store = workspace.repo_loader().op_store();
+    let op_id = wc.operation_id().clone();
+    let op_data = op_store.read_operation(&op_id).map_err(|err| {
+        user_error_with_message(
+            ErrorCode::StaleWorkingCopy,
+            "Failed to read working copy's current operation",
+            err,
+        )
+        .hinted("Run `jj workspace update-stale` without `--force-rebuild` to recover")
+    })?;
+    let repo = workspace.repo_loader().load_at(&Operation::new(
+        op_store.clone(),
+        op_id.clone(),
+        op_data,
+    ))?;
+    let workspace_id = workspace.workspace_id();
+    let wc_commit_id = repo.view().get_wc_commit_id(workspace_id).ok_or_else(|| {
+        user_error(
+            ErrorCode::NoWorkingCopy,
+            format!(
+                "The workspace '{}' has no working-copy commit at operation {}",
+                workspace_id.as_str(),
+                short_operation_hash(&op_id)
+            ),
+        )
+    })?;
+    let wc_commit = repo.store().get_commit(wc_commit_id)?;
+
+    let mut locked_wc = wc.start_mutation_for_rebuild();
+    let num_file_states = locked_wc.rebuild(&wc_commit)?;
+    Box::new(locked_wc).finish(op_id)?;
+    writeln!(
+        ui.status(),
+        "Rebuilt {num_file_states} file states from commit {}",
+        short_commit_hash(wc_commit.id())
+    )?;
+    writeln!(
+        ui.hint_default(),
+        "Files which differ from that commit will be recorded as modifications by the next \
+         command."
+    )?;
+    Ok(())
+}
+
 #[instrument(skip_all)]
 fn cmd_workspace_update_stale(
     ui: &mut Ui,
     command: &CommandHelper,
-    _args: &WorkspaceUpdateStaleArgs,
+    args: &WorkspaceUpdateStaleArgs,
 ) -> Result<(), CommandError> {
+    if args.force_rebuild {
+        return rebuild_working_copy_state(ui, command);
+    }
+
     // Snapshot the current working copy on top of the last known working-copy
     // operation, then merge the concurrent operations. The wc_commit_id of the
     // merged repo wouldn't change because the old one wins, but it's probably
@@ -402,6 +785,7 @@ fn cmd_workspace_update_stale(
     let mut workspace_command = command.workspace_helper_no_snapshot(ui)?;
 
     let repo = workspace_command.repo().clone();
+    let checkout_options = workspace_command.checkout_options()?;
     let (mut locked_ws, desired_wc_commit) =
         workspace_command.unchecked_start_working_copy_mutation()?;
     match check_stale_working_copy(locked_ws.locked_wc(), &desired_wc_commit, &repo)? {
@@ -415,11 +799,14 @@ fn cmd_workspace_update_stale(
             // The same check as start_working_copy_mutation(), but with the stale
             // working-copy commit.
             if known_wc_commit.tree_id() != locked_ws.locked_wc().old_tree_id() {
-                return Err(user_error("Concurrent working copy operation. Try again."));
+                return Err(user_error(
+                    ErrorCode::ConcurrentOperation,
+                    "Concurrent working copy operation. Try again.",
+                ));
             }
-            let stats = locked_ws
+            let mut stats = locked_ws
                 .locked_wc()
-                .check_out(&desired_wc_commit)
+                .check_out(&desired_wc_commit, &checkout_options)
                 .map_err(|err| {
                     internal_error_with_message(
                         format!(
@@ -437,7 +824,11 @@ fn cmd_workspace_update_stale(
                 })?;
                 writeln!(formatter)?;
             }
+            let backup_tree_id = stats.backup_tree_id.take();
             print_checkout_stats(ui, stats, &desired_wc_commit)?;
+            if let Some(tree_id) = backup_tree_id {
+                workspace_command.record_working_copy_backup(ui, &known_wc_commit, tree_id)?;
+            }
         }
     }
     Ok(())
@@ -13,24 +13,32 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::cell::{Cell, RefCell};
 use std::cmp::max;
 use std::collections::HashMap;
-use std::io;
 use std::rc::Rc;
+use std::{io, iter};
 
+use futures::StreamExt as _;
 use itertools::Itertools as _;
-use jj_lib::backend::{ChangeId, CommitId};
+use jj_lib::backend::{ChangeId, CommitId, Timestamp};
 use jj_lib::commit::Commit;
 use jj_lib::extensions_map::ExtensionsMap;
 use jj_lib::git;
 use jj_lib::hex_util::to_reverse_hex;
 use jj_lib::id_prefix::IdPrefixContext;
+use jj_lib::matchers::EverythingMatcher;
 use jj_lib::object_id::ObjectId as _;
-use jj_lib::op_store::{RefTarget, RemoteRef, WorkspaceId};
+use jj_lib::op_store::{OperationId, RefTarget, RemoteRef, WorkspaceId};
 use jj_lib::repo::Repo;
-use jj_lib::revset::{self, Revset, RevsetExpression, RevsetModifier, RevsetParseContext};
+use jj_lib::revset::{
+    self, Revset, RevsetEvaluationError, RevsetExpression, RevsetModifier, RevsetParseContext,
+};
 use once_cell::unsync::OnceCell;
+use pollster::FutureExt as _;
+use thiserror::Error;
 
+use crate::diff_util::{self, DiffStats};
 use crate::template_builder::{
     self, merge_fn_map, BuildContext, CoreTemplateBuildFnTable, CoreTemplatePropertyKind,
     IntoTemplateProperty, TemplateBuildMethodFnMap, TemplateLanguage,
@@ -175,6 +183,26 @@ impl<'repo> TemplateLanguage<'repo> for CommitTemplateLanguage<'repo> {
                 let build = template_parser::lookup_method(type_name, table, function)?;
                 build(self, build_ctx, property, function)
             }
+            CommitTemplatePropertyKind::TreeDiff(property) => {
+                let table = &self.build_fn_table.tree_diff_methods;
+                let build = template_parser::lookup_method(type_name, table, function)?;
+                build(self, build_ctx, property, function)
+            }
+            CommitTemplatePropertyKind::DiffStats(property) => {
+                let table = &self.build_fn_table.diff_stats_methods;
+                let build = template_parser::lookup_method(type_name, table, function)?;
+                build(self, build_ctx, property, function)
+            }
+            CommitTemplatePropertyKind::AheadBehind(property) => {
+                let table = &self.build_fn_table.ahead_behind_methods;
+                let build = template_parser::lookup_method(type_name, table, function)?;
+                build(self, build_ctx, property, function)
+            }
+            CommitTemplatePropertyKind::WorkspaceRef(property) => {
+                let table = &self.build_fn_table.workspace_ref_methods;
+                let build = template_parser::lookup_method(type_name, table, function)?;
+                build(self, build_ctx, property, function)
+            }
         }
     }
 }
@@ -245,6 +273,30 @@ impl<'repo> CommitTemplateLanguage<'repo> {
     ) -> CommitTemplatePropertyKind<'repo> {
         CommitTemplatePropertyKind::ShortestIdPrefix(Box::new(property))
     }
+
+    pub fn wrap_tree_diff(
+        property: impl TemplateProperty<Output = TreeDiff> + 'repo,
+    ) -> CommitTemplatePropertyKind<'repo> {
+        CommitTemplatePropertyKind::TreeDiff(Box::new(property))
+    }
+
+    pub fn wrap_diff_stats(
+        property: impl TemplateProperty<Output = Rc<DiffStats>> + 'repo,
+    ) -> CommitTemplatePropertyKind<'repo> {
+        CommitTemplatePropertyKind::DiffStats(Box::new(property))
+    }
+
+    pub fn wrap_ahead_behind(
+        property: impl TemplateProperty<Output = AheadBehind> + 'repo,
+    ) -> CommitTemplatePropertyKind<'repo> {
+        CommitTemplatePropertyKind::AheadBehind(Box::new(property))
+    }
+
+    pub fn wrap_workspace_ref(
+        property: impl TemplateProperty<Output = WorkspaceRef> + 'repo,
+    ) -> CommitTemplatePropertyKind<'repo> {
+        CommitTemplatePropertyKind::WorkspaceRef(Box::new(property))
+    }
 }
 
 pub enum CommitTemplatePropertyKind<'repo> {
@@ -257,6 +309,10 @@ pub enum CommitTemplatePropertyKind<'repo> {
     RefNameList(Box<dyn TemplateProperty<Output = Vec<Rc<RefName>>> + 'repo>),
     CommitOrChangeId(Box<dyn TemplateProperty<Output = CommitOrChangeId> + 'repo>),
     ShortestIdPrefix(Box<dyn TemplateProperty<Output = ShortestIdPrefix> + 'repo>),
+    TreeDiff(Box<dyn TemplateProperty<Output = TreeDiff> + 'repo>),
+    DiffStats(Box<dyn TemplateProperty<Output = Rc<DiffStats>> + 'repo>),
+    AheadBehind(Box<dyn TemplateProperty<Output = AheadBehind> + 'repo>),
+    WorkspaceRef(Box<dyn TemplateProperty<Output = WorkspaceRef> + 'repo>),
 }
 
 impl<'repo> IntoTemplateProperty<'repo> for CommitTemplatePropertyKind<'repo> {
@@ -271,6 +327,10 @@ impl<'repo> IntoTemplateProperty<'repo> for CommitTemplatePropertyKind<'repo> {
             CommitTemplatePropertyKind::RefNameList(_) => "List<RefName>",
             CommitTemplatePropertyKind::CommitOrChangeId(_) => "CommitOrChangeId",
             CommitTemplatePropertyKind::ShortestIdPrefix(_) => "ShortestIdPrefix",
+            CommitTemplatePropertyKind::TreeDiff(_) => "TreeDiff",
+            CommitTemplatePropertyKind::DiffStats(_) => "DiffStats",
+            CommitTemplatePropertyKind::AheadBehind(_) => "AheadBehind",
+            CommitTemplatePropertyKind::WorkspaceRef(_) => "WorkspaceRef",
         }
     }
 
@@ -293,6 +353,10 @@ impl<'repo> IntoTemplateProperty<'repo> for CommitTemplatePropertyKind<'repo> {
             }
             CommitTemplatePropertyKind::CommitOrChangeId(_) => None,
             CommitTemplatePropertyKind::ShortestIdPrefix(_) => None,
+            CommitTemplatePropertyKind::TreeDiff(_) => None,
+            CommitTemplatePropertyKind::DiffStats(_) => None,
+            CommitTemplatePropertyKind::AheadBehind(_) => None,
+            CommitTemplatePropertyKind::WorkspaceRef(_) => None,
         }
     }
 
@@ -328,6 +392,10 @@ impl<'repo> IntoTemplateProperty<'repo> for CommitTemplatePropertyKind<'repo> {
             CommitTemplatePropertyKind::ShortestIdPrefix(property) => {
                 Some(property.into_template())
             }
+            CommitTemplatePropertyKind::TreeDiff(_) => None,
+            CommitTemplatePropertyKind::DiffStats(property) => Some(property.into_template()),
+            CommitTemplatePropertyKind::AheadBehind(property) => Some(property.into_template()),
+            CommitTemplatePropertyKind::WorkspaceRef(property) => Some(property.into_template()),
         }
     }
 }
@@ -343,6 +411,10 @@ pub struct CommitTemplateBuildFnTable<'repo> {
     pub ref_name_methods: CommitTemplateBuildMethodFnMap<'repo, Rc<RefName>>,
     pub commit_or_change_id_methods: CommitTemplateBuildMethodFnMap<'repo, CommitOrChangeId>,
     pub shortest_id_prefix_methods: CommitTemplateBuildMethodFnMap<'repo, ShortestIdPrefix>,
+    pub tree_diff_methods: CommitTemplateBuildMethodFnMap<'repo, TreeDiff>,
+    pub diff_stats_methods: CommitTemplateBuildMethodFnMap<'repo, Rc<DiffStats>>,
+    pub ahead_behind_methods: CommitTemplateBuildMethodFnMap<'repo, AheadBehind>,
+    pub workspace_ref_methods: CommitTemplateBuildMethodFnMap<'repo, WorkspaceRef>,
 }
 
 impl<'repo> CommitTemplateBuildFnTable<'repo> {
@@ -354,6 +426,10 @@ impl<'repo> CommitTemplateBuildFnTable<'repo> {
             ref_name_methods: builtin_ref_name_methods(),
             commit_or_change_id_methods: builtin_commit_or_change_id_methods(),
             shortest_id_prefix_methods: builtin_shortest_id_prefix_methods(),
+            tree_diff_methods: builtin_tree_diff_methods(),
+            diff_stats_methods: builtin_diff_stats_methods(),
+            ahead_behind_methods: builtin_ahead_behind_methods(),
+            workspace_ref_methods: builtin_workspace_ref_methods(),
         }
     }
 
@@ -364,6 +440,10 @@ impl<'repo> CommitTemplateBuildFnTable<'repo> {
             ref_name_methods: HashMap::new(),
             commit_or_change_id_methods: HashMap::new(),
             shortest_id_prefix_methods: HashMap::new(),
+            tree_diff_methods: HashMap::new(),
+            diff_stats_methods: HashMap::new(),
+            ahead_behind_methods: HashMap::new(),
+            workspace_ref_methods: HashMap::new(),
         }
     }
 
@@ -374,6 +454,10 @@ impl<'repo> CommitTemplateBuildFnTable<'repo> {
             ref_name_methods,
             commit_or_change_id_methods,
             shortest_id_prefix_methods,
+            tree_diff_methods,
+            diff_stats_methods,
+            ahead_behind_methods,
+            workspace_ref_methods,
         } = extension;
 
         self.core.merge(core);
@@ -387,6 +471,10 @@ impl<'repo> CommitTemplateBuildFnTable<'repo> {
             &mut self.shortest_id_prefix_methods,
             shortest_id_prefix_methods,
         );
+        merge_fn_map(&mut self.tree_diff_methods, tree_diff_methods);
+        merge_fn_map(&mut self.diff_stats_methods, diff_stats_methods);
+        merge_fn_map(&mut self.ahead_behind_methods, ahead_behind_methods);
+        merge_fn_map(&mut self.workspace_ref_methods, workspace_ref_methods);
     }
 }
 
@@ -397,6 +485,8 @@ pub struct CommitKeywordCache<'repo> {
     tags_index: OnceCell<Rc<RefNamesIndex>>,
     git_refs_index: OnceCell<Rc<RefNamesIndex>>,
     is_immutable_fn: OnceCell<Rc<RevsetContainingFn<'repo>>>,
+    diff_stats: Rc<DiffStatsCache>,
+    ahead_behind: Rc<AheadBehindCache>,
 }
 
 impl<'repo> CommitKeywordCache<'repo> {
@@ -415,6 +505,14 @@ impl<'repo> CommitKeywordCache<'repo> {
             .get_or_init(|| Rc::new(build_ref_names_index(repo.view().git_refs())))
     }
 
+    pub fn diff_stats(&self) -> &Rc<DiffStatsCache> {
+        &self.diff_stats
+    }
+
+    pub fn ahead_behind(&self) -> &Rc<AheadBehindCache> {
+        &self.ahead_behind
+    }
+
     pub fn is_immutable_fn(
         &self,
         language: &CommitTemplateLanguage<'repo>,
@@ -631,6 +729,25 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(L::wrap_boolean(out_property))
         },
     );
+    map.insert(
+        "ahead_behind",
+        |language, _build_ctx, self_property, function| {
+            let [revset_node] = function.expect_exact_arguments()?;
+
+            let counter =
+                template_parser::expect_string_literal_with(revset_node, |revset, span| {
+                    let reference_ids = evaluate_user_revset(language, span, revset)?
+                        .iter()
+                        .collect_vec();
+                    AheadBehindCounter::new(language, reference_ids)
+                        .map_err(|err| TemplateParseError::expression(err.to_string(), span))
+                })?;
+
+            let out_property =
+                self_property.and_then(move |commit| Ok(counter.count(commit.id())?));
+            Ok(L::wrap_ahead_behind(out_property))
+        },
+    );
     map.insert(
         "conflict",
         |_language, _build_ctx, self_property, function| {
@@ -639,6 +756,15 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(L::wrap_boolean(out_property))
         },
     );
+    map.insert("note", |language, _build_ctx, self_property, function| {
+        function.expect_no_arguments()?;
+        let repo = language.repo;
+        let out_property = self_property.map(|commit| {
+            let note = repo.view().get_note(commit.change_id()).unwrap_or_default();
+            text_util::complete_newline(note)
+        });
+        Ok(L::wrap_string(out_property))
+    });
     map.insert("empty", |language, _build_ctx, self_property, function| {
         function.expect_no_arguments()?;
         let repo = language.repo;
@@ -651,6 +777,31 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
         let out_property = self_property.map(|commit| commit.id() == repo.store().root_commit_id());
         Ok(L::wrap_boolean(out_property))
     });
+    map.insert("diff", |language, _build_ctx, self_property, function| {
+        function.expect_no_arguments()?;
+        let cache = language.keyword_cache.diff_stats().clone();
+        let out_property = self_property.map(move |commit| TreeDiff {
+            commit,
+            stats_cache: cache.clone(),
+        });
+        Ok(L::wrap_tree_diff(out_property))
+    });
+    map.insert(
+        "diff_stat",
+        |language, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let repo = language.repo;
+            let cache = language.keyword_cache.diff_stats().clone();
+            let out_property = self_property.and_then(move |commit| {
+                let diff = TreeDiff {
+                    commit,
+                    stats_cache: cache.clone(),
+                };
+                diff.stats(repo)
+            });
+            Ok(L::wrap_diff_stats(out_property))
+        },
+    );
     map
 }
 
@@ -717,6 +868,191 @@ fn evaluate_user_revset<'repo>(
     evaluate_revset_expression(language, span, expression)
 }
 
+/// Numbers of commits ahead of and behind a reference revision.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AheadBehind {
+    /// Commits reachable from the commit but not from the reference.
+    pub ahead: usize,
+    /// Commits reachable from the reference but not from the commit.
+    pub behind: usize,
+}
+
+/// Ahead/behind counts of commits, keyed by the reference commits.
+#[derive(Debug, Default)]
+pub struct AheadBehindCache {
+    counts: RefCell<HashMap<Vec<CommitId>, HashMap<CommitId, AheadBehind>>>,
+    /// Commits which are counted together when the first of them is counted.
+    batch_ids: RefCell<Vec<CommitId>>,
+    is_used: Cell<bool>,
+}
+
+impl AheadBehindCache {
+    /// Whether any counter has been created for this cache.
+    pub fn is_used(&self) -> bool {
+        self.is_used.get()
+    }
+
+    /// Sets the commits that are going to be counted, so that they can be
+    /// counted by a single walk.
+    pub fn set_batch(&self, commit_ids: Vec<CommitId>) {
+        *self.batch_ids.borrow_mut() = commit_ids;
+    }
+}
+
+/// Error returned when the reference revset of [`AheadBehindCounter`] is
+/// empty.
+#[derive(Debug, Error)]
+#[error("Reference revset is empty")]
+pub struct EmptyReferenceError;
+
+/// Counts commits ahead of and behind the reference revisions.
+///
+/// The counts of a batch of commits are computed by a single walk over the
+/// ancestors of the commits and the reference, so ancestry shared between the
+/// commits is only visited once.
+pub struct AheadBehindCounter<'repo> {
+    repo: &'repo dyn Repo,
+    reference_ids: Vec<CommitId>,
+    cache: Rc<AheadBehindCache>,
+}
+
+impl<'repo> AheadBehindCounter<'repo> {
+    /// Creates counter relative to the non-empty set of `reference_ids`.
+    pub fn new(
+        language: &CommitTemplateLanguage<'repo>,
+        reference_ids: Vec<CommitId>,
+    ) -> Result<Self, EmptyReferenceError> {
+        if reference_ids.is_empty() {
+            return Err(EmptyReferenceError);
+        }
+        let cache = language.keyword_cache.ahead_behind().clone();
+        cache.is_used.set(true);
+        Ok(AheadBehindCounter {
+            repo: language.repo,
+            reference_ids,
+            cache,
+        })
+    }
+
+    /// Returns the counts of `commit_id`.
+    ///
+    /// If the commit isn't cached yet, the uncached commits of the batch set
+    /// on the cache are counted along with it.
+    pub fn count(&self, commit_id: &CommitId) -> Result<AheadBehind, RevsetEvaluationError> {
+        if let Some(counts) = self.cached(commit_id) {
+            return Ok(counts);
+        }
+        let commit_ids = iter::once(commit_id)
+            .chain(self.cache.batch_ids.borrow().iter())
+            .cloned()
+            .collect_vec();
+        self.count_all(&commit_ids)?;
+        Ok(self.cached(commit_id).unwrap())
+    }
+
+    fn cached(&self, commit_id: &CommitId) -> Option<AheadBehind> {
+        let counts = self.cache.counts.borrow();
+        counts.get(&self.reference_ids)?.get(commit_id).copied()
+    }
+
+    /// Counts the commits ahead of and behind the reference for all of
+    /// `commit_ids` at once, and caches the results.
+    fn count_all(&self, commit_ids: &[CommitId]) -> Result<(), RevsetEvaluationError> {
+        let commit_ids = commit_ids
+            .iter()
+            .filter(|id| self.cached(id).is_none())
+            .unique()
+            .cloned()
+            .collect_vec();
+        if commit_ids.is_empty() {
+            return Ok(());
+        }
+        // Bit `i` is set on the ancestors of `commit_ids[i]`, and the last bit
+        // on the ancestors of the reference.
+        let reference_bit = commit_ids.len();
+        let num_words = reference_bit / 64 + 1;
+        let mut all_bits = vec![u64::MAX; num_words];
+        all_bits[num_words - 1] = u64::MAX >> (63 - reference_bit % 64);
+        let mut reachable: HashMap<CommitId, Vec<u64>> = HashMap::new();
+        let sources = commit_ids
+            .iter()
+            .enumerate()
+            .chain(self.reference_ids.iter().map(|id| (reference_bit, id)));
+        for (bit, id) in sources {
+            let bits = reachable
+                .entry(id.clone())
+                .or_insert_with(|| vec![0; num_words]);
+            bits[bit / 64] |= 1 << (bit % 64);
+        }
+        // Commits reachable from all sources don't count, and neither do their
+        // ancestors, so the walk can stop once only such commits are left.
+        let mut num_partial = reachable.values().filter(|bits| **bits != all_bits).count();
+
+        let mut num_reference_ancestors = 0;
+        let mut num_ahead = vec![0; commit_ids.len()];
+        let mut num_common = vec![0; commit_ids.len()];
+        let expression = RevsetExpression::commits(commit_ids.clone())
+            .union(&RevsetExpression::commits(self.reference_ids.clone()))
+            .ancestors();
+        let revset = expression.evaluate_programmatic(self.repo)?;
+        for (id, edges) in revset.iter_graph() {
+            if num_partial == 0 {
+                break;
+            }
+            // Descendants are visited first, so the bits are complete.
+            let bits = reachable.remove(&id).unwrap();
+            if bits != all_bits {
+                num_partial -= 1;
+            }
+            let is_reference_ancestor = (bits[reference_bit / 64] >> (reference_bit % 64)) & 1 != 0;
+            if is_reference_ancestor {
+                num_reference_ancestors += 1;
+            }
+            let counts = if is_reference_ancestor {
+                &mut num_common
+            } else {
+                &mut num_ahead
+            };
+            for (i, word) in bits.iter().enumerate() {
+                let mut word = *word;
+                while word != 0 {
+                    let bit = i * 64 + word.trailing_zeros() as usize;
+                    if bit != reference_bit {
+                        counts[bit] += 1;
+                    }
+                    word &= word - 1;
+                }
+            }
+            for edge in edges {
+                let parent_bits = reachable.entry(edge.target).or_default();
+                let was_partial = !parent_bits.is_empty() && *parent_bits != all_bits;
+                if parent_bits.is_empty() {
+                    parent_bits.resize(num_words, 0);
+                }
+                for (parent_word, word) in parent_bits.iter_mut().zip(&bits) {
+                    *parent_word |= word;
+                }
+                match (was_partial, *parent_bits != all_bits) {
+                    (false, true) => num_partial += 1,
+                    (true, false) => num_partial -= 1,
+                    _ => {}
+                }
+            }
+        }
+
+        let mut cache = self.cache.counts.borrow_mut();
+        let counts = cache.entry(self.reference_ids.clone()).or_default();
+        for (i, id) in commit_ids.into_iter().enumerate() {
+            let ahead_behind = AheadBehind {
+                ahead: num_ahead[i],
+                behind: num_reference_ancestors - num_common[i],
+            };
+            counts.insert(id, ahead_behind);
+        }
+        Ok(())
+    }
+}
+
 /// Branch or tag name with metadata.
 #[derive(Debug)]
 pub struct RefName {
@@ -731,6 +1067,8 @@ pub struct RefName {
     /// Local ref is synchronized with all tracking remotes, or tracking remote
     /// ref is synchronized with the local.
     synced: bool,
+    /// User-provided description of the local ref.
+    description: String,
 }
 
 #[derive(Debug)]
@@ -753,6 +1091,16 @@ impl RefName {
         name: impl Into<String>,
         target: RefTarget,
         remote_refs: impl IntoIterator<Item = &'a RemoteRef>,
+    ) -> Rc<Self> {
+        Self::local_with_description(name, target, remote_refs, "")
+    }
+
+    /// Creates local ref representation with the user-provided `description`.
+    pub fn local_with_description<'a>(
+        name: impl Into<String>,
+        target: RefTarget,
+        remote_refs: impl IntoIterator<Item = &'a RemoteRef>,
+        description: impl Into<String>,
     ) -> Rc<Self> {
         let synced = remote_refs
             .into_iter()
@@ -763,6 +1111,7 @@ impl RefName {
             target,
             tracking_ref: None,
             synced,
+            description: description.into(),
         })
     }
 
@@ -798,6 +1147,7 @@ impl RefName {
             target: remote_ref.target,
             tracking_ref,
             synced,
+            description: String::new(),
         })
     }
 
@@ -813,6 +1163,7 @@ impl RefName {
             target,
             tracking_ref: None,
             synced: false, // has no local counterpart
+            description: String::new(),
         })
     }
 
@@ -922,6 +1273,14 @@ fn builtin_ref_name_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Rc
             Ok(L::wrap_string(out_property))
         },
     );
+    map.insert(
+        "description",
+        |_language, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|ref_name| ref_name.description.clone());
+            Ok(L::wrap_string(out_property))
+        },
+    );
     map.insert(
         "present",
         |_language, _build_ctx, self_property, function| {
@@ -1034,14 +1393,16 @@ impl RefNamesIndex {
 
 fn build_branches_index(repo: &dyn Repo) -> RefNamesIndex {
     let mut index = RefNamesIndex::default();
-    for (branch_name, branch_target) in repo.view().branches() {
+    let view = repo.view();
+    for (branch_name, branch_target) in view.branches() {
         let local_target = branch_target.local_target;
         let remote_refs = branch_target.remote_refs;
         if local_target.is_present() {
-            let ref_name = RefName::local(
+            let ref_name = RefName::local_with_description(
                 branch_name,
                 local_target.clone(),
                 remote_refs.iter().map(|&(_, remote_ref)| remote_ref),
+                view.get_branch_description(branch_name).unwrap_or_default(),
             );
             index.insert(local_target.added_ids(), ref_name);
         }
@@ -1212,3 +1573,224 @@ fn builtin_shortest_id_prefix_methods<'repo>(
     });
     map
 }
+
+/// Diff stats of commits, calculated on demand.
+pub type DiffStatsCache = RefCell<HashMap<CommitId, Rc<DiffStats>>>;
+
+/// Changes made by a commit relative to its (auto-merged) parents.
+#[derive(Clone)]
+pub struct TreeDiff {
+    commit: Commit,
+    stats_cache: Rc<DiffStatsCache>,
+}
+
+impl TreeDiff {
+    /// Paths of the changed files.
+    fn files(&self, repo: &dyn Repo) -> Result<Vec<String>, TemplatePropertyError> {
+        let from_tree = self.commit.parent_tree(repo)?;
+        let to_tree = self.commit.tree()?;
+        let paths = from_tree
+            .diff_stream(&to_tree, &EverythingMatcher)
+            .map(|(path, _)| path.as_internal_file_string().to_owned())
+            .collect()
+            .block_on();
+        Ok(paths)
+    }
+
+    /// Line-based stats of the changed files. Calculated once per commit.
+    fn stats(&self, repo: &dyn Repo) -> Result<Rc<DiffStats>, TemplatePropertyError> {
+        if let Some(stats) = self.stats_cache.borrow().get(self.commit.id()) {
+            return Ok(stats.clone());
+        }
+        let from_tree = self.commit.parent_tree(repo)?;
+        let to_tree = self.commit.tree()?;
+        let tree_diff = from_tree.diff_stream(&to_tree, &EverythingMatcher);
+        let stats = Rc::new(DiffStats::calculate(repo.store(), tree_diff)?);
+        self.stats_cache
+            .borrow_mut()
+            .insert(self.commit.id().clone(), stats.clone());
+        Ok(stats)
+    }
+}
+
+fn builtin_tree_diff_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, TreeDiff> {
+    type L<'repo> = CommitTemplateLanguage<'repo>;
+    // Not using maplit::hashmap!{} or custom declarative macro here because
+    // code completion inside macro is quite restricted.
+    let mut map = CommitTemplateBuildMethodFnMap::<TreeDiff>::new();
+    map.insert("files", |language, _build_ctx, self_property, function| {
+        function.expect_no_arguments()?;
+        let repo = language.repo;
+        let out_property = self_property.and_then(|diff| diff.files(repo));
+        Ok(L::wrap_string_list(out_property))
+    });
+    map.insert("stat", |language, _build_ctx, self_property, function| {
+        function.expect_no_arguments()?;
+        let repo = language.repo;
+        let out_property = self_property.and_then(|diff| diff.stats(repo));
+        Ok(L::wrap_diff_stats(out_property))
+    });
+    map
+}
+
+impl Template for Rc<DiffStats> {
+    fn format(&self, formatter: &mut TemplateFormatter) -> io::Result<()> {
+        diff_util::write_diff_stat_summary(formatter.as_mut(), self)
+    }
+}
+
+fn builtin_diff_stats_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Rc<DiffStats>> {
+    type L<'repo> = CommitTemplateLanguage<'repo>;
+    // Not using maplit::hashmap!{} or custom declarative macro here because
+    // code completion inside macro is quite restricted.
+    let mut map = CommitTemplateBuildMethodFnMap::<Rc<DiffStats>>::new();
+    map.insert(
+        "total_added",
+        |_language, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property =
+                self_property.and_then(|stats| Ok(stats.count_total_added().try_into()?));
+            Ok(L::wrap_integer(out_property))
+        },
+    );
+    map.insert(
+        "total_removed",
+        |_language, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property =
+                self_property.and_then(|stats| Ok(stats.count_total_removed().try_into()?));
+            Ok(L::wrap_integer(out_property))
+        },
+    );
+    map.insert(
+        "total_files",
+        |_language, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property =
+                self_property.and_then(|stats| Ok(stats.entries().len().try_into()?));
+            Ok(L::wrap_integer(out_property))
+        },
+    );
+    map
+}
+
+impl Template for AheadBehind {
+    fn format(&self, formatter: &mut TemplateFormatter) -> io::Result<()> {
+        write!(formatter.labeled("ahead"), "+{}", self.ahead)?;
+        write!(formatter, " ")?;
+        write!(formatter.labeled("behind"), "-{}", self.behind)
+    }
+}
+
+fn builtin_ahead_behind_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, AheadBehind> {
+    type L<'repo> = CommitTemplateLanguage<'repo>;
+    // Not using maplit::hashmap!{} or custom declarative macro here because
+    // code completion inside macro is quite restricted.
+    let mut map = CommitTemplateBuildMethodFnMap::<AheadBehind>::new();
+    map.insert("ahead", |_language, _build_ctx, self_property, function| {
+        function.expect_no_arguments()?;
+        let out_property = self_property.and_then(|counts| Ok(counts.ahead.try_into()?));
+        Ok(L::wrap_integer(out_property))
+    });
+    map.insert(
+        "behind",
+        |_language, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.and_then(|counts| Ok(counts.behind.try_into()?));
+            Ok(L::wrap_integer(out_property))
+        },
+    );
+    map
+}
+
+/// Workspace and its working-copy commit.
+#[derive(Clone, Debug)]
+pub struct WorkspaceRef {
+    /// Name of the workspace.
+    pub name: String,
+    /// Working-copy commit of the workspace in the repo view.
+    pub target: Commit,
+    /// Whether this is the workspace the command is run in.
+    pub current: bool,
+    /// Whether the working copy is known to be stale.
+    pub stale: bool,
+    /// Whether the workspace directory no longer exists or no longer holds
+    /// this workspace.
+    pub missing: bool,
+    /// Operation the working copy was last updated at, and when that
+    /// operation finished, if known.
+    pub last_operation: Option<(OperationId, Timestamp)>,
+}
+
+impl Template for WorkspaceRef {
+    fn format(&self, formatter: &mut TemplateFormatter) -> io::Result<()> {
+        write!(formatter.labeled("name"), "{}", self.name)
+    }
+}
+
+fn builtin_workspace_ref_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, WorkspaceRef> {
+    type L<'repo> = CommitTemplateLanguage<'repo>;
+    // Not using maplit::hashmap!{} or custom declarative macro here because
+    // code completion inside macro is quite restricted.
+    let mut map = CommitTemplateBuildMethodFnMap::<WorkspaceRef>::new();
+    map.insert("name", |_language, _build_ctx, self_property, function| {
+        function.expect_no_arguments()?;
+        let out_property = self_property.map(|workspace| workspace.name);
+        Ok(L::wrap_string(out_property))
+    });
+    map.insert(
+        "target",
+        |_language, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|workspace| workspace.target);
+            Ok(L::wrap_commit(out_property))
+        },
+    );
+    map.insert(
+        "current",
+        |_language, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|workspace| workspace.current);
+            Ok(L::wrap_boolean(out_property))
+        },
+    );
+    map.insert("stale", |_language, _build_ctx, self_property, function| {
+        function.expect_no_arguments()?;
+        let out_property = self_property.map(|workspace| workspace.stale);
+        Ok(L::wrap_boolean(out_property))
+    });
+    map.insert(
+        "missing",
+        |_language, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|workspace| workspace.missing);
+            Ok(L::wrap_boolean(out_property))
+        },
+    );
+    map.insert(
+        "last_operation_id",
+        |_language, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|workspace| {
+                workspace
+                    .last_operation
+                    .map_or_else(String::new, |(id, _)| id.hex())
+            });
+            Ok(L::wrap_string(out_property))
+        },
+    );
+    map.insert(
+        "last_updated",
+        |_language, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.and_then(|workspace| {
+                let (_, time) = workspace.last_operation.ok_or_else(|| {
+                    TemplatePropertyError("Last operation of the workspace is unknown".into())
+                })?;
+                Ok(time)
+            });
+            Ok(L::wrap_timestamp(out_property))
+        },
+    );
+    map
+}
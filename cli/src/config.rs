@@ -26,7 +26,7 @@ use regex::{Captures, Regex};
 use thiserror::Error;
 use tracing::instrument;
 
-use crate::command_error::{user_error, user_error_with_message, CommandError};
+use crate::command_error::{user_error, user_error_with_message, CommandError, ErrorCode};
 
 pub fn to_toml_value(value: &config::Value) -> Result<toml_edit::Value, config::ConfigError> {
     fn type_error<T: fmt::Display>(message: T) -> config::ConfigError {
@@ -179,6 +179,19 @@ pub enum ConfigSource {
     CommandArg,
 }
 
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ConfigSource::Default => "default",
+            ConfigSource::Env => "env",
+            ConfigSource::User => "user",
+            ConfigSource::Repo => "repo",
+            ConfigSource::CommandArg => "cli",
+        };
+        write!(f, "{name}")
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct AnnotatedValue {
     pub path: ConfigNamePathBuf,
@@ -521,6 +534,9 @@ fn env_overrides() -> config::Config {
     if let Ok(value) = env::var("JJ_EDITOR") {
         builder = builder.set_override("ui.editor", value).unwrap();
     }
+    if env::var("JJ_ERROR_JSON").is_ok_and(|value| value == "1") {
+        builder = builder.set_override("ui.error-format", "json").unwrap();
+    }
     builder.build().unwrap()
 }
 
@@ -564,69 +580,155 @@ fn read_config_path(config_path: &Path) -> Result<config::Config, config::Config
         .build()
 }
 
-pub fn write_config_value_to_file(
-    key: &ConfigNamePathBuf,
-    value_str: &str,
-    path: &Path,
-) -> Result<(), CommandError> {
-    // Read config
+fn read_config_document(path: &Path) -> Result<toml_edit::Document, CommandError> {
     let config_toml = std::fs::read_to_string(path).or_else(|err| {
         match err.kind() {
             // If config doesn't exist yet, read as empty and we'll write one.
             std::io::ErrorKind::NotFound => Ok("".to_string()),
             _ => Err(user_error_with_message(
+                ErrorCode::Io,
                 format!("Failed to read file {path}", path = path.display()),
                 err,
             )),
         }
     })?;
-    let mut doc: toml_edit::Document = config_toml.parse().map_err(|err| {
+    config_toml.parse().map_err(|err| {
         user_error_with_message(
+            ErrorCode::Config,
             format!("Failed to parse file {path}", path = path.display()),
             err,
         )
-    })?;
+    })
+}
 
-    // Apply config value
-    // Interpret value as string if it can't be parsed as a TOML value.
-    // TODO(#531): Infer types based on schema (w/ --type arg to override).
-    let item = match value_str.parse() {
-        Ok(value) => toml_edit::Item::Value(value),
-        _ => toml_edit::value(value_str),
-    };
+fn write_config_document(doc: &toml_edit::Document, path: &Path) -> Result<(), CommandError> {
+    std::fs::write(path, doc.to_string()).map_err(|err| {
+        user_error_with_message(
+            ErrorCode::Io,
+            format!("Failed to write file {path}", path = path.display()),
+            err,
+        )
+    })
+}
+
+/// Looks up the table containing the `key`, creating intermediate tables if
+/// `create` is true. Returns `None` if a parent table doesn't exist.
+fn parent_table_mut<'a>(
+    doc: &'a mut toml_edit::Document,
+    key: &ConfigNamePathBuf,
+    action: &str,
+    create: bool,
+) -> Result<Option<&'a mut toml_edit::Table>, CommandError> {
     let mut target_table = doc.as_table_mut();
     let mut key_parts_iter = key.components();
-    let last_key_part = key_parts_iter.next_back().expect("key must not be empty");
-    for key_part in key_parts_iter {
-        target_table = target_table
-            .entry(key_part)
-            .or_insert_with(|| toml_edit::Item::Table(toml_edit::Table::new()))
-            .as_table_mut()
-            .ok_or_else(|| {
-                user_error(format!(
-                    "Failed to set {key}: would overwrite non-table value with parent table"
-                ))
-            })?;
+    key_parts_iter.next_back().expect("key must not be empty");
+    for (i, key_part) in key_parts_iter.enumerate() {
+        let item = if create {
+            target_table.entry(key_part).or_insert_with(|| {
+                let mut table = toml_edit::Table::new();
+                // Don't emit a header for a table which only contains subtables.
+                table.set_implicit(true);
+                toml_edit::Item::Table(table)
+            })
+        } else if let Some(item) = target_table.get_mut(key_part) {
+            item
+        } else {
+            return Ok(None);
+        };
+        target_table = item.as_table_mut().ok_or_else(|| {
+            let parent: ConfigNamePathBuf = key.components().take(i + 1).cloned().collect();
+            user_error(
+                ErrorCode::Config,
+                format!("Failed to {action} {key}: {parent} is not a table"),
+            )
+        })?;
     }
+    Ok(Some(target_table))
+}
+
+/// Sets the `key` to the given `value` in the TOML file, preserving comments
+/// and formatting of the other entries.
+pub fn write_config_value_to_file(
+    key: &ConfigNamePathBuf,
+    value: toml_edit::Value,
+    path: &Path,
+) -> Result<(), CommandError> {
+    let mut doc = read_config_document(path)?;
+    let target_table = parent_table_mut(&mut doc, key, "set", true)?.unwrap();
+    let last_key_part = key.components().next_back().unwrap();
     // Error out if overwriting non-scalar value for key (table or array) with
     // scalar.
     match target_table.get(last_key_part) {
         None | Some(toml_edit::Item::None | toml_edit::Item::Value(_)) => {}
         Some(toml_edit::Item::Table(_) | toml_edit::Item::ArrayOfTables(_)) => {
-            return Err(user_error(format!(
-                "Failed to set {key}: would overwrite entire table"
-            )));
+            return Err(user_error(
+                ErrorCode::Config,
+                format!("Failed to set {key}: would overwrite entire table"),
+            ));
         }
     }
-    target_table[last_key_part] = item;
+    target_table[last_key_part] = toml_edit::Item::Value(value);
+    write_config_document(&doc, path)
+}
 
-    // Write config back
-    std::fs::write(path, doc.to_string()).map_err(|err| {
-        user_error_with_message(
-            format!("Failed to write file {path}", path = path.display()),
-            err,
-        )
-    })
+/// Appends the `value` to the array at `key` in the TOML file. A new array is
+/// created if the `key` doesn't exist.
+pub fn append_config_value_to_file(
+    key: &ConfigNamePathBuf,
+    value: toml_edit::Value,
+    path: &Path,
+) -> Result<(), CommandError> {
+    let mut doc = read_config_document(path)?;
+    let target_table = parent_table_mut(&mut doc, key, "append to", true)?.unwrap();
+    let last_key_part = key.components().next_back().unwrap();
+    match target_table.get_mut(last_key_part) {
+        None | Some(toml_edit::Item::None) => {
+            target_table[last_key_part] = toml_edit::value(toml_edit::Array::from_iter([value]));
+        }
+        Some(toml_edit::Item::Value(toml_edit::Value::Array(array))) => {
+            array.push_formatted(value.decorated(if array.is_empty() { "" } else { " " }, ""));
+        }
+        Some(_) => {
+            return Err(user_error(
+                ErrorCode::Config,
+                format!("Failed to append to {key}: existing value is not an array"),
+            ));
+        }
+    }
+    write_config_document(&doc, path)
+}
+
+/// Removes the `key` from the TOML file, preserving comments and formatting
+/// of the other entries.
+///
+/// If the `key` is a table, `confirm_remove_table` is called to decide whether
+/// to delete the entire table. Returns `false` if the `key` doesn't exist, in
+/// which case the file isn't modified.
+pub fn remove_config_value_from_file(
+    key: &ConfigNamePathBuf,
+    path: &Path,
+    confirm_remove_table: impl FnOnce() -> Result<bool, CommandError>,
+) -> Result<bool, CommandError> {
+    let mut doc = read_config_document(path)?;
+    let Some(target_table) = parent_table_mut(&mut doc, key, "unset", false)? else {
+        return Ok(false);
+    };
+    let last_key_part = key.components().next_back().unwrap();
+    match target_table.get(last_key_part) {
+        None | Some(toml_edit::Item::None) => return Ok(false),
+        Some(toml_edit::Item::Value(_)) => {}
+        Some(toml_edit::Item::Table(_) | toml_edit::Item::ArrayOfTables(_)) => {
+            if !confirm_remove_table()? {
+                return Err(user_error(
+                    ErrorCode::Config,
+                    format!("Failed to unset {key}: would delete entire table"),
+                ));
+            }
+        }
+    }
+    target_table.remove(last_key_part);
+    write_config_document(&doc, path)?;
+    Ok(true)
 }
 
 /// Command name and arguments specified by config.
@@ -9,6 +9,9 @@ use crate::cli_util::{edit_temp_file, WorkspaceCommandHelper};
 use crate::command_error::CommandError;
 use crate::diff_util::DiffFormat;
 use crate::formatter::PlainTextFormatter;
+use crate::generic_templater::GenericTemplateLanguage;
+use crate::template_builder::TemplateLanguage as _;
+use crate::templater::{TemplatePropertyExt as _, TemplateRenderer};
 use crate::text_util;
 use crate::ui::Ui;
 
@@ -77,6 +80,54 @@ pub fn combine_messages(
     edit_description(repo, &combined, settings)
 }
 
+/// Descriptions of the commits to be combined, which are available to the
+/// template given to `combine_messages_with_template()`.
+#[derive(Clone, Debug)]
+pub struct CombinedDescriptions {
+    pub destination: String,
+    pub sources: Vec<String>,
+}
+
+pub fn combined_descriptions_template_language(
+) -> GenericTemplateLanguage<'static, CombinedDescriptions> {
+    type L = GenericTemplateLanguage<'static, CombinedDescriptions>;
+    let mut language = L::new();
+    language.add_keyword("destination", |self_property| {
+        let out_property = self_property.map(|descriptions| descriptions.destination);
+        Ok(L::wrap_string(out_property))
+    });
+    language.add_keyword("sources", |self_property| {
+        let out_property = self_property.map(|descriptions| descriptions.sources);
+        Ok(L::wrap_string_list(out_property))
+    });
+    language
+}
+
+/// Combines the descriptions of the `sources` and `destination` commits by
+/// evaluating the `template`. Returns `None` if the result is empty.
+pub fn combine_messages_with_template(
+    sources: &[&Commit],
+    destination: &Commit,
+    template: &TemplateRenderer<'_, CombinedDescriptions>,
+) -> Result<Option<String>, CommandError> {
+    let descriptions = CombinedDescriptions {
+        destination: destination.description().to_owned(),
+        sources: sources
+            .iter()
+            .map(|commit| commit.description().to_owned())
+            .collect(),
+    };
+    let mut output = Vec::new();
+    template.format(&descriptions, &mut PlainTextFormatter::new(&mut output))?;
+    let output = String::from_utf8_lossy(&output);
+    let description = output.trim_matches('\n');
+    if description.trim().is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(text_util::complete_newline(description)))
+    }
+}
+
 /// Create a description from a list of paragraphs.
 ///
 /// Based on the Git CLI behavior. See `opt_parse_m()` and `cleanup_mode` in
@@ -90,11 +141,29 @@ pub fn join_message_paragraphs(paragraphs: &[String]) -> String {
         .join("\n")
 }
 
+/// Parses an author identity in the form `Name <email>` into the name and
+/// email. Used as a clap value parser.
+pub fn parse_author(author: &str) -> Result<(String, String), String> {
+    let parsed = author
+        .trim()
+        .strip_suffix('>')
+        .and_then(|rest| rest.rsplit_once('<'))
+        .map(|(name, email)| (name.trim(), email.trim()))
+        .filter(|(name, email)| {
+            !name.is_empty() && !email.is_empty() && !email.contains(['<', '>'])
+        });
+    match parsed {
+        Some((name, email)) => Ok((name.to_owned(), email.to_owned())),
+        None => Err(r#"expected an author in the form "Name <email>""#.to_owned()),
+    }
+}
+
 pub fn description_template_for_describe(
     ui: &Ui,
     settings: &UserSettings,
     workspace_command: &WorkspaceCommandHelper,
     commit: &Commit,
+    description: &str,
 ) -> Result<String, CommandError> {
     let mut diff_summary_bytes = Vec::new();
     let diff_renderer = workspace_command.diff_renderer(vec![DiffFormat::Summary]);
@@ -104,10 +173,10 @@ pub fn description_template_for_describe(
         commit,
         &EverythingMatcher,
     )?;
-    let description = if commit.description().is_empty() {
+    let description = if description.is_empty() {
         settings.default_description()
     } else {
-        commit.description().to_owned()
+        description.to_owned()
     };
     if diff_summary_bytes.is_empty() {
         Ok(description)
@@ -12,17 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::cmp::max;
-use std::collections::VecDeque;
+use std::cmp::{max, Reverse};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::io;
+use std::io::Write as _;
 use std::ops::Range;
 
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
 use futures::{try_join, Stream, StreamExt};
 use itertools::Itertools;
-use jj_lib::backend::{BackendError, BackendResult, TreeValue};
+use jj_lib::backend::{BackendError, BackendResult, FileId, TreeValue};
 use jj_lib::commit::Commit;
 use jj_lib::conflicts::{materialize_tree_value, MaterializedTreeValue};
-use jj_lib::diff::{Diff, DiffHunk};
+use jj_lib::diff::{Diff, DiffAlgorithm, DiffHunk};
 use jj_lib::files::DiffLine;
 use jj_lib::matchers::Matcher;
 use jj_lib::merge::MergedTreeValue;
@@ -38,6 +42,7 @@ use thiserror::Error;
 use tracing::instrument;
 use unicode_width::UnicodeWidthStr as _;
 
+use crate::command_error::CommandError;
 use crate::config::CommandNameAndArgs;
 use crate::formatter::Formatter;
 use crate::merge_tools::{self, DiffGenerateError, ExternalMergeTool};
@@ -45,10 +50,13 @@ use crate::text_util;
 use crate::ui::Ui;
 
 const DEFAULT_CONTEXT_LINES: usize = 3;
+/// Binary files larger than this aren't inlined in Git-format diffs.
+const MAX_GIT_BINARY_PATCH_SIZE: usize = 64 << 20;
 
 #[derive(clap::Args, Clone, Debug)]
 #[command(next_help_heading = "Diff Formatting Options")]
-#[command(group(clap::ArgGroup::new("short-format").args(&["summary", "stat", "types", "name_only"])))]
+#[command(group(clap::ArgGroup::new("short-format").args(&["summary", "stat", "types", "name_only", "name_status"])))]
+#[command(group(clap::ArgGroup::new("name-list").args(&["name_only", "name_status"])))]
 #[command(group(clap::ArgGroup::new("long-format").args(&["git", "color_words", "tool"])))]
 pub struct DiffFormatArgs {
     /// For each path, show only whether it was modified, added, or deleted
@@ -57,6 +65,11 @@ pub struct DiffFormatArgs {
     /// Show a histogram of the changes
     #[arg(long)]
     pub stat: bool,
+    /// Limit the width of the `--stat` output to the given number of columns
+    ///
+    /// Defaults to the width of the terminal.
+    #[arg(long, value_name = "COLS", requires = "stat")]
+    pub stat_width: Option<usize>,
     /// For each path, show only its type before and after
     ///
     /// The diff is shown as two letters. The first letter indicates the type
@@ -72,9 +85,27 @@ pub struct DiffFormatArgs {
     ///    `jj diff -r @- --name_only | xargs perl -pi -e's/OLD/NEW/g`
     #[arg(long)]
     pub name_only: bool,
+    /// For each path, show only its path and a letter indicating whether it
+    /// was modified (M), added (A), or deleted (D)
+    ///
+    /// The letter and the path are separated by a tab character, as in
+    /// `git diff --name-status`.
+    #[arg(long)]
+    pub name_status: bool,
+    /// Terminate each path of `--name-only` or `--name-status` with NUL
+    /// instead of newline, and separate the status letter with NUL
+    ///
+    /// The paths are printed verbatim, and the output is never colorized.
+    #[arg(short = 'z', requires = "name-list")]
+    pub null_terminated: bool,
     /// Show a Git-format diff
     #[arg(long)]
     pub git: bool,
+    /// Include binary file contents in the Git-format diff
+    ///
+    /// The output can be applied by `git apply`. Implies `--git`.
+    #[arg(long, conflicts_with_all = ["color_words", "tool"])]
+    pub binary: bool,
     /// Show a word-level diff with changes indicated only by color
     #[arg(long)]
     pub color_words: bool,
@@ -84,16 +115,53 @@ pub struct DiffFormatArgs {
     /// Number of lines of context to show
     #[arg(long)]
     context: Option<usize>,
+    /// Algorithm used to find the changed lines in the Git and color-words
+    /// formats
+    ///
+    /// Defaults to the `diff.algorithm` setting, or `histogram` if unset.
+    #[arg(long, value_name = "ALGORITHM", value_parser = diff_algorithm_parser())]
+    algorithm: Option<DiffAlgorithm>,
+}
+
+const DIFF_ALGORITHMS: [(&str, DiffAlgorithm); 2] = [
+    ("histogram", DiffAlgorithm::Histogram),
+    ("myers", DiffAlgorithm::Myers),
+];
+
+fn parse_diff_algorithm(name: &str) -> Option<DiffAlgorithm> {
+    DIFF_ALGORITHMS
+        .iter()
+        .find(|(known_name, _)| *known_name == name)
+        .map(|(_, algorithm)| *algorithm)
+}
+
+fn diff_algorithm_parser() -> impl TypedValueParser<Value = DiffAlgorithm> {
+    PossibleValuesParser::new(DIFF_ALGORITHMS.map(|(name, _)| name))
+        .map(|name| parse_diff_algorithm(&name).unwrap())
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DiffFormat {
     Summary,
-    Stat,
+    Stat {
+        width: Option<usize>,
+    },
     Types,
-    NameOnly,
-    Git { context: usize },
-    ColorWords { context: usize },
+    NameOnly {
+        null_terminated: bool,
+    },
+    NameStatus {
+        null_terminated: bool,
+    },
+    Git {
+        context: usize,
+        binary: bool,
+        algorithm: DiffAlgorithm,
+    },
+    ColorWords {
+        context: usize,
+        algorithm: DiffAlgorithm,
+    },
     Tool(Box<ExternalMergeTool>),
 }
 
@@ -104,7 +172,7 @@ pub fn diff_formats_for(
 ) -> Result<Vec<DiffFormat>, config::ConfigError> {
     let formats = diff_formats_from_args(settings, args)?;
     if formats.is_empty() {
-        Ok(vec![default_diff_format(settings, args.context)?])
+        Ok(vec![default_diff_format(settings, args)?])
     } else {
         Ok(formats)
     }
@@ -120,7 +188,7 @@ pub fn diff_formats_for_log(
     let mut formats = diff_formats_from_args(settings, args)?;
     // --patch implies default if no format other than --summary is specified
     if patch && matches!(formats.as_slice(), [] | [DiffFormat::Summary]) {
-        formats.push(default_diff_format(settings, args.context)?);
+        formats.push(default_diff_format(settings, args)?);
         formats.dedup();
     }
     Ok(formats)
@@ -130,23 +198,49 @@ fn diff_formats_from_args(
     settings: &UserSettings,
     args: &DiffFormatArgs,
 ) -> Result<Vec<DiffFormat>, config::ConfigError> {
+    // The algorithm only matters to the content-diff formats, so it isn't
+    // resolved for the others.
+    let algorithm = if args.git || args.binary || args.color_words {
+        diff_algorithm_for(settings, args)?
+    } else {
+        DiffAlgorithm::default()
+    };
     let mut formats = [
         (args.summary, DiffFormat::Summary),
         (args.types, DiffFormat::Types),
-        (args.name_only, DiffFormat::NameOnly),
         (
-            args.git,
+            args.name_only,
+            DiffFormat::NameOnly {
+                null_terminated: args.null_terminated,
+            },
+        ),
+        (
+            args.name_status,
+            DiffFormat::NameStatus {
+                null_terminated: args.null_terminated,
+            },
+        ),
+        (
+            args.git || args.binary,
             DiffFormat::Git {
                 context: args.context.unwrap_or(DEFAULT_CONTEXT_LINES),
+                binary: args.binary,
+                algorithm,
             },
         ),
         (
             args.color_words,
             DiffFormat::ColorWords {
                 context: args.context.unwrap_or(DEFAULT_CONTEXT_LINES),
+                algorithm,
+            },
+        ),
+        (
+            args.stat,
+            DiffFormat::Stat {
+                width: args.stat_width,
             },
         ),
-        (args.stat, DiffFormat::Stat),
     ]
     .into_iter()
     .filter_map(|(arg, format)| arg.then_some(format))
@@ -159,10 +253,31 @@ fn diff_formats_from_args(
     Ok(formats)
 }
 
+/// Returns the line-diff algorithm requested by `--algorithm` or the
+/// `diff.algorithm` setting.
+fn diff_algorithm_for(
+    settings: &UserSettings,
+    args: &DiffFormatArgs,
+) -> Result<DiffAlgorithm, config::ConfigError> {
+    if let Some(algorithm) = args.algorithm {
+        return Ok(algorithm);
+    }
+    let Some(name) = settings.config().get_string("diff.algorithm").optional()? else {
+        return Ok(DiffAlgorithm::default());
+    };
+    parse_diff_algorithm(&name).ok_or_else(|| {
+        let names = DIFF_ALGORITHMS.map(|(name, _)| name).join(", ");
+        config::ConfigError::Message(format!(
+            "invalid diff algorithm: {name} (possible values: {names})"
+        ))
+    })
+}
+
 fn default_diff_format(
     settings: &UserSettings,
-    num_context_lines: Option<usize>,
+    args: &DiffFormatArgs,
 ) -> Result<DiffFormat, config::ConfigError> {
+    let num_context_lines = args.context;
     let config = settings.config();
     if let Some(args) = config.get("ui.diff.tool").optional()? {
         // External "tool" overrides the internal "format" option.
@@ -184,14 +299,22 @@ fn default_diff_format(
     match name.as_ref() {
         "summary" => Ok(DiffFormat::Summary),
         "types" => Ok(DiffFormat::Types),
-        "name-only" => Ok(DiffFormat::NameOnly),
+        "name-only" => Ok(DiffFormat::NameOnly {
+            null_terminated: false,
+        }),
+        "name-status" => Ok(DiffFormat::NameStatus {
+            null_terminated: false,
+        }),
         "git" => Ok(DiffFormat::Git {
             context: num_context_lines.unwrap_or(DEFAULT_CONTEXT_LINES),
+            binary: false,
+            algorithm: diff_algorithm_for(settings, args)?,
         }),
         "color-words" => Ok(DiffFormat::ColorWords {
             context: num_context_lines.unwrap_or(DEFAULT_CONTEXT_LINES),
+            algorithm: diff_algorithm_for(settings, args)?,
         }),
-        "stat" => Ok(DiffFormat::Stat),
+        "stat" => Ok(DiffFormat::Stat { width: None }),
         _ => Err(config::ConfigError::Message(format!(
             "invalid diff format: {name}"
         ))),
@@ -209,14 +332,47 @@ pub enum DiffRenderError {
         path: String,
         source: Box<dyn std::error::Error + Send + Sync>,
     },
+    #[error(
+        "Binary file {path} is too large to include in the diff ({size} bytes, limit is {limit} \
+         bytes)"
+    )]
+    BinaryTooLarge {
+        path: String,
+        size: usize,
+        limit: usize,
+    },
+    #[error("External diff tools can't compare individual files")]
+    ToolUnsupported,
     #[error(transparent)]
     Io(#[from] io::Error),
 }
 
+/// Converts `err` to a `CommandError`, listing the configured tools if the
+/// external diff tool couldn't be found.
+pub fn diff_render_error_with_tool_hint(
+    settings: &UserSettings,
+    err: DiffRenderError,
+) -> CommandError {
+    let is_tool_not_found =
+        matches!(&err, DiffRenderError::DiffGenerate(err) if err.is_tool_not_found());
+    let mut cmd_err = CommandError::from(err);
+    if is_tool_not_found {
+        if let Ok(names) = merge_tools::configured_tool_names(settings) {
+            cmd_err.add_hint(format!(
+                "Configure the tool in `merge-tools.<name>`, or use one of the configured tools: \
+                 {}",
+                names.join(", ")
+            ));
+        }
+    }
+    cmd_err
+}
+
 /// Configuration and environment to render textual diff.
 pub struct DiffRenderer<'a> {
     repo: &'a dyn Repo,
     path_converter: &'a RepoPathUiConverter,
+    relative_root: RepoPathBuf,
     formats: Vec<DiffFormat>,
 }
 
@@ -230,9 +386,17 @@ impl<'a> DiffRenderer<'a> {
             repo,
             formats,
             path_converter,
+            relative_root: RepoPathBuf::root(),
         }
     }
 
+    /// Strips the `root` directory from the repository paths printed in the
+    /// Git format. Paths in other formats are displayed by the path converter.
+    pub fn with_relative_root(mut self, root: RepoPathBuf) -> Self {
+        self.relative_root = root;
+        self
+    }
+
     /// Generates diff between `from_tree` and `to_tree`.
     pub fn show_diff(
         &self,
@@ -241,48 +405,119 @@ impl<'a> DiffRenderer<'a> {
         from_tree: &MergedTree,
         to_tree: &MergedTree,
         matcher: &dyn Matcher,
+    ) -> Result<(), DiffRenderError> {
+        self.show_diff_with(
+            ui,
+            formatter,
+            || from_tree.diff_stream(to_tree, matcher),
+            |ui, formatter, tool| {
+                merge_tools::generate_diff(ui, formatter.raw(), from_tree, to_tree, matcher, tool)
+                    .map_err(DiffRenderError::DiffGenerate)
+            },
+        )
+    }
+
+    /// Generates diff between `from_value` and `to_value` as if they were
+    /// stored at `path`.
+    ///
+    /// External diff tools aren't supported since they compare whole trees.
+    pub fn show_value_diff(
+        &self,
+        ui: &Ui,
+        formatter: &mut dyn Formatter,
+        path: &RepoPath,
+        from_value: &MergedTreeValue,
+        to_value: &MergedTreeValue,
+    ) -> Result<(), DiffRenderError> {
+        self.show_diff_with(
+            ui,
+            formatter,
+            || {
+                let diff = (path.to_owned(), Ok((from_value.clone(), to_value.clone())));
+                futures::stream::iter([diff]).boxed()
+            },
+            |_ui, _formatter, _tool| Err(DiffRenderError::ToolUnsupported),
+        )
+    }
+
+    fn show_diff_with<'b>(
+        &self,
+        ui: &Ui,
+        formatter: &mut dyn Formatter,
+        tree_diff: impl Fn() -> TreeDiffStream<'b>,
+        show_tool_diff: impl Fn(
+            &Ui,
+            &mut dyn Formatter,
+            &ExternalMergeTool,
+        ) -> Result<(), DiffRenderError>,
     ) -> Result<(), DiffRenderError> {
         let repo = self.repo;
         let path_converter = self.path_converter;
         for format in &self.formats {
             match format {
                 DiffFormat::Summary => {
-                    let tree_diff = from_tree.diff_stream(to_tree, matcher);
+                    let tree_diff = tree_diff();
                     show_diff_summary(formatter, tree_diff, path_converter)?;
                 }
-                DiffFormat::Stat => {
-                    let tree_diff = from_tree.diff_stream(to_tree, matcher);
+                DiffFormat::Stat { width } => {
+                    let tree_diff = tree_diff();
                     // TODO: In graph log, graph width should be subtracted
-                    let width = usize::from(ui.term_width().unwrap_or(80));
+                    let width = width.unwrap_or_else(|| usize::from(ui.term_width().unwrap_or(80)));
                     show_diff_stat(repo, formatter, tree_diff, path_converter, width)?;
                 }
                 DiffFormat::Types => {
-                    let tree_diff = from_tree.diff_stream(to_tree, matcher);
+                    let tree_diff = tree_diff();
                     show_types(formatter, tree_diff, path_converter)?;
                 }
-                DiffFormat::NameOnly => {
-                    let tree_diff = from_tree.diff_stream(to_tree, matcher);
-                    show_names(formatter, tree_diff, path_converter)?;
+                DiffFormat::NameOnly { null_terminated } => {
+                    let tree_diff = tree_diff();
+                    if *null_terminated {
+                        show_names_null_terminated(formatter.raw(), tree_diff, path_converter)?;
+                    } else {
+                        show_names(formatter, tree_diff, path_converter)?;
+                    }
                 }
-                DiffFormat::Git { context } => {
-                    let tree_diff = from_tree.diff_stream(to_tree, matcher);
-                    show_git_diff(repo, formatter, *context, tree_diff)?;
+                DiffFormat::NameStatus { null_terminated } => {
+                    let tree_diff = tree_diff();
+                    if *null_terminated {
+                        show_name_status_null_terminated(
+                            formatter.raw(),
+                            repo.store(),
+                            tree_diff,
+                            path_converter,
+                        )?;
+                    } else {
+                        show_name_status(formatter, repo.store(), tree_diff, path_converter)?;
+                    }
                 }
-                DiffFormat::ColorWords { context } => {
-                    let tree_diff = from_tree.diff_stream(to_tree, matcher);
-                    show_color_words_diff(repo, formatter, *context, tree_diff, path_converter)?;
+                DiffFormat::Git {
+                    context,
+                    binary,
+                    algorithm,
+                } => {
+                    let tree_diff = tree_diff();
+                    show_git_diff(
+                        repo,
+                        formatter,
+                        *context,
+                        *binary,
+                        *algorithm,
+                        &self.relative_root,
+                        tree_diff,
+                    )?;
                 }
-                DiffFormat::Tool(tool) => {
-                    merge_tools::generate_diff(
-                        ui,
-                        formatter.raw(),
-                        from_tree,
-                        to_tree,
-                        matcher,
-                        tool,
-                    )
-                    .map_err(DiffRenderError::DiffGenerate)?;
+                DiffFormat::ColorWords { context, algorithm } => {
+                    let tree_diff = tree_diff();
+                    show_color_words_diff(
+                        repo,
+                        formatter,
+                        *context,
+                        *algorithm,
+                        tree_diff,
+                        path_converter,
+                    )?;
                 }
+                DiffFormat::Tool(tool) => show_tool_diff(ui, formatter, tool)?,
             }
         }
         Ok(())
@@ -306,6 +541,7 @@ fn show_color_words_diff_hunks(
     left: &[u8],
     right: &[u8],
     num_context_lines: usize,
+    algorithm: DiffAlgorithm,
     formatter: &mut dyn Formatter,
 ) -> io::Result<()> {
     const SKIPPED_CONTEXT_LINE: &str = "    ...\n";
@@ -314,7 +550,7 @@ fn show_color_words_diff_hunks(
     let mut skipped_context = false;
     // Are the lines in `context` to be printed before the next modified line?
     let mut context_before = true;
-    for diff_line in files::diff(left, right) {
+    for diff_line in files::diff_with_algorithm(left, right, algorithm) {
         if diff_line.is_unmodified() {
             context.push_back(diff_line.clone());
             let mut start_skipping_context = false;
@@ -444,22 +680,26 @@ impl FileContent {
 
 fn file_content_for_diff(reader: &mut dyn io::Read) -> io::Result<FileContent> {
     // If this is a binary file, don't show the full contents.
-    // Determine whether it's binary by whether the first 8k bytes contain a null
-    // character; this is the same heuristic used by git as of writing: https://github.com/git/git/blob/eea0e59ffbed6e33d171ace5be13cde9faa41639/xdiff-interface.c#L192-L198
-    const PEEK_SIZE: usize = 8000;
     // TODO: currently we look at the whole file, even though for binary files we
     // only need to know the file size. To change that we'd have to extend all
     // the data backends to support getting the length.
     let mut contents = vec![];
     reader.read_to_end(&mut contents)?;
 
-    let start = &contents[..PEEK_SIZE.min(contents.len())];
     Ok(FileContent {
-        is_binary: start.contains(&b'\0'),
+        is_binary: is_binary_content(&contents),
         contents,
     })
 }
 
+/// Determines whether the contents are likely binary by looking for a null
+/// character in the first 8k bytes; this is the same heuristic used by git as
+/// of writing: https://github.com/git/git/blob/eea0e59ffbed6e33d171ace5be13cde9faa41639/xdiff-interface.c#L192-L198
+pub fn is_binary_content(contents: &[u8]) -> bool {
+    const PEEK_SIZE: usize = 8000;
+    contents[..PEEK_SIZE.min(contents.len())].contains(&b'\0')
+}
+
 fn diff_content(path: &RepoPath, value: MaterializedTreeValue) -> io::Result<FileContent> {
     match value {
         MaterializedTreeValue::Absent => Ok(FileContent::empty()),
@@ -518,6 +758,7 @@ pub fn show_color_words_diff(
     repo: &dyn Repo,
     formatter: &mut dyn Formatter,
     num_context_lines: usize,
+    algorithm: DiffAlgorithm,
     tree_diff: TreeDiffStream,
     path_converter: &RepoPathUiConverter,
 ) -> Result<(), DiffRenderError> {
@@ -556,6 +797,7 @@ pub fn show_color_words_diff(
                         &[],
                         &right_content.contents,
                         num_context_lines,
+                        algorithm,
                         formatter,
                     )?;
                 }
@@ -617,6 +859,7 @@ pub fn show_color_words_diff(
                         &left_content.contents,
                         &right_content.contents,
                         num_context_lines,
+                        algorithm,
                         formatter,
                     )?;
                 }
@@ -636,6 +879,7 @@ pub fn show_color_words_diff(
                         &left_content.contents,
                         &[],
                         num_context_lines,
+                        algorithm,
                         formatter,
                     )?;
                 }
@@ -654,12 +898,22 @@ struct GitDiffPart {
     content: Vec<u8>,
 }
 
+impl GitDiffPart {
+    fn is_binary(&self) -> bool {
+        is_binary_content(&self.content)
+    }
+
+    fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(10)]
+    }
+}
+
 fn git_diff_part(
     path: &RepoPath,
     value: MaterializedTreeValue,
 ) -> Result<GitDiffPart, DiffRenderError> {
     let mode;
-    let mut hash;
+    let hash;
     let mut contents: Vec<u8>;
     match value {
         MaterializedTreeValue::Absent => {
@@ -682,7 +936,6 @@ fn git_diff_part(
                 "100644".to_string()
             };
             hash = id.hex();
-            // TODO: use `file_content_for_diff` instead of showing binary
             contents = vec![];
             reader.read_to_end(&mut contents)?;
         }
@@ -714,7 +967,6 @@ fn git_diff_part(
             panic!("Unexpected tree in diff at path {path:?}");
         }
     }
-    hash.truncate(10);
     Ok(GitDiffPart {
         mode,
         hash,
@@ -739,6 +991,7 @@ fn unified_diff_hunks<'content>(
     left_content: &'content [u8],
     right_content: &'content [u8],
     num_context_lines: usize,
+    algorithm: DiffAlgorithm,
 ) -> Vec<UnifiedDiffHunk<'content>> {
     let mut hunks = vec![];
     let mut current_hunk = UnifiedDiffHunk {
@@ -747,7 +1000,11 @@ fn unified_diff_hunks<'content>(
         lines: vec![],
     };
     let mut show_context_after = false;
-    let diff = Diff::for_tokenizer(&[left_content, right_content], diff::find_line_ranges);
+    let diff = Diff::for_tokenizer_with_algorithm(
+        &[left_content, right_content],
+        diff::find_line_ranges,
+        algorithm,
+    );
     for hunk in diff.hunks() {
         match hunk {
             DiffHunk::Matching(content) => {
@@ -820,8 +1077,9 @@ fn show_unified_diff_hunks(
     left_content: &[u8],
     right_content: &[u8],
     num_context_lines: usize,
+    algorithm: DiffAlgorithm,
 ) -> io::Result<()> {
-    for hunk in unified_diff_hunks(left_content, right_content, num_context_lines) {
+    for hunk in unified_diff_hunks(left_content, right_content, num_context_lines, algorithm) {
         writeln!(
             formatter.labeled("hunk_header"),
             "@@ -{},{} +{},{} @@",
@@ -887,6 +1145,9 @@ pub fn show_git_diff(
     repo: &dyn Repo,
     formatter: &mut dyn Formatter,
     num_context_lines: usize,
+    binary: bool,
+    algorithm: DiffAlgorithm,
+    relative_root: &RepoPath,
     tree_diff: TreeDiffStream,
 ) -> Result<(), DiffRenderError> {
     formatter.push_label("diff")?;
@@ -894,58 +1155,126 @@ pub fn show_git_diff(
     let mut diff_stream = materialized_diff_stream(repo.store(), tree_diff);
     async {
         while let Some((path, diff)) = diff_stream.next().await {
-            let path_string = path.as_internal_file_string();
+            let path_string = path
+                .strip_prefix(relative_root)
+                .unwrap_or(&path)
+                .as_internal_file_string();
             let (left_value, right_value) = diff?;
-            if left_value.is_absent() {
-                let right_part = git_diff_part(&path, right_value)?;
-                formatter.with_label("file_header", |formatter| {
-                    writeln!(formatter, "diff --git a/{path_string} b/{path_string}")?;
-                    writeln!(formatter, "new file mode {}", &right_part.mode)?;
-                    writeln!(formatter, "index 0000000000..{}", &right_part.hash)?;
-                    writeln!(formatter, "--- /dev/null")?;
-                    writeln!(formatter, "+++ b/{path_string}")
-                })?;
-                show_unified_diff_hunks(formatter, &[], &right_part.content, num_context_lines)?;
-            } else if right_value.is_present() {
-                let left_part = git_diff_part(&path, left_value)?;
-                let right_part = git_diff_part(&path, right_value)?;
-                formatter.with_label("file_header", |formatter| {
-                    writeln!(formatter, "diff --git a/{path_string} b/{path_string}")?;
-                    if left_part.mode != right_part.mode {
-                        writeln!(formatter, "old mode {}", &left_part.mode)?;
-                        writeln!(formatter, "new mode {}", &right_part.mode)?;
-                        if left_part.hash != right_part.hash {
-                            writeln!(formatter, "index {}...{}", &left_part.hash, right_part.hash)?;
-                        }
-                    } else if left_part.hash != right_part.hash {
+            // There's nothing to show for a path absent on both sides.
+            if left_value.is_absent() && right_value.is_absent() {
+                continue;
+            }
+            let left_part = if left_value.is_absent() {
+                None
+            } else {
+                Some(git_diff_part(&path, left_value)?)
+            };
+            let right_part = if right_value.is_absent() {
+                None
+            } else {
+                Some(git_diff_part(&path, right_value)?)
+            };
+            // Without --binary, binary files are diffed like text files.
+            let is_binary = binary
+                && left_part
+                    .iter()
+                    .chain(&right_part)
+                    .any(|part| part.is_binary());
+            let left_content = left_part.as_ref().map_or(&[][..], |part| &part.content);
+            let right_content = right_part.as_ref().map_or(&[][..], |part| &part.content);
+            // Check the size before writing anything for this file.
+            if is_binary && left_content != right_content {
+                for content in [left_content, right_content] {
+                    if content.len() > MAX_GIT_BINARY_PATCH_SIZE {
+                        return Err(DiffRenderError::BinaryTooLarge {
+                            path: path_string.to_owned(),
+                            size: content.len(),
+                            limit: MAX_GIT_BINARY_PATCH_SIZE,
+                        });
+                    }
+                }
+            }
+            // `git apply` refuses binary patches without the full object ids in
+            // the two-dot form.
+            let index_separator = if is_binary { ".." } else { "..." };
+            let hash_of = |part: &GitDiffPart| {
+                if is_binary {
+                    part.hash.clone()
+                } else {
+                    part.short_hash().to_owned()
+                }
+            };
+            formatter.with_label("file_header", |formatter| {
+                writeln!(formatter, "diff --git a/{path_string} b/{path_string}")?;
+                match (&left_part, &right_part) {
+                    (None, Some(right_part)) => {
+                        let right_hash = hash_of(right_part);
+                        writeln!(formatter, "new file mode {}", &right_part.mode)?;
                         writeln!(
                             formatter,
-                            "index {}...{} {}",
-                            &left_part.hash, right_part.hash, left_part.mode
+                            "index {}..{right_hash}",
+                            "0".repeat(right_hash.len())
                         )?;
+                        if !is_binary {
+                            writeln!(formatter, "--- /dev/null")?;
+                            writeln!(formatter, "+++ b/{path_string}")?;
+                        }
                     }
-                    if left_part.content != right_part.content {
-                        writeln!(formatter, "--- a/{path_string}")?;
-                        writeln!(formatter, "+++ b/{path_string}")?;
+                    (Some(left_part), Some(right_part)) => {
+                        let left_hash = hash_of(left_part);
+                        let right_hash = hash_of(right_part);
+                        if left_part.mode != right_part.mode {
+                            writeln!(formatter, "old mode {}", &left_part.mode)?;
+                            writeln!(formatter, "new mode {}", &right_part.mode)?;
+                            if left_part.hash != right_part.hash {
+                                writeln!(
+                                    formatter,
+                                    "index {left_hash}{index_separator}{right_hash}"
+                                )?;
+                            }
+                        } else if left_part.hash != right_part.hash {
+                            writeln!(
+                                formatter,
+                                "index {left_hash}{index_separator}{right_hash} {}",
+                                left_part.mode
+                            )?;
+                        }
+                        if !is_binary && left_part.content != right_part.content {
+                            writeln!(formatter, "--- a/{path_string}")?;
+                            writeln!(formatter, "+++ b/{path_string}")?;
+                        }
                     }
-                    Ok(())
-                })?;
+                    (Some(left_part), None) => {
+                        let left_hash = hash_of(left_part);
+                        writeln!(formatter, "deleted file mode {}", &left_part.mode)?;
+                        writeln!(
+                            formatter,
+                            "index {left_hash}..{}",
+                            "0".repeat(left_hash.len())
+                        )?;
+                        if !is_binary {
+                            writeln!(formatter, "--- a/{path_string}")?;
+                            writeln!(formatter, "+++ /dev/null")?;
+                        }
+                    }
+                    (None, None) => unreachable!("absent paths are skipped above"),
+                }
+                Ok(())
+            })?;
+            if !is_binary {
                 show_unified_diff_hunks(
                     formatter,
-                    &left_part.content,
-                    &right_part.content,
+                    left_content,
+                    right_content,
                     num_context_lines,
+                    algorithm,
                 )?;
+            } else if left_content == right_content {
+                // Mode-only change, which is fully described by the header.
             } else {
-                let left_part = git_diff_part(&path, left_value)?;
-                formatter.with_label("file_header", |formatter| {
-                    writeln!(formatter, "diff --git a/{path_string} b/{path_string}")?;
-                    writeln!(formatter, "deleted file mode {}", &left_part.mode)?;
-                    writeln!(formatter, "index {}..0000000000", &left_part.hash)?;
-                    writeln!(formatter, "--- a/{path_string}")?;
-                    writeln!(formatter, "+++ /dev/null")
-                })?;
-                show_unified_diff_hunks(formatter, &left_part.content, &[], num_context_lines)?;
+                writeln!(formatter, "GIT binary patch")?;
+                show_git_binary_literal(formatter, right_content)?;
+                show_git_binary_literal(formatter, left_content)?;
             }
         }
         Ok::<(), DiffRenderError>(())
@@ -955,6 +1284,45 @@ pub fn show_git_diff(
     Ok(())
 }
 
+/// Writes `content` as a "literal" hunk of Git's binary patch format, which is
+/// zlib-compressed data encoded in base85, at most 52 bytes per line.
+fn show_git_binary_literal(formatter: &mut dyn Formatter, content: &[u8]) -> io::Result<()> {
+    let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+    encoder.write_all(content)?;
+    let compressed = encoder.finish()?;
+    writeln!(formatter, "literal {}", content.len())?;
+    for chunk in compressed.chunks(52) {
+        let len = chunk.len() as u8;
+        let mut line = vec![if len <= 26 {
+            b'A' + len - 1
+        } else {
+            b'a' + len - 27
+        }];
+        encode_git_base85(chunk, &mut line);
+        line.push(b'\n');
+        formatter.write_all(&line)?;
+    }
+    writeln!(formatter)
+}
+
+/// Encodes `data` in the base85 variant used by Git. The last group of bytes is
+/// padded with zeros.
+fn encode_git_base85(data: &[u8], out: &mut Vec<u8>) {
+    const ALPHABET: &[u8; 85] =
+        b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+    for group in data.chunks(4) {
+        let mut bytes = [0; 4];
+        bytes[..group.len()].copy_from_slice(group);
+        let mut acc = u32::from_be_bytes(bytes);
+        let mut encoded = [0; 5];
+        for c in encoded.iter_mut().rev() {
+            *c = ALPHABET[(acc % 85) as usize];
+            acc /= 85;
+        }
+        out.extend_from_slice(&encoded);
+    }
+}
+
 #[instrument(skip_all)]
 pub fn show_diff_summary(
     formatter: &mut dyn Formatter,
@@ -965,15 +1333,9 @@ pub fn show_diff_summary(
         async {
             while let Some((repo_path, diff)) = tree_diff.next().await {
                 let (before, after) = diff.unwrap();
+                let (status, label) = diff_status_char_and_label(&before, &after);
                 let ui_path = path_converter.format_file_path(&repo_path);
-                if before.is_present() && after.is_present() {
-                    writeln!(formatter.labeled("modified"), "M {ui_path}")?;
-                } else if before.is_absent() {
-                    writeln!(formatter.labeled("added"), "A {ui_path}")?;
-                } else {
-                    // `R` could be interpreted as "renamed"
-                    writeln!(formatter.labeled("removed"), "D {ui_path}")?;
-                }
+                writeln!(formatter.labeled(label), "{status} {ui_path}")?;
             }
             Ok(())
         }
@@ -981,14 +1343,87 @@ pub fn show_diff_summary(
     })
 }
 
-struct DiffStat {
-    path: String,
-    added: usize,
-    removed: usize,
+pub fn diff_status_char_and_label(
+    before: &MergedTreeValue,
+    after: &MergedTreeValue,
+) -> (char, &'static str) {
+    let status = if before.is_present() && after.is_present() {
+        'M'
+    } else if before.is_absent() {
+        'A'
+    } else {
+        // `R` could be interpreted as "renamed"
+        'D'
+    };
+    (status, diff_status_label(status))
+}
+
+/// Returns the label for a status character returned by
+/// `diff_status_char_and_label()`.
+pub fn diff_status_label(status: char) -> &'static str {
+    match status {
+        'M' => "modified",
+        'A' => "added",
+        'D' => "removed",
+        _ => panic!("unknown diff status {status:?}"),
+    }
+}
+
+/// Line-based statistics of the changed files.
+#[derive(Clone, Debug)]
+pub struct DiffStats {
+    entries: Vec<DiffStat>,
+}
+
+impl DiffStats {
+    /// Calculates the number of added and removed lines of each file.
+    pub fn calculate(store: &Store, tree_diff: TreeDiffStream) -> Result<Self, DiffRenderError> {
+        let mut entries = vec![];
+        let mut diff_stream = materialized_diff_stream(store, tree_diff);
+        async {
+            while let Some((repo_path, diff)) = diff_stream.next().await {
+                let (left, right) = diff?;
+                let is_conflict = matches!(right, MaterializedTreeValue::Conflict { .. });
+                let left_content = diff_content(&repo_path, left)?;
+                let right_content = diff_content(&repo_path, right)?;
+                let mut stat = get_diff_stat(repo_path, &left_content, &right_content);
+                stat.is_conflict = is_conflict;
+                entries.push(stat);
+            }
+            Ok::<(), DiffRenderError>(())
+        }
+        .block_on()?;
+        Ok(DiffStats { entries })
+    }
+
+    /// Stats of each changed file.
+    pub fn entries(&self) -> &[DiffStat] {
+        &self.entries
+    }
+
+    /// Total number of added lines.
+    pub fn count_total_added(&self) -> usize {
+        self.entries.iter().map(|stat| stat.added).sum()
+    }
+
+    /// Total number of removed lines.
+    pub fn count_total_removed(&self) -> usize {
+        self.entries.iter().map(|stat| stat.removed).sum()
+    }
+}
+
+/// Line-based statistics of a changed file.
+#[derive(Clone, Debug)]
+pub struct DiffStat {
+    pub path: RepoPathBuf,
+    pub added: usize,
+    pub removed: usize,
+    /// Whether the file is conflicted after the change.
+    pub is_conflict: bool,
 }
 
 fn get_diff_stat(
-    path: String,
+    path: RepoPathBuf,
     left_content: &FileContent,
     right_content: &FileContent,
 ) -> DiffStat {
@@ -1015,9 +1450,13 @@ fn get_diff_stat(
         path,
         added,
         removed,
+        is_conflict: false,
     }
 }
 
+/// Appended to the stat line of a file that is conflicted after the change.
+const CONFLICT_MARKER: &str = " (conflict)";
+
 pub fn show_diff_stat(
     repo: &dyn Repo,
     formatter: &mut dyn Formatter,
@@ -1025,29 +1464,29 @@ pub fn show_diff_stat(
     path_converter: &RepoPathUiConverter,
     display_width: usize,
 ) -> Result<(), DiffRenderError> {
-    let mut stats: Vec<DiffStat> = vec![];
-    let mut max_path_width = 0;
-    let mut max_diffs = 0;
-
-    let mut diff_stream = materialized_diff_stream(repo.store(), tree_diff);
-    async {
-        while let Some((repo_path, diff)) = diff_stream.next().await {
-            let (left, right) = diff?;
-            let path = path_converter.format_file_path(&repo_path);
-            let left_content = diff_content(&repo_path, left)?;
-            let right_content = diff_content(&repo_path, right)?;
-            max_path_width = max(max_path_width, path.width());
-            let stat = get_diff_stat(path, &left_content, &right_content);
-            max_diffs = max(max_diffs, stat.added + stat.removed);
-            stats.push(stat);
-        }
-        Ok::<(), DiffRenderError>(())
-    }
-    .block_on()?;
+    let stats = DiffStats::calculate(repo.store(), tree_diff)?;
+    let ui_paths = stats
+        .entries()
+        .iter()
+        .map(|stat| path_converter.format_file_path(&stat.path))
+        .collect_vec();
+    let max_path_width = ui_paths.iter().map(|path| path.width()).max().unwrap_or(0);
+    let max_diffs = stats
+        .entries()
+        .iter()
+        .map(|stat| stat.added + stat.removed)
+        .max()
+        .unwrap_or(0);
 
     let number_padding = max_diffs.to_string().len();
+    let conflict_marker_width = if stats.entries().iter().any(|stat| stat.is_conflict) {
+        CONFLICT_MARKER.len()
+    } else {
+        0
+    };
     // 4 characters padding for the graph
-    let available_width = display_width.saturating_sub(4 + " | ".len() + number_padding);
+    let available_width =
+        display_width.saturating_sub(4 + " | ".len() + number_padding + conflict_marker_width);
     // Always give at least a tiny bit of room
     let available_width = max(available_width, 5);
     let max_path_width = max_path_width.clamp(3, (0.7 * available_width as f64) as usize);
@@ -1059,16 +1498,11 @@ pub fn show_diff_stat(
     };
 
     formatter.with_label("diff", |formatter| {
-        let mut total_added = 0;
-        let mut total_removed = 0;
-        let total_files = stats.len();
-        for stat in &stats {
-            total_added += stat.added;
-            total_removed += stat.removed;
+        for (stat, ui_path) in stats.entries().iter().zip(&ui_paths) {
             let bar_added = (stat.added as f64 * factor).ceil() as usize;
             let bar_removed = (stat.removed as f64 * factor).ceil() as usize;
             // replace start of path with ellipsis if the path is too long
-            let (path, path_width) = text_util::elide_start(&stat.path, "...", max_path_width);
+            let (path, path_width) = text_util::elide_start(ui_path, "...", max_path_width);
             let path_pad_width = max_path_width - path_width;
             write!(
                 formatter,
@@ -1078,23 +1512,163 @@ pub fn show_diff_stat(
                 if bar_added + bar_removed > 0 { " " } else { "" },
             )?;
             write!(formatter.labeled("added"), "{}", "+".repeat(bar_added))?;
-            writeln!(formatter.labeled("removed"), "{}", "-".repeat(bar_removed))?;
+            write!(formatter.labeled("removed"), "{}", "-".repeat(bar_removed))?;
+            if stat.is_conflict {
+                write!(formatter.labeled("conflict"), "{CONFLICT_MARKER}")?;
+            }
+            writeln!(formatter)?;
         }
-        writeln!(
-            formatter.labeled("stat-summary"),
-            "{} file{} changed, {} insertion{}(+), {} deletion{}(-)",
-            total_files,
-            if total_files == 1 { "" } else { "s" },
-            total_added,
-            if total_added == 1 { "" } else { "s" },
-            total_removed,
-            if total_removed == 1 { "" } else { "s" },
-        )?;
+        write_diff_stat_summary(formatter, &stats)?;
+        writeln!(formatter)?;
         Ok(())
     })?;
     Ok(())
 }
 
+/// Whitespace problem to look for in added lines.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WhitespaceCheck {
+    /// Whitespace at the end of a line.
+    TrailingSpace,
+    /// Space immediately followed by a tab in the indentation.
+    SpaceBeforeTab,
+    /// Tab in the indentation.
+    TabInIndent,
+}
+
+impl WhitespaceCheck {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "trailing-space" => Some(WhitespaceCheck::TrailingSpace),
+            "space-before-tab" => Some(WhitespaceCheck::SpaceBeforeTab),
+            "tab-in-indent" => Some(WhitespaceCheck::TabInIndent),
+            _ => None,
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            WhitespaceCheck::TrailingSpace => "trailing whitespace",
+            WhitespaceCheck::SpaceBeforeTab => "space before tab in indent",
+            WhitespaceCheck::TabInIndent => "tab in indent",
+        }
+    }
+
+    /// Returns true if the `line` (without newline) has this problem.
+    fn matches(self, line: &[u8]) -> bool {
+        let indent_len = line
+            .iter()
+            .position(|b| !matches!(b, b' ' | b'\t'))
+            .unwrap_or(line.len());
+        let indent = &line[..indent_len];
+        match self {
+            WhitespaceCheck::TrailingSpace => {
+                matches!(line.last(), Some(b' ' | b'\t' | b'\r'))
+            }
+            WhitespaceCheck::SpaceBeforeTab => indent.windows(2).any(|w| w == b" \t"),
+            WhitespaceCheck::TabInIndent => indent.contains(&b'\t'),
+        }
+    }
+}
+
+/// Loads the whitespace checks enabled by the `diff.whitespace` setting.
+pub fn whitespace_checks_from_settings(
+    settings: &UserSettings,
+) -> Result<Vec<WhitespaceCheck>, config::ConfigError> {
+    let names: Vec<String> = settings.config().get("diff.whitespace")?;
+    names
+        .iter()
+        .map(|name| {
+            WhitespaceCheck::from_name(name).ok_or_else(|| {
+                config::ConfigError::Message(format!(
+                    "Invalid whitespace check in diff.whitespace: {name}"
+                ))
+            })
+        })
+        .try_collect()
+}
+
+/// Writes `path:line: problem` and the line for each added line that has one
+/// of the whitespace `checks`. Binary files are skipped. Returns the number of
+/// lines with problems.
+pub fn show_whitespace_check(
+    formatter: &mut dyn Formatter,
+    store: &Store,
+    tree_diff: TreeDiffStream,
+    path_converter: &RepoPathUiConverter,
+    checks: &[WhitespaceCheck],
+) -> Result<usize, DiffRenderError> {
+    let mut num_problems = 0;
+    let mut diff_stream = materialized_diff_stream(store, tree_diff);
+    async {
+        while let Some((path, diff)) = diff_stream.next().await {
+            let (left, right) = diff?;
+            let left_content = diff_content(&path, left)?;
+            let right_content = diff_content(&path, right)?;
+            if right_content.is_binary {
+                continue;
+            }
+            let ui_path = path_converter.format_file_path(&path);
+            let hunks = unified_diff_hunks(
+                &left_content.contents,
+                &right_content.contents,
+                0,
+                DiffAlgorithm::default(),
+            );
+            for hunk in hunks {
+                let mut line_number = hunk.right_line_range.start;
+                for (line_type, content) in hunk.lines {
+                    if line_type == DiffLineType::Removed {
+                        continue;
+                    }
+                    let line = content.strip_suffix(b"\n").unwrap_or(content);
+                    let problems = checks
+                        .iter()
+                        .filter(|check| check.matches(line))
+                        .map(|check| check.description())
+                        .collect_vec();
+                    if !problems.is_empty() {
+                        num_problems += 1;
+                        writeln!(
+                            formatter,
+                            "{ui_path}:{line_number}: {}",
+                            problems.join(", ")
+                        )?;
+                        formatter.with_label("diff", |formatter| {
+                            formatter.with_label("added", |formatter| {
+                                formatter.write_all(b"+")?;
+                                formatter.write_all(line)
+                            })?;
+                            writeln!(formatter)
+                        })?;
+                    }
+                    line_number += 1;
+                }
+            }
+        }
+        Ok::<(), DiffRenderError>(())
+    }
+    .block_on()?;
+    Ok(num_problems)
+}
+
+/// Writes the total number of changed files and lines, without newline.
+pub fn write_diff_stat_summary(formatter: &mut dyn Formatter, stats: &DiffStats) -> io::Result<()> {
+    let total_files = stats.entries().len();
+    let total_added = stats.count_total_added();
+    let total_removed = stats.count_total_removed();
+    write!(
+        formatter.labeled("stat-summary"),
+        "{} file{} changed, {} insertion{}(+), {} deletion{}(-)",
+        total_files,
+        if total_files == 1 { "" } else { "s" },
+        total_added,
+        if total_added == 1 { "" } else { "s" },
+        total_removed,
+        if total_removed == 1 { "" } else { "s" },
+    )
+}
+
 pub fn show_types(
     formatter: &mut dyn Formatter,
     mut tree_diff: TreeDiffStream,
@@ -1144,3 +1718,237 @@ pub fn show_names(
     }
     .block_on()
 }
+
+/// Writes NUL-terminated paths to the raw output, without any labels.
+pub fn show_names_null_terminated(
+    output: &mut dyn io::Write,
+    mut tree_diff: TreeDiffStream,
+    path_converter: &RepoPathUiConverter,
+) -> io::Result<()> {
+    async {
+        while let Some((repo_path, _)) = tree_diff.next().await {
+            write!(output, "{}\0", path_converter.format_file_path(&repo_path))?;
+        }
+        Ok(())
+    }
+    .block_on()
+}
+
+/// Renames are only detected between this many deleted and added files on
+/// each side, as comparing contents is quadratic.
+const MAX_RENAME_CANDIDATES: usize = 1000;
+
+/// Minimum similarity in percent for a deleted and an added file to be
+/// reported as a rename, the same as Git's default.
+const MIN_RENAME_SCORE: u32 = 50;
+
+/// Entry of the `--name-status` output.
+enum NameStatusEntry {
+    Changed(char, RepoPathBuf),
+    Renamed {
+        score: u32,
+        source: RepoPathBuf,
+        target: RepoPathBuf,
+    },
+}
+
+/// Collects the changed paths, pairing up deleted and added files with
+/// similar contents as renames like `git diff --name-status` does.
+fn collect_name_status(
+    store: &Store,
+    mut tree_diff: TreeDiffStream,
+) -> Result<Vec<NameStatusEntry>, DiffRenderError> {
+    let mut changes = vec![];
+    async {
+        while let Some((repo_path, diff)) = tree_diff.next().await {
+            let (before, after) = diff?;
+            let (status, _) = diff_status_char_and_label(&before, &after);
+            let file_id = match status {
+                'A' => resolved_file_id(&after),
+                'D' => resolved_file_id(&before),
+                _ => None,
+            };
+            changes.push((status, repo_path, file_id));
+        }
+        Ok::<(), DiffRenderError>(())
+    }
+    .block_on()?;
+
+    let deleted = changes
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (status, _, id))| (*status == 'D').then_some((i, id.as_ref()?)))
+        .collect_vec();
+    let added = changes
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (status, _, id))| (*status == 'A').then_some((i, id.as_ref()?)))
+        .collect_vec();
+    // Maps the index of an added file to the index of its source and the score.
+    let mut renames: HashMap<usize, (usize, u32)> = HashMap::new();
+    let mut used_sources = HashSet::new();
+    // Files with the same id are exact renames, which don't need to be read.
+    for &(target, target_id) in &added {
+        if let Some(&(source, _)) = deleted
+            .iter()
+            .find(|(source, source_id)| *source_id == target_id && !used_sources.contains(source))
+        {
+            used_sources.insert(source);
+            renames.insert(target, (source, 100));
+        }
+    }
+    let sources = deleted
+        .iter()
+        .filter(|(i, _)| !used_sources.contains(i))
+        .collect_vec();
+    let targets = added
+        .iter()
+        .filter(|(i, _)| !renames.contains_key(i))
+        .collect_vec();
+    if !sources.is_empty()
+        && !targets.is_empty()
+        && sources.len() <= MAX_RENAME_CANDIDATES
+        && targets.len() <= MAX_RENAME_CANDIDATES
+    {
+        let read_contents = |(i, id): &(usize, &FileId)| -> BackendResult<Vec<u8>> {
+            let mut contents = vec![];
+            store
+                .read_file(&changes[*i].1, id)?
+                .read_to_end(&mut contents)
+                .map_err(|err| BackendError::ReadFile {
+                    path: changes[*i].1.clone(),
+                    id: (*id).clone(),
+                    source: err.into(),
+                })?;
+            Ok(contents)
+        };
+        let source_contents: Vec<_> = sources.iter().map(|x| read_contents(x)).try_collect()?;
+        let target_contents: Vec<_> = targets.iter().map(|x| read_contents(x)).try_collect()?;
+        let mut candidates = vec![];
+        for (t, target_content) in target_contents.iter().enumerate() {
+            for (s, source_content) in source_contents.iter().enumerate() {
+                let score = similarity_score(source_content, target_content);
+                if score >= MIN_RENAME_SCORE {
+                    candidates.push((score, t, s));
+                }
+            }
+        }
+        // Pair up the most similar files first.
+        candidates.sort_by_key(|&(score, t, s)| (Reverse(score), t, s));
+        let mut used_targets = HashSet::new();
+        for (score, t, s) in candidates {
+            let (target, _) = *targets[t];
+            let (source, _) = *sources[s];
+            if !used_targets.contains(&target) && !used_sources.contains(&source) {
+                used_targets.insert(target);
+                used_sources.insert(source);
+                renames.insert(target, (source, score));
+            }
+        }
+    }
+
+    let mut entries = vec![];
+    for (i, (status, repo_path, _)) in changes.iter().enumerate() {
+        if let Some(&(source, score)) = renames.get(&i) {
+            entries.push(NameStatusEntry::Renamed {
+                score,
+                source: changes[source].1.clone(),
+                target: repo_path.clone(),
+            });
+        } else if !used_sources.contains(&i) {
+            entries.push(NameStatusEntry::Changed(*status, repo_path.clone()));
+        }
+    }
+    Ok(entries)
+}
+
+fn resolved_file_id(value: &MergedTreeValue) -> Option<FileId> {
+    match value.as_resolved()? {
+        Some(TreeValue::File { id, .. }) => Some(id.clone()),
+        _ => None,
+    }
+}
+
+/// Returns how much of the larger of the two non-empty files is made up of
+/// lines shared with the other one, in percent.
+fn similarity_score(source: &[u8], target: &[u8]) -> u32 {
+    if source.is_empty() || target.is_empty() {
+        return 0;
+    }
+    let mut source_lines: HashMap<&[u8], usize> = HashMap::new();
+    for line in source.split_inclusive(|&b| b == b'\n') {
+        *source_lines.entry(line).or_default() += 1;
+    }
+    let mut shared_len = 0;
+    for line in target.split_inclusive(|&b| b == b'\n') {
+        if let Some(count) = source_lines.get_mut(line).filter(|count| **count > 0) {
+            *count -= 1;
+            shared_len += line.len();
+        }
+    }
+    (shared_len * 100 / max(source.len(), target.len())) as u32
+}
+
+pub fn show_name_status(
+    formatter: &mut dyn Formatter,
+    store: &Store,
+    tree_diff: TreeDiffStream,
+    path_converter: &RepoPathUiConverter,
+) -> Result<(), DiffRenderError> {
+    let entries = collect_name_status(store, tree_diff)?;
+    formatter.with_label("diff", |formatter| {
+        for entry in &entries {
+            match entry {
+                NameStatusEntry::Changed(status, repo_path) => {
+                    let ui_path = path_converter.format_file_path(repo_path);
+                    writeln!(
+                        formatter.labeled(diff_status_label(*status)),
+                        "{status}\t{ui_path}"
+                    )?;
+                }
+                NameStatusEntry::Renamed {
+                    score,
+                    source,
+                    target,
+                } => {
+                    let source = path_converter.format_file_path(source);
+                    let target = path_converter.format_file_path(target);
+                    writeln!(
+                        formatter.labeled("renamed"),
+                        "R{score:03}\t{source}\t{target}"
+                    )?;
+                }
+            }
+        }
+        io::Result::Ok(())
+    })?;
+    Ok(())
+}
+
+/// Writes NUL-separated status letters and paths to the raw output, without
+/// any labels.
+pub fn show_name_status_null_terminated(
+    output: &mut dyn io::Write,
+    store: &Store,
+    tree_diff: TreeDiffStream,
+    path_converter: &RepoPathUiConverter,
+) -> Result<(), DiffRenderError> {
+    for entry in collect_name_status(store, tree_diff)? {
+        match entry {
+            NameStatusEntry::Changed(status, repo_path) => {
+                let ui_path = path_converter.format_file_path(&repo_path);
+                write!(output, "{status}\0{ui_path}\0")?;
+            }
+            NameStatusEntry::Renamed {
+                score,
+                source,
+                target,
+            } => {
+                let source = path_converter.format_file_path(&source);
+                let target = path_converter.format_file_path(&target);
+                write!(output, "R{score:03}\0{source}\0{target}\0")?;
+            }
+        }
+    }
+    Ok(())
+}
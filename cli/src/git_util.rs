@@ -17,6 +17,7 @@
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::sync::Mutex;
 use std::time::Instant;
 use std::{error, iter};
 
@@ -29,14 +30,17 @@ use jj_lib::store::Store;
 use jj_lib::workspace::Workspace;
 use unicode_width::UnicodeWidthStr;
 
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, CommandError, ErrorCode};
 use crate::formatter::Formatter;
 use crate::progress::Progress;
-use crate::ui::Ui;
+use crate::ui::{ProgressOutput, Ui};
 
 pub fn get_git_repo(store: &Store) -> Result<git2::Repository, CommandError> {
     match store.backend_impl().downcast_ref::<GitBackend>() {
-        None => Err(user_error("The repo is not backed by a git repo")),
+        None => Err(user_error(
+            ErrorCode::NoRepo,
+            "The repo is not backed by a git repo",
+        )),
         Some(git_backend) => Ok(git_backend.open_git_repo()?),
     }
 }
@@ -250,6 +254,111 @@ pub fn with_remote_git_callbacks<T>(
     f(callbacks)
 }
 
+/// Remote callbacks for fetching from several remotes concurrently.
+///
+/// The transfer progress of all remotes is combined into a single progress
+/// bar, and credential prompts are issued one at a time.
+pub struct ConcurrentRemoteCallbacks<'a> {
+    ui: &'a Ui,
+    progress: Mutex<Option<CombinedProgress>>,
+    prompt_lock: Mutex<()>,
+}
+
+struct CombinedProgress {
+    progress: Progress,
+    output: ProgressOutput,
+    remotes: Vec<git::Progress>,
+}
+
+impl<'a> ConcurrentRemoteCallbacks<'a> {
+    pub fn new(ui: &'a Ui, num_remotes: usize) -> Self {
+        let progress = ui.progress_output().map(|output| CombinedProgress {
+            progress: Progress::new(Instant::now()),
+            output,
+            remotes: iter::repeat_with(|| git::Progress {
+                bytes_downloaded: None,
+                overall: 0.0,
+            })
+            .take(num_remotes)
+            .collect(),
+        });
+        ConcurrentRemoteCallbacks {
+            ui,
+            progress: Mutex::new(progress),
+            prompt_lock: Mutex::new(()),
+        }
+    }
+
+    /// Calls `f` with the callbacks for the remote at `index`.
+    pub fn with_callbacks<T>(
+        &self,
+        index: usize,
+        f: impl FnOnce(git::RemoteCallbacks<'_>) -> T,
+    ) -> T {
+        let ui = self.ui;
+        let mut callbacks = git::RemoteCallbacks::default();
+        let mut progress_callback = |x: &git::Progress| {
+            let mut progress = self.progress.lock().unwrap();
+            if let Some(combined) = progress.as_mut() {
+                combined.remotes[index] = git::Progress {
+                    bytes_downloaded: x.bytes_downloaded,
+                    overall: x.overall,
+                };
+                combined.update();
+            }
+        };
+        if self.progress.lock().unwrap().is_some() {
+            callbacks.progress = Some(&mut progress_callback);
+        }
+        let mut get_ssh_keys = get_ssh_keys; // Coerce to unit fn type
+        callbacks.get_ssh_keys = Some(&mut get_ssh_keys);
+        let mut get_pw = |url: &str, _username: &str| {
+            let _lock = self.prompt_lock.lock().unwrap();
+            pinentry_get_pw(url).or_else(|| terminal_get_pw(ui, url))
+        };
+        callbacks.get_password = Some(&mut get_pw);
+        let mut get_user_pw = |url: &str| {
+            let _lock = self.prompt_lock.lock().unwrap();
+            Some((terminal_get_username(ui, url)?, terminal_get_pw(ui, url)?))
+        };
+        callbacks.get_username_password = Some(&mut get_user_pw);
+        f(callbacks)
+    }
+
+    /// Clears the progress bar, which may be left behind if a fetch failed.
+    pub fn finish(self) {
+        if let Some(mut combined) = self.progress.into_inner().unwrap() {
+            let done = git::Progress {
+                bytes_downloaded: None,
+                overall: 1.0,
+            };
+            _ = combined
+                .progress
+                .update(Instant::now(), &done, &mut combined.output);
+        }
+    }
+}
+
+impl CombinedProgress {
+    fn update(&mut self) {
+        let overall =
+            self.remotes.iter().map(|x| x.overall).sum::<f32>() / self.remotes.len() as f32;
+        let bytes_downloaded = self
+            .remotes
+            .iter()
+            .filter_map(|x| x.bytes_downloaded)
+            .reduce(|a, b| a + b);
+        // The bar is cleared once all remotes are done.
+        let combined = git::Progress {
+            bytes_downloaded,
+            overall,
+        };
+        _ = self
+            .progress
+            .update(Instant::now(), &combined, &mut self.output);
+    }
+}
+
 pub fn print_git_import_stats(
     ui: &mut Ui,
     repo: &dyn Repo,
@@ -9,11 +9,11 @@ use jj_lib::backend::MergedTreeId;
 use jj_lib::fsmonitor::FsmonitorSettings;
 use jj_lib::gitignore::GitIgnoreFile;
 use jj_lib::local_working_copy::{TreeState, TreeStateError};
-use jj_lib::matchers::Matcher;
+use jj_lib::matchers::{EverythingMatcher, Matcher};
 use jj_lib::merged_tree::MergedTree;
 use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::store::Store;
-use jj_lib::working_copy::{CheckoutError, SnapshotOptions};
+use jj_lib::working_copy::{CheckoutError, CheckoutOptions, SnapshotOptions};
 use pollster::FutureExt;
 use tempfile::TempDir;
 use thiserror::Error;
@@ -82,8 +82,12 @@ fn check_out(
     std::fs::create_dir(&wc_dir).map_err(DiffCheckoutError::SetUpDir)?;
     std::fs::create_dir(&state_dir).map_err(DiffCheckoutError::SetUpDir)?;
     let mut tree_state = TreeState::init(store, wc_dir, state_dir)?;
-    tree_state.set_sparse_patterns(sparse_patterns)?;
-    tree_state.check_out(tree)?;
+    // The directory is freshly created, so there's nothing to back up.
+    let options = CheckoutOptions {
+        backup_unsnapshotted: false,
+    };
+    tree_state.set_sparse_patterns(sparse_patterns, &options)?;
+    tree_state.check_out(tree, &options)?;
     Ok(tree_state)
 }
 
@@ -282,6 +286,7 @@ diff editing in mind and be a little inaccurate.
             fsmonitor_settings: FsmonitorSettings::None,
             progress: None,
             max_new_file_size: u64::MAX,
+            matcher: &EverythingMatcher,
         })?;
         Ok(output_tree_state.current_tree_id().clone())
     }
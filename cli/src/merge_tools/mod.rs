@@ -19,6 +19,7 @@ mod external;
 use std::sync::Arc;
 
 use config::ConfigError;
+use itertools::Itertools as _;
 use jj_lib::backend::MergedTreeId;
 use jj_lib::conflicts::extract_as_single_hunk;
 use jj_lib::gitignore::GitIgnoreFile;
@@ -61,6 +62,17 @@ pub enum DiffGenerateError {
     DiffCheckoutError(#[from] DiffCheckoutError),
 }
 
+impl DiffGenerateError {
+    /// Whether the external tool program couldn't be found.
+    pub fn is_tool_not_found(&self) -> bool {
+        matches!(
+            self,
+            DiffGenerateError::ExternalTool(ExternalToolError::FailedToExecute { source, .. })
+                if source.kind() == std::io::ErrorKind::NotFound
+        )
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ConflictResolveError {
     #[error(transparent)]
@@ -140,6 +152,12 @@ fn get_tool_config(settings: &UserSettings, name: &str) -> Result<Option<MergeTo
     }
 }
 
+/// Returns the names of the tools configured in `[merge-tools]`, sorted.
+pub fn configured_tool_names(settings: &UserSettings) -> Result<Vec<String>, ConfigError> {
+    let tools_table = settings.config().get_table("merge-tools")?;
+    Ok(tools_table.into_keys().sorted().collect())
+}
+
 /// Loads external diff/merge tool options from `[merge-tools.<name>]`.
 pub fn get_external_tool_config(
     settings: &UserSettings,
@@ -32,6 +32,9 @@ use crate::templater::{
     TemplatePropertyExt as _, TimestampRange,
 };
 
+/// Operation tag in which the reason given by `--reason` is stored.
+pub const REASON_TAG: &str = "reason";
+
 pub trait OperationTemplateLanguageExtension {
     fn build_fn_table(&self) -> OperationTemplateBuildFnTable;
 
@@ -248,6 +251,20 @@ fn builtin_operation_methods() -> OperationTemplateBuildMethodFnMap<Operation> {
         let out_property = self_property.map(|op| op.id().clone());
         Ok(L::wrap_operation_id(out_property))
     });
+    map.insert(
+        "reason",
+        |_language, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|op| {
+                op.metadata()
+                    .tags
+                    .get(REASON_TAG)
+                    .cloned()
+                    .unwrap_or_default()
+            });
+            Ok(L::wrap_string(out_property))
+        },
+    );
     map.insert("tags", |_language, _build_ctx, self_property, function| {
         function.expect_no_arguments()?;
         let out_property = self_property.map(|op| {
@@ -255,6 +272,7 @@ fn builtin_operation_methods() -> OperationTemplateBuildMethodFnMap<Operation> {
             op.metadata()
                 .tags
                 .iter()
+                .sorted()
                 .map(|(key, value)| format!("{key}: {value}"))
                 .join("\n")
         });
@@ -1,12 +1,14 @@
 use std::io;
 use std::path::Path;
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use crossterm::terminal::{Clear, ClearType};
 use jj_lib::fmt_util::binary_prefix;
 use jj_lib::git;
 use jj_lib::repo_path::RepoPath;
+use jj_lib::rewrite::RewriteProgress;
 
 use crate::cleanup_guard::CleanupGuard;
 use crate::text_util;
@@ -209,6 +211,84 @@ pub fn snapshot_progress(ui: &Ui) -> Option<impl Fn(&RepoPath) + '_> {
     })
 }
 
+/// Reports the progress of a rewrite of multiple commits.
+///
+/// If the process is interrupted while this is alive, a message saying that
+/// the repo wasn't changed is printed. The rewrite happens within a
+/// transaction, which doesn't get committed in that case.
+pub struct RewriteProgressReporter {
+    action: &'static str,
+    output: Option<ProgressOutput>,
+    guard: Option<OutputGuard>,
+    next_display_time: Instant,
+    finished: Arc<AtomicBool>,
+    _interrupt_guard: CleanupGuard,
+}
+
+impl RewriteProgressReporter {
+    /// Creates a reporter which describes the rewrite as `action` (e.g.
+    /// "Rebasing").
+    pub fn new(ui: &Ui, action: &'static str) -> Self {
+        let finished = Arc::new(AtomicBool::new(false));
+        let interrupt_guard = CleanupGuard::new({
+            let finished = finished.clone();
+            move || {
+                if !finished.load(Ordering::SeqCst) {
+                    eprintln!(
+                        "\r{}Interrupted. Nothing was changed.",
+                        Clear(ClearType::CurrentLine)
+                    );
+                }
+            }
+        });
+        Self {
+            action,
+            output: ui.progress_output(),
+            guard: None,
+            // Don't clutter the output during fast operations.
+            next_display_time: Instant::now() + INITIAL_DELAY,
+            finished,
+            _interrupt_guard: interrupt_guard,
+        }
+    }
+
+    pub fn update(&mut self, progress: RewriteProgress) {
+        let Some(output) = &mut self.output else {
+            return;
+        };
+        if progress.visited == progress.total {
+            // Clears the progress line if any.
+            self.guard = None;
+            return;
+        }
+        let now = Instant::now();
+        if now < self.next_display_time {
+            return;
+        }
+        self.next_display_time = now + Duration::from_secs(1) / UPDATE_HZ;
+
+        if self.guard.is_none() {
+            self.guard = Some(output.output_guard(format!("\r{}", Clear(ClearType::CurrentLine))));
+        }
+        _ = write!(
+            output,
+            "\r{}{} {}/{} commits",
+            Clear(ClearType::CurrentLine),
+            self.action,
+            progress.visited,
+            progress.total,
+        );
+        _ = output.flush();
+    }
+}
+
+impl Drop for RewriteProgressReporter {
+    fn drop(&mut self) {
+        // Runs before the interrupt guard is dropped, so nothing is printed.
+        self.finished.store(true, Ordering::SeqCst);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -30,7 +30,7 @@ use jj_lib::revset::{
 use jj_lib::settings::ConfigResultExt as _;
 use thiserror::Error;
 
-use crate::command_error::{user_error, CommandError};
+use crate::command_error::{user_error, CommandError, ErrorCode};
 use crate::config::LayeredConfigs;
 use crate::formatter::Formatter;
 use crate::templater::TemplateRenderer;
@@ -47,6 +47,7 @@ pub enum UserRevsetEvaluationError {
 }
 
 /// Wrapper around `RevsetExpression` to provide convenient methods.
+#[derive(Clone)]
 pub struct RevsetExpressionEvaluator<'repo> {
     repo: &'repo dyn Repo,
     extensions: Arc<RevsetExtensions>,
@@ -79,6 +80,11 @@ impl<'repo> RevsetExpressionEvaluator<'repo> {
         self.expression = self.expression.intersection(other);
     }
 
+    /// Expands the underlying expression to include the revisions in between.
+    pub fn connect(&mut self) {
+        self.expression = self.expression.connected();
+    }
+
     /// Evaluates the expression.
     pub fn evaluate(&self) -> Result<Box<dyn Revset + 'repo>, UserRevsetEvaluationError> {
         let symbol_resolver = default_symbol_resolver(
@@ -191,9 +197,10 @@ pub(super) fn evaluate_revset_to_single_commit<'a>(
     let mut iter = expression.evaluate_to_commits()?.fuse();
     match (iter.next(), iter.next()) {
         (Some(commit), None) => Ok(commit?),
-        (None, _) => Err(user_error(format!(
-            r#"Revset "{revision_str}" didn't resolve to any revisions"#
-        ))),
+        (None, _) => Err(user_error(
+            ErrorCode::EmptyRevset,
+            format!(r#"Revset "{revision_str}" didn't resolve to any revisions"#),
+        )),
         (Some(commit0), Some(commit1)) => {
             let mut iter = [commit0, commit1].into_iter().chain(iter);
             let commits: Vec<_> = iter.by_ref().take(5).try_collect()?;
@@ -219,9 +226,10 @@ fn format_multiple_revisions_error(
     should_hint_about_all_prefix: bool,
 ) -> CommandError {
     assert!(commits.len() >= 2);
-    let mut cmd_err = user_error(format!(
-        r#"Revset "{revision_str}" resolved to more than one revision"#
-    ));
+    let mut cmd_err = user_error(
+        ErrorCode::MultipleRevisions,
+        format!(r#"Revset "{revision_str}" resolved to more than one revision"#),
+    );
     let write_commits_summary = |formatter: &mut dyn Formatter| {
         for commit in commits {
             write!(formatter, "  ")?;
@@ -248,6 +256,7 @@ fn format_multiple_revisions_error(
         );
     } else if let RevsetExpression::CommitRef(RevsetCommitRef::Symbol(branch_name)) = expression {
         // Separate hint if there's a conflicted branch
+        cmd_err.code = ErrorCode::BranchConflict;
         cmd_err.add_formatted_hint_with(|formatter| {
             writeln!(
                 formatter,
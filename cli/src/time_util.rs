@@ -1,5 +1,5 @@
 use chrono::format::StrftimeItems;
-use chrono::{DateTime, FixedOffset, LocalResult, TimeZone, Utc};
+use chrono::{DateTime, FixedOffset, LocalResult, SecondsFormat, TimeZone, Utc};
 use jj_lib::backend::Timestamp;
 use once_cell::sync::Lazy;
 use thiserror::Error;
@@ -72,6 +72,12 @@ pub fn format_absolute_timestamp(timestamp: &Timestamp) -> Result<String, Timest
     format_absolute_timestamp_with(timestamp, &DEFAULT_FORMAT)
 }
 
+/// Formats the timestamp as RFC 3339 with millisecond precision.
+pub fn format_rfc3339_timestamp(timestamp: &Timestamp) -> Result<String, TimestampOutOfRange> {
+    let datetime = datetime_from_timestamp(timestamp)?;
+    Ok(datetime.to_rfc3339_opts(SecondsFormat::Millis, false))
+}
+
 pub fn format_absolute_timestamp_with(
     timestamp: &Timestamp,
     format: &FormattingItems,
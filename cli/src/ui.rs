@@ -167,10 +167,12 @@ impl Write for UiStderr<'_> {
 
 pub struct Ui {
     quiet: bool,
+    error_format: ErrorFormat,
     pager_cmd: CommandNameAndArgs,
     paginate: PaginationChoice,
     progress_indicator: bool,
     formatter_factory: FormatterFactory,
+    diff_formatter_factory: FormatterFactory,
     output: UiOutput,
 }
 
@@ -221,12 +223,22 @@ fn color_setting(config: &config::Config) -> ColorChoice {
         .unwrap_or_default()
 }
 
+/// Color choice for diff output. Falls back to `ui.color` if unset.
+fn diff_color_setting(config: &config::Config) -> ColorChoice {
+    config
+        .get_string("ui.diff.color")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_else(|| color_setting(config))
+}
+
 fn prepare_formatter_factory(
     config: &config::Config,
+    color_choice: ColorChoice,
     stdout: &Stdout,
 ) -> Result<FormatterFactory, config::ConfigError> {
     let terminal = stdout.is_terminal();
-    let (color, debug) = match color_setting(config) {
+    let (color, debug) = match color_choice {
         ColorChoice::Always => (true, false),
         ColorChoice::Never => (false, false),
         ColorChoice::Debug => (true, true),
@@ -255,6 +267,22 @@ pub enum PaginationChoice {
     Auto,
 }
 
+/// How errors are printed to stderr.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all(deserialize = "kebab-case"))]
+pub enum ErrorFormat {
+    #[default]
+    Text,
+    /// One JSON object with stable error code, for scripts
+    Json,
+}
+
+fn error_format_setting(config: &config::Config) -> Result<ErrorFormat, CommandError> {
+    config
+        .get::<ErrorFormat>("ui.error-format")
+        .map_err(|err| config_error_with_message("Invalid `ui.error-format`", err))
+}
+
 fn pagination_setting(config: &config::Config) -> Result<PaginationChoice, CommandError> {
     config
         .get::<PaginationChoice>("ui.paginate")
@@ -270,11 +298,16 @@ fn pager_setting(config: &config::Config) -> Result<CommandNameAndArgs, CommandE
 impl Ui {
     pub fn with_config(config: &config::Config) -> Result<Ui, CommandError> {
         let quiet = be_quiet(config);
-        let formatter_factory = prepare_formatter_factory(config, &io::stdout())?;
+        let formatter_factory =
+            prepare_formatter_factory(config, color_setting(config), &io::stdout())?;
+        let diff_formatter_factory =
+            prepare_formatter_factory(config, diff_color_setting(config), &io::stdout())?;
         let progress_indicator = progress_indicator_setting(config);
         Ok(Ui {
             quiet,
+            error_format: error_format_setting(config)?,
             formatter_factory,
+            diff_formatter_factory,
             pager_cmd: pager_setting(config)?,
             paginate: pagination_setting(config)?,
             progress_indicator,
@@ -284,10 +317,14 @@ impl Ui {
 
     pub fn reset(&mut self, config: &config::Config) -> Result<(), CommandError> {
         self.quiet = be_quiet(config);
+        self.error_format = error_format_setting(config)?;
         self.paginate = pagination_setting(config)?;
         self.pager_cmd = pager_setting(config)?;
         self.progress_indicator = progress_indicator_setting(config);
-        self.formatter_factory = prepare_formatter_factory(config, &io::stdout())?;
+        self.formatter_factory =
+            prepare_formatter_factory(config, color_setting(config), &io::stdout())?;
+        self.diff_formatter_factory =
+            prepare_formatter_factory(config, diff_color_setting(config), &io::stdout())?;
         Ok(())
     }
 
@@ -330,6 +367,10 @@ impl Ui {
         self.formatter_factory.is_color()
     }
 
+    pub fn error_format(&self) -> ErrorFormat {
+        self.error_format
+    }
+
     pub fn new_formatter<'output, W: Write + 'output>(
         &self,
         output: W,
@@ -354,6 +395,13 @@ impl Ui {
         for_outputs!(UiStdout, self.stdout(), w => self.new_formatter(w))
     }
 
+    /// Creates a formatter for the locked stdout stream to print diffs.
+    ///
+    /// This is colorized according to `ui.diff.color` instead of `ui.color`.
+    pub fn diff_stdout_formatter(&self) -> Box<dyn Formatter + '_> {
+        for_outputs!(UiStdout, self.stdout(), w => self.diff_formatter_factory.new_formatter(w))
+    }
+
     /// Locked stderr stream.
     pub fn stderr(&self) -> UiStderr<'_> {
         match &self.output {
@@ -26,6 +26,7 @@ mod test_diffedit_command;
 mod test_duplicate_command;
 mod test_edit_command;
 mod test_file_chmod_command;
+mod test_file_list_command;
 mod test_file_print_command;
 mod test_fix_command;
 mod test_generate_md_cli_help;
@@ -39,6 +40,7 @@ mod test_git_remotes;
 mod test_git_submodule;
 mod test_gitignores;
 mod test_global_opts;
+mod test_help_command;
 mod test_immutable_commits;
 mod test_init_command;
 mod test_interdiff_command;
@@ -46,6 +48,7 @@ mod test_log_command;
 mod test_move_command;
 mod test_new_command;
 mod test_next_prev_commands;
+mod test_note_command;
 mod test_obslog_command;
 mod test_operations;
 mod test_parallelize_command;
@@ -64,6 +67,7 @@ mod test_status_command;
 mod test_tag_command;
 mod test_templater;
 mod test_undo;
+mod test_unsign_command;
 mod test_unsquash_command;
 mod test_untrack_command;
 mod test_util_command;
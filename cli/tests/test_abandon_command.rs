@@ -364,6 +364,112 @@ fn test_double_abandon() {
     "###);
 }
 
+#[test]
+fn test_abandon_restore_descendants() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file"), "foo\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    std::fs::write(repo_path.join("file"), "bar\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    std::fs::write(repo_path.join("file"), "baz\n").unwrap();
+
+    // Remove the commit containing "bar". By default, the child is rebased and
+    // conflicts
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["abandon", "-r", "@-"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Abandoned commit rlvkpnrz 225adef1 (no description set)
+    Rebased 1 descendant commits onto parents of abandoned commits
+    New conflicts appeared in these commits:
+      kkmpptxz 5cc05e72 (conflict) (no description set)
+    To resolve the conflicts, start by updating to it:
+      jj new kkmpptxzrspx
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you may want inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    Working copy now at: kkmpptxz 5cc05e72 (conflict) (no description set)
+    Parent commit      : qpvuntsm 485d52a9 (no description set)
+    Added 0 files, modified 1 files, removed 0 files
+    There are unresolved conflicts at these paths:
+    file    2-sided conflict
+    "###);
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+
+    // With --restore-descendants, the child keeps its content, and the changes
+    // from the abandoned commit are moved into it
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["abandon", "-r", "@-", "--restore-descendants"],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Abandoned commit rlvkpnrz 225adef1 (no description set)
+    Rebased 1 descendant commits (while preserving their content) onto parents of abandoned commits
+    Working copy now at: kkmpptxz 0d5ce873 (no description set)
+    Parent commit      : qpvuntsm 485d52a9 (no description set)
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--git"]);
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/file b/file
+    index 257cc5642c...76018072e0 100644
+    --- a/file
+    +++ b/file
+    @@ -1,1 +1,1 @@
+    -foo
+    +baz
+    "###);
+    // No commit is conflicted
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["log", "--no-graph", "-T", r#"if(conflict, "conflict\n")"#],
+    );
+    insta::assert_snapshot!(stdout, @"");
+}
+
+#[test]
+fn test_abandon_retain_branches() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    create_commit(&test_env, &repo_path, "a", &[]);
+    create_commit(&test_env, &repo_path, "b", &["a"]);
+    create_commit(&test_env, &repo_path, "c", &["b"]);
+
+    // By default, the branch moves to the parent
+    test_env.jj_cmd_ok(&repo_path, &["abandon", "b"]);
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r###"
+    @  [roy] c
+    ◉  [rlv] a b
+    ◉  [zzz]
+    "###);
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+
+    // With --retain-branches, the branch stays on the abandoned commit
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["abandon", "b", "--retain-branches"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Abandoned commit zsuskuln 1394f625 b | b
+    Rebased 1 descendant commits onto parents of abandoned commits
+    Working copy now at: royxmykx 934236c8 c | c
+    Parent commit      : rlvkpnrz 2443ea76 a | a
+    Added 0 files, modified 0 files, removed 1 files
+    "###);
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r###"
+    @  [roy] c
+    ◉  [rlv] a
+    ◉  [zzz]
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-r", "b", "--no-graph"]);
+    insta::assert_snapshot!(stdout, @r###"
+    zsuskuln hidden test.user@example.com 2001-02-03 08:05:11 b 1394f625
+    b
+    "###);
+}
+
 fn get_log_output(test_env: &TestEnvironment, repo_path: &Path) -> String {
     test_env.jj_cmd_success(
         repo_path,
@@ -209,17 +209,18 @@ fn test_alias_global_args_before_and_after() {
     "###);
 
     // Can pass global args before
-    let stdout = test_env.jj_cmd_success(&repo_path, &["l", "--at-op", "@-"]);
+    let (stdout, _stderr) = test_env.jj_cmd_ok(&repo_path, &["l", "--at-op", "@-"]);
     insta::assert_snapshot!(stdout, @r###"
     ◉  0000000000000000000000000000000000000000
     "###);
     // Can pass global args after
-    let stdout = test_env.jj_cmd_success(&repo_path, &["--at-op", "@-", "l"]);
+    let (stdout, _stderr) = test_env.jj_cmd_ok(&repo_path, &["--at-op", "@-", "l"]);
     insta::assert_snapshot!(stdout, @r###"
     ◉  0000000000000000000000000000000000000000
     "###);
     // Test passing global args both before and after
-    let stdout = test_env.jj_cmd_success(&repo_path, &["--at-op", "abc123", "l", "--at-op", "@-"]);
+    let (stdout, _stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["--at-op", "abc123", "l", "--at-op", "@-"]);
     insta::assert_snapshot!(stdout, @r###"
     ◉  0000000000000000000000000000000000000000
     "###);
@@ -240,7 +241,7 @@ fn test_alias_global_args_in_definition() {
     );
 
     // The global argument in the alias is respected
-    let stdout = test_env.jj_cmd_success(&repo_path, &["l"]);
+    let (stdout, _stderr) = test_env.jj_cmd_ok(&repo_path, &["l"]);
     insta::assert_snapshot!(stdout, @r###"
     ◉  [38;5;4m0000000000000000000000000000000000000000[39m
     "###);
@@ -14,7 +14,7 @@
 
 use std::path::Path;
 
-use crate::common::TestEnvironment;
+use crate::common::{get_stderr_string, get_stdout_string, TestEnvironment};
 
 #[test]
 fn test_branch_multiple_names() {
@@ -194,6 +194,63 @@ fn test_branch_move() {
     "###);
 }
 
+#[test]
+fn test_branch_move_backwards_confirm() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config("branch.require-confirm-backwards = true");
+
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m=old"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m=new"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "foo"]);
+
+    // Without a terminal, the move is refused
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["branch", "set", "-r@-", "foo"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Refusing to move branch backwards or sideways: foo
+    Hint: Use --allow-backwards to allow it.
+    "###);
+
+    // Declined at the prompt
+    let assert = test_env
+        .jj_cmd_stdin(&repo_path, &["branch", "move", "--to=@-", "foo"], "n\n")
+        .assert()
+        .code(1);
+    let stdout = test_env.normalize_output(&get_stdout_string(&assert));
+    let stderr = test_env.normalize_output(&get_stderr_string(&assert));
+    insta::assert_snapshot!(stdout, @r###"
+    Branch foo would be moved backwards or sideways.
+      From: kkmpptxz 48bf3dd8 foo | (empty) new
+      To:   qpvuntsm 8b0dc2d1 (empty) old
+    Move it anyway? (yN): 
+    "###);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Refusing to move branch backwards or sideways: foo
+    Hint: Use --allow-backwards to allow it.
+    "###);
+
+    // Confirmed at the prompt
+    let (stdout, stderr) =
+        test_env.jj_cmd_stdin_ok(&repo_path, &["branch", "set", "-r@-", "foo"], "y\n");
+    insta::assert_snapshot!(stdout, @r###"
+    Branch foo would be moved backwards or sideways.
+      From: kkmpptxz 48bf3dd8 foo | (empty) new
+      To:   qpvuntsm 8b0dc2d1 (empty) old
+    Move it anyway? (yN): 
+    "###);
+    insta::assert_snapshot!(stderr, @"");
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    foo: qpvuntsm 8b0dc2d1 (empty) old
+    "###);
+
+    // Forward moves don't ask
+    let (stdout, stderr) =
+        test_env.jj_cmd_stdin_ok(&repo_path, &["branch", "move", "--to=@", "foo"], "");
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @"");
+}
+
 #[test]
 fn test_branch_move_matching() {
     let test_env = TestEnvironment::default();
@@ -407,6 +464,105 @@ fn test_branch_rename() {
     "###);
 }
 
+#[test]
+fn test_branch_description() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    // Set up remote
+    let git_repo_path = test_env.env_root().join("git-repo");
+    git2::Repository::init_bare(git_repo_path).unwrap();
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["git", "remote", "add", "origin", "../git-repo"],
+    );
+
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m=commit-0"]);
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["branch", "create", "foo", "--description=Work on foo"],
+    );
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "bar"]);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    bar: qpvuntsm 79a5671f (empty) commit-0
+    foo: qpvuntsm 79a5671f (empty) commit-0
+      Work on foo
+    "###);
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "log",
+            "-r@",
+            "-T",
+            r#"branches.map(|b| b.name() ++ "=" ++ b.description())"#,
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    @  bar= foo=Work on foo
+    │
+    ~
+    "###);
+
+    // The description survives push and fetch, and isn't sent to the remote
+    test_env.jj_cmd_ok(&repo_path, &["git", "push", "--all"]);
+    test_env.jj_cmd_ok(&repo_path, &["git", "fetch"]);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    bar: qpvuntsm 79a5671f (empty) commit-0
+      @origin: qpvuntsm 79a5671f (empty) commit-0
+    foo: qpvuntsm 79a5671f (empty) commit-0
+      Work on foo
+      @origin: qpvuntsm 79a5671f (empty) commit-0
+    "###);
+
+    // The description can be edited later
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["branch", "set", "foo", "bar", "--description=Updated"],
+    );
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    bar: qpvuntsm 79a5671f (empty) commit-0
+      Updated
+      @origin: qpvuntsm 79a5671f (empty) commit-0
+    foo: qpvuntsm 79a5671f (empty) commit-0
+      Updated
+      @origin: qpvuntsm 79a5671f (empty) commit-0
+    "###);
+
+    // Renaming carries the description over
+    test_env.jj_cmd_ok(&repo_path, &["branch", "rename", "foo", "foo2"]);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    bar: qpvuntsm 79a5671f (empty) commit-0
+      Updated
+      @origin: qpvuntsm 79a5671f (empty) commit-0
+    foo (deleted)
+      @origin: qpvuntsm 79a5671f (empty) commit-0
+    foo2: qpvuntsm 79a5671f (empty) commit-0
+      Updated
+    "###);
+
+    // Undo restores the description along with the branch
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "set", "bar", "--description="]);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    bar: qpvuntsm 79a5671f (empty) commit-0
+      @origin: qpvuntsm 79a5671f (empty) commit-0
+    foo: qpvuntsm 79a5671f (empty) commit-0
+      Updated
+      @origin: qpvuntsm 79a5671f (empty) commit-0
+    "###);
+
+    // Deleting the branch removes the description
+    test_env.jj_cmd_ok(&repo_path, &["branch", "delete", "foo"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "set", "foo"]);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    bar: qpvuntsm 79a5671f (empty) commit-0
+      @origin: qpvuntsm 79a5671f (empty) commit-0
+    foo: qpvuntsm 79a5671f (empty) commit-0
+      @origin: qpvuntsm 79a5671f (empty) commit-0
+    "###);
+}
+
 #[test]
 fn test_branch_forget_glob() {
     let test_env = TestEnvironment::default();
@@ -1021,6 +1177,215 @@ fn test_branch_track_conflict() {
     "###);
 }
 
+#[test]
+fn test_branch_create_set_track() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    let git_repo_path = test_env.env_root().join("git-repo");
+    git2::Repository::init_bare(git_repo_path).unwrap();
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["git", "remote", "add", "origin", "../git-repo"],
+    );
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "main"]);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "a"]);
+    test_env.jj_cmd_ok(&repo_path, &["git", "push", "-b", "main"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "untrack", "main@origin"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "delete", "main"]);
+
+    // The remote branch must exist
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &["branch", "create", "main", "--track", "upstream"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: No such remote branch: main@upstream
+    Hint: Use `jj git push --remote upstream --branch main` to create the branch on the remote.
+    "###);
+
+    // The local target must match the remote target
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &["branch", "create", "main", "--track", "origin"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Branch target differs from the remote branch: main@origin
+    Hint: Use --allow-divergent to track it anyway.
+    "###);
+
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["branch", "create", "main", "-r@-", "--track", "origin"],
+    );
+    insta::assert_snapshot!(stderr, @"");
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    main: qpvuntsm 427890ea (empty) a
+      @origin: qpvuntsm 427890ea (empty) a
+    "###);
+
+    // Creation and tracking are undone together
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @"main@origin: qpvuntsm 427890ea (empty) a");
+
+    let stderr =
+        test_env.jj_cmd_failure(&repo_path, &["branch", "set", "main", "--track", "origin"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Branch target differs from the remote branch: main@origin
+    Hint: Use --allow-divergent to track it anyway.
+    "###);
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "branch",
+            "set",
+            "main",
+            "--track",
+            "origin",
+            "--allow-divergent",
+        ],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Created branches: main
+    Hint: Consider using `jj branch move` if your intention was to move existing branches.
+    "###);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    main: mzvwutvl 64511363 (empty) (no description set)
+      @origin (behind by 1 commits): qpvuntsm 427890ea (empty) a
+    "###);
+
+    // Tracking an already tracked remote branch is a no-op
+    let (_stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["branch", "set", "main", "--track", "origin"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: Remote branch already tracked: main@origin
+    Nothing changed.
+    "###);
+}
+
+#[test]
+fn test_branch_create_at_remote_head() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    let git_repo_path = test_env.env_root().join("git-repo");
+    git2::Repository::init_bare(git_repo_path).unwrap();
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["git", "remote", "add", "origin", "../git-repo"],
+    );
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "main", "feature"]);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "a"]);
+    test_env.jj_cmd_ok(&repo_path, &["git", "push", "--all"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "untrack", "glob:*@origin"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "delete", "glob:*"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-m", "b"]);
+
+    // The remote branch must exist
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &["branch", "create", "main", "--at-remote-head", "upstream"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: No such remote branch: main@upstream
+    Hint: Use `jj branch list --all-remotes` to list the remote branches.
+    "###);
+
+    // Cannot be combined with --revision
+    let stderr = test_env.jj_cmd_cli_error(
+        &repo_path,
+        &[
+            "branch",
+            "create",
+            "main",
+            "--at-remote-head",
+            "origin",
+            "-r@",
+        ],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--at-remote-head <REMOTE>' cannot be used with '--revision <REVISION>'
+
+    Usage: jj branch create --at-remote-head <REMOTE> <NAMES>...
+
+    For more information, try '--help'.
+    "###);
+
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["branch", "create", "main", "--at-remote-head", "origin"],
+    );
+    insta::assert_snapshot!(stderr, @"");
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    feature@origin: qpvuntsm 427890ea (empty) a
+    main: qpvuntsm 427890ea (empty) a
+    main@origin: qpvuntsm 427890ea (empty) a
+    "###);
+
+    // The created branch can be tracked at the same time
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "branch",
+            "create",
+            "feature",
+            "--at-remote-head",
+            "origin",
+            "--track",
+            "origin",
+        ],
+    );
+    insta::assert_snapshot!(stderr, @"");
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    feature: qpvuntsm 427890ea (empty) a
+      @origin: qpvuntsm 427890ea (empty) a
+    main: qpvuntsm 427890ea (empty) a
+    main@origin: qpvuntsm 427890ea (empty) a
+    "###);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "delete", "glob:*"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "untrack", "feature@origin"]);
+
+    // Make the remote branch conflicted by concurrent imports
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-m", "c"]);
+    let git_repo = git2::Repository::open(repo_path.join(".jj/repo/store/git")).unwrap();
+    let set_remote_main = |description: &str| {
+        let commit_id = test_env.jj_cmd_success(
+            &repo_path,
+            &[
+                "log",
+                "-r",
+                &format!("description({description})"),
+                "--no-graph",
+                "-T",
+                "commit_id",
+            ],
+        );
+        git_repo
+            .reference(
+                "refs/remotes/origin/main",
+                git2::Oid::from_str(&commit_id).unwrap(),
+                true,
+                "",
+            )
+            .unwrap();
+    };
+    set_remote_main("b");
+    test_env.jj_cmd_ok(&repo_path, &["git", "import"]);
+    set_remote_main("c");
+    test_env.jj_cmd_ok(&repo_path, &["git", "import", "--at-op=@-"]);
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &["branch", "create", "main", "--at-remote-head", "origin"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Concurrent modification detected, resolving automatically.
+    Error: Remote branch main@origin is conflicted
+    Hint: It points to multiple commits: 4a192237baf1, 9faba6ac91ae. Use `jj branch create -r <REVISION>` to create the branch at one of them.
+    "###);
+}
+
 #[test]
 fn test_branch_track_untrack_patterns() {
     let test_env = TestEnvironment::default();
@@ -1080,9 +1445,10 @@ fn test_branch_track_untrack_patterns() {
         test_env.jj_cmd_failure(&repo_path, &["branch", "untrack", "main@origin"]), @r###"
     Error: No such remote branch: main@origin
     "###);
-    insta::assert_snapshot!(
-        test_env.jj_cmd_failure(&repo_path, &["branch", "track", "glob:maine@*"]), @r###"
-    Error: No matching remote branches for patterns: maine@*
+    let (_, stderr) = test_env.jj_cmd_ok(&repo_path, &["branch", "track", "glob:maine@*"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: No matching remote branches for pattern: maine@*
+    Nothing changed.
     "###);
     insta::assert_snapshot!(
         test_env.jj_cmd_failure(
@@ -1100,6 +1466,10 @@ fn test_branch_track_untrack_patterns() {
     Nothing changed.
     "###);
 
+    // Glob patterns skip already tracked branches silently
+    let (_, stderr) = test_env.jj_cmd_ok(&repo_path, &["branch", "track", "glob:feature1@*"]);
+    insta::assert_snapshot!(stderr, @"Nothing changed.");
+
     // Untrack non-tracking branch
     let (_, stderr) = test_env.jj_cmd_ok(&repo_path, &["branch", "untrack", "feature2@origin"]);
     insta::assert_snapshot!(stderr, @r###"
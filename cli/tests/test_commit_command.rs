@@ -123,6 +123,49 @@ fn test_commit_interactive() {
     "###);
 }
 
+#[test]
+fn test_commit_interactive_empty_selection() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let workspace_path = test_env.env_root().join("repo");
+
+    std::fs::write(workspace_path.join("file1"), "foo\n").unwrap();
+    let diff_editor = test_env.set_up_fake_diff_editor();
+    std::fs::write(diff_editor, "rm file1").unwrap();
+
+    // Selecting nothing creates an empty commit by default
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        &workspace_path,
+        &["commit", "--tool=fake-diff-editor", "-m=empty"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: No changes were selected, so the commit will be empty.
+    Working copy now at: rlvkpnrz b3cbfb3f (no description set)
+    Parent commit      : qpvuntsm 1e9d9f0f (empty) empty
+    "###);
+    insta::assert_snapshot!(get_log_output(&test_env, &workspace_path), @r###"
+    @  b3cbfb3f75ca
+    ◉  1e9d9f0fff69 empty
+    ◉  000000000000
+    "###);
+
+    // Or aborts if configured
+    test_env.jj_cmd_ok(&workspace_path, &["undo"]);
+    test_env.add_config("ui.allow-empty-interactive-commit = false");
+    let stderr = test_env.jj_cmd_failure(
+        &workspace_path,
+        &["commit", "--tool=fake-diff-editor", "-m=empty"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: No changes were selected, so nothing was committed
+    Hint: Set `ui.allow-empty-interactive-commit = true` to create an empty commit instead.
+    "###);
+    insta::assert_snapshot!(get_log_output(&test_env, &workspace_path), @r###"
+    @  0e15949eed93
+    ◉  000000000000
+    "###);
+}
+
 #[test]
 fn test_commit_with_default_description() {
     let mut test_env = TestEnvironment::default();
@@ -217,6 +260,68 @@ fn test_commit_paths_warning() {
     "###);
 }
 
+#[test]
+fn test_commit_author() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["commit", "-m", "first", "--author", "Foo Bar <foo@bar.com>"],
+    );
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "log",
+            "-T",
+            r#"description.first_line() ++ " " ++ author.email() ++ " " ++ committer.email()"#,
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    @   test.user@example.com test.user@example.com
+    ◉  first foo@bar.com test.user@example.com
+    ◉
+    "###);
+
+    // --reset-author resets the author timestamp too, also when combined with
+    // --author
+    let get_signatures = |revset: &str| {
+        test_env.jj_cmd_success(
+            &repo_path,
+            &[
+                "log",
+                "--no-graph",
+                "-r",
+                revset,
+                "-T",
+                r#"author.email() ++ " " ++ author.timestamp() ++ " " ++ committer.timestamp() ++ "\n""#,
+            ],
+        )
+    };
+    insta::assert_snapshot!(get_signatures("@"), @r###"
+    test.user@example.com 2001-02-03 04:05:08.000 +07:00 2001-02-03 04:05:08.000 +07:00
+    "###);
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m", "second", "--reset-author"]);
+    insta::assert_snapshot!(get_signatures("@-"), @r###"
+    test.user@example.com 2001-02-03 04:05:11.000 +07:00 2001-02-03 04:05:11.000 +07:00
+    "###);
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "commit",
+            "-m",
+            "third",
+            "--reset-author",
+            "--author",
+            "Baz Qux <baz@qux.com>",
+        ],
+    );
+    insta::assert_snapshot!(get_signatures("@-"), @r###"
+    baz@qux.com 2001-02-03 04:05:13.000 +07:00 2001-02-03 04:05:13.000 +07:00
+    "###);
+}
+
 fn get_log_output(test_env: &TestEnvironment, cwd: &Path) -> String {
     let template = r#"commit_id.short() ++ " " ++ description"#;
     test_env.jj_cmd_success(cwd, &["log", "-T", template])
@@ -870,3 +870,175 @@ fn test_log_contained_in() {
     Hint: Did you mean "main"?
     "###);
 }
+
+#[test]
+fn test_log_ahead_behind() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.jj_cmd_ok(&repo_path, &["new", "-mA", "root()"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-mB"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "main"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-mC"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-mD", "description(A)"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-mE", "root()"]);
+
+    let template = r#"
+    separate(" ",
+      description.first_line(),
+      self.ahead_behind("main"),
+      self.ahead_behind("main").ahead(),
+      self.ahead_behind("main").behind(),
+    ) ++ "\n"
+    "#;
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-r::", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  E +1 -2 1 2
+    │ ◉  D +1 -1 1 1
+    │ │ ◉  C +1 -0 1 0
+    │ │ ◉  B +0 -0 0 0
+    │ ├─╯
+    │ ◉  A +0 -1 0 1
+    ├─╯
+    ◉  +0 -2 0 2
+    "###);
+
+    // Unrelated reference: both counts are reported
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "log",
+            "-r::",
+            "--ahead-behind=description(E)",
+            "-T",
+            r#"description"#,
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    @  +0 -0 E
+    │ ◉  +2 -1 D
+    │ │ ◉  +3 -1 C
+    │ │ ◉  +2 -1 B
+    │ ├─╯
+    │ ◉  +1 -1 A
+    ├─╯
+    ◉  +0 -1
+    "###);
+
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "log",
+            "-r::main",
+            "--no-graph",
+            "--ahead-behind=main",
+            "-T",
+            r#"description"#,
+            "--color=always",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    [38;5;2m+0[39m [38;5;1m-0[39m B
+    [38;5;2m+0[39m [38;5;1m-1[39m A
+    [38;5;2m+0[39m [38;5;1m-2[39m 
+    "###);
+
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &["log", "--ahead-behind=unknown", "-T", r#"description"#],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Revision "unknown" doesn't exist
+    "###);
+
+    // The reference must not be empty
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["log", "--ahead-behind=none()"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Revset "none()" didn't resolve to any revisions
+    "###);
+    let stderr =
+        test_env.jj_cmd_failure(&repo_path, &["log", "-T", r#"self.ahead_behind("none()")"#]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Failed to parse template: Reference revset is empty
+    Caused by:  --> 1:19
+      |
+    1 | self.ahead_behind("none()")
+      |                   ^------^
+      |
+      = Reference revset is empty
+    "###);
+
+    // Ancestors shared through a merge are counted once
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["new", "-mF", "description(C)", "description(D)"],
+    );
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "log",
+            "-r::",
+            "--ahead-behind=description(D)",
+            "-T",
+            r#"description"#,
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    @    +3 -0 F
+    ├─╮
+    │ ◉  +0 -0 D
+    ◉ │  +2 -1 C
+    ◉ │  +1 -1 B
+    ├─╯
+    ◉  +0 -1 A
+    │ ◉  +1 -2 E
+    ├─╯
+    ◉  +0 -2
+    "###);
+}
+
+#[test]
+fn test_log_diff_stat() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file1"), "foo\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m=left"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-m=right"]);
+    std::fs::write(repo_path.join("file2"), "bar\nbaz\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new", "description(left)", "@", "-m=merge"]);
+    std::fs::write(repo_path.join("file1"), "foo\nqux\n").unwrap();
+    std::fs::remove_file(repo_path.join("file2")).unwrap();
+    std::fs::write(repo_path.join("file3"), "").unwrap();
+
+    let template = r#"
+    separate(" ",
+      description.first_line(),
+      "[" ++ self.diff().files().join(", ") ++ "]",
+      self.diff_stat().total_files(),
+      self.diff_stat().total_added(),
+      self.diff_stat().total_removed(),
+    ) ++ "\n"
+    "#;
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-r::@", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @    merge [file1, file2, file3] 3 1 2
+    ├─╮
+    │ ◉  right [file2] 1 2 0
+    ◉ │  left [file1] 1 1 0
+    ├─╯
+    ◉  [] 0 0 0
+    "###);
+
+    let template = r#"description.first_line() ++ ": " ++ self.diff_stat() ++ "\n""#;
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-r::@", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @    merge: 3 files changed, 1 insertion(+), 2 deletions(-)
+    ├─╮
+    │ ◉  right: 1 file changed, 2 insertions(+), 0 deletions(-)
+    ◉ │  left: 1 file changed, 1 insertion(+), 0 deletions(-)
+    ├─╯
+    ◉  : 0 files changed, 0 insertions(+), 0 deletions(-)
+    "###);
+}
@@ -59,6 +59,81 @@ fn test_concurrent_operation_divergence() {
     "###);
 }
 
+#[test]
+fn test_op_log_since_divergence() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    let op_log_args = ["op", "log", "--since-divergence", "-T", "description"];
+
+    // A single head operation
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "message 1"]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &op_log_args);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    No divergence: there is a single head operation.
+    "###);
+
+    // Three concurrent operations diverging from the same base, one of which
+    // is followed by another operation
+    let base_op_id = test_env.jj_cmd_success(
+        &repo_path,
+        &["op", "log", "--no-graph", "-n1", "-T", "id.short()"],
+    );
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "message 2"]);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "message 3"]);
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["new", "-m", "message 4", "--at-op", &base_op_id],
+    );
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["describe", "-m", "message 5", "--at-op", &base_op_id],
+    );
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &op_log_args);
+    insta::assert_snapshot!(stdout, @r###"
+    ◉  describe commit f66aca3e2b28f94c799326b8ac8d01a8db26e01b
+    │ ◉  new empty commit
+    ├─╯
+    │ ◉  describe commit 955b5f33e8b20cab40be9685738c75b96e910195
+    │ ◉  new empty commit
+    ├─╯
+    ◉  describe commit 230dd059e1b059aefc0da06a2e5a7dbf22362f22
+    "###);
+    insta::assert_snapshot!(stderr, @r###"
+    3 concurrent head operations diverged after operation e31015019d90
+    "###);
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "op",
+            "log",
+            "--since-divergence",
+            "--no-graph",
+            "-T",
+            r#"description ++ "\n""#,
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    describe commit f66aca3e2b28f94c799326b8ac8d01a8db26e01b
+    new empty commit
+    describe commit 955b5f33e8b20cab40be9685738c75b96e910195
+    new empty commit
+    describe commit 230dd059e1b059aefc0da06a2e5a7dbf22362f22
+    "###);
+    insta::assert_snapshot!(stderr, @r###"
+    3 concurrent head operations diverged after operation e31015019d90
+    "###);
+
+    // Resolving the concurrent operations leaves a single head
+    test_env.jj_cmd_ok(&repo_path, &["status"]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &op_log_args);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    No divergence: there is a single head operation.
+    "###);
+}
+
 #[test]
 fn test_concurrent_operations_auto_rebase() {
     let test_env = TestEnvironment::default();
@@ -70,11 +145,8 @@ fn test_concurrent_operations_auto_rebase() {
     let stdout = test_env.jj_cmd_success(&repo_path, &["op", "log"]);
     insta::assert_snapshot!(stdout, @r###"
     @  66d1dd775c54 test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
-    │  describe commit 4e8f9d2be039994f589b4e57ac5e9488703e604d
-    │  args: jj describe -m initial
-    ◉  130d67859810 test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
-    │  snapshot working copy
-    │  args: jj describe -m initial
+    ╷  describe commit 4e8f9d2be039994f589b4e57ac5e9488703e604d
+    ╷  args: jj describe -m initial
     ◉  b51416386f26 test-username@host.example.com 2001-02-03 04:05:07.000 +07:00 - 2001-02-03 04:05:07.000 +07:00
     │  add workspace 'default'
     ◉  9a7d829846af test-username@host.example.com 2001-02-03 04:05:07.000 +07:00 - 2001-02-03 04:05:07.000 +07:00
@@ -156,7 +228,6 @@ fn test_concurrent_operations_wc_modified() {
     │ ◉  new empty commit
     ├─╯
     ◉  describe commit 506f4ec3c2c62befa15fabc34ca9d4e6d7bef254
-    ◉  snapshot working copy
     ◉  add workspace 'default'
     ◉  initialize repo
     ◉
@@ -182,7 +253,8 @@ fn test_concurrent_snapshot_wc_reloadable() {
     test_env.jj_cmd_ok(&repo_path, &["commit", "-m", "new child1"]);
 
     let template = r#"id ++ "\n" ++ description ++ "\n" ++ tags"#;
-    let op_log_stdout = test_env.jj_cmd_success(&repo_path, &["op", "log", "-T", template]);
+    let op_log_stdout =
+        test_env.jj_cmd_success(&repo_path, &["op", "log", "--all", "-T", template]);
     insta::assert_snapshot!(op_log_stdout, @r###"
     @  9f11958bcf79340028eeabf9b0381cd8d2ae2258d0097b8ce8bd24fe7138eca08d9eb113bb4722ebacd9b7a6fa017e3888f72907be7487f275823c8d21359eed
     │  commit 554d22b2c43c1c47e279430197363e8daabe2fd6
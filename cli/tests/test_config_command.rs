@@ -14,11 +14,12 @@
 
 use std::path::PathBuf;
 
+use indoc::indoc;
 use insta::assert_snapshot;
 use itertools::Itertools;
 use regex::Regex;
 
-use crate::common::TestEnvironment;
+use crate::common::{get_stderr_string, get_stdout_string, TestEnvironment};
 
 #[test]
 fn test_config_list_single() {
@@ -315,6 +316,75 @@ fn test_config_layer_override_default() {
     "###);
 }
 
+#[test]
+fn test_config_list_show_origin() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config(
+        r#"
+    merge-tools.vimdiff.program = "user"
+    merge-tools.vimdiff.merge-args = ["user-arg"]
+    "#,
+    );
+    std::fs::write(
+        repo_path.join(".jj/repo/config.toml"),
+        r#"merge-tools.vimdiff.program = "repo""#,
+    )
+    .unwrap();
+    test_env.add_env_var("EDITOR", "env-base");
+
+    // Defaults, including arrays, are annotated along with the overriding values
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "config",
+            "list",
+            "merge-tools.vimdiff",
+            "--include-defaults",
+            "--include-overridden",
+            "--show-origin",
+            "--config-toml=merge-tools.vimdiff.program='cli'",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    default: merge-tools.vimdiff.edit-args = ["-f", "-d", "$left", "$right"]
+    default: # merge-tools.vimdiff.merge-args = ["-f", "-d", "$output", "-M", "$left", "$base", "$right", "-c", "wincmd J", "-c", "set modifiable", "-c", "set write"]
+    default: merge-tools.vimdiff.merge-tool-edits-conflict-markers = true
+    default: # merge-tools.vimdiff.program = "vim"
+    user: merge-tools.vimdiff.merge-args = ["user-arg"]
+    user: # merge-tools.vimdiff.program = "user"
+    repo: # merge-tools.vimdiff.program = "repo"
+    cli: merge-tools.vimdiff.program = "cli"
+    "###);
+
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["config", "list", "ui.editor", "--show-origin"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    env: ui.editor = "env-base"
+    "###);
+
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "config",
+            "list",
+            "merge-tools.vimdiff.program",
+            "--include-defaults",
+            "--include-overridden",
+            "-T",
+            r#"source ++ " " ++ name ++ "\n""#,
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    default merge-tools.vimdiff.program
+    user merge-tools.vimdiff.program
+    repo merge-tools.vimdiff.program
+    "###);
+}
+
 #[test]
 fn test_config_layer_override_env() {
     let mut test_env = TestEnvironment::default();
@@ -597,8 +667,160 @@ fn test_config_set_nontable_parent() {
         &repo_path,
         &["config", "set", "--user", "test-nontable.foo", "test-val"],
     );
+    insta::assert_snapshot!(stderr, @"Error: Failed to set test-nontable.foo: test-nontable is not a table");
+}
+
+#[test]
+fn test_config_set_explicit_type() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let user_config_path = test_env.config_path().join("config.toml");
+    test_env.set_config_path(user_config_path.clone());
+    let repo_path = test_env.env_root().join("repo");
+
+    let set_value = |key, value_type, value| {
+        test_env.jj_cmd_success(
+            &repo_path,
+            &["config", "set", "--user", "--type", value_type, key, value],
+        );
+    };
+    set_value("test-table.auto", "auto", "42");
+    set_value("test-table.boolean", "bool", "false");
+    set_value("test-table.integer", "int", "-7");
+    set_value("test-table.string", "string", "42");
+    set_value("test-table.quoted", "string", r#""foo""#);
+    set_value("test-table.array", "json", r#"["one", 2, true]"#);
+    set_value("test-table.inline", "json", r#"{"x": 1.5}"#);
+    insta::assert_snapshot!(std::fs::read_to_string(&user_config_path).unwrap(), @r###"
+    [test-table]
+    auto = 42
+    boolean = false
+    integer = -7
+    string = "42"
+    quoted = "\"foo\""
+    array = ["one", 2, true]
+    inline = { x = 1.5 }
+    "###);
+
+    let set_value_failure = |key, value_type, value| {
+        test_env.jj_cmd_failure(
+            &repo_path,
+            &["config", "set", "--user", "--type", value_type, key, value],
+        )
+    };
+    insta::assert_snapshot!(set_value_failure("test-table.boolean", "bool", "yes"), @"Error: Invalid bool value: provided string was not `true` or `false`");
+    insta::assert_snapshot!(set_value_failure("test-table.integer", "int", "1.0"), @"Error: Invalid int value: invalid digit found in string");
+    insta::assert_snapshot!(set_value_failure("test-table.array", "json", "[null]"), @"Error: Invalid json value: null can't be represented in TOML");
+    insta::assert_snapshot!(set_value_failure("test-table.integer", "json", "18446744073709551615"), @"Error: Invalid json value: number 18446744073709551615 is out of range");
+}
+
+#[test]
+fn test_config_set_append() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let user_config_path = test_env.config_path().join("config.toml");
+    test_env.set_config_path(user_config_path.clone());
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(
+        &user_config_path,
+        indoc! {r#"
+            # Remotes to fetch from
+            [git]
+            fetch = ["origin"] # keep origin first
+            push = "origin"
+        "#},
+    )
+    .unwrap();
+
+    let append_args = |key, value| ["config", "set", "--user", "--append", key, value];
+    test_env.jj_cmd_ok(&repo_path, &append_args("git.fetch", "upstream"));
+    test_env.jj_cmd_ok(&repo_path, &append_args("ui.diff.tool", "difft"));
+    insta::assert_snapshot!(std::fs::read_to_string(&user_config_path).unwrap(), @r###"
+    # Remotes to fetch from
+    [git]
+    fetch = ["origin", "upstream"] # keep origin first
+    push = "origin"
+
+    [ui.diff]
+    tool = ["difft"]
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["config", "list", "git.fetch"]);
+    insta::assert_snapshot!(stdout, @r###"git.fetch = ["origin", "upstream"]"###);
+
+    let stderr = test_env.jj_cmd_failure(&repo_path, &append_args("git.push", "upstream"));
+    insta::assert_snapshot!(stderr, @"Error: Failed to append to git.push: existing value is not an array");
+}
+
+#[test]
+fn test_config_unset() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let user_config_path = test_env.config_path().join("config.toml");
+    test_env.set_config_path(user_config_path.clone());
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(
+        &user_config_path,
+        indoc! {r#"
+            # User settings
+            [user]
+            name = "Test User" # not a real person
+            email = "test.user@example.com"
+
+            [ui]
+            # Always page
+            paginate = "auto"
+        "#},
+    )
+    .unwrap();
+
+    test_env.jj_cmd_ok(&repo_path, &["config", "unset", "--user", "user.email"]);
+    test_env.jj_cmd_ok(&repo_path, &["config", "unset", "--user", "ui.paginate"]);
+    insta::assert_snapshot!(std::fs::read_to_string(&user_config_path).unwrap(), @r###"
+    # User settings
+    [user]
+    name = "Test User" # not a real person
+
+    [ui]
+    "###);
+
+    // Unsetting a missing key does nothing
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["config", "unset", "--user", "user.email"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: Key user.email doesn't exist; nothing to unset
+    "###);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["config", "unset", "--user", "nope.x"]);
+    insta::assert_snapshot!(stdout, @"");
     insta::assert_snapshot!(stderr, @r###"
-    Error: Failed to set test-nontable.foo: would overwrite non-table value with parent table
+    Warning: Key nope.x doesn't exist; nothing to unset
+    "###);
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["config", "unset", "--user", "user.name.x"]);
+    insta::assert_snapshot!(stderr, @"Error: Failed to unset user.name.x: user.name is not a table");
+
+    // Removing a table requires confirmation
+    let assert = test_env
+        .jj_cmd(&repo_path, &["config", "unset", "--user", "user"])
+        .assert()
+        .code(1);
+    insta::assert_snapshot!(test_env.normalize_output(&get_stdout_string(&assert)), @r###"
+    user is a table. Remove the entire table? (yN): N
+    "###);
+    insta::assert_snapshot!(test_env.normalize_output(&get_stderr_string(&assert)), @r###"
+    Error: Failed to unset user: would delete entire table
+    "###);
+    let assert = test_env
+        .jj_cmd_stdin(&repo_path, &["config", "unset", "--user", "user"], "n\n")
+        .assert()
+        .code(1);
+    insta::assert_snapshot!(test_env.normalize_output(&get_stdout_string(&assert)), @"user is a table. Remove the entire table? (yN): ");
+    let (stdout, stderr) =
+        test_env.jj_cmd_stdin_ok(&repo_path, &["config", "unset", "--user", "user"], "y\n");
+    insta::assert_snapshot!(stdout, @"user is a table. Remove the entire table? (yN): ");
+    insta::assert_snapshot!(stderr, @"");
+    insta::assert_snapshot!(std::fs::read_to_string(&user_config_path).unwrap(), @r###"
+
+    [ui]
     "###);
 }
 
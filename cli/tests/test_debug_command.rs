@@ -228,6 +228,48 @@ fn test_debug_tree() {
     );
 }
 
+#[test]
+fn test_debug_snapshot_paths() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let workspace_path = test_env.env_root().join("repo");
+    std::fs::create_dir(workspace_path.join("dir1")).unwrap();
+    std::fs::create_dir(workspace_path.join("dir2")).unwrap();
+    std::fs::write(workspace_path.join("dir1/file"), "1\n").unwrap();
+    std::fs::write(workspace_path.join("dir2/file"), "1\n").unwrap();
+    test_env.jj_cmd_ok(&workspace_path, &["debug", "snapshot"]);
+
+    std::fs::write(workspace_path.join("dir1/file"), "2\n").unwrap();
+    std::fs::remove_file(workspace_path.join("dir2/file")).unwrap();
+    std::fs::write(workspace_path.join("dir2/added"), "2\n").unwrap();
+
+    // Only the changes in dir1 are recorded
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&workspace_path, &["debug", "snapshot", "--paths", "dir1"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @"Warning: Only the specified paths were snapshotted. Changes to other paths will be recorded by the next snapshot.");
+    let stdout = test_env.jj_cmd_success(
+        &workspace_path,
+        &["diff", "--summary", "--ignore-working-copy"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    A dir1/file
+    A dir2/file
+    "###);
+    let stdout = test_env.jj_cmd_success(
+        &workspace_path,
+        &["file", "show", "dir1/file", "--ignore-working-copy"],
+    );
+    insta::assert_snapshot!(stdout, @"2");
+
+    // The next full snapshot records the rest
+    let stdout = test_env.jj_cmd_success(&workspace_path, &["diff", "--summary"]);
+    insta::assert_snapshot!(stdout, @r###"
+    A dir1/file
+    A dir2/added
+    "###);
+}
+
 #[test]
 fn test_debug_operation_id() {
     let test_env = TestEnvironment::default();
@@ -247,6 +247,156 @@ fn test_multiple_message_args() {
     "###);
 }
 
+#[test]
+fn test_describe_reuse_message() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    let edit_script = test_env.set_up_fake_editor();
+    // The editor fails if it's opened
+    std::fs::write(&edit_script, "fail").unwrap();
+
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m", "source message"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "other"]);
+
+    // Copy the description onto the working-copy commit without opening the
+    // editor
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["describe", "--reuse-message=@--"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Working copy now at: kkmpptxz a4f3db4d (empty) source message
+    Parent commit      : rlvkpnrz a2f2e5f8 (empty) (no description set)
+    "###);
+
+    // Copy it onto another commit
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["describe", "-r", "@-", "--reuse-message=@--"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 1 descendant commits
+    Working copy now at: kkmpptxz cebbe958 (empty) source message
+    Parent commit      : rlvkpnrz 8cb401d1 (empty) source message
+    "###);
+
+    // Open the editor with the description of another commit
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "other"]);
+    std::fs::write(&edit_script, "dump editor0").unwrap();
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["describe", "--reedit-message=@--"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Working copy now at: kkmpptxz a99eb64e (empty) source message
+    Parent commit      : rlvkpnrz 8cb401d1 (empty) source message
+    "###);
+    insta::assert_snapshot!(
+        std::fs::read_to_string(test_env.env_root().join("editor0")).unwrap(), @r###"
+    source message
+
+    JJ: Lines starting with "JJ: " (like this one) will be removed.
+    "###);
+
+    // Combining with -m is ambiguous
+    let stderr = test_env.jj_cmd_cli_error(
+        &repo_path,
+        &["describe", "--reuse-message=@--", "-m", "message"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--reuse-message <REVISION>' cannot be used with '--message <MESSAGE>'
+
+    Usage: jj describe --reuse-message <REVISION> [REVISIONS]...
+
+    For more information, try '--help'.
+    "###);
+}
+
+#[test]
+fn test_describe_no_edit() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    let edit_script = test_env.set_up_fake_editor();
+    // The editor fails if it's opened
+    std::fs::write(&edit_script, "fail").unwrap();
+
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m", "first"]);
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m", "second"]);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "third"]);
+    let get_log_output = || {
+        test_env.jj_cmd_success(
+            &repo_path,
+            &[
+                "log",
+                "-T",
+                r#"description.first_line() ++ " " ++ author.email()"#,
+            ],
+        )
+    };
+
+    // Keeping the description without other changes does nothing
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["describe", "--no-edit"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Nothing changed.
+    "###);
+
+    // Metadata of multiple revisions can be edited at once, keeping their
+    // descriptions
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "describe",
+            "-r",
+            "@- | @--",
+            "--no-edit",
+            "--author",
+            "Foo Bar <foo@bar.com>",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 1 descendant commits
+    Working copy now at: kkmpptxz 0bf3c002 (empty) third
+    Parent commit      : rlvkpnrz 2d82615f (empty) second
+    "###);
+    insta::assert_snapshot!(get_log_output(), @r###"
+    @  third test.user@example.com
+    ◉  second foo@bar.com
+    ◉  first foo@bar.com
+    ◉
+    "###);
+
+    // The description can be cleared explicitly
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["describe", "-r", "@--", "-m", ""]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 2 descendant commits
+    Working copy now at: kkmpptxz 5150ec79 (empty) third
+    Parent commit      : rlvkpnrz b9721b2c (empty) second
+    "###);
+    insta::assert_snapshot!(get_log_output(), @r###"
+    @  third test.user@example.com
+    ◉  second foo@bar.com
+    ◉   foo@bar.com
+    ◉
+    "###);
+
+    // The editor can't be opened for multiple revisions
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["describe", "@ | @-"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Cannot edit the descriptions of multiple revisions in an editor
+    Hint: Use --message, --stdin, or --reuse-message to set the description, or --no-edit to keep it
+    "###);
+
+    // Combining with -m is ambiguous
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["describe", "--no-edit", "-m", "message"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--no-edit' cannot be used with '--message <MESSAGE>'
+
+    Usage: jj describe --no-edit [REVISIONS]...
+
+    For more information, try '--help'.
+    "###);
+}
+
 #[test]
 fn test_describe_default_description() {
     let mut test_env = TestEnvironment::default();
@@ -322,4 +472,105 @@ fn test_describe_author() {
     │  Ove Ridder ove.ridder@example.com 2001-02-03 04:05:09.000 +07:00
     ~
     "###);
+
+    // Set an explicit author, keeping the author timestamp
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["describe", "--no-edit", "--author", "Foo Bar <foo@bar.com>"],
+    );
+    insta::assert_snapshot!(get_signatures(), @r###"
+    @  Foo Bar foo@bar.com 2001-02-03 04:05:09.000 +07:00
+    │  Test User test.user@example.com 2001-02-03 04:05:11.000 +07:00
+    ~
+    "###);
+
+    // The author timestamp is reset if combined with --reset-author
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "describe",
+            "--no-edit",
+            "--reset-author",
+            "--author",
+            "Baz Qux <baz@qux.com>",
+        ],
+    );
+    insta::assert_snapshot!(get_signatures(), @r###"
+    @  Baz Qux baz@qux.com 2001-02-03 04:05:13.000 +07:00
+    │  Test User test.user@example.com 2001-02-03 04:05:13.000 +07:00
+    ~
+    "###);
+
+    // Malformed authors are rejected
+    let stderr = test_env.jj_cmd_cli_error(
+        &repo_path,
+        &["describe", "--no-edit", "--author", "foo@bar.com"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    error: invalid value 'foo@bar.com' for '--author <AUTHOR>': expected an author in the form "Name <email>"
+
+    For more information, try '--help'.
+    "###);
+}
+
+#[test]
+fn test_describe_author_multiple_commits() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "a"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "b"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "c"]);
+    let get_log = || {
+        test_env.jj_cmd_success(
+            &repo_path,
+            &[
+                "log",
+                "-T",
+                r#"description.first_line() ++ " " ++ author.name() ++ " " ++ author.timestamp() ++ " " ++ committer.name()"#,
+            ],
+        )
+    };
+
+    // Set the author of a range of commits, keeping their author timestamps
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "describe",
+            "-r",
+            "description(a) | description(b)",
+            "--no-edit",
+            "--author",
+            "Foo Bar <foo@bar.com>",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 1 descendant commits
+    Working copy now at: zsuskuln f1e7ba0f (empty) c
+    Parent commit      : kkmpptxz fc559cb9 (empty) b
+    "###);
+    insta::assert_snapshot!(get_log(), @r###"
+    @  c Test User 2001-02-03 04:05:10.000 +07:00 Test User
+    ◉  b Foo Bar 2001-02-03 04:05:09.000 +07:00 Test User
+    ◉  a Foo Bar 2001-02-03 04:05:08.000 +07:00 Test User
+    ◉    1970-01-01 00:00:00.000 +00:00
+    "###);
+
+    // Editing the descriptions of several commits at once requires --no-edit
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &[
+            "describe",
+            "-r",
+            "::@ & ~root()",
+            "--author",
+            "Baz Qux <baz@qux.com>",
+        ],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Cannot edit the descriptions of multiple revisions in an editor
+    Hint: Use --message, --stdin, or --reuse-message to set the description, or --no-edit to keep it
+    "###);
 }
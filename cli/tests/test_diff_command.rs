@@ -14,7 +14,9 @@
 
 use itertools::Itertools;
 
-use crate::common::{escaped_fake_diff_editor_path, strip_last_line, TestEnvironment};
+use crate::common::{
+    escaped_fake_diff_editor_path, get_stderr_string, get_stdout_string, TestEnvironment,
+};
 
 #[test]
 fn test_diff_basic() {
@@ -136,7 +138,7 @@ fn test_diff_basic() {
     insta::assert_snapshot!(stdout, @r###"
     [1m<<diff file_header::diff --git a/>><<diff file_header::file1>><<diff file_header:: b/>><<diff file_header::file1>><<diff file_header::>>[0m
     [1m<<diff file_header::deleted file mode >><<diff file_header::100644>><<diff file_header::>>[0m
-    [1m<<diff file_header::index >><<diff file_header::257cc5642c>><<diff file_header::..0000000000>>[0m
+    [1m<<diff file_header::index >><<diff file_header::257cc5642c>><<diff file_header::..>><<diff file_header::0000000000>><<diff file_header::>>[0m
     [1m<<diff file_header::--- a/>><<diff file_header::file1>><<diff file_header::>>[0m
     [1m<<diff file_header::+++ /dev/null>>[0m
     [38;5;6m<<diff hunk_header::@@ ->><<diff hunk_header::1>><<diff hunk_header::,>><<diff hunk_header::1>><<diff hunk_header:: +>><<diff hunk_header::1>><<diff hunk_header::,>><<diff hunk_header::0>><<diff hunk_header:: @@>>[39m
@@ -152,7 +154,7 @@ fn test_diff_basic() {
     [38;5;2m<<diff added::+>><<diff added::baz quux>>[39m
     [1m<<diff file_header::diff --git a/>><<diff file_header::file3>><<diff file_header:: b/>><<diff file_header::file3>><<diff file_header::>>[0m
     [1m<<diff file_header::new file mode >><<diff file_header::100644>><<diff file_header::>>[0m
-    [1m<<diff file_header::index 0000000000..>><<diff file_header::257cc5642c>><<diff file_header::>>[0m
+    [1m<<diff file_header::index >><<diff file_header::0000000000>><<diff file_header::..>><<diff file_header::257cc5642c>><<diff file_header::>>[0m
     [1m<<diff file_header::--- /dev/null>>[0m
     [1m<<diff file_header::+++ b/>><<diff file_header::file3>><<diff file_header::>>[0m
     [38;5;6m<<diff hunk_header::@@ ->><<diff hunk_header::1>><<diff hunk_header::,>><<diff hunk_header::0>><<diff hunk_header:: +>><<diff hunk_header::1>><<diff hunk_header::,>><<diff hunk_header::1>><<diff hunk_header:: @@>>[39m
@@ -240,6 +242,90 @@ fn test_diff_basic() {
     insta::assert_snapshot!(stderr, @"");
 }
 
+#[test]
+fn test_diff_merge_base() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file1"), "base\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m=base"]);
+    std::fs::write(repo_path.join("file2"), "main\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "main"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "description(base)"]);
+    std::fs::write(repo_path.join("file1"), "feature\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m=feature 1"]);
+    std::fs::write(repo_path.join("file3"), "feature\n").unwrap();
+
+    // Changes on "main" aren't shown as reverted
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--merge-base=main", "-s"]);
+    insta::assert_snapshot!(stdout, @r###"
+    M file1
+    A file3
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--from=main", "-s"]);
+    insta::assert_snapshot!(stdout, @r###"
+    M file1
+    D file2
+    A file3
+    "###);
+
+    // Composes with --to, path filtering, and other formats
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["diff", "--merge-base=main", "--to=@-", "--git", "file1"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/file1 b/file1
+    index df967b96a5...a7453f0750 100644
+    --- a/file1
+    +++ b/file1
+    @@ -1,1 +1,1 @@
+    -base
+    +feature
+    "###);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["diff", "--merge-base=main", "file2"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: No matching entries for paths: file2
+    "###);
+
+    // Multiple merge bases (criss-cross) are merged
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-m=left"]);
+    std::fs::write(repo_path.join("left"), "left\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-m=right"]);
+    std::fs::write(repo_path.join("right"), "right\n").unwrap();
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "new",
+            "description(left)",
+            "description(right)",
+            "-m=merge 1",
+        ],
+    );
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "new",
+            "description(left)",
+            "description(right)",
+            "-m=merge 2",
+        ],
+    );
+    std::fs::write(repo_path.join("merge2"), "merge2\n").unwrap();
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["diff", "--merge-base=description('merge 1')", "-s"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    A merge2
+    "###);
+
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["diff", "--merge-base=main", "-r=@"]);
+    insta::assert_snapshot!(stderr.lines().next().unwrap(), @"error: the argument '--merge-base <REVISION>' cannot be used with '--revision <REVISION>'");
+}
+
 #[test]
 fn test_diff_empty() {
     let test_env = TestEnvironment::default();
@@ -268,6 +354,45 @@ fn test_diff_empty() {
     "###);
 }
 
+#[test]
+fn test_diff_color_setting() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config(r#"ui.diff.color = "always""#);
+
+    std::fs::write(repo_path.join("file1"), "foo\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    std::fs::write(repo_path.join("file1"), "bar\n").unwrap();
+
+    // Diff output is colorized even though stdout isn't a terminal
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff"]);
+    insta::assert_snapshot!(stdout, @r###"
+    [38;5;3mModified regular file file1:[39m
+    [38;5;1m   1[39m [38;5;2m   1[39m: [4m[38;5;1mfoo[38;5;2mbar[24m[39m
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["show", "-T", "description"]);
+    insta::assert_snapshot!(stdout, @r###"
+    [38;5;3mModified regular file file1:[39m
+    [38;5;1m   1[39m [38;5;2m   1[39m: [4m[38;5;1mfoo[38;5;2mbar[24m[39m
+    "###);
+
+    // The setting doesn't apply to other commands
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-r@", "-T", "commit_id"]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  309d365736ed8208085f712ce84f3ba0c46dfccf
+    │
+    ~
+    "###);
+
+    // The command-line flag takes precedence
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--color=never"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Modified regular file file1:
+       1    1: foobar
+    "###);
+}
+
 #[test]
 fn test_diff_types() {
     let test_env = TestEnvironment::default();
@@ -365,6 +490,97 @@ fn test_diff_name_only() {
     "###);
 }
 
+#[test]
+fn test_diff_name_status() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("deleted"), "d").unwrap();
+    std::fs::write(repo_path.join("modified"), "m").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    std::fs::remove_file(repo_path.join("deleted")).unwrap();
+    std::fs::write(repo_path.join("modified"), "mod").unwrap();
+    std::fs::write(repo_path.join("added with space"), "add").unwrap();
+
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--name-status"]);
+    insta::assert_debug_snapshot!(stdout, @r###"
+    "A\tadded with space\nD\tdeleted\nM\tmodified\n"
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["show", "--name-status", "--color=always"]);
+    insta::assert_snapshot!(stdout.lines().skip(7).join("\n"), @r###"
+    [38;5;2mA	added with space[39m
+    [38;5;1mD	deleted[39m
+    [38;5;6mM	modified[39m
+    "###);
+
+    // -z terminates the paths with NUL, and is never colorized
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["diff", "--name-status", "-z", "--color=always"],
+    );
+    insta::assert_debug_snapshot!(stdout, @r###"
+    "A\0added with space\0D\0deleted\0M\0modified\0"
+    "###);
+    let stdout =
+        test_env.jj_cmd_success(&repo_path, &["diff", "--name-only", "-z", "--color=always"]);
+    insta::assert_debug_snapshot!(stdout, @r###"
+    "added with space\0deleted\0modified\0"
+    "###);
+
+    // Path names are printed verbatim
+    if cfg!(unix) {
+        std::fs::write(repo_path.join("tab\t\"quote\""), "").unwrap();
+        let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--name-only", "-z"]);
+        insta::assert_debug_snapshot!(stdout, @r###"
+        "added with space\0deleted\0modified\0tab\t\"quote\"\0"
+        "###);
+    }
+
+    // Deleted and added files with similar contents are reported as renames
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    std::fs::write(repo_path.join("copied"), "a\nb\nc\nd\n").unwrap();
+    std::fs::write(repo_path.join("edited"), "1\n2\n3\n4\n").unwrap();
+    std::fs::write(repo_path.join("replaced"), "x\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    std::fs::rename(repo_path.join("copied"), repo_path.join("copied2")).unwrap();
+    std::fs::remove_file(repo_path.join("edited")).unwrap();
+    std::fs::write(repo_path.join("edited2"), "1\n2\n3\n5\n").unwrap();
+    std::fs::remove_file(repo_path.join("replaced")).unwrap();
+    std::fs::write(repo_path.join("replaced2"), "y\n").unwrap();
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--name-status"]);
+    insta::assert_snapshot!(stdout, @r###"
+    R100	copied	copied2
+    R075	edited	edited2
+    D	replaced
+    A	replaced2
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--name-status", "-z"]);
+    insta::assert_debug_snapshot!(stdout, @r###"
+    "R100\0copied\0copied2\0R075\0edited\0edited2\0D\0replaced\0A\0replaced2\0"
+    "###);
+    test_env.jj_cmd_ok(&repo_path, &["edit", "@--"]);
+
+    // -z requires a format listing path names
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["diff", "-z"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the following required arguments were not provided:
+      <--name-only|--name-status>
+
+    Usage: jj diff -z <--name-only|--name-status> [PATHS]...
+
+    For more information, try '--help'.
+    "###);
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["diff", "--name-only", "--name-status"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--name-only' cannot be used with '--name-status'
+
+    Usage: jj diff --name-only [PATHS]...
+
+    For more information, try '--help'.
+    "###);
+}
+
 #[test]
 fn test_diff_bad_args() {
     let test_env = TestEnvironment::default();
@@ -523,6 +739,185 @@ fn test_diff_relative_paths() {
     "###);
 }
 
+#[test]
+fn test_diff_relative_option() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::create_dir_all(repo_path.join("dir1").join("subdir1")).unwrap();
+    std::fs::create_dir(repo_path.join("dir2")).unwrap();
+    std::fs::write(repo_path.join("file1"), "foo1\n").unwrap();
+    std::fs::write(repo_path.join("dir1").join("file2"), "foo2\n").unwrap();
+    std::fs::write(repo_path.join("dir2").join("file4"), "foo4\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    std::fs::write(repo_path.join("file1"), "bar1\n").unwrap();
+    std::fs::write(repo_path.join("dir1").join("file2"), "bar2\n").unwrap();
+    std::fs::write(
+        repo_path.join("dir1").join("subdir1").join("file3"),
+        "bar3\n",
+    )
+    .unwrap();
+    std::fs::write(repo_path.join("dir2").join("file4"), "bar4\n").unwrap();
+
+    // Defaults to the current directory
+    let stdout = test_env.jj_cmd_success(&repo_path.join("dir1"), &["diff", "-s", "--relative"]);
+    #[cfg(unix)]
+    insta::assert_snapshot!(stdout, @r###"
+    M file2
+    A subdir1/file3
+    "###);
+    #[cfg(windows)]
+    insta::assert_snapshot!(stdout, @r###"
+    M file2
+    A subdir1\file3
+    "###);
+
+    // The Git headers are relative too
+    let stdout = test_env.jj_cmd_success(&repo_path.join("dir1"), &["diff", "--git", "--relative"]);
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/file2 b/file2
+    index 54b060eee9...1fe912cdd8 100644
+    --- a/file2
+    +++ b/file2
+    @@ -1,1 +1,1 @@
+    -foo2
+    +bar2
+    diff --git a/subdir1/file3 b/subdir1/file3
+    new file mode 100644
+    index 0000000000..f3c8b75ec6
+    --- /dev/null
+    +++ b/subdir1/file3
+    @@ -1,0 +1,1 @@
+    +bar3
+    "###);
+
+    // The directory is relative to the current directory
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "-s", "--relative=dir1/subdir1"]);
+    insta::assert_snapshot!(stdout, @r###"
+    A file3
+    "###);
+    let stdout = test_env.jj_cmd_success(
+        &repo_path.join("dir2"),
+        &["diff", "-s", "--relative=../dir1"],
+    );
+    #[cfg(unix)]
+    insta::assert_snapshot!(stdout, @r###"
+    M file2
+    A subdir1/file3
+    "###);
+    #[cfg(windows)]
+    insta::assert_snapshot!(stdout, @r###"
+    M file2
+    A subdir1\file3
+    "###);
+
+    // Combined with paths, files outside the directory are excluded
+    let stdout = test_env.jj_cmd_success(
+        &repo_path.join("dir1"),
+        &["diff", "-s", "--relative", "subdir1", "../file1"],
+    );
+    #[cfg(unix)]
+    insta::assert_snapshot!(stdout, @r###"
+    A subdir1/file3
+    "###);
+    #[cfg(windows)]
+    insta::assert_snapshot!(stdout, @r###"
+    A subdir1\file3
+    "###);
+}
+
+#[test]
+fn test_diff_merge_parents() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file"), "base\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m=left"]);
+    std::fs::write(repo_path.join("file"), "left\n").unwrap();
+    std::fs::write(repo_path.join("left-only"), "left\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new", "@-", "-m=right"]);
+    std::fs::write(repo_path.join("file"), "right\n").unwrap();
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["new", "description(left)", "description(right)", "-m=merge"],
+    );
+
+    // Compare the two parents of the merge commit
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "diff",
+            "--from=description(left)",
+            "--to=description(right)",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    Modified regular file file:
+       1    1: leftright
+    Removed regular file left-only:
+       1     : left
+    "###);
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "diff",
+            "--from=description(left)",
+            "--to=description(right)",
+            "-s",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    M file
+    D left-only
+    "###);
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "diff",
+            "--from=description(left)",
+            "--to=description(right)",
+            "--stat",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    file      | 2 +-
+    left-only | 1 -
+    2 files changed, 1 insertion(+), 2 deletions(-)
+    "###);
+
+    // The conflicted merge is materialized with conflict markers
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["diff", "--from=description(left)", "--to=@", "--git"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/file b/file
+    index 45cf141ba6...0000000000 100644
+    --- a/file
+    +++ b/file
+    @@ -1,1 +1,7 @@
+    -left
+    +<<<<<<< Conflict 1 of 1
+    +%%%%%%% Changes from base to side #1
+    +-base
+    ++left
+    ++++++++ Contents of side #2
+    +right
+    +>>>>>>> Conflict 1 of 1 ends
+    "###);
+
+    // Selecting both parents at once is an error
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["diff", "--from=@-", "--to=@"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Revset "@-" resolved to more than one revision
+    Hint: The revset "@-" resolved to these revisions:
+      kkmpptxz 10105034 right
+      rlvkpnrz b60359b7 left
+    "###);
+}
+
 #[test]
 fn test_diff_missing_newline() {
     let test_env = TestEnvironment::default();
@@ -976,10 +1371,16 @@ fn test_diff_external_tool() {
 
     // --tool=:builtin shouldn't be ignored
     let stderr = test_env.jj_cmd_failure(&repo_path, &["diff", "--tool=:builtin"]);
-    insta::assert_snapshot!(strip_last_line(&stderr), @r###"
+    // The OS error message varies by platform
+    let stderr = stderr
+        .lines()
+        .filter(|line| !line.starts_with("2: "))
+        .join("\n");
+    insta::assert_snapshot!(stderr, @r###"
     Error: Failed to generate diff
     Caused by:
     1: Error executing ':builtin' (run with --debug to see the exact invocation)
+    Hint: Configure the tool in `merge-tools.<name>`, or use one of the configured tools: diffedit3, diffedit3-ssh, fake-diff-editor, kdiff3, meld, meld-3, vimdiff, vscode
     "###);
 }
 
@@ -1026,6 +1427,120 @@ fn test_diff_external_tool_symlink() {
     );
 }
 
+#[test]
+fn test_diff_algorithm() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file"), "a\na\na\nunique\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    std::fs::write(repo_path.join("file"), "unique\na\na\na\n").unwrap();
+
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--git"]);
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/file b/file
+    index d8c6b12dc2...121a4b4dee 100644
+    --- a/file
+    +++ b/file
+    @@ -1,4 +1,4 @@
+    -a
+    -a
+    -a
+     unique
+    +a
+    +a
+    +a
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--git", "--algorithm=myers"]);
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/file b/file
+    index d8c6b12dc2...121a4b4dee 100644
+    --- a/file
+    +++ b/file
+    @@ -1,4 +1,4 @@
+    +unique
+     a
+     a
+     a
+    -unique
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--algorithm=myers"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Modified regular file file:
+            1: unique
+       1    2: a
+       2    3: a
+       3    4: a
+       4     : unique
+    "###);
+
+    // The setting is used by default, and by `jj show`
+    test_env.add_config(r#"diff.algorithm = "myers""#);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["show", "--git", "-T", "''"]);
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/file b/file
+    index d8c6b12dc2...121a4b4dee 100644
+    --- a/file
+    +++ b/file
+    @@ -1,4 +1,4 @@
+    +unique
+     a
+     a
+     a
+    -unique
+    "###);
+    // The flag overrides the setting
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["diff", "--git", "--algorithm=histogram", "--context=0"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/file b/file
+    index d8c6b12dc2...121a4b4dee 100644
+    --- a/file
+    +++ b/file
+    @@ -1,3 +1,0 @@
+    -a
+    -a
+    -a
+    @@ -5,0 +2,3 @@
+    +a
+    +a
+    +a
+    "###);
+
+    // Unknown algorithms are rejected
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["diff", "--algorithm=patience"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: invalid value 'patience' for '--algorithm <ALGORITHM>'
+      [possible values: histogram, myers]
+
+    For more information, try '--help'.
+    "###);
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &["diff", "--config-toml=diff.algorithm='patience'"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Config error: invalid diff algorithm: patience (possible values: histogram, myers)
+    For help, see https://github.com/martinvonz/jj/blob/main/docs/config.md.
+    "###);
+
+    // The algorithm isn't resolved for formats that don't diff the contents
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "diff",
+            "--summary",
+            "--config-toml=diff.algorithm='patience'",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    M file
+    "###);
+}
+
 #[test]
 fn test_diff_stat() {
     let test_env = TestEnvironment::default();
@@ -1055,6 +1570,48 @@ fn test_diff_stat() {
     "###);
 }
 
+#[test]
+fn test_diff_stat_width() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file1"), "foo\n".repeat(100)).unwrap();
+    std::fs::write(repo_path.join("file2"), "foo\n".repeat(10)).unwrap();
+
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--stat"]);
+    insta::assert_snapshot!(stdout, @r###"
+    file1 | 100 +++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
+    file2 |  10 +++++++
+    2 files changed, 110 insertions(+), 0 deletions(-)
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--stat", "--stat-width=30"]);
+    insta::assert_snapshot!(stdout, @r###"
+    file1 | 100 +++++++++++++++
+    file2 |  10 ++
+    2 files changed, 110 insertions(+), 0 deletions(-)
+    "###);
+
+    // A configured stat format uses the terminal width
+    let stdout =
+        test_env.jj_cmd_success(&repo_path, &["diff", "--config-toml=ui.diff.format='stat'"]);
+    insta::assert_snapshot!(stdout, @r###"
+    file1 | 100 +++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++
+    file2 |  10 +++++++
+    2 files changed, 110 insertions(+), 0 deletions(-)
+    "###);
+
+    // The width requires --stat
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["diff", "--stat-width=20"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the following required arguments were not provided:
+      --stat
+
+    Usage: jj diff --stat --stat-width <COLS> [PATHS]...
+
+    For more information, try '--help'.
+    "###);
+}
+
 #[test]
 fn test_diff_stat_long_name_or_stat() {
     let mut test_env = TestEnvironment::default();
@@ -1204,4 +1761,295 @@ fn test_diff_binary() {
     file4.png | 1 +
     4 files changed, 6 insertions(+), 6 deletions(-)
     "###);
+
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--binary"]);
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/file1.png b/file1.png
+    deleted file mode 100644
+    index 2b65b23c22953db3bdeca4a1ec6fc5c5d7c53cb2..0000000000000000000000000000000000000000
+    GIT binary patch
+    literal 0
+    Hc$@<O00001
+
+    literal 16
+    Xc%17D@N?(olHy8CN=`{lOJ@K8B_{-C
+
+    diff --git a/file2.png b/file2.png
+    index 7f036ce788241b5ff8adf8c1721e64b9598e3963..3bd1f0e29744a1f32b08d5650e62e2e62afb177c 100644
+    GIT binary patch
+    literal 8
+    Pc$`bi&*w@?EaCzH45k8(
+
+    literal 16
+    Xc%17D@N?(olHxKjG%_|ZHDdq(9&`f_
+
+    diff --git a/file3.png b/file3.png
+    new file mode 100644
+    index 0000000000000000000000000000000000000000..deacfbc28605a89087e163297cac2870fb46222b
+    GIT binary patch
+    literal 12
+    Tc%17D@N?(olH#hUtYQEF6)po6
+
+    literal 0
+    Hc$@<O00001
+
+    diff --git a/file4.png b/file4.png
+    new file mode 100644
+    index 0000000000000000000000000000000000000000..4227ca4e8736af63036e7457e2db376ddf7e5795
+    GIT binary patch
+    literal 3
+    Kc${NkU;qFB0{{U4
+
+    literal 0
+    Hc$@<O00001
+    "###);
+}
+
+#[test]
+fn test_diff_binary_mixed_with_text() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("binary"), b"\0\x01\x02").unwrap();
+    std::fs::write(repo_path.join("text"), "foo\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    std::fs::write(repo_path.join("binary"), b"\0\x03").unwrap();
+    std::fs::write(repo_path.join("text"), "bar\n").unwrap();
+
+    // Text files are shown as usual, and only the binary files get the full
+    // object ids in the index line
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--binary"]);
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/binary b/binary
+    index 8352675d67aed6625ece79af41c27fdb4ee2e867..a903574af00b573ad9bdb2bccf8d93ed00c675de 100644
+    GIT binary patch
+    literal 2
+    Jc${Nk1^@sB00aO4
+
+    literal 3
+    Kc${NkWC8#H2LJ>B
+
+    diff --git a/text b/text
+    index 257cc5642c...5716ca5987 100644
+    --- a/text
+    +++ b/text
+    @@ -1,1 +1,1 @@
+    -foo
+    +bar
+    "###);
+}
+
+#[test]
+fn test_diff_binary_mode_only() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("binary"), b"\0\x01\x02").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    test_env.jj_cmd_ok(&repo_path, &["file", "chmod", "x", "binary"]);
+
+    // The contents are unchanged, so only the mode lines are shown
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--binary"]);
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/binary b/binary
+    old mode 100644
+    new mode 100755
+    "###);
+}
+
+#[test]
+fn test_diff_binary_too_large() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config("snapshot.max-new-file-size = \"100MiB\"");
+
+    std::fs::write(repo_path.join("large"), vec![0; (64 << 20) + 1]).unwrap();
+
+    // Nothing is written for the file if it's too large
+    let assert = test_env
+        .jj_cmd(&repo_path, &["diff", "--binary"])
+        .assert()
+        .code(1);
+    let stdout = test_env.normalize_output(&get_stdout_string(&assert));
+    let stderr = test_env.normalize_output(&get_stderr_string(&assert));
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Binary file large is too large to include in the diff (67108865 bytes, limit is 67108864 bytes)
+    "###);
+}
+
+#[test]
+fn test_diff_path_pair() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file1"), "foo\nbar\n").unwrap();
+    std::fs::write(repo_path.join("file2"), "foo\nbaz\n").unwrap();
+    std::fs::create_dir(repo_path.join("dir")).unwrap();
+    std::fs::write(repo_path.join("dir").join("file3"), "foo\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["diff", "-r=@-", "--path-pair", "file1", "file2"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    Modified regular file file2:
+       1    1: foo
+       2    2: barbaz
+    "###);
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["diff", "-r=@-", "--git", "--path-pair", "file1", "file2"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/file2 b/file2
+    index 3bd1f0e297...0c071e1d07 100644
+    --- a/file2
+    +++ b/file2
+    @@ -1,2 +1,2 @@
+     foo
+    -bar
+    +baz
+    "###);
+
+    // A missing file is shown as added or removed
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["diff", "-r=@-", "--git", "--path-pair", "file1", "missing"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/file1 b/file1
+    deleted file mode 100644
+    index 3bd1f0e297..0000000000
+    --- a/file1
+    +++ /dev/null
+    @@ -1,2 +1,0 @@
+    -foo
+    -bar
+    "###);
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "diff",
+            "-r=@-",
+            "--summary",
+            "--path-pair",
+            "missing",
+            "file1",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @"A file1");
+
+    // The paths are resolved in the given revision
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &["diff", "-r=root()", "--path-pair", "file1", "file2"],
+    );
+    insta::assert_snapshot!(stderr, @"Error: Neither file1 nor file2 exists in this revision");
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["diff", "--path-pair", "file1", "dir"]);
+    insta::assert_snapshot!(stderr, @"Error: dir is a directory");
+
+    // External diff tools can only compare trees
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &[
+            "diff",
+            "-r=@-",
+            "--tool=false",
+            "--path-pair",
+            "file1",
+            "file2",
+        ],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: External diff tools can't compare individual files
+    "###);
+}
+
+#[test]
+fn test_diff_check() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file1"), "foo \nbar\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    // Only added lines are checked
+    std::fs::write(repo_path.join("file1"), "foo \nbar\t\nbaz\n").unwrap();
+    std::fs::write(repo_path.join("file2"), " \tindented\n\tok\n").unwrap();
+    std::fs::write(repo_path.join("binary"), b"\0 \n").unwrap();
+    std::fs::write(repo_path.join("clean"), "clean\n").unwrap();
+
+    let assert = test_env
+        .jj_cmd(&repo_path, &["diff", "--check"])
+        .assert()
+        .code(1);
+    let stdout = test_env
+        .normalize_output(&get_stdout_string(&assert))
+        .replace('\t', "<TAB>");
+    let stderr = test_env.normalize_output(&get_stderr_string(&assert));
+    insta::assert_snapshot!(stdout, @r###"
+    file1:2: trailing whitespace
+    +bar<TAB>
+    file2:1: space before tab in indent
+    + <TAB>indented
+    "###);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Found whitespace problems in 2 lines
+    "###);
+
+    // Can be restricted to some paths
+    let assert = test_env
+        .jj_cmd(&repo_path, &["diff", "--check", "file2"])
+        .assert()
+        .code(1);
+    let stdout = test_env
+        .normalize_output(&get_stdout_string(&assert))
+        .replace('\t', "<TAB>");
+    insta::assert_snapshot!(stdout, @r###"
+    file2:1: space before tab in indent
+    + <TAB>indented
+    "###);
+    let (stdout, _stderr) = test_env.jj_cmd_ok(&repo_path, &["diff", "--check", "clean"]);
+    insta::assert_snapshot!(stdout, @"");
+
+    // The checks are configurable
+    let assert = test_env
+        .jj_cmd(
+            &repo_path,
+            &[
+                "diff",
+                "--check",
+                "file2",
+                "--config-toml=diff.whitespace = ['tab-in-indent']",
+            ],
+        )
+        .assert()
+        .code(1);
+    let stdout = test_env
+        .normalize_output(&get_stdout_string(&assert))
+        .replace('\t', "<TAB>");
+    insta::assert_snapshot!(stdout, @r###"
+    file2:1: tab in indent
+    + <TAB>indented
+    file2:2: tab in indent
+    +<TAB>ok
+    "###);
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &[
+            "diff",
+            "--check",
+            "--config-toml=diff.whitespace = ['blank']",
+        ],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Config error: Invalid whitespace check in diff.whitespace: blank
+    For help, see https://github.com/martinvonz/jj/blob/main/docs/config.md.
+    "###);
 }
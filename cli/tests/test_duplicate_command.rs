@@ -250,6 +250,84 @@ fn test_duplicate_many() {
     "###);
 }
 
+#[test]
+fn test_duplicate_reuse_change_ids() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    create_commit(&test_env, &repo_path, "a", &[]);
+    create_commit(&test_env, &repo_path, "b", &["a"]);
+
+    // The duplicates get new change ids by default
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["duplicate", "b", "--new-change-ids"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @"Duplicated 1394f625cbbd as royxmykx 6c47909e b");
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log"]);
+    insta::assert_snapshot!(stdout, @r###"
+    ◉  royxmykx test.user@example.com 2001-02-03 08:05:12 6c47909e
+    │  b
+    │ @  zsuskuln test.user@example.com 2001-02-03 08:05:11 b 1394f625
+    ├─╯  b
+    ◉  rlvkpnrz test.user@example.com 2001-02-03 08:05:09 a 2443ea76
+    │  a
+    ◉  zzzzzzzz root() 00000000
+    "###);
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+
+    // The duplicates can keep the change ids of the originals
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["duplicate", "a::b", "--reuse-change-ids"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Duplicated 2443ea76b0b1 as rlvkpnrz?? 3d341b2f a
+    Duplicated 1394f625cbbd as zsuskuln?? f0544346 b
+    The duplicates share change ids with the originals, so 2 changes are now divergent.
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log"]);
+    insta::assert_snapshot!(stdout, @r###"
+    ◉  zsuskuln?? test.user@example.com 2001-02-03 08:05:15 f0544346
+    │  b
+    ◉  rlvkpnrz?? test.user@example.com 2001-02-03 08:05:15 3d341b2f
+    │  a
+    │ @  zsuskuln?? test.user@example.com 2001-02-03 08:05:11 b 1394f625
+    │ │  b
+    │ ◉  rlvkpnrz?? test.user@example.com 2001-02-03 08:05:09 a 2443ea76
+    ├─╯  a
+    ◉  zzzzzzzz root() 00000000
+    "###);
+
+    // Abandoning one of the copies clears the divergence
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["abandon", "description(a) & ~a"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Abandoned commit rlvkpnrz?? 3d341b2f a
+    Rebased 1 descendant commits onto parents of abandoned commits
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log"]);
+    insta::assert_snapshot!(stdout, @r###"
+    ◉  zsuskuln?? test.user@example.com 2001-02-03 08:05:17 99d01236
+    │  b
+    │ @  zsuskuln?? test.user@example.com 2001-02-03 08:05:11 b 1394f625
+    │ │  b
+    │ ◉  rlvkpnrz test.user@example.com 2001-02-03 08:05:09 a 2443ea76
+    ├─╯  a
+    ◉  zzzzzzzz root() 00000000
+    "###);
+
+    let stderr = test_env.jj_cmd_cli_error(
+        &repo_path,
+        &["duplicate", "b", "--reuse-change-ids", "--new-change-ids"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--reuse-change-ids' cannot be used with '--new-change-ids'
+
+    Usage: jj duplicate --reuse-change-ids <REVISIONS>...
+
+    For more information, try '--help'.
+    "###);
+}
+
 // https://github.com/martinvonz/jj/issues/1050
 #[test]
 fn test_undo_after_duplicate() {
@@ -0,0 +1,187 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_file_list() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file1"), "a\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    std::fs::write(repo_path.join("file1"), "b\n").unwrap();
+    std::fs::create_dir(repo_path.join("dir")).unwrap();
+    std::fs::write(repo_path.join("dir").join("file2"), "c\n").unwrap();
+
+    // Lists the files in the working copy by default
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "list"]);
+    insta::assert_snapshot!(stdout.replace('\\', "/"), @r###"
+    dir/file2
+    file1
+    "###);
+
+    // Can list the files in another revision
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "list", "-r@-"]);
+    insta::assert_snapshot!(stdout, @"file1");
+
+    // Filesets scope the listing
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "list", "dir"]);
+    insta::assert_snapshot!(stdout.replace('\\', "/"), @"dir/file2");
+}
+
+#[test]
+fn test_file_list_template() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file"), "base\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "base"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "base", "-m", "a"]);
+    std::fs::write(repo_path.join("file"), "a\n").unwrap();
+    std::fs::write(repo_path.join("executable"), "12345\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["file", "chmod", "x", "executable"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "a"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "base", "-m", "b"]);
+    std::fs::write(repo_path.join("file"), "b\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "b"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "a", "b"]);
+
+    let template = r#"separate(" ", path, size, executable, symlink, conflicted) ++ "\n""#;
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "list", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    executable 6 true false false
+    file 0 false false true
+    "###);
+
+    // Conflicted entries can be filtered out
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["file", "list", "-T", r#"if(conflicted, "", path ++ "\n")"#],
+    );
+    insta::assert_snapshot!(stdout, @"executable");
+
+    // The default template can be configured
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "file",
+            "list",
+            "-rb",
+            "--config-toml",
+            r#"templates.file_list = 'path ++ "\0"'"#,
+        ],
+    );
+    insta::assert_debug_snapshot!(stdout, @r###"
+    "file\0"
+    "###);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_file_list_symlink() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file"), "a\n").unwrap();
+    std::os::unix::fs::symlink("file", repo_path.join("link")).unwrap();
+
+    let template = r#"separate(" ", path, size, executable, symlink, conflicted) ++ "\n""#;
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "list", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    file 2 false false false
+    link 4 false true false
+    "###);
+}
+
+#[test]
+fn test_file_list_ignored_untracked() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join(".gitignore"), "*.log\ntarget/\n").unwrap();
+    std::fs::write(repo_path.join("file1"), "a\n").unwrap();
+    std::fs::write(repo_path.join("file1.log"), "log\n").unwrap();
+    std::fs::create_dir_all(repo_path.join("target").join("debug")).unwrap();
+    std::fs::write(repo_path.join("target").join("debug").join("out"), "").unwrap();
+    std::fs::create_dir(repo_path.join("dir")).unwrap();
+    std::fs::write(repo_path.join("dir").join("file2.log"), "log\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["status"]);
+
+    // Files created without snapshotting are untracked
+    std::fs::write(repo_path.join("new"), "new\n").unwrap();
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["file", "list", "--untracked", "--ignore-working-copy"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    new
+    "###);
+
+    // Files in ignored directories are listed, too
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "list", "--ignored"]);
+    insta::assert_snapshot!(stdout.replace('\\', "/"), @r###"
+    dir/file2.log
+    file1.log
+    target/debug/out
+    "###);
+
+    // The next snapshot tracks the new file
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "list", "--untracked"]);
+    insta::assert_snapshot!(stdout, @"");
+
+    // Filesets scope the listing
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "list", "--ignored", "dir"]);
+    insta::assert_snapshot!(stdout.replace('\\', "/"), @r###"
+    dir/file2.log
+    "###);
+
+    // Tracked files matching the ignore patterns aren't listed
+    test_env.jj_cmd_ok(&repo_path, &["file", "list"]);
+    std::fs::write(repo_path.join(".gitignore"), "*.log\ntarget/\nfile1\n").unwrap();
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "list", "--ignored", "file1"]);
+    insta::assert_snapshot!(stdout, @"");
+
+    // Files outside the sparse patterns aren't listed
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "sparse",
+            "set",
+            "--clear",
+            "--add",
+            ".gitignore",
+            "--add",
+            "dir",
+        ],
+    );
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "list", "--ignored"]);
+    insta::assert_snapshot!(stdout.replace('\\', "/"), @r###"
+    dir/file2.log
+    "###);
+
+    // Can't be combined with a revision
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["file", "list", "--ignored", "-r@-"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--ignored' cannot be used with '--revision <REVISION>'
+
+    Usage: jj file list --ignored [PATHS]...
+
+    For more information, try '--help'.
+    "###);
+}
@@ -308,6 +308,43 @@ fn test_git_clone_colocate() {
     "###);
 }
 
+#[test]
+fn test_git_clone_colocate_default_branch() {
+    let test_env = TestEnvironment::default();
+    let git_repo_path = test_env.env_root().join("source");
+    let git_repo = git2::Repository::init(git_repo_path).unwrap();
+    set_up_non_empty_git_repo(&git_repo);
+
+    // The default branch is tracked even if auto-local-branch is off
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        test_env.env_root(),
+        &["git", "clone", "source", "clone", "--colocate"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Fetching into new repo in "$TEST_ENV/clone"
+    branch: main@origin [new] untracked
+    Working copy now at: sqpuoqvx cad212e1 (empty) (no description set)
+    Parent commit      : mzyxwzks 9f01a0e0 main | message
+    Added 1 files, modified 0 files, removed 0 files
+    "###);
+    let clone_path = test_env.env_root().join("clone");
+    insta::assert_snapshot!(get_branch_output(&test_env, &clone_path), @r###"
+    main: mzyxwzks 9f01a0e0 message
+      @git: mzyxwzks 9f01a0e0 message
+      @origin: mzyxwzks 9f01a0e0 message
+    "###);
+
+    // The Git repo agrees with the checked-out working copy
+    let jj_git_repo = git2::Repository::open(&clone_path).unwrap();
+    let main_id = jj_git_repo
+        .find_reference("refs/heads/main")
+        .unwrap()
+        .target()
+        .unwrap();
+    assert_eq!(jj_git_repo.head().unwrap().target(), Some(main_id));
+    assert!(jj_git_repo.head_detached().unwrap());
+}
+
 #[test]
 fn test_git_clone_remote_default_branch() {
     let test_env = TestEnvironment::default();
@@ -226,6 +226,65 @@ fn test_git_fetch_multiple_remotes_from_config() {
     "###);
 }
 
+#[test]
+fn test_git_fetch_refetch() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("git.auto-local-branch = true");
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    add_git_remote(&test_env, &repo_path, "rem1");
+
+    test_env.jj_cmd_ok(&repo_path, &["git", "fetch"]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["git", "fetch", "--refetch"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Hint: Fetching from the only existing remote: rem1
+    Re-imported 1 unchanged refs from rem1
+    Nothing changed.
+    "###);
+    // The tracked local branch isn't conflicted since the target is unchanged
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    rem1: qxosxrvv 6a211027 message
+      @rem1: qxosxrvv 6a211027 message
+    "###);
+}
+
+#[test]
+fn test_git_fetch_refetch_recovers_untracked_branch() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    add_git_remote(&test_env, &repo_path, "rem1");
+
+    // The branch was imported without a local branch
+    test_env.jj_cmd_ok(&repo_path, &["git", "fetch"]);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    rem1@rem1: qxosxrvv 6a211027 message
+    "###);
+
+    // A plain fetch doesn't apply the new setting to the unchanged branch
+    test_env.add_config("git.auto-local-branch = true");
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["git", "fetch"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Hint: Fetching from the only existing remote: rem1
+    Nothing changed.
+    "###);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    rem1@rem1: qxosxrvv 6a211027 message
+    "###);
+
+    // The reset remote-tracking branch is imported as if it were new
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["git", "fetch", "--refetch"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Hint: Fetching from the only existing remote: rem1
+    Re-imported 1 unchanged refs from rem1
+    "###);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    rem1: qxosxrvv 6a211027 message
+      @rem1: qxosxrvv 6a211027 message
+    "###);
+}
+
 #[test]
 fn test_git_fetch_nonexistent_remote() {
     let test_env = TestEnvironment::default();
@@ -233,16 +292,31 @@ fn test_git_fetch_nonexistent_remote() {
     let repo_path = test_env.env_root().join("repo");
     add_git_remote(&test_env, &repo_path, "rem1");
 
-    let stderr = &test_env.jj_cmd_failure(
+    let (stdout, stderr) = test_env.jj_cmd_ok(
         &repo_path,
         &["git", "fetch", "--remote", "rem1", "--remote", "rem2"],
     );
+    insta::assert_snapshot!(stdout, @"");
     insta::assert_snapshot!(stderr, @r###"
     branch: rem1@rem1 [new] untracked
-    Error: No git remote named 'rem2'
+    Warning: Failed to fetch from rem2: No git remote named 'rem2'
+    Fetched from rem1
+    "###);
+    // The other remote should still have been fetched
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    rem1@rem1: qxosxrvv 6a211027 message
+    "###);
+
+    // Fails if no remote could be fetched
+    let stderr = &test_env.jj_cmd_failure(
+        &repo_path,
+        &["git", "fetch", "--remote", "rem2", "--remote", "rem3"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: Failed to fetch from rem2: No git remote named 'rem2'
+    Failed to fetch from rem3: No git remote named 'rem3'
+    Error: Failed to fetch from all remotes
     "###);
-    // No remote should have been fetched as part of the failing transaction
-    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @"");
 }
 
 #[test]
@@ -253,13 +327,98 @@ fn test_git_fetch_nonexistent_remote_from_config() {
     add_git_remote(&test_env, &repo_path, "rem1");
     test_env.add_config(r#"git.fetch = ["rem1", "rem2"]"#);
 
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["git", "fetch"]);
+    insta::assert_snapshot!(stderr, @r###"
+    branch: rem1@rem1 [new] untracked
+    Warning: Failed to fetch from rem2: No git remote named 'rem2'
+    Fetched from rem1
+    "###);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    rem1@rem1: qxosxrvv 6a211027 message
+    "###);
+}
+
+#[test]
+fn test_git_fetch_partial_failure_is_error() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    add_git_remote(&test_env, &repo_path, "rem1");
+    test_env.add_config(r#"git.fetch = ["rem1", "rem2"]"#);
+    test_env.add_config("git.fetch-partial-failure-is-error = true");
+
     let stderr = &test_env.jj_cmd_failure(&repo_path, &["git", "fetch"]);
     insta::assert_snapshot!(stderr, @r###"
     branch: rem1@rem1 [new] untracked
-    Error: No git remote named 'rem2'
+    Warning: Failed to fetch from rem2: No git remote named 'rem2'
+    Fetched from rem1
+    Error: Failed to fetch from 1 of 2 remotes
+    "###);
+    // The fetched remote is still recorded
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    rem1@rem1: qxosxrvv 6a211027 message
+    "###);
+
+    // The partial failure has its own error code
+    test_env.add_config(r#"ui.error-format = "json""#);
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["git", "fetch"]);
+    insta::assert_snapshot!(stderr.lines().last().unwrap(), @r###"
+    {"causes":[],"code":"partial-fetch","hints":[],"message":"Failed to fetch from 1 of 2 remotes"}
+    "###);
+}
+
+#[test]
+fn test_git_fetch_concurrency() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("git.auto-local-branch = true");
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    for remote in ["rem1", "rem2", "rem3", "rem4", "rem5"] {
+        add_git_remote(&test_env, &repo_path, remote);
+    }
+
+    // The result doesn't depend on the order the fetches finish in
+    test_env.add_config("git.fetch-concurrency = 2");
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["git", "fetch", "--all-remotes"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    branch: rem1@rem1 [new] tracked
+    branch: rem2@rem2 [new] tracked
+    branch: rem3@rem3 [new] tracked
+    branch: rem4@rem4 [new] tracked
+    branch: rem5@rem5 [new] tracked
+    "###);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    rem1: qxosxrvv 6a211027 message
+      @rem1: qxosxrvv 6a211027 message
+    rem2: yszkquru 2497a8a0 message
+      @rem2: yszkquru 2497a8a0 message
+    rem3: lvsrtwwm 4ffdff2b message
+      @rem3: lvsrtwwm 4ffdff2b message
+    rem4: nlukzotu edde5977 message
+      @rem4: nlukzotu edde5977 message
+    rem5: uqqwuynk 7018e6a4 message
+      @rem5: uqqwuynk 7018e6a4 message
+    "###);
+
+    // --jobs overrides the config
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["git", "fetch", "--all-remotes", "--jobs=5"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    branch: rem1@rem1 [new] tracked
+    branch: rem2@rem2 [new] tracked
+    branch: rem3@rem3 [new] tracked
+    branch: rem4@rem4 [new] tracked
+    branch: rem5@rem5 [new] tracked
+    "###);
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["git", "fetch", "--jobs=0"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: invalid value '0' for '--jobs <N>': 0 is not in 1..=65535
+
+    For more information, try '--help'.
     "###);
-    // No remote should have been fetched as part of the failing transaction
-    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @"");
 }
 
 #[test]
@@ -308,6 +467,84 @@ fn test_git_fetch_from_remote_named_git() {
     "###);
 }
 
+#[test]
+fn test_git_fetch_import_tags() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    add_git_remote(&test_env, &repo_path, "origin");
+    let remote_git_repo = git2::Repository::open(test_env.env_root().join("origin")).unwrap();
+    let oid = remote_git_repo.refname_to_id("refs/heads/origin").unwrap();
+    remote_git_repo
+        .reference("refs/tags/v1", oid, false, "")
+        .unwrap();
+
+    // Tags aren't imported if disabled
+    test_env.jj_cmd_ok(&repo_path, &["git", "fetch", "--import-tags=false"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["tag", "list"]);
+    insta::assert_snapshot!(stdout, @"");
+
+    // Tags are imported by default
+    test_env.jj_cmd_ok(&repo_path, &["git", "fetch"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["tag", "list"]);
+    insta::assert_snapshot!(stdout, @r###"
+    v1: oputwtnw ffecd2d6 message
+    "###);
+
+    // Already imported tags aren't deleted if tag import is disabled
+    remote_git_repo
+        .find_reference("refs/tags/v1")
+        .unwrap()
+        .delete()
+        .unwrap();
+    let git_repo_path = repo_path.join(".jj/repo/store/git");
+    let git_repo = git2::Repository::open(git_repo_path).unwrap();
+    git_repo
+        .find_reference("refs/tags/v1")
+        .unwrap()
+        .delete()
+        .unwrap();
+    test_env.add_config("git.import-tags = false");
+    test_env.jj_cmd_ok(&repo_path, &["git", "fetch"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["tag", "list"]);
+    insta::assert_snapshot!(stdout, @r###"
+    v1: oputwtnw ffecd2d6 message
+    "###);
+
+    // The command-line option overrides the setting
+    test_env.jj_cmd_ok(&repo_path, &["git", "fetch", "--import-tags=true"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["tag", "list"]);
+    insta::assert_snapshot!(stdout, @"");
+}
+
+#[test]
+fn test_git_fetch_import_tags_colocated() {
+    let test_env = TestEnvironment::default();
+    let repo_path = test_env.env_root().join("repo");
+    git2::Repository::init(&repo_path).unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["git", "init", "--git-repo=."]);
+    add_git_remote(&test_env, &repo_path, "origin");
+    let remote_git_repo = git2::Repository::open(test_env.env_root().join("origin")).unwrap();
+    let oid = remote_git_repo.refname_to_id("refs/heads/origin").unwrap();
+    remote_git_repo
+        .reference("refs/tags/v1", oid, false, "")
+        .unwrap();
+
+    // Tags aren't fetched into the Git repo if disabled, so they aren't
+    // imported by the next command either
+    test_env.jj_cmd_ok(&repo_path, &["git", "fetch", "--import-tags=false"]);
+    let git_repo = git2::Repository::open(&repo_path).unwrap();
+    assert!(git_repo.find_reference("refs/tags/v1").is_err());
+    let stdout = test_env.jj_cmd_success(&repo_path, &["tag", "list"]);
+    insta::assert_snapshot!(stdout, @"");
+
+    test_env.jj_cmd_ok(&repo_path, &["git", "fetch"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["tag", "list"]);
+    insta::assert_snapshot!(stdout, @r###"
+    v1: oputwtnw ffecd2d6 message
+    "###);
+}
+
 #[test]
 fn test_git_fetch_prune_before_updating_tips() {
     let test_env = TestEnvironment::default();
@@ -173,6 +173,77 @@ fn test_git_import_undo() {
     "###);
 }
 
+#[test]
+fn test_git_import_only() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    let git_repo = git2::Repository::open(repo_path.join(".jj/repo/store/git")).unwrap();
+
+    let commit_id =
+        test_env.jj_cmd_success(&repo_path, &["log", "-Tcommit_id", "--no-graph", "-r@"]);
+    let commit = git_repo
+        .find_commit(git2::Oid::from_str(&commit_id).unwrap())
+        .unwrap();
+    git_repo.branch("feature-1", &commit, true).unwrap();
+    git_repo.branch("feature-2", &commit, true).unwrap();
+    git_repo.branch("main", &commit, true).unwrap();
+    git_repo
+        .tag_lightweight("v1.0", commit.as_object(), false)
+        .unwrap();
+    let base_operation_id = test_env.current_operation_id(&repo_path);
+
+    // Only the matching branches are imported
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["git", "import", "--only=glob:feature-*"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    branch: feature-1 [new] tracked
+    branch: feature-2 [new] tracked
+    "###);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    feature-1: qpvuntsm 230dd059 (empty) (no description set)
+      @git: qpvuntsm 230dd059 (empty) (no description set)
+    feature-2: qpvuntsm 230dd059 (empty) (no description set)
+      @git: qpvuntsm 230dd059 (empty) (no description set)
+    "###);
+
+    // Tags can be selected too, and the other refs are left unchanged
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["git", "import", "--only=v1.0", "--only=feature-1"],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    tag: v1.0 [new] 
+    Warning: The working-copy commit in workspace 'default' became immutable, so a new commit has been created on top of it.
+    Working copy now at: royxmykx eb08b363 (empty) (no description set)
+    Parent commit      : qpvuntsm 230dd059 feature-1 feature-2 | (empty) (no description set)
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["tag", "list"]);
+    insta::assert_snapshot!(stdout, @r###"
+    v1.0: qpvuntsm 230dd059 (empty) (no description set)
+    "###);
+
+    // A pattern matching nothing doesn't import anything
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["git", "import", "--only=unknown"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: No branches or tags found matching `unknown`
+    Nothing changed.
+    "###);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    feature-1: qpvuntsm 230dd059 (empty) (no description set)
+      @git: qpvuntsm 230dd059 (empty) (no description set)
+    feature-2: qpvuntsm 230dd059 (empty) (no description set)
+      @git: qpvuntsm 230dd059 (empty) (no description set)
+    "###);
+
+    // The partial import is a single operation that can be undone
+    test_env.jj_cmd_ok(&repo_path, &["op", "restore", &base_operation_id]);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @"");
+}
+
 #[test]
 fn test_git_import_move_export_with_default_undo() {
     let test_env = TestEnvironment::default();
@@ -333,7 +333,10 @@ fn test_git_push_deletion_unexpectedly_moved() {
       @origin: rlzusymt 8476341e (empty) description 2
     "###);
 
-    let stderr = test_env.jj_cmd_failure(&workspace_root, &["git", "push", "--branch", "branch1"]);
+    let stderr = test_env.jj_cmd_failure(
+        &workspace_root,
+        &["git", "push", "--branch", "branch1", "--force-delete"],
+    );
     insta::assert_snapshot!(stderr, @r###"
     Branch changes to push to origin:
       Delete branch branch1 from d13ecdbda2a2
@@ -389,7 +392,10 @@ fn test_git_push_unexpectedly_deleted() {
     "###);
     // Pushing a *deleted* branch succeeds if deleted on remote, even if we expect
     // branch1@origin to exist and point somewhere.
-    let (stdout, stderr) = test_env.jj_cmd_ok(&workspace_root, &["git", "push", "-bbranch1"]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &workspace_root,
+        &["git", "push", "-bbranch1", "--force-delete"],
+    );
     insta::assert_snapshot!(stdout, @"");
     insta::assert_snapshot!(stderr, @r###"
     Branch changes to push to origin:
@@ -479,8 +485,9 @@ fn test_git_push_multiple() {
         test_env.jj_cmd_ok(&workspace_root, &["git", "push", "--all", "--dry-run"]);
     insta::assert_snapshot!(stdout, @"");
     insta::assert_snapshot!(stderr, @r###"
+    Warning: Not deleting branch branch1 on the remote
+    Hint: Use `--deleted` or `--branch` to delete remote branches.
     Branch changes to push to origin:
-      Delete branch branch1 from d13ecdbda2a2
       Move sideways branch branch2 from 8476341eb395 to c4a3c3105d92
       Add branch my-branch to c4a3c3105d92
     Dry-run requested, not pushing.
@@ -492,6 +499,8 @@ fn test_git_push_multiple() {
     );
     insta::assert_snapshot!(stdout, @"");
     insta::assert_snapshot!(stderr, @r###"
+    Warning: Deleting branch branch1 would make these commits unreachable on origin:
+      xtvrqkyv d13ecdbd branch1@origin | (empty) description 1
     Branch changes to push to origin:
       Delete branch branch1 from d13ecdbda2a2
       Add branch my-branch to c4a3c3105d92
@@ -512,6 +521,8 @@ fn test_git_push_multiple() {
     );
     insta::assert_snapshot!(stdout, @"");
     insta::assert_snapshot!(stderr, @r###"
+    Warning: Deleting branch branch1 would make these commits unreachable on origin:
+      xtvrqkyv d13ecdbd branch1@origin | (empty) description 1
     Branch changes to push to origin:
       Delete branch branch1 from d13ecdbda2a2
       Add branch my-branch to c4a3c3105d92
@@ -524,6 +535,8 @@ fn test_git_push_multiple() {
     );
     insta::assert_snapshot!(stdout, @"");
     insta::assert_snapshot!(stderr, @r###"
+    Warning: Deleting branch branch1 would make these commits unreachable on origin:
+      xtvrqkyv d13ecdbd branch1@origin | (empty) description 1
     Branch changes to push to origin:
       Delete branch branch1 from d13ecdbda2a2
       Move sideways branch branch2 from 8476341eb395 to c4a3c3105d92
@@ -546,12 +559,15 @@ fn test_git_push_multiple() {
     let (stdout, stderr) = test_env.jj_cmd_ok(&workspace_root, &["git", "push", "--all"]);
     insta::assert_snapshot!(stdout, @"");
     insta::assert_snapshot!(stderr, @r###"
+    Warning: Not deleting branch branch1 on the remote
+    Hint: Use `--deleted` or `--branch` to delete remote branches.
     Branch changes to push to origin:
-      Delete branch branch1 from d13ecdbda2a2
       Move sideways branch branch2 from 8476341eb395 to c4a3c3105d92
       Add branch my-branch to c4a3c3105d92
     "###);
     insta::assert_snapshot!(get_branch_output(&test_env, &workspace_root), @r###"
+    branch1 (deleted)
+      @origin: xtvrqkyv d13ecdbd (empty) description 1
     branch2: yqosqzyt c4a3c310 (empty) foo
       @origin: yqosqzyt c4a3c310 (empty) foo
     my-branch: yqosqzyt c4a3c310 (empty) foo
@@ -563,7 +579,7 @@ fn test_git_push_multiple() {
     │  (empty) foo
     │ ◉  rlzusymt test.user@example.com 2001-02-03 08:05:10 8476341e
     ├─╯  (empty) description 2
-    │ ◉  xtvrqkyv test.user@example.com 2001-02-03 08:05:08 d13ecdbd
+    │ ◉  xtvrqkyv test.user@example.com 2001-02-03 08:05:08 branch1@origin d13ecdbd
     ├─╯  (empty) description 1
     ◉  zzzzzzzz root() 00000000
     "###);
@@ -820,6 +836,29 @@ fn test_git_push_conflict() {
     insta::assert_snapshot!(stderr, @r###"
     Error: Won't push commit 73c265a92cfd since it has conflicts
     "###);
+    // Allowing empty descriptions doesn't allow conflicts
+    test_env.jj_cmd_ok(&workspace_root, &["describe", "-m="]);
+    let stderr = test_env.jj_cmd_failure(
+        &workspace_root,
+        &["git", "push", "--all", "--allow-empty-description"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Won't push commit 931656672cab since it has conflicts
+    "###);
+}
+
+#[test]
+fn test_git_push_hidden_branch_target() {
+    let (test_env, workspace_root) = set_up();
+    test_env.jj_cmd_ok(&workspace_root, &["new", "branch1", "-m", "foo"]);
+    test_env.jj_cmd_ok(&workspace_root, &["branch", "create", "my-branch"]);
+    test_env.jj_cmd_ok(&workspace_root, &["abandon", "--retain-branches"]);
+    let stderr =
+        test_env.jj_cmd_failure(&workspace_root, &["git", "push", "--branch", "my-branch"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Won't push branch my-branch pointing to a hidden commit
+    Hint: Use `jj branch set` to move the branch to a visible commit.
+    "###);
 }
 
 #[test]
@@ -832,7 +871,7 @@ fn test_git_push_no_description() {
     insta::assert_snapshot!(stderr, @r###"
     Error: Won't push commit 5b36783cd11c since it has no description
     "###);
-    test_env.jj_cmd_ok(
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
         &workspace_root,
         &[
             "git",
@@ -842,6 +881,11 @@ fn test_git_push_no_description() {
             "--allow-empty-description",
         ],
     );
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: Pushing commit 5b36783cd11c with no description
+    Branch changes to push to origin:
+      Add branch my-branch to 5b36783cd11c
+    "###);
 }
 
 #[test]
@@ -940,6 +984,68 @@ fn test_git_push_missing_author_in_immutable() {
     "###);
 }
 
+#[test]
+fn test_git_push_private_commits() {
+    let (test_env, workspace_root) = set_up();
+    test_env.jj_cmd_ok(&workspace_root, &["new", "branch1", "-m=private: 1"]);
+    test_env.jj_cmd_ok(&workspace_root, &["new", "-m=private: 2"]);
+    test_env.jj_cmd_ok(&workspace_root, &["new", "-m=public"]);
+    test_env.jj_cmd_ok(&workspace_root, &["branch", "set", "branch1"]);
+
+    // Nothing is private by default
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        &workspace_root,
+        &["git", "push", "--branch=branch1", "--dry-run"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Branch changes to push to origin:
+      Move forward branch branch1 from d13ecdbda2a2 to e43572674d74
+    Dry-run requested, not pushing.
+    "###);
+
+    test_env.add_config(r#"git.private-commits = 'description(glob:"private:*")'"#);
+    let stderr = test_env.jj_cmd_failure(&workspace_root, &["git", "push", "--branch=branch1"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Won't push 2 private commits:
+      yostqsxw 1641a97a (empty) private: 2
+      vruxwmqv 4f13ae5b (empty) private: 1
+    Hint: Configured git.private-commits: 'description(glob:"private:*")'. Use --allow-private to push them anyway.
+    "###);
+    let stderr = test_env.jj_cmd_failure(&workspace_root, &["git", "push", "--change=@"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Creating branch push-znkkpsqqskkl for revision znkkpsqqskkl
+    Error: Won't push 2 private commits:
+      yostqsxw 1641a97a (empty) private: 2
+      vruxwmqv 4f13ae5b (empty) private: 1
+    Hint: Configured git.private-commits: 'description(glob:"private:*")'. Use --allow-private to push them anyway.
+    "###);
+    // The branch for --change wasn't created
+    insta::assert_snapshot!(get_branch_output(&test_env, &workspace_root), @r###"
+    branch1: znkkpsqq e4357267 (empty) public
+      @origin (behind by 3 commits): xtvrqkyv d13ecdbd (empty) description 1
+    branch2: rlzusymt 8476341e (empty) description 2
+      @origin: rlzusymt 8476341e (empty) description 2
+    "###);
+
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        &workspace_root,
+        &["git", "push", "--branch=branch1", "--allow-private"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Branch changes to push to origin:
+      Move forward branch branch1 from d13ecdbda2a2 to e43572674d74
+    "###);
+
+    // Private commits that are already on the remote don't block later pushes
+    test_env.jj_cmd_ok(&workspace_root, &["new", "-m=public 2"]);
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&workspace_root, &["git", "push", "--change=@"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Creating branch push-uyznsvlquzzm for revision uyznsvlquzzm
+    Branch changes to push to origin:
+      Add branch push-uyznsvlquzzm to 27beb00ea528
+    "###);
+}
+
 #[test]
 fn test_git_push_missing_committer() {
     let (test_env, workspace_root) = set_up();
@@ -1020,7 +1126,18 @@ fn test_git_push_deleted() {
     let (test_env, workspace_root) = set_up();
 
     test_env.jj_cmd_ok(&workspace_root, &["branch", "delete", "branch1"]);
-    let (stdout, stderr) = test_env.jj_cmd_ok(&workspace_root, &["git", "push", "--deleted"]);
+    // The commit would become unreachable on the remote
+    let stderr = test_env.jj_cmd_failure(&workspace_root, &["git", "push", "--deleted"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: Deleting branch branch1 would make these commits unreachable on origin:
+      xtvrqkyv d13ecdbd branch1@origin | (empty) description 1
+    Error: Refusing to delete remote branches that would leave commits unreachable
+    Hint: Use `--force-delete` to delete them anyway.
+    "###);
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &workspace_root,
+        &["git", "push", "--deleted", "--force-delete"],
+    );
     insta::assert_snapshot!(stdout, @"");
     insta::assert_snapshot!(stderr, @r###"
     Branch changes to push to origin:
@@ -1043,6 +1160,39 @@ fn test_git_push_deleted() {
     "###);
 }
 
+#[test]
+fn test_git_push_deleted_still_reachable() {
+    let (test_env, workspace_root) = set_up();
+
+    test_env.jj_cmd_ok(
+        &workspace_root,
+        &["branch", "create", "-r=branch2", "branch3"],
+    );
+    test_env.jj_cmd_ok(&workspace_root, &["git", "push", "-b=branch3"]);
+    // No confirmation is needed since the commit is still reachable from branch2
+    test_env.jj_cmd_ok(&workspace_root, &["branch", "delete", "branch3"]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&workspace_root, &["git", "push", "--deleted"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Branch changes to push to origin:
+      Delete branch branch3 from 8476341eb395
+    "###);
+}
+
+#[test]
+fn test_git_push_deleted_unprotected() {
+    let (test_env, workspace_root) = set_up();
+    test_env.add_config("git.push-protect-deletions = false");
+
+    test_env.jj_cmd_ok(&workspace_root, &["branch", "delete", "branch1"]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&workspace_root, &["git", "push", "--all"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Branch changes to push to origin:
+      Delete branch branch1 from d13ecdbda2a2
+    "###);
+}
+
 #[test]
 fn test_git_push_conflicting_branches() {
     let (test_env, workspace_root) = set_up();
@@ -1156,9 +1306,9 @@ fn test_git_push_tracked_vs_all() {
     let (_stdout, stderr) =
         test_env.jj_cmd_ok(&workspace_root, &["git", "push", "--tracked", "--dry-run"]);
     insta::assert_snapshot!(stderr, @r###"
-    Branch changes to push to origin:
-      Delete branch branch2 from 8476341eb395
-    Dry-run requested, not pushing.
+    Warning: Not deleting branch branch2 on the remote
+    Hint: Use `--deleted` or `--branch` to delete remote branches.
+    Nothing changed.
     "###);
 
     // Untrack the last remaining tracked branch.
@@ -1254,6 +1404,315 @@ fn test_git_push_to_remote_named_git() {
     "###);
 }
 
+#[test]
+fn test_git_push_tags() {
+    let (test_env, workspace_root) = set_up();
+    let git_repo = git2::Repository::open(workspace_root.join(".jj/repo/store/git")).unwrap();
+    let origin_git_repo =
+        git2::Repository::open(test_env.env_root().join("origin/.jj/repo/store/git")).unwrap();
+    let signature = git2::Signature::now("Some One", "some.one@example.com").unwrap();
+    let commit1 = git_repo
+        .find_reference("refs/remotes/origin/branch1")
+        .unwrap()
+        .peel_to_commit()
+        .unwrap();
+    let commit2 = git_repo
+        .find_reference("refs/remotes/origin/branch2")
+        .unwrap()
+        .peel_to_commit()
+        .unwrap();
+    git_repo
+        .tag_lightweight("v1.0", commit1.as_object(), false)
+        .unwrap();
+    git_repo
+        .tag("v2.0", commit2.as_object(), &signature, "release 2", false)
+        .unwrap();
+    test_env.jj_cmd_ok(&workspace_root, &["git", "import"]);
+
+    // Dry run shows the tags to push
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&workspace_root, &["git", "push", "--tags", "--dry-run"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Tags to push to origin:
+      Push tag v1.0 at d13ecdbda2a2
+      Push tag v2.0 at 8476341eb395
+    Dry-run requested, not pushing.
+    "###);
+
+    // Push only the tags matching the pattern
+    let (stdout, stderr) = test_env.jj_cmd_ok(&workspace_root, &["git", "push", "--tags=v1.0"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Tags to push to origin:
+      Push tag v1.0 at d13ecdbda2a2
+    "###);
+    assert!(origin_git_repo.find_reference("refs/tags/v1.0").is_ok());
+    assert!(origin_git_repo.find_reference("refs/tags/v2.0").is_err());
+
+    // Annotated tags are pushed as-is
+    test_env.jj_cmd_ok(&workspace_root, &["git", "push", "--tags"]);
+    let origin_tag = origin_git_repo
+        .find_reference("refs/tags/v2.0")
+        .unwrap()
+        .peel_to_tag()
+        .unwrap();
+    assert_eq!(origin_tag.message(), Some("release 2"));
+
+    // Moving a tag on the remote requires --force-tags
+    git_repo
+        .tag_lightweight("v1.0", commit2.as_object(), true)
+        .unwrap();
+    test_env.jj_cmd_ok(&workspace_root, &["git", "import"]);
+    let stderr = test_env.jj_cmd_failure(&workspace_root, &["git", "push", "--tags=v1.0"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Tags to push to origin:
+      Push tag v1.0 at 8476341eb395
+    Error: Refusing to move tags that already exist on the remote: refs/tags/v1.0
+    Hint: Use --force-tags to move them.
+    "###);
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &workspace_root,
+        &["git", "push", "--tags=v1.0", "--force-tags"],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Tags to push to origin:
+      Push tag v1.0 at 8476341eb395
+    "###);
+    assert_eq!(
+        origin_git_repo
+            .find_reference("refs/tags/v1.0")
+            .unwrap()
+            .peel_to_commit()
+            .unwrap()
+            .id(),
+        commit2.id()
+    );
+
+    // No tags match the pattern
+    let (stdout, stderr) = test_env.jj_cmd_ok(&workspace_root, &["git", "push", "--tags=glob:v3*"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: No tags matching v3*
+    Nothing changed.
+    "###);
+}
+
+#[test]
+fn test_git_push_tags_refuses_bad_commits() {
+    let (test_env, workspace_root) = set_up();
+    let git_repo = git2::Repository::open(workspace_root.join(".jj/repo/store/git")).unwrap();
+    test_env.jj_cmd_ok(&workspace_root, &["new", "root()"]);
+    test_env.jj_cmd_ok(&workspace_root, &["git", "export"]);
+    let stdout = test_env.jj_cmd_success(
+        &workspace_root,
+        &["log", "-r=@", "--no-graph", "-T=commit_id"],
+    );
+    let commit = git_repo
+        .find_commit(git2::Oid::from_str(&stdout).unwrap())
+        .unwrap();
+    git_repo
+        .tag_lightweight("empty", commit.as_object(), false)
+        .unwrap();
+    test_env.jj_cmd_ok(&workspace_root, &["git", "import"]);
+
+    let stderr = test_env.jj_cmd_failure(&workspace_root, &["git", "push", "--tags"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Won't push tag empty since it points to commit 41658cf47e0d, which has no description
+    "###);
+    // The tag can be pushed if empty descriptions are allowed
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        &workspace_root,
+        &["git", "push", "--tags", "--allow-empty-description"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: Pushing tag empty pointing to commit 41658cf47e0d with no description
+    Tags to push to origin:
+      Push tag empty at 41658cf47e0d
+    "###);
+}
+
+#[test]
+fn test_git_push_tags_private_commits() {
+    let (test_env, workspace_root) = set_up();
+    let git_repo = git2::Repository::open(workspace_root.join(".jj/repo/store/git")).unwrap();
+    test_env.jj_cmd_ok(&workspace_root, &["new", "branch1", "-m=private: 1"]);
+    test_env.jj_cmd_ok(&workspace_root, &["new", "-m=public"]);
+    test_env.jj_cmd_ok(&workspace_root, &["git", "export"]);
+    let stdout = test_env.jj_cmd_success(
+        &workspace_root,
+        &["log", "-r=@", "--no-graph", "-T=commit_id"],
+    );
+    let commit = git_repo
+        .find_commit(git2::Oid::from_str(&stdout).unwrap())
+        .unwrap();
+    git_repo
+        .tag_lightweight("v1.0", commit.as_object(), false)
+        .unwrap();
+    test_env.jj_cmd_ok(&workspace_root, &["git", "import"]);
+
+    test_env.add_config(r#"git.private-commits = 'description(glob:"private:*")'"#);
+    let stderr = test_env.jj_cmd_failure(&workspace_root, &["git", "push", "--tags"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Won't push 1 private commit:
+      vruxwmqv 4f13ae5b (empty) private: 1
+    Hint: Configured git.private-commits: 'description(glob:"private:*")'. Use --allow-private to push them anyway.
+    "###);
+
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        &workspace_root,
+        &["git", "push", "--tags", "--allow-private"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Tags to push to origin:
+      Push tag v1.0 at c298be95c78b
+    "###);
+}
+
+#[test]
+fn test_git_push_remote_ref_prefix() {
+    let (test_env, workspace_root) = set_up();
+    let origin_git_repo =
+        git2::Repository::open(test_env.env_root().join("origin/.jj/repo/store/git")).unwrap();
+    let get_origin_ref = |name: &str| {
+        origin_git_repo
+            .find_reference(name)
+            .ok()
+            .and_then(|reference| reference.target())
+            .map(|oid| oid.to_string()[..12].to_owned())
+    };
+    test_env.jj_cmd_ok(&workspace_root, &["new", "branch1", "-m", "for review"]);
+    test_env.jj_cmd_ok(&workspace_root, &["branch", "set", "branch1"]);
+
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &workspace_root,
+        &[
+            "git",
+            "push",
+            "--remote-ref-prefix=refs/for/",
+            "-b",
+            "branch1",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Branch changes to push to origin under refs/for/:
+      Push branch branch1 to refs/for/branch1 at 2f81c6ccdd96
+    "###);
+    insta::assert_debug_snapshot!(
+        (get_origin_ref("refs/for/branch1"), get_origin_ref("refs/heads/branch1")),
+        @r###"
+    (
+        Some(
+            "2f81c6ccdd96",
+        ),
+        Some(
+            "d13ecdbda2a2",
+        ),
+    )
+    "###
+    );
+    // The remote-tracking branch is left unchanged
+    insta::assert_snapshot!(get_branch_output(&test_env, &workspace_root), @r###"
+    branch1: vruxwmqv 2f81c6cc (empty) for review
+      @origin (behind by 1 commits): xtvrqkyv d13ecdbd (empty) description 1
+    branch2: rlzusymt 8476341e (empty) description 2
+      @origin: rlzusymt 8476341e (empty) description 2
+    "###);
+
+    // A branch in sync with its remote-tracking branch can still be pushed
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &workspace_root,
+        &[
+            "git",
+            "push",
+            "--remote-ref-prefix=refs/for/",
+            "-b",
+            "branch2",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Branch changes to push to origin under refs/for/:
+      Push branch branch2 to refs/for/branch2 at 8476341eb395
+    "###);
+    insta::assert_debug_snapshot!(get_origin_ref("refs/for/branch2"), @r###"
+    Some(
+        "8476341eb395",
+    )
+    "###);
+
+    // Existing refs are only updated by fast-forwards
+    test_env.jj_cmd_ok(&workspace_root, &["new", "branch2", "-m", "sideways"]);
+    test_env.jj_cmd_ok(
+        &workspace_root,
+        &["branch", "set", "branch1", "--allow-backwards"],
+    );
+    let stderr = test_env.jj_cmd_failure(
+        &workspace_root,
+        &[
+            "git",
+            "push",
+            "--remote-ref-prefix=refs/for/",
+            "-b",
+            "branch1",
+        ],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Branch changes to push to origin under refs/for/:
+      Push branch branch1 to refs/for/branch1 at b3583e48b145
+    Error: Refusing to push a branch that isn't a fast-forward of the remote ref. Affected refs: refs/for/branch1
+    Hint: Make the branch a descendant of the remote ref, and push again.
+    "###);
+
+    // The prefix can be configured
+    test_env.add_config(r#"git.push-remote-ref-prefix = "refs/users/test/""#);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&workspace_root, &["git", "push", "-b", "branch1"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Branch changes to push to origin under refs/users/test/:
+      Push branch branch1 to refs/users/test/branch1 at b3583e48b145
+    "###);
+    insta::assert_debug_snapshot!(get_origin_ref("refs/users/test/branch1"), @r###"
+    Some(
+        "b3583e48b145",
+    )
+    "###);
+
+    // Branches can't be deleted under the prefix
+    test_env.jj_cmd_ok(&workspace_root, &["branch", "delete", "branch2"]);
+    let stderr = test_env.jj_cmd_failure(&workspace_root, &["git", "push", "-b", "branch2"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Cannot delete branch branch2 under refs/users/test/ on the remote
+    Hint: Only branches under refs/heads/ can be deleted.
+    "###);
+
+    // Invalid prefixes are rejected
+    let stderr = test_env.jj_cmd_failure(
+        &workspace_root,
+        &["git", "push", "--remote-ref-prefix=for/", "-b", "branch1"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Invalid remote ref prefix: for/
+    Hint: The prefix must be a ref namespace starting with `refs/` and ending with `/`, such as `refs/for/`.
+    "###);
+    let stderr = test_env.jj_cmd_failure(
+        &workspace_root,
+        &[
+            "git",
+            "push",
+            "--remote-ref-prefix=refs/for",
+            "-b",
+            "branch1",
+        ],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Invalid remote ref prefix: refs/for
+    Hint: The prefix must be a ref namespace starting with `refs/` and ending with `/`, such as `refs/for/`.
+    "###);
+}
+
 fn get_branch_output(test_env: &TestEnvironment, repo_path: &Path) -> String {
     // --quiet to suppress deleted branches hint
     test_env.jj_cmd_success(repo_path, &["branch", "list", "--all-remotes", "--quiet"])
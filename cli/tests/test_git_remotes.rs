@@ -144,6 +144,123 @@ fn test_git_remote_set_url() {
     "###);
 }
 
+#[test]
+fn test_git_remote_set_url_push_and_invalid() {
+    let test_env = TestEnvironment::default();
+
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["git", "remote", "add", "foo", "http://example.com/repo/foo"],
+    );
+
+    // Invalid URLs are rejected without changing the remote
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &[
+            "git",
+            "remote",
+            "set-url",
+            "foo",
+            "http://exa mple.com/repo",
+        ],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Invalid Git remote URL 'http://exa mple.com/repo'
+    Caused by:
+    1: URL "http://exa mple.com/repo" can not be parsed as valid URL
+    2: invalid domain character
+    "###);
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &["git", "remote", "set-url", "foo", "--push", ""],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Invalid Git remote URL ''
+    Caused by: local path "" does not specify a path to a repository
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["git", "remote", "list"]);
+    insta::assert_snapshot!(stdout, @"foo http://example.com/repo/foo");
+
+    // The push URL is separate from the fetch URL
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "git",
+            "remote",
+            "set-url",
+            "foo",
+            "--push",
+            "ssh://git@example.com/repo/foo",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @"");
+    let stdout = test_env.jj_cmd_success(&repo_path, &["git", "remote", "list"]);
+    insta::assert_snapshot!(stdout, @"foo http://example.com/repo/foo (push: ssh://git@example.com/repo/foo)");
+}
+
+#[test]
+fn test_git_remote_set_url_keeps_tracking() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "origin1"]);
+    let origin1_path = test_env.env_root().join("origin1");
+    test_env.jj_cmd_ok(&origin1_path, &["describe", "-m=description 1"]);
+    test_env.jj_cmd_ok(&origin1_path, &["branch", "create", "main"]);
+    test_env.jj_cmd_ok(&origin1_path, &["git", "export"]);
+    let origin1_git_path = origin1_path.join(".jj/repo/store/git");
+
+    // The new remote location has moved the branch forward
+    test_env.jj_cmd_ok(
+        test_env.env_root(),
+        &[
+            "git",
+            "clone",
+            "--config-toml=git.auto-local-branch=true",
+            origin1_git_path.to_str().unwrap(),
+            "origin2",
+        ],
+    );
+    let origin2_path = test_env.env_root().join("origin2");
+    test_env.jj_cmd_ok(&origin2_path, &["new", "main", "-m=description 2"]);
+    test_env.jj_cmd_ok(&origin2_path, &["branch", "set", "main"]);
+    test_env.jj_cmd_ok(&origin2_path, &["git", "export"]);
+    let origin2_git_path = origin2_path.join(".jj/repo/store/git");
+
+    test_env.jj_cmd_ok(
+        test_env.env_root(),
+        &[
+            "git",
+            "clone",
+            "--config-toml=git.auto-local-branch=true",
+            origin1_git_path.to_str().unwrap(),
+            "local",
+        ],
+    );
+    let local_path = test_env.env_root().join("local");
+    test_env.jj_cmd_ok(
+        &local_path,
+        &[
+            "git",
+            "remote",
+            "set-url",
+            "origin",
+            origin2_git_path.to_str().unwrap(),
+        ],
+    );
+
+    // The next fetch uses the new URL, and the local branch still tracks the
+    // remote branch
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&local_path, &["git", "fetch"]);
+    insta::assert_snapshot!(stderr, @"branch: main@origin [updated] tracked");
+    let stdout = test_env.jj_cmd_success(&local_path, &["branch", "list", "--all-remotes"]);
+    insta::assert_snapshot!(stdout, @r###"
+    main: kykxqpsk 873fddf3 (empty) description 2
+      @origin: kykxqpsk 873fddf3 (empty) description 2
+    "###);
+}
+
 #[test]
 fn test_git_remote_rename() {
     let test_env = TestEnvironment::default();
@@ -545,6 +545,50 @@ fn test_invalid_config() {
     "###);
 }
 
+#[test]
+fn test_error_format_json() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config(r#"ui.error-format = "json""#);
+
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["edit", "root()"]);
+    insta::assert_snapshot!(stderr, @r###"
+    {"causes":[],"code":"immutable-commit","hints":[],"message":"The root commit 000000000000 is immutable"}
+    "###);
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["log", "-r", "foo("]);
+    insta::assert_snapshot!(stderr, @r###"
+    {"causes":[" --> 1:5\n  |\n1 | foo(\n  |     ^---\n  |\n  = expected <identifier> or <expression>"],"code":"revset-parse","hints":[],"message":"Failed to parse revset: Syntax error"}
+    "###);
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["log", "--no-such-flag"]);
+    insta::assert_snapshot!(stderr, @r###"
+    {"causes":[],"code":"cli-error","hints":[],"message":"unexpected argument '--no-such-flag' found\n\n  tip: to pass '--no-such-flag' as a value, use '-- --no-such-flag'\n\nUsage: jj log [OPTIONS] [PATHS]...\n\nFor more information, try '--help'."}
+    "###);
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["branch", "rename", "nope", "new"]);
+    insta::assert_snapshot!(stderr, @r###"
+    {"causes":[],"code":"no-such-branch","hints":[],"message":"No such branch: nope"}
+    "###);
+
+    // Hints are included
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "main"]);
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["log", "-r", "mian"]);
+    insta::assert_snapshot!(stderr, @r###"
+    {"causes":[],"code":"no-such-revision","hints":["Did you mean \"main\"?"],"message":"Revision \"mian\" doesn't exist"}
+    "###);
+
+    // Help is still printed as usual
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "--help"]);
+    insta::assert_snapshot!(stdout.lines().next().unwrap(), @"Show revision history");
+
+    // The environment variable enables it too
+    test_env.add_config(r#"ui.error-format = "text""#);
+    test_env.add_env_var("JJ_ERROR_JSON", "1");
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["edit", "root()"]);
+    insta::assert_snapshot!(stderr, @r###"
+    {"causes":[],"code":"immutable-commit","hints":[],"message":"The root commit 000000000000 is immutable"}
+    "###);
+}
+
 #[test]
 fn test_no_user_configured() {
     // Test that the user is reminded if they haven't configured their name or email
@@ -0,0 +1,112 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_help_subcommand() {
+    let test_env = TestEnvironment::default();
+
+    // `jj help <command>` is the same as `jj <command> --help`
+    let stdout = test_env.jj_cmd_success(test_env.env_root(), &["help", "git", "push"]);
+    assert_eq!(
+        stdout,
+        test_env.jj_cmd_success(test_env.env_root(), &["git", "push", "--help"])
+    );
+
+    // The help topics are listed after the subcommands
+    let stdout = test_env.jj_cmd_success(test_env.env_root(), &["help"]);
+    let topics = stdout
+        .lines()
+        .skip_while(|line| *line != "Help Topics:")
+        .collect::<Vec<_>>();
+    insta::assert_snapshot!(topics.join("\n"), @r###"
+    Help Topics:
+      configuration  Settings and config files
+      conflicts      How conflicts are recorded and resolved
+      filesets       The language for selecting files
+      glossary       Definitions of the terms used in jj
+      revsets        The language for selecting revisions
+      templates      The language for customizing command output
+    "###);
+
+    let stderr = test_env.jj_cmd_cli_error(test_env.env_root(), &["help", "nonexistent"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: unrecognized subcommand 'nonexistent'
+
+      tip: a similar subcommand exists: 'next'
+
+    Usage: jj [OPTIONS] [COMMAND]
+
+    For more information, try '--help'.
+    "###);
+}
+
+#[test]
+fn test_help_topic() {
+    let test_env = TestEnvironment::default();
+
+    // Paragraphs are rewrapped, code blocks are printed as is
+    let stdout = test_env.jj_cmd_success(test_env.env_root(), &["help", "filesets"]);
+    insta::assert_snapshot!(stdout.lines().take(24).collect::<Vec<_>>().join("\n"), @r###"
+    Filesets
+
+    Jujutsu supports a functional language for selecting a set of files.
+    Expressions in this language are called "filesets" (the idea comes from
+    [Mercurial](https://repo.mercurial-scm.org/hg/help/filesets)). The language
+    consists of file patterns, operators, and functions.
+
+    **Filesets support is still experimental.** It can be enabled by
+    `ui.allow-filesets`.
+
+        ui.allow-filesets = true
+
+    Many `jj` commands accept fileset expressions as positional arguments. File
+    names passed to these commands [must be quoted][string-literals] if they
+    contain whitespace or meta characters. However, as a special case, quotes can
+    be omitted if the expression has no operators nor function calls. For example:
+
+    * `jj diff 'Foo Bar'` (shell quotes are required, but inner quotes are
+      optional)
+    * `jj diff '~"Foo Bar"'` (both shell and inner quotes are required)
+    * `jj diff '"Foo(1)"'` (both shell and inner quotes are required)
+
+    [string-literals]: templates.md#string-literals
+    "###);
+}
+
+#[test]
+fn test_help_keyword() {
+    let test_env = TestEnvironment::default();
+
+    let stdout = test_env.jj_cmd_success(test_env.env_root(), &["help", "-k", "latest"]);
+    insta::assert_snapshot!(stdout, @"jj help revsets: `latest(x[, count])`: Latest `count` commits in `x`, based on committer");
+    // The search is case-insensitive, and the parentheses of functions are
+    // optional
+    let stdout = test_env.jj_cmd_success(test_env.env_root(), &["help", "-k", "LATEST()"]);
+    insta::assert_snapshot!(stdout, @"jj help revsets: `latest(x[, count])`: Latest `count` commits in `x`, based on committer");
+
+    // Options of subcommands are found too
+    let stdout = test_env.jj_cmd_success(test_env.env_root(), &["help", "-k", "--no-elide"]);
+    insta::assert_snapshot!(stdout, @r###"
+    jj log: Spans of revisions that are not included in the graph per `--revisions` are rendered as a synthetic node labeled "(elided revisions)", unless `--no-elide` is given. Use `--elided` or the `ui.log-elided-style` setting to render them differently.
+    "###);
+
+    // Words that aren't quoted as code don't match
+    let stderr = test_env.jj_cmd_failure(test_env.env_root(), &["help", "-k", "interchangeably"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: No help found for keyword interchangeably
+    Hint: Run `jj help` to list the subcommands and help topics.
+    "###);
+}
@@ -50,6 +50,7 @@ fn test_log_with_no_template() {
     - builtin_op_log_compact
     - builtin_op_log_node
     - builtin_op_log_node_ascii
+    - builtin_op_show
     - commit_summary_separator
     - description_placeholder
     - email_placeholder
@@ -57,6 +58,216 @@ fn test_log_with_no_template() {
     "###);
 }
 
+#[test]
+fn test_log_group_by_description() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m=feature"]);
+    for _ in 0..3 {
+        test_env.jj_cmd_ok(&repo_path, &["new", "-m=fixup! feature"]);
+    }
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m=other"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m=fixup! other"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m=fixup! other"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "@-", "-m=fixup! other"]);
+
+    let template = r#"description.first_line() ++ "\n""#;
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  fixup! other
+    │ ◉  fixup! other
+    ├─╯
+    ◉  fixup! other
+    ◉  other
+    ◉  fixup! feature
+    ◉  fixup! feature
+    ◉  fixup! feature
+    ◉  feature
+    ◉
+    "###);
+
+    // Revisions with other children in the graph are not collapsed
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["log", "-T", template, "--group-by-description"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    @  fixup! other
+    │ ◉  fixup! other
+    ├─╯
+    ◉  fixup! other
+    ◉  other
+    ◉  fixup! feature
+    ◌  (2 more revisions with the same description)
+    ◉  feature
+    ◉
+    "###);
+
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "log",
+            "-T",
+            template,
+            "--group-by-description",
+            "--reversed",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    ◉
+    ◉  feature
+    ◉  fixup! feature
+    ◌  (2 more revisions with the same description)
+    ◉  other
+    ◉    fixup! other
+    ├─╮
+    │ ◉  fixup! other
+    @  fixup! other
+    "###);
+}
+
+#[test]
+fn test_log_with_content() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("config.toml"), "a = 1\n").unwrap();
+    std::fs::write(repo_path.join("other"), "other\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m", "first"]);
+    std::fs::write(repo_path.join("config.toml"), "a = 1\nb = 2").unwrap();
+    std::fs::write(repo_path.join("binary"), b"\0binary").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "second"]);
+
+    // The full contents are shown under each revision, including the
+    // unchanged ones
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "log",
+            "-T",
+            "description",
+            "-r=::@ ~ root()",
+            "--with-content=config.toml",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    @  second
+    │  config.toml:
+    │      a = 1
+    │      b = 2
+    ◉  first
+    │  config.toml:
+    ~      a = 1
+    "###);
+
+    // Binary files are skipped
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "log",
+            "-T",
+            "description",
+            "-r=@",
+            "--no-graph",
+            "--with-content=binary",
+            "--with-content=config.toml",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    second
+    binary: (binary file, not shown)
+    config.toml:
+        a = 1
+        b = 2
+    "###);
+}
+
+#[test]
+fn test_log_conflict_detail() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file"), "base\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "base"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "left"]);
+    std::fs::write(repo_path.join("file"), "a\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new", "@-", "-m", "right"]);
+    std::fs::write(repo_path.join("file"), "b\n").unwrap();
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["new", "description(left)", "@", "-m", "merge"],
+    );
+
+    // Only conflicted revisions are annotated
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["log", "-T", "description", "--conflict-detail"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    @    merge
+    ├─╮    file    2-sided conflict
+    │ ◉  right
+    ◉ │  left
+    ├─╯
+    ◉  base
+    ◉
+    "###);
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "log",
+            "-T",
+            "description",
+            "-r@",
+            "--no-graph",
+            "--conflict-detail",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    merge
+      file    2-sided conflict
+    "###);
+
+    // Long lists of conflicted files are truncated
+    test_env.jj_cmd_ok(&repo_path, &["edit", "description(left)"]);
+    for i in 0..12 {
+        std::fs::write(repo_path.join(format!("file{i:02}")), "a\n").unwrap();
+    }
+    test_env.jj_cmd_ok(&repo_path, &["edit", "description(right)"]);
+    for i in 0..12 {
+        std::fs::write(repo_path.join(format!("file{i:02}")), "b\n").unwrap();
+    }
+    let (stdout, _stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "log",
+            "-T",
+            "description",
+            "-r",
+            "description(merge)",
+            "--conflict-detail",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    ◉  merge
+    │    file      2-sided conflict
+    ~    file00    2-sided conflict
+         file01    2-sided conflict
+         file02    2-sided conflict
+         file03    2-sided conflict
+         file04    2-sided conflict
+         file05    2-sided conflict
+         file06    2-sided conflict
+         file07    2-sided conflict
+         file08    2-sided conflict
+         ...and 3 more conflicted files
+    "###);
+}
+
 #[test]
 fn test_log_with_or_without_diff() {
     let test_env = TestEnvironment::default();
@@ -1472,6 +1683,260 @@ fn test_elided() {
     "###);
 }
 
+#[test]
+fn test_elided_marker() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "initial"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "main branch 1"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "main branch 2"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "@--", "-m", "side branch 1"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "side branch 2"]);
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["new", "-m", "merge", r#"description("main branch 2")"#, "@"],
+    );
+
+    let get_log = |revs: &str, extra_args: &[&str]| -> String {
+        let mut args = vec!["log", "-T", r#"description ++ "\n""#, "-r", revs];
+        args.extend_from_slice(extra_args);
+        test_env.jj_cmd_success(&repo_path, &args)
+    };
+
+    // Only one parent of the merge is elided
+    let revs = r#"@ | description("main branch 2") | description("side branch 1")"#;
+    insta::assert_snapshot!(get_log(revs, &["--elided=marker"]), @r###"
+    @    merge
+    ├─╮
+    │ ~
+    │ ◉  side branch 1
+    │ │
+    │ ~
+    │
+    ◉  main branch 2
+    │
+    ~
+    "###);
+    insta::assert_snapshot!(get_log(revs, &["--elided=marker", "--reversed"]), @r###"
+    ◉  main branch 2
+    │
+    │ ◉  side branch 1
+    │ │
+    │ ~
+    ├─╯
+    @  merge
+    "###);
+
+    // Several spans are elided in a row
+    test_env.add_config(r#"ui.log-elided-style = "marker""#);
+    insta::assert_snapshot!(get_log("@ | description(initial)", &[]), @r###"
+    @  merge
+    │
+    ~
+    ◉  initial
+    │
+    ~
+    "###);
+    insta::assert_snapshot!(get_log("@ | description(initial)", &["--reversed"]), @r###"
+    ◉  initial
+    │
+    ~
+    @  merge
+    "###);
+
+    // The command-line option overrides the config
+    insta::assert_snapshot!(get_log(revs, &["--elided=none"]), @r###"
+    @    merge
+    ├─╮
+    │ ◉  side branch 1
+    │ │
+    │ ~
+    │
+    ◉  main branch 2
+    │
+    ~
+    "###);
+
+    test_env.add_config(r#"ui.log-elided-style = "bogus""#);
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["log"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Config error: Invalid `ui.log-elided-style`
+    Caused by: enum ElidedStyle does not have variant constructor bogus
+    For help, see https://github.com/martinvonz/jj/blob/main/docs/config.md.
+    "###);
+}
+
+#[test]
+fn test_log_all_and_no_elide() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "initial"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "main branch 1"]);
+    std::fs::write(repo_path.join("file"), "main\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "main branch 2"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "@--", "-m", "side branch 1"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "side branch 2"]);
+    std::fs::write(repo_path.join("file"), "side\n").unwrap();
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["new", "-m", "merge", r#"description("main branch 2")"#, "@"],
+    );
+    std::fs::write(repo_path.join("file"), "merged\n").unwrap();
+
+    let get_log = |args: &[&str]| -> (String, String) {
+        let mut log_args = vec!["log", "-T", r#"description ++ "\n""#];
+        log_args.extend_from_slice(args);
+        test_env.jj_cmd_ok(&repo_path, &log_args)
+    };
+
+    // --all shows all revisions
+    let (stdout, _stderr) = get_log(&["--all"]);
+    insta::assert_snapshot!(stdout, @r###"
+    @    merge
+    ├─╮
+    │ ◉  side branch 2
+    │ │
+    │ ◉  side branch 1
+    │ │
+    ◉ │  main branch 2
+    │ │
+    ◉ │  main branch 1
+    ├─╯
+    ◉  initial
+    │
+    ◉
+    "###);
+
+    // --all can be combined with paths
+    let (stdout, _stderr) = get_log(&["--all", "file"]);
+    insta::assert_snapshot!(stdout, @r###"
+    @    merge
+    ├─╮
+    ◌ │  (elided revisions)
+    │ ◉  side branch 2
+    │ │
+    │ ~
+    │
+    ◉  main branch 1
+    │
+    ~
+    "###);
+
+    // The set of revisions shown by --all can be configured
+    let (stdout, _stderr) = get_log(&[
+        "--all",
+        "--config-toml",
+        "revsets.log-graph-all = '~root()'",
+    ]);
+    insta::assert_snapshot!(stdout, @r###"
+    @    merge
+    ├─╮
+    │ ◉  side branch 2
+    │ │
+    │ ◉  side branch 1
+    │ │
+    ◉ │  main branch 2
+    │ │
+    ◉ │  main branch 1
+    ├─╯
+    ◉  initial
+    │
+    ~
+    "###);
+
+    // --all can't be combined with --revisions
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["log", "--all", "-r", "@"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--all' cannot be used with '--revisions <REVISIONS>'
+
+    Usage: jj log --all [PATHS]...
+
+    For more information, try '--help'.
+    "###);
+
+    // --no-elide renders the elided revisions
+    let revset = "@ | @- | description(initial)";
+    let (stdout, _stderr) = get_log(&["-r", revset]);
+    insta::assert_snapshot!(stdout, @r###"
+    @    merge
+    ├─╮
+    │ ◉  side branch 2
+    │ │
+    │ ◌  (elided revisions)
+    ◉ │  main branch 2
+    │ │
+    ◌ │  (elided revisions)
+    ├─╯
+    ◉  initial
+    │
+    ~
+    "###);
+    let (stdout, stderr) = get_log(&["-r", revset, "--no-elide"]);
+    insta::assert_snapshot!(stdout, @r###"
+    @    merge
+    ├─╮
+    │ ◉  side branch 2
+    │ │
+    │ ◉  side branch 1
+    │ │
+    ◉ │  main branch 2
+    │ │
+    ◉ │  main branch 1
+    ├─╯
+    ◉  initial
+    │
+    ~
+    "###);
+    insta::assert_snapshot!(stderr, @"");
+
+    // --no-elide combined with paths renders the revisions in between
+    let (stdout, _stderr) = get_log(&["--all", "file", "--no-elide"]);
+    insta::assert_snapshot!(stdout, @r###"
+    @    merge
+    ├─╮
+    │ ◉  side branch 2
+    │ │
+    │ ~
+    │
+    ◉  main branch 2
+    │
+    ◉  main branch 1
+    │
+    ~
+    "###);
+
+    // Too many revisions to render without eliding
+    let (stdout, stderr) = get_log(&[
+        "-r",
+        revset,
+        "--no-elide",
+        "--config-toml",
+        "ui.log-no-elide-limit = 5",
+    ]);
+    insta::assert_snapshot!(stdout, @r###"
+    @    merge
+    ├─╮
+    │ ◉  side branch 2
+    │ │
+    │ ◌  (elided revisions)
+    ◉ │  main branch 2
+    │ │
+    ◌ │  (elided revisions)
+    ├─╯
+    ◉  initial
+    │
+    ~
+    "###);
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: Not expanding elided revisions because the graph would show more than 5 revisions.
+    Hint: Set `ui.log-no-elide-limit` to a larger value to show them.
+    "###);
+}
+
 #[test]
 fn test_log_with_custom_symbols() {
     // Test that elided commits are shown as synthetic nodes.
@@ -269,16 +269,13 @@ fn test_new_insert_after_children() {
     );
     insta::assert_snapshot!(stdout, @"");
     insta::assert_snapshot!(stderr, @r###"
-    Working copy now at: kxryzmor 6d63e17b (empty) G
-    Parent commit      : qpvuntsm 5ef24e4b A | (empty) A
+    Working copy now at: kxryzmor 78a97058 (empty) G
     Parent commit      : mzvwutvl 83376b27 C | (empty) C
     "###);
     insta::assert_snapshot!(get_short_log_output(&test_env, &repo_path), @r###"
-    @    G
-    ├─╮
-    │ ◉  C
-    │ ◉  B
-    ├─╯
+    @  G
+    ◉  C
+    ◉  B
     ◉  A
     │ ◉    F
     │ ├─╮
@@ -290,6 +287,76 @@ fn test_new_insert_after_children() {
     "###);
 }
 
+#[test]
+fn test_new_insert_after_revset() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    setup_before_insertion(&test_env, &repo_path);
+
+    // A revset may resolve to multiple insertion points
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["new", "-m", "G", "--insert-after", "B|D"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 2 descendant commits
+    Working copy now at: lylxulpl 26d47089 (empty) G
+    Parent commit      : vruxwmqv c9257eff D | (empty) D
+    Parent commit      : kkmpptxz bfd4157e B | (empty) B
+    "###);
+    insta::assert_snapshot!(get_short_log_output(&test_env, &repo_path), @r###"
+    ◉  C
+    │ ◉  F
+    ╭─┤
+    @ │    G
+    ├───╮
+    │ │ ◉  B
+    │ │ ◉  A
+    ◉ │ │  D
+    ├───╯
+    │ ◉  E
+    ├─╯
+    ◉  root
+    "###);
+}
+
+#[test]
+fn test_new_insert_before_revset() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    setup_before_insertion(&test_env, &repo_path);
+
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["new", "-m", "G", "--insert-before", "C|F"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 2 descendant commits
+    Working copy now at: lylxulpl 702b9bae (empty) G
+    Parent commit      : vruxwmqv c9257eff D | (empty) D
+    Parent commit      : znkkpsqq 41a89ffc E | (empty) E
+    Parent commit      : kkmpptxz bfd4157e B | (empty) B
+    "###);
+    insta::assert_snapshot!(get_short_log_output(&test_env, &repo_path), @r###"
+    ◉  C
+    │ ◉  F
+    ├─╯
+    @      G
+    ├─┬─╮
+    │ │ ◉  B
+    │ │ ◉  A
+    │ ◉ │  E
+    │ ├─╯
+    ◉ │  D
+    ├─╯
+    ◉  root
+    "###);
+
+    let stderr =
+        test_env.jj_cmd_failure(&repo_path, &["new", "-m", "H", "--insert-before", "none()"]);
+    insta::assert_snapshot!(stderr, @"Error: Empty revision set");
+}
+
 #[test]
 fn test_new_insert_before() {
     let test_env = TestEnvironment::default();
@@ -355,6 +422,40 @@ fn test_new_insert_before() {
     "###);
 }
 
+#[test]
+fn test_new_insert_before_no_edit() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    setup_before_insertion(&test_env, &repo_path);
+
+    // The working copy is rebased onto the new commit, but isn't moved to it
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["new", "--no-edit", "-m", "G", "--insert-before", "F"],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Created new commit lylxulpl d18738ca (empty) G
+    Rebased 1 descendant commits
+    Working copy now at: kmkuslsw e188e01b F | (empty) F
+    Parent commit      : lylxulpl d18738ca (empty) G
+    "###);
+    insta::assert_snapshot!(get_short_log_output(&test_env, &repo_path), @r###"
+    @  F
+    ◉    G
+    ├─╮
+    │ ◉  E
+    ◉ │  D
+    ├─╯
+    │ ◉  C
+    │ ◉  B
+    │ ◉  A
+    ├─╯
+    ◉  root
+    "###);
+}
+
 #[test]
 fn test_new_insert_before_root_successors() {
     let test_env = TestEnvironment::default();
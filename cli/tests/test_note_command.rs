@@ -0,0 +1,123 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_note_set_show() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    // No note yet
+    let stdout = test_env.jj_cmd_success(&repo_path, &["note", "show"]);
+    insta::assert_snapshot!(stdout, @"");
+
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["note", "set", "-m", "TODO: tests"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Set note on qpvuntsm 230dd059 (empty) (no description set)
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["note", "show", "-r", "@"]);
+    insta::assert_snapshot!(stdout, @r###"
+    TODO: tests
+    "###);
+
+    // Setting the same note again is a no-op
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["note", "set", "-m", "TODO: tests"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Nothing changed.
+    "###);
+
+    // The note follows the change when it's rewritten
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "first"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "second"]);
+    test_env.jj_cmd_ok(&repo_path, &["rebase", "-r", "@-", "-d", "@"]);
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r###"
+    ◉  qpvuntsmwlqt first
+    │  TODO: tests
+    @  yqosqzytrlsw second
+    ◉  zzzzzzzzzzzz
+    "###);
+
+    // Setting a note can be undone
+    test_env.jj_cmd_ok(&repo_path, &["note", "set", "-r", "@", "-m", "second note"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["note", "show", "-r", "@"]);
+    insta::assert_snapshot!(stdout, @r###"
+    second note
+    "###);
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["note", "show", "-r", "@"]);
+    insta::assert_snapshot!(stdout, @"");
+
+    // An empty message removes the note
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["note", "set", "-r", "description(first)", "-m", ""],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Removed note from qpvuntsm 5123b4da (empty) first
+    "###);
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r###"
+    ◉  qpvuntsmwlqt first
+    @  yqosqzytrlsw second
+    ◉  zzzzzzzzzzzz
+    "###);
+}
+
+#[test]
+fn test_note_list() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "first"]);
+    test_env.jj_cmd_ok(&repo_path, &["note", "set", "-m", "line 1\nline 2"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "second"]);
+    test_env.jj_cmd_ok(&repo_path, &["note", "set", "-m", "abandon me"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["note", "list"]);
+    insta::assert_snapshot!(stdout, @r###"
+    zsuskuln 3dc07d5e (empty) second
+      abandon me
+    qpvuntsm fa15625b (empty) first
+      line 1
+      line 2
+    "###);
+
+    // Notes of abandoned changes are kept, but only listed with --all
+    test_env.jj_cmd_ok(&repo_path, &["abandon", "@"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["note", "list"]);
+    insta::assert_snapshot!(stdout, @r###"
+    qpvuntsm fa15625b (empty) first
+      line 1
+      line 2
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["note", "list", "--all"]);
+    insta::assert_snapshot!(stdout, @r###"
+    zsuskulnrvyr (no visible commit)
+      abandon me
+    qpvuntsm fa15625b (empty) first
+      line 1
+      line 2
+    "###);
+}
+
+fn get_log_output(test_env: &TestEnvironment, repo_path: &Path) -> String {
+    let template = r#"separate(" ", change_id.short(), description.first_line()) ++ "\n" ++ note"#;
+    test_env.jj_cmd_success(repo_path, &["log", "-T", template])
+}
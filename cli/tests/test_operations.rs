@@ -146,6 +146,7 @@ fn test_op_log_with_no_template() {
     - builtin_op_log_compact
     - builtin_op_log_node
     - builtin_op_log_node_ascii
+    - builtin_op_show
     - commit_summary_separator
     - description_placeholder
     - email_placeholder
@@ -165,6 +166,453 @@ fn test_op_log_limit() {
     "###);
 }
 
+#[test]
+fn test_op_log_reversed() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "description 0"]);
+    // Create concurrent operations
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["branch", "create", "--at-op", "@-", "-r", "@", "concurrent"],
+    );
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "description 1"]);
+
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "log", "-Tdescription", "--reversed"]);
+    insta::assert_snapshot!(stdout, @r###"
+    ◉
+    ◉  initialize repo
+    ◉    add workspace 'default'
+    ├─╮
+    │ ◉  create branch concurrent pointing to commit 230dd059e1b059aefc0da06a2e5a7dbf22362f22
+    ◉ │  describe commit 230dd059e1b059aefc0da06a2e5a7dbf22362f22
+    ├─╯
+    ◉  resolve concurrent operations
+    @  new empty commit
+    "###);
+
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "op",
+            "log",
+            r#"-Tdescription ++ "\n""#,
+            "--reversed",
+            "--no-graph",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+
+    initialize repo
+    add workspace 'default'
+    create branch concurrent pointing to commit 230dd059e1b059aefc0da06a2e5a7dbf22362f22
+    describe commit 230dd059e1b059aefc0da06a2e5a7dbf22362f22
+    resolve concurrent operations
+    new empty commit
+    "###);
+
+    // The limit is applied from the oldest operation
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["op", "log", "-Tdescription", "--reversed", "--limit=2"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    ◉
+    ◉  initialize repo
+    "###);
+}
+
+#[test]
+fn test_op_log_show_future() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "description 1"]);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "description 2"]);
+
+    // Newer operations are hidden by default
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["op", "log", "-Tdescription", "--at-op=@--"]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  add workspace 'default'
+    ◉  initialize repo
+    ◉
+    "###);
+    insta::assert_snapshot!(stderr, @"Hint: 2 newer operations are not shown. Use `--show-future` to show them.");
+
+    // They can be shown, with the "@" marker staying on the selected operation
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["op", "log", "-Tdescription", "--at-op=@--", "--show-future"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    ◉  describe commit d13ecdbda2a2e7471a29db2bed6f08ca28f91264
+    ◉  describe commit 230dd059e1b059aefc0da06a2e5a7dbf22362f22
+    @  add workspace 'default'
+    ◉  initialize repo
+    ◉
+    "###);
+    insta::assert_snapshot!(stderr, @"");
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "op",
+            "log",
+            "--at-op=@-",
+            "--show-future",
+            "--no-graph",
+            "--color=always",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    [38;5;8m50ae57288e08 test-username@host.example.com 2001-02-03 04:05:09.000 +07:00 - 2001-02-03 04:05:09.000 +07:00[39m
+    [38;5;8mdescribe commit d13ecdbda2a2e7471a29db2bed6f08ca28f91264[39m
+    [38;5;8margs: jj describe -m 'description 2'[39m
+    [1m[38;5;12m7d0e8d4c6ffd[39m [38;5;3mtest-username@host.example.com[39m [38;5;14m2001-02-03 04:05:08.000 +07:00[39m - [38;5;14m2001-02-03 04:05:08.000 +07:00[39m[0m
+    [1mdescribe commit 230dd059e1b059aefc0da06a2e5a7dbf22362f22[0m
+    [1m[38;5;5margs: jj describe -m 'description 1'[39m[0m
+    [38;5;4mb51416386f26[39m [38;5;3mtest-username@host.example.com[39m [38;5;6m2001-02-03 04:05:07.000 +07:00[39m - [38;5;6m2001-02-03 04:05:07.000 +07:00[39m
+    add workspace 'default'
+    [38;5;4m9a7d829846af[39m [38;5;3mtest-username@host.example.com[39m [38;5;6m2001-02-03 04:05:07.000 +07:00[39m - [38;5;6m2001-02-03 04:05:07.000 +07:00[39m
+    initialize repo
+    [38;5;4m000000000000[39m [38;5;2mroot()[39m
+    "###);
+
+    // There's nothing to hint at the head operation
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["op", "log", "--show-future"]);
+    insta::assert_snapshot!(stderr, @"");
+
+    // The commit log tells how many operations were made after the selected one
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["log", "--at-op=@--"]);
+    insta::assert_snapshot!(stderr, @"Hint: Showing the repo at operation b51416386f26. There are 2 newer operations.");
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["log", "--at-op=@-"]);
+    insta::assert_snapshot!(stderr, @"Hint: Showing the repo at operation 7d0e8d4c6ffd. There is 1 newer operation.");
+
+    // Divergent operations aren't newer than the selected one
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["describe", "--at-op=7d0e8d4c6ffd", "-m", "divergent"],
+    );
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["log", "--at-op=50ae57288e08"]);
+    insta::assert_snapshot!(stderr, @"");
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["op", "log", "--at-op=50ae57288e08"]);
+    insta::assert_snapshot!(stderr, @"");
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["log", "--at-op=7d0e8d4c6ffd"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Hint: Showing the repo at operation 7d0e8d4c6ffd. There are 2 newer operations.
+    "###);
+}
+
+#[test]
+fn test_op_log_stat() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "commit 1"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "commit 2"]);
+    test_env.jj_cmd_ok(&repo_path, &["abandon", "@-"]);
+    // Create concurrent operations so that the next command merges them
+    let op_id = test_env.jj_cmd_success(
+        &repo_path,
+        &["op", "log", "--no-graph", "-n1", "-Tid.short()"],
+    );
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["new", "root()", "--at-op", &op_id, "-m", "side 1"],
+    );
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["new", "root()", "--at-op", &op_id, "-m", "side 2"],
+    );
+    test_env.jj_cmd_ok(&repo_path, &["status"]);
+
+    // The commits added by both sides of the merge operation aren't counted
+    // again by the merge operation
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["op", "log", "-Tdescription ++ \"\\n\"", "--stat"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    @    resolve concurrent operations
+    ├─╮  commits: +0 -0, 4 total
+    ◉ │  new empty commit
+    │ │  commits: +1 -0, 3 total
+    │ ◉  new empty commit
+    ├─╯  commits: +1 -0, 3 total
+    ◉  abandon commit 2de2688b2c79c6f675377ad6ebf1b7b12b4e31b3
+    │  commits: +1 -2, 2 total
+    ◉  new empty commit
+    │  commits: +1 -0, 3 total
+    ◉  new empty commit
+    │  commits: +1 -0, 2 total
+    ◉  add workspace 'default'
+    │  commits: +1 -0, 1 total
+    ◉  initialize repo
+    │  commits: +0 -0, 0 total
+    ◉
+       commits: +0 -0, 0 total
+    "###);
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "op",
+            "log",
+            "-Tdescription ++ \"\\n\"",
+            "--stat",
+            "--no-graph",
+            "-n2",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    resolve concurrent operations
+    commits: +0 -0, 4 total
+    new empty commit
+    commits: +1 -0, 3 total
+    "###);
+}
+
+#[test]
+fn test_op_log_format_tsv() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "tab\there\nnewline"]);
+    // Create concurrent operations
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["branch", "create", "--at-op", "@-", "-r", "@", "concurrent"],
+    );
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "log", "--format=tsv"]);
+    // Ids are shortened to keep the snapshot readable
+    let lines = stdout
+        .lines()
+        .map(|line| {
+            let fields = line.split('\t').collect_vec();
+            assert_eq!(fields.len(), 6, "{line:?}");
+            assert_eq!(fields[0].len(), 128);
+            let parent_ids = fields[1].split_whitespace().map(|id| &id[..12]).join(" ");
+            [
+                &fields[0][..12],
+                &parent_ids,
+                fields[2],
+                fields[3],
+                fields[4],
+                fields[5],
+            ]
+            .join(" | ")
+        })
+        .join("\n");
+    insta::assert_snapshot!(lines, @r###"
+    a23e798ad7a9 | 7862ae921d02 | 2001-02-03T04:05:10.000+07:00 | 2001-02-03T04:05:10.000+07:00 | test-username@host.example.com | jj new
+    7862ae921d02 | 39fb489fbd87 228c365b76fc | 2001-02-03T04:05:10.000+07:00 | 2001-02-03T04:05:10.000+07:00 | test-username@host.example.com | jj new
+    39fb489fbd87 | b51416386f26 | 2001-02-03T04:05:08.000+07:00 | 2001-02-03T04:05:08.000+07:00 | test-username@host.example.com | jj describe -m 'tab\there\nnewline'
+    228c365b76fc | b51416386f26 | 2001-02-03T04:05:09.000+07:00 | 2001-02-03T04:05:09.000+07:00 | test-username@host.example.com | jj branch create --at-op @- -r @ concurrent
+    b51416386f26 | 9a7d829846af | 2001-02-03T04:05:07.000+07:00 | 2001-02-03T04:05:07.000+07:00 | test-username@host.example.com | add workspace 'default'
+    9a7d829846af | 000000000000 | 2001-02-03T04:05:07.000+07:00 | 2001-02-03T04:05:07.000+07:00 | test-username@host.example.com | initialize repo
+    000000000000 |  | 1970-01-01T00:00:00.000+00:00 | 1970-01-01T00:00:00.000+00:00 | @ | 
+    "###);
+
+    // The limit and --reversed apply as usual
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["op", "log", "--format=tsv", "--reversed", "--limit=1"],
+    );
+    insta::assert_snapshot!(stdout.replace('\t', "<TAB>"), @r###"
+    00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000<TAB><TAB>1970-01-01T00:00:00.000+00:00<TAB>1970-01-01T00:00:00.000+00:00<TAB>@<TAB>
+    "###);
+
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["op", "log", "--format=tsv", "--stat"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--format <FORMAT>' cannot be used with '--stat'
+
+    Usage: jj operation log --format <FORMAT>
+
+    For more information, try '--help'.
+    "###);
+}
+
+#[test]
+fn test_op_log_trivial() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    let template = r#"id.short() ++ "\n" ++ description ++ "\n""#;
+
+    // The current operation is shown even if it only snapshotted the working
+    // copy
+    std::fs::write(repo_path.join("file"), "contents").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["debug", "snapshot"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  e1e762d39b39
+    │  snapshot working copy
+    ◉  b51416386f26
+    │  add workspace 'default'
+    ◉  9a7d829846af
+    │  initialize repo
+    ◉  000000000000
+    "###);
+
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  0045198c6050
+    ╷  new empty commit
+    ◉  b51416386f26
+    │  add workspace 'default'
+    ◉  9a7d829846af
+    │  initialize repo
+    ◉  000000000000
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "log", "-T", template, "--all"]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  0045198c6050
+    │  new empty commit
+    ◉  e1e762d39b39
+    │  snapshot working copy
+    ◉  b51416386f26
+    │  add workspace 'default'
+    ◉  9a7d829846af
+    │  initialize repo
+    ◉  000000000000
+    "###);
+
+    // Snapshots can be selected explicitly
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "op",
+            "log",
+            "-T",
+            template,
+            "--no-graph",
+            "-r",
+            "snapshot()",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    e1e762d39b39
+    snapshot working copy
+    "###);
+}
+
+#[test]
+fn test_op_log_opset() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "initial"]);
+    let initial_commit_id =
+        test_env.jj_cmd_success(&repo_path, &["log", "--no-graph", "-r@", "-T", "commit_id"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "main"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "child"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "feature"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "set", "main", "-r@"]);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "@-", "-m", "renamed"]);
+    let template = r#"id.short() ++ "\n" ++ description ++ "\n""#;
+
+    // Operations are connected to their closest shown ancestors
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["op", "log", "-T", template, "-r", r#"command("branch")"#],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    ◉  ef71267920b9
+    │  point branch main to commit 6dcea6fadd2cc91c91906929d53e159487fadde1
+    ◉  4baf6cbc85e8
+    ╷  create branch feature pointing to commit 6dcea6fadd2cc91c91906929d53e159487fadde1
+    ◉  07d58eaa944a
+       create branch main pointing to commit 68e1101283b0a6c4694f92fab85bcd90f0c83652
+    "###);
+
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["op", "log", "-T", template, "-r", "branches(exact:main)"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    @  d41a19e34f33
+    │  describe commit 68e1101283b0a6c4694f92fab85bcd90f0c83652
+    ◉  ef71267920b9
+    ╷  point branch main to commit 6dcea6fadd2cc91c91906929d53e159487fadde1
+    ◉  07d58eaa944a
+       create branch main pointing to commit 68e1101283b0a6c4694f92fab85bcd90f0c83652
+    "###);
+
+    // The commit was created and rewritten by these operations
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "op",
+            "log",
+            "-T",
+            template,
+            "--no-graph",
+            "-r",
+            &format!("commit({initial_commit_id})"),
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    d41a19e34f33
+    describe commit 68e1101283b0a6c4694f92fab85bcd90f0c83652
+    6ceb832e776d
+    describe commit 230dd059e1b059aefc0da06a2e5a7dbf22362f22
+    "###);
+
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "op",
+            "log",
+            "-T",
+            template,
+            "--no-graph",
+            "-r",
+            r#"after("2001-02-03 04:05:10") & ~tags(glob:"*branch*")"#,
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    d41a19e34f33
+    describe commit 68e1101283b0a6c4694f92fab85bcd90f0c83652
+    d06e05579ba5
+    new empty commit
+    "###);
+
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["op", "log", "-r", "main"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Failed to parse opset: Expected function call, such as `description(pattern)`
+    Caused by:  --> 1:1
+      |
+    1 | main
+      | ^--^
+      |
+      = Expected function call, such as `description(pattern)`
+    "###);
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["op", "log", "-r", "branch(main)"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Failed to parse opset: Function "branch" doesn't exist
+    Caused by:  --> 1:1
+      |
+    1 | branch(main)
+      | ^----^
+      |
+      = Function "branch" doesn't exist
+    Hint: Did you mean "branches"?
+    "###);
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["op", "log", "-r", r#"after("yesterday")"#]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Failed to parse opset: Invalid date "yesterday"
+    Caused by:  --> 1:7
+      |
+    1 | after("yesterday")
+      |       ^---------^
+      |
+      = Invalid date "yesterday"
+    "###);
+}
+
 #[test]
 fn test_op_log_no_graph() {
     let test_env = TestEnvironment::default();
@@ -431,13 +879,14 @@ fn test_op_abandon_ancestors() {
     "###);
     insta::assert_snapshot!(
         test_env.jj_cmd_success(&repo_path, &["debug", "local-working-copy", "--ignore-working-copy"]), @r###"
-    Current operation: OperationId("0699d720d0cecd80fb7d765c45955708c61b12feb1d7ed9ff2777ae719471f04ffed3c1dc24efdbf94bdb74426065d6fa9a4f0862a89db2c8c8e359eefc45462")
+    Current operation: OperationId("e2463749e91e7b786a2eb45e1f45b3e83b49e706501c02720b7176d4e627a1a49c2e33a3e663d8431624f23b50748a14a38f54af47e840afdc4a696e52264b6d")
     Current tree: Merge(Resolved(TreeId("4b825dc642cb6eb9a060e54bf8d69288fbee4904")))
     "###);
     insta::assert_snapshot!(test_env.jj_cmd_success(&repo_path, &["op", "log"]), @r###"
-    @  0699d720d0ce test-username@host.example.com 2001-02-03 04:05:21.000 +07:00 - 2001-02-03 04:05:21.000 +07:00
+    @  e2463749e91e test-username@host.example.com 2001-02-03 04:05:21.000 +07:00 - 2001-02-03 04:05:21.000 +07:00
     │  undo operation d92d0753399f732e438bdd88fa7e5214cba2a310d120ec1714028a514c7116bcf04b4a0b26c04dbecf0a917f1d4c8eb05571b8816dd98b0502aaf321e92500b3
     │  args: jj undo
+    │  undone-operation: d92d0753399f732e438bdd88fa7e5214cba2a310d120ec1714028a514c7116bcf04b4a0b26c04dbecf0a917f1d4c8eb05571b8816dd98b0502aaf321e92500b3
     ◉  8545e0137524 test-username@host.example.com 2001-02-03 04:05:09.000 +07:00 - 2001-02-03 04:05:09.000 +07:00
     │  commit 81a4ef3dd421f3184289df1c58bd3a16ea1e3d8e
     │  args: jj commit -m 'commit 2'
@@ -450,9 +899,10 @@ fn test_op_abandon_ancestors() {
     Nothing changed.
     "###);
     insta::assert_snapshot!(test_env.jj_cmd_success(&repo_path, &["op", "log", "-n1"]), @r###"
-    @  0699d720d0ce test-username@host.example.com 2001-02-03 04:05:21.000 +07:00 - 2001-02-03 04:05:21.000 +07:00
+    @  e2463749e91e test-username@host.example.com 2001-02-03 04:05:21.000 +07:00 - 2001-02-03 04:05:21.000 +07:00
     │  undo operation d92d0753399f732e438bdd88fa7e5214cba2a310d120ec1714028a514c7116bcf04b4a0b26c04dbecf0a917f1d4c8eb05571b8816dd98b0502aaf321e92500b3
     │  args: jj undo
+    │  undone-operation: d92d0753399f732e438bdd88fa7e5214cba2a310d120ec1714028a514c7116bcf04b4a0b26c04dbecf0a917f1d4c8eb05571b8816dd98b0502aaf321e92500b3
     "###);
 }
 
@@ -507,9 +957,352 @@ fn test_op_abandon_without_updating_working_copy() {
     "###);
 }
 
+#[test]
+fn test_op_show() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config(r#"template-aliases.'format_time_range(x)' = 'x'"#);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "description 0"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "main"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "description 1"]);
+
+    // Shows the current operation by default
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "show"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Operation ID: 36a6471ee00032955199a6f9f31d95f5f79bc2bf52910c80d432e1453fb97efe220aaaf3ab425f63b8c4dd6c216dd7ce085926c03086a8d0227a5c0cb01893a8
+    User: test-username@host.example.com
+    Time: 2001-02-03 04:05:10.000 +07:00 - 2001-02-03 04:05:10.000 +07:00
+    Description: new empty commit
+    Tags:
+      args: jj new -m 'description 1'
+    Parent operations:
+      393d628674ab create branch main pointing to commit 19611c995a342c01f525583e5fcafdd211f6d009
+    This is the current operation.
+
+    Changed commits:
+      + zsuskuln 0427f566 (empty) description 1
+    Changed working copies:
+      default@: 19611c995a34 -> 0427f566a16a
+    "###);
+
+    // Shows branch changes
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "show", "@-"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Operation ID: 393d628674ab785126a4bae6db3f72066a56040236d51028b4c61f76aac511e923e9faff3872bae406e716d916adc8d18532a14adc914b5f997717bf2a3bf13e
+    User: test-username@host.example.com
+    Time: 2001-02-03 04:05:09.000 +07:00 - 2001-02-03 04:05:09.000 +07:00
+    Description: create branch main pointing to commit 19611c995a342c01f525583e5fcafdd211f6d009
+    Tags:
+      args: jj branch create main
+    Parent operations:
+      c1851f1c3d90 describe commit 230dd059e1b059aefc0da06a2e5a7dbf22362f22
+    This operation is an ancestor of the current operation.
+
+    Changed local branches:
+      main: (absent) -> 19611c995a34
+    "###);
+
+    // Shows the changes of an operation that is no longer reachable
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "op",
+            "log",
+            "--no-graph",
+            "-T",
+            "id.short() ++ \"\\n\"",
+            "-n1",
+        ],
+    );
+    let undone_op_id = stdout.trim().to_owned();
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    test_env.jj_cmd_ok(&repo_path, &["op", "abandon", &undone_op_id]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "show", &undone_op_id]);
+    insta::assert_snapshot!(stdout, @r###"
+    Operation ID: 36a6471ee00032955199a6f9f31d95f5f79bc2bf52910c80d432e1453fb97efe220aaaf3ab425f63b8c4dd6c216dd7ce085926c03086a8d0227a5c0cb01893a8
+    User: test-username@host.example.com
+    Time: 2001-02-03 04:05:10.000 +07:00 - 2001-02-03 04:05:10.000 +07:00
+    Description: new empty commit
+    Tags:
+      args: jj new -m 'description 1'
+    Parent operations:
+      393d628674ab create branch main pointing to commit 19611c995a342c01f525583e5fcafdd211f6d009
+    This operation is not reachable from the current operation.
+
+    Changed commits:
+      + zsuskuln hidden 0427f566 (empty) description 1
+    Changed working copies:
+      default@: 19611c995a34 -> 0427f566a16a
+    "###);
+
+    // The root operation has no changes
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "show", "000000000000"]);
+    insta::assert_snapshot!(stdout, @r###"
+    000000000000 root()
+    Parent operations:
+      (none)
+    This operation is an ancestor of the current operation.
+
+    The root operation has no changes.
+    "###);
+
+    // Custom template
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "op",
+            "show",
+            "@-",
+            "-T",
+            r#"description.first_line() ++ "\n""#,
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    create branch main pointing to commit 19611c995a342c01f525583e5fcafdd211f6d009
+    Parent operations:
+      c1851f1c3d90 describe commit 230dd059e1b059aefc0da06a2e5a7dbf22362f22
+    This operation is an ancestor of the current operation.
+
+    Changed local branches:
+      main: (absent) -> 19611c995a34
+    "###);
+}
+
+#[test]
+fn test_op_show_merge() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config(r#"template-aliases.'format_time_range(x)' = 'x'"#);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "description 0"]);
+    // Create concurrent operations
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["branch", "create", "--at-op", "@-", "-r", "@", "concurrent"],
+    );
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "description 1"]);
+
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "show", "@-"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Operation ID: e238afb0d3c7124de4040dd3209037513cf1ad5a99de0de6955d2d77d496ba13e3ef19ea1df28e05a31215e33e3e489bf0531987f5448182e0bb8c20339e39b9
+    User: test-username@host.example.com
+    Time: 2001-02-03 04:05:10.000 +07:00 - 2001-02-03 04:05:10.000 +07:00
+    Description: resolve concurrent operations
+    Tags:
+      args: jj new -m 'description 1'
+    Parent operations:
+      c1851f1c3d90 describe commit 230dd059e1b059aefc0da06a2e5a7dbf22362f22
+      228c365b76fc create branch concurrent pointing to commit 230dd059e1b059aefc0da06a2e5a7dbf22362f22
+    This operation is an ancestor of the current operation.
+
+    Changed local branches:
+      concurrent: 230dd059e1b0 -> 19611c995a34
+    "###);
+}
+
+#[test]
+fn test_op_show_patch() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    // Rewritten commit is compared to its previous version
+    std::fs::write(repo_path.join("file1"), "a\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m", "first"]);
+    let stdout =
+        test_env.jj_cmd_success(&repo_path, &["op", "show", "@-", "-T", "", "-p", "--git"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Parent operations:
+      b51416386f26 add workspace 'default'
+    This operation is an ancestor of the current operation.
+
+    Changed commits:
+      + qpvuntsm hidden b739eb46 (no description set)
+    diff --git a/file1 b/file1
+    new file mode 100644
+    index 0000000000..7898192261
+    --- /dev/null
+    +++ b/file1
+    @@ -1,0 +1,1 @@
+    +a
+      - qpvuntsm hidden 230dd059 (empty) (no description set)
+    Changed working copies:
+      default@: 230dd059e1b0 -> b739eb4695e9
+    "###);
+
+    // Newly created commit shows its changes as added, and the summary format
+    // can be used to keep the output small
+    test_env.jj_cmd_ok(&repo_path, &["duplicate", "description(first)"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "show", "-T", "", "--summary"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Parent operations:
+      d7a2b90260f1 commit b739eb4695e92fa2c403d65fddc8354ba753821f
+    This is the current operation.
+
+    Changed commits:
+      + zsuskuln ecfaf406 first
+    A file1
+    "###);
+
+    // Abandoned commit shows its changes as removed
+    test_env.jj_cmd_ok(&repo_path, &["abandon", "description(first) & ~@-"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "show", "-T", "", "-p"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Parent operations:
+      9da3ae7e94e1 duplicate 1 commit(s)
+    This is the current operation.
+
+    Changed commits:
+      - zsuskuln hidden ecfaf406 first
+    Removed regular file file1:
+       1     : a
+    "###);
+
+    // Changes of a merge operation are relative to the merged parents. The
+    // rebased commit has no content changes.
+    let base_op_id = test_env.current_operation_id(&repo_path);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "@-", "-m", "renamed"]);
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "new",
+            "--at-op",
+            &base_op_id,
+            "--no-edit",
+            "@-",
+            "-m",
+            "child",
+        ],
+    );
+    test_env.jj_cmd_ok(&repo_path, &["st"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "show", "-T", "", "-p"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Parent operations:
+      6ed891cd1a1e describe commit df9a1eb8e9f812ffddb9881f6ca54a181832f643
+      86056a8c55c7 new empty commit
+    This is the current operation.
+
+    Changed commits:
+      + znkkpsqq b0f8e147 (empty) child
+      - znkkpsqq hidden 6043b6c2 (empty) child
+      - qpvuntsm hidden df9a1eb8 first
+    "###);
+}
+
+#[test]
+fn test_op_diff() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file1"), "a\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m", "first"]);
+    let base_op_id = test_env.current_operation_id(&repo_path);
+
+    // By default, the current operation is compared to its parent
+    test_env.jj_cmd_ok(&repo_path, &["describe", "@-", "-m", "renamed"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "diff"]);
+    insta::assert_snapshot!(stdout, @r###"
+    From operation: d7a2b90260f1 commit b739eb4695e92fa2c403d65fddc8354ba753821f
+      To operation: fce9932ecde1 describe commit df9a1eb8e9f812ffddb9881f6ca54a181832f643
+
+    Changed commits:
+      + rlvkpnrz f4e98b08 (empty) (no description set)
+      + qpvuntsm 1f878241 renamed
+      - rlvkpnrz hidden b46193b8 (empty) (no description set)
+      - qpvuntsm hidden df9a1eb8 first
+    Changed working copies:
+      default@: b46193b8c4b7 -> f4e98b082cf2
+    "###);
+
+    // The combined content changes of several operations can be shown
+    std::fs::write(repo_path.join("file1"), "b\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["squash"]);
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["op", "diff", "--from", &base_op_id, "-p", "--git"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    From operation: d7a2b90260f1 commit b739eb4695e92fa2c403d65fddc8354ba753821f
+      To operation: 4bb590370733 squash commits into 1f878241563eb1366cb3b2c33bce5e4afd24ee18
+
+    Changed commits:
+      + royxmykx 9487656d (empty) (no description set)
+      + qpvuntsm c438f92c renamed
+    diff --git a/file1 b/file1
+    index 7898192261...6178079822 100644
+    --- a/file1
+    +++ b/file1
+    @@ -1,1 +1,1 @@
+    -a
+    +b
+      - rlvkpnrz hidden b46193b8 (empty) (no description set)
+      - qpvuntsm hidden df9a1eb8 first
+    Changed working copies:
+      default@: b46193b8c4b7 -> 9487656da141
+    "###);
+
+    // The operations may have diverged
+    let squash_op_id = test_env.current_operation_id(&repo_path);
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["new", "--at-op", &base_op_id, "@-", "-m", "child"],
+    );
+    test_env.jj_cmd_ok(&repo_path, &["st"]);
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "op",
+            "log",
+            "--no-graph",
+            "-T",
+            r#"id ++ " " ++ description ++ "\n""#,
+        ],
+    );
+    let (new_op_id, _) = stdout
+        .lines()
+        .find_map(|line| line.split_once(" new empty commit"))
+        .unwrap();
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "op",
+            "diff",
+            "--from",
+            &squash_op_id,
+            "--to",
+            new_op_id,
+            "--summary",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    From operation: 4bb590370733 squash commits into 1f878241563eb1366cb3b2c33bce5e4afd24ee18
+      To operation: 3146849f5b51 new empty commit
+
+    Changed commits:
+      + yostqsxw hidden 2ea7e246 (empty) child
+      + qpvuntsm hidden df9a1eb8 first
+    M file1
+      - royxmykx 9487656d (empty) (no description set)
+      - qpvuntsm c438f92c renamed
+    Changed working copies:
+      default@: 9487656da141 -> 2ea7e246d446
+    "###);
+
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "diff", "--from", "@", "--to", "@"]);
+    insta::assert_snapshot!(stdout, @r###"
+    From operation: 992a2a46a74f resolve concurrent operations
+      To operation: 992a2a46a74f resolve concurrent operations
+
+    No changes.
+    "###);
+}
+
 fn get_log_output(test_env: &TestEnvironment, repo_path: &Path, op_id: &str) -> String {
-    test_env.jj_cmd_success(
+    let (stdout, _stderr) = test_env.jj_cmd_ok(
         repo_path,
         &["log", "-T", "commit_id", "--at-op", op_id, "-r", "all()"],
-    )
+    );
+    stdout
 }
@@ -2262,7 +2262,8 @@ fn test_rebase_skip_empty() {
     let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["rebase", "-d=b", "--skip-empty"]);
     insta::assert_snapshot!(stdout, @"");
     insta::assert_snapshot!(stderr, @r###"
-    Rebased 3 commits
+    Rebased 2 commits
+    Abandoned 1 empty commit
     Working copy now at: yostqsxw 6b74c840 (empty) also already empty
     Parent commit      : vruxwmqv 48a31526 (empty) already empty
     "###);
@@ -2278,6 +2279,234 @@ fn test_rebase_skip_empty() {
     "###);
 }
 
+#[test]
+fn test_rebase_simplify_merges() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    create_commit(&test_env, &repo_path, "a", &[]);
+    create_commit(&test_env, &repo_path, "b", &["a"]);
+    create_commit(&test_env, &repo_path, "c", &["a"]);
+    create_commit(&test_env, &repo_path, "d", &["b", "c"]);
+    let setup_opid = test_env.current_operation_id(&repo_path);
+
+    // Without the option, "d" is kept as a merge of "b" and its descendant "c"
+    test_env.jj_cmd_ok(&repo_path, &["rebase", "-s", "c", "-d", "b"]);
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r###"
+    @    d
+    ├─╮
+    │ ◉  c
+    ├─╯
+    ◉  b
+    ◉  a
+    ◉
+    "###);
+
+    // The redundant parent "b" is removed, and the contents are unchanged
+    test_env.jj_cmd_ok(&repo_path, &["op", "restore", &setup_opid]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["rebase", "-s", "c", "-d", "b", "--simplify-merges"],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 2 commits
+    Removed redundant parents of 1 merge commits:
+      vruxwmqv 1dec1447 d | d
+    Working copy now at: vruxwmqv 1dec1447 d | d
+    Parent commit      : royxmykx 5f9dd001 c | c
+    "###);
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r###"
+    @  d
+    ◉  c
+    ◉  b
+    ◉  a
+    ◉
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "list", "-r", "d"]);
+    insta::assert_snapshot!(stdout, @r###"
+    a
+    b
+    c
+    d
+    "###);
+
+    // A genuine merge is kept
+    test_env.jj_cmd_ok(&repo_path, &["op", "restore", &setup_opid]);
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["rebase", "-s", "c", "-d", "root()", "--simplify-merges"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 2 commits
+    Working copy now at: vruxwmqv 9ec8e8b3 d | d
+    Parent commit      : zsuskuln 1394f625 b | b
+    Parent commit      : royxmykx 1889baef c | c
+    "###);
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r###"
+    @    d
+    ├─╮
+    │ ◉  c
+    ◉ │  b
+    ◉ │  a
+    ├─╯
+    ◉
+    "###);
+}
+
+#[test]
+fn test_rebase_empty_policy() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    create_commit(&test_env, &repo_path, "a", &[]);
+    create_commit(&test_env, &repo_path, "b", &["a"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "a", "-m", "will become empty"]);
+    test_env.jj_cmd_ok(&repo_path, &["restore", "--from=b"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "a"]);
+    test_env.jj_cmd_ok(&repo_path, &["restore", "--from=b"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "already empty"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "not empty"]);
+    std::fs::write(repo_path.join("file"), "file\n").unwrap();
+    let template = r#"separate(" ", description.first_line(), if(empty, "(empty)"))"#;
+
+    // Test the setup
+    insta::assert_snapshot!(test_env.jj_cmd_success(&repo_path, &["log", "-T", template]), @r###"
+    @  not empty
+    ◉  already empty (empty)
+    ◉
+    │ ◉  will become empty
+    ├─╯
+    │ ◉  b
+    ├─╯
+    ◉  a
+    ◉  (empty)
+    "###);
+    let setup_opid = test_env.current_operation_id(&repo_path);
+
+    // Commits that became empty are dropped, unless they have a description
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["rebase", "-s=all:a+ ~ b", "-d=b", "--empty=drop"],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 3 commits
+    Abandoned 1 empty commit
+    Working copy now at: kpqxywon acabd720 not empty
+    Parent commit      : znkkpsqq c32bb0ce (empty) already empty
+    "###);
+    insta::assert_snapshot!(test_env.jj_cmd_success(&repo_path, &["log", "-T", template]), @r###"
+    @  not empty
+    ◉  already empty (empty)
+    │ ◉  will become empty (empty)
+    ├─╯
+    ◉  b
+    ◉  a
+    ◉  (empty)
+    "###);
+
+    // All empty commits are kept
+    test_env.jj_cmd_ok(&repo_path, &["op", "restore", &setup_opid]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["rebase", "-s=all:a+ ~ b", "-d=b", "--empty=keep"],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 4 commits
+    Working copy now at: kpqxywon 86af8cb0 not empty
+    Parent commit      : znkkpsqq 38f3e00f (empty) already empty
+    "###);
+    insta::assert_snapshot!(test_env.jj_cmd_success(&repo_path, &["log", "-T", template]), @r###"
+    @  not empty
+    ◉  already empty (empty)
+    ◉  (empty)
+    │ ◉  will become empty (empty)
+    ├─╯
+    ◉  b
+    ◉  a
+    ◉  (empty)
+    "###);
+
+    // All empty commits are abandoned, even ones that were already empty
+    test_env.jj_cmd_ok(&repo_path, &["op", "restore", &setup_opid]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["rebase", "-s=all:a+ ~ b", "-d=b", "--empty=abandon"],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 1 commits
+    Abandoned 3 empty commits
+    Working copy now at: kpqxywon 06cf6f03 not empty
+    Parent commit      : zsuskuln 1394f625 b | b
+    "###);
+    insta::assert_snapshot!(test_env.jj_cmd_success(&repo_path, &["log", "-T", template]), @r###"
+    @  not empty
+    ◉  b
+    ◉  a
+    ◉  (empty)
+    "###);
+
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["rebase", "-r=b", "-d=a", "--empty=drop"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--revisions <REVISIONS>' cannot be used with '--empty <EMPTY>'
+
+    Usage: jj rebase --revisions <REVISIONS> <--destination <DESTINATION>|--insert-after <INSERT_AFTER>|--insert-before <INSERT_BEFORE>>
+
+    For more information, try '--help'.
+    "###);
+}
+
+#[test]
+fn test_rebase_empty_policy_merge() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    create_commit(&test_env, &repo_path, "a", &[]);
+    create_commit(&test_env, &repo_path, "b", &[]);
+    create_commit(&test_env, &repo_path, "c", &[]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "b", "c"]);
+    let template = r#"separate(" ", description.first_line(), if(empty, "(empty)"))"#;
+
+    // Test the setup
+    insta::assert_snapshot!(test_env.jj_cmd_success(&repo_path, &["log", "-T", template]), @r###"
+    @    (empty)
+    ├─╮
+    │ ◉  c
+    ◉ │  b
+    ├─╯
+    │ ◉  a
+    ├─╯
+    ◉  (empty)
+    "###);
+
+    // An empty merge commit isn't abandoned
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["rebase", "-s=b", "-d=a", "--empty=abandon"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 2 commits
+    Working copy now at: vruxwmqv d1362abe (empty) (no description set)
+    Parent commit      : zsuskuln 00079a64 b | b
+    Parent commit      : royxmykx fe2e8e8b c | c
+    Added 1 files, modified 0 files, removed 0 files
+    "###);
+    insta::assert_snapshot!(test_env.jj_cmd_success(&repo_path, &["log", "-T", template]), @r###"
+    @    (empty)
+    ├─╮
+    │ ◉  c
+    ◉ │  b
+    ◉ │  a
+    ├─╯
+    ◉  (empty)
+    "###);
+}
+
 #[test]
 fn test_rebase_skip_if_on_destination() {
     let test_env = TestEnvironment::default();
@@ -2394,6 +2623,123 @@ fn test_rebase_skip_if_on_destination() {
     "###);
 }
 
+#[test]
+fn test_rebase_update_refs() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    create_commit(&test_env, &repo_path, "a", &[]);
+    create_commit(&test_env, &repo_path, "b", &["a"]);
+    create_commit(&test_env, &repo_path, "c", &["b"]);
+    create_commit(&test_env, &repo_path, "d", &["a"]);
+    // Test the setup
+    insta::assert_snapshot!(get_branch_log_output(&test_env, &repo_path), @r###"
+    @  vruxwmqv 4cc44fbf d
+    │ ◉  royxmykx 7e4fbf4f c
+    │ ◉  zsuskuln 1394f625 b
+    ├─╯
+    ◉  rlvkpnrz 2443ea76 a
+    ◉  zzzzzzzz 00000000
+    "###);
+
+    // Branches are left on the original commits
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["rebase", "-s", "b", "-d", "d", "--no-update-refs"],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 2 commits
+    Left 2 branches on the original commits
+    "###);
+    insta::assert_snapshot!(get_branch_log_output(&test_env, &repo_path), @r###"
+    ◉  royxmykx e4235c31
+    ◉  zsuskuln ad4951c3
+    @  vruxwmqv 4cc44fbf d
+    │ ◉  royxmykx 7e4fbf4f c
+    │ ◉  zsuskuln 1394f625 b
+    ├─╯
+    ◉  rlvkpnrz 2443ea76 a
+    ◉  zzzzzzzz 00000000
+    "###);
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+
+    // By default, branches follow the rebased commits
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["rebase", "-s", "b", "-d", "d"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @"Rebased 2 commits");
+    insta::assert_snapshot!(get_branch_log_output(&test_env, &repo_path), @r###"
+    ◉  royxmykx 5e65ea51 c
+    ◉  zsuskuln 209e3fcf b
+    @  vruxwmqv 4cc44fbf d
+    ◉  rlvkpnrz 2443ea76 a
+    ◉  zzzzzzzz 00000000
+    "###);
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+
+    // A branch on a commit that became empty moves to its parent
+    create_commit(&test_env, &repo_path, "e", &["c"]);
+    std::fs::remove_file(repo_path.join("e")).unwrap();
+    std::fs::write(repo_path.join("d"), "d\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["new", "d"]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["rebase", "-s", "b", "-d", "d", "--skip-empty"],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 2 commits
+    Abandoned 1 empty commit
+    Moved branch e to the parent of its abandoned commit: royxmykx 2176185f c e | c
+    "###);
+    insta::assert_snapshot!(get_branch_log_output(&test_env, &repo_path), @r###"
+    ◉  royxmykx 2176185f c e
+    ◉  zsuskuln 19422abd b
+    │ @  xtnwkqum 3d25c544
+    ├─╯
+    ◉  vruxwmqv 4cc44fbf d
+    ◉  rlvkpnrz 2443ea76 a
+    ◉  zzzzzzzz 00000000
+    "###);
+}
+
+#[test]
+fn test_rebase_insert_after_update_refs() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    create_commit(&test_env, &repo_path, "a", &[]);
+    create_commit(&test_env, &repo_path, "b", &["a"]);
+    create_commit(&test_env, &repo_path, "c", &["b"]);
+    create_commit(&test_env, &repo_path, "d", &["a"]);
+
+    // Branches follow commits moved into the middle of a chain
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["rebase", "-r", "d", "--insert-after", "b"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 1 commits onto destination
+    Rebased 1 descendant commits
+    Working copy now at: vruxwmqv 2beaf94f d | d
+    Parent commit      : zsuskuln 1394f625 b | b
+    Added 1 files, modified 0 files, removed 0 files
+    "###);
+    insta::assert_snapshot!(get_branch_log_output(&test_env, &repo_path), @r###"
+    ◉  royxmykx 8d6fbf7e c
+    @  vruxwmqv 2beaf94f d
+    ◉  zsuskuln 1394f625 b
+    ◉  rlvkpnrz 2443ea76 a
+    ◉  zzzzzzzz 00000000
+    "###);
+}
+
+fn get_branch_log_output(test_env: &TestEnvironment, repo_path: &Path) -> String {
+    let template = r#"separate(" ", change_id.shortest(8), commit_id.shortest(8), branches)"#;
+    test_env.jj_cmd_success(repo_path, &["log", "-T", template])
+}
+
 fn get_long_log_output(test_env: &TestEnvironment, repo_path: &Path) -> String {
     let template = r#"description.first_line() ++ "  " ++ change_id.shortest(8) ++ "  " ++ commit_id.shortest(8)"#;
     test_env.jj_cmd_success(repo_path, &["log", "-T", template])
@@ -16,7 +16,7 @@ use std::path::Path;
 
 use indoc::indoc;
 
-use crate::common::TestEnvironment;
+use crate::common::{get_stderr_string, get_stdout_string, TestEnvironment};
 
 fn create_commit(
     test_env: &TestEnvironment,
@@ -981,3 +981,177 @@ fn test_multiple_conflicts() {
     Error: No conflicts found at this revision
     "###);
 }
+
+#[test]
+fn test_resolve_pick_side() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    create_commit(
+        &test_env,
+        &repo_path,
+        "base",
+        &[],
+        &[("file1", "base1\n"), ("file2", "base2\n")],
+    );
+    create_commit(
+        &test_env,
+        &repo_path,
+        "a",
+        &["base"],
+        &[("file1", "a1\n"), ("file2", "a2\n")],
+    );
+    create_commit(
+        &test_env,
+        &repo_path,
+        "b",
+        &["base"],
+        &[("file1", "b1\n"), ("file2", "b2\n")],
+    );
+    create_commit(&test_env, &repo_path, "c", &["base"], &[("file1", "c1\n")]);
+    create_commit(&test_env, &repo_path, "conflict", &["a", "b"], &[]);
+
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["resolve", "--ours", "file1"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Resolved 1 conflict by picking side #1
+    New conflicts appeared in these commits:
+      znkkpsqq ad4bfcfe conflict | (conflict) conflict
+    To resolve the conflicts, start by updating to it:
+      jj new znkkpsqqskkl
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you may want inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    Working copy now at: znkkpsqq ad4bfcfe conflict | (conflict) conflict
+    Parent commit      : zsuskuln a647189e a | a
+    Parent commit      : royxmykx e065d727 b | b
+    Added 0 files, modified 1 files, removed 0 files
+    There are unresolved conflicts at these paths:
+    file2    2-sided conflict
+    "###);
+    insta::assert_snapshot!(std::fs::read_to_string(repo_path.join("file1")).unwrap(), @"a1");
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["resolve", "--theirs"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Resolved 1 conflict by picking side #2
+    Existing conflicts were resolved or abandoned from these commits:
+      znkkpsqq hidden ad4bfcfe (conflict) conflict
+    Working copy now at: znkkpsqq 3590b366 conflict | conflict
+    Parent commit      : zsuskuln a647189e a | a
+    Parent commit      : royxmykx e065d727 b | b
+    Added 0 files, modified 1 files, removed 0 files
+    "###);
+    insta::assert_snapshot!(std::fs::read_to_string(repo_path.join("file2")).unwrap(), @"b2");
+
+    // Conflicts with more than two sides are skipped
+    test_env.jj_cmd_ok(&repo_path, &["new", "a", "b", "c"]);
+    insta::assert_snapshot!(test_env.jj_cmd_success(&repo_path, &["resolve", "--list"]), @r###"
+    file1    3-sided conflict
+    file2    2-sided conflict
+    "###);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["resolve", "--theirs"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: Skipping file1 since it's a 3-sided conflict
+    Resolved 1 conflict by picking side #2
+    New conflicts appeared in these commits:
+      lylxulpl 4be73565 (conflict) (no description set)
+    To resolve the conflicts, start by updating to it:
+      jj new lylxulplsnyw
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you may want inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    Working copy now at: lylxulpl 4be73565 (conflict) (no description set)
+    Parent commit      : zsuskuln a647189e a | a
+    Parent commit      : royxmykx e065d727 b | b
+    Parent commit      : vruxwmqv a505a532 c | c
+    Added 0 files, modified 1 files, removed 0 files
+    There are unresolved conflicts at these paths:
+    file1    3-sided conflict
+    "###);
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["resolve", "--ours"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: Skipping file1 since it's a 3-sided conflict
+    Error: None of the conflicts could be resolved
+    "###);
+}
+
+#[test]
+fn test_resolve_check() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    create_commit(&test_env, &repo_path, "base", &[], &[("file", "base\n")]);
+    create_commit(&test_env, &repo_path, "a", &["base"], &[("file", "a\n")]);
+    create_commit(&test_env, &repo_path, "b", &["base"], &[("file", "b\n")]);
+    create_commit(&test_env, &repo_path, "conflict", &["a", "b"], &[]);
+
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["resolve", "--check", "-r=a"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    No conflict markers found.
+    "###);
+
+    // Conflict markers committed in resolved files are reported, but text that
+    // merely resembles markers isn't
+    create_commit(
+        &test_env,
+        &repo_path,
+        "markers",
+        &[],
+        &[
+            (
+                "committed",
+                indoc! {"
+                    line 1
+                    <<<<<<< Conflict 1 of 1
+                    %%%%%%% Changes from base to side #1
+                    -base
+                    +a
+                    +++++++ Contents of side #2
+                    b
+                    >>>>>>> Conflict 1 of 1 ends
+                "},
+            ),
+            (
+                "lookalike",
+                indoc! {"
+                    <<<<<<< not a conflict
+                    =======
+                    >>>>>>> not a conflict either
+                "},
+            ),
+        ],
+    );
+    let assert = test_env
+        .jj_cmd(&repo_path, &["resolve", "--check"])
+        .assert()
+        .code(1);
+    insta::assert_snapshot!(get_stdout_string(&assert), @r###"
+    committed:2: conflict markers
+    "###);
+    insta::assert_snapshot!(get_stderr_string(&assert), @r###"
+    Error: Found conflicts in 1 file
+    "###);
+
+    // Conflicted files are reported too
+    let assert = test_env
+        .jj_cmd(&repo_path, &["resolve", "--check", "-r=conflict"])
+        .assert()
+        .code(1);
+    insta::assert_snapshot!(get_stdout_string(&assert), @r###"
+    file: unresolved conflict
+    "###);
+    insta::assert_snapshot!(get_stderr_string(&assert), @r###"
+    Error: Found conflicts in 1 file
+    "###);
+
+    // Scoped by fileset
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["resolve", "--check", "lookalike"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    No conflict markers found.
+    "###);
+}
@@ -250,6 +250,16 @@ fn test_bad_function_call() {
       |
       = expected <expression>
     "###);
+
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["log", "-r", "fork_point(@, none())"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Cannot find the fork point of an empty set of revisions
+    "###);
+
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["log", "-r", "midpoint(@..@)"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Cannot find the midpoint of an empty set of revisions
+    "###);
 }
 
 #[test]
@@ -361,13 +371,13 @@ fn test_alias() {
 
     let stderr = test_env.jj_cmd_failure(&repo_path, &["log", "-r", "identity()"]);
     insta::assert_snapshot!(stderr, @r###"
-    Error: Failed to parse revset: Function "identity": Expected 1 arguments
+    Error: Failed to parse revset: Function "identity": Expected 1 arguments (declared as alias "identity(x)")
     Caused by:  --> 1:10
       |
     1 | identity()
       |          ^
       |
-      = Function "identity": Expected 1 arguments
+      = Function "identity": Expected 1 arguments (declared as alias "identity(x)")
     "###);
 
     let stderr = test_env.jj_cmd_failure(&repo_path, &["log", "-r", "my_author(none())"]);
@@ -70,6 +70,7 @@ fn test_show_with_no_template() {
     - builtin_op_log_compact
     - builtin_op_log_node
     - builtin_op_log_node_ascii
+    - builtin_op_show
     - commit_summary_separator
     - description_placeholder
     - email_placeholder
@@ -105,3 +106,259 @@ fn test_show_relative_timestamps() {
         (no description set)
     "###);
 }
+
+#[test]
+fn test_show_multiple_revisions() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file1"), "foo\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m", "first"]);
+    std::fs::write(repo_path.join("file2"), "bar\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "second"]);
+
+    // Parents are shown first
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["show", "-T", "description", "-r", "@ | @-", "--git"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    first
+    diff --git a/file1 b/file1
+    new file mode 100644
+    index 0000000000..257cc5642c
+    --- /dev/null
+    +++ b/file1
+    @@ -1,0 +1,1 @@
+    +foo
+
+    second
+    diff --git a/file2 b/file2
+    new file mode 100644
+    index 0000000000..5716ca5987
+    --- /dev/null
+    +++ b/file2
+    @@ -1,0 +1,1 @@
+    +bar
+    "###);
+
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "show",
+            "-T",
+            "description",
+            "-r",
+            "::@ ~ root()",
+            "--summary",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    first
+    A file1
+
+    second
+    A file2
+    "###);
+
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["show", "-T", "description", "@-", "@", "--no-patch"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    first
+
+    second
+    "###);
+
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["show", "--no-patch", "--git"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--no-patch' cannot be used with '--git'
+
+    Usage: jj show --no-patch [REVISIONS]...
+
+    For more information, try '--help'.
+    "###);
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["show", "--no-patch", "--binary"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--no-patch' cannot be used with '--binary'
+
+    Usage: jj show --no-patch [REVISIONS]...
+
+    For more information, try '--help'.
+    "###);
+    let stderr = test_env.jj_cmd_cli_error(&repo_path, &["show", "--no-patch", "--context", "5"]);
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--no-patch' cannot be used with '--context <CONTEXT>'
+
+    Usage: jj show --no-patch [REVISIONS]...
+
+    For more information, try '--help'.
+    "###);
+
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["show", "none()"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    No revisions to show.
+    "###);
+}
+
+#[test]
+fn test_show_no_patch_with_stat() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file"), "base\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m", "base"]);
+    std::fs::write(repo_path.join("file"), "left\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "left"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "description(base)", "-m", "right"]);
+    std::fs::write(repo_path.join("file"), "right\n").unwrap();
+    std::fs::write(repo_path.join("other"), "other\n").unwrap();
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "new",
+            "description(left)",
+            "description(right)",
+            "-m",
+            "merge",
+        ],
+    );
+
+    // Only the stat is shown, without the patch
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "show",
+            "-T",
+            "description",
+            "--no-patch",
+            "--stat",
+            "description(right)",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    right
+    file  | 2 +-
+    other | 1 +
+    2 files changed, 2 insertions(+), 1 deletion(-)
+    "###);
+
+    // The merge is compared against the auto-merged parents, so the conflict
+    // inherited from them isn't listed
+    std::fs::write(repo_path.join("other"), "other\nmore\n").unwrap();
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["show", "-T", "description", "--no-patch", "--stat"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    merge
+    other | 1 +
+    1 file changed, 1 insertion(+), 0 deletions(-)
+    "###);
+
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["show", "-T", "description", "--no-patch", "--summary"],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    merge
+    M other
+    "###);
+
+    // A conflict introduced by the revision is marked
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "rebase",
+            "-r",
+            "description(right)",
+            "-d",
+            "description(left)",
+        ],
+    );
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "show",
+            "-T",
+            "description",
+            "--no-patch",
+            "--stat",
+            "description(right)",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    right
+    file  | 8 +++++++- (conflict)
+    other | 1 +
+    2 files changed, 8 insertions(+), 1 deletion(-)
+    "###);
+
+    // The conflict marker fits in the terminal width
+    test_env.add_env_var("COLUMNS", "30");
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "show",
+            "-T",
+            "description",
+            "--no-patch",
+            "--stat",
+            "description(right)",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    right
+    file  | 8 ++++++- (conflict)
+    other | 1 +
+    2 files changed, 8 insertions(+), 1 deletion(-)
+    "###);
+}
+
+#[test]
+fn test_show_external_tool() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file1"), "foo\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "left"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-m", "right"]);
+    std::fs::write(repo_path.join("file2"), "foo\n").unwrap();
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["new", "description(left)", "@", "-m", "merge"],
+    );
+    std::fs::write(repo_path.join("file3"), "foo\n").unwrap();
+
+    let edit_script = test_env.set_up_fake_diff_editor();
+    std::fs::write(
+        &edit_script,
+        "print-files-before\0print --\0print-files-after",
+    )
+    .unwrap();
+
+    // The merge commit is compared to the auto-merged parents
+    let stdout =
+        test_env.jj_cmd_success(&repo_path, &["show", "-T", "''", "--tool=fake-diff-editor"]);
+    insta::assert_snapshot!(stdout, @r###"
+    --
+    file3
+    "###);
+
+    // An unknown tool is reported along with the configured tools
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["show", "-T", "''", "--tool=no-such-tool"]);
+    // The OS error message varies by platform
+    let stderr = stderr
+        .lines()
+        .filter(|line| !line.starts_with("2: "))
+        .join("\n");
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Failed to generate diff
+    Caused by:
+    1: Error executing 'no-such-tool' (run with --debug to see the exact invocation)
+    Hint: Configure the tool in `merge-tools.<name>`, or use one of the configured tools: diffedit3, diffedit3-ssh, fake-diff-editor, kdiff3, meld, meld-3, vimdiff, vscode
+    "###);
+}
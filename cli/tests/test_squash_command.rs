@@ -1128,6 +1128,201 @@ fn test_squash_use_destination_message_and_message_mutual_exclusion() {
     "###);
 }
 
+#[test]
+fn test_squash_message_template() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    // The editor must not be opened
+    let edit_script = test_env.set_up_fake_editor();
+    std::fs::write(edit_script, "fail").unwrap();
+
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m=a"]);
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m=b"]);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m=c"]);
+
+    // Both the source and destination descriptions are available
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "squash",
+            "--from=description(b)::",
+            "--into=description(a)",
+            r#"--message-template=destination ++ sources.map(|s| "* " ++ s).join("")"#,
+        ],
+    );
+    insta::assert_snapshot!(get_description(&test_env, &repo_path, "@-"), @r###"
+    a
+    * b
+    * c
+    "###);
+
+    // An empty result keeps the destination description
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["squash", "--message-template", r#"if(false, "x")"#],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    The message template produced an empty description; keeping the description of the destination revision.
+    Working copy now at: vruxwmqv cb1f7541 (empty) (no description set)
+    Parent commit      : rlvkpnrz 04e55a19 (empty) b
+    "###);
+    insta::assert_snapshot!(get_description(&test_env, &repo_path, "@-"), @r###"
+    b
+    "###);
+
+    // Template errors are reported before squashing
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["squash", "--message-template=author"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Failed to parse template: Keyword "author" doesn't exist
+    Caused by:  --> 1:1
+      |
+    1 | author
+      | ^----^
+      |
+      = Keyword "author" doesn't exist
+    "###);
+}
+
+#[test]
+fn test_squash_files() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file1"), "a\n").unwrap();
+    std::fs::write(repo_path.join("file3"), "a\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m=a"]);
+    std::fs::write(repo_path.join("file3"), "b\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m=b"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-m=c"]);
+    std::fs::write(repo_path.join("file3"), "c\n").unwrap();
+    // Make file3 conflicted in "b"
+    test_env.jj_cmd_ok(&repo_path, &["rebase", "-s=description(b)", "-d=@"]);
+    test_env.jj_cmd_ok(&repo_path, &["edit", "description(b)"]);
+    std::fs::write(repo_path.join("file1"), "b\n").unwrap();
+    std::fs::write(repo_path.join("file2"), "b\n").unwrap();
+    insta::assert_snapshot!(get_log_output_with_description(&test_env, &repo_path), @r###"
+    @  8c84ee13025d b
+    ◉  4f7e934fec1b c
+    │ ◉  5d0d33b9b5cd a
+    ├─╯
+    ◉  000000000000
+    "###);
+
+    let edit_script = test_env.set_up_fake_editor();
+    let edit_files = |files: &[&str]| {
+        let mut content = "dump files\0write\n".to_owned();
+        for file in files {
+            content.push_str(file);
+            content.push('\n');
+        }
+        std::fs::write(&edit_script, content).unwrap();
+    };
+
+    // The changed files are listed, including the conflicted one
+    edit_files(&["file2", "file3"]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["squash", "--files"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 1 descendant commits
+    New conflicts appeared in these commits:
+      zsuskuln b834d7ea (conflict) c
+    To resolve the conflicts, start by updating to the first one:
+      jj new zsuskulnrvyr
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you may want inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    Working copy now at: rlvkpnrz 404193d4 (conflict) b
+    Parent commit      : zsuskuln b834d7ea (conflict) c
+    There are unresolved conflicts at these paths:
+    file3    2-sided conflict
+    "###);
+    insta::assert_snapshot!(
+        std::fs::read_to_string(test_env.env_root().join("files")).unwrap(), @r###"
+    JJ: Choose the files to move into: zsuskuln 4f7e934f c
+    JJ: Remove the lines of the files whose changes should stay in the source.
+    JJ: Lines starting with "JJ: " (like this one) will be removed.
+    file1
+    file2
+    file3
+    "###);
+    insta::assert_snapshot!(get_log_output_with_description(&test_env, &repo_path), @r###"
+    @  404193d4137c b
+    ◉  b834d7ea469e c
+    │ ◉  5d0d33b9b5cd a
+    ├─╯
+    ◉  000000000000
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "-s", "-r=@-"]);
+    insta::assert_snapshot!(stdout, @r###"
+    A file2
+    A file3
+    "###);
+
+    // Selecting all the files abandons the source and combines the descriptions
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    std::fs::write(
+        &edit_script,
+        "write\nfile1\nfile2\nfile3\n\0next invocation\n\0write\nc+b\n",
+    )
+    .unwrap();
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["squash", "--files"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Existing conflicts were resolved or abandoned from these commits:
+      rlvkpnrz hidden 8c84ee13 (conflict) b
+    New conflicts appeared in these commits:
+      zsuskuln 79d0db0c (conflict) c+b
+    To resolve the conflicts, start by updating to it:
+      jj new zsuskulnrvyr
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you may want inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    Working copy now at: kmkuslsw 53195667 (conflict) (empty) (no description set)
+    Parent commit      : zsuskuln 79d0db0c (conflict) c+b
+    There are unresolved conflicts at these paths:
+    file3    2-sided conflict
+    "###);
+    insta::assert_snapshot!(get_log_output_with_description(&test_env, &repo_path), @r###"
+    @  531956673c96
+    ◉  79d0db0cb730 c+b
+    │ ◉  5d0d33b9b5cd a
+    ├─╯
+    ◉  000000000000
+    "###);
+
+    // Selecting no files aborts
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    edit_files(&[]);
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["squash", "--files"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: No files selected
+    "###);
+
+    // Only changed files can be selected
+    edit_files(&["file4"]);
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["squash", "--files"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Not a changed file: file4
+    "###);
+
+    // The paths restrict the listed files
+    edit_files(&["file1"]);
+    test_env.jj_cmd_ok(&repo_path, &["squash", "--files", "file1", "file2"]);
+    insta::assert_snapshot!(
+        std::fs::read_to_string(test_env.env_root().join("files")).unwrap(), @r###"
+    JJ: Choose the files to move into: zsuskuln 4f7e934f c
+    JJ: Remove the lines of the files whose changes should stay in the source.
+    JJ: Lines starting with "JJ: " (like this one) will be removed.
+    file1
+    file2
+    "###);
+}
+
 fn get_description(test_env: &TestEnvironment, repo_path: &Path, rev: &str) -> String {
     test_env.jj_cmd_success(
         repo_path,
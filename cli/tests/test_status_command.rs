@@ -224,3 +224,72 @@ fn test_status_simplify_conflict_sides() {
     Then run `jj squash` to move the resolution into the conflicted commit.
     "###);
 }
+
+#[test]
+fn test_status_cache() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    let cache_path = repo_path
+        .join(".jj")
+        .join("working_copy")
+        .join("status_cache");
+
+    std::fs::write(repo_path.join("file1"), "1").unwrap();
+    let stdout = test_env.jj_cmd_success(&repo_path, &["status"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Working copy changes:
+    A file1
+    Working copy : qpvuntsm 25abbc67 (no description set)
+    Parent commit: zzzzzzzz 00000000 (empty) (no description set)
+    "###);
+    insta::assert_snapshot!(std::fs::read_to_string(&cache_path).unwrap(), @r###"
+    25abbc67b9fb357af41b33fa4e7cbf154597d8a5 4b825dc642cb6eb9a060e54bf8d69288fbee4904
+    A file1
+    "###);
+
+    // The cache is invalidated when a new snapshot changes the diff
+    std::fs::write(repo_path.join("file2"), "2").unwrap();
+    let stdout = test_env.jj_cmd_success(&repo_path, &["status"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Working copy changes:
+    A file1
+    A file2
+    Working copy : qpvuntsm c0f117de (no description set)
+    Parent commit: zzzzzzzz 00000000 (empty) (no description set)
+    "###);
+
+    // The cache is reused as long as the working-copy commit is unchanged
+    let cache = std::fs::read_to_string(&cache_path).unwrap();
+    let key = cache.lines().next().unwrap();
+    std::fs::write(&cache_path, format!("{key}\nD cached\n")).unwrap();
+    let stdout = test_env.jj_cmd_success(&repo_path, &["status"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Working copy changes:
+    D cached
+    Working copy : qpvuntsm c0f117de (no description set)
+    Parent commit: zzzzzzzz 00000000 (empty) (no description set)
+    "###);
+
+    // A corrupt cache is ignored
+    std::fs::write(&cache_path, format!("{key}\nX /invalid\n")).unwrap();
+    let stdout = test_env.jj_cmd_success(&repo_path, &["status"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Working copy changes:
+    A file1
+    A file2
+    Working copy : qpvuntsm c0f117de (no description set)
+    Parent commit: zzzzzzzz 00000000 (empty) (no description set)
+    "###);
+
+    // So is a cache with paths that aren't in the internal format
+    std::fs::write(&cache_path, format!("{key}\nM /invalid\nA dir//file\n")).unwrap();
+    let stdout = test_env.jj_cmd_success(&repo_path, &["status"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Working copy changes:
+    A file1
+    A file2
+    Working copy : qpvuntsm c0f117de (no description set)
+    Parent commit: zzzzzzzz 00000000 (empty) (no description set)
+    "###);
+}
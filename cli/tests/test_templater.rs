@@ -111,7 +111,69 @@ fn test_templater_parse_error() {
       | ^-----^
       |
       = Keyword "builtin" doesn't exist
-    Hint: Did you mean "builtin_log_comfortable", "builtin_log_compact", "builtin_log_detailed", "builtin_log_node", "builtin_log_node_ascii", "builtin_log_oneline", "builtin_op_log_comfortable", "builtin_op_log_compact", "builtin_op_log_node", "builtin_op_log_node_ascii"?
+    Hint: Did you mean "builtin_log_comfortable", "builtin_log_compact", "builtin_log_detailed", "builtin_log_node", "builtin_log_node_ascii", "builtin_log_oneline", "builtin_op_log_comfortable", "builtin_op_log_compact", "builtin_op_log_node", "builtin_op_log_node_ascii", "builtin_op_show"?
+    "###);
+}
+
+#[test]
+fn test_templater_template_file() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::create_dir(repo_path.join("dir")).unwrap();
+    std::fs::write(
+        repo_path.join("dir").join("template"),
+        "commit_id.short()\n++ \" \"\n++ description.first_line()\n",
+    )
+    .unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "first"]);
+
+    // The path is relative to the current directory
+    let stdout = test_env.jj_cmd_success(
+        &repo_path.join("dir"),
+        &["log", "--no-graph", "-r@", "--template-file", "template"],
+    );
+    insta::assert_snapshot!(stdout, @"2a3387278ab5 first");
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["show", "--no-patch", "--template-file", "dir/template"],
+    );
+    insta::assert_snapshot!(stdout, @"2a3387278ab5 first");
+
+    // Parse errors point at the line in the file
+    std::fs::write(
+        repo_path.join("bad-template"),
+        "commit_id.short()\n++ descripton\n",
+    )
+    .unwrap();
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["log", "--template-file", "bad-template"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Failed to parse template: Keyword "descripton" doesn't exist
+    Caused by:  --> 2:4
+      |
+    2 | ++ descripton
+      |    ^--------^
+      |
+      = Keyword "descripton" doesn't exist
+    Hint: Did you mean "description", "description_placeholder"?
+    Hint: The template was read from bad-template
+    "###);
+
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["log", "--template-file", "missing"]);
+    insta::assert_snapshot!(stderr.lines().next().unwrap_or_default(), @r###"
+    Error: Failed to read template file missing
+    "###);
+
+    let stderr = test_env.jj_cmd_cli_error(
+        &repo_path,
+        &["log", "-T", "commit_id", "--template-file", "bad-template"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    error: the argument '--template <TEMPLATE>' cannot be used with '--template-file <PATH>'
+
+    Usage: jj log --template <TEMPLATE> [PATHS]...
+
+    For more information, try '--help'.
     "###);
 }
 
@@ -253,22 +315,22 @@ fn test_templater_alias() {
     "###);
 
     insta::assert_snapshot!(render_err("identity()"), @r###"
-    Error: Failed to parse template: Function "identity": Expected 1 arguments
+    Error: Failed to parse template: Function "identity": Expected 1 arguments (declared as alias "identity(x)")
     Caused by:  --> 1:10
       |
     1 | identity()
       |          ^
       |
-      = Function "identity": Expected 1 arguments
+      = Function "identity": Expected 1 arguments (declared as alias "identity(x)")
     "###);
     insta::assert_snapshot!(render_err("identity(commit_id, commit_id)"), @r###"
-    Error: Failed to parse template: Function "identity": Expected 1 arguments
+    Error: Failed to parse template: Function "identity": Expected 1 arguments (declared as alias "identity(x)")
     Caused by:  --> 1:10
       |
     1 | identity(commit_id, commit_id)
       |          ^------------------^
       |
-      = Function "identity": Expected 1 arguments
+      = Function "identity": Expected 1 arguments (declared as alias "identity(x)")
     "###);
 
     insta::assert_snapshot!(render_err(r#"coalesce(label("x", "not boolean"), "")"#), @r###"
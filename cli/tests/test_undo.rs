@@ -46,6 +46,125 @@ fn test_undo_rewrite_with_child() {
     "###);
 }
 
+#[test]
+fn test_undo_rewrite_with_child_conflict() {
+    // Test that if the descendants rebased by an undo end up with conflicts,
+    // they're reported, and the undo can be reverted as a whole.
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file"), "initial\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "parent"]);
+    std::fs::write(repo_path.join("file"), "modified\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["status"]);
+    let op_id_hex = test_env.current_operation_id(&repo_path);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "child"]);
+    std::fs::write(repo_path.join("file"), "child\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["status"]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["undo", &op_id_hex]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 1 descendant commit
+    New conflicts appeared in these commits:
+      mzvwutvl 7102fd84 (conflict) child
+    To resolve the conflicts, start by updating to it:
+      jj new mzvwutvlkqwt
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you may want inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    Working copy now at: mzvwutvl 7102fd84 (conflict) child
+    Parent commit      : qpvuntsm ad86655c parent
+    Added 0 files, modified 1 files, removed 0 files
+    There are unresolved conflicts at these paths:
+    file    2-sided conflict
+    Hint: Run `jj undo` to revert this undo instead of resolving the conflicts.
+    "###);
+
+    // Undoing the undo restores the state without conflicts
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    let template = r#"description.first_line() ++ if(conflict, " (conflict)")"#;
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  child
+    ◉  parent
+    ◉
+    "###);
+}
+
+#[test]
+fn test_undo_rewrite_with_child_no_rebase_descendants() {
+    // Test that undoing an operation that rewrote some commit can leave the
+    // descendants on top of the rewritten commit.
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "initial"]);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "modified"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "log"]);
+    let op_id_hex = stdout[3..15].to_string();
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "child"]);
+    let (stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["undo", &op_id_hex, "--no-rebase-descendants"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Left 1 descendant commit on top of the undone commits:
+      mzvwutvl ffabc572 (empty) child
+    Hint: Use `jj rebase` to move the orphaned commits onto the restored commits.
+    "###);
+
+    // The child is still on top of the modified commit, which remains visible
+    // alongside the restored commit. Both are reachable via the change id.
+    let template = r#"change_id.short() ++ " " ++ description"#;
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  mzvwutvlkqwt child
+    ◉  qpvuntsmwlqt modified
+    │ ◉  qpvuntsmwlqt initial
+    ├─╯
+    ◉  zzzzzzzzzzzz
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-r", "qpvuntsm", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    ◉  qpvuntsmwlqt modified
+    │
+    ~
+
+    ◉  qpvuntsmwlqt initial
+    │
+    ~
+    "###);
+
+    // The orphaned child can be reattached to the restored commit, leaving the
+    // modified commit behind to be abandoned
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["rebase", "-s", "@", "-d", "description(initial)"],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebased 1 commits
+    Working copy now at: mzvwutvl 670884e0 (empty) child
+    Parent commit      : qpvuntsm?? 68e11012 (empty) initial
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  mzvwutvlkqwt child
+    ◉  qpvuntsmwlqt initial
+    │ ◉  qpvuntsmwlqt modified
+    ├─╯
+    ◉  zzzzzzzzzzzz
+    "###);
+    test_env.jj_cmd_ok(&repo_path, &["abandon", "description(modified)"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  mzvwutvlkqwt child
+    ◉  qpvuntsmwlqt initial
+    ◉  zzzzzzzzzzzz
+    "###);
+}
+
 #[test]
 fn test_git_push_undo() {
     let test_env = TestEnvironment::default();
@@ -100,14 +219,9 @@ fn test_git_push_undo() {
     test_env.advance_test_rng_seed_to_multiple_of(100_000);
     test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "CC"]);
     test_env.jj_cmd_ok(&repo_path, &["git", "fetch"]);
-    // TODO: The user would probably not expect a conflict here. It currently is
-    // because the undo made us forget that the remote was at v2, so the fetch
-    // made us think it updated from v1 to v2 (instead of the no-op it could
-    // have been).
-    //
-    // One option to solve this would be to have undo not restore remote-tracking
-    // branches, but that also has undersired consequences: the second fetch in `jj
-    // git fetch && jj undo && jj git fetch` would become a no-op.
+    // The restore made us forget that the remote was at v2, so the fetch made
+    // us think it updated from v1 to v2. `jj undo` keeps the remote-tracking
+    // branches of a push instead (see test_git_push_undo_keeps_remote_tracking).
     insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
     main (conflicted):
       - qpvuntsm hidden 2080bdb8 (empty) AA
@@ -324,6 +438,91 @@ fn test_git_push_undo_repo_only() {
     "###);
 }
 
+#[test]
+fn test_git_push_undo_keeps_remote_tracking() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"revset-aliases."immutable_heads()" = "none()""#);
+    let git_repo_path = test_env.env_root().join("git-repo");
+    git2::Repository::init_bare(git_repo_path).unwrap();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "clone", "git-repo", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.advance_test_rng_seed_to_multiple_of(100_000);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "main"]);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "AA"]);
+    test_env.jj_cmd_ok(&repo_path, &["git", "push"]);
+    test_env.advance_test_rng_seed_to_multiple_of(100_000);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "BB"]);
+    test_env.jj_cmd_ok(&repo_path, &["git", "push"]);
+    let push_op_id = test_env.current_operation_id(&repo_path);
+
+    // Undoing the push keeps the remote-tracking branches, which still match
+    // the remote
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Nothing changed.
+    Hint: The remote-tracking branches updated by the push were kept, since the push wasn't undone on the remote. Use `--what=repo --what=remote-tracking` to restore them, too.
+    "###);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    main: qpvuntsm 75e78001 (empty) BB
+      @origin: qpvuntsm 75e78001 (empty) BB
+    "###);
+    test_env.advance_test_rng_seed_to_multiple_of(100_000);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "CC"]);
+    test_env.jj_cmd_ok(&repo_path, &["git", "fetch"]);
+    // There is no conflict, since the fetch didn't find any changes
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    main: qpvuntsm 20b2cc4b (empty) CC
+      @origin (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 75e78001 (empty) BB
+    "###);
+
+    // The remote-tracking branches can still be restored explicitly
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["undo", "--what=repo", "--what=remote-tracking", &push_op_id],
+    );
+    insta::assert_snapshot!(stderr, @"");
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    main: qpvuntsm 20b2cc4b (empty) CC
+      @origin (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 2080bdb8 (empty) AA
+    "###);
+}
+
+#[test]
+fn test_git_push_undo_range_keeps_remote_tracking() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"revset-aliases."immutable_heads()" = "none()""#);
+    let git_repo_path = test_env.env_root().join("git-repo");
+    git2::Repository::init_bare(git_repo_path).unwrap();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "clone", "git-repo", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.advance_test_rng_seed_to_multiple_of(100_000);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "main"]);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "AA"]);
+    test_env.jj_cmd_ok(&repo_path, &["git", "push"]);
+    let pre_range_op_id = test_env.current_operation_id(&repo_path);
+    test_env.advance_test_rng_seed_to_multiple_of(100_000);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "BB"]);
+    test_env.jj_cmd_ok(&repo_path, &["git", "push"]);
+    test_env.advance_test_rng_seed_to_multiple_of(100_000);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "CC"]);
+
+    // Undoing a range that includes a push keeps the remote-tracking branches,
+    // even if the push isn't the last operation of the range
+    let range = format!("{pre_range_op_id}..@");
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["undo", &range]);
+    insta::assert_snapshot!(stderr, @r###"
+    Working copy now at: qpvuntsm 2080bdb8 main* | (empty) AA
+    Parent commit      : zzzzzzzz 00000000 (empty) (no description set)
+    Hint: The remote-tracking branches updated by the push were kept, since the push wasn't undone on the remote. Use `--what=repo --what=remote-tracking` to restore them, too.
+    "###);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    main: qpvuntsm 2080bdb8 (empty) AA
+      @origin (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 75e78001 (empty) BB
+    "###);
+}
+
 #[test]
 fn test_branch_track_untrack_undo() {
     let test_env = TestEnvironment::default();
@@ -385,6 +584,540 @@ fn test_branch_track_untrack_undo() {
     "###);
 }
 
+#[test]
+fn test_undo_redo() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    // Nothing has been undone yet
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["redo"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Nothing to redo
+    Hint: The latest operation is not an undo operation
+    "###);
+
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "first"]);
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    insta::assert_snapshot!(get_description(&test_env, &repo_path), @"");
+
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["redo"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Working copy now at: qpvuntsm ef6b9b66 (empty) first
+    Parent commit      : zzzzzzzz 00000000 (empty) (no description set)
+    "###);
+    insta::assert_snapshot!(get_description(&test_env, &repo_path), @"first");
+
+    // The redo can't be redone again
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["redo"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Nothing to redo
+    Hint: The last undo operation has already been redone
+    "###);
+
+    // Repeated undo/redo toggles between the two states
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    insta::assert_snapshot!(get_description(&test_env, &repo_path), @"");
+    test_env.jj_cmd_ok(&repo_path, &["op", "redo"]);
+    insta::assert_snapshot!(get_description(&test_env, &repo_path), @"first");
+
+    // Undoing an undo suggests `jj redo`
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Working copy now at: qpvuntsm ef6b9b66 (empty) first
+    Parent commit      : zzzzzzzz 00000000 (empty) (no description set)
+    Hint: The undone operation was itself an undo. Use `jj redo` to reapply an undone operation.
+    "###);
+    insta::assert_snapshot!(get_description(&test_env, &repo_path), @"first");
+
+    // An operation after the undo discards it
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["redo"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Nothing to redo
+    Hint: The latest operation is not an undo operation
+    "###);
+}
+
+#[test]
+fn test_redo_after_snapshot() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "main"]);
+    test_env.jj_cmd_ok(&repo_path, &["new"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "feature"]);
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+
+    // A snapshot which doesn't touch the undone refs is skipped over
+    std::fs::write(repo_path.join("file"), "contents").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["debug", "snapshot"]);
+    test_env.jj_cmd_ok(&repo_path, &["redo"]);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @r###"
+    feature: kkmpptxz b6b1cdea (no description set)
+    main: qpvuntsm 230dd059 (empty) (no description set)
+    "###);
+
+    // A snapshot which rewrote the same working-copy commit requires --force
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "description"]);
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    std::fs::write(repo_path.join("file"), "modified").unwrap();
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["redo"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Operations after the undo changed the same refs: feature, working copy of default
+    Hint: Use --force to redo anyway
+    "###);
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &["redo", "--config-toml=ui.error-format='json'"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    {"causes":[],"code":"redo-conflict","hints":["Use --force to redo anyway"],"message":"Operations after the undo changed the same refs: feature, working copy of default"}
+    "###);
+    // The working-copy commit snapshotted after the undo is kept
+    test_env.jj_cmd_ok(&repo_path, &["redo", "--force"]);
+    insta::assert_snapshot!(get_description(&test_env, &repo_path), @"");
+}
+
+#[test]
+fn test_undo_skips_snapshot() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(&repo_path, &["commit", "-m", "first"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "main", "-r@-"]);
+
+    // The snapshot made before the undo is skipped, and its changes are kept
+    std::fs::write(repo_path.join("file"), "contents").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    insta::assert_snapshot!(get_branch_output(&test_env, &repo_path), @"");
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "show", "file"]);
+    insta::assert_snapshot!(stdout, @"contents");
+
+    // An explicitly given snapshot operation is undone
+    std::fs::write(repo_path.join("file"), "modified").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["debug", "snapshot"]);
+    test_env.jj_cmd_ok(&repo_path, &["undo", "@"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["file", "show", "file"]);
+    insta::assert_snapshot!(stdout, @"contents");
+}
+
+#[test]
+fn test_undo_with_reason() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "first"]);
+    test_env.jj_cmd_ok(&repo_path, &["undo", "--reason", "wrong commit"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "log", "--limit", "1"]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  8e8d1acebef1 test-username@host.example.com 2001-02-03 04:05:09.000 +07:00 - 2001-02-03 04:05:09.000 +07:00
+    │  undo operation 550c7992bb0a258d9a92030eab65848a9f606b7ce2fd9fc882a05abfd75d6b21c805facfe69619ba40487ee4ded344a4bc06e334a9a4e017b2c80a18cfde910e
+    │  args: jj undo --reason 'wrong commit'
+    │  reason: wrong commit
+    │  undone-operation: 550c7992bb0a258d9a92030eab65848a9f606b7ce2fd9fc882a05abfd75d6b21c805facfe69619ba40487ee4ded344a4bc06e334a9a4e017b2c80a18cfde910e
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "show"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Operation ID: 8e8d1acebef133ca50656a3ca3b0d0e047356c2c2f84910dd2dc4f9f7c2e570c828b283cdb327dca6227f833b9e3a809cc5e1d8213b07b23338b0d01cb2f47e2
+    User: test-username@host.example.com
+    Time: 2001-02-03 04:05:09.000 +07:00 - 2001-02-03 04:05:09.000 +07:00
+    Description: undo operation 550c7992bb0a258d9a92030eab65848a9f606b7ce2fd9fc882a05abfd75d6b21c805facfe69619ba40487ee4ded344a4bc06e334a9a4e017b2c80a18cfde910e
+    Tags:
+      args: jj undo --reason 'wrong commit'
+      reason: wrong commit
+      undone-operation: 550c7992bb0a258d9a92030eab65848a9f606b7ce2fd9fc882a05abfd75d6b21c805facfe69619ba40487ee4ded344a4bc06e334a9a4e017b2c80a18cfde910e
+    Parent operations:
+      550c7992bb0a describe commit 230dd059e1b059aefc0da06a2e5a7dbf22362f22
+    This is the current operation.
+
+    Changed commits:
+      + qpvuntsm 230dd059 (empty) (no description set)
+      - qpvuntsm hidden fa15625b (empty) first
+    Changed working copies:
+      default@: fa15625b4a98 -> 230dd059e1b0
+    "###);
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "op",
+            "log",
+            "--no-graph",
+            "--limit",
+            "2",
+            "-T",
+            r#"description ++ ": " ++ reason ++ "\n""#,
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    undo operation 550c7992bb0a258d9a92030eab65848a9f606b7ce2fd9fc882a05abfd75d6b21c805facfe69619ba40487ee4ded344a4bc06e334a9a4e017b2c80a18cfde910e: wrong commit
+    describe commit 230dd059e1b059aefc0da06a2e5a7dbf22362f22: 
+    "###);
+
+    // Restoring accepts a reason too
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["op", "restore", "@-", "--reason", "  back to first  "],
+    );
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["op", "log", "--no-graph", "--limit", "1", "-T", "reason"],
+    );
+    insta::assert_snapshot!(stdout, @"back to first");
+
+    // An empty reason is omitted
+    test_env.jj_cmd_ok(&repo_path, &["undo", "--reason", ""]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "log", "--limit", "1"]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  6eff6f72332b test-username@host.example.com 2001-02-03 04:05:15.000 +07:00 - 2001-02-03 04:05:15.000 +07:00
+    │  undo operation 6fd4b562ffd687d9f312e0fdf88bb79723392b6eb7e211984eea80813cfb6d2c484ee8d387977f92a296bfd0ae2f4ed0bd56eca09713d7cc53efe88dcaa692be
+    │  args: jj undo --reason
+    │  undone-operation: 6fd4b562ffd687d9f312e0fdf88bb79723392b6eb7e211984eea80813cfb6d2c484ee8d387977f92a296bfd0ae2f4ed0bd56eca09713d7cc53efe88dcaa692be
+    "###);
+
+    // The reason is limited in length
+    let long_reason = "x".repeat(201);
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["undo", "--reason", &long_reason]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: The reason must not be longer than 200 characters
+    "###);
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["undo", "--reason", "two\nlines"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: The reason must be a single line
+    "###);
+}
+
+#[test]
+fn test_op_restore_revisions() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "initial"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "log"]);
+    let op_id_hex = stdout[3..15].to_string();
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "modified"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "-r@", "main"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "child"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-m", "unrelated"]);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "unrelated modified"]);
+
+    // The child didn't exist at the operation, so it's skipped. The unrelated
+    // commit is left alone.
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &[
+            "op",
+            "restore",
+            &op_id_hex,
+            "-r",
+            "main | description(child)",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Warning: Skipping change royxmykxtrkr since it didn't exist at operation 6ceb832e776d
+    Restored 1 commits from operation 6ceb832e776d
+    Rebased 1 descendant commits
+    "###);
+    let template = r#"description.first_line() ++ " " ++ branches"#;
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    ◉  child
+    ◉  initial main
+    │ @  unrelated modified
+    ├─╯
+    ◉
+    "###);
+
+    // Restoring again is a no-op
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["op", "restore", &op_id_hex, "-r", "description(initial)"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Nothing changed.
+    "###);
+
+    // The restore can be undone
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  unrelated modified
+    │ ◉  child
+    │ ◉  modified main
+    ├─╯
+    ◉
+    "###);
+}
+
+#[test]
+fn test_op_restore_interactive() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    let edit_script = test_env.set_up_fake_diff_editor();
+    let template = r#"description.first_line() ++ " " ++ branches"#;
+
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "initial"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "main"]);
+    let op_id_hex = test_env.current_operation_id(&repo_path);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "child"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "set", "main", "-r@"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "feature"]);
+
+    // Restore only the branch, leaving the working copy and the new branch
+    std::fs::write(
+        &edit_script,
+        [
+            "print-files-before",
+            "print-files-after",
+            "reset working-copies/default",
+            "reset branches/feature",
+        ]
+        .join("\0"),
+    )
+    .unwrap();
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["op", "restore", "-i", &op_id_hex]);
+    insta::assert_snapshot!(stdout, @r###"
+    branches/feature
+    branches/main
+    heads/6dcea6fadd2cc91c91906929d53e159487fadde1
+    working-copies/default
+    JJ-INSTRUCTIONS
+    branches/main
+    heads/68e1101283b0a6c4694f92fab85bcd90f0c83652
+    working-copies/default
+    "###);
+    insta::assert_snapshot!(stderr, @r###"
+    Restored 3 of 5 parts of the repo state from operation 490db8680ebc
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  child feature
+    ◉  initial main
+    ◉
+    "###);
+
+    // Restore the deletion of the new branch
+    std::fs::write(
+        &edit_script,
+        "print-files-before\0reset working-copies/default",
+    )
+    .unwrap();
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["op", "restore", "-i", &op_id_hex]);
+    insta::assert_snapshot!(stdout, @r###"
+    branches/feature
+    working-copies/default
+    "###);
+    insta::assert_snapshot!(stderr, @r###"
+    Restored 1 of 2 parts of the repo state from operation 490db8680ebc
+    "###);
+
+    // Nothing is restored if all changes are discarded
+    std::fs::write(&edit_script, "reset working-copies/default").unwrap();
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["op", "restore", "-i", &op_id_hex]);
+    insta::assert_snapshot!(stderr, @r###"
+    Nothing changed.
+    "###);
+
+    // Restore everything that's left
+    std::fs::write(&edit_script, "").unwrap();
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["op", "restore", "--tool=fake-diff-editor", &op_id_hex],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Restored 1 of 1 parts of the repo state from operation 490db8680ebc
+    Working copy now at: qpvuntsm 68e11012 main | (empty) initial
+    Parent commit      : zzzzzzzz 00000000 (empty) (no description set)
+    "###);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  initial main
+    ◉
+    "###);
+}
+
+#[test]
+fn test_op_restore_interactive_file_names() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    let edit_script = test_env.set_up_fake_diff_editor();
+    let op_id_hex = test_env.current_operation_id(&repo_path);
+    test_env.jj_cmd_ok(
+        &repo_path,
+        &["branch", "create", ".", "..", ".hidden", "a/b"],
+    );
+
+    // Names which aren't valid file names are escaped
+    let git_objects_dir = repo_path.join(".jj/repo/store/git/objects");
+    let count_git_objects = || count_files(&git_objects_dir);
+    let num_git_objects = count_git_objects();
+    std::fs::write(&edit_script, "print-files-before").unwrap();
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["op", "restore", "-i", &op_id_hex]);
+    insta::assert_snapshot!(stdout, @r###"
+    branches/%2E
+    branches/%2E.
+    branches/%2Ehidden
+    branches/a%2Fb
+    "###);
+    insta::assert_snapshot!(stderr, @r###"
+    Restored 4 of 4 parts of the repo state from operation b51416386f26
+    "###);
+    // The files shown in the diff editor aren't written to the repo
+    assert_eq!(count_git_objects(), num_git_objects);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["branch", "list"]);
+    insta::assert_snapshot!(stdout, @"");
+}
+
+fn count_files(dir: &Path) -> usize {
+    std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| {
+            let entry = entry.unwrap();
+            if entry.file_type().unwrap().is_dir() {
+                count_files(&entry.path())
+            } else {
+                1
+            }
+        })
+        .sum()
+}
+
+#[test]
+fn test_undo_what_commits_or_local_branches() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    let template = r#"description.first_line() ++ " " ++ branches"#;
+
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "base"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "feature"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "feature"]);
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-m", "unrelated"]);
+    // Abandoning the commit moves the branch to its parent
+    test_env.jj_cmd_ok(&repo_path, &["abandon", "description(feature)"]);
+    let abandon_op_id = test_env.current_operation_id(&repo_path);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  unrelated
+    │ ◉  base feature
+    ├─╯
+    ◉
+    "###);
+
+    // Undo only the branch move. The abandoned commit becomes visible again
+    // because the branch points to it.
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["undo", "--what=local-branches"]);
+    insta::assert_snapshot!(stderr, @"");
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  unrelated
+    │ ◉  feature feature
+    │ ◉  base
+    ├─╯
+    ◉
+    "###);
+
+    // Undo only the abandonment, leaving the branch where it was moved to
+    test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    let (_stdout, stderr) =
+        test_env.jj_cmd_ok(&repo_path, &["undo", "--what=commits", &abandon_op_id]);
+    insta::assert_snapshot!(stderr, @"");
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  unrelated
+    │ ◉  feature
+    │ ◉  base feature
+    ├─╯
+    ◉
+    "###);
+}
+
+#[test]
+fn test_undo_range() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+    let template = r#"description.first_line() ++ " " ++ branches"#;
+
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "first"]);
+    let base_op_id = test_env.current_operation_id(&repo_path);
+    test_env.jj_cmd_ok(&repo_path, &["new", "-m", "second"]);
+    test_env.jj_cmd_ok(&repo_path, &["branch", "create", "feature"]);
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "second modified"]);
+    let last_op_id = test_env.current_operation_id(&repo_path);
+    let redact_op_ids = |s: String| s.replace(&base_op_id, "BASE").replace(&last_op_id, "LAST");
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-m", "unrelated"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  unrelated
+    │ ◉  second modified feature
+    │ ◉  first
+    ├─╯
+    ◉
+    "###);
+
+    // The operations in the range are undone in one operation, leaving the
+    // later operation intact
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        &repo_path,
+        &["undo", &format!("{base_op_id}..{last_op_id}")],
+    );
+    insta::assert_snapshot!(stderr, @"");
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  unrelated
+    │ ◉  first
+    ├─╯
+    ◉
+    "###);
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &["op", "log", "-n1", "--no-graph", "-T", "description"],
+    );
+    insta::assert_snapshot!(redact_op_ids(stdout), @"undo operations BASE..LAST");
+
+    // The range undo can be redone
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["redo"]);
+    insta::assert_snapshot!(stderr, @"");
+    let stdout = test_env.jj_cmd_success(&repo_path, &["log", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    @  unrelated
+    │ ◉  second modified feature
+    │ ◉  first
+    ├─╯
+    ◉
+    "###);
+
+    // The range must go forward
+    let stderr = test_env.jj_cmd_failure(
+        &repo_path,
+        &["undo", &format!("{last_op_id}..{base_op_id}")],
+    );
+    insta::assert_snapshot!(redact_op_ids(stderr), @r###"
+    Error: Operation LAST is not an ancestor of operation BASE
+    "###);
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["undo", "@..@"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: The range of operations to undo is empty
+    "###);
+}
+
+fn get_description(test_env: &TestEnvironment, repo_path: &Path) -> String {
+    test_env.jj_cmd_success(
+        repo_path,
+        &["log", "--no-graph", "-r@", "-T", "description"],
+    )
+}
+
 fn get_branch_output(test_env: &TestEnvironment, repo_path: &Path) -> String {
     // --quiet to suppress deleted branches hint
     test_env.jj_cmd_success(repo_path, &["branch", "list", "--all-remotes", "--quiet"])
@@ -0,0 +1,125 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_unsign() {
+    let test_env = TestEnvironment::default();
+    let workspace_root = test_env.env_root().join("repo");
+    let git_repo = git2::Repository::init(&workspace_root).unwrap();
+    let signed_a = create_git_commit(&git_repo, "a", &[], true);
+    let signed_b = create_git_commit(&git_repo, "b", &[signed_a], true);
+    let unsigned_c = create_git_commit(&git_repo, "c", &[signed_b], false);
+    git_repo
+        .reference("refs/heads/main", unsigned_c, false, "")
+        .unwrap();
+    test_env.jj_cmd_ok(&workspace_root, &["git", "init", "--git-repo", "."]);
+    test_env.jj_cmd_ok(&workspace_root, &["new", "main"]);
+    insta::assert_snapshot!(get_log_output(&test_env, &workspace_root), @r###"
+    @  rlvkpnrzqnoo c8ac7d568ae7
+    ◉  uwszvplxltps ebf762f06778 c
+    ◉  vkyrtrvxpkrw af1d95dbaf05 b
+    ◉  rvxuvwympwmv 7d77a719fa37 a
+    ◉  zzzzzzzzzzzz 000000000000
+    "###);
+
+    // Unsigned commits are reported and left alone
+    let (stdout, stderr) = test_env.jj_cmd_ok(&workspace_root, &["unsign", "-r", "description(c)"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Skipping unsigned commit uwszvplx ebf762f0 main | c
+    Nothing changed.
+    "###);
+
+    // Immutable commits can't be unsigned
+    let stderr = test_env.jj_cmd_failure(&workspace_root, &["unsign", "-r", "root()"]);
+    insta::assert_snapshot!(stderr, @"Error: The root commit 000000000000 is immutable");
+
+    // Change ids are preserved and descendants are rebased
+    let (stdout, stderr) = test_env.jj_cmd_ok(&workspace_root, &["unsign", "-r", "root()..main"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Skipping unsigned commit uwszvplx ebf762f0 main | c
+    Unsigned 2 commits
+    Rebased 2 descendant commits
+    Working copy now at: rlvkpnrz 4b3796b1 (empty) (no description set)
+    Parent commit      : uwszvplx 9afbd60d main | c
+    "###);
+    insta::assert_snapshot!(get_log_output(&test_env, &workspace_root), @r###"
+    @  rlvkpnrzqnoo 4b3796b1a6d0
+    ◉  uwszvplxltps 9afbd60d7ed9 c
+    ◉  vkyrtrvxpkrw ddacaa739520 b
+    ◉  rvxuvwympwmv 5c67636e2fdc a
+    ◉  zzzzzzzzzzzz 000000000000
+    "###);
+
+    let stdout = test_env.jj_cmd_success(
+        &workspace_root,
+        &[
+            "log",
+            "--no-graph",
+            "-r",
+            "::main ~ root()",
+            "-T",
+            r#"commit_id ++ "\n""#,
+        ],
+    );
+    for commit_id in stdout.lines() {
+        let oid = git2::Oid::from_str(commit_id).unwrap();
+        assert!(git_repo.extract_signature(&oid, None).is_err());
+    }
+}
+
+fn create_git_commit(
+    git_repo: &git2::Repository,
+    message: &str,
+    parents: &[git2::Oid],
+    signed: bool,
+) -> git2::Oid {
+    let signature =
+        git2::Signature::new("Some One", "some.one@example.com", &git2::Time::new(0, 0)).unwrap();
+    let mut tree_builder = git_repo.treebuilder(None).unwrap();
+    let file_oid = git_repo.blob(message.as_bytes()).unwrap();
+    tree_builder
+        .insert(message, file_oid, git2::FileMode::Blob.into())
+        .unwrap();
+    let tree = git_repo.find_tree(tree_builder.write().unwrap()).unwrap();
+    let parents = parents
+        .iter()
+        .map(|oid| git_repo.find_commit(*oid).unwrap())
+        .collect::<Vec<_>>();
+    let parents = parents.iter().collect::<Vec<_>>();
+    let buffer = git_repo
+        .commit_create_buffer(&signature, &signature, message, &tree, &parents)
+        .unwrap();
+    let content = buffer.as_str().unwrap();
+    if signed {
+        let sig = "-----BEGIN SSH SIGNATURE-----\nfake\n-----END SSH SIGNATURE-----";
+        git_repo.commit_signed(content, sig, None).unwrap()
+    } else {
+        git_repo
+            .odb()
+            .unwrap()
+            .write(git2::ObjectType::Commit, content.as_bytes())
+            .unwrap()
+    }
+}
+
+fn get_log_output(test_env: &TestEnvironment, cwd: &Path) -> String {
+    let template = r#"change_id.short() ++ " " ++ commit_id.short() ++ " " ++ description"#;
+    test_env.jj_cmd_success(cwd, &["log", "-T", template])
+}
@@ -98,6 +98,44 @@ fn test_untrack() {
     assert!(!files_after.contains("target"));
 }
 
+#[test]
+fn test_untrack_jjignore() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"ui.allow-init-native = true"#);
+    test_env.jj_cmd_ok(test_env.env_root(), &["init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file1"), "initial").unwrap();
+    std::fs::write(repo_path.join("file2.bak"), "initial").unwrap();
+    std::fs::write(repo_path.join("file3.log"), "initial").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["st"]);
+
+    // .jjignore overrides .gitignore, and .jj/repo/ignore also applies
+    std::fs::write(repo_path.join(".gitignore"), "*.bak\nfile1\n").unwrap();
+    std::fs::write(repo_path.join(".jjignore"), "!file1\n").unwrap();
+    std::fs::write(repo_path.join(".jj/repo/ignore"), "*.log\n").unwrap();
+    let stderr = test_env.jj_cmd_failure(&repo_path, &["untrack", "file1"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: 'file1' is not ignored.
+    Hint: Files that are not ignored will be added back by the next command.
+    Make sure they're ignored, then try again.
+    "###);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["untrack", "file2.bak", "file3.log"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @"");
+
+    // Ignored files aren't added back by snapshotting
+    let stdout = test_env.jj_cmd_success(&repo_path, &["status"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Working copy changes:
+    A .gitignore
+    A .jjignore
+    A file1
+    Working copy : qpvuntsm a2404263 (no description set)
+    Parent commit: zzzzzzzz 00000000 (empty) (no description set)
+    "###);
+}
+
 #[test]
 fn test_untrack_sparse() {
     let test_env = TestEnvironment::default();
@@ -52,3 +52,135 @@ fn test_snapshot_large_file() {
         This will increase the maximum file size allowed for new files, for this command only.
     "###);
 }
+
+#[test]
+fn test_backup_on_discard() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file"), "snapshotted\n").unwrap();
+    std::fs::write(repo_path.join("other"), "unchanged\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "original"]);
+
+    // Modify the file on disk after it has been snapshotted, while the diff
+    // editor is running. The working-copy update then overwrites it.
+    let edit_script = test_env.set_up_fake_diff_editor();
+    std::fs::write(
+        &edit_script,
+        format!(
+            "write file\nedited\n\0write {}\nmodified externally\n",
+            repo_path.join("file").display()
+        ),
+    )
+    .unwrap();
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["diffedit"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Created qpvuntsm 7b308b56 original
+    Working copy now at: qpvuntsm 7b308b56 original
+    Parent commit      : zzzzzzzz 00000000 (empty) (no description set)
+    Added 0 files, modified 1 files, removed 0 files
+    Warning: Unsnapshotted changes to 1 file were overwritten. They were saved in hidden commit 82746971c301:
+      file
+    Hint: Run `jj restore --from 82746971c301 <paths>` with the paths above to recover them, or `jj new 82746971c301` to continue from the backup. Use `jj op show` to see the operation that recorded the backup.
+    "###);
+    insta::assert_snapshot!(std::fs::read_to_string(repo_path.join("file")).unwrap(), @"edited");
+
+    let stdout = test_env.jj_cmd_success(
+        &repo_path,
+        &[
+            "op",
+            "show",
+            "-T",
+            "description ++ \"\\n\" ++ tags ++ \"\\n\"",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    back up discarded working-copy changes
+    working-copy-backup: 82746971c301fd61b9ba5eeecfa6c2e55e68cfe8
+    Parent operations:
+      1d9f94eefb00 edit commit 5d8bbb4c861584d7d5f5836e89121eea8036cc8e
+    This is the current operation.
+
+    No changes.
+    "###);
+
+    // The backup commit is hidden, but its changes can be restored.
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--git", "-r", "82746971c301"]);
+    insta::assert_snapshot!(stdout, @r###"
+    diff --git a/file b/file
+    index d334948934...a512c75a8f 100644
+    --- a/file
+    +++ b/file
+    @@ -1,1 +1,1 @@
+    -snapshotted
+    +modified externally
+    "###);
+    test_env.jj_cmd_ok(&repo_path, &["restore", "--from", "82746971c301", "file"]);
+    insta::assert_snapshot!(std::fs::read_to_string(repo_path.join("file")).unwrap(), @"modified externally");
+
+    // The backup can be disabled
+    test_env.add_config("working-copy.backup-on-discard = false");
+    std::fs::write(
+        &edit_script,
+        format!(
+            "write file\nedited again\n\0write {}\nmodified externally again\n",
+            repo_path.join("file").display()
+        ),
+    )
+    .unwrap();
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["diffedit"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Created qpvuntsm 65ffbc30 original
+    Working copy now at: qpvuntsm 65ffbc30 original
+    Parent commit      : zzzzzzzz 00000000 (empty) (no description set)
+    Added 0 files, modified 1 files, removed 0 files
+    "###);
+    insta::assert_snapshot!(std::fs::read_to_string(repo_path.join("file")).unwrap(), @"edited again");
+}
+
+#[test]
+fn test_backup_on_discard_restore_elsewhere() {
+    let mut test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "repo"]);
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file"), "snapshotted\n").unwrap();
+    std::fs::write(repo_path.join("other"), "unchanged\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "original"]);
+    let edit_script = test_env.set_up_fake_diff_editor();
+    std::fs::write(
+        &edit_script,
+        format!(
+            "write file\nedited\n\0write {}\nmodified externally\n",
+            repo_path.join("file").display()
+        ),
+    )
+    .unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["diffedit"]);
+
+    // Undo skips the operation that recorded the backup, and undoes the
+    // diffedit instead
+    let (stdout, stderr) = test_env.jj_cmd_ok(&repo_path, &["undo"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Working copy now at: qpvuntsm 5d8bbb4c original
+    Parent commit      : zzzzzzzz 00000000 (empty) (no description set)
+    Added 0 files, modified 1 files, removed 0 files
+    "###);
+    insta::assert_snapshot!(std::fs::read_to_string(repo_path.join("file")).unwrap(), @"snapshotted");
+
+    // Move the working copy to an unrelated commit. Restoring the backed-up
+    // path there doesn't touch the commit's other files.
+    test_env.jj_cmd_ok(&repo_path, &["new", "root()", "-m", "unrelated"]);
+    std::fs::write(repo_path.join("unrelated"), "unrelated\n").unwrap();
+    test_env.jj_cmd_ok(&repo_path, &["restore", "--from", "82746971c301", "file"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["diff", "--summary"]);
+    insta::assert_snapshot!(stdout, @r###"
+    A file
+    A unrelated
+    "###);
+    insta::assert_snapshot!(std::fs::read_to_string(repo_path.join("file")).unwrap(), @"modified externally");
+}
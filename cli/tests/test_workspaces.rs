@@ -29,7 +29,7 @@ fn test_workspaces_add_second_workspace() {
 
     let stdout = test_env.jj_cmd_success(&main_path, &["workspace", "list"]);
     insta::assert_snapshot!(stdout, @r###"
-    default: rlvkpnrz 8183d0fc (empty) (no description set)
+    default (current): rlvkpnrz 8183d0fc (empty) (no description set) (updated 2001-02-03 08:05:08 by operation 55d0b6e4f53a)
     "###);
 
     let (stdout, stderr) = test_env.jj_cmd_ok(
@@ -64,8 +64,8 @@ fn test_workspaces_add_second_workspace() {
     // Both workspaces show up when we list them
     let stdout = test_env.jj_cmd_success(&main_path, &["workspace", "list"]);
     insta::assert_snapshot!(stdout, @r###"
-    default: rlvkpnrz 8183d0fc (empty) (no description set)
-    second: rzvqmyuk 5ed2222c (empty) (no description set)
+    default (current): rlvkpnrz 8183d0fc (empty) (no description set) (updated 2001-02-03 08:05:10 by operation 7e59a9bbc7be)
+    second: rzvqmyuk 5ed2222c (empty) (no description set) (updated 2001-02-03 08:05:10 by operation 7e59a9bbc7be)
     "###);
 }
 
@@ -107,7 +107,7 @@ fn test_workspaces_add_second_workspace_on_merge() {
 
     let stdout = test_env.jj_cmd_success(&main_path, &["workspace", "list"]);
     insta::assert_snapshot!(stdout, @r###"
-    default: zsuskuln 35e47bff (empty) merge
+    default (current): zsuskuln 35e47bff (empty) merge (updated 2001-02-03 08:05:10 by operation 7a95c9312243)
     "###);
 
     test_env.jj_cmd_ok(
@@ -144,7 +144,7 @@ fn test_workspaces_add_workspace_at_revision() {
 
     let stdout = test_env.jj_cmd_success(&main_path, &["workspace", "list"]);
     insta::assert_snapshot!(stdout, @r###"
-    default: kkmpptxz dadeedb4 (empty) (no description set)
+    default (current): kkmpptxz dadeedb4 (empty) (no description set) (updated 2001-02-03 08:05:09 by operation 471a861917c8)
     "###);
 
     let (_, stderr) = test_env.jj_cmd_ok(
@@ -388,6 +388,17 @@ fn test_workspaces_updated_by_other() {
     Hint: Run `jj workspace update-stale` to update it.
     See https://github.com/martinvonz/jj/blob/main/docs/working-copy.md#stale-working-copy for more information.
     "###);
+
+    // The stale workspace is detected from the other workspaces too
+    let stdout = test_env.jj_cmd_success(&secondary_path, &["workspace", "list", "--stale"]);
+    insta::assert_snapshot!(stdout, @r###"
+    secondary (current) (stale): pmmvwywv e82cd4ee (empty) (no description set) (updated 2001-02-03 08:05:09 by operation f46ea702e886)
+    "###);
+    let stdout = test_env.jj_cmd_success(&main_path, &["workspace", "list", "--stale"]);
+    insta::assert_snapshot!(stdout, @r###"
+    secondary (stale): pmmvwywv e82cd4ee (empty) (no description set) (updated 2001-02-03 08:05:09 by operation f46ea702e886)
+    "###);
+
     let (stdout, stderr) = test_env.jj_cmd_ok(&secondary_path, &["workspace", "update-stale"]);
     // It was detected that the working copy is now stale, but clean. So no
     // divergent commit should be created.
@@ -404,6 +415,8 @@ fn test_workspaces_updated_by_other() {
     ◉  d41244767d45
     ◉  000000000000
     "###);
+    let stdout = test_env.jj_cmd_success(&secondary_path, &["workspace", "list", "--stale"]);
+    insta::assert_snapshot!(stdout, @"");
 }
 
 #[test]
@@ -459,9 +472,7 @@ fn test_workspaces_current_op_discarded_by_other() {
     ◉  f336f5b6e8 Create initial working-copy commit in workspace secondary
     ◉  aacb3bda7d add workspace 'secondary'
     ◉  46bcf7d75e new empty commit
-    ◉  4d2f5d7cbf snapshot working copy
     ◉  2f863a1573 new empty commit
-    ◉  f01631d976 snapshot working copy
     ◉  17dbb2fe40 add workspace 'default'
     ◉  cecfee9647 initialize repo
     ◉  0000000000
@@ -535,6 +546,86 @@ fn test_workspaces_current_op_discarded_by_other() {
     "###);
 }
 
+#[test]
+fn test_workspaces_undo_from_other_workspace() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "main"]);
+    let main_path = test_env.env_root().join("main");
+    let secondary_path = test_env.env_root().join("secondary");
+
+    std::fs::write(main_path.join("file"), "contents").unwrap();
+    test_env.jj_cmd_ok(&main_path, &["commit", "-m", "initial"]);
+    test_env.jj_cmd_ok(
+        &main_path,
+        &["workspace", "add", "--name", "second", "../secondary"],
+    );
+
+    // Move the working copy of the second workspace, then undo it from the
+    // default workspace. The default workspace's working copy isn't touched,
+    // and the second workspace becomes stale.
+    test_env.jj_cmd_ok(&secondary_path, &["new", "root()"]);
+    let stdout =
+        test_env.jj_cmd_success(&main_path, &["op", "show", "-T", r#"description ++ "\n""#]);
+    insta::assert_snapshot!(stdout, @r###"
+    new empty commit
+    Parent operations:
+      ab51352d373f Create initial working-copy commit in workspace second
+    This is the current operation.
+
+    Changed commits:
+      + zsuskuln f652c321 (empty) (no description set)
+      - pmmvwywv hidden 0a77a39d (empty) (no description set)
+    Changed working copies:
+      second@: 0a77a39d7d6f -> f652c32197cf
+    "###);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&main_path, &["undo"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Hint: The working copies of these workspaces were not updated and are now stale: second
+    Run `jj workspace update-stale` in them to update them.
+    "###);
+    insta::assert_snapshot!(get_log_output(&test_env, &main_path), @r###"
+    ◉  0a77a39d7d6f second@
+    │ @  8183d0fcaa4c default@
+    ├─╯
+    ◉  751b12b7b981
+    ◉  000000000000
+    "###);
+    let stderr = test_env.jj_cmd_failure(&secondary_path, &["st"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: The working copy is stale (not updated since operation aed6258e415b).
+    Hint: Run `jj workspace update-stale` to update it.
+    See https://github.com/martinvonz/jj/blob/main/docs/working-copy.md#stale-working-copy for more information.
+    "###);
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&secondary_path, &["workspace", "update-stale"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Working copy now at: pmmvwywv 0a77a39d (empty) (no description set)
+    Added 1 files, modified 0 files, removed 0 files
+    "###);
+
+    // Move the working copy of the default workspace, then undo it from the
+    // second workspace
+    test_env.jj_cmd_ok(&main_path, &["new", "root()"]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(&secondary_path, &["undo"]);
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Hint: The working copies of these workspaces were not updated and are now stale: default
+    Run `jj workspace update-stale` in them to update them.
+    "###);
+    let (_stdout, stderr) = test_env.jj_cmd_ok(&main_path, &["workspace", "update-stale"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Working copy now at: rlvkpnrz 8183d0fc (empty) (no description set)
+    Added 1 files, modified 0 files, removed 0 files
+    "###);
+    insta::assert_snapshot!(get_log_output(&test_env, &main_path), @r###"
+    ◉  0a77a39d7d6f second@
+    │ @  8183d0fcaa4c default@
+    ├─╯
+    ◉  751b12b7b981
+    ◉  000000000000
+    "###);
+}
+
 #[test]
 fn test_workspaces_update_stale_noop() {
     let test_env = TestEnvironment::default();
@@ -599,6 +690,52 @@ fn test_workspaces_update_stale_snapshot() {
     "###);
 }
 
+/// Test rebuilding a corrupted working copy state.
+#[test]
+fn test_workspaces_update_stale_force_rebuild() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "main"]);
+    let main_path = test_env.env_root().join("main");
+
+    std::fs::write(main_path.join("file1"), "base\n").unwrap();
+    std::fs::write(main_path.join("file2"), "base\n").unwrap();
+    test_env.jj_cmd_ok(&main_path, &["new"]);
+
+    // Corrupt the tree state, and modify a file that jj doesn't know about yet
+    let tree_state_path = main_path.join(".jj/working_copy/tree_state");
+    std::fs::write(&tree_state_path, "garbage").unwrap();
+    std::fs::write(main_path.join("file1"), "modified\n").unwrap();
+    let stderr = test_env.jj_cmd_internal_error(&main_path, &["status"]);
+    assert!(
+        stderr.contains("Hint: The working copy state may be corrupted."),
+        "{stderr}"
+    );
+
+    let op_log_before =
+        test_env.jj_cmd_success(&main_path, &["op", "log", "--ignore-working-copy"]);
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &main_path,
+        &["workspace", "update-stale", "--force-rebuild"],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Rebuilt 2 file states from commit fb7d552feb4b
+    Hint: Files which differ from that commit will be recorded as modifications by the next command.
+    "###);
+    // The op store isn't touched
+    let op_log_after = test_env.jj_cmd_success(&main_path, &["op", "log", "--ignore-working-copy"]);
+    assert_eq!(op_log_before, op_log_after);
+
+    // The modified file is snapshotted instead of being lost
+    let stdout = test_env.jj_cmd_success(&main_path, &["status"]);
+    insta::assert_snapshot!(stdout, @r###"
+    Working copy changes:
+    M file1
+    Working copy : rlvkpnrz 4a6ed909 (no description set)
+    Parent commit: qpvuntsm 4c296ec9 (no description set)
+    "###);
+}
+
 /// Test forgetting workspaces
 #[test]
 fn test_workspaces_forget() {
@@ -617,7 +754,7 @@ fn test_workspaces_forget() {
     // When listing workspaces, only the secondary workspace shows up
     let stdout = test_env.jj_cmd_success(&main_path, &["workspace", "list"]);
     insta::assert_snapshot!(stdout, @r###"
-    secondary: pmmvwywv 18463f43 (empty) (no description set)
+    secondary: pmmvwywv 18463f43 (empty) (no description set) (updated 2001-02-03 08:05:09 by operation e94ba9d067f8)
     "###);
 
     // `jj status` tells us that there's no working copy here
@@ -680,16 +817,16 @@ fn test_workspaces_forget_multi_transaction() {
     // there should be three workspaces
     let stdout = test_env.jj_cmd_success(&main_path, &["workspace", "list"]);
     insta::assert_snapshot!(stdout, @r###"
-    default: rlvkpnrz 909d51b1 (empty) (no description set)
-    second: pmmvwywv 18463f43 (empty) (no description set)
-    third: rzvqmyuk cc383fa2 (empty) (no description set)
+    default (current): rlvkpnrz 909d51b1 (empty) (no description set) (updated 2001-02-03 08:05:10 by operation cbe54511302d)
+    second: pmmvwywv 18463f43 (empty) (no description set) (updated 2001-02-03 08:05:09 by operation 5674508fe3c7)
+    third: rzvqmyuk cc383fa2 (empty) (no description set) (updated 2001-02-03 08:05:10 by operation cbe54511302d)
     "###);
 
     // delete two at once, in a single tx
     test_env.jj_cmd_ok(&main_path, &["workspace", "forget", "second", "third"]);
     let stdout = test_env.jj_cmd_success(&main_path, &["workspace", "list"]);
     insta::assert_snapshot!(stdout, @r###"
-    default: rlvkpnrz 909d51b1 (empty) (no description set)
+    default (current): rlvkpnrz 909d51b1 (empty) (no description set) (updated 2001-02-03 08:05:12 by operation 6c88cdee70e6)
     "###);
 
     // the op log should have multiple workspaces forgotten in a single tx
@@ -703,24 +840,207 @@ fn test_workspaces_forget_multi_transaction() {
     // now, undo, and that should restore both workspaces
     test_env.jj_cmd_ok(&main_path, &["op", "undo"]);
 
-    // finally, there should be three workspaces at the end
+    // finally, there should be three workspaces at the end. Their recorded
+    // paths were kept, so they're found again.
     let stdout = test_env.jj_cmd_success(&main_path, &["workspace", "list"]);
     insta::assert_snapshot!(stdout, @r###"
-    default: rlvkpnrz 909d51b1 (empty) (no description set)
-    second: pmmvwywv 18463f43 (empty) (no description set)
-    third: rzvqmyuk cc383fa2 (empty) (no description set)
+    default (current): rlvkpnrz 909d51b1 (empty) (no description set) (updated 2001-02-03 08:05:15 by operation b4bd6a7ff8a0)
+    second: pmmvwywv 18463f43 (empty) (no description set) (updated 2001-02-03 08:05:09 by operation 5674508fe3c7)
+    third: rzvqmyuk cc383fa2 (empty) (no description set) (updated 2001-02-03 08:05:10 by operation cbe54511302d)
     "###);
 }
 
-/// Test context of commit summary template
+#[test]
+fn test_workspaces_forget_remove_jj_dir() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "main"]);
+    let main_path = test_env.env_root().join("main");
+    let second_path = test_env.env_root().join("second");
+    let third_path = test_env.env_root().join("third");
+
+    std::fs::write(main_path.join("file"), "contents").unwrap();
+    test_env.jj_cmd_ok(&main_path, &["new"]);
+    test_env.jj_cmd_ok(&main_path, &["workspace", "add", "../second"]);
+    test_env.jj_cmd_ok(&main_path, &["workspace", "add", "../third"]);
+
+    // The current workspace's .jj directory can't be removed
+    let stderr = test_env.jj_cmd_failure(&main_path, &["workspace", "forget", "--remove-jj-dir"]);
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Cannot remove the .jj directory of the current workspace
+    Hint: Run the command from another workspace, or forget the workspace without --remove-jj-dir.
+    "###);
+
+    // Only the .jj directory is removed, the working-copy files are kept
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &main_path,
+        &["workspace", "forget", "--remove-jj-dir", "second"],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr.replace('\\', "/"), @r###"
+    Removed the .jj directory of workspace second: $TEST_ENV/second/.jj
+    "###);
+    assert!(!second_path.join(".jj").exists());
+    assert!(second_path.join("file").exists());
+
+    // The .jj directory holding the repo is never removed
+    let (_stdout, stderr) = test_env.jj_cmd_ok(
+        &third_path,
+        &["workspace", "forget", "--remove-jj-dir", "default"],
+    );
+    insta::assert_snapshot!(stderr.replace('\\', "/"), @r###"
+    Warning: Not removing $TEST_ENV/main/.jj since it contains the repo
+    "###);
+    assert!(main_path.join(".jj").join("repo").is_dir());
+    let stdout = test_env.jj_cmd_success(&third_path, &["workspace", "list"]);
+    insta::assert_snapshot!(stdout, @r###"
+    third (current): rzvqmyuk cc383fa2 (empty) (no description set) (updated 2001-02-03 08:05:13 by operation ab3d0d9da261)
+    "###);
+}
+
+#[test]
+fn test_workspaces_forget_include_working_copy() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "main"]);
+    let main_path = test_env.env_root().join("main");
+
+    std::fs::write(main_path.join("file"), "contents").unwrap();
+    test_env.jj_cmd_ok(&main_path, &["new"]);
+    test_env.jj_cmd_ok(&main_path, &["workspace", "add", "../secondary"]);
+    test_env.jj_cmd_ok(&main_path, &["workspace", "add", "../third"]);
+    let third_path = test_env.env_root().join("third");
+    std::fs::write(third_path.join("file"), "changed").unwrap();
+    test_env.jj_cmd_ok(&third_path, &["st"]);
+    insta::assert_snapshot!(get_log_output(&test_env, &main_path), @r###"
+    ◉  ec127615be42 third@
+    │ ◉  18463f438cc9 secondary@
+    ├─╯
+    │ @  909d51b17292 default@
+    ├─╯
+    ◉  4e8f9d2be039
+    ◉  000000000000
+    "###);
+
+    // The current workspace can't be forgotten this way
+    let stderr = test_env.jj_cmd_failure(
+        &main_path,
+        &["workspace", "forget", "--include-working-copy"],
+    );
+    insta::assert_snapshot!(stderr, @r###"
+    Error: Cannot abandon the working-copy commit of the current workspace
+    Hint: Run the command from another workspace, or forget the workspace without --include-working-copy.
+    "###);
+
+    // The empty working-copy commit is abandoned, the other one is kept
+    let (stdout, stderr) = test_env.jj_cmd_ok(
+        &main_path,
+        &[
+            "workspace",
+            "forget",
+            "--include-working-copy",
+            "secondary",
+            "third",
+        ],
+    );
+    insta::assert_snapshot!(stdout, @"");
+    insta::assert_snapshot!(stderr, @r###"
+    Abandoned working-copy commit of workspace secondary: pmmvwywv 18463f43 (empty) (no description set)
+    Warning: Kept the working-copy commits of these workspaces since they have changes or are in use:
+      third: rzvqmyuk ec127615 (no description set)
+    "###);
+    insta::assert_snapshot!(get_log_output(&test_env, &main_path), @r###"
+    ◉  ec127615be42
+    │ @  909d51b17292
+    ├─╯
+    ◉  4e8f9d2be039
+    ◉  000000000000
+    "###);
+
+    // The operation can be undone
+    test_env.jj_cmd_ok(&main_path, &["undo"]);
+    insta::assert_snapshot!(get_log_output(&test_env, &main_path), @r###"
+    ◉  ec127615be42 third@
+    │ ◉  18463f438cc9 secondary@
+    ├─╯
+    │ @  909d51b17292 default@
+    ├─╯
+    ◉  4e8f9d2be039
+    ◉  000000000000
+    "###);
+}
+
+/// Test listing workspaces that are stale or whose directories are missing
+#[test]
+fn test_list_workspaces_stale_and_missing() {
+    let test_env = TestEnvironment::default();
+    test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "main"]);
+    let main_path = test_env.env_root().join("main");
+    std::fs::write(main_path.join("file"), "contents\n").unwrap();
+    test_env.jj_cmd_ok(&main_path, &["commit", "-m", "initial"]);
+    test_env.jj_cmd_ok(&main_path, &["workspace", "add", "../second"]);
+    test_env.jj_cmd_ok(&main_path, &["workspace", "add", "../third"]);
+
+    // Rewrite the working-copy commit of the second workspace from the main
+    // workspace, and delete the directory of the third workspace
+    test_env.jj_cmd_ok(&main_path, &["new", "@-"]);
+    std::fs::write(main_path.join("file"), "changed\n").unwrap();
+    test_env.jj_cmd_ok(&main_path, &["squash", "--into", "second@"]);
+    std::fs::remove_dir_all(test_env.env_root().join("third")).unwrap();
+
+    let template =
+        r#"separate(" ", name, current, stale, missing, last_operation_id.substr(0, 12)) ++ "\n""#;
+    let stdout = test_env.jj_cmd_success(&main_path, &["workspace", "list", "-T", template]);
+    insta::assert_snapshot!(stdout, @r###"
+    default true false false d8266be88c97
+    second false true false 431d548aee78
+    third false false true
+    "###);
+    let stdout = test_env.jj_cmd_success(&main_path, &["workspace", "list"]);
+    insta::assert_snapshot!(stdout, @r###"
+    default (current): royxmykx b6d1823e (empty) (no description set) (updated 2001-02-03 08:05:12 by operation d8266be88c97)
+    second (stale): pmmvwywv d3e26bc0 (no description set) (updated 2001-02-03 08:05:09 by operation 431d548aee78)
+    third (missing): rzvqmyuk 963f6838 (empty) (no description set)
+    "###);
+    let stdout = test_env.jj_cmd_success(&main_path, &["workspace", "list", "--stale"]);
+    insta::assert_snapshot!(stdout, @r###"
+    second (stale): pmmvwywv d3e26bc0 (no description set) (updated 2001-02-03 08:05:09 by operation 431d548aee78)
+    "###);
+
+    // The time of the last update is unknown for the missing workspace
+    let stdout = test_env.jj_cmd_success(
+        &main_path,
+        &[
+            "workspace",
+            "list",
+            "-T",
+            r#"name ++ ": " ++ last_updated ++ "\n""#,
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    default: 2001-02-03 04:05:12.000 +07:00
+    second: 2001-02-03 04:05:09.000 +07:00
+    third: <Error: Last operation of the workspace is unknown>
+    "###);
+
+    // The second workspace is no longer stale once it's updated
+    test_env.jj_cmd_ok(
+        &test_env.env_root().join("second"),
+        &["workspace", "update-stale"],
+    );
+    let stdout = test_env.jj_cmd_success(&main_path, &["workspace", "list", "--stale"]);
+    insta::assert_snapshot!(stdout, @"");
+}
+
+/// Test context of workspace list template
 #[test]
 fn test_list_workspaces_template() {
     let test_env = TestEnvironment::default();
     test_env.jj_cmd_ok(test_env.env_root(), &["git", "init", "main"]);
     test_env.add_config(
         r#"
-        templates.commit_summary = """commit_id.short() ++ " " ++ description.first_line() ++
-                                      if(current_working_copy, " (current)")"""
+        templates.workspace_list = """name ++ ": " ++ target.commit_id().short() ++ " " ++
+                                      target.description().first_line() ++
+                                      if(target.current_working_copy(), " (current)") ++ "\n"
+                                   """
         "#,
     );
     let main_path = test_env.env_root().join("main");
@@ -745,6 +1065,20 @@ fn test_list_workspaces_template() {
     default: 8183d0fcaa4c 
     second: 0a77a39d7d6f  (current)
     "###);
+
+    let stdout = test_env.jj_cmd_success(
+        &secondary_path,
+        &[
+            "workspace",
+            "list",
+            "-T",
+            r#"separate(" ", name, current, stale) ++ "\n""#,
+        ],
+    );
+    insta::assert_snapshot!(stdout, @r###"
+    default false false
+    second true false
+    "###);
 }
 
 /// Test getting the workspace root from primary and secondary workspaces
@@ -802,7 +1136,7 @@ fn test_debug_snapshot() {
     ◉  000000000000 root()
     "###);
     test_env.jj_cmd_ok(&repo_path, &["describe", "-m", "initial"]);
-    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "log"]);
+    let stdout = test_env.jj_cmd_success(&repo_path, &["op", "log", "--all"]);
     insta::assert_snapshot!(stdout, @r###"
     @  9ac6e7144e8a test-username@host.example.com 2001-02-03 04:05:10.000 +07:00 - 2001-02-03 04:05:10.000 +07:00
     │  describe commit 4e8f9d2be039994f589b4e57ac5e9488703e604d
@@ -380,6 +380,34 @@ pub fn parse_conflict(input: &[u8], num_sides: usize) -> Option<Vec<Merge<Conten
     }
 }
 
+/// Finds conflict markers in `input`, and returns the 0-based line numbers of
+/// the conflict start markers.
+///
+/// Unlike `parse_conflict()`, conflicts with any number of sides are found.
+/// Text that merely resembles a marker is ignored: a conflict needs a start
+/// marker, a well-formed body, and an end marker.
+pub fn find_conflict_markers(input: &[u8]) -> Vec<usize> {
+    let mut start_line_numbers = vec![];
+    let mut pos = 0;
+    let mut conflict_start = None;
+    for (line_number, line) in input.split_inclusive(|b| *b == b'\n').enumerate() {
+        if CONFLICT_MARKER_REGEX.is_match_at(line, 0) {
+            if line[0] == CONFLICT_START_LINE_CHAR {
+                conflict_start = Some((line_number, pos + line.len()));
+            } else if line[0] == CONFLICT_END_LINE_CHAR {
+                if let Some((start_line_number, body_start)) = conflict_start.take() {
+                    let hunk = parse_conflict_hunk(&input[body_start..pos]);
+                    if hunk.num_sides() > 1 {
+                        start_line_numbers.push(start_line_number);
+                    }
+                }
+            }
+        }
+        pos += line.len();
+    }
+    start_line_numbers
+}
+
 fn parse_conflict_hunk(input: &[u8]) -> Merge<ContentHunk> {
     enum State {
         Diff,
@@ -439,6 +467,10 @@ fn parse_conflict_hunk(input: &[u8]) -> Merge<ContentHunk> {
         }
     }
 
+    if adds.len() != removes.len() + 1 {
+        // Doesn't look like a conflict
+        return Merge::resolved(ContentHunk(vec![]));
+    }
     Merge::from_removes_adds(removes, adds)
 }
 
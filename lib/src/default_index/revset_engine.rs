@@ -15,8 +15,8 @@
 #![allow(missing_docs)]
 
 use std::cell::RefCell;
-use std::cmp::{Ordering, Reverse};
-use std::collections::{BTreeSet, BinaryHeap, HashSet};
+use std::cmp::{min, Ordering, Reverse};
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
 use std::ops::Range;
 use std::rc::Rc;
 use std::sync::Arc;
@@ -886,6 +886,43 @@ impl<'index> EvaluationContext<'index> {
                 });
                 Ok(Box::new(EagerRevset { positions }))
             }
+            ResolvedExpression::ForkPoint(expression1, expression2) => {
+                let positions1 = self
+                    .evaluate(expression1)?
+                    .positions()
+                    .attach(index)
+                    .collect_vec();
+                let positions2 = self
+                    .evaluate(expression2)?
+                    .positions()
+                    .attach(index)
+                    .collect_vec();
+                if positions1.is_empty() || positions2.is_empty() {
+                    return Err(RevsetEvaluationError::Other(
+                        "Cannot find the fork point of an empty set of revisions".to_owned(),
+                    ));
+                }
+                let positions = index
+                    .common_ancestors_pos(&positions1, &positions2)
+                    .into_iter()
+                    .rev()
+                    .collect();
+                Ok(Box::new(EagerRevset { positions }))
+            }
+            ResolvedExpression::Midpoint(candidates) => {
+                let positions = self
+                    .evaluate(candidates)?
+                    .positions()
+                    .attach(index)
+                    .collect_vec();
+                if positions.is_empty() {
+                    return Err(RevsetEvaluationError::Other(
+                        "Cannot find the midpoint of an empty set of revisions".to_owned(),
+                    ));
+                }
+                let positions = vec![find_midpoint_pos(index, &positions)];
+                Ok(Box::new(EagerRevset { positions }))
+            }
             ResolvedExpression::Latest { candidates, count } => {
                 let candidate_set = self.evaluate(candidates)?;
                 Ok(Box::new(
@@ -996,6 +1033,95 @@ impl<'index> EvaluationContext<'index> {
     }
 }
 
+/// Finds the candidate which splits `positions` most evenly into its ancestors
+/// and the other commits, similar to `git bisect`.
+///
+/// `positions` must be non-empty and sorted in descending order. Ancestors are
+/// counted by following parents within the candidates, which is exact if the
+/// candidates are a range such as `x..y`.
+fn find_midpoint_pos(index: &CompositeIndex, positions: &[IndexPosition]) -> IndexPosition {
+    // Number the candidates so that parents come before their children.
+    let total = positions.len();
+    let local_indices: HashMap<IndexPosition, usize> = positions
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &pos)| (pos, i))
+        .collect();
+    let parents_within = positions
+        .iter()
+        .rev()
+        .map(|&pos| {
+            index
+                .entry_by_pos(pos)
+                .parent_positions()
+                .iter()
+                .filter_map(|parent_pos| local_indices.get(parent_pos).copied())
+                .collect_vec()
+        })
+        .collect_vec();
+    let mut remaining_children = vec![0; total];
+    for &parent in parents_within.iter().flatten() {
+        remaining_children[parent] += 1;
+    }
+
+    // Compute the ancestor set of each candidate as a bitset in a single pass.
+    // A set is dropped once all its children have been visited, and is reused
+    // by a sole remaining child, so linear history stays cheap.
+    let mut ancestor_sets: Vec<Option<Vec<u64>>> = vec![None; total];
+    let mut ancestor_counts = vec![0; total];
+    for (i, parents) in parents_within.iter().enumerate() {
+        let word_len = i / 64 + 1;
+        let (mut set, count) = match parents.as_slice() {
+            [] => (vec![0; word_len], 1),
+            &[parent] => {
+                let set = if remaining_children[parent] == 1 {
+                    ancestor_sets[parent].take().unwrap()
+                } else {
+                    ancestor_sets[parent].clone().unwrap()
+                };
+                (set, ancestor_counts[parent] + 1)
+            }
+            _ => {
+                let mut set = vec![0; word_len];
+                for &parent in parents {
+                    let parent_set = ancestor_sets[parent].as_ref().unwrap();
+                    for (word, parent_word) in set.iter_mut().zip(parent_set) {
+                        *word |= parent_word;
+                    }
+                }
+                let count = set
+                    .iter()
+                    .map(|word| word.count_ones() as usize)
+                    .sum::<usize>()
+                    + 1;
+                (set, count)
+            }
+        };
+        set.resize(word_len, 0);
+        set[i / 64] |= 1 << (i % 64);
+        ancestor_counts[i] = count;
+        for &parent in parents {
+            remaining_children[parent] -= 1;
+            if remaining_children[parent] == 0 {
+                ancestor_sets[parent] = None;
+            }
+        }
+        if remaining_children[i] > 0 {
+            ancestor_sets[i] = Some(set);
+        }
+    }
+
+    let best_index = (0..total)
+        .max_by_key(|&i| {
+            let count = ancestor_counts[i];
+            // Prefer the oldest candidate among equally good ones.
+            (min(count, total - count), Reverse(i))
+        })
+        .unwrap();
+    positions[total - 1 - best_index]
+}
+
 struct PurePredicateFn<F>(F);
 
 impl<F> fmt::Debug for PurePredicateFn<F> {
@@ -1081,6 +1207,11 @@ fn build_predicate_fn(
             let commit = store.get_commit(&entry.commit_id()).unwrap();
             commit.has_conflict().unwrap()
         }),
+        RevsetFilterPredicate::Signed => box_pure_predicate_fn(move |index, pos| {
+            let entry = index.entry_by_pos(pos);
+            let commit = store.get_commit(&entry.commit_id()).unwrap();
+            commit.is_signed()
+        }),
         RevsetFilterPredicate::Extension(ext) => {
             let ext = ext.clone();
             box_pure_predicate_fn(move |index, pos| {
@@ -69,6 +69,33 @@ pub fn find_nonword_ranges(text: &[u8]) -> Vec<Range<usize>> {
         .collect()
 }
 
+/// Algorithm used to find the unchanged ranges between two sequences of
+/// tokens.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DiffAlgorithm {
+    /// Matches the least frequent tokens first and recurses into the regions
+    /// between them.
+    #[default]
+    Histogram,
+    /// Finds a shortest edit script using Myers' O(ND) algorithm.
+    Myers,
+}
+
+impl DiffAlgorithm {
+    fn unchanged_ranges(
+        self,
+        left: &[u8],
+        right: &[u8],
+        left_ranges: &[Range<usize>],
+        right_ranges: &[Range<usize>],
+    ) -> Vec<(Range<usize>, Range<usize>)> {
+        match self {
+            DiffAlgorithm::Histogram => unchanged_ranges(left, right, left_ranges, right_ranges),
+            DiffAlgorithm::Myers => myers_unchanged_ranges(left, right, left_ranges, right_ranges),
+        }
+    }
+}
+
 struct Histogram<'a> {
     word_to_positions: HashMap<&'a [u8], Vec<usize>>,
     count_to_words: BTreeMap<usize, Vec<&'a [u8]>>,
@@ -287,6 +314,92 @@ pub(crate) fn unchanged_ranges(
     result
 }
 
+/// Edit distance above which [`myers_unchanged_ranges()`] gives up and falls
+/// back to the histogram algorithm. The memory used by the search grows
+/// quadratically with the edit distance.
+const MYERS_MAX_EDIT_DISTANCE: usize = 2000;
+
+/// Finds unchanged ranges among the ones given as arguments using Myers' diff
+/// algorithm. The data between those ranges is ignored.
+fn myers_unchanged_ranges(
+    left: &[u8],
+    right: &[u8],
+    left_ranges: &[Range<usize>],
+    right_ranges: &[Range<usize>],
+) -> Vec<(Range<usize>, Range<usize>)> {
+    let left_tokens = left_ranges.iter().map(|r| &left[r.clone()]).collect_vec();
+    let right_tokens = right_ranges.iter().map(|r| &right[r.clone()]).collect_vec();
+    let n = left_tokens.len() as isize;
+    let m = right_tokens.len() as isize;
+    if n == 0 || m == 0 {
+        return vec![];
+    }
+
+    // `v[k + offset]` is the furthest x reached on diagonal k = x - y. Before
+    // each step d, the part of `v` that step d reads (diagonals -d-1..=d+1) is
+    // saved so that the path can be traced back afterwards.
+    let max_d = (n + m) as usize;
+    let offset = max_d as isize + 1;
+    let mut v = vec![0isize; 2 * max_d + 3];
+    let mut trace: Vec<Vec<isize>> = vec![];
+    let mut found = false;
+    for d in 0..=max_d.min(MYERS_MAX_EDIT_DISTANCE) as isize {
+        trace.push(v[(offset - d - 1) as usize..=(offset + d + 1) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let i = (offset + k) as usize;
+            let mut x = if k == -d || (k != d && v[i - 1] < v[i + 1]) {
+                v[i + 1]
+            } else {
+                v[i - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && left_tokens[x as usize] == right_tokens[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[i] = x;
+            if x >= n && y >= m {
+                found = true;
+                break;
+            }
+        }
+        if found {
+            break;
+        }
+    }
+    if !found {
+        return unchanged_ranges(left, right, left_ranges, right_ranges);
+    }
+
+    let mut result = vec![];
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        // `v[0]` corresponds to diagonal -d-1.
+        let at = |k: isize| v[(k + d + 1) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            result.push((
+                left_ranges[x as usize].clone(),
+                right_ranges[y as usize].clone(),
+            ));
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    result.reverse();
+    result
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 struct UnchangedRange {
     base_range: Range<usize>,
@@ -332,6 +445,7 @@ pub struct Diff<'input> {
     // relative to the base region's start. By making them relative, they don't need to change
     // when the base range changes.
     unchanged_regions: Vec<UnchangedRange>,
+    algorithm: DiffAlgorithm,
 }
 
 /// Takes the current regions and intersects it with the new unchanged ranges
@@ -383,6 +497,16 @@ impl<'input> Diff<'input> {
     pub fn for_tokenizer(
         inputs: &[&'input [u8]],
         tokenizer: impl Fn(&[u8]) -> Vec<Range<usize>>,
+    ) -> Self {
+        Self::for_tokenizer_with_algorithm(inputs, tokenizer, DiffAlgorithm::default())
+    }
+
+    /// Like [`Diff::for_tokenizer()`], but finds the unchanged regions (and
+    /// any later refinements) with the given `algorithm`.
+    pub fn for_tokenizer_with_algorithm(
+        inputs: &[&'input [u8]],
+        tokenizer: impl Fn(&[u8]) -> Vec<Range<usize>>,
+        algorithm: DiffAlgorithm,
     ) -> Self {
         assert!(!inputs.is_empty());
         let base_input = inputs[0];
@@ -403,7 +527,7 @@ impl<'input> Diff<'input> {
             offsets: vec![],
         }];
         for (i, other_token_ranges) in other_token_ranges.iter().enumerate() {
-            let unchanged_diff_ranges = unchanged_ranges(
+            let unchanged_diff_ranges = algorithm.unchanged_ranges(
                 base_input,
                 other_inputs[i],
                 &base_token_ranges,
@@ -425,6 +549,7 @@ impl<'input> Diff<'input> {
             base_input,
             other_inputs,
             unchanged_regions,
+            algorithm,
         };
         diff.compact_unchanged_regions();
         diff
@@ -441,7 +566,14 @@ impl<'input> Diff<'input> {
     // probably mean that many callers repeat the same code. Perhaps it
     // should be possible to refine a whole diff *or* individual hunks.
     pub fn default_refinement(inputs: &[&'input [u8]]) -> Self {
-        let mut diff = Diff::for_tokenizer(inputs, find_line_ranges);
+        Self::default_refinement_with_algorithm(inputs, DiffAlgorithm::default())
+    }
+
+    pub fn default_refinement_with_algorithm(
+        inputs: &[&'input [u8]],
+        algorithm: DiffAlgorithm,
+    ) -> Self {
+        let mut diff = Diff::for_tokenizer_with_algorithm(inputs, find_line_ranges, algorithm);
         diff.refine_changed_regions(find_word_ranges);
         diff.refine_changed_regions(find_nonword_ranges);
         diff
@@ -480,7 +612,8 @@ impl<'input> Diff<'input> {
                 slices.push(&self.other_inputs[i][changed_range]);
             }
 
-            let refined_diff = Diff::for_tokenizer(&slices, &tokenizer);
+            let refined_diff =
+                Diff::for_tokenizer_with_algorithm(&slices, &tokenizer, self.algorithm);
 
             for UnchangedRange {
                 base_range,
@@ -607,6 +740,15 @@ impl<'diff, 'input> Iterator for DiffHunkIterator<'diff, 'input> {
 /// algorithm correctly). It first diffs lines in the input and then refines
 /// the changed ranges at the word level.
 pub fn diff<'a>(left: &'a [u8], right: &'a [u8]) -> Vec<DiffHunk<'a>> {
+    diff_with_algorithm(left, right, DiffAlgorithm::default())
+}
+
+/// Like [`diff()`], but uses the given `algorithm`.
+pub fn diff_with_algorithm<'a>(
+    left: &'a [u8],
+    right: &'a [u8],
+    algorithm: DiffAlgorithm,
+) -> Vec<DiffHunk<'a>> {
     if left == right {
         return vec![DiffHunk::Matching(left)];
     }
@@ -617,7 +759,7 @@ pub fn diff<'a>(left: &'a [u8], right: &'a [u8]) -> Vec<DiffHunk<'a>> {
         return vec![DiffHunk::Different(vec![left, b""])];
     }
 
-    Diff::default_refinement(&[left, right])
+    Diff::default_refinement_with_algorithm(&[left, right], algorithm)
         .hunks()
         .collect_vec()
 }
@@ -782,6 +924,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_myers_unchanged_ranges_insert_in_middle() {
+        assert_eq!(
+            myers_unchanged_ranges(
+                b"a b b c",
+                b"a b X b c",
+                &[0..1, 2..3, 4..5, 6..7],
+                &[0..1, 2..3, 4..5, 6..7, 8..9],
+            ),
+            vec![(0..1, 0..1), (2..3, 2..3), (4..5, 6..7), (6..7, 8..9)]
+        );
+    }
+
+    #[test]
+    fn test_myers_unchanged_ranges_non_unique_removed() {
+        assert_eq!(
+            myers_unchanged_ranges(
+                b"a a a a",
+                b"a b a c",
+                &[0..1, 2..3, 4..5, 6..7],
+                &[0..1, 2..3, 4..5, 6..7],
+            ),
+            vec![(0..1, 0..1), (2..3, 4..5)]
+        );
+    }
+
+    #[test]
+    fn test_myers_unchanged_ranges_nothing_in_common() {
+        assert_eq!(
+            myers_unchanged_ranges(b"a b", b"c d", &[0..1, 2..3], &[0..1, 2..3]),
+            vec![]
+        );
+    }
+
     #[test]
     fn test_intersect_regions_existing_empty() {
         let actual = intersect_regions(vec![], &[(20..25, 55..60)]);
@@ -964,6 +1140,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_diff_with_algorithm_unique_word_moved() {
+        assert_eq!(
+            diff_with_algorithm(b"a a a U", b"U a a a", DiffAlgorithm::Histogram),
+            vec![
+                DiffHunk::Different(vec![b"a a a ", b""]),
+                DiffHunk::Matching(b"U"),
+                DiffHunk::Different(vec![b"", b" a a a"]),
+            ],
+        );
+        assert_eq!(
+            diff_with_algorithm(b"a a a U", b"U a a a", DiffAlgorithm::Myers),
+            vec![
+                DiffHunk::Different(vec![b"", b"U "]),
+                DiffHunk::Matching(b"a a a"),
+                DiffHunk::Different(vec![b" U", b""]),
+            ],
+        );
+    }
+
     #[test]
     fn test_diff_recursion_needed() {
         assert_eq!(
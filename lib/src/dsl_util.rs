@@ -443,6 +443,12 @@ impl<'a> AliasFunctionOverloads<'a> {
         self.overloads.iter().map(|(params, _)| params.len())
     }
 
+    fn ids(self) -> impl Iterator<Item = AliasId<'a>> {
+        self.overloads
+            .iter()
+            .map(move |(params, _)| AliasId::Function(self.name, params))
+    }
+
     fn min_arity(self) -> usize {
         self.arities().next().unwrap()
     }
@@ -623,11 +629,20 @@ where
             let Some((id, params, defn)) = overloads.find_by_arity(function.arity()) else {
                 let min = overloads.min_arity();
                 let max = overloads.max_arity();
-                let err = if max - min + 1 == overloads.arities().len() {
+                let mut err = if max - min + 1 == overloads.arities().len() {
                     function.invalid_arguments_count(min, Some(max))
                 } else {
                     function.invalid_arguments_count_with_arities(overloads.arities())
                 };
+                // Point to the alias declarations since the function may look
+                // like a builtin.
+                let decls = overloads.ids().map(|id| format!(r#""{id}""#)).join(", ");
+                let noun = if overloads.overloads.len() == 1 {
+                    "alias"
+                } else {
+                    "aliases"
+                };
+                err.message = format!("{} (declared as {noun} {decls})", err.message);
                 return Err(E::invalid_arguments(err));
             };
             // Resolve arguments in the current scope, and pass them in to the alias
@@ -19,8 +19,10 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 use std::default::Default;
 use std::io::Read;
 use std::path::PathBuf;
+use std::time::Duration;
 use std::{fmt, iter, str};
 
+use backoff::ExponentialBackoff;
 use git2::Oid;
 use itertools::Itertools;
 use tempfile::NamedTempFile;
@@ -209,6 +211,9 @@ pub struct GitImportStats {
     /// Remote `(ref_name, (old_remote_ref, new_target))`s to be merged in to
     /// the local refs.
     pub changed_remote_refs: BTreeMap<RefName, (RemoteRef, RefTarget)>,
+    /// Remote refs which hadn't changed since the last import, but were
+    /// reset and imported again by `refetch()`.
+    pub reimported_refs: Vec<RefName>,
 }
 
 #[derive(Debug)]
@@ -333,6 +338,7 @@ pub fn import_some_refs(
     let stats = GitImportStats {
         abandoned_commits,
         changed_remote_refs,
+        reimported_refs: vec![],
     };
     Ok(stats)
 }
@@ -1046,6 +1052,11 @@ pub enum GitRemoteManagementError {
         name = REMOTE_NAME_FOR_LOCAL_GIT_REPO
     )]
     RemoteReservedForLocalGitRepo,
+    #[error("Invalid Git remote URL '{url}'")]
+    InvalidUrl {
+        url: String,
+        source: gix::url::parse::Error,
+    },
     #[error(transparent)]
     InternalGitError(git2::Error),
 }
@@ -1148,6 +1159,32 @@ pub fn set_remote_url(
     git_repo: &git2::Repository,
     remote_name: &str,
     new_remote_url: &str,
+) -> Result<(), GitRemoteManagementError> {
+    check_remote_url_update(git_repo, remote_name, new_remote_url)?;
+    git_repo
+        .remote_set_url(remote_name, new_remote_url)
+        .map_err(GitRemoteManagementError::InternalGitError)?;
+    Ok(())
+}
+
+/// Sets the URL used for pushing to the remote, which is separate from the
+/// URL used for fetching.
+pub fn set_remote_push_url(
+    git_repo: &git2::Repository,
+    remote_name: &str,
+    new_push_url: &str,
+) -> Result<(), GitRemoteManagementError> {
+    check_remote_url_update(git_repo, remote_name, new_push_url)?;
+    git_repo
+        .remote_set_pushurl(remote_name, Some(new_push_url))
+        .map_err(GitRemoteManagementError::InternalGitError)?;
+    Ok(())
+}
+
+fn check_remote_url_update(
+    git_repo: &git2::Repository,
+    remote_name: &str,
+    new_url: &str,
 ) -> Result<(), GitRemoteManagementError> {
     if remote_name == REMOTE_NAME_FOR_LOCAL_GIT_REPO {
         return Err(GitRemoteManagementError::RemoteReservedForLocalGitRepo);
@@ -1164,9 +1201,12 @@ pub fn set_remote_url(
         }
     })?;
 
-    git_repo
-        .remote_set_url(remote_name, new_remote_url)
-        .map_err(GitRemoteManagementError::InternalGitError)?;
+    // libgit2 accepts any string, so the URL is checked before it's written
+    // to the config.
+    gix::url::parse(new_url.into()).map_err(|err| GitRemoteManagementError::InvalidUrl {
+        url: new_url.to_owned(),
+        source: err,
+    })?;
     Ok(())
 }
 
@@ -1229,8 +1269,86 @@ pub fn fetch(
     callbacks: RemoteCallbacks<'_>,
     git_settings: &GitSettings,
 ) -> Result<GitFetchStats, GitFetchError> {
-    // Perform a `git fetch` on the local git repo, updating the remote-tracking
-    // branches in the git repo.
+    fetch_impl(
+        mut_repo,
+        git_repo,
+        remote_name,
+        branch_names,
+        callbacks,
+        git_settings,
+        false,
+    )
+}
+
+/// Like `fetch()`, but resets the matching remote-tracking branches before
+/// importing them, so they are imported as if they were seen for the first
+/// time.
+///
+/// The local branches are merged with the remote branches as usual, so
+/// branches whose targets are unchanged won't be conflicted.
+#[tracing::instrument(skip(mut_repo, git_repo, callbacks))]
+pub fn refetch(
+    mut_repo: &mut MutableRepo,
+    git_repo: &git2::Repository,
+    remote_name: &str,
+    branch_names: &[StringPattern],
+    callbacks: RemoteCallbacks<'_>,
+    git_settings: &GitSettings,
+) -> Result<GitFetchStats, GitFetchError> {
+    fetch_impl(
+        mut_repo,
+        git_repo,
+        remote_name,
+        branch_names,
+        callbacks,
+        git_settings,
+        true,
+    )
+}
+
+fn fetch_impl(
+    mut_repo: &mut MutableRepo,
+    git_repo: &git2::Repository,
+    remote_name: &str,
+    branch_names: &[StringPattern],
+    callbacks: RemoteCallbacks<'_>,
+    git_settings: &GitSettings,
+    refetch: bool,
+) -> Result<GitFetchStats, GitFetchError> {
+    let default_branch = fetch_refs(git_repo, remote_name, branch_names, true, callbacks)?;
+    let import_stats = import_fetched_refs(
+        mut_repo,
+        remote_name,
+        branch_names,
+        git_settings,
+        refetch,
+        true,
+    )?;
+    let stats = GitFetchStats {
+        default_branch,
+        import_stats,
+    };
+    Ok(stats)
+}
+
+/// Performs a `git fetch` on the local git repo, updating the remote-tracking
+/// branches in the git repo, but doesn't import them into the jj repo. Returns
+/// the remote's default branch.
+///
+/// Since this doesn't touch the jj repo, fetches from several remotes can run
+/// concurrently, each with its own `git2::Repository`. The fetched refs should
+/// then be imported by `import_fetched_refs()`.
+///
+/// If `fetch_tags` is false, no tags are fetched into `refs/tags`, so a
+/// colocated repo won't import them later either.
+#[tracing::instrument(skip(git_repo, callbacks))]
+pub fn fetch_refs(
+    git_repo: &git2::Repository,
+    remote_name: &str,
+    branch_names: &[StringPattern],
+    fetch_tags: bool,
+    callbacks: RemoteCallbacks<'_>,
+) -> Result<Option<String>, GitFetchError> {
     let mut remote = git_repo.find_remote(remote_name).map_err(|err| {
         if is_remote_not_found_err(&err) {
             GitFetchError::NoSuchRemote(remote_name.to_string())
@@ -1244,6 +1362,12 @@ pub fn fetch(
     fetch_options.proxy_options(proxy_options);
     let callbacks = callbacks.into_git();
     fetch_options.remote_callbacks(callbacks);
+    let autotag = if fetch_tags {
+        git2::AutotagOption::Unspecified
+    } else {
+        git2::AutotagOption::None
+    };
+    fetch_options.download_tags(autotag);
     // At this point, we are only updating Git's remote tracking branches, not the
     // local branches.
     let refspecs: Vec<_> = branch_names
@@ -1258,15 +1382,16 @@ pub fn fetch(
         .ok_or(GitFetchError::InvalidBranchPattern)?;
     if refspecs.is_empty() {
         // Don't fall back to the base refspecs.
-        let stats = GitFetchStats::default();
-        return Ok(stats);
+        return Ok(None);
     }
+    // Files like `shallow` and `FETCH_HEAD` are briefly locked while written,
+    // so fetches running concurrently in the same repo may have to wait.
     tracing::debug!("remote.download");
-    remote.download(&refspecs, Some(&mut fetch_options))?;
+    retry_if_locked(|| remote.download(&refspecs, Some(&mut fetch_options)))?;
     tracing::debug!("remote.prune");
-    remote.prune(None)?;
+    retry_if_locked(|| remote.prune(None))?;
     tracing::debug!("remote.update_tips");
-    remote.update_tips(None, false, git2::AutotagOption::Unspecified, None)?;
+    retry_if_locked(|| remote.update_tips(None, false, autotag, None))?;
     // TODO: We could make it optional to get the default branch since we only care
     // about it on clone.
     let mut default_branch = None;
@@ -1282,23 +1407,116 @@ pub fn fetch(
     }
     tracing::debug!("remote.disconnect");
     remote.disconnect()?;
+    Ok(default_branch)
+}
+
+fn retry_if_locked<T>(mut f: impl FnMut() -> Result<T, git2::Error>) -> Result<T, git2::Error> {
+    let backoff = ExponentialBackoff {
+        initial_interval: Duration::from_millis(1),
+        max_elapsed_time: Some(Duration::from_secs(10)),
+        ..Default::default()
+    };
+    let try_once = || {
+        f().map_err(|err| {
+            if err.code() == git2::ErrorCode::Locked {
+                backoff::Error::transient(err)
+            } else {
+                backoff::Error::permanent(err)
+            }
+        })
+    };
+    backoff::retry(backoff, try_once).map_err(|err| match err {
+        backoff::Error::Permanent(err) | backoff::Error::Transient { err, .. } => err,
+    })
+}
 
-    // Import the remote-tracking branches into the jj repo and update jj's
-    // local branches. We also import local tags since remote tags should have
-    // been merged by Git.
+/// Imports the remote-tracking branches fetched from `remote_name` by
+/// `fetch_refs()` into the jj repo, and updates jj's local branches.
+///
+/// If `refetch` is true, the matching remote-tracking branches are reset
+/// before importing, so they are imported with the current settings even if
+/// they haven't changed since the last import. Branches deleted on the remote
+/// are left to the import to delete.
+///
+/// If `import_tags` is false, tags are neither imported nor deleted, so the
+/// tags imported previously are kept as is.
+pub fn import_fetched_refs(
+    mut_repo: &mut MutableRepo,
+    remote_name: &str,
+    branch_names: &[StringPattern],
+    git_settings: &GitSettings,
+    refetch: bool,
+    import_tags: bool,
+) -> Result<GitImportStats, GitImportError> {
+    if branch_names.is_empty() {
+        // Nothing was fetched.
+        return Ok(GitImportStats::default());
+    }
+    // We also import local tags since remote tags should have been merged by
+    // Git.
     tracing::debug!("import_refs");
-    let import_stats = import_some_refs(mut_repo, git_settings, |ref_name| {
+    let git_ref_filter = |ref_name: &RefName| {
         to_remote_branch(ref_name, remote_name)
             .map(|branch| branch_names.iter().any(|pattern| pattern.matches(branch)))
-            .unwrap_or_else(|| matches!(ref_name, RefName::Tag(_)))
-    })?;
-    let stats = GitFetchStats {
-        default_branch,
-        import_stats,
+            .unwrap_or_else(|| import_tags && matches!(ref_name, RefName::Tag(_)))
     };
+    let reset_refs = if refetch {
+        reset_remote_branches(mut_repo, remote_name, branch_names)?
+    } else {
+        vec![]
+    };
+    let mut stats = import_some_refs(mut_repo, git_settings, git_ref_filter)?;
+    // Report the reset refs against their state before the reset.
+    for (ref_name, old_remote_ref) in reset_refs {
+        let Some((reset_remote_ref, new_target)) = stats.changed_remote_refs.get_mut(&ref_name)
+        else {
+            continue;
+        };
+        if *new_target == old_remote_ref.target {
+            stats.changed_remote_refs.remove(&ref_name);
+            stats.reimported_refs.push(ref_name);
+        } else {
+            *reset_remote_ref = old_remote_ref;
+        }
+    }
     Ok(stats)
 }
 
+/// Forgets the remote-tracking branches of `remote_name` matching
+/// `branch_names` which still exist in the Git repo, and returns them.
+fn reset_remote_branches(
+    mut_repo: &mut MutableRepo,
+    remote_name: &str,
+    branch_names: &[StringPattern],
+) -> Result<Vec<(RefName, RemoteRef)>, GitImportError> {
+    let git_backend = get_git_backend(mut_repo.store()).ok_or(GitImportError::UnexpectedBackend)?;
+    let git_repo = git_backend.git_repo();
+    let remote_branches = mut_repo
+        .view()
+        .remote_branches(remote_name)
+        .filter(|(branch, _)| branch_names.iter().any(|pattern| pattern.matches(branch)))
+        .map(|(branch, remote_ref)| (branch.to_owned(), remote_ref.clone()))
+        .collect_vec();
+    let mut reset_refs = vec![];
+    for (branch, remote_ref) in remote_branches {
+        let git_ref_name = format!("refs/remotes/{remote_name}/{branch}");
+        let git_ref = git_repo
+            .try_find_reference(&git_ref_name)
+            .map_err(GitImportError::from_git)?;
+        if git_ref.is_none() {
+            continue;
+        }
+        mut_repo.set_remote_branch(&branch, remote_name, RemoteRef::absent());
+        mut_repo.set_git_ref_target(&git_ref_name, RefTarget::absent());
+        let ref_name = RefName::RemoteBranch {
+            branch,
+            remote: remote_name.to_owned(),
+        };
+        reset_refs.push((ref_name, remote_ref));
+    }
+    Ok(reset_refs)
+}
+
 #[derive(Error, Debug, PartialEq)]
 pub enum GitPushError {
     #[error("No git remote named '{0}'")]
@@ -1310,8 +1528,12 @@ pub enum GitPushError {
     RemoteReservedForLocalGitRepo,
     #[error("Refs in unexpected location: {0:?}")]
     RefInUnexpectedLocation(Vec<String>),
+    #[error("Refs already exist on the remote at a different location: {0:?}")]
+    RefAlreadyExists(Vec<String>),
     #[error("Remote rejected the update of some refs (do you have permission to push to {0:?}?)")]
     RefUpdateRejected(Vec<String>),
+    #[error("Refs can't be deleted under a custom ref prefix: {0:?}")]
+    RefDeletionUnsupported(Vec<String>),
     // TODO: I'm sure there are other errors possible, such as transport-level errors,
     // and errors caused by the remote rejecting the push.
     #[error("Unexpected git error when pushing")]
@@ -1323,6 +1545,33 @@ pub struct GitBranchPushTargets {
     pub branch_updates: Vec<(String, BranchPushUpdate)>,
 }
 
+/// Tags to be pushed as they are in the underlying Git repo.
+///
+/// Annotated tags are pushed as-is. Unless `force` is set, tags that already
+/// exist on the remote at a different location are not updated.
+#[derive(Clone, Debug, Default)]
+pub struct GitTagPushTargets {
+    pub tag_names: Vec<String>,
+    pub force: bool,
+}
+
+impl GitTagPushTargets {
+    fn qualified_names(&self) -> Vec<String> {
+        self.tag_names
+            .iter()
+            .map(|name| format!("refs/tags/{name}"))
+            .collect()
+    }
+
+    fn remote_ref_expectation(&self) -> RemoteRefExpectation<'static> {
+        if self.force {
+            RemoteRefExpectation::Anywhere
+        } else {
+            RemoteRefExpectation::AbsentOrUnchanged
+        }
+    }
+}
+
 pub struct GitRefUpdate {
     pub qualified_name: String,
     /// Expected position on the remote or None if we expect the ref to not
@@ -1333,12 +1582,14 @@ pub struct GitRefUpdate {
     pub new_target: Option<CommitId>,
 }
 
-/// Pushes the specified branches and updates the repo view accordingly.
+/// Pushes the specified branches along with the tags, and updates the repo view
+/// accordingly.
 pub fn push_branches(
     mut_repo: &mut MutableRepo,
     git_repo: &git2::Repository,
     remote_name: &str,
     targets: &GitBranchPushTargets,
+    tags: &GitTagPushTargets,
     callbacks: RemoteCallbacks<'_>,
 ) -> Result<(), GitPushError> {
     let ref_updates = targets
@@ -1350,7 +1601,14 @@ pub fn push_branches(
             new_target: update.new_target.clone(),
         })
         .collect_vec();
-    push_updates(mut_repo, git_repo, remote_name, &ref_updates, callbacks)?;
+    push_updates_and_tags(
+        mut_repo,
+        git_repo,
+        remote_name,
+        &ref_updates,
+        tags,
+        callbacks,
+    )?;
 
     // TODO: add support for partially pushed refs? we could update the view
     // excluding rejected refs, but the transaction would be aborted anyway
@@ -1368,6 +1626,56 @@ pub fn push_branches(
     Ok(())
 }
 
+/// Pushes the specified branches under `ref_prefix` on the remote instead of
+/// `refs/heads/` along with the tags, without updating the repo view.
+///
+/// There are no remote-tracking branches for such refs, so a ref is only
+/// updated if it doesn't exist on the remote yet or if the update is a
+/// fast-forward. Deleting branches isn't supported.
+pub fn push_branches_to_ref_prefix(
+    repo: &dyn Repo,
+    git_repo: &git2::Repository,
+    remote_name: &str,
+    ref_prefix: &str,
+    targets: &GitBranchPushTargets,
+    tags: &GitTagPushTargets,
+    callbacks: RemoteCallbacks<'_>,
+) -> Result<(), GitPushError> {
+    let mut qualified_names = vec![];
+    let mut refspecs = vec![];
+    let mut deleted_names = vec![];
+    for (branch_name, update) in &targets.branch_updates {
+        let qualified_name = format!("{ref_prefix}{branch_name}");
+        if let Some(new_target) = &update.new_target {
+            refspecs.push(format!("+{}:{qualified_name}", new_target.hex()));
+            qualified_names.push(qualified_name);
+        } else {
+            deleted_names.push(qualified_name);
+        }
+    }
+    if !deleted_names.is_empty() {
+        return Err(GitPushError::RefDeletionUnsupported(deleted_names));
+    }
+    let qualified_tag_names = tags.qualified_names();
+    let mut qualified_remote_refs_expectations: HashMap<_, _> = qualified_names
+        .iter()
+        .map(|qualified_name| (qualified_name.as_str(), RemoteRefExpectation::FastForward))
+        .collect();
+    for qualified_name in &qualified_tag_names {
+        qualified_remote_refs_expectations
+            .insert(qualified_name.as_str(), tags.remote_ref_expectation());
+        refspecs.push(format!("+{qualified_name}:{qualified_name}"));
+    }
+    push_refs(
+        repo,
+        git_repo,
+        remote_name,
+        &qualified_remote_refs_expectations,
+        &refspecs,
+        callbacks,
+    )
+}
+
 /// Pushes the specified Git refs without updating the repo view.
 pub fn push_updates(
     repo: &dyn Repo,
@@ -1376,12 +1684,31 @@ pub fn push_updates(
     updates: &[GitRefUpdate],
     callbacks: RemoteCallbacks<'_>,
 ) -> Result<(), GitPushError> {
-    let mut qualified_remote_refs_expected_locations = HashMap::new();
+    push_updates_and_tags(
+        repo,
+        git_repo,
+        remote_name,
+        updates,
+        &GitTagPushTargets::default(),
+        callbacks,
+    )
+}
+
+fn push_updates_and_tags(
+    repo: &dyn Repo,
+    git_repo: &git2::Repository,
+    remote_name: &str,
+    updates: &[GitRefUpdate],
+    tags: &GitTagPushTargets,
+    callbacks: RemoteCallbacks<'_>,
+) -> Result<(), GitPushError> {
+    let qualified_tag_names = tags.qualified_names();
+    let mut qualified_remote_refs_expectations = HashMap::new();
     let mut refspecs = vec![];
     for update in updates {
-        qualified_remote_refs_expected_locations.insert(
+        qualified_remote_refs_expectations.insert(
             update.qualified_name.as_str(),
-            update.expected_current_target.as_ref(),
+            RemoteRefExpectation::Location(update.expected_current_target.as_ref()),
         );
         if let Some(new_target) = &update.new_target {
             // We always force-push. We use the push_negotiation callback in
@@ -1395,23 +1722,39 @@ pub fn push_updates(
             refspecs.push(format!(":{}", update.qualified_name));
         }
     }
+    for qualified_name in &qualified_tag_names {
+        qualified_remote_refs_expectations
+            .insert(qualified_name.as_str(), tags.remote_ref_expectation());
+        refspecs.push(format!("+{qualified_name}:{qualified_name}"));
+    }
     // TODO(ilyagr): `push_refs`, or parts of it, should probably be inlined. This
     // requires adjusting some tests.
     push_refs(
         repo,
         git_repo,
         remote_name,
-        &qualified_remote_refs_expected_locations,
+        &qualified_remote_refs_expectations,
         &refspecs,
         callbacks,
     )
 }
 
+/// Pushes the specified tags without updating the repo view.
+pub fn push_tags(
+    repo: &dyn Repo,
+    git_repo: &git2::Repository,
+    remote_name: &str,
+    tags: &GitTagPushTargets,
+    callbacks: RemoteCallbacks<'_>,
+) -> Result<(), GitPushError> {
+    push_updates_and_tags(repo, git_repo, remote_name, &[], tags, callbacks)
+}
+
 fn push_refs(
     repo: &dyn Repo,
     git_repo: &git2::Repository,
     remote_name: &str,
-    qualified_remote_refs_expected_locations: &HashMap<&str, Option<&CommitId>>,
+    qualified_remote_refs_expectations: &HashMap<&str, RemoteRefExpectation>,
     refspecs: &[String],
     callbacks: RemoteCallbacks<'_>,
 ) -> Result<(), GitPushError> {
@@ -1425,11 +1768,10 @@ fn push_refs(
             GitPushError::InternalGitError(err)
         }
     })?;
-    let mut remaining_remote_refs: HashSet<_> = qualified_remote_refs_expected_locations
-        .keys()
-        .copied()
-        .collect();
+    let mut remaining_remote_refs: HashSet<_> =
+        qualified_remote_refs_expectations.keys().copied().collect();
     let mut failed_push_negotiations = vec![];
+    let mut existing_remote_refs = vec![];
     let push_result = {
         let mut push_options = git2::PushOptions::new();
         let mut proxy_options = git2::ProxyOptions::new();
@@ -1441,13 +1783,42 @@ fn push_refs(
                 let dst_refname = update
                     .dst_refname()
                     .expect("Expect reference name to be valid UTF-8");
-                let expected_remote_location = *qualified_remote_refs_expected_locations
+                let expectation = *qualified_remote_refs_expectations
                     .get(dst_refname)
                     .expect("Push is trying to move a ref it wasn't asked to move");
                 let oid_to_maybe_commitid =
                     |oid: git2::Oid| (!oid.is_zero()).then(|| CommitId::from_bytes(oid.as_bytes()));
                 let actual_remote_location = oid_to_maybe_commitid(update.src());
                 let local_location = oid_to_maybe_commitid(update.dst());
+                let expected_remote_location = match expectation {
+                    RemoteRefExpectation::Location(location) => location,
+                    RemoteRefExpectation::AbsentOrUnchanged => {
+                        if !update.src().is_zero() && update.src() != update.dst() {
+                            existing_remote_refs.push(dst_refname.to_string());
+                        }
+                        continue;
+                    }
+                    RemoteRefExpectation::FastForward => {
+                        let is_fast_forward = match (&actual_remote_location, &local_location) {
+                            (None, _) => true,
+                            (Some(actual), Some(local)) => {
+                                actual == local
+                                    || repo.index().has_id(actual)
+                                        && repo.index().is_ancestor(actual, local)
+                            }
+                            (Some(_), None) => false,
+                        };
+                        if !is_fast_forward {
+                            tracing::info!(
+                                "Cannot push {dst_refname} to {local_location:?}; it is at \
+                                 {actual_remote_location:?} on the server, which isn't an ancestor",
+                            );
+                            failed_push_negotiations.push(dst_refname.to_string());
+                        }
+                        continue;
+                    }
+                    RemoteRefExpectation::Anywhere => continue,
+                };
 
                 match allow_push(
                     repo.index(),
@@ -1490,7 +1861,7 @@ fn push_refs(
                     }
                 }
             }
-            if failed_push_negotiations.is_empty() {
+            if failed_push_negotiations.is_empty() && existing_remote_refs.is_empty() {
                 Ok(())
             } else {
                 Err(git2::Error::from_str("failed push negotiation"))
@@ -1518,6 +1889,10 @@ fn push_refs(
         Err(GitPushError::RefInUnexpectedLocation(
             failed_push_negotiations,
         ))
+    } else if !existing_remote_refs.is_empty() {
+        assert!(push_result.is_err());
+        existing_remote_refs.sort();
+        Err(GitPushError::RefAlreadyExists(existing_remote_refs))
     } else {
         push_result?;
         if remaining_remote_refs.is_empty() {
@@ -1534,6 +1909,21 @@ fn push_refs(
     }
 }
 
+/// What the location of a ref on the remote is expected to be before pushing.
+#[derive(Clone, Copy, Debug)]
+enum RemoteRefExpectation<'a> {
+    /// The ref is expected at the given location, or to not exist if `None`.
+    Location(Option<&'a CommitId>),
+    /// The ref is expected to not exist, or to already be at the pushed
+    /// location.
+    AbsentOrUnchanged,
+    /// The ref is expected to not exist, or to be an ancestor of the pushed
+    /// location.
+    FastForward,
+    /// The ref may be anywhere.
+    Anywhere,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum PushAllowReason {
     NormalMatch,
@@ -65,6 +65,8 @@ pub mod op_heads_store;
 pub mod op_store;
 pub mod op_walk;
 pub mod operation;
+pub mod opset;
+mod opset_parser;
 #[allow(missing_docs)]
 pub mod protos;
 pub mod refs;
@@ -91,3 +93,4 @@ pub mod union_find;
 pub mod view;
 pub mod working_copy;
 pub mod workspace;
+pub mod workspace_store;
@@ -53,7 +53,8 @@ use crate::fsmonitor::{watchman, WatchmanConfig};
 use crate::gitignore::GitIgnoreFile;
 use crate::lock::FileLock;
 use crate::matchers::{
-    DifferenceMatcher, EverythingMatcher, FilesMatcher, IntersectionMatcher, Matcher, PrefixMatcher,
+    DifferenceMatcher, EverythingMatcher, FilesMatcher, IntersectionMatcher, Matcher,
+    PrefixMatcher, Visit,
 };
 use crate::merge::{Merge, MergeBuilder, MergedTreeValue};
 use crate::merged_tree::{MergedTree, MergedTreeBuilder};
@@ -64,8 +65,8 @@ use crate::settings::HumanByteSize;
 use crate::store::Store;
 use crate::tree::Tree;
 use crate::working_copy::{
-    CheckoutError, CheckoutStats, LockedWorkingCopy, ResetError, SnapshotError, SnapshotOptions,
-    SnapshotProgress, WorkingCopy, WorkingCopyFactory, WorkingCopyStateError,
+    CheckoutError, CheckoutOptions, CheckoutStats, LockedWorkingCopy, ResetError, SnapshotError,
+    SnapshotOptions, SnapshotProgress, WorkingCopy, WorkingCopyFactory, WorkingCopyStateError,
 };
 
 #[cfg(unix)]
@@ -484,6 +485,16 @@ struct DirectoryToVisit<'a> {
     file_states: FileStates<'a>,
 }
 
+/// Files in the working copy which aren't tracked by the tree state.
+#[derive(Clone, Debug, Default)]
+pub struct UntrackedFiles {
+    /// Untracked files matching the `.gitignore` or `.jjignore` rules.
+    pub ignored: Vec<RepoPathBuf>,
+    /// Untracked files not matching the ignore rules. These are usually
+    /// picked up by the next snapshot.
+    pub not_ignored: Vec<RepoPathBuf>,
+}
+
 #[derive(Debug, Error)]
 pub enum TreeStateError {
     #[error("Reading tree state from {path}")]
@@ -764,9 +775,13 @@ impl TreeState {
             fsmonitor_settings,
             progress,
             max_new_file_size,
+            matcher: paths_matcher,
         } = options;
 
         let sparse_matcher = self.sparse_matcher();
+        // A partial snapshot doesn't see the changes to the other paths, so the
+        // fsmonitor clock shouldn't move past them.
+        let is_partial = paths_matcher.visit(RepoPath::root()) != Visit::AllRecursively;
 
         let fsmonitor_clock_needs_save = fsmonitor_settings != FsmonitorSettings::None;
         let mut is_dirty = fsmonitor_clock_needs_save;
@@ -778,8 +793,14 @@ impl TreeState {
             None => &EverythingMatcher,
             Some(fsmonitor_matcher) => fsmonitor_matcher.as_ref(),
         };
+        let fsmonitor_matcher = IntersectionMatcher::new(fsmonitor_matcher, paths_matcher);
+        let watchman_clock = if is_partial {
+            self.watchman_clock.clone()
+        } else {
+            watchman_clock
+        };
 
-        let matcher = IntersectionMatcher::new(sparse_matcher.as_ref(), fsmonitor_matcher);
+        let matcher = IntersectionMatcher::new(sparse_matcher.as_ref(), &fsmonitor_matcher);
         if matcher.visit(RepoPath::root()).is_nothing() {
             // No need to iterate file states to build empty deleted_files.
             self.watchman_clock = watchman_clock;
@@ -798,16 +819,17 @@ impl TreeState {
                 git_ignore: base_ignores,
                 file_states: self.file_states.all(),
             };
-            self.visit_directory(
-                &matcher,
-                &current_tree,
+            let visitor = SnapshotVisitor {
+                tree_state: self,
+                matcher: &matcher,
+                current_tree: &current_tree,
                 tree_entries_tx,
                 file_states_tx,
                 present_files_tx,
-                directory_to_visit,
                 progress,
                 max_new_file_size,
-            )
+            };
+            walk_directory(&matcher, directory_to_visit, false, &visitor)
         })?;
 
         let mut tree_builder = MergedTreeBuilder::new(self.tree_id.clone());
@@ -869,173 +891,43 @@ impl TreeState {
         Ok(is_dirty)
     }
 
-    #[allow(clippy::too_many_arguments)]
-    fn visit_directory(
+    /// Walks the working-copy directory the same way as the snapshot does, and
+    /// collects the files that aren't tracked.
+    ///
+    /// Unlike the snapshot, ignored directories are descended into so the
+    /// files within them can be reported as ignored.
+    #[instrument(skip_all)]
+    pub fn untracked_files(
         &self,
+        base_ignores: Arc<GitIgnoreFile>,
         matcher: &dyn Matcher,
-        current_tree: &MergedTree,
-        tree_entries_tx: Sender<(RepoPathBuf, MergedTreeValue)>,
-        file_states_tx: Sender<(RepoPathBuf, FileState)>,
-        present_files_tx: Sender<RepoPathBuf>,
-        directory_to_visit: DirectoryToVisit,
-        progress: Option<&SnapshotProgress>,
-        max_new_file_size: u64,
-    ) -> Result<(), SnapshotError> {
-        let DirectoryToVisit {
-            dir,
-            disk_dir,
-            git_ignore,
-            file_states,
-        } = directory_to_visit;
-
-        if matcher.visit(&dir).is_nothing() {
-            return Ok(());
+    ) -> Result<UntrackedFiles, SnapshotError> {
+        // Files outside the sparse patterns aren't checked out, so they are
+        // never considered untracked.
+        let sparse_matcher = self.sparse_matcher();
+        let matcher = IntersectionMatcher::new(sparse_matcher.as_ref(), matcher);
+        let directory_to_visit = DirectoryToVisit {
+            dir: RepoPathBuf::root(),
+            disk_dir: self.working_copy_path.clone(),
+            git_ignore: base_ignores,
+            file_states: self.file_states.all(),
+        };
+        let (untracked_files_tx, untracked_files_rx) = channel();
+        let visitor = UntrackedFilesVisitor { untracked_files_tx };
+        walk_directory(&matcher, directory_to_visit, false, &visitor)?;
+        drop(visitor);
+        let mut untracked = UntrackedFiles::default();
+        for (path, ignored) in untracked_files_rx
+            .iter()
+            .sorted_unstable_by(|(path1, _), (path2, _)| path1.cmp(path2))
+        {
+            if ignored {
+                untracked.ignored.push(path);
+            } else {
+                untracked.not_ignored.push(path);
+            }
         }
-
-        let git_ignore = git_ignore
-            .chain_with_file(&dir.to_internal_dir_string(), disk_dir.join(".gitignore"))?;
-        let dir_entries = disk_dir
-            .read_dir()
-            .unwrap()
-            .map(|maybe_entry| maybe_entry.unwrap())
-            .collect_vec();
-        dir_entries.into_par_iter().try_for_each_with(
-            (
-                tree_entries_tx.clone(),
-                file_states_tx.clone(),
-                present_files_tx.clone(),
-            ),
-            |(tree_entries_tx, file_states_tx, present_files_tx),
-             entry|
-             -> Result<(), SnapshotError> {
-                let file_type = entry.file_type().unwrap();
-                let file_name = entry.file_name();
-                let name = file_name
-                    .to_str()
-                    .ok_or_else(|| SnapshotError::InvalidUtf8Path {
-                        path: file_name.clone(),
-                    })?;
-
-                if name == ".jj" || name == ".git" {
-                    return Ok(());
-                }
-                let path = dir.join(RepoPathComponent::new(name));
-                let maybe_current_file_state = file_states.get(&path);
-                if let Some(file_state) = &maybe_current_file_state {
-                    if file_state.file_type == FileType::GitSubmodule {
-                        return Ok(());
-                    }
-                }
-
-                if file_type.is_dir() {
-                    let file_states = file_states.prefixed(&path);
-                    if git_ignore.matches(&path.to_internal_dir_string()) {
-                        // If the whole directory is ignored, visit only paths we're already
-                        // tracking.
-                        for (tracked_path, current_file_state) in file_states {
-                            if !matcher.matches(tracked_path) {
-                                continue;
-                            }
-                            let disk_path = tracked_path.to_fs_path(&self.working_copy_path);
-                            let metadata = match disk_path.symlink_metadata() {
-                                Ok(metadata) => metadata,
-                                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
-                                    continue;
-                                }
-                                Err(err) => {
-                                    return Err(SnapshotError::Other {
-                                        message: format!(
-                                            "Failed to stat file {}",
-                                            disk_path.display()
-                                        ),
-                                        err: err.into(),
-                                    });
-                                }
-                            };
-                            if let Some(new_file_state) = file_state(&metadata) {
-                                present_files_tx.send(tracked_path.to_owned()).ok();
-                                let update = self.get_updated_tree_value(
-                                    tracked_path,
-                                    disk_path,
-                                    Some(&current_file_state),
-                                    current_tree,
-                                    &new_file_state,
-                                )?;
-                                if let Some(tree_value) = update {
-                                    tree_entries_tx
-                                        .send((tracked_path.to_owned(), tree_value))
-                                        .ok();
-                                }
-                                if new_file_state != current_file_state {
-                                    file_states_tx
-                                        .send((tracked_path.to_owned(), new_file_state))
-                                        .ok();
-                                }
-                            }
-                        }
-                    } else {
-                        let directory_to_visit = DirectoryToVisit {
-                            dir: path,
-                            disk_dir: entry.path(),
-                            git_ignore: git_ignore.clone(),
-                            file_states,
-                        };
-                        self.visit_directory(
-                            matcher,
-                            current_tree,
-                            tree_entries_tx.clone(),
-                            file_states_tx.clone(),
-                            present_files_tx.clone(),
-                            directory_to_visit,
-                            progress,
-                            max_new_file_size,
-                        )?;
-                    }
-                } else if matcher.matches(&path) {
-                    if let Some(progress) = progress {
-                        progress(&path);
-                    }
-                    if maybe_current_file_state.is_none()
-                        && git_ignore.matches(path.as_internal_file_string())
-                    {
-                        // If it wasn't already tracked and it matches
-                        // the ignored paths, then
-                        // ignore it.
-                    } else {
-                        let metadata = entry.metadata().map_err(|err| SnapshotError::Other {
-                            message: format!("Failed to stat file {}", entry.path().display()),
-                            err: err.into(),
-                        })?;
-                        if maybe_current_file_state.is_none() && metadata.len() > max_new_file_size
-                        {
-                            return Err(SnapshotError::NewFileTooLarge {
-                                path: entry.path().clone(),
-                                size: HumanByteSize(metadata.len()),
-                                max_size: HumanByteSize(max_new_file_size),
-                            });
-                        }
-                        if let Some(new_file_state) = file_state(&metadata) {
-                            present_files_tx.send(path.clone()).ok();
-                            let update = self.get_updated_tree_value(
-                                &path,
-                                entry.path(),
-                                maybe_current_file_state.as_ref(),
-                                current_tree,
-                                &new_file_state,
-                            )?;
-                            if let Some(tree_value) = update {
-                                tree_entries_tx.send((path.clone(), tree_value)).ok();
-                            }
-                            if Some(&new_file_state) != maybe_current_file_state.as_ref() {
-                                file_states_tx.send((path, new_file_state)).ok();
-                            }
-                        }
-                    }
-                }
-                Ok(())
-            },
-        )?;
-        Ok(())
+        Ok(untracked)
     }
 
     #[instrument(skip_all)]
@@ -1287,7 +1179,11 @@ impl TreeState {
         Ok(())
     }
 
-    pub fn check_out(&mut self, new_tree: &MergedTree) -> Result<CheckoutStats, CheckoutError> {
+    pub fn check_out(
+        &mut self,
+        new_tree: &MergedTree,
+        options: &CheckoutOptions,
+    ) -> Result<CheckoutStats, CheckoutError> {
         let old_tree = self.current_tree().map_err(|err| match err {
             err @ BackendError::ObjectNotFound { .. } => CheckoutError::SourceNotFound {
                 source: Box::new(err),
@@ -1295,7 +1191,7 @@ impl TreeState {
             other => CheckoutError::InternalBackendError(other),
         })?;
         let stats = self
-            .update(&old_tree, new_tree, self.sparse_matcher().as_ref())
+            .update(&old_tree, new_tree, self.sparse_matcher().as_ref(), options)
             .block_on()?;
         self.tree_id = new_tree.id();
         Ok(stats)
@@ -1304,6 +1200,7 @@ impl TreeState {
     pub fn set_sparse_patterns(
         &mut self,
         sparse_patterns: Vec<RepoPathBuf>,
+        options: &CheckoutOptions,
     ) -> Result<CheckoutStats, CheckoutError> {
         let tree = self.current_tree().map_err(|err| match err {
             err @ BackendError::ObjectNotFound { .. } => CheckoutError::SourceNotFound {
@@ -1316,9 +1213,11 @@ impl TreeState {
         let added_matcher = DifferenceMatcher::new(&new_matcher, &old_matcher);
         let removed_matcher = DifferenceMatcher::new(&old_matcher, &new_matcher);
         let empty_tree = MergedTree::resolved(Tree::null(self.store.clone(), RepoPathBuf::root()));
-        let added_stats = self.update(&empty_tree, &tree, &added_matcher).block_on()?;
+        let added_stats = self
+            .update(&empty_tree, &tree, &added_matcher, options)
+            .block_on()?;
         let removed_stats = self
-            .update(&tree, &empty_tree, &removed_matcher)
+            .update(&tree, &empty_tree, &removed_matcher, options)
             .block_on()?;
         self.sparse_patterns = sparse_patterns;
         assert_eq!(added_stats.updated_files, 0);
@@ -1331,6 +1230,7 @@ impl TreeState {
             added_files: added_stats.added_files,
             removed_files: removed_stats.removed_files,
             skipped_files: added_stats.skipped_files,
+            backup_tree_id: removed_stats.backup_tree_id,
         })
     }
 
@@ -1339,6 +1239,7 @@ impl TreeState {
         old_tree: &MergedTree,
         new_tree: &MergedTree,
         matcher: &dyn Matcher,
+        options: &CheckoutOptions,
     ) -> Result<CheckoutStats, CheckoutError> {
         // TODO: maybe it's better not include the skipped counts in the "intended"
         // counts
@@ -1347,17 +1248,23 @@ impl TreeState {
             added_files: 0,
             removed_files: 0,
             skipped_files: 0,
+            backup_tree_id: None,
         };
         let mut changed_file_states = Vec::new();
         let mut deleted_files = HashSet::new();
+        let mut backup_values = Vec::new();
         let mut diff_stream = Box::pin(
             old_tree
                 .diff_stream(new_tree, matcher)
                 .map(|(path, diff)| async {
                     match diff {
                         Ok((before, after)) => {
-                            let result = materialize_tree_value(&self.store, &path, after).await;
-                            (path, result.map(|value| (before.is_present(), value)))
+                            let result =
+                                materialize_tree_value(&self.store, &path, after.clone()).await;
+                            (
+                                path,
+                                result.map(|value| (before.is_present(), after, value)),
+                            )
                         }
                         Err(err) => (path, Err(err)),
                     }
@@ -1365,7 +1272,7 @@ impl TreeState {
                 .buffered(self.store.concurrency().max(1)),
         );
         while let Some((path, data)) = diff_stream.next().await {
-            let (present_before, after) = data?;
+            let (present_before, after_value, after) = data?;
             if after.is_absent() {
                 stats.removed_files += 1;
             } else if !present_before {
@@ -1376,6 +1283,13 @@ impl TreeState {
             let disk_path = path.to_fs_path(&self.working_copy_path);
 
             if present_before {
+                if options.backup_unsnapshotted {
+                    if let Some(value) = self.unsnapshotted_value(&path, &disk_path, old_tree)? {
+                        if value != after_value {
+                            backup_values.push((path.clone(), value));
+                        }
+                    }
+                }
                 fs::remove_file(&disk_path).ok();
             } else if disk_path.exists() {
                 changed_file_states.push((path, FileState::placeholder()));
@@ -1432,9 +1346,46 @@ impl TreeState {
         }
         self.file_states
             .merge_in(changed_file_states, &deleted_files);
+        if !backup_values.is_empty() {
+            let mut tree_builder = MergedTreeBuilder::new(old_tree.id());
+            for (path, value) in backup_values {
+                tree_builder.set_or_remove(path, value);
+            }
+            stats.backup_tree_id = Some(tree_builder.write_tree(&self.store)?);
+        }
         Ok(stats)
     }
 
+    /// Returns the on-disk value of the file at `path` if it was modified since
+    /// the last snapshot and differs from the value in `old_tree`.
+    fn unsnapshotted_value(
+        &self,
+        path: &RepoPath,
+        disk_path: &Path,
+        old_tree: &MergedTree,
+    ) -> Result<Option<MergedTreeValue>, CheckoutError> {
+        let Some(new_file_state) = disk_path
+            .symlink_metadata()
+            .ok()
+            .as_ref()
+            .and_then(file_state)
+        else {
+            return Ok(None);
+        };
+        let current_file_state = self.file_states().get(path);
+        self.get_updated_tree_value(
+            path,
+            disk_path.to_owned(),
+            current_file_state.as_ref(),
+            old_tree,
+            &new_file_state,
+        )
+        .map_err(|err| CheckoutError::Other {
+            message: format!("Failed to back up modified file {}", disk_path.display()),
+            err: err.into(),
+        })
+    }
+
     pub async fn reset(&mut self, new_tree: &MergedTree) -> Result<(), ResetError> {
         let old_tree = self.current_tree().map_err(|err| match err {
             err @ BackendError::ObjectNotFound { .. } => ResetError::SourceNotFound {
@@ -1498,6 +1449,247 @@ impl TreeState {
     }
 }
 
+/// Receives the entries found while walking the working-copy directory.
+trait DirectoryVisitor: Sync {
+    /// Visits a directory matching the ignore rules. Returns whether the walk
+    /// should descend into it.
+    fn visit_ignored_dir(
+        &self,
+        dir: &RepoPath,
+        file_states: FileStates<'_>,
+    ) -> Result<bool, SnapshotError>;
+
+    /// Visits a file accepted by the matcher. `ignored` is set if the file
+    /// isn't tracked and matches the ignore rules.
+    fn visit_file(
+        &self,
+        entry: &fs::DirEntry,
+        path: RepoPathBuf,
+        file_state: Option<FileState>,
+        ignored: bool,
+    ) -> Result<(), SnapshotError>;
+}
+
+/// Walks the working-copy directory in parallel, skipping `.jj`, `.git`, and
+/// Git submodules. Everything within an ignored directory is ignored.
+fn walk_directory(
+    matcher: &dyn Matcher,
+    directory_to_visit: DirectoryToVisit,
+    dir_ignored: bool,
+    visitor: &impl DirectoryVisitor,
+) -> Result<(), SnapshotError> {
+    let DirectoryToVisit {
+        dir,
+        disk_dir,
+        git_ignore,
+        file_states,
+    } = directory_to_visit;
+
+    if matcher.visit(&dir).is_nothing() {
+        return Ok(());
+    }
+
+    // `.jjignore` is chained after `.gitignore` so it takes precedence.
+    let git_ignore = git_ignore
+        .chain_with_file(&dir.to_internal_dir_string(), disk_dir.join(".gitignore"))?
+        .chain_with_file(&dir.to_internal_dir_string(), disk_dir.join(".jjignore"))?;
+    let read_dir_error = |err: std::io::Error| SnapshotError::Other {
+        message: format!("Failed to read directory {}", disk_dir.display()),
+        err: err.into(),
+    };
+    let dir_entries = disk_dir
+        .read_dir()
+        .map_err(read_dir_error)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(read_dir_error)?;
+    dir_entries
+        .into_par_iter()
+        .try_for_each(|entry| -> Result<(), SnapshotError> {
+            let file_type = entry.file_type().map_err(read_dir_error)?;
+            let file_name = entry.file_name();
+            let name = file_name
+                .to_str()
+                .ok_or_else(|| SnapshotError::InvalidUtf8Path {
+                    path: file_name.clone(),
+                })?;
+
+            if name == ".jj" || name == ".git" {
+                return Ok(());
+            }
+            let path = dir.join(RepoPathComponent::new(name));
+            let maybe_current_file_state = file_states.get(&path);
+            if let Some(file_state) = &maybe_current_file_state {
+                if file_state.file_type == FileType::GitSubmodule {
+                    return Ok(());
+                }
+            }
+
+            if file_type.is_dir() {
+                let file_states = file_states.prefixed(&path);
+                let ignored = dir_ignored || git_ignore.matches(&path.to_internal_dir_string());
+                if ignored && !visitor.visit_ignored_dir(&path, file_states)? {
+                    return Ok(());
+                }
+                let directory_to_visit = DirectoryToVisit {
+                    dir: path,
+                    disk_dir: entry.path(),
+                    git_ignore: git_ignore.clone(),
+                    file_states,
+                };
+                walk_directory(matcher, directory_to_visit, ignored, visitor)
+            } else if matcher.matches(&path) {
+                let ignored = maybe_current_file_state.is_none()
+                    && (dir_ignored || git_ignore.matches(path.as_internal_file_string()));
+                visitor.visit_file(&entry, path, maybe_current_file_state, ignored)
+            } else {
+                Ok(())
+            }
+        })
+}
+
+/// Collects the changes to the tree and the file states for the snapshot.
+struct SnapshotVisitor<'a> {
+    tree_state: &'a TreeState,
+    matcher: &'a dyn Matcher,
+    current_tree: &'a MergedTree,
+    tree_entries_tx: Sender<(RepoPathBuf, MergedTreeValue)>,
+    file_states_tx: Sender<(RepoPathBuf, FileState)>,
+    present_files_tx: Sender<RepoPathBuf>,
+    progress: Option<&'a SnapshotProgress<'a>>,
+    max_new_file_size: u64,
+}
+
+impl SnapshotVisitor<'_> {
+    fn update_file_state(
+        &self,
+        path: RepoPathBuf,
+        disk_path: PathBuf,
+        maybe_current_file_state: Option<&FileState>,
+        new_file_state: FileState,
+    ) -> Result<(), SnapshotError> {
+        self.present_files_tx.send(path.clone()).ok();
+        let update = self.tree_state.get_updated_tree_value(
+            &path,
+            disk_path,
+            maybe_current_file_state,
+            self.current_tree,
+            &new_file_state,
+        )?;
+        if let Some(tree_value) = update {
+            self.tree_entries_tx.send((path.clone(), tree_value)).ok();
+        }
+        if Some(&new_file_state) != maybe_current_file_state {
+            self.file_states_tx.send((path, new_file_state)).ok();
+        }
+        Ok(())
+    }
+}
+
+impl DirectoryVisitor for SnapshotVisitor<'_> {
+    fn visit_ignored_dir(
+        &self,
+        _dir: &RepoPath,
+        file_states: FileStates<'_>,
+    ) -> Result<bool, SnapshotError> {
+        // If the whole directory is ignored, visit only paths we're already
+        // tracking.
+        for (tracked_path, current_file_state) in file_states {
+            if !self.matcher.matches(tracked_path) {
+                continue;
+            }
+            let disk_path = tracked_path.to_fs_path(&self.tree_state.working_copy_path);
+            let metadata = match disk_path.symlink_metadata() {
+                Ok(metadata) => metadata,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    continue;
+                }
+                Err(err) => {
+                    return Err(SnapshotError::Other {
+                        message: format!("Failed to stat file {}", disk_path.display()),
+                        err: err.into(),
+                    });
+                }
+            };
+            if let Some(new_file_state) = file_state(&metadata) {
+                self.update_file_state(
+                    tracked_path.to_owned(),
+                    disk_path,
+                    Some(&current_file_state),
+                    new_file_state,
+                )?;
+            }
+        }
+        Ok(false)
+    }
+
+    fn visit_file(
+        &self,
+        entry: &fs::DirEntry,
+        path: RepoPathBuf,
+        maybe_current_file_state: Option<FileState>,
+        ignored: bool,
+    ) -> Result<(), SnapshotError> {
+        if let Some(progress) = self.progress {
+            progress(&path);
+        }
+        if ignored {
+            // If it wasn't already tracked and it matches the ignored paths,
+            // then ignore it.
+            return Ok(());
+        }
+        let metadata = entry.metadata().map_err(|err| SnapshotError::Other {
+            message: format!("Failed to stat file {}", entry.path().display()),
+            err: err.into(),
+        })?;
+        if maybe_current_file_state.is_none() && metadata.len() > self.max_new_file_size {
+            return Err(SnapshotError::NewFileTooLarge {
+                path: entry.path().clone(),
+                size: HumanByteSize(metadata.len()),
+                max_size: HumanByteSize(self.max_new_file_size),
+            });
+        }
+        if let Some(new_file_state) = file_state(&metadata) {
+            self.update_file_state(
+                path,
+                entry.path(),
+                maybe_current_file_state.as_ref(),
+                new_file_state,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Collects the untracked files, and whether each of them is ignored.
+struct UntrackedFilesVisitor {
+    untracked_files_tx: Sender<(RepoPathBuf, bool)>,
+}
+
+impl DirectoryVisitor for UntrackedFilesVisitor {
+    fn visit_ignored_dir(
+        &self,
+        _dir: &RepoPath,
+        _file_states: FileStates<'_>,
+    ) -> Result<bool, SnapshotError> {
+        // Unlike the snapshot, descend so the files within can be reported as
+        // ignored.
+        Ok(true)
+    }
+
+    fn visit_file(
+        &self,
+        _entry: &fs::DirEntry,
+        path: RepoPathBuf,
+        file_state: Option<FileState>,
+        ignored: bool,
+    ) -> Result<(), SnapshotError> {
+        if file_state.is_none() {
+            self.untracked_files_tx.send((path, ignored)).ok();
+        }
+        Ok(())
+    }
+}
+
 fn checkout_error_for_stat_error(err: std::io::Error, path: &Path) -> CheckoutError {
     CheckoutError::Other {
         message: format!("Failed to stat file {}", path.display()),
@@ -1634,6 +1826,46 @@ impl LocalWorkingCopy {
         &self.state_path
     }
 
+    /// Locks the working copy for rebuilding its tree state.
+    ///
+    /// Unlike `start_mutation()`, this doesn't fail if the tree state can't be
+    /// read or decoded. In that case, the tree state starts out empty. Call
+    /// `LockedLocalWorkingCopy::rebuild()` to repopulate it.
+    pub fn start_mutation_for_rebuild(&self) -> LockedLocalWorkingCopy {
+        let lock_path = self.state_path.join("working_copy.lock");
+        let lock = FileLock::lock(lock_path);
+
+        let tree_state = TreeState::load(
+            self.store.clone(),
+            self.working_copy_path.clone(),
+            self.state_path.clone(),
+        )
+        .unwrap_or_else(|err| {
+            tracing::warn!(?err, "discarding unreadable working copy state");
+            TreeState::empty(
+                self.store.clone(),
+                self.working_copy_path.clone(),
+                self.state_path.clone(),
+            )
+        });
+        let wc = LocalWorkingCopy {
+            store: self.store.clone(),
+            working_copy_path: self.working_copy_path.clone(),
+            state_path: self.state_path.clone(),
+            checkout_state: OnceCell::new(),
+            tree_state: OnceCell::with_value(tree_state),
+        };
+        let old_operation_id = wc.operation_id().clone();
+        let old_tree_id = wc.tree_state.get().unwrap().current_tree_id().clone();
+        LockedLocalWorkingCopy {
+            wc,
+            lock,
+            old_operation_id,
+            old_tree_id,
+            tree_state_dirty: true,
+        }
+    }
+
     fn write_proto(&self, proto: crate::protos::working_copy::Checkout) {
         let mut temp_file = NamedTempFile::new_in(&self.state_path).unwrap();
         temp_file
@@ -1692,6 +1924,21 @@ impl LocalWorkingCopy {
         Ok(self.tree_state()?.file_states())
     }
 
+    /// Lists the files in the working copy which aren't tracked. See
+    /// [`TreeState::untracked_files()`].
+    pub fn untracked_files(
+        &self,
+        base_ignores: Arc<GitIgnoreFile>,
+        matcher: &dyn Matcher,
+    ) -> Result<UntrackedFiles, SnapshotError> {
+        let tree_state = self
+            .tree_state()
+            .map_err(
+                |WorkingCopyStateError { message, err }| SnapshotError::Other { message, err },
+            )?;
+        tree_state.untracked_files(base_ignores, matcher)
+    }
+
     #[instrument(skip_all)]
     fn save(&mut self) {
         self.write_proto(crate::protos::working_copy::Checkout {
@@ -1801,7 +2048,11 @@ impl LockedWorkingCopy for LockedLocalWorkingCopy {
         Ok(tree_state.current_tree_id().clone())
     }
 
-    fn check_out(&mut self, commit: &Commit) -> Result<CheckoutStats, CheckoutError> {
+    fn check_out(
+        &mut self,
+        commit: &Commit,
+        options: &CheckoutOptions,
+    ) -> Result<CheckoutStats, CheckoutError> {
         // TODO: Write a "pending_checkout" file with the new TreeId so we can
         // continue an interrupted update if we find such a file.
         let new_tree = commit.tree()?;
@@ -1812,7 +2063,7 @@ impl LockedWorkingCopy for LockedLocalWorkingCopy {
                 message: "Failed to load the working copy state".to_string(),
                 err: err.into(),
             })?
-            .check_out(&new_tree)?;
+            .check_out(&new_tree, options)?;
         self.tree_state_dirty = true;
         Ok(stats)
     }
@@ -1852,6 +2103,7 @@ impl LockedWorkingCopy for LockedLocalWorkingCopy {
     fn set_sparse_patterns(
         &mut self,
         new_sparse_patterns: Vec<RepoPathBuf>,
+        options: &CheckoutOptions,
     ) -> Result<CheckoutStats, CheckoutError> {
         // TODO: Write a "pending_checkout" file with new sparse patterns so we can
         // continue an interrupted update if we find such a file.
@@ -1862,7 +2114,7 @@ impl LockedWorkingCopy for LockedLocalWorkingCopy {
                 message: "Failed to load the working copy state".to_string(),
                 err: err.into(),
             })?
-            .set_sparse_patterns(new_sparse_patterns)?;
+            .set_sparse_patterns(new_sparse_patterns, options)?;
         self.tree_state_dirty = true;
         Ok(stats)
     }
@@ -1903,6 +2155,20 @@ impl LockedLocalWorkingCopy {
         self.tree_state_dirty = true;
         Ok(())
     }
+
+    /// Discards all file states and rebuilds them from the tree of `commit`
+    /// without touching the files on disk. Returns the number of file states.
+    ///
+    /// The rebuilt file states have no recorded mtime, so files which differ
+    /// from `commit` will be detected as modified by the next snapshot.
+    pub fn rebuild(&mut self, commit: &Commit) -> Result<usize, ResetError> {
+        self.recover(commit)?;
+        let file_states = self.wc.file_states().map_err(|err| ResetError::Other {
+            message: "Failed to read the working copy state".to_string(),
+            err: err.into(),
+        })?;
+        Ok(file_states.paths().len())
+    }
 }
 
 #[cfg(test)]
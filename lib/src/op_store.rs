@@ -24,8 +24,8 @@ use itertools::Itertools as _;
 use once_cell::sync::Lazy;
 use thiserror::Error;
 
-use crate::backend::{CommitId, MillisSinceEpoch, Timestamp};
-use crate::content_hash::ContentHash;
+use crate::backend::{ChangeId, CommitId, MillisSinceEpoch, Timestamp};
+use crate::content_hash::{ContentHash, DigestUpdate};
 use crate::merge::Merge;
 use crate::object_id::{id_type, HexPrefix, ObjectId, PrefixResolution};
 
@@ -255,7 +255,7 @@ pub struct BranchTarget<'a> {
 
 /// Represents the way the repo looks at a given time, just like how a Tree
 /// object represents how the file system looks at a given time.
-#[derive(ContentHash, PartialEq, Eq, Clone, Debug, Default)]
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
 pub struct View {
     /// All head commits
     pub head_ids: HashSet<CommitId>,
@@ -271,6 +271,43 @@ pub struct View {
     // (.jj/working_copy/) has the source of truth about which commit *is* checked out (to be
     // precise: the commit to which we most recently completed an update to).
     pub wc_commit_ids: HashMap<WorkspaceId, CommitId>,
+    /// Free-form notes attached to changes. Since they are keyed by change id,
+    /// they follow the change when its commit is rewritten.
+    pub notes: BTreeMap<ChangeId, String>,
+    /// Free-form descriptions attached to local branches. These are local
+    /// metadata and are never sent to remotes.
+    pub branch_descriptions: BTreeMap<String, String>,
+}
+
+// Implemented manually so that views without notes or branch descriptions have
+// the same id as before they were added.
+impl ContentHash for View {
+    fn hash(&self, state: &mut impl DigestUpdate) {
+        let View {
+            head_ids,
+            local_branches,
+            tags,
+            remote_views,
+            git_refs,
+            git_head,
+            wc_commit_ids,
+            notes,
+            branch_descriptions,
+        } = self;
+        head_ids.hash(state);
+        local_branches.hash(state);
+        tags.hash(state);
+        remote_views.hash(state);
+        git_refs.hash(state);
+        git_head.hash(state);
+        wc_commit_ids.hash(state);
+        if !notes.is_empty() {
+            notes.hash(state);
+        }
+        if !branch_descriptions.is_empty() {
+            branch_descriptions.hash(state);
+        }
+    }
 }
 
 /// Represents the state of the remote repo.
@@ -26,7 +26,7 @@ use crate::object_id::{HexPrefix, PrefixResolution};
 use crate::op_heads_store::{OpHeadResolutionError, OpHeadsStore};
 use crate::op_store::{OpStore, OpStoreError, OpStoreResult, OperationId};
 use crate::operation::Operation;
-use crate::repo::{ReadonlyRepo, Repo as _, RepoLoader};
+use crate::repo::{ReadonlyRepo, Repo as _, RepoLoader, RepoLoaderError};
 use crate::{dag_walk, op_heads_store};
 
 /// Error that may occur during evaluation of operation set expression.
@@ -41,6 +41,9 @@ pub enum OpsetEvaluationError {
     /// Failed to access operation object.
     #[error(transparent)]
     OpStore(#[from] OpStoreError),
+    /// Failed to load the repo at an operation.
+    #[error(transparent)]
+    RepoLoader(#[from] RepoLoaderError),
 }
 
 /// Error that may occur during parsing and resolution of operation set
@@ -224,6 +227,96 @@ pub fn walk_ancestors(head_ops: &[Operation]) -> impl Iterator<Item = OpStoreRes
     .map_ok(|OperationByEndTime(op)| op)
 }
 
+/// Walks `head_ops`, `base_op`, and their ancestors in reverse topological
+/// order. Each operation is paired with whether it is `base_op` or one of its
+/// ancestors.
+///
+/// Operations that aren't ancestors of `base_op` were made after it (or in a
+/// divergent branch of the operation log.)
+pub fn walk_ancestors_relative_to(
+    head_ops: &[Operation],
+    base_op: &Operation,
+) -> impl Iterator<Item = OpStoreResult<(Operation, bool)>> {
+    let all_heads = head_ops.iter().chain([base_op]).cloned().collect_vec();
+    let mut reachable_ids = HashSet::from([base_op.id().clone()]);
+    walk_ancestors(&all_heads).map_ok(move |op| {
+        let is_reachable = reachable_ids.remove(op.id());
+        if is_reachable {
+            reachable_ids.extend(op.parent_ids().iter().cloned());
+        }
+        (op, is_reachable)
+    })
+}
+
+/// Finds the operations reachable from `head_ops` that are descendants of
+/// `base_op`, excluding `base_op` itself.
+///
+/// Descendants are visited before `base_op` in reverse topological order, so
+/// the walk stops there. It also stops at the first operation that ended
+/// before `base_op` did, as no descendant can be older than `base_op`.
+/// Divergent operations that aren't descendants of `base_op` are not included.
+pub fn find_descendant_ids(
+    head_ops: &[Operation],
+    base_op: &Operation,
+) -> OpStoreResult<HashSet<OperationId>> {
+    let base_end_time = &base_op.metadata().end_time.timestamp;
+    let mut ops = vec![];
+    for op in walk_ancestors(head_ops) {
+        let op = op?;
+        if op.id() == base_op.id() || op.metadata().end_time.timestamp < *base_end_time {
+            break;
+        }
+        ops.push(op);
+    }
+    // Parents are visited after their children, so iterate from the oldest.
+    let mut descendant_ids = HashSet::from([base_op.id().clone()]);
+    for op in ops.iter().rev() {
+        if op.parent_ids().iter().any(|id| descendant_ids.contains(id)) {
+            descendant_ids.insert(op.id().clone());
+        }
+    }
+    descendant_ids.remove(base_op.id());
+    Ok(descendant_ids)
+}
+
+/// Finds the closest operation that is an ancestor of (or same as) all of the
+/// `head_ops`.
+///
+/// If there are multiple such operations (as in a criss-cross history), the
+/// one visited first in reverse topological order is returned. Returns `None`
+/// if `head_ops` is empty.
+pub fn find_closest_common_ancestor(head_ops: &[Operation]) -> OpStoreResult<Option<Operation>> {
+    if head_ops.is_empty() {
+        return Ok(None);
+    }
+    let head_indices: HashMap<&OperationId, usize> = head_ops
+        .iter()
+        .enumerate()
+        .map(|(i, op)| (op.id(), i))
+        .collect();
+    // Indices of the heads each operation is reachable from. Children are
+    // visited before their parents, so the set is complete when an operation
+    // is emitted.
+    let mut reachable_from: HashMap<OperationId, HashSet<usize>> = HashMap::new();
+    for op in walk_ancestors(head_ops) {
+        let op = op?;
+        let mut heads = reachable_from.remove(op.id()).unwrap_or_default();
+        if let Some(&i) = head_indices.get(op.id()) {
+            heads.insert(i);
+        }
+        if heads.len() == head_ops.len() {
+            return Ok(Some(op));
+        }
+        for parent_id in op.parent_ids() {
+            reachable_from
+                .entry(parent_id.clone())
+                .or_default()
+                .extend(&heads);
+        }
+    }
+    Ok(None)
+}
+
 /// Stats about `reparent_range()`.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ReparentStats {
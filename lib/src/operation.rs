@@ -110,6 +110,14 @@ impl Operation {
         &self.data.metadata
     }
 
+    /// Returns true if the operation only snapshotted the working copy.
+    ///
+    /// Such operations are usually not interesting on their own, so user
+    /// interfaces may want to hide them by default.
+    pub fn is_trivial(&self) -> bool {
+        self.metadata().is_snapshot
+    }
+
     pub fn store_operation(&self) -> &op_store::Operation {
         &self.data
     }
@@ -0,0 +1,571 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Functional language for selecting operations from the operation log.
+
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::iter;
+use std::sync::Arc;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone as _};
+use itertools::Itertools as _;
+use once_cell::sync::Lazy;
+
+use crate::backend::{CommitId, MillisSinceEpoch, Timestamp};
+use crate::dsl_util::collect_similar;
+use crate::object_id::{HexPrefix, PrefixResolution};
+use crate::op_store::RefTarget;
+use crate::op_walk::OpsetEvaluationError;
+use crate::operation::Operation;
+use crate::opset_parser::{
+    self, BinaryOp, ExpressionKind, ExpressionNode, FunctionCallNode, UnaryOp,
+};
+pub use crate::opset_parser::{OpsetParseError, OpsetParseErrorKind, OpsetParseResult};
+use crate::repo::{ReadonlyRepo, Repo as _, RepoLoader};
+use crate::str_util::StringPattern;
+use crate::view::View;
+
+/// Context needed to resolve an opset expression.
+#[derive(Clone, Debug)]
+pub struct OpsetParseContext {
+    /// Time that relative dates such as "2 days ago" are resolved against.
+    /// Dates without a time zone are in the time zone of this timestamp.
+    pub now: Timestamp,
+}
+
+/// Property of a single operation to be tested.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OperationPredicate {
+    /// Matches the operation description.
+    Description(StringPattern),
+    /// Matches the command line, or the description if it wasn't recorded.
+    Command(StringPattern),
+    /// Matches the name or value of any of the operation tags.
+    Tags(StringPattern),
+    /// Matches `username@hostname`.
+    User(StringPattern),
+    /// Operations started at or after the time.
+    After(MillisSinceEpoch),
+    /// Operations started before the time.
+    Before(MillisSinceEpoch),
+    /// Operations snapshotting the working copy.
+    Snapshot,
+    /// Operations that moved, created, or deleted a matching local branch.
+    Branches(StringPattern),
+    /// Operations that made the commit visible or hidden.
+    Commit(HexPrefix),
+}
+
+/// Set of operations to be selected from the operation log.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OpsetExpression {
+    /// Matches nothing.
+    None,
+    /// Matches everything.
+    All,
+    /// Matches operations with the property.
+    Filter(OperationPredicate),
+    /// Union of expressions.
+    Union(Box<OpsetExpression>, Box<OpsetExpression>),
+    /// Intersection of expressions.
+    Intersection(Box<OpsetExpression>, Box<OpsetExpression>),
+    /// Difference of expressions.
+    Difference(Box<OpsetExpression>, Box<OpsetExpression>),
+}
+
+impl OpsetExpression {
+    /// Expression that matches nothing.
+    pub fn none() -> Self {
+        OpsetExpression::None
+    }
+
+    /// Expression that matches everything.
+    pub fn all() -> Self {
+        OpsetExpression::All
+    }
+
+    /// Expression that matches operations with the property.
+    pub fn filter(predicate: OperationPredicate) -> Self {
+        OpsetExpression::Filter(predicate)
+    }
+
+    /// Expression that matches either `self` or `other` (or both).
+    pub fn union(self, other: Self) -> Self {
+        OpsetExpression::Union(Box::new(self), Box::new(other))
+    }
+
+    /// Expression that matches both `self` and `other`.
+    pub fn intersection(self, other: Self) -> Self {
+        OpsetExpression::Intersection(Box::new(self), Box::new(other))
+    }
+
+    /// Expression that matches `self` but not `other`.
+    pub fn difference(self, other: Self) -> Self {
+        OpsetExpression::Difference(Box::new(self), Box::new(other))
+    }
+
+    /// Returns true if the operation is in this set.
+    ///
+    /// Predicates that compare the operation with its parents load the parent
+    /// views. `commit()` also needs the index at the operation, which is
+    /// loaded only if the index used for the previously matched operations
+    /// doesn't contain the operation's commits. Matching the operations
+    /// from the newest to the oldest, as the log is walked, therefore loads
+    /// a single index.
+    pub fn matches(
+        &self,
+        context: &OpsetMatchContext,
+        op: &Operation,
+    ) -> Result<bool, OpsetEvaluationError> {
+        match self {
+            OpsetExpression::None => Ok(false),
+            OpsetExpression::All => Ok(true),
+            OpsetExpression::Filter(predicate) => predicate_matches(predicate, context, op),
+            OpsetExpression::Union(lhs, rhs) => {
+                Ok(lhs.matches(context, op)? || rhs.matches(context, op)?)
+            }
+            OpsetExpression::Intersection(lhs, rhs) => {
+                Ok(lhs.matches(context, op)? && rhs.matches(context, op)?)
+            }
+            OpsetExpression::Difference(lhs, rhs) => {
+                Ok(lhs.matches(context, op)? && !rhs.matches(context, op)?)
+            }
+        }
+    }
+}
+
+/// State shared between the `OpsetExpression::matches()` calls for the
+/// operations of a log.
+pub struct OpsetMatchContext<'a> {
+    repo_loader: &'a RepoLoader,
+    commit_index: RefCell<Option<CommitIndex>>,
+}
+
+impl<'a> OpsetMatchContext<'a> {
+    /// Creates context for matching operations of the repo.
+    pub fn new(repo_loader: &'a RepoLoader) -> Self {
+        OpsetMatchContext {
+            repo_loader,
+            commit_index: RefCell::new(None),
+        }
+    }
+}
+
+/// Index used to resolve and look up the commits of `commit()` predicates.
+struct CommitIndex {
+    repo: Arc<ReadonlyRepo>,
+    resolved_ids: Vec<(HexPrefix, Option<CommitId>)>,
+}
+
+impl CommitIndex {
+    fn resolve(&mut self, prefix: &HexPrefix) -> Option<CommitId> {
+        if let Some((_, id)) = self.resolved_ids.iter().find(|(p, _)| p == prefix) {
+            return id.clone();
+        }
+        let id = match self.repo.index().resolve_commit_id_prefix(prefix) {
+            PrefixResolution::SingleMatch(id) => Some(id),
+            PrefixResolution::NoMatch | PrefixResolution::AmbiguousMatch => None,
+        };
+        self.resolved_ids.push((prefix.clone(), id.clone()));
+        id
+    }
+}
+
+fn predicate_matches(
+    predicate: &OperationPredicate,
+    context: &OpsetMatchContext,
+    op: &Operation,
+) -> Result<bool, OpsetEvaluationError> {
+    let metadata = op.metadata();
+    match predicate {
+        OperationPredicate::Description(pattern) => Ok(pattern.matches(&metadata.description)),
+        OperationPredicate::Command(pattern) => {
+            let command = metadata.tags.get("args").unwrap_or(&metadata.description);
+            Ok(pattern.matches(command))
+        }
+        OperationPredicate::Tags(pattern) => Ok(metadata
+            .tags
+            .iter()
+            .any(|(name, value)| pattern.matches(name) || pattern.matches(value))),
+        OperationPredicate::User(pattern) => {
+            Ok(pattern.matches(&format!("{}@{}", metadata.username, metadata.hostname)))
+        }
+        OperationPredicate::After(time) => Ok(metadata.start_time.timestamp >= *time),
+        OperationPredicate::Before(time) => Ok(metadata.start_time.timestamp < *time),
+        OperationPredicate::Snapshot => Ok(metadata.is_snapshot),
+        OperationPredicate::Branches(pattern) => {
+            let view = op.view()?;
+            let parent_views = load_parent_views(op)?;
+            let names: BTreeSet<&str> = view
+                .local_branches()
+                .chain(parent_views.iter().flat_map(|view| view.local_branches()))
+                .map(|(name, _)| name)
+                .filter(|name| pattern.matches(name))
+                .collect();
+            let is_changed = names.into_iter().any(|name| {
+                let target = view.get_local_branch(name);
+                is_changed_from_parents(
+                    target,
+                    parent_views
+                        .iter()
+                        .map(|parent| parent.get_local_branch(name)),
+                    RefTarget::absent_ref(),
+                )
+            });
+            Ok(is_changed)
+        }
+        OperationPredicate::Commit(prefix) => {
+            let view = op.view()?;
+            let parent_views = load_parent_views(op)?;
+            let mut commit_index = context.commit_index.borrow_mut();
+            // Commits are never removed from the index, so the index at a
+            // later operation can be reused as long as it has all the heads.
+            let has_heads = |commit_index: &CommitIndex| {
+                let index = commit_index.repo.index();
+                iter::once(&view)
+                    .chain(&parent_views)
+                    .flat_map(|view| view.heads())
+                    .all(|id| index.has_id(id))
+            };
+            if !commit_index.as_ref().is_some_and(has_heads) {
+                // The index at the operation includes the commits of its
+                // parents.
+                *commit_index = Some(CommitIndex {
+                    repo: context.repo_loader.load_at(op)?,
+                    resolved_ids: vec![],
+                });
+            }
+            let commit_index = commit_index.as_mut().unwrap();
+            let Some(commit_id) = commit_index.resolve(prefix) else {
+                return Ok(false);
+            };
+            let index = commit_index.repo.index();
+            let is_visible = |heads: &HashSet<CommitId>| {
+                heads.iter().any(|head| index.is_ancestor(&commit_id, head))
+            };
+            Ok(is_changed_from_parents(
+                is_visible(view.heads()),
+                parent_views.iter().map(|parent| is_visible(parent.heads())),
+                false,
+            ))
+        }
+    }
+}
+
+fn load_parent_views(op: &Operation) -> Result<Vec<View>, OpsetEvaluationError> {
+    let views = op.parents().map(|parent| parent?.view()).try_collect()?;
+    Ok(views)
+}
+
+/// Returns true if the `value` differs from the values at all parents, or from
+/// the `initial` value if there are no parents. Changes made on one side of a
+/// merge operation are attributed to that side.
+fn is_changed_from_parents<T: PartialEq>(
+    value: T,
+    mut parent_values: impl ExactSizeIterator<Item = T>,
+    initial: T,
+) -> bool {
+    if parent_values.len() == 0 {
+        return value != initial;
+    }
+    parent_values.all(|parent_value| parent_value != value)
+}
+
+type OpsetFunction = fn(&OpsetParseContext, &FunctionCallNode) -> OpsetParseResult<OpsetExpression>;
+
+static BUILTIN_FUNCTION_MAP: Lazy<HashMap<&'static str, OpsetFunction>> = Lazy::new(|| {
+    // Not using maplit::hashmap!{} or custom declarative macro here because
+    // code completion inside macro is quite restricted.
+    let mut map: HashMap<&'static str, OpsetFunction> = HashMap::new();
+    map.insert("none", |_context, function| {
+        function.expect_no_arguments()?;
+        Ok(OpsetExpression::none())
+    });
+    map.insert("all", |_context, function| {
+        function.expect_no_arguments()?;
+        Ok(OpsetExpression::all())
+    });
+    map.insert("description", |_context, function| {
+        let [arg] = function.expect_exact_arguments()?;
+        let pattern = expect_string_pattern(arg)?;
+        Ok(OpsetExpression::filter(OperationPredicate::Description(
+            pattern,
+        )))
+    });
+    map.insert("command", |_context, function| {
+        let [arg] = function.expect_exact_arguments()?;
+        let pattern = expect_string_pattern(arg)?;
+        Ok(OpsetExpression::filter(OperationPredicate::Command(
+            pattern,
+        )))
+    });
+    map.insert("tags", |_context, function| {
+        let [arg] = function.expect_exact_arguments()?;
+        let pattern = expect_string_pattern(arg)?;
+        Ok(OpsetExpression::filter(OperationPredicate::Tags(pattern)))
+    });
+    map.insert("user", |_context, function| {
+        let [arg] = function.expect_exact_arguments()?;
+        let pattern = expect_string_pattern(arg)?;
+        Ok(OpsetExpression::filter(OperationPredicate::User(pattern)))
+    });
+    map.insert("after", |context, function| {
+        let [arg] = function.expect_exact_arguments()?;
+        let time = expect_date(context, arg)?;
+        Ok(OpsetExpression::filter(OperationPredicate::After(time)))
+    });
+    map.insert("before", |context, function| {
+        let [arg] = function.expect_exact_arguments()?;
+        let time = expect_date(context, arg)?;
+        Ok(OpsetExpression::filter(OperationPredicate::Before(time)))
+    });
+    map.insert("snapshot", |_context, function| {
+        function.expect_no_arguments()?;
+        Ok(OpsetExpression::filter(OperationPredicate::Snapshot))
+    });
+    map.insert("branches", |_context, function| {
+        let [arg] = function.expect_exact_arguments()?;
+        let pattern = expect_string_pattern(arg)?;
+        Ok(OpsetExpression::filter(OperationPredicate::Branches(
+            pattern,
+        )))
+    });
+    map.insert("commit", |_context, function| {
+        let [arg] = function.expect_exact_arguments()?;
+        let text = expect_string_literal(arg)?;
+        let prefix = HexPrefix::new(&text)
+            .ok_or_else(|| OpsetParseError::expression("Invalid commit ID prefix", arg.span))?;
+        Ok(OpsetExpression::filter(OperationPredicate::Commit(prefix)))
+    });
+    map
+});
+
+fn expect_string_literal(node: &ExpressionNode) -> OpsetParseResult<String> {
+    match &node.kind {
+        ExpressionKind::Identifier(name) => Ok((*name).to_owned()),
+        ExpressionKind::String(name) => Ok(name.clone()),
+        _ => Err(OpsetParseError::expression(
+            "Expected string literal",
+            node.span,
+        )),
+    }
+}
+
+fn expect_string_pattern(node: &ExpressionNode) -> OpsetParseResult<StringPattern> {
+    match &node.kind {
+        ExpressionKind::StringPattern { kind, value } => StringPattern::from_str_kind(value, kind)
+            .map_err(|err| {
+                OpsetParseError::expression("Invalid string pattern", node.span).with_source(err)
+            }),
+        _ => Ok(StringPattern::Substring(expect_string_literal(node)?)),
+    }
+}
+
+fn expect_date(
+    context: &OpsetParseContext,
+    node: &ExpressionNode,
+) -> OpsetParseResult<MillisSinceEpoch> {
+    let text = expect_string_literal(node)?;
+    parse_date(&text, &context.now)
+        .ok_or_else(|| OpsetParseError::expression(format!(r#"Invalid date "{text}""#), node.span))
+}
+
+/// Parses a date such as "2024-01-02", "2024-01-02 15:04", an RFC 3339
+/// timestamp, or a relative date such as "2 days ago".
+fn parse_date(text: &str, now: &Timestamp) -> Option<MillisSinceEpoch> {
+    let text = text.trim();
+    if text == "now" {
+        return Some(now.timestamp);
+    }
+    if let Some(duration) = text.strip_suffix(" ago") {
+        let (count, unit) = duration.split_whitespace().collect_tuple()?;
+        let count: i64 = count.parse().ok()?;
+        let unit_millis = match unit.strip_suffix('s').unwrap_or(unit) {
+            "second" => 1000,
+            "minute" => 60 * 1000,
+            "hour" => 60 * 60 * 1000,
+            "day" => 24 * 60 * 60 * 1000,
+            "week" => 7 * 24 * 60 * 60 * 1000,
+            _ => return None,
+        };
+        let millis = now
+            .timestamp
+            .0
+            .checked_sub(count.checked_mul(unit_millis)?)?;
+        return Some(MillisSinceEpoch(millis));
+    }
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(text) {
+        return Some(MillisSinceEpoch(datetime.timestamp_millis()));
+    }
+    let naive = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M"))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                .ok()?
+                .and_hms_opt(0, 0, 0)
+        })?;
+    let offset = FixedOffset::east_opt(now.tz_offset * 60)?;
+    let datetime = offset.from_local_datetime(&naive).single()?;
+    Some(MillisSinceEpoch(datetime.timestamp_millis()))
+}
+
+fn resolve_function(
+    context: &OpsetParseContext,
+    function: &FunctionCallNode,
+) -> OpsetParseResult<OpsetExpression> {
+    if let Some(func) = BUILTIN_FUNCTION_MAP.get(function.name) {
+        func(context, function)
+    } else {
+        Err(OpsetParseError::new(
+            OpsetParseErrorKind::NoSuchFunction {
+                name: function.name.to_owned(),
+                candidates: collect_similar(function.name, BUILTIN_FUNCTION_MAP.keys()),
+            },
+            function.name_span,
+        ))
+    }
+}
+
+fn resolve_expression(
+    context: &OpsetParseContext,
+    node: &ExpressionNode,
+) -> OpsetParseResult<OpsetExpression> {
+    match &node.kind {
+        ExpressionKind::Identifier(_)
+        | ExpressionKind::String(_)
+        | ExpressionKind::StringPattern { .. } => Err(OpsetParseError::expression(
+            "Expected function call, such as `description(pattern)`",
+            node.span,
+        )),
+        ExpressionKind::Unary(op, arg_node) => {
+            let arg = resolve_expression(context, arg_node)?;
+            match op {
+                UnaryOp::Negate => Ok(OpsetExpression::all().difference(arg)),
+            }
+        }
+        ExpressionKind::Binary(op, lhs_node, rhs_node) => {
+            let lhs = resolve_expression(context, lhs_node)?;
+            let rhs = resolve_expression(context, rhs_node)?;
+            match op {
+                BinaryOp::Union => Ok(lhs.union(rhs)),
+                BinaryOp::Intersection => Ok(lhs.intersection(rhs)),
+                BinaryOp::Difference => Ok(lhs.difference(rhs)),
+            }
+        }
+        ExpressionKind::FunctionCall(function) => resolve_function(context, function),
+    }
+}
+
+/// Parses text into `OpsetExpression`.
+pub fn parse(text: &str, context: &OpsetParseContext) -> OpsetParseResult<OpsetExpression> {
+    let node = opset_parser::parse_program(text)?;
+    resolve_expression(context, &node)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    fn context() -> OpsetParseContext {
+        // 2001-02-03T04:05:06+07:00
+        OpsetParseContext {
+            now: Timestamp {
+                timestamp: MillisSinceEpoch(981_147_906_000),
+                tz_offset: 7 * 60,
+            },
+        }
+    }
+
+    fn parse_into_expression(text: &str) -> Result<OpsetExpression, OpsetParseErrorKind> {
+        parse(text, &context()).map_err(|err| err.kind().clone())
+    }
+
+    #[test]
+    fn test_parse_function() {
+        assert_eq!(
+            parse_into_expression(r#"tags("push")"#),
+            Ok(OpsetExpression::filter(OperationPredicate::Tags(
+                StringPattern::Substring("push".to_owned())
+            )))
+        );
+        assert_eq!(
+            parse_into_expression(r#"branches(exact:main)"#),
+            Ok(OpsetExpression::filter(OperationPredicate::Branches(
+                StringPattern::exact("main")
+            )))
+        );
+        assert_matches!(
+            parse_into_expression("commit(xyz)"),
+            Err(OpsetParseErrorKind::Expression(_))
+        );
+        assert_matches!(
+            parse_into_expression("description()"),
+            Err(OpsetParseErrorKind::InvalidArguments { .. })
+        );
+        assert_matches!(
+            parse_into_expression("descriptoin(foo)"),
+            Err(OpsetParseErrorKind::NoSuchFunction { .. })
+        );
+        assert_matches!(
+            parse_into_expression("foo"),
+            Err(OpsetParseErrorKind::Expression(_))
+        );
+    }
+
+    #[test]
+    fn test_parse_compound_expression() {
+        let snapshot = || OpsetExpression::filter(OperationPredicate::Snapshot);
+        assert_eq!(
+            parse_into_expression("~snapshot()"),
+            Ok(OpsetExpression::all().difference(snapshot()))
+        );
+        assert_eq!(
+            parse_into_expression("all() ~ snapshot() | none()"),
+            Ok(OpsetExpression::all()
+                .difference(snapshot())
+                .union(OpsetExpression::none()))
+        );
+    }
+
+    #[test]
+    fn test_parse_date() {
+        let now = context().now;
+        let day_millis = 24 * 60 * 60 * 1000;
+        assert_eq!(parse_date("now", &now), Some(now.timestamp));
+        assert_eq!(
+            parse_date("2 days ago", &now),
+            Some(MillisSinceEpoch(now.timestamp.0 - 2 * day_millis))
+        );
+        assert_eq!(
+            parse_date("1 hour ago", &now),
+            Some(MillisSinceEpoch(now.timestamp.0 - 60 * 60 * 1000))
+        );
+        // Dates without a time zone are in the time zone of "now"
+        assert_eq!(
+            parse_date("2001-02-03", &now),
+            parse_date("2001-02-03T00:00:00+07:00", &now)
+        );
+        assert_eq!(parse_date("2001-02-03 04:05:06", &now), Some(now.timestamp));
+        assert_eq!(
+            parse_date("2001-02-03 04:05", &now),
+            Some(MillisSinceEpoch(now.timestamp.0 - 6000))
+        );
+        assert_eq!(parse_date("2 fortnights ago", &now), None);
+        assert_eq!(parse_date("yesterday-ish", &now), None);
+    }
+}
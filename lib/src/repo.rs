@@ -49,7 +49,9 @@ use crate::refs::{
     diff_named_ref_targets, diff_named_remote_refs, merge_ref_targets, merge_remote_refs,
 };
 use crate::revset::{RevsetEvaluationError, RevsetExpression, RevsetIteratorExt};
-use crate::rewrite::{merge_commit_trees, CommitRewriter, DescendantRebaser, RebaseOptions};
+use crate::rewrite::{
+    merge_commit_trees, CommitRewriter, DescendantRebaser, RebaseOptions, RewriteProgress,
+};
 use crate::settings::{RepoSettings, UserSettings};
 use crate::signing::{SignInitError, Signer};
 use crate::simple_op_heads_store::SimpleOpHeadsStore;
@@ -911,6 +913,20 @@ impl MutableRepo {
         !self.parent_mapping.is_empty()
     }
 
+    /// Forgets the recorded rewrites and abandoned commits without rebasing
+    /// their descendants.
+    ///
+    /// The old commits are removed from the view heads, so they will remain
+    /// visible only if they still have visible descendants. Returns the ids of
+    /// the old commits.
+    pub fn drop_rewrites(&mut self) -> Vec<CommitId> {
+        let old_ids = self.parent_mapping.drain().map(|(id, _)| id).collect_vec();
+        for old_id in &old_ids {
+            self.view_mut().remove_head(old_id);
+        }
+        old_ids
+    }
+
     /// Calculates new parents for a commit that's currently based on the given
     /// parents. It does that by considering how previous commits have been
     /// rewritten and abandoned.
@@ -1171,13 +1187,29 @@ impl MutableRepo {
         &mut self,
         settings: &UserSettings,
         roots: Vec<CommitId>,
+        callback: impl FnMut(CommitRewriter) -> BackendResult<()>,
+    ) -> BackendResult<()> {
+        self.transform_descendants_with_progress(settings, roots, &mut |_| {}, callback)
+    }
+
+    /// Like [`Self::transform_descendants()`], but calls `progress` after each
+    /// visited commit.
+    pub fn transform_descendants_with_progress(
+        &mut self,
+        settings: &UserSettings,
+        roots: Vec<CommitId>,
+        progress: &mut dyn FnMut(RewriteProgress),
         mut callback: impl FnMut(CommitRewriter) -> BackendResult<()>,
     ) -> BackendResult<()> {
         let mut to_visit = self.find_descendants_to_rebase(roots)?;
+        let total = to_visit.len();
+        let mut visited = 0;
         while let Some(old_commit) = to_visit.pop() {
             let new_parent_ids = self.new_parents(old_commit.parent_ids().to_vec());
             let rewriter = CommitRewriter::new(self, old_commit, new_parent_ids);
             callback(rewriter)?;
+            visited += 1;
+            progress(RewriteProgress { visited, total });
         }
         self.update_rewritten_references(settings)?;
         // Since we didn't necessarily visit all descendants of rewritten commits (e.g.
@@ -1198,6 +1230,7 @@ impl MutableRepo {
         &'repo mut self,
         settings: &'settings UserSettings,
         options: RebaseOptions,
+        progress: &mut dyn FnMut(RewriteProgress),
     ) -> BackendResult<Option<DescendantRebaser<'settings, 'repo>>> {
         if !self.has_rewrites() {
             // Optimization
@@ -1208,7 +1241,7 @@ impl MutableRepo {
             self.find_descendants_to_rebase(self.parent_mapping.keys().cloned().collect())?;
         let mut rebaser = DescendantRebaser::new(settings, self, to_visit);
         *rebaser.mut_options() = options;
-        rebaser.rebase_all()?;
+        rebaser.rebase_all(progress)?;
         Ok(Some(rebaser))
     }
 
@@ -1219,9 +1252,20 @@ impl MutableRepo {
         &mut self,
         settings: &UserSettings,
         options: RebaseOptions,
+    ) -> BackendResult<usize> {
+        self.rebase_descendants_with_options_and_progress(settings, options, &mut |_| {})
+    }
+
+    /// Like [`Self::rebase_descendants_with_options()`], but calls `progress`
+    /// after each visited commit.
+    pub fn rebase_descendants_with_options_and_progress(
+        &mut self,
+        settings: &UserSettings,
+        options: RebaseOptions,
+        progress: &mut dyn FnMut(RewriteProgress),
     ) -> BackendResult<usize> {
         let result = self
-            .rebase_descendants_return_rebaser(settings, options)?
+            .rebase_descendants_return_rebaser(settings, options, progress)?
             .map_or(0, |rebaser| rebaser.into_map().len());
         self.parent_mapping.clear();
         Ok(result)
@@ -1250,7 +1294,7 @@ impl MutableRepo {
             // We do not set RebaseOptions here, since this function does not currently return
             // enough information to describe the results of a rebase if some commits got
             // abandoned
-            .rebase_descendants_return_rebaser(settings, options)?
+            .rebase_descendants_return_rebaser(settings, options, &mut |_| {})?
             .map_or(HashMap::new(), |rebaser| rebaser.into_map()));
         self.parent_mapping.clear();
         result
@@ -1271,6 +1315,27 @@ impl MutableRepo {
         Ok(num_rebased)
     }
 
+    /// Rewrite descendants of abandoned or rewritten commits onto their new
+    /// parents without changing their trees. Returns the number of reparented
+    /// commits.
+    ///
+    /// Unlike `rebase_descendants()`, the contents of the descendants are kept
+    /// as they are, so no conflicts can arise.
+    pub fn reparent_descendants(&mut self, settings: &UserSettings) -> BackendResult<usize> {
+        let roots = self.parent_mapping.keys().cloned().collect_vec();
+        let mut num_reparented = 0;
+        self.transform_descendants(settings, roots, |rewriter| {
+            if rewriter.parents_changed() {
+                let builder = rewriter.reparent(settings)?;
+                builder.write()?;
+                num_reparented += 1;
+            }
+            Ok(())
+        })?;
+        self.parent_mapping.clear();
+        Ok(num_reparented)
+    }
+
     pub fn rebase_descendants_return_map(
         &mut self,
         settings: &UserSettings,
@@ -1538,6 +1603,14 @@ impl MutableRepo {
         self.view_mut().set_git_head_target(target);
     }
 
+    pub fn set_note(&mut self, change_id: ChangeId, text: String) {
+        self.view_mut().set_note(change_id, text);
+    }
+
+    pub fn set_branch_description(&mut self, name: &str, description: String) {
+        self.view_mut().set_branch_description(name, description);
+    }
+
     pub fn set_view(&mut self, data: op_store::View) {
         self.view_mut().set_view(data);
         self.view.mark_dirty();
@@ -1637,6 +1710,42 @@ impl MutableRepo {
             other.git_head(),
         );
         self.set_git_head_target(new_git_head_target);
+
+        // Merge notes. If both sides changed the same note, we keep the self side.
+        let changed_notes = itertools::chain(base.notes().keys(), other.notes().keys())
+            .filter(|change_id| base.get_note(change_id) != other.get_note(change_id))
+            .unique()
+            .cloned()
+            .collect_vec();
+        for change_id in changed_notes {
+            if self.view().get_note(&change_id) == base.get_note(&change_id) {
+                let text = other.get_note(&change_id).unwrap_or_default().to_owned();
+                self.set_note(change_id, text);
+            }
+        }
+
+        // Merge branch descriptions the same way. Descriptions of branches
+        // which no longer exist locally are dropped.
+        let changed_branch_descriptions = itertools::chain(
+            base.branch_descriptions().keys(),
+            other.branch_descriptions().keys(),
+        )
+        .filter(|name| base.get_branch_description(name) != other.get_branch_description(name))
+        .unique()
+        .cloned()
+        .collect_vec();
+        for name in changed_branch_descriptions {
+            if self.view().get_local_branch(&name).is_absent() {
+                continue;
+            }
+            if self.view().get_branch_description(&name) == base.get_branch_description(&name) {
+                let description = other
+                    .get_branch_description(&name)
+                    .unwrap_or_default()
+                    .to_owned();
+                self.set_branch_description(&name, description);
+            }
+        }
     }
 
     /// Finds and records commits that were rewritten or abandoned between
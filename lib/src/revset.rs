@@ -135,6 +135,8 @@ pub enum RevsetFilterPredicate {
     File(FilesetExpression),
     /// Commits with conflicts
     HasConflict,
+    /// Commits with a cryptographic signature. The signature isn't verified.
+    Signed,
     /// Custom predicates provided by extensions
     Extension(Rc<dyn RevsetFilterExtension>),
 }
@@ -172,6 +174,10 @@ pub enum RevsetExpression {
     },
     Heads(Rc<RevsetExpression>),
     Roots(Rc<RevsetExpression>),
+    // Heads of the commits which are ancestors of both sets
+    ForkPoint(Rc<RevsetExpression>, Rc<RevsetExpression>),
+    // Commit which splits the candidates roughly in half by ancestry
+    Midpoint(Rc<RevsetExpression>),
     Latest {
         candidates: Rc<RevsetExpression>,
         count: usize,
@@ -286,6 +292,17 @@ impl RevsetExpression {
         Rc::new(RevsetExpression::Roots(self.clone()))
     }
 
+    /// Heads of the commits which are ancestors of both `self` and `other`.
+    pub fn fork_point(self: &Rc<RevsetExpression>, other: &Rc<RevsetExpression>) -> Rc<Self> {
+        Rc::new(RevsetExpression::ForkPoint(self.clone(), other.clone()))
+    }
+
+    /// Commit in `self` which has roughly half of the commits in `self` as
+    /// ancestors.
+    pub fn midpoint(self: &Rc<RevsetExpression>) -> Rc<RevsetExpression> {
+        Rc::new(RevsetExpression::Midpoint(self.clone()))
+    }
+
     /// Parents of `self`.
     pub fn parents(self: &Rc<RevsetExpression>) -> Rc<RevsetExpression> {
         self.ancestors_at(1)
@@ -512,6 +529,10 @@ pub enum ResolvedExpression {
     },
     Heads(Box<ResolvedExpression>),
     Roots(Box<ResolvedExpression>),
+    /// Heads of the common ancestors of both sets.
+    ForkPoint(Box<ResolvedExpression>, Box<ResolvedExpression>),
+    /// Commit which splits the candidates roughly in half by ancestry.
+    Midpoint(Box<ResolvedExpression>),
     Latest {
         candidates: Box<ResolvedExpression>,
         count: usize,
@@ -655,6 +676,17 @@ static BUILTIN_FUNCTION_MAP: Lazy<HashMap<&'static str, RevsetFunction>> = Lazy:
         function.expect_no_arguments()?;
         Ok(RevsetExpression::git_head())
     });
+    map.insert("fork_point", |function, context| {
+        let [arg1, arg2] = function.expect_exact_arguments()?;
+        let expression1 = lower_expression(arg1, context)?;
+        let expression2 = lower_expression(arg2, context)?;
+        Ok(expression1.fork_point(&expression2))
+    });
+    map.insert("midpoint", |function, context| {
+        let [arg] = function.expect_exact_arguments()?;
+        let candidates = lower_expression(arg, context)?;
+        Ok(candidates.midpoint())
+    });
     map.insert("latest", |function, context| {
         let ([candidates_arg], [count_opt_arg]) = function.expect_arguments()?;
         let candidates = lower_expression(candidates_arg, context)?;
@@ -722,6 +754,18 @@ static BUILTIN_FUNCTION_MAP: Lazy<HashMap<&'static str, RevsetFunction>> = Lazy:
         function.expect_no_arguments()?;
         Ok(RevsetExpression::filter(RevsetFilterPredicate::HasConflict))
     });
+    map.insert("conflicts", |function, _context| {
+        function.expect_no_arguments()?;
+        Ok(RevsetExpression::filter(RevsetFilterPredicate::HasConflict))
+    });
+    map.insert("signed", |function, _context| {
+        function.expect_no_arguments()?;
+        Ok(RevsetExpression::filter(RevsetFilterPredicate::Signed))
+    });
+    map.insert("unsigned", |function, _context| {
+        function.expect_no_arguments()?;
+        Ok(RevsetExpression::filter(RevsetFilterPredicate::Signed).negated())
+    });
     map.insert("present", |function, context| {
         let [arg] = function.expect_exact_arguments()?;
         let expression = lower_expression(arg, context)?;
@@ -946,6 +990,16 @@ fn try_transform_expression<E>(
             RevsetExpression::Roots(candidates) => {
                 transform_rec(candidates, pre, post)?.map(RevsetExpression::Roots)
             }
+            RevsetExpression::ForkPoint(expression1, expression2) => {
+                transform_rec_pair((expression1, expression2), pre, post)?.map(
+                    |(expression1, expression2)| {
+                        RevsetExpression::ForkPoint(expression1, expression2)
+                    },
+                )
+            }
+            RevsetExpression::Midpoint(candidates) => {
+                transform_rec(candidates, pre, post)?.map(RevsetExpression::Midpoint)
+            }
             RevsetExpression::Latest { candidates, count } => transform_rec(candidates, pre, post)?
                 .map(|candidates| RevsetExpression::Latest {
                     candidates,
@@ -1746,6 +1800,13 @@ impl VisibilityResolutionContext<'_> {
             RevsetExpression::Roots(candidates) => {
                 ResolvedExpression::Roots(self.resolve(candidates).into())
             }
+            RevsetExpression::ForkPoint(expression1, expression2) => ResolvedExpression::ForkPoint(
+                self.resolve(expression1).into(),
+                self.resolve(expression2).into(),
+            ),
+            RevsetExpression::Midpoint(candidates) => {
+                ResolvedExpression::Midpoint(self.resolve(candidates).into())
+            }
             RevsetExpression::Latest { candidates, count } => ResolvedExpression::Latest {
                 candidates: self.resolve(candidates).into(),
                 count: *count,
@@ -1827,6 +1888,8 @@ impl VisibilityResolutionContext<'_> {
             | RevsetExpression::Reachable { .. }
             | RevsetExpression::Heads(_)
             | RevsetExpression::Roots(_)
+            | RevsetExpression::ForkPoint(..)
+            | RevsetExpression::Midpoint(_)
             | RevsetExpression::Latest { .. } => {
                 ResolvedPredicateExpression::Set(self.resolve(expression).into())
             }
@@ -1630,14 +1630,14 @@ mod tests {
             with_aliases([("F()", "x")]).parse("F(a)").unwrap_err().kind,
             RevsetParseErrorKind::InvalidFunctionArguments {
                 name: "F".to_owned(),
-                message: "Expected 0 arguments".to_owned()
+                message: r#"Expected 0 arguments (declared as alias "F()")"#.to_owned()
             }
         );
         assert_eq!(
             with_aliases([("F(x)", "x")]).parse("F()").unwrap_err().kind,
             RevsetParseErrorKind::InvalidFunctionArguments {
                 name: "F".to_owned(),
-                message: "Expected 1 arguments".to_owned()
+                message: r#"Expected 1 arguments (declared as alias "F(x)")"#.to_owned()
             }
         );
         assert_eq!(
@@ -1647,7 +1647,7 @@ mod tests {
                 .kind,
             RevsetParseErrorKind::InvalidFunctionArguments {
                 name: "F".to_owned(),
-                message: "Expected 2 arguments".to_owned()
+                message: r#"Expected 2 arguments (declared as alias "F(x, y)")"#.to_owned()
             }
         );
         assert_eq!(
@@ -1657,7 +1657,8 @@ mod tests {
                 .kind,
             RevsetParseErrorKind::InvalidFunctionArguments {
                 name: "F".to_owned(),
-                message: "Expected 1 to 2 arguments".to_owned()
+                message: r#"Expected 1 to 2 arguments (declared as aliases "F(x)", "F(x, y)")"#
+                    .to_owned()
             }
         );
         assert_eq!(
@@ -1667,7 +1668,8 @@ mod tests {
                 .kind,
             RevsetParseErrorKind::InvalidFunctionArguments {
                 name: "F".to_owned(),
-                message: "Expected 0, 2 arguments".to_owned()
+                message: r#"Expected 0, 2 arguments (declared as aliases "F()", "F(x, y)")"#
+                    .to_owned()
             }
         );
 
@@ -237,6 +237,11 @@ impl<'repo> CommitRewriter<'repo> {
             let should_abandon = match empty {
                 EmptyBehaviour::Keep => false,
                 EmptyBehaviour::AbandonNewlyEmpty => *parent.tree_id() == new_tree_id && !was_empty,
+                EmptyBehaviour::AbandonNewlyEmptyUndescribed => {
+                    *parent.tree_id() == new_tree_id
+                        && !was_empty
+                        && self.old_commit.description().is_empty()
+                }
                 EmptyBehaviour::AbandonAllEmpty => *parent.tree_id() == new_tree_id,
             };
             if should_abandon {
@@ -350,6 +355,9 @@ pub enum EmptyBehaviour {
     /// originally empty.
     /// Will never skip merge commits with multiple non-empty parents.
     AbandonNewlyEmpty,
+    /// Like `AbandonNewlyEmpty`, but keeps commits that have a description.
+    /// Will never skip merge commits with multiple non-empty parents.
+    AbandonNewlyEmptyUndescribed,
     /// Skips all empty commits, including ones that were empty before the
     /// rebase.
     /// Will never skip merge commits with multiple non-empty parents.
@@ -370,6 +378,15 @@ pub struct RebaseOptions {
     pub simplify_ancestor_merge: bool,
 }
 
+/// Progress of a rewrite of multiple commits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RewriteProgress {
+    /// Number of commits visited so far.
+    pub visited: usize,
+    /// Total number of commits to visit.
+    pub total: usize,
+}
+
 pub(crate) struct DescendantRebaser<'settings, 'repo> {
     settings: &'settings UserSettings,
     mut_repo: &'repo mut MutableRepo,
@@ -434,9 +451,14 @@ impl<'settings, 'repo> DescendantRebaser<'settings, 'repo> {
         Ok(())
     }
 
-    pub fn rebase_all(&mut self) -> BackendResult<()> {
+    /// Rebases all commits, calling `progress` after each visited commit.
+    pub fn rebase_all(&mut self, progress: &mut dyn FnMut(RewriteProgress)) -> BackendResult<()> {
+        let total = self.to_visit.len();
+        let mut visited = 0;
         while let Some(old_commit) = self.to_visit.pop() {
             self.rebase_one(old_commit)?;
+            visited += 1;
+            progress(RewriteProgress { visited, total });
         }
         self.mut_repo.update_rewritten_references(self.settings)
     }
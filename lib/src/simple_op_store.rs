@@ -27,7 +27,7 @@ use prost::Message;
 use tempfile::NamedTempFile;
 use thiserror::Error;
 
-use crate::backend::{CommitId, MillisSinceEpoch, Timestamp};
+use crate::backend::{ChangeId, CommitId, MillisSinceEpoch, Timestamp};
 use crate::content_hash::blake2b_hash;
 use crate::file_util::{persist_content_addressed_temp_file, IoResultExt as _, PathError};
 use crate::merge::Merge;
@@ -434,6 +434,22 @@ fn view_to_proto(view: &View) -> crate::protos::op_store::View {
 
     proto.git_head = ref_target_to_proto(&view.git_head);
 
+    for (change_id, text) in &view.notes {
+        proto.notes.push(crate::protos::op_store::Note {
+            change_id: change_id.to_bytes(),
+            text: text.clone(),
+        });
+    }
+
+    for (name, description) in &view.branch_descriptions {
+        proto
+            .branch_descriptions
+            .push(crate::protos::op_store::BranchDescription {
+                name: name.clone(),
+                description: description.clone(),
+            });
+    }
+
     proto
 }
 
@@ -480,6 +496,15 @@ fn view_from_proto(proto: crate::protos::op_store::View) -> View {
         view.git_head = RefTarget::normal(CommitId::new(proto.git_head_legacy));
     }
 
+    for note in proto.notes {
+        view.notes.insert(ChangeId::new(note.change_id), note.text);
+    }
+
+    for branch_description in proto.branch_descriptions {
+        view.branch_descriptions
+            .insert(branch_description.name, branch_description.description);
+    }
+
     if !proto.has_git_refs_migrated_to_remote {
         migrate_git_refs_to_remote(&mut view);
     }
@@ -744,6 +769,7 @@ mod tests {
                 WorkspaceId::default() => default_wc_commit_id,
                 WorkspaceId::new("test".to_string()) => test_wc_commit_id,
             },
+            ..Default::default()
         }
     }
 
@@ -797,10 +823,30 @@ mod tests {
     fn test_read_write_view() {
         let temp_dir = testutils::new_temp_dir();
         let store = SimpleOpStore::init(temp_dir.path());
-        let view = create_view();
-        let view_id = store.write_view(&view).unwrap();
-        let read_view = store.read_view(&view_id).unwrap();
-        assert_eq!(read_view, view);
+        let views = [
+            create_view(),
+            View {
+                notes: btreemap! {
+                    ChangeId::from_hex("eee111") => "some note".to_string(),
+                },
+                ..create_view()
+            },
+            View {
+                branch_descriptions: btreemap! {
+                    "main".to_string() => "main line of development".to_string(),
+                },
+                ..create_view()
+            },
+        ];
+        let mut view_ids = vec![];
+        for view in views {
+            let view_id = store.write_view(&view).unwrap();
+            let read_view = store.read_view(&view_id).unwrap();
+            assert_eq!(read_view, view);
+            view_ids.push(view_id);
+        }
+        // The optional fields are part of the view ID
+        assert!(view_ids.iter().all_unique());
     }
 
     #[test]
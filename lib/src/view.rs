@@ -18,7 +18,7 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 
 use itertools::Itertools;
 
-use crate::backend::CommitId;
+use crate::backend::{ChangeId, CommitId};
 use crate::op_store::{BranchTarget, RefTarget, RefTargetOptionExt as _, RemoteRef, WorkspaceId};
 use crate::refs::LocalAndRemoteRef;
 use crate::str_util::StringPattern;
@@ -136,6 +136,7 @@ impl View {
             self.data.local_branches.insert(name.to_owned(), target);
         } else {
             self.data.local_branches.remove(name);
+            self.data.branch_descriptions.remove(name);
         }
     }
 
@@ -304,6 +305,46 @@ impl View {
         self.data.git_head = target;
     }
 
+    /// Returns the notes attached to changes.
+    pub fn notes(&self) -> &BTreeMap<ChangeId, String> {
+        &self.data.notes
+    }
+
+    pub fn get_note(&self, change_id: &ChangeId) -> Option<&str> {
+        self.data.notes.get(change_id).map(String::as_str)
+    }
+
+    /// Attaches the `text` to the change. If the text is empty, the note will
+    /// be removed.
+    pub fn set_note(&mut self, change_id: ChangeId, text: String) {
+        if text.is_empty() {
+            self.data.notes.remove(&change_id);
+        } else {
+            self.data.notes.insert(change_id, text);
+        }
+    }
+
+    /// Returns the descriptions attached to local branches.
+    pub fn branch_descriptions(&self) -> &BTreeMap<String, String> {
+        &self.data.branch_descriptions
+    }
+
+    pub fn get_branch_description(&self, name: &str) -> Option<&str> {
+        self.data.branch_descriptions.get(name).map(String::as_str)
+    }
+
+    /// Attaches the `description` to the local branch. If the description is
+    /// empty, the existing description will be removed.
+    pub fn set_branch_description(&mut self, name: &str, description: String) {
+        if description.is_empty() {
+            self.data.branch_descriptions.remove(name);
+        } else {
+            self.data
+                .branch_descriptions
+                .insert(name.to_owned(), description);
+        }
+    }
+
     /// Iterates all commit ids referenced by this view.
     ///
     /// This can include hidden commits referenced by remote branches, previous
@@ -329,6 +370,8 @@ impl View {
             git_refs,
             git_head,
             wc_commit_ids,
+            notes: _,
+            branch_descriptions: _,
         } = &self.data;
         itertools::chain!(
             head_ids,
@@ -26,6 +26,7 @@ use crate::backend::{BackendError, MergedTreeId};
 use crate::commit::Commit;
 use crate::fsmonitor::FsmonitorSettings;
 use crate::gitignore::{GitIgnoreError, GitIgnoreFile};
+use crate::matchers::{EverythingMatcher, Matcher};
 use crate::op_store::{OperationId, WorkspaceId};
 use crate::repo_path::{RepoPath, RepoPathBuf};
 use crate::settings::HumanByteSize;
@@ -102,7 +103,11 @@ pub trait LockedWorkingCopy {
     fn snapshot(&mut self, options: SnapshotOptions) -> Result<MergedTreeId, SnapshotError>;
 
     /// Check out the specified commit in the working copy.
-    fn check_out(&mut self, commit: &Commit) -> Result<CheckoutStats, CheckoutError>;
+    fn check_out(
+        &mut self,
+        commit: &Commit,
+        options: &CheckoutOptions,
+    ) -> Result<CheckoutStats, CheckoutError>;
 
     /// Update to another commit without touching the files in the working copy.
     fn reset(&mut self, commit: &Commit) -> Result<(), ResetError>;
@@ -123,6 +128,7 @@ pub trait LockedWorkingCopy {
     fn set_sparse_patterns(
         &mut self,
         new_sparse_patterns: Vec<RepoPathBuf>,
+        options: &CheckoutOptions,
     ) -> Result<CheckoutStats, CheckoutError>;
 
     /// Finish the modifications to the working copy by writing the updated
@@ -198,6 +204,9 @@ pub struct SnapshotOptions<'a> {
     /// (depending on implementation)
     /// return `SnapshotError::NewFileTooLarge`.
     pub max_new_file_size: u64,
+    /// Only the paths matching this are snapshotted. The previously recorded
+    /// state is kept for the other paths, so deleting them isn't recorded.
+    pub matcher: &'a dyn Matcher,
 }
 
 impl SnapshotOptions<'_> {
@@ -208,6 +217,7 @@ impl SnapshotOptions<'_> {
             fsmonitor_settings: FsmonitorSettings::None,
             progress: None,
             max_new_file_size: u64::MAX,
+            matcher: &EverythingMatcher,
         }
     }
 }
@@ -215,6 +225,23 @@ impl SnapshotOptions<'_> {
 /// A callback for getting progress updates.
 pub type SnapshotProgress<'a> = dyn Fn(&RepoPath) + 'a + Sync;
 
+/// Options used when checking out a tree in the working copy.
+#[derive(Clone, Debug)]
+pub struct CheckoutOptions {
+    /// Whether to save unsnapshotted changes to the files that are overwritten
+    /// or removed. See `CheckoutStats::backup_tree_id`.
+    pub backup_unsnapshotted: bool,
+}
+
+impl CheckoutOptions {
+    /// Create an instance for use in tests.
+    pub fn empty_for_test() -> Self {
+        CheckoutOptions {
+            backup_unsnapshotted: false,
+        }
+    }
+}
+
 /// Stats about a checkout operation on a working copy. All "files" mentioned
 /// below may also be symlinks or materialized conflicts.
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -230,6 +257,10 @@ pub struct CheckoutStats {
     /// working copy but were skipped because there was an untracked (probably
     /// ignored) file in its place.
     pub skipped_files: u32,
+    /// If files with unsnapshotted modifications were overwritten or removed,
+    /// the id of a tree containing their contents on top of the old tree.
+    /// Always `None` unless `CheckoutOptions::backup_unsnapshotted` is set.
+    pub backup_tree_id: Option<MergedTreeId>,
 }
 
 /// The working-copy checkout failed.
@@ -38,9 +38,10 @@ use crate::settings::UserSettings;
 use crate::signing::{SignInitError, Signer};
 use crate::store::Store;
 use crate::working_copy::{
-    CheckoutError, CheckoutStats, LockedWorkingCopy, WorkingCopy, WorkingCopyFactory,
-    WorkingCopyStateError,
+    CheckoutError, CheckoutOptions, CheckoutStats, LockedWorkingCopy, WorkingCopy,
+    WorkingCopyFactory, WorkingCopyStateError,
 };
+use crate::workspace_store::{SimpleWorkspaceStore, WorkspaceStoreError};
 
 #[derive(Error, Debug)]
 pub enum WorkspaceInitError {
@@ -130,6 +131,16 @@ fn init_working_copy(
     Ok((working_copy, repo))
 }
 
+/// Records where the new workspace lives, so it can be found from the other
+/// workspaces. A workspace whose path isn't valid Unicode isn't recorded.
+fn record_workspace_root(workspace: &Workspace) -> Result<(), WorkspaceInitError> {
+    let workspace_store = SimpleWorkspaceStore::load(workspace.repo_path());
+    match workspace_store.add(workspace.workspace_id(), workspace.workspace_root()) {
+        Ok(()) | Err(WorkspaceStoreError::NonUnicodePath) => Ok(()),
+        Err(WorkspaceStoreError::Path(err)) => Err(err.into()),
+    }
+}
+
 impl Workspace {
     fn new(
         workspace_root: &Path,
@@ -281,6 +292,7 @@ impl Workspace {
             )?;
             let repo_loader = repo.loader();
             let workspace = Workspace::new(workspace_root, working_copy, repo_loader)?;
+            record_workspace_root(&workspace)?;
             Ok((workspace, repo))
         })()
         .inspect_err(|_err| {
@@ -338,6 +350,7 @@ impl Workspace {
             workspace_id,
         )?;
         let workspace = Workspace::new(workspace_root, working_copy, repo.loader())?;
+        record_workspace_root(&workspace)?;
         Ok((workspace, repo))
     }
 
@@ -387,6 +400,7 @@ impl Workspace {
         operation_id: OperationId,
         old_tree_id: Option<&MergedTreeId>,
         commit: &Commit,
+        options: &CheckoutOptions,
     ) -> Result<CheckoutStats, CheckoutError> {
         let mut locked_ws =
             self.start_working_copy_mutation()
@@ -403,7 +417,7 @@ impl Workspace {
                 return Err(CheckoutError::ConcurrentCheckout);
             }
         }
-        let stats = locked_ws.locked_wc().check_out(commit)?;
+        let stats = locked_ws.locked_wc().check_out(commit, options)?;
         locked_ws
             .finish(operation_id)
             .map_err(|err| CheckoutError::Other {
@@ -508,7 +522,7 @@ impl WorkspaceLoader {
         }
     }
 
-    fn load_working_copy(
+    pub fn load_working_copy(
         &self,
         store: &Arc<Store>,
         working_copy_factories: &WorkingCopyFactories,
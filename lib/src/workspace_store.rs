@@ -0,0 +1,94 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Records where the workspaces of a repo are on disk.
+
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use thiserror::Error;
+
+use crate::file_util::{IoResultExt as _, PathError};
+use crate::op_store::WorkspaceId;
+
+/// Error that may occur while reading or updating the workspace store.
+#[derive(Debug, Error)]
+pub enum WorkspaceStoreError {
+    /// The workspace path isn't valid Unicode.
+    #[error("Workspace path could not be interpreted as Unicode text")]
+    NonUnicodePath,
+    /// Failed to access the store.
+    #[error(transparent)]
+    Path(#[from] PathError),
+}
+
+/// Workspace store that keeps the root path of each workspace in a file named
+/// after the hex-encoded workspace name.
+///
+/// Workspaces created before the store existed aren't recorded.
+/// Records are kept when a workspace is forgotten, so the path is still known
+/// if the forget is undone.
+#[derive(Clone, Debug)]
+pub struct SimpleWorkspaceStore {
+    store_dir: PathBuf,
+}
+
+impl SimpleWorkspaceStore {
+    /// Loads the store of the repo at `repo_path`. The store directory is
+    /// created when the first workspace is added.
+    pub fn load(repo_path: &Path) -> Self {
+        SimpleWorkspaceStore {
+            store_dir: repo_path.join("workspace_store"),
+        }
+    }
+
+    fn entry_path(&self, workspace_id: &WorkspaceId) -> PathBuf {
+        self.store_dir
+            .join(hex::encode(workspace_id.as_str().as_bytes()))
+    }
+
+    /// Records that the workspace lives at `workspace_root`, replacing any
+    /// previously recorded path.
+    pub fn add(
+        &self,
+        workspace_id: &WorkspaceId,
+        workspace_root: &Path,
+    ) -> Result<(), WorkspaceStoreError> {
+        let root_str = workspace_root
+            .to_str()
+            .ok_or(WorkspaceStoreError::NonUnicodePath)?;
+        fs::create_dir_all(&self.store_dir).context(&self.store_dir)?;
+        let path = self.entry_path(workspace_id);
+        fs::write(&path, root_str).context(&path)?;
+        Ok(())
+    }
+
+    /// Returns the recorded root path of the workspace, or `None` if the
+    /// workspace isn't recorded.
+    pub fn get_workspace_path(
+        &self,
+        workspace_id: &WorkspaceId,
+    ) -> Result<Option<PathBuf>, WorkspaceStoreError> {
+        let path = self.entry_path(workspace_id);
+        match fs::read(&path) {
+            Ok(buf) => {
+                let root_str =
+                    String::from_utf8(buf).map_err(|_| WorkspaceStoreError::NonUnicodePath)?;
+                Ok(Some(PathBuf::from(root_str)))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(PathError { path, error: err }.into()),
+        }
+    }
+}
@@ -15,7 +15,8 @@
 use indoc::indoc;
 use jj_lib::backend::FileId;
 use jj_lib::conflicts::{
-    extract_as_single_hunk, materialize_merge_result, parse_conflict, update_from_content,
+    extract_as_single_hunk, find_conflict_markers, materialize_merge_result, parse_conflict,
+    update_from_content,
 };
 use jj_lib::merge::Merge;
 use jj_lib::repo::Repo;
@@ -744,6 +745,84 @@ fn test_parse_conflict_malformed_diff() {
     )
 }
 
+#[test]
+fn test_parse_conflict_unbalanced_sides() {
+    // There's a removed side without a corresponding added side
+    assert_eq!(
+        parse_conflict(
+            indoc! {b"
+            line 1
+            <<<<<<<
+            -------
+            base
+            >>>>>>>
+            line 5
+            "},
+            2
+        ),
+        None
+    )
+}
+
+#[test]
+fn test_find_conflict_markers() {
+    assert_eq!(find_conflict_markers(b""), Vec::<usize>::new());
+    assert_eq!(
+        find_conflict_markers(b"line 1\nline 2\n"),
+        Vec::<usize>::new()
+    );
+
+    // Conflicts with any number of sides are found
+    let content = indoc! {b"
+        line 1
+        <<<<<<<
+        %%%%%%%
+        -line 2
+        +left
+        +++++++
+        right
+        >>>>>>>
+        line 3
+        <<<<<<< Conflict 2 of 2
+        +++++++ Contents of side #1
+        a
+        ------- Contents of base #1
+        b
+        +++++++ Contents of side #2
+        c
+        ------- Contents of base #2
+        d
+        +++++++ Contents of side #3
+        e
+        >>>>>>> Conflict 2 of 2 ends
+        "};
+    assert_eq!(find_conflict_markers(content), vec![1, 9]);
+
+    // Text that only resembles conflict markers isn't reported
+    let content = indoc! {b"
+        <<<<<<<
+        just some text
+        >>>>>>>
+        =======
+        +++++++
+        >>>>>>>
+        <<<<<<<
+        -------
+        base
+        >>>>>>>
+        "};
+    assert_eq!(find_conflict_markers(content), Vec::<usize>::new());
+    // An unterminated conflict isn't reported
+    let content = indoc! {b"
+        <<<<<<<
+        +++++++
+        left
+        +++++++
+        right
+        "};
+    assert_eq!(find_conflict_markers(content), Vec::<usize>::new());
+}
+
 #[test]
 fn test_update_conflict_from_content() {
     let test_repo = TestRepo::init();
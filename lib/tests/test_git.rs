@@ -26,7 +26,7 @@ use jj_lib::commit_builder::CommitBuilder;
 use jj_lib::git;
 use jj_lib::git::{
     FailedRefExportReason, GitBranchPushTargets, GitFetchError, GitImportError, GitPushError,
-    GitRefUpdate, RefName, SubmoduleConfig,
+    GitRefUpdate, GitTagPushTargets, RefName, SubmoduleConfig,
 };
 use jj_lib::git_backend::GitBackend;
 use jj_lib::object_id::ObjectId;
@@ -2618,6 +2618,7 @@ fn test_push_branches_success() {
         &clone_repo,
         "origin",
         &targets,
+        &GitTagPushTargets::default(),
         git::RemoteCallbacks::default(),
     );
     assert_eq!(result, Ok(()));
@@ -2687,6 +2688,7 @@ fn test_push_branches_deletion() {
         &get_git_repo(&setup.jj_repo),
         "origin",
         &targets,
+        &GitTagPushTargets::default(),
         git::RemoteCallbacks::default(),
     );
     assert_eq!(result, Ok(()));
@@ -2744,6 +2746,7 @@ fn test_push_branches_mixed_deletion_and_addition() {
         &clone_repo,
         "origin",
         &targets,
+        &GitTagPushTargets::default(),
         git::RemoteCallbacks::default(),
     );
     assert_eq!(result, Ok(()));
@@ -2782,6 +2785,42 @@ fn test_push_branches_mixed_deletion_and_addition() {
     assert!(!tx.mut_repo().has_changes());
 }
 
+#[test]
+fn test_push_branches_to_ref_prefix_deletion() {
+    let settings = testutils::user_settings();
+    let temp_dir = testutils::new_temp_dir();
+    let setup = set_up_push_repos(&settings, &temp_dir);
+
+    let targets = GitBranchPushTargets {
+        branch_updates: vec![(
+            "main".to_owned(),
+            BranchPushUpdate {
+                old_target: Some(setup.main_commit.id().clone()),
+                new_target: None,
+            },
+        )],
+    };
+    let result = git::push_branches_to_ref_prefix(
+        setup.jj_repo.as_ref(),
+        &get_git_repo(&setup.jj_repo),
+        "origin",
+        "refs/for/",
+        &targets,
+        &GitTagPushTargets::default(),
+        git::RemoteCallbacks::default(),
+    );
+    assert_eq!(
+        result,
+        Err(GitPushError::RefDeletionUnsupported(vec![
+            "refs/for/main".to_owned()
+        ]))
+    );
+
+    // Check that the branch on the remote is left alone
+    let source_repo = git2::Repository::open(&setup.source_repo_dir).unwrap();
+    assert!(source_repo.find_reference("refs/heads/main").is_ok());
+}
+
 #[test]
 fn test_push_branches_not_fast_forward() {
     let settings = testutils::user_settings();
@@ -2803,6 +2842,7 @@ fn test_push_branches_not_fast_forward() {
         &get_git_repo(&setup.jj_repo),
         "origin",
         &targets,
+        &GitTagPushTargets::default(),
         git::RemoteCallbacks::default(),
     );
     assert_eq!(result, Ok(()));
@@ -3027,6 +3067,132 @@ fn test_push_updates_success() {
     assert_eq!(new_target, Some(new_oid));
 }
 
+#[test]
+fn test_push_tags() {
+    let settings = testutils::user_settings();
+    let temp_dir = testutils::new_temp_dir();
+    let setup = set_up_push_repos(&settings, &temp_dir);
+    let clone_repo = get_git_repo(&setup.jj_repo);
+    let source_repo = git2::Repository::open(&setup.source_repo_dir).unwrap();
+
+    // Create a lightweight and an annotated tag in the local Git repo
+    let main_oid = git_id(&setup.main_commit);
+    clone_repo
+        .reference("refs/tags/light", main_oid, false, "")
+        .unwrap();
+    let signature = git2::Signature::now("Someone", "someone@example.com").unwrap();
+    let annotated_oid = clone_repo
+        .tag(
+            "annotated",
+            &clone_repo.find_object(main_oid, None).unwrap(),
+            &signature,
+            "message",
+            false,
+        )
+        .unwrap();
+
+    let push_tags = |tag_names: &[&str], force: bool| {
+        git::push_tags(
+            setup.jj_repo.as_ref(),
+            &clone_repo,
+            "origin",
+            &GitTagPushTargets {
+                tag_names: tag_names.iter().map(|name| name.to_string()).collect(),
+                force,
+            },
+            git::RemoteCallbacks::default(),
+        )
+    };
+    assert_eq!(push_tags(&["light", "annotated"], false), Ok(()));
+    let remote_target = |name: &str| {
+        source_repo
+            .find_reference(&format!("refs/tags/{name}"))
+            .unwrap()
+            .target()
+    };
+    assert_eq!(remote_target("light"), Some(main_oid));
+    // The annotated tag object is pushed as-is
+    assert_eq!(remote_target("annotated"), Some(annotated_oid));
+
+    // Pushing an unchanged tag is a no-op
+    assert_eq!(push_tags(&["light"], false), Ok(()));
+
+    // Moving a tag on the remote requires force
+    let child_oid = git_id(&setup.child_of_main_commit);
+    clone_repo
+        .reference("refs/tags/light", child_oid, true, "")
+        .unwrap();
+    assert_eq!(
+        push_tags(&["light"], false),
+        Err(GitPushError::RefAlreadyExists(vec![
+            "refs/tags/light".to_owned()
+        ]))
+    );
+    assert_eq!(remote_target("light"), Some(main_oid));
+    assert_eq!(push_tags(&["light"], true), Ok(()));
+    assert_eq!(remote_target("light"), Some(child_oid));
+}
+
+#[test]
+fn test_push_branches_with_tags() {
+    let settings = testutils::user_settings();
+    let temp_dir = testutils::new_temp_dir();
+    let setup = set_up_push_repos(&settings, &temp_dir);
+    let clone_repo = get_git_repo(&setup.jj_repo);
+    let source_repo = git2::Repository::open(&setup.source_repo_dir).unwrap();
+    let main_oid = git_id(&setup.main_commit);
+    let child_oid = git_id(&setup.child_of_main_commit);
+    source_repo
+        .reference("refs/tags/existing", main_oid, false, "")
+        .unwrap();
+    clone_repo
+        .reference("refs/tags/existing", child_oid, false, "")
+        .unwrap();
+    clone_repo
+        .reference("refs/tags/new", child_oid, false, "")
+        .unwrap();
+
+    let targets = GitBranchPushTargets {
+        branch_updates: vec![(
+            "main".to_owned(),
+            BranchPushUpdate {
+                old_target: Some(setup.main_commit.id().clone()),
+                new_target: Some(setup.child_of_main_commit.id().clone()),
+            },
+        )],
+    };
+    let push = |tag_names: &[&str]| {
+        let mut tx = setup.jj_repo.start_transaction(&settings);
+        git::push_branches(
+            tx.mut_repo(),
+            &clone_repo,
+            "origin",
+            &targets,
+            &GitTagPushTargets {
+                tag_names: tag_names.iter().map(|name| name.to_string()).collect(),
+                force: false,
+            },
+            git::RemoteCallbacks::default(),
+        )
+    };
+    let remote_target = |name: &str| source_repo.find_reference(name).unwrap().target();
+
+    // Nothing is pushed if a tag can't be pushed
+    assert_eq!(
+        push(&["existing", "new"]),
+        Err(GitPushError::RefAlreadyExists(vec![
+            "refs/tags/existing".to_owned()
+        ]))
+    );
+    assert_eq!(remote_target("refs/heads/main"), Some(main_oid));
+    assert!(source_repo.find_reference("refs/tags/new").is_err());
+
+    // The branch and the tag are pushed together
+    assert_eq!(push(&["new"]), Ok(()));
+    assert_eq!(remote_target("refs/heads/main"), Some(child_oid));
+    assert_eq!(remote_target("refs/tags/new"), Some(child_oid));
+}
+
 #[test]
 fn test_push_updates_no_such_remote() {
     let settings = testutils::user_settings();
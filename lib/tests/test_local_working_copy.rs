@@ -24,7 +24,9 @@ use itertools::Itertools;
 use jj_lib::backend::{MergedTreeId, TreeId, TreeValue};
 use jj_lib::file_util::{check_symlink_support, try_symlink};
 use jj_lib::fsmonitor::FsmonitorSettings;
+use jj_lib::gitignore::GitIgnoreFile;
 use jj_lib::local_working_copy::LocalWorkingCopy;
+use jj_lib::matchers::{EverythingMatcher, PrefixMatcher};
 use jj_lib::merge::{Merge, MergedTreeValue};
 use jj_lib::merged_tree::{MergedTree, MergedTreeBuilder};
 use jj_lib::op_store::{OperationId, WorkspaceId};
@@ -32,7 +34,7 @@ use jj_lib::repo::{ReadonlyRepo, Repo};
 use jj_lib::repo_path::{RepoPath, RepoPathBuf, RepoPathComponent};
 use jj_lib::secret_backend::SecretBackend;
 use jj_lib::settings::UserSettings;
-use jj_lib::working_copy::{CheckoutStats, SnapshotError, SnapshotOptions};
+use jj_lib::working_copy::{CheckoutOptions, CheckoutStats, SnapshotError, SnapshotOptions};
 use jj_lib::workspace::{default_working_copy_factories, LockedWorkspace, Workspace};
 use test_case::test_case;
 use testutils::{
@@ -226,10 +228,20 @@ fn test_checkout_file_transitions(backend: TestRepoBackend) {
     let right_commit = commit_with_tree(&store, right_tree_id.clone());
 
     let ws = &mut test_workspace.workspace;
-    ws.check_out(repo.op_id().clone(), None, &left_commit)
-        .unwrap();
-    ws.check_out(repo.op_id().clone(), None, &right_commit)
-        .unwrap();
+    ws.check_out(
+        repo.op_id().clone(),
+        None,
+        &left_commit,
+        &CheckoutOptions::empty_for_test(),
+    )
+    .unwrap();
+    ws.check_out(
+        repo.op_id().clone(),
+        None,
+        &right_commit,
+        &CheckoutOptions::empty_for_test(),
+    )
+    .unwrap();
 
     // Check that the working copy is clean.
     let new_tree = test_workspace.snapshot().unwrap();
@@ -325,9 +337,20 @@ fn test_conflict_subdirectory() {
     let merged_commit = commit_with_tree(repo.store(), merged_tree.id());
     let repo = &test_workspace.repo;
     let ws = &mut test_workspace.workspace;
-    ws.check_out(repo.op_id().clone(), None, &commit1).unwrap();
-    ws.check_out(repo.op_id().clone(), None, &merged_commit)
-        .unwrap();
+    ws.check_out(
+        repo.op_id().clone(),
+        None,
+        &commit1,
+        &CheckoutOptions::empty_for_test(),
+    )
+    .unwrap();
+    ws.check_out(
+        repo.op_id().clone(),
+        None,
+        &merged_commit,
+        &CheckoutOptions::empty_for_test(),
+    )
+    .unwrap();
 }
 
 #[test]
@@ -371,13 +394,25 @@ fn test_acl() {
         &default_working_copy_factories(),
     )
     .unwrap();
-    ws.check_out(repo.op_id().clone(), None, &commit1).unwrap();
+    ws.check_out(
+        repo.op_id().clone(),
+        None,
+        &commit1,
+        &CheckoutOptions::empty_for_test(),
+    )
+    .unwrap();
     assert!(!secret_modified_path.to_fs_path(&workspace_root).is_file());
     assert!(!secret_added_path.to_fs_path(&workspace_root).is_file());
     assert!(!secret_deleted_path.to_fs_path(&workspace_root).is_file());
     assert!(became_secret_path.to_fs_path(&workspace_root).is_file());
     assert!(!became_public_path.to_fs_path(&workspace_root).is_file());
-    ws.check_out(repo.op_id().clone(), None, &commit2).unwrap();
+    ws.check_out(
+        repo.op_id().clone(),
+        None,
+        &commit2,
+        &CheckoutOptions::empty_for_test(),
+    )
+    .unwrap();
     assert!(!secret_modified_path.to_fs_path(&workspace_root).is_file());
     assert!(!secret_added_path.to_fs_path(&workspace_root).is_file());
     assert!(!secret_deleted_path.to_fs_path(&workspace_root).is_file());
@@ -396,7 +431,13 @@ fn test_tree_builder_file_directory_transition() {
     let mut check_out_tree = |tree_id: &TreeId| {
         let tree = repo.store().get_tree(RepoPath::root(), tree_id).unwrap();
         let commit = commit_with_tree(repo.store(), MergedTreeId::Legacy(tree.id().clone()));
-        ws.check_out(repo.op_id().clone(), None, &commit).unwrap();
+        ws.check_out(
+            repo.op_id().clone(),
+            None,
+            &commit,
+            &CheckoutOptions::empty_for_test(),
+        )
+        .unwrap();
     };
 
     let parent_path = RepoPath::from_internal_string("foo/bar");
@@ -473,7 +514,14 @@ fn test_conflicting_changes_on_disk() {
     )
     .unwrap();
 
-    let stats = ws.check_out(repo.op_id().clone(), None, &commit).unwrap();
+    let stats = ws
+        .check_out(
+            repo.op_id().clone(),
+            None,
+            &commit,
+            &CheckoutOptions::empty_for_test(),
+        )
+        .unwrap();
     assert_eq!(
         stats,
         CheckoutStats {
@@ -481,6 +529,7 @@ fn test_conflicting_changes_on_disk() {
             added_files: 3,
             removed_files: 0,
             skipped_files: 3,
+            backup_tree_id: None,
         }
     );
 
@@ -519,7 +568,13 @@ fn test_reset() {
 
     let ws = &mut test_workspace.workspace;
     let commit = commit_with_tree(repo.store(), tree_with_file.id());
-    ws.check_out(repo.op_id().clone(), None, &commit).unwrap();
+    ws.check_out(
+        repo.op_id().clone(),
+        None,
+        &commit,
+        &CheckoutOptions::empty_for_test(),
+    )
+    .unwrap();
 
     // Test the setup: the file should exist on disk and in the tree state.
     assert!(ignored_path.to_fs_path(&workspace_root).is_file());
@@ -571,7 +626,13 @@ fn test_checkout_discard() {
     let commit2 = commit_with_tree(repo.store(), tree2.id());
 
     let ws = &mut test_workspace.workspace;
-    ws.check_out(repo.op_id().clone(), None, &commit1).unwrap();
+    ws.check_out(
+        repo.op_id().clone(),
+        None,
+        &commit1,
+        &CheckoutOptions::empty_for_test(),
+    )
+    .unwrap();
     let wc: &LocalWorkingCopy = ws.working_copy().as_any().downcast_ref().unwrap();
     let state_path = wc.state_path().to_path_buf();
 
@@ -582,7 +643,10 @@ fn test_checkout_discard() {
 
     // Start a checkout
     let mut locked_ws = ws.start_working_copy_mutation().unwrap();
-    locked_ws.locked_wc().check_out(&commit2).unwrap();
+    locked_ws
+        .locked_wc()
+        .check_out(&commit2, &CheckoutOptions::empty_for_test())
+        .unwrap();
     // The change should be reflected in the working copy but not saved
     assert!(!file1_path.to_fs_path(&workspace_root).is_file());
     assert!(file2_path.to_fs_path(&workspace_root).is_file());
@@ -603,6 +667,86 @@ fn test_checkout_discard() {
     assert!(!reloaded_wc.file_states().unwrap().contains_path(file2_path));
 }
 
+#[test]
+fn test_checkout_backs_up_unsnapshotted_changes() {
+    // Modify files on disk after they were checked out, then check out another
+    // tree without snapshotting. The modified contents should be saved in a
+    // backup tree.
+    let settings = testutils::user_settings();
+    let mut test_workspace = TestWorkspace::init(&settings);
+    let repo = test_workspace.repo.clone();
+    let workspace_root = test_workspace.workspace.workspace_root().clone();
+
+    let modified_path = RepoPath::from_internal_string("modified");
+    let updated_path = RepoPath::from_internal_string("updated");
+    let unchanged_path = RepoPath::from_internal_string("unchanged");
+
+    let tree1 = create_tree(
+        &repo,
+        &[
+            (modified_path, "1"),
+            (updated_path, "1"),
+            (unchanged_path, "1"),
+        ],
+    );
+    let tree2 = create_tree(&repo, &[(updated_path, "2")]);
+    let commit1 = commit_with_tree(repo.store(), tree1.id());
+    let commit2 = commit_with_tree(repo.store(), tree2.id());
+
+    let ws = &mut test_workspace.workspace;
+    ws.check_out(
+        repo.op_id().clone(),
+        None,
+        &commit1,
+        &CheckoutOptions::empty_for_test(),
+    )
+    .unwrap();
+
+    // A file that differs from both trees is backed up, whereas a file that
+    // already has the new contents isn't.
+    let backup_options = CheckoutOptions {
+        backup_unsnapshotted: true,
+    };
+    std::fs::write(modified_path.to_fs_path(&workspace_root), "modified").unwrap();
+    std::fs::write(updated_path.to_fs_path(&workspace_root), "2").unwrap();
+    let stats = ws
+        .check_out(
+            repo.op_id().clone(),
+            Some(&tree1.id()),
+            &commit2,
+            &backup_options,
+        )
+        .unwrap();
+    let backup_tree_id = stats.backup_tree_id.unwrap();
+    assert!(!modified_path.to_fs_path(&workspace_root).exists());
+    assert_eq!(
+        std::fs::read_to_string(updated_path.to_fs_path(&workspace_root)).unwrap(),
+        "2"
+    );
+
+    let backup_tree = repo.store().get_root_tree(&backup_tree_id).unwrap();
+    let expected_tree = create_tree(
+        &repo,
+        &[
+            (modified_path, "modified"),
+            (updated_path, "1"),
+            (unchanged_path, "1"),
+        ],
+    );
+    assert_eq!(backup_tree.id(), expected_tree.id());
+
+    // Checking out a clean working copy doesn't create a backup
+    let stats = ws
+        .check_out(
+            repo.op_id().clone(),
+            Some(&tree2.id()),
+            &commit1,
+            &backup_options,
+        )
+        .unwrap();
+    assert_eq!(stats.backup_tree_id, None);
+}
+
 #[test]
 fn test_materialize_snapshot_conflicted_files() {
     let settings = testutils::user_settings();
@@ -629,7 +773,14 @@ fn test_materialize_snapshot_conflicted_files() {
         .unwrap();
     let commit = commit_with_tree(repo.store(), merged_tree.id());
 
-    let stats = ws.check_out(repo.op_id().clone(), None, &commit).unwrap();
+    let stats = ws
+        .check_out(
+            repo.op_id().clone(),
+            None,
+            &commit,
+            &CheckoutOptions::empty_for_test(),
+        )
+        .unwrap();
     assert_eq!(
         stats,
         CheckoutStats {
@@ -637,6 +788,7 @@ fn test_materialize_snapshot_conflicted_files() {
             added_files: 2,
             removed_files: 0,
             skipped_files: 0,
+            backup_tree_id: None,
         }
     );
 
@@ -859,6 +1011,77 @@ fn test_gitignores() {
     );
 }
 
+#[test]
+fn test_jjignores() {
+    // Tests that .jjignore files are respected like .gitignore files, and that
+    // they take precedence over the .gitignore in the same directory.
+
+    let settings = testutils::user_settings();
+    // (ignore files with their contents, file to snapshot, expected to be tracked)
+    let cases = [
+        (vec![(".jjignore", "file\n")], "file", false),
+        (vec![(".jjignore", "file\n")], "dir/file", false),
+        (vec![(".jjignore", "/file\n")], "dir/file", true),
+        (vec![(".jjignore", "dir/\n")], "dir/file", false),
+        (vec![(".jjignore", "file/\n")], "file", true),
+        (vec![(".jjignore", "*.o\n!keep.o\n")], "keep.o", true),
+        (vec![(".jjignore", "*.o\n!keep.o\n")], "other.o", false),
+        (
+            vec![(".gitignore", "file\n"), (".jjignore", "!file\n")],
+            "file",
+            true,
+        ),
+        (
+            vec![(".gitignore", "!file\n"), (".jjignore", "file\n")],
+            "file",
+            false,
+        ),
+        (
+            vec![(".gitignore", "*.o\n"), (".jjignore", "")],
+            "file.o",
+            false,
+        ),
+        (vec![("dir/.jjignore", "file\n")], "dir/file", false),
+        (vec![("dir/.jjignore", "file\n")], "file", true),
+        (
+            vec![(".jjignore", "file\n"), ("dir/.jjignore", "!file\n")],
+            "dir/file",
+            true,
+        ),
+        (
+            vec![(".jjignore", "file\n"), ("dir/.gitignore", "!file\n")],
+            "dir/file",
+            true,
+        ),
+        (
+            vec![(".gitignore", "file\n"), ("dir/.jjignore", "!file\n")],
+            "dir/file",
+            true,
+        ),
+    ];
+    for (ignore_files, file, expected_tracked) in cases {
+        let mut test_workspace = TestWorkspace::init(&settings);
+        let workspace_root = test_workspace.workspace.workspace_root().clone();
+        std::fs::create_dir(workspace_root.join("dir")).unwrap();
+        for &(ignore_path, contents) in &ignore_files {
+            testutils::write_working_copy_file(
+                &workspace_root,
+                RepoPath::from_internal_string(ignore_path),
+                contents,
+            );
+        }
+        let file_path = RepoPath::from_internal_string(file);
+        testutils::write_working_copy_file(&workspace_root, file_path, "contents");
+
+        let tree = test_workspace.snapshot().unwrap();
+        assert_eq!(
+            tree.path_value(file_path).unwrap().is_present(),
+            expected_tracked,
+            "ignore files: {ignore_files:?}, file: {file:?}"
+        );
+    }
+}
+
 #[test]
 fn test_gitignores_in_ignored_dir() {
     // Tests that .gitignore files in an ignored directory are ignored, i.e. that
@@ -876,7 +1099,13 @@ fn test_gitignores_in_ignored_dir() {
     let tree1 = create_tree(&test_workspace.repo, &[(gitignore_path, "ignored\n")]);
     let commit1 = commit_with_tree(test_workspace.repo.store(), tree1.id());
     let ws = &mut test_workspace.workspace;
-    ws.check_out(op_id.clone(), None, &commit1).unwrap();
+    ws.check_out(
+        op_id.clone(),
+        None,
+        &commit1,
+        &CheckoutOptions::empty_for_test(),
+    )
+    .unwrap();
 
     testutils::write_working_copy_file(&workspace_root, nested_gitignore_path, "!file\n");
     testutils::write_working_copy_file(&workspace_root, ignored_path, "contents");
@@ -904,6 +1133,66 @@ fn test_gitignores_in_ignored_dir() {
     assert_eq!(tree_entries(&new_tree), tree_entries(&tree2));
 }
 
+#[test]
+fn test_untracked_files() {
+    // Tests that untracked files are listed without snapshotting them, and that
+    // the files in an ignored directory are reported as ignored.
+
+    let settings = testutils::user_settings();
+    let mut test_workspace = TestWorkspace::init(&settings);
+    let workspace_root = test_workspace.workspace.workspace_root().clone();
+
+    let gitignore_path = RepoPath::from_internal_string(".gitignore");
+    let tracked_path = RepoPath::from_internal_string("tracked");
+    let added_path = RepoPath::from_internal_string("added");
+    let ignored_path = RepoPath::from_internal_string("ignored");
+    let subdir_added_path = RepoPath::from_internal_string("dir/added");
+    let ignored_dir_path = RepoPath::from_internal_string("ignored_dir/file");
+    let nested_gitignore_path = RepoPath::from_internal_string("ignored_dir/.gitignore");
+
+    testutils::write_working_copy_file(&workspace_root, gitignore_path, "ignored*\n");
+    testutils::write_working_copy_file(&workspace_root, tracked_path, "1");
+    test_workspace.snapshot().unwrap();
+
+    testutils::write_working_copy_file(&workspace_root, added_path, "2");
+    testutils::write_working_copy_file(&workspace_root, ignored_path, "2");
+    std::fs::create_dir(workspace_root.join("dir")).unwrap();
+    testutils::write_working_copy_file(&workspace_root, subdir_added_path, "2");
+    std::fs::create_dir(workspace_root.join("ignored_dir")).unwrap();
+    testutils::write_working_copy_file(&workspace_root, nested_gitignore_path, "!file\n");
+    testutils::write_working_copy_file(&workspace_root, ignored_dir_path, "2");
+
+    let wc: &LocalWorkingCopy = test_workspace
+        .workspace
+        .working_copy()
+        .as_any()
+        .downcast_ref()
+        .unwrap();
+    let untracked = wc
+        .untracked_files(GitIgnoreFile::empty(), &EverythingMatcher)
+        .unwrap();
+    assert_eq!(
+        untracked.ignored,
+        to_owned_path_vec(&[ignored_path, nested_gitignore_path, ignored_dir_path])
+    );
+    assert_eq!(
+        untracked.not_ignored,
+        to_owned_path_vec(&[added_path, subdir_added_path])
+    );
+
+    let untracked = wc
+        .untracked_files(
+            GitIgnoreFile::empty(),
+            &PrefixMatcher::new([RepoPath::from_internal_string("dir")]),
+        )
+        .unwrap();
+    assert_eq!(untracked.ignored, vec![]);
+    assert_eq!(
+        untracked.not_ignored,
+        to_owned_path_vec(&[subdir_added_path])
+    );
+}
+
 #[test]
 fn test_gitignores_checkout_never_overwrites_ignored() {
     // Tests that a .gitignore'd file doesn't get overwritten if check out a commit
@@ -928,7 +1217,14 @@ fn test_gitignores_checkout_never_overwrites_ignored() {
     // "contents". The exiting contents ("garbage") shouldn't be replaced in the
     // working copy.
     let ws = &mut test_workspace.workspace;
-    assert!(ws.check_out(repo.op_id().clone(), None, &commit).is_ok());
+    assert!(ws
+        .check_out(
+            repo.op_id().clone(),
+            None,
+            &commit,
+            &CheckoutOptions::empty_for_test()
+        )
+        .is_ok());
 
     // Check that the old contents are in the working copy
     let path = workspace_root.join("modified");
@@ -998,7 +1294,13 @@ fn test_gitignores_ignored_directory_already_tracked() {
 
     // Check out the tree with the files in `ignored/`
     let ws = &mut test_workspace.workspace;
-    ws.check_out(repo.op_id().clone(), None, &commit).unwrap();
+    ws.check_out(
+        repo.op_id().clone(),
+        None,
+        &commit,
+        &CheckoutOptions::empty_for_test(),
+    )
+    .unwrap();
 
     // Make some changes inside the ignored directory and check that they are
     // detected when we snapshot. The files that are still there should not be
@@ -1103,7 +1405,13 @@ fn test_gitsubmodule() {
     let tree = store.get_root_tree(&tree_id).unwrap();
     let commit = commit_with_tree(repo.store(), tree.id());
     let ws = &mut test_workspace.workspace;
-    ws.check_out(repo.op_id().clone(), None, &commit).unwrap();
+    ws.check_out(
+        repo.op_id().clone(),
+        None,
+        &commit,
+        &CheckoutOptions::empty_for_test(),
+    )
+    .unwrap();
 
     std::fs::create_dir(submodule_path.to_fs_path(&workspace_root)).unwrap();
 
@@ -1147,7 +1455,14 @@ fn test_existing_directory_symlink() {
 
     // Checkout should fail because "parent" already exists and is a symlink.
     let ws = &mut test_workspace.workspace;
-    assert!(ws.check_out(repo.op_id().clone(), None, &commit).is_err());
+    assert!(ws
+        .check_out(
+            repo.op_id().clone(),
+            None,
+            &commit,
+            &CheckoutOptions::empty_for_test()
+        )
+        .is_err());
 
     // Therefore, "../escaped" shouldn't be created.
     assert!(!workspace_root.parent().unwrap().join("escaped").exists());
@@ -1247,6 +1562,56 @@ fn test_fsmonitor() {
     }
 }
 
+#[test]
+fn test_snapshot_matching_paths() {
+    // Tests that a snapshot limited to some paths keeps the recorded state of
+    // the other paths
+    let settings = testutils::user_settings();
+    let mut test_workspace = TestWorkspace::init(&settings);
+    let repo = test_workspace.repo.clone();
+    let workspace_root = test_workspace.workspace.workspace_root().clone();
+
+    let dir1_file_path = RepoPath::from_internal_string("dir1/file");
+    let dir1_added_path = RepoPath::from_internal_string("dir1/added");
+    let dir2_file_path = RepoPath::from_internal_string("dir2/file");
+    let dir2_added_path = RepoPath::from_internal_string("dir2/added");
+    testutils::write_working_copy_file(&workspace_root, dir1_file_path, "1\n");
+    testutils::write_working_copy_file(&workspace_root, dir2_file_path, "1\n");
+    test_workspace.snapshot().unwrap();
+
+    testutils::write_working_copy_file(&workspace_root, dir1_file_path, "2\n");
+    testutils::write_working_copy_file(&workspace_root, dir1_added_path, "2\n");
+    std::fs::remove_file(dir2_file_path.to_fs_path(&workspace_root)).unwrap();
+    testutils::write_working_copy_file(&workspace_root, dir2_added_path, "2\n");
+
+    let matcher = PrefixMatcher::new([RepoPath::from_internal_string("dir1")]);
+    let ws = &mut test_workspace.workspace;
+    let mut locked_ws = ws.start_working_copy_mutation().unwrap();
+    let tree_id = locked_ws
+        .locked_wc()
+        .snapshot(SnapshotOptions {
+            matcher: &matcher,
+            ..SnapshotOptions::empty_for_test()
+        })
+        .unwrap();
+    locked_ws.finish(repo.op_id().clone()).unwrap();
+    insta::assert_snapshot!(testutils::dump_tree(repo.store(), &tree_id), @r###"
+    tree 39caca77d1a0fc4ef68c
+      file "dir1/added" (d4556355a5d285b0c778): "2\n"
+      file "dir1/file" (d4556355a5d285b0c778): "2\n"
+      file "dir2/file" (f551dde2c6b502084af9): "1\n"
+    "###);
+
+    // A full snapshot records the other changes
+    let tree = test_workspace.snapshot().unwrap();
+    insta::assert_snapshot!(testutils::dump_tree(repo.store(), &tree.id()), @r###"
+    tree b2d972a3980554e298fd
+      file "dir1/added" (d4556355a5d285b0c778): "2\n"
+      file "dir1/file" (d4556355a5d285b0c778): "2\n"
+      file "dir2/added" (d4556355a5d285b0c778): "2\n"
+    "###);
+}
+
 #[test]
 fn test_snapshot_max_new_file_size() {
     let settings = UserSettings::from_config(
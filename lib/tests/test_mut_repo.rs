@@ -546,6 +546,46 @@ fn test_rebase_descendants_divergent_rewrite() {
     assert!(rebase_map.is_empty());
 }
 
+#[test]
+fn test_drop_rewrites() {
+    // Test that dropping the recorded rewrites leaves the descendants in place
+    // and hides the old commits that have no descendants.
+    let settings = testutils::user_settings();
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction(&settings);
+    let mut graph_builder = CommitGraphBuilder::new(&settings, tx.mut_repo());
+    let commit1 = graph_builder.initial_commit();
+    let commit2 = graph_builder.commit_with_parents(&[&commit1]);
+    let commit3 = graph_builder.commit_with_parents(&[&commit2]);
+    let commit4 = graph_builder.commit_with_parents(&[&commit1]);
+    let repo = tx.commit("test");
+
+    let mut tx = repo.start_transaction(&settings);
+    let mut_repo = tx.mut_repo();
+    let mut graph_builder = CommitGraphBuilder::new(&settings, mut_repo);
+    let commit5 = graph_builder.commit_with_parents(&[&commit1]);
+    mut_repo.set_rewritten_commit(commit2.id().clone(), commit5.id().clone());
+    mut_repo.record_abandoned_commit(commit4.id().clone());
+    let mut old_ids = tx.mut_repo().drop_rewrites();
+    old_ids.sort();
+    let mut expected_old_ids = vec![commit2.id().clone(), commit4.id().clone()];
+    expected_old_ids.sort();
+    assert_eq!(old_ids, expected_old_ids);
+    assert!(!tx.mut_repo().has_rewrites());
+    // Commit 3 is left on top of commit 2, and commit 4 is hidden
+    assert_eq!(
+        *tx.mut_repo().view().heads(),
+        hashset! {commit3.id().clone(), commit5.id().clone()}
+    );
+    let rebase_map = tx
+        .mut_repo()
+        .rebase_descendants_return_map(&settings)
+        .unwrap();
+    assert!(rebase_map.is_empty());
+}
+
 #[test]
 fn test_rename_remote() {
     let settings = testutils::user_settings();
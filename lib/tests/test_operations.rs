@@ -399,6 +399,54 @@ fn test_reparent_range_branchy() {
     assert_eq!(new_op_f.parent_ids(), slice::from_ref(repo_d.op_id()));
 }
 
+#[test]
+fn test_find_closest_common_ancestor() {
+    let settings = testutils::user_settings();
+    let test_repo = TestRepo::init();
+    let repo_0 = test_repo.repo;
+
+    // Set up branchy operation graph:
+    // D E F
+    // | |/
+    // | C
+    // |/
+    // B
+    // A
+    // 0 (initial)
+    let random_tx = |repo: &Arc<ReadonlyRepo>| {
+        let mut tx = repo.start_transaction(&settings);
+        write_random_commit(tx.mut_repo(), &settings);
+        tx
+    };
+    let repo_a = random_tx(&repo_0).commit("op A");
+    let repo_b = random_tx(&repo_a).commit("op B");
+    let repo_c = random_tx(&repo_b).commit("op C");
+    let repo_d = random_tx(&repo_b).commit("op D");
+    let repo_e = random_tx(&repo_c).commit("op E");
+    let repo_f = random_tx(&repo_c).commit("op F");
+
+    let find = |repos: &[&Arc<ReadonlyRepo>]| {
+        let head_ops = repos
+            .iter()
+            .map(|repo| repo.operation().clone())
+            .collect_vec();
+        op_walk::find_closest_common_ancestor(&head_ops)
+            .unwrap()
+            .map(|op| op.id().clone())
+    };
+    assert_eq!(find(&[]), None);
+    assert_eq!(find(&[&repo_d]), Some(repo_d.op_id().clone()));
+    assert_eq!(find(&[&repo_e, &repo_f]), Some(repo_c.op_id().clone()));
+    assert_eq!(find(&[&repo_d, &repo_e]), Some(repo_b.op_id().clone()));
+    assert_eq!(
+        find(&[&repo_d, &repo_e, &repo_f]),
+        Some(repo_b.op_id().clone())
+    );
+    // A head that is an ancestor of the others is the common ancestor
+    assert_eq!(find(&[&repo_c, &repo_e]), Some(repo_c.op_id().clone()));
+    assert_eq!(find(&[&repo_a, &repo_d]), Some(repo_a.op_id().clone()));
+}
+
 fn stable_op_id_settings() -> UserSettings {
     UserSettings::from_config(
         testutils::base_config()
@@ -411,6 +459,142 @@ fn stable_op_id_settings() -> UserSettings {
     )
 }
 
+#[test]
+fn test_walk_ancestors_relative_to() {
+    let settings = testutils::user_settings();
+    let test_repo = TestRepo::init();
+    let repo_0 = test_repo.repo;
+
+    fn op_parents<const N: usize>(op: &Operation) -> [Operation; N] {
+        let parents: Vec<_> = op.parents().try_collect().unwrap();
+        parents.try_into().unwrap()
+    }
+
+    // Set up branchy operation graph:
+    // E
+    // |\
+    // | D
+    // C |
+    // |/
+    // B
+    // A
+    // 0 (initial)
+    let random_tx = |repo: &Arc<ReadonlyRepo>| {
+        let mut tx = repo.start_transaction(&settings);
+        write_random_commit(tx.mut_repo(), &settings);
+        tx
+    };
+    let repo_a = random_tx(&repo_0).commit("op A");
+    let repo_b = random_tx(&repo_a).commit("op B");
+    let tx_c = random_tx(&repo_b);
+    let tx_d = random_tx(&repo_b);
+    let repo_e = testutils::commit_transactions(&settings, vec![tx_c, tx_d]);
+    let [op_c, op_d] = op_parents(repo_e.operation());
+
+    let walk = |head_ops: &[Operation], base_op: &Operation| {
+        op_walk::walk_ancestors_relative_to(head_ops, base_op)
+            .map_ok(|(op, is_reachable)| (op.id().clone(), is_reachable))
+            .try_collect::<_, Vec<_>, _>()
+            .unwrap()
+    };
+    let op_ids_before_b = op_walk::walk_ancestors(slice::from_ref(repo_b.operation()))
+        .map_ok(|op| (op.id().clone(), true))
+        .try_collect::<_, Vec<_>, _>()
+        .unwrap();
+
+    // Operations after B are not ancestors of B
+    let actual = walk(slice::from_ref(repo_e.operation()), repo_b.operation());
+    assert_eq!(actual[0], (repo_e.op_id().clone(), false));
+    assert_eq!(
+        actual[1..3].iter().sorted().collect_vec(),
+        [(op_c.id().clone(), false), (op_d.id().clone(), false)]
+            .iter()
+            .sorted()
+            .collect_vec()
+    );
+    assert_eq!(actual[3..], op_ids_before_b);
+
+    // The sibling operation D is not an ancestor of C
+    let actual = walk(slice::from_ref(repo_e.operation()), &op_c);
+    assert_eq!(
+        actual
+            .iter()
+            .filter(|(_, is_reachable)| !is_reachable)
+            .map(|(id, _)| id)
+            .sorted()
+            .collect_vec(),
+        [repo_e.op_id(), op_d.id()]
+            .into_iter()
+            .sorted()
+            .collect_vec()
+    );
+    assert!(actual.contains(&(op_c.id().clone(), true)));
+
+    // The base operation is included even if it isn't reachable from the heads
+    let actual = walk(slice::from_ref(repo_b.operation()), &op_c);
+    assert_eq!(actual[0], (op_c.id().clone(), true));
+    assert_eq!(actual[1..], op_ids_before_b);
+}
+
+#[test]
+fn test_find_descendant_ids() {
+    let settings = testutils::user_settings();
+    let test_repo = TestRepo::init();
+    let repo_0 = test_repo.repo;
+
+    fn op_parents<const N: usize>(op: &Operation) -> [Operation; N] {
+        let parents: Vec<_> = op.parents().try_collect().unwrap();
+        parents.try_into().unwrap()
+    }
+
+    // Set up branchy operation graph:
+    // E
+    // |\
+    // | D
+    // C |
+    // |/
+    // B
+    // A
+    // 0 (initial)
+    let random_tx = |repo: &Arc<ReadonlyRepo>| {
+        let mut tx = repo.start_transaction(&settings);
+        write_random_commit(tx.mut_repo(), &settings);
+        tx
+    };
+    let repo_a = random_tx(&repo_0).commit("op A");
+    let repo_b = random_tx(&repo_a).commit("op B");
+    let tx_c = random_tx(&repo_b);
+    let tx_d = random_tx(&repo_b);
+    let repo_e = testutils::commit_transactions(&settings, vec![tx_c, tx_d]);
+    let op_e = repo_e.operation();
+    let [op_c, op_d] = op_parents(op_e);
+
+    let find = |head_ops: &[Operation], base_op: &Operation| {
+        op_walk::find_descendant_ids(head_ops, base_op)
+            .unwrap()
+            .into_iter()
+            .sorted()
+            .collect_vec()
+    };
+
+    // Descendants of B are on both branches
+    assert_eq!(
+        find(slice::from_ref(op_e), repo_b.operation()),
+        [op_e.id(), op_c.id(), op_d.id()]
+            .into_iter()
+            .cloned()
+            .sorted()
+            .collect_vec()
+    );
+
+    // The sibling operation D is not a descendant of C
+    assert_eq!(find(slice::from_ref(op_e), &op_c), vec![op_e.id().clone()]);
+    assert_eq!(find(&[op_c.clone(), op_d.clone()], &op_c), vec![]);
+
+    // The head itself has no descendants
+    assert_eq!(find(slice::from_ref(op_e), op_e), vec![]);
+}
+
 #[test]
 fn test_resolve_op_id() {
     let settings = stable_op_id_settings();
@@ -28,12 +28,15 @@ use jj_lib::repo::Repo;
 use jj_lib::repo_path::{RepoPath, RepoPathUiConverter};
 use jj_lib::revset::{
     optimize, parse, DefaultSymbolResolver, FailingSymbolResolver, ResolvedExpression, Revset,
-    RevsetAliasesMap, RevsetExpression, RevsetExtensions, RevsetFilterPredicate,
-    RevsetParseContext, RevsetResolutionError, RevsetWorkspaceContext, SymbolResolverExtension,
+    RevsetAliasesMap, RevsetEvaluationError, RevsetExpression, RevsetExtensions,
+    RevsetFilterPredicate, RevsetParseContext, RevsetResolutionError, RevsetWorkspaceContext,
+    SymbolResolverExtension,
 };
 use jj_lib::settings::GitSettings;
+use jj_lib::signing::{SignBehavior, Signer};
 use jj_lib::workspace::Workspace;
 use test_case::test_case;
+use testutils::test_signing_backend::TestSigningBackend;
 use testutils::{
     create_random_commit, create_tree, write_random_commit, CommitGraphBuilder, TestRepo,
     TestRepoBackend, TestWorkspace,
@@ -831,6 +834,13 @@ fn test_resolve_symbol_git_refs() {
 }
 
 fn resolve_commit_ids(repo: &dyn Repo, revset_str: &str) -> Vec<CommitId> {
+    try_resolve_commit_ids(repo, revset_str).unwrap()
+}
+
+fn try_resolve_commit_ids(
+    repo: &dyn Repo,
+    revset_str: &str,
+) -> Result<Vec<CommitId>, RevsetEvaluationError> {
     let settings = testutils::user_settings();
     let aliases_map = RevsetAliasesMap::default();
     let revset_extensions = RevsetExtensions::default();
@@ -845,7 +855,7 @@ fn resolve_commit_ids(repo: &dyn Repo, revset_str: &str) -> Vec<CommitId> {
     let expression = expression
         .resolve_user_expression(repo, &symbol_resolver)
         .unwrap();
-    expression.evaluate(repo).unwrap().iter().collect()
+    Ok(expression.evaluate(repo)?.iter().collect())
 }
 
 fn resolve_commit_ids_in_workspace(
@@ -1028,6 +1038,148 @@ fn test_evaluate_expression_roots() {
     );
 }
 
+#[test]
+fn test_evaluate_expression_fork_point() {
+    let settings = testutils::user_settings();
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    // 5 6
+    // |\|
+    // | X
+    // |/ \
+    // 3   4
+    // |   |
+    // 2   |
+    // |/--
+    // 1
+    let root_commit = repo.store().root_commit();
+    let mut tx = repo.start_transaction(&settings);
+    let mut_repo = tx.mut_repo();
+    let mut graph_builder = CommitGraphBuilder::new(&settings, mut_repo);
+    let commit1 = graph_builder.initial_commit();
+    let commit2 = graph_builder.commit_with_parents(&[&commit1]);
+    let commit3 = graph_builder.commit_with_parents(&[&commit2]);
+    let commit4 = graph_builder.commit_with_parents(&[&commit1]);
+    let commit5 = graph_builder.commit_with_parents(&[&commit3, &commit4]);
+    let commit6 = graph_builder.commit_with_parents(&[&commit3, &commit4]);
+
+    // Fork point of a commit and itself is the commit
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("fork_point({}, {})", commit2.id().hex(), commit2.id().hex())
+        ),
+        vec![commit2.id().clone()]
+    );
+
+    // Fork point of a commit and its ancestor is the ancestor
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("fork_point({}, {})", commit3.id().hex(), commit1.id().hex())
+        ),
+        vec![commit1.id().clone()]
+    );
+
+    // Fork point of siblings is the common parent
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("fork_point({}, {})", commit3.id().hex(), commit4.id().hex())
+        ),
+        vec![commit1.id().clone()]
+    );
+
+    // Criss-cross merges have multiple fork points
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("fork_point({}, {})", commit5.id().hex(), commit6.id().hex())
+        ),
+        vec![commit4.id().clone(), commit3.id().clone()]
+    );
+
+    // Fork point with the root is the root
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("fork_point(root(), {})", commit5.id().hex())
+        ),
+        vec![root_commit.id().clone()]
+    );
+
+    // Fork point of an empty set is an error
+    assert_matches!(
+        try_resolve_commit_ids(
+            mut_repo,
+            &format!("fork_point(none(), {})", commit5.id().hex())
+        ),
+        Err(RevsetEvaluationError::Other(_))
+    );
+}
+
+#[test]
+fn test_evaluate_expression_midpoint() {
+    let settings = testutils::user_settings();
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction(&settings);
+    let mut_repo = tx.mut_repo();
+    let mut graph_builder = CommitGraphBuilder::new(&settings, mut_repo);
+    let commit1 = graph_builder.initial_commit();
+    let commit2 = graph_builder.commit_with_parents(&[&commit1]);
+    let commit3 = graph_builder.commit_with_parents(&[&commit2]);
+    let commit4 = graph_builder.commit_with_parents(&[&commit3]);
+    let commit5 = graph_builder.commit_with_parents(&[&commit4]);
+    let commit6 = graph_builder.commit_with_parents(&[&commit1]);
+    let commit7 = graph_builder.commit_with_parents(&[&commit5, &commit6]);
+    let commit8 = graph_builder.commit_with_parents(&[&commit6, &commit4]);
+
+    // Midpoint of a single commit is the commit
+    assert_eq!(
+        resolve_commit_ids(mut_repo, &format!("midpoint({})", commit2.id().hex())),
+        vec![commit2.id().clone()]
+    );
+
+    // Midpoint of a linear range
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("midpoint({}..{})", commit1.id().hex(), commit5.id().hex())
+        ),
+        vec![commit3.id().clone()]
+    );
+
+    // Midpoint of a set including a merge: commit3 has 4 of the 8 commits as
+    // ancestors (including itself)
+    assert_eq!(
+        resolve_commit_ids(mut_repo, &format!("midpoint(::{})", commit7.id().hex())),
+        vec![commit3.id().clone()]
+    );
+
+    // Ancestors shared by the parents of merges are counted once: commit3 and
+    // commit4 both split the 9 commits into 4 and 5, and the older one wins
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!(
+                "midpoint(::({} | {}))",
+                commit7.id().hex(),
+                commit8.id().hex()
+            )
+        ),
+        vec![commit3.id().clone()]
+    );
+
+    // Midpoint of an empty set is an error
+    assert_matches!(
+        try_resolve_commit_ids(mut_repo, "midpoint(none())"),
+        Err(RevsetEvaluationError::Other(_))
+    );
+}
+
 #[test]
 fn test_evaluate_expression_parents() {
     let settings = testutils::user_settings();
@@ -2946,6 +3098,55 @@ fn test_evaluate_expression_conflict() {
         resolve_commit_ids(mut_repo, "conflict()"),
         vec![commit4.id().clone()]
     );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "conflicts()"),
+        vec![commit4.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, &format!("::{} & conflicts()", commit3.id().hex())),
+        vec![]
+    );
+}
+
+#[test]
+fn test_evaluate_expression_signed() {
+    let settings = testutils::user_settings();
+    let signer = Signer::new(Some(Box::new(TestSigningBackend)), vec![]);
+    let test_workspace =
+        TestWorkspace::init_with_backend_and_signer(&settings, TestRepoBackend::Local, signer);
+    let repo = &test_workspace.repo;
+
+    let mut tx = repo.start_transaction(&settings);
+    let mut_repo = tx.mut_repo();
+
+    let commit1 = write_random_commit(mut_repo, &settings);
+    let commit2 = create_random_commit(mut_repo, &settings)
+        .set_parents(vec![commit1.id().clone()])
+        .set_sign_behavior(SignBehavior::Own)
+        .write()
+        .unwrap();
+    let commit3 = create_random_commit(mut_repo, &settings)
+        .set_parents(vec![commit2.id().clone()])
+        .write()
+        .unwrap();
+
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "signed()"),
+        vec![commit2.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, &format!("::{} & unsigned()", commit3.id().hex())),
+        vec![
+            commit3.id().clone(),
+            commit1.id().clone(),
+            repo.store().root_commit_id().clone(),
+        ]
+    );
+    // Composes with other predicates
+    assert_eq!(
+        resolve_commit_ids(mut_repo, &format!("{}:: & unsigned()", commit2.id().hex())),
+        vec![commit3.id().clone()]
+    );
 }
 
 #[test]
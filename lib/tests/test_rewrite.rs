@@ -1535,6 +1535,7 @@ fn test_rebase_descendants_update_checkout_abandoned_merge() {
 
 #[test_case(EmptyBehaviour::Keep; "keep all commits")]
 #[test_case(EmptyBehaviour::AbandonNewlyEmpty; "abandon newly empty commits")]
+#[test_case(EmptyBehaviour::AbandonNewlyEmptyUndescribed; "abandon newly empty undescribed commits")]
 #[test_case(EmptyBehaviour::AbandonAllEmpty ; "abandon all empty commits")]
 fn test_empty_commit_option(empty_behavior: EmptyBehaviour) {
     let settings = testutils::user_settings();
@@ -1610,7 +1611,8 @@ fn test_empty_commit_option(empty_behavior: EmptyBehaviour) {
         .unwrap();
 
     let new_head = match empty_behavior {
-        EmptyBehaviour::Keep => {
+        // All the commits have descriptions, so none of them are abandoned.
+        EmptyBehaviour::Keep | EmptyBehaviour::AbandonNewlyEmptyUndescribed => {
             // The commit C isn't empty.
             let new_commit_c =
                 assert_rebased_onto(tx.mut_repo(), &rebase_map, &commit_c, &[commit_bd.id()]);
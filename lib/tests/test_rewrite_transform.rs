@@ -14,7 +14,9 @@
 
 use std::collections::HashMap;
 
+use jj_lib::backend::BackendError;
 use jj_lib::repo::Repo;
+use jj_lib::rewrite::RewriteProgress;
 use maplit::hashset;
 use testutils::{CommitGraphBuilder, TestRepo};
 
@@ -121,3 +123,98 @@ fn test_transform_descendants_sync_linearize_merge() {
 
     assert_eq!(new_commit_c.parent_ids(), vec![commit_b.id().clone()]);
 }
+
+// Rewrite B and C, reporting progress after each commit.
+//
+// C
+// B
+// A
+#[test]
+fn test_transform_descendants_progress() {
+    let settings = testutils::user_settings();
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction(&settings);
+    let mut graph_builder = CommitGraphBuilder::new(&settings, tx.mut_repo());
+    let commit_a = graph_builder.initial_commit();
+    let commit_b = graph_builder.commit_with_parents(&[&commit_a]);
+    let _commit_c = graph_builder.commit_with_parents(&[&commit_b]);
+
+    let mut reported = vec![];
+    tx.mut_repo()
+        .transform_descendants_with_progress(
+            &settings,
+            vec![commit_b.id().clone()],
+            &mut |progress| reported.push(progress),
+            |rewriter| {
+                rewriter.reparent(&settings)?.write()?;
+                Ok(())
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        reported,
+        vec![
+            RewriteProgress {
+                visited: 1,
+                total: 2
+            },
+            RewriteProgress {
+                visited: 2,
+                total: 2
+            },
+        ]
+    );
+}
+
+// An error in the middle of the rewrite leaves the repo at the previous
+// operation.
+//
+// C
+// B
+// A
+#[test]
+fn test_transform_descendants_interrupted() {
+    let settings = testutils::user_settings();
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction(&settings);
+    let mut graph_builder = CommitGraphBuilder::new(&settings, tx.mut_repo());
+    let commit_a = graph_builder.initial_commit();
+    let commit_b = graph_builder.commit_with_parents(&[&commit_a]);
+    let commit_c = graph_builder.commit_with_parents(&[&commit_b]);
+    let repo = tx.commit("test");
+
+    let mut tx = repo.start_transaction(&settings);
+    let mut reported = vec![];
+    let result = tx.mut_repo().transform_descendants_with_progress(
+        &settings,
+        vec![commit_b.id().clone()],
+        &mut |progress| reported.push(progress),
+        |rewriter| {
+            if *rewriter.old_commit() == commit_c {
+                return Err(BackendError::Other("interrupted".into()));
+            }
+            rewriter.reparent(&settings)?.write()?;
+            Ok(())
+        },
+    );
+    assert!(result.is_err());
+    assert_eq!(
+        reported,
+        vec![RewriteProgress {
+            visited: 1,
+            total: 2
+        }]
+    );
+    drop(tx);
+
+    let reloaded_repo = repo.reload_at_head(&settings).unwrap();
+    assert_eq!(reloaded_repo.op_id(), repo.op_id());
+    assert_eq!(
+        *reloaded_repo.view().heads(),
+        hashset! {commit_c.id().clone()}
+    );
+}
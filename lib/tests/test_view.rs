@@ -14,6 +14,7 @@
 
 use std::collections::BTreeMap;
 
+use jj_lib::backend::ChangeId;
 use jj_lib::op_store::{BranchTarget, RefTarget, RemoteRef, RemoteRefState, WorkspaceId};
 use jj_lib::repo::Repo;
 use maplit::{btreemap, hashset};
@@ -418,6 +419,101 @@ fn test_merge_views_git_heads() {
     assert_eq!(repo.view().git_head(), &expected_git_head);
 }
 
+#[test]
+fn test_merge_views_notes() {
+    // Tests merging of notes (by performing concurrent operations).
+    let settings = testutils::user_settings();
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx0 = repo.start_transaction(&settings);
+    let mut_repo = tx0.mut_repo();
+    mut_repo.set_note(ChangeId::from_hex("aaa111"), "unchanged".to_owned());
+    mut_repo.set_note(ChangeId::from_hex("aaa222"), "removed by tx1".to_owned());
+    mut_repo.set_note(ChangeId::from_hex("aaa333"), "changed by tx2".to_owned());
+    mut_repo.set_note(ChangeId::from_hex("aaa444"), "changed by both".to_owned());
+    let repo = tx0.commit("test");
+
+    let mut tx1 = repo.start_transaction(&settings);
+    let mut_repo = tx1.mut_repo();
+    mut_repo.set_note(ChangeId::from_hex("aaa222"), "".to_owned());
+    mut_repo.set_note(ChangeId::from_hex("aaa444"), "tx1".to_owned());
+    mut_repo.set_note(ChangeId::from_hex("aaa555"), "added by tx1".to_owned());
+
+    let mut tx2 = repo.start_transaction(&settings);
+    let mut_repo = tx2.mut_repo();
+    mut_repo.set_note(ChangeId::from_hex("aaa333"), "tx2".to_owned());
+    mut_repo.set_note(ChangeId::from_hex("aaa444"), "tx2".to_owned());
+
+    let repo = commit_transactions(&settings, vec![tx1, tx2]);
+    let view = repo.view();
+    let get_note = |hex| view.get_note(&ChangeId::from_hex(hex));
+    assert_eq!(get_note("aaa111"), Some("unchanged"));
+    assert_eq!(get_note("aaa222"), None);
+    assert_eq!(get_note("aaa333"), Some("tx2"));
+    // One side wins if both sides changed the same note
+    assert!(matches!(get_note("aaa444"), Some("tx1" | "tx2")));
+    assert_eq!(get_note("aaa555"), Some("added by tx1"));
+}
+
+#[test]
+fn test_merge_views_branch_descriptions() {
+    // Tests merging of branch descriptions (by performing concurrent
+    // operations).
+    let settings = testutils::user_settings();
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx0 = repo.start_transaction(&settings);
+    let mut_repo = tx0.mut_repo();
+    let commit = write_random_commit(mut_repo, &settings);
+    let target = RefTarget::normal(commit.id().clone());
+    for name in [
+        "unchanged",
+        "removed",
+        "changed",
+        "both",
+        "added",
+        "deleted",
+    ] {
+        mut_repo.set_local_branch_target(name, target.clone());
+    }
+    mut_repo.set_branch_description("unchanged", "unchanged".to_owned());
+    mut_repo.set_branch_description("removed", "removed by tx1".to_owned());
+    mut_repo.set_branch_description("changed", "changed by tx2".to_owned());
+    mut_repo.set_branch_description("both", "changed by both".to_owned());
+    mut_repo.set_branch_description("deleted", "deleted by tx1".to_owned());
+    let repo = tx0.commit("test");
+
+    let mut tx1 = repo.start_transaction(&settings);
+    let mut_repo = tx1.mut_repo();
+    mut_repo.set_branch_description("removed", "".to_owned());
+    mut_repo.set_branch_description("both", "tx1".to_owned());
+    mut_repo.set_branch_description("added", "added by tx1".to_owned());
+    mut_repo.set_local_branch_target("deleted", RefTarget::absent());
+
+    let mut tx2 = repo.start_transaction(&settings);
+    let mut_repo = tx2.mut_repo();
+    mut_repo.set_branch_description("changed", "tx2".to_owned());
+    mut_repo.set_branch_description("both", "tx2".to_owned());
+    mut_repo.set_branch_description("deleted", "changed by tx2".to_owned());
+
+    let repo = commit_transactions(&settings, vec![tx1, tx2]);
+    let view = repo.view();
+    assert_eq!(view.get_branch_description("unchanged"), Some("unchanged"));
+    assert_eq!(view.get_branch_description("removed"), None);
+    assert_eq!(view.get_branch_description("changed"), Some("tx2"));
+    // One side wins if both sides changed the same description
+    assert!(matches!(
+        view.get_branch_description("both"),
+        Some("tx1" | "tx2")
+    ));
+    assert_eq!(view.get_branch_description("added"), Some("added by tx1"));
+    // The description doesn't outlive the branch
+    assert!(view.get_local_branch("deleted").is_absent());
+    assert_eq!(view.get_branch_description("deleted"), None);
+}
+
 #[test]
 fn test_merge_views_divergent() {
     // We start with just commit A. Operation 1 rewrites it as A2. Operation 2
@@ -20,6 +20,7 @@ use jj_lib::repo::Repo;
 use jj_lib::workspace::{
     default_working_copy_factories, default_working_copy_factory, Workspace, WorkspaceLoadError,
 };
+use jj_lib::workspace_store::SimpleWorkspaceStore;
 use testutils::{TestRepo, TestWorkspace};
 
 #[test]
@@ -85,6 +86,24 @@ fn test_init_additional_workspace() {
         workspace.repo_path().canonicalize().unwrap()
     );
     assert_eq!(same_workspace.workspace_root(), ws2.workspace_root());
+
+    // Both workspaces are recorded in the repo
+    let workspace_store = SimpleWorkspaceStore::load(workspace.repo_path());
+    assert_eq!(
+        workspace_store
+            .get_workspace_path(&WorkspaceId::default())
+            .unwrap(),
+        Some(workspace.workspace_root().clone())
+    );
+    assert_eq!(
+        workspace_store.get_workspace_path(&ws2_id).unwrap(),
+        Some(ws2.workspace_root().clone())
+    );
+    let unknown_id = WorkspaceId::new("unknown".to_string());
+    assert_eq!(
+        workspace_store.get_workspace_path(&unknown_id).unwrap(),
+        None
+    );
 }
 
 /// Test cross-thread access to a workspace, which requires it to be Send